@@ -7,12 +7,16 @@ use criterion::Criterion;
 
 use rand::rngs::OsRng;
 
-use ice_frost::dkg::{Coefficients, DistributedKeyGeneration, EncryptedSecretShare, Participant};
-use ice_frost::keys::DiffieHellmanPrivateKey;
+use ice_frost::dkg::{
+    Coefficients, DealerRole, DistributedKeyGeneration, EncryptedSecretShare, Participant,
+    SignerRole,
+};
+use ice_frost::keys::{DiffieHellmanPrivateKey, IndividualVerifyingKey};
 use ice_frost::parameters::ThresholdParameters;
 use ice_frost::testing::Secp256k1Sha256;
 
-type ParticipantDKG = Participant<Secp256k1Sha256>;
+type ParticipantDKG = Participant<Secp256k1Sha256, DealerRole>;
+type SignerDKG = Participant<Secp256k1Sha256, SignerRole>;
 type Dkg<T> = DistributedKeyGeneration<T, Secp256k1Sha256>;
 type DHSkey = DiffieHellmanPrivateKey<Secp256k1Sha256>;
 type Coeff = Coefficients<Secp256k1Sha256>;
@@ -21,7 +25,8 @@ const NUMBER_OF_PARTICIPANTS: u32 = 5;
 const THRESHOLD_OF_PARTICIPANTS: u32 = 3;
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let params = ThresholdParameters::new(NUMBER_OF_PARTICIPANTS, THRESHOLD_OF_PARTICIPANTS);
+    let params =
+        ThresholdParameters::new(NUMBER_OF_PARTICIPANTS, THRESHOLD_OF_PARTICIPANTS).unwrap();
     let rng = OsRng;
 
     c.bench_function("Participant creation (dealer)", move |b| {
@@ -29,7 +34,7 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 
     c.bench_function("Participant creation (signer)", move |b| {
-        b.iter(|| ParticipantDKG::new_signer(&params, 1, rng))
+        b.iter(|| SignerDKG::new_signer(&params, 1, rng))
     });
 
     let mut participants = Vec::<ParticipantDKG>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
@@ -140,14 +145,27 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| p1_state.clone().finish());
     });
 
+    let verifying_keys: Vec<_> = participants_states_2
+        .iter()
+        .map(|state| state.clone().finish().unwrap().1.to_public())
+        .collect();
+    let commitments: Vec<_> = participants
+        .iter()
+        .map(|p| p.commitments().clone())
+        .collect();
+
+    c.bench_function("Verify all verifying keys", move |b| {
+        b.iter(|| IndividualVerifyingKey::verify_all(&verifying_keys, &commitments));
+    });
+
     let (_group_key, p1_sk) = participants_states_2[0].clone().finish().unwrap();
 
-    let mut signers = Vec::<ParticipantDKG>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
-    let (s1, s1_dh_sk) = ParticipantDKG::new_signer(&params, 1, rng).unwrap();
+    let mut signers = Vec::<SignerDKG>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
+    let (s1, s1_dh_sk) = SignerDKG::new_signer(&params, 1, rng).unwrap();
     signers.push(s1.clone());
 
     for i in 2..NUMBER_OF_PARTICIPANTS + 1 {
-        let (s, _) = ParticipantDKG::new_signer(&params, i, rng).unwrap();
+        let (s, _) = SignerDKG::new_signer(&params, i, rng).unwrap();
         signers.push(s);
     }
 
@@ -0,0 +1,40 @@
+//! Benchmarks for the share ciphers of ICE-FROST Distributed Key Generation sessions.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use ice_frost::testing::Secp256k1Sha256;
+use ice_frost::{Aes256Gcm, ShareCipher, XChaCha20Poly1305};
+
+fn share_cipher_benchmark<S: ShareCipher>(c: &mut Criterion, name: &str) {
+    let mut key = vec![0u8; S::KEY_LENGTH];
+    OsRng.fill_bytes(&mut key);
+    let mut nonce = S::Nonce::default();
+    OsRng.fill_bytes(nonce.as_mut());
+    // A secret share is encrypted as a single serialized scalar.
+    let mut plaintext = [0u8; 32];
+    OsRng.fill_bytes(&mut plaintext);
+
+    let ciphertext = S::encrypt::<Secp256k1Sha256>(&key, &nonce, &plaintext).unwrap();
+
+    c.bench_function(&format!("{} share encryption", name), |b| {
+        b.iter(|| S::encrypt::<Secp256k1Sha256>(&key, &nonce, &plaintext));
+    });
+
+    c.bench_function(&format!("{} share decryption", name), |b| {
+        b.iter(|| S::decrypt::<Secp256k1Sha256>(&key, &nonce, &ciphertext));
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    share_cipher_benchmark::<Aes256Gcm>(c, "AES-256-GCM");
+    share_cipher_benchmark::<XChaCha20Poly1305>(c, "XChaCha20-Poly1305");
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -5,19 +5,23 @@ extern crate criterion;
 
 use criterion::Criterion;
 
+use ark_ff::UniformRand;
+use ark_secp256k1::Projective;
 use rand::rngs::OsRng;
 
-use ice_frost::dkg::{Coefficients, DistributedKeyGeneration, EncryptedSecretShare, Participant};
+use ice_frost::dkg::{
+    Coefficients, DealerRole, DistributedKeyGeneration, EncryptedSecretShare, Participant,
+};
 use ice_frost::keys::{DiffieHellmanPrivateKey, IndividualSigningKey};
 use ice_frost::parameters::ThresholdParameters;
 use ice_frost::sign::{
-    generate_commitment_share_lists, PublicCommitmentShareList, SecretCommitmentShareList,
-    SignatureAggregator,
+    compute_binding_factors, compute_group_nonce, generate_commitment_share_lists,
+    PublicCommitmentShareList, SecretCommitmentShareList, SignatureAggregator, Signer,
 };
 use ice_frost::testing::Secp256k1Sha256;
 use ice_frost::CipherSuite;
 
-type ParticipantDKG = Participant<Secp256k1Sha256>;
+type ParticipantDKG = Participant<Secp256k1Sha256, DealerRole>;
 type Coeff = Coefficients<Secp256k1Sha256>;
 type Dkg<T> = DistributedKeyGeneration<T, Secp256k1Sha256>;
 type DHSkey = DiffieHellmanPrivateKey<Secp256k1Sha256>;
@@ -30,7 +34,8 @@ const NUMBER_OF_PARTICIPANTS: u32 = 5;
 const THRESHOLD_OF_PARTICIPANTS: u32 = 3;
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let params = ThresholdParameters::new(NUMBER_OF_PARTICIPANTS, THRESHOLD_OF_PARTICIPANTS);
+    let params =
+        ThresholdParameters::new(NUMBER_OF_PARTICIPANTS, THRESHOLD_OF_PARTICIPANTS).unwrap();
     let rng = OsRng;
 
     let mut participants = Vec::<ParticipantDKG>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
@@ -126,7 +131,8 @@ fn criterion_benchmark(c: &mut Criterion) {
     let mut participants_secret_comshares =
         Vec::<SecretCommShareList>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
     let (p1_public_comshares, p1_secret_comshares) =
-        generate_commitment_share_lists(&mut OsRng, &participants_secret_keys[0].clone(), 1);
+        generate_commitment_share_lists(&mut OsRng, &participants_secret_keys[0].clone(), 1)
+            .unwrap();
     participants_public_comshares.push(p1_public_comshares);
     participants_secret_comshares.push(p1_secret_comshares.clone());
 
@@ -135,19 +141,22 @@ fn criterion_benchmark(c: &mut Criterion) {
             &mut OsRng,
             &participants_secret_keys[(i - 1) as usize].clone(),
             1,
-        );
+        )
+        .unwrap();
         participants_public_comshares.push(pi_public_comshares);
         participants_secret_comshares.push(pi_secret_comshares);
     }
 
-    let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+    let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
     for i in 1..THRESHOLD_OF_PARTICIPANTS + 1 {
-        aggregator.include_signer(
-            i,
-            participants_public_comshares[(i - 1) as usize].commitments[0],
-            (&participants_secret_keys[(i - 1) as usize]).into(),
-        );
+        aggregator
+            .include_signer(
+                i,
+                participants_public_comshares[(i - 1) as usize].commitments[0],
+                (&participants_secret_keys[(i - 1) as usize]).into(),
+            )
+            .unwrap();
     }
 
     let signers = aggregator.get_signers().clone();
@@ -166,7 +175,9 @@ fn criterion_benchmark(c: &mut Criterion) {
                 &signers,
             )
             .unwrap();
-        aggregator.include_partial_signature(pi_partial_signature);
+        aggregator
+            .include_partial_signature(pi_partial_signature)
+            .unwrap();
     }
 
     c.bench_function("Partial signature creation", move |b| {
@@ -194,8 +205,38 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
+/// Benchmark the computation of the group commitment of signing sessions with large
+/// numbers of signers, which dominates the aggregation of their signatures.
+///
+/// Its multi-scalar multiplication runs on multiple threads with the `parallel` feature.
+fn group_commitment_benchmark(c: &mut Criterion) {
+    let message_hash =
+        Secp256k1Sha256::h4(b"This is a test of the tsunami alert system. This is only a test.")
+            .unwrap();
+
+    for number_of_signers in [64u32, 256, 1024] {
+        let signers: Vec<Signer<Secp256k1Sha256>> = (1..=number_of_signers)
+            .map(|participant_index| Signer {
+                participant_index,
+                published_commitment_share: (
+                    Projective::rand(&mut OsRng),
+                    Projective::rand(&mut OsRng),
+                ),
+            })
+            .collect();
+        let binding_factors = compute_binding_factors(&message_hash, &signers).unwrap();
+
+        c.bench_function(
+            &format!("Group commitment with {} signers", number_of_signers),
+            move |b| {
+                b.iter(|| compute_group_nonce(&signers, &binding_factors));
+            },
+        );
+    }
+}
+
 criterion_group!(
     name = benches;
     config = Criterion::default().sample_size(10);
-    targets = criterion_benchmark);
+    targets = criterion_benchmark, group_commitment_benchmark);
 criterion_main!(benches);
@@ -0,0 +1,443 @@
+//! The backup module for splitting a [`DiffieHellmanPrivateKey`] into printable
+//! backup shares, any threshold of which allows to restore it.
+//!
+//! Restoring the very same key, rather than generating a new one, allows a participant
+//! to recover after the loss of its hardware while still being able to decrypt the
+//! secret shares already sent to it.
+//!
+//! The key is split with Shamir's secret sharing, and each [`KeyBackupShare`] is
+//! encoded as a sequence of words from the BIP-39 English word list, each word
+//! carrying 11 bits of the share. The share also carries a fingerprint of the backed
+//! up key, so that shares from distinct backups cannot be mixed, and a checksum, so
+//! that mistyped words are detected.
+//!
+//! [`DiffieHellmanPrivateKey`]: crate::keys::DiffieHellmanPrivateKey
+//! [`KeyBackupShare`]: crate::backup::KeyBackupShare
+
+use ark_ff::{UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{DhKeyProvider, DiffieHellmanPrivateKey};
+use crate::utils::{
+    calculate_lagrange_coefficients, ct_eq_encoded, hash_to_array, vec, Scalar, String, ToString,
+    Vec,
+};
+use crate::{Error, FrostResult};
+
+/// The number of bits encoded by each word of a mnemonic.
+const BITS_PER_WORD: usize = 11;
+
+/// The length in bytes of the key fingerprint and of the checksum of a [`KeyBackupShare`].
+const TAG_LENGTH: usize = 4;
+
+/// A share of a [`DiffieHellmanPrivateKey`] backup.
+///
+/// Its evaluation of the sharing polynomial is redacted from the
+/// [`Debug`](core::fmt::Debug) output of the share.
+#[derive(Clone, Zeroize)]
+pub struct KeyBackupShare<C: CipherSuite> {
+    /// The number of shares needed to restore the key.
+    pub(crate) threshold: u8,
+    /// The index of this share, starting at 1.
+    pub(crate) index: u8,
+    /// The fingerprint of the public key matching the backed up key.
+    pub(crate) fingerprint: [u8; TAG_LENGTH],
+    /// The evaluation of the sharing polynomial at this share's index.
+    pub(crate) share: Scalar<C>,
+}
+
+impl<C: CipherSuite> core::fmt::Debug for KeyBackupShare<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyBackupShare")
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("fingerprint", &self.fingerprint)
+            .field("share", &format_args!("<redacted>"))
+            .finish()
+    }
+}
+
+impl<C: CipherSuite> Drop for KeyBackupShare<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> ConstantTimeEq for KeyBackupShare<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.threshold.ct_eq(&other.threshold)
+            & self.index.ct_eq(&other.index)
+            & self.fingerprint.ct_eq(&other.fingerprint)
+            & ct_eq_encoded(&self.share, &other.share)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for KeyBackupShare<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for KeyBackupShare<C> {}
+
+impl<C: CipherSuite> KeyBackupShare<C> {
+    /// The number of shares needed to restore the key.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// The index of this share, starting at 1.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Encode this [`KeyBackupShare`] as a space-separated sequence of words
+    /// from the BIP-39 English word list.
+    pub fn to_mnemonic(&self) -> FrostResult<C, String> {
+        let mut bytes = Zeroizing::new(self.payload()?);
+        let checksum = Self::checksum(&bytes)?;
+        bytes.extend_from_slice(&checksum);
+
+        let word_list = bip39::Language::English.word_list();
+        let word_count = word_count(bytes.len());
+        let words: Vec<&str> = (0..word_count)
+            .map(|i| word_list[read_bits(&bytes, i * BITS_PER_WORD) as usize])
+            .collect();
+
+        Ok(words.join(" "))
+    }
+
+    /// Decode a [`KeyBackupShare`] from a mnemonic, as produced by [`KeyBackupShare::to_mnemonic`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the decoded share, otherwise an
+    /// [`Error::DeserializationError`] if the mnemonic contains unknown words, does
+    /// not have the expected length, or fails its checksum.
+    pub fn from_mnemonic(mnemonic: &str) -> FrostResult<C, Self> {
+        let invalid_data =
+            || Error::DeserializationError(ark_serialize::SerializationError::InvalidData.into());
+
+        let length = 2 + TAG_LENGTH + Scalar::<C>::zero().compressed_size() + TAG_LENGTH;
+        let words: Vec<&str> = mnemonic.split_whitespace().collect();
+        if words.len() != word_count(length) {
+            return Err(invalid_data());
+        }
+
+        // The decoded bytes hold the share, and are zeroized on every return path.
+        let mut bytes = Zeroizing::new(vec![0u8; length]);
+        for (i, word) in words.iter().enumerate() {
+            let value = bip39::Language::English
+                .find_word(&word.to_lowercase())
+                .ok_or_else(invalid_data)?;
+            if !write_bits(&mut bytes, i * BITS_PER_WORD, value) {
+                return Err(invalid_data());
+            }
+        }
+
+        let (payload, checksum) = bytes.split_at(length - TAG_LENGTH);
+        if Self::checksum(payload)?[..] != checksum[..] {
+            return Err(invalid_data());
+        }
+
+        let mut fingerprint = [0u8; TAG_LENGTH];
+        fingerprint.copy_from_slice(&payload[2..2 + TAG_LENGTH]);
+        let share = Scalar::<C>::deserialize_compressed(&payload[2 + TAG_LENGTH..])
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+        let backup_share = Self {
+            threshold: payload[0],
+            index: payload[1],
+            fingerprint,
+            share,
+        };
+
+        if backup_share.index == 0 || backup_share.threshold == 0 {
+            return Err(invalid_data());
+        }
+
+        Ok(backup_share)
+    }
+
+    /// The threshold, index, fingerprint and share, as encoded in a mnemonic.
+    fn payload(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = vec![self.threshold, self.index];
+        bytes.extend_from_slice(&self.fingerprint);
+        self.share
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    fn checksum(payload: &[u8]) -> FrostResult<C, [u8; TAG_LENGTH]> {
+        let hash = hash_to_array::<C>((C::context_string() + "key backup").as_bytes(), payload)?;
+
+        let mut checksum = [0u8; TAG_LENGTH];
+        checksum.copy_from_slice(&hash.as_ref()[..TAG_LENGTH]);
+        Ok(checksum)
+    }
+}
+
+impl<C: CipherSuite> DiffieHellmanPrivateKey<C> {
+    /// Split this [`DiffieHellmanPrivateKey`] into `m` [`KeyBackupShare`]s, any `k`
+    /// of which allow to restore it with [`DiffieHellmanPrivateKey::restore`].
+    pub fn backup(
+        &self,
+        m: u8,
+        k: u8,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Vec<KeyBackupShare<C>>> {
+        if k == 0 || k > m {
+            return Err(Error::Custom(
+                "The backup threshold must be between 1 and the number of shares".to_string(),
+            ));
+        }
+
+        let fingerprint = key_fingerprint(self)?;
+        let mut coefficients: Vec<Scalar<C>> =
+            (1..k).map(|_| Scalar::<C>::rand(&mut rng)).collect();
+
+        let shares = (1..=m)
+            .map(|index| {
+                let x = Scalar::<C>::from(index as u64);
+                let share = coefficients
+                    .iter()
+                    .rev()
+                    .fold(Scalar::<C>::zero(), |acc, coefficient| {
+                        (acc + coefficient) * x
+                    })
+                    + self.0;
+
+                KeyBackupShare {
+                    threshold: k,
+                    index,
+                    fingerprint,
+                    share,
+                }
+            })
+            .collect();
+        coefficients.zeroize();
+
+        Ok(shares)
+    }
+
+    /// Restore a [`DiffieHellmanPrivateKey`] from at least a threshold of its
+    /// [`KeyBackupShare`]s.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the restored key, otherwise an
+    /// [`Error::Custom`] if the shares are not enough, come from distinct backups,
+    /// or do not restore the backed up key.
+    pub fn restore(shares: &[KeyBackupShare<C>]) -> FrostResult<C, Self> {
+        let first = shares
+            .first()
+            .ok_or_else(|| Error::Custom("No backup share was provided".to_string()))?;
+        if shares
+            .iter()
+            .any(|s| s.threshold != first.threshold || s.fingerprint != first.fingerprint)
+        {
+            return Err(Error::Custom(
+                "The backup shares come from distinct backups".to_string(),
+            ));
+        }
+
+        let mut indices: Vec<u32> = shares.iter().map(|s| s.index as u32).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.len() != shares.len() {
+            return Err(Error::Custom("Duplicate backup shares".to_string()));
+        }
+        if indices.len() < first.threshold as usize {
+            return Err(Error::Custom("Not enough backup shares".to_string()));
+        }
+
+        let indices = &indices[..first.threshold as usize];
+        let mut secret = Scalar::<C>::zero();
+        for share in shares
+            .iter()
+            .filter(|s| indices.contains(&(s.index as u32)))
+        {
+            secret +=
+                calculate_lagrange_coefficients::<C>(share.index as u32, indices)? * share.share;
+        }
+
+        let key = Self(secret);
+        if key_fingerprint(&key)? != first.fingerprint {
+            return Err(Error::Custom(
+                "The backup shares do not restore the backed up key".to_string(),
+            ));
+        }
+
+        Ok(key)
+    }
+}
+
+/// The fingerprint of the [`DiffieHellmanPublicKey`](crate::keys::DiffieHellmanPublicKey)
+/// matching a `key`.
+fn key_fingerprint<C: CipherSuite>(
+    key: &DiffieHellmanPrivateKey<C>,
+) -> FrostResult<C, [u8; TAG_LENGTH]> {
+    let public_key = key.dh_public_key()?.to_bytes()?;
+    let hash = hash_to_array::<C>(
+        (C::context_string() + "key backup fingerprint").as_bytes(),
+        &public_key,
+    )?;
+
+    let mut fingerprint = [0u8; TAG_LENGTH];
+    fingerprint.copy_from_slice(&hash.as_ref()[..TAG_LENGTH]);
+    Ok(fingerprint)
+}
+
+/// The number of words needed to encode `length` bytes.
+fn word_count(length: usize) -> usize {
+    let bits = length * 8;
+    match bits % BITS_PER_WORD {
+        0 => bits / BITS_PER_WORD,
+        _ => bits / BITS_PER_WORD + 1,
+    }
+}
+
+/// Read the 11 bits of `bytes` starting at bit `offset`, padding with zeroes.
+fn read_bits(bytes: &[u8], offset: usize) -> u16 {
+    (0..BITS_PER_WORD).fold(0u16, |acc, i| {
+        let bit = bytes
+            .get((offset + i) / 8)
+            .map_or(0, |byte| (byte >> (7 - (offset + i) % 8)) & 1);
+        (acc << 1) | bit as u16
+    })
+}
+
+/// Write the 11 bits of `value` to `bytes` starting at bit `offset`.
+///
+/// Returns whether the bits past the end of `bytes` are all zero.
+fn write_bits(bytes: &mut [u8], offset: usize, value: u16) -> bool {
+    let mut padding_is_zero = true;
+    for i in 0..BITS_PER_WORD {
+        let bit = ((value >> (BITS_PER_WORD - 1 - i)) & 1) as u8;
+        match bytes.get_mut((offset + i) / 8) {
+            Some(byte) => *byte |= bit << (7 - (offset + i) % 8),
+            None => padding_is_zero &= bit == 0,
+        }
+    }
+    padding_is_zero
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn backup_and_restore() {
+        let key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Scalar::<Secp256k1Sha256>::rand(&mut OsRng));
+        let shares = key.backup(5, 3, OsRng).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        let mnemonics: Vec<String> = shares.iter().map(|s| s.to_mnemonic().unwrap()).collect();
+        assert_eq!(mnemonics[0].split(' ').count(), 31);
+        let decoded: Vec<KeyBackupShare<Secp256k1Sha256>> = mnemonics
+            .iter()
+            .map(|m| KeyBackupShare::from_mnemonic(m).unwrap())
+            .collect();
+        assert_eq!(decoded, shares);
+
+        let restored = DiffieHellmanPrivateKey::restore(&decoded[1..4]).unwrap();
+        assert_eq!(restored, key);
+        let restored = DiffieHellmanPrivateKey::restore(&[
+            decoded[4].clone(),
+            decoded[0].clone(),
+            decoded[2].clone(),
+            decoded[3].clone(),
+        ])
+        .unwrap();
+        assert_eq!(restored, key);
+
+        assert!(DiffieHellmanPrivateKey::restore(&decoded[..2]).is_err());
+        assert!(DiffieHellmanPrivateKey::restore(&[
+            decoded[0].clone(),
+            decoded[0].clone(),
+            decoded[1].clone()
+        ])
+        .is_err());
+
+        // Shares from another backup of the same key cannot be mixed in.
+        let other_shares = key.backup(5, 3, OsRng).unwrap();
+        let mut mixed = decoded[..2].to_vec();
+        mixed.push(other_shares[2].clone());
+        assert!(DiffieHellmanPrivateKey::restore(&mixed).is_err());
+
+        // A mistyped word is detected.
+        let mut words: Vec<&str> = mnemonics[0].split(' ').collect();
+        words[7] = if words[7] == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        assert!(KeyBackupShare::<Secp256k1Sha256>::from_mnemonic(&words.join(" ")).is_err());
+        assert!(KeyBackupShare::<Secp256k1Sha256>::from_mnemonic("abandon ability").is_err());
+    }
+
+    #[test]
+    fn non_canonical_share_is_rejected() {
+        let key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Scalar::<Secp256k1Sha256>::rand(&mut OsRng));
+        let share = &key.backup(3, 2, OsRng).unwrap()[0];
+
+        // A share exceeding the scalar field modulus, under a valid checksum.
+        let mut bytes = share.payload().unwrap();
+        for byte in bytes[2 + TAG_LENGTH..].iter_mut() {
+            *byte = 0xff;
+        }
+        bytes.extend_from_slice(&KeyBackupShare::<Secp256k1Sha256>::checksum(&bytes).unwrap());
+
+        let word_list = bip39::Language::English.word_list();
+        let mnemonic: Vec<&str> = (0..word_count(bytes.len()))
+            .map(|i| word_list[read_bits(&bytes, i * BITS_PER_WORD) as usize])
+            .collect();
+
+        assert!(matches!(
+            KeyBackupShare::<Secp256k1Sha256>::from_mnemonic(&mnemonic.join(" ")),
+            Err(Error::DeserializationError(_))
+        ));
+    }
+
+    #[test]
+    fn debug_redacts_share() {
+        let key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Scalar::<Secp256k1Sha256>::rand(&mut OsRng));
+        let share = &key.backup(3, 2, OsRng).unwrap()[0];
+
+        let debug = format!("{:?}", share);
+        assert!(debug.contains("threshold: 2"));
+        assert!(debug.contains("index: 1"));
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains(&share.share.to_string()));
+    }
+
+    #[test]
+    fn constant_time_equality() {
+        let key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Scalar::<Secp256k1Sha256>::rand(&mut OsRng));
+        let shares = key.backup(3, 2, OsRng).unwrap();
+
+        assert!(bool::from(shares[0].ct_eq(&shares[0].clone())));
+        assert!(!bool::from(shares[0].ct_eq(&shares[1])));
+
+        // Shares differing only by their secret evaluation are distinct.
+        let mut other_share = shares[0].clone();
+        other_share.share += Scalar::<Secp256k1Sha256>::from(1u64);
+        assert!(!bool::from(shares[0].ct_eq(&other_share)));
+        assert!(shares[0] != other_share);
+    }
+}
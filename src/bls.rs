@@ -0,0 +1,294 @@
+//! The bls module, providing a threshold BLS signing mode over the keys of an
+//! ICE-FROST Distributed Key Generation session.
+//!
+//! When the group of a [`CipherSuite`] is the first source group \\( \mathbb{G}\_1 \\)
+//! of a pairing-friendly curve, the [`IndividualSigningKey`]s produced by the DKG are
+//! Shamir shares of the group signing key, which can directly produce BLS signatures
+//! in \\( \mathbb{G}\_2 \\). Each signer computes its [`BlsPartialSignature`]
+//! \\( \sigma\_i = s\_i \cdot H(m) \\) alone, and any \\( t \\) valid partial signatures
+//! are interpolated into the [`BlsSignature`] \\( \sigma = s \cdot H(m) \\), without
+//! any nonce or commitment round.
+//!
+//! BLS signatures are deterministic, and verified with a pairing check
+//! \\( e(G, \sigma) = e(Y, H(m)) \\), hence they are not compatible with the Schnorr
+//! signatures of the [`sign`](crate::sign) module, although produced by the same keys.
+//!
+//! [`IndividualSigningKey`]: crate::keys::IndividualSigningKey
+//! [`BlsPartialSignature`]: crate::bls::BlsPartialSignature
+//! [`BlsSignature`]: crate::bls::BlsSignature
+
+use ark_ec::pairing::Pairing;
+use ark_ec::Group;
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{calculate_lagrange_coefficients, Scalar, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+/// The second source group of the pairing of a [`BlsCipherSuite`], holding the signatures.
+pub type G2<C> = <<C as BlsCipherSuite>::Engine as Pairing>::G2;
+
+/// A [`CipherSuite`] whose group is the first source group of a pairing, enabling the
+/// threshold BLS signing mode.
+pub trait BlsCipherSuite: CipherSuite {
+    /// The pairing whose first source group is the group of this [`CipherSuite`].
+    type Engine: Pairing<G1 = Self::G, ScalarField = Scalar<Self>>;
+
+    /// Hash the given `message` to the second source group of the pairing.
+    ///
+    /// Implementations should follow the hash-to-curve specification of RFC 9380,
+    /// with a domain separation tag derived from [`CipherSuite::context_string`].
+    fn hash_to_g2(message: &[u8]) -> FrostResult<Self, G2<Self>>;
+}
+
+/// A partial BLS signature, made by a participant alone over a message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BlsPartialSignature<C: BlsCipherSuite> {
+    pub(crate) index: u32,
+    pub(crate) sigma: G2<C>,
+}
+
+impl<C: BlsCipherSuite> BlsPartialSignature<C> {
+    /// The index of the participant who made this partial signature.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Verify this [`BlsPartialSignature`] on the given `message` against the
+    /// [`IndividualVerifyingKey`] of its signer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the partial signature is
+    /// valid, otherwise an [`Error::InvalidSignature`].
+    pub fn verify(
+        &self,
+        verifying_key: &IndividualVerifyingKey<C>,
+        message: &[u8],
+    ) -> FrostResult<C, ()> {
+        if verifying_key.index != self.index {
+            return Err(Error::InvalidSignature);
+        }
+
+        verify_pairing::<C>(&verifying_key.share, &self.sigma, message)
+    }
+
+    /// Serialize this [`BlsPartialSignature`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`BlsPartialSignature`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: BlsCipherSuite> ValidatePoints<C> for BlsPartialSignature<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_g2::<C>(&self.sigma)
+    }
+}
+
+/// A threshold BLS signature of an ICE-FROST group.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BlsSignature<C: BlsCipherSuite> {
+    pub(crate) sigma: G2<C>,
+}
+
+impl<C: BlsCipherSuite> BlsSignature<C> {
+    /// Interpolate the given `partial_signatures` on the `message` into the
+    /// [`BlsSignature`] of the group with the given `parameters`.
+    ///
+    /// Each partial signature is verified against the [`IndividualVerifyingKey`] of its
+    /// signer among the given `verifying_keys`, and the first \\( t \\) valid ones are
+    /// interpolated, any \\( t \\) of them yielding the same signature.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`BlsSignature`], otherwise an
+    /// [`Error::MisbehavingParticipants`] listing the signers whose partial signature
+    /// is invalid or who have no verifying key, or an [`Error::MissingShares`] if fewer
+    /// than \\( t \\) distinct signers contributed.
+    pub fn aggregate(
+        parameters: &ThresholdParameters<C>,
+        message: &[u8],
+        partial_signatures: &[BlsPartialSignature<C>],
+        verifying_keys: &[IndividualVerifyingKey<C>],
+    ) -> FrostResult<C, Self> {
+        let mut partial_signatures = partial_signatures.to_vec();
+        partial_signatures.sort_by_key(|partial| partial.index);
+        partial_signatures.dedup_by_key(|partial| partial.index);
+
+        let misbehaving_participants: Vec<u32> = partial_signatures
+            .iter()
+            .filter(
+                |partial| match verifying_keys.iter().find(|key| key.index == partial.index) {
+                    Some(verifying_key) => partial.verify(verifying_key, message).is_err(),
+                    None => true,
+                },
+            )
+            .map(|partial| partial.index)
+            .collect();
+        if !misbehaving_participants.is_empty() {
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        if partial_signatures.len() < parameters.t as usize {
+            return Err(Error::MissingShares);
+        }
+        partial_signatures.truncate(parameters.t as usize);
+
+        let indices: Vec<u32> = partial_signatures
+            .iter()
+            .map(|partial| partial.index)
+            .collect();
+        let mut sigma = G2::<C>::zero();
+        for partial in partial_signatures.iter() {
+            sigma += partial.sigma * calculate_lagrange_coefficients::<C>(partial.index, &indices)?;
+        }
+
+        Ok(Self { sigma })
+    }
+
+    /// Verify this [`BlsSignature`] on the given `message` against the
+    /// [`GroupVerifyingKey`] of the group.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signature is valid,
+    /// otherwise an [`Error::InvalidSignature`].
+    pub fn verify(&self, group_key: &GroupVerifyingKey<C>, message: &[u8]) -> FrostResult<C, ()> {
+        verify_pairing::<C>(&group_key.key, &self.sigma, message)
+    }
+
+    /// Serialize this [`BlsSignature`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`BlsSignature`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: BlsCipherSuite> ValidatePoints<C> for BlsSignature<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_g2::<C>(&self.sigma)
+    }
+}
+
+impl<C: BlsCipherSuite> IndividualSigningKey<C> {
+    /// Compute the [`BlsPartialSignature`] of this participant on the given `message`.
+    ///
+    /// Unlike [`IndividualSigningKey::sign`], no commitment share is needed, as BLS
+    /// signatures are deterministic.
+    pub fn bls_sign(&self, message: &[u8]) -> FrostResult<C, BlsPartialSignature<C>> {
+        Ok(BlsPartialSignature {
+            index: self.index,
+            sigma: C::hash_to_g2(message)? * self.key,
+        })
+    }
+}
+
+/// Check the pairing equation \\( e(G, \sigma) = e(Y, H(m)) \\).
+fn verify_pairing<C: BlsCipherSuite>(
+    public_key: &C::G,
+    sigma: &G2<C>,
+    message: &[u8],
+) -> FrostResult<C, ()> {
+    let hashed_message = C::hash_to_g2(message)?;
+
+    let check =
+        C::Engine::multi_pairing([-C::G::generator(), *public_key], [*sigma, hashed_message]);
+    match !sigma.is_zero() && check.is_zero() {
+        true => Ok(()),
+        false => Err(Error::InvalidSignature),
+    }
+}
+
+/// Validate a point of the second source group, which is always checked to lie in the
+/// prime-order subgroup, regardless of the [`PointValidationPolicy`](crate::PointValidationPolicy)
+/// of the [`CipherSuite`], as it has a non-trivial cofactor on pairing-friendly curves.
+fn validate_g2<C: BlsCipherSuite>(point: &G2<C>) -> FrostResult<C, ()> {
+    if point.is_zero() {
+        return Err(Error::IdentityPoint);
+    }
+
+    point.check().map_err(|_| Error::InvalidPoint)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::testing::conformance::generate_keys;
+    use crate::testing::Bls12381G1Sha256;
+
+    #[test]
+    fn threshold_bls_3_out_of_5() {
+        let params = ThresholdParameters::new(5, 3).unwrap();
+        let (group_key, signing_keys) = generate_keys::<Bls12381G1Sha256>(&params).unwrap();
+        let verifying_keys: Vec<_> = signing_keys.iter().map(|key| key.to_public()).collect();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let partial_signatures: Vec<_> = signing_keys
+            .iter()
+            .map(|key| key.bls_sign(message).unwrap())
+            .collect();
+        for (partial, verifying_key) in partial_signatures.iter().zip(verifying_keys.iter()) {
+            assert!(partial.verify(verifying_key, message).is_ok());
+            assert_eq!(
+                BlsPartialSignature::from_bytes(&partial.to_bytes().unwrap()).unwrap(),
+                *partial
+            );
+        }
+
+        // Any subset of t signers yields the same signature.
+        let signature =
+            BlsSignature::aggregate(&params, message, &partial_signatures[..3], &verifying_keys)
+                .unwrap();
+        assert!(signature.verify(&group_key, message).is_ok());
+        assert_eq!(
+            BlsSignature::aggregate(&params, message, &partial_signatures[2..], &verifying_keys)
+                .unwrap(),
+            signature
+        );
+        assert_eq!(
+            BlsSignature::from_bytes(&signature.to_bytes().unwrap()).unwrap(),
+            signature
+        );
+        assert_eq!(
+            signature.verify(&group_key, b"another message"),
+            Err(Error::InvalidSignature)
+        );
+
+        assert_eq!(
+            BlsSignature::aggregate(&params, message, &partial_signatures[..2], &verifying_keys),
+            Err(Error::MissingShares)
+        );
+
+        // Invalid partial signatures are attributed to their signer.
+        let mut tampered = partial_signatures.clone();
+        tampered[1] = signing_keys[1].bls_sign(b"another message").unwrap();
+        assert_eq!(
+            BlsSignature::aggregate(&params, message, &tampered, &verifying_keys),
+            Err(Error::MisbehavingParticipants(vec![2]))
+        );
+    }
+}
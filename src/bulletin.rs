@@ -0,0 +1,565 @@
+//! The bulletin module, running ICE-FROST distributed key generation and signing
+//! sessions entirely through a publicly readable bulletin board.
+//!
+//! A [`BulletinBoard`] stores content under its hash, and lists the hashes of the
+//! content posted under a topic, as can be provided by IPFS, object storage or smart
+//! contract storage. Participants never exchange point-to-point messages: each
+//! [`ProtocolFrame`] is posted under the topic of its session and round through a
+//! [`BulletinChannel`], the secret shares of the distributed key generation being
+//! encrypted for their recipient.
+//!
+//! Polling is deterministic: whatever the order in which a board lists the posts of a
+//! topic, the frames of a round are sorted by sender, invalid frames are skipped, and
+//! senders having posted conflicting frames for the same round are excluded, so that
+//! all participants reading the same posts obtain the same view of the session.
+//!
+//! Boards do not authenticate posters, hence a sender index can be claimed by anyone
+//! able to post. The messages of the distributed key generation are bound to the index
+//! of their sender by zero-knowledge proofs and encryption, and invalid partial
+//! signatures are identified by the signature aggregator, but a board restricting who
+//! can post under a session's topics prevents the resulting denials of service.
+//!
+//! [`BulletinBoard`]: crate::bulletin::BulletinBoard
+//! [`ProtocolFrame`]: crate::message::ProtocolFrame
+//! [`BulletinChannel`]: crate::bulletin::BulletinChannel
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::{Complaint, EncryptedSecretShare, Participant};
+use crate::message::{ProtocolFrame, ProtocolMessage, ProtocolRound};
+use crate::sign::{PartialThresholdSignature, PublicCommitmentShareList};
+use crate::utils::{hash_to_array, BTreeMap, Vec};
+use crate::FrostResult;
+
+/// A publicly readable bulletin board, storing content under its hash.
+pub trait BulletinBoard<C: CipherSuite> {
+    /// Post `content` under the given `topic`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the hash under which the content can
+    /// be retrieved with [`BulletinBoard::get`].
+    fn post(&mut self, topic: &[u8], content: &[u8]) -> FrostResult<C, Vec<u8>>;
+
+    /// Retrieve the content with the given `hash`, if any.
+    fn get(&self, hash: &[u8]) -> FrostResult<C, Option<Vec<u8>>>;
+
+    /// List the hashes of the content posted under the given `topic`, in any order.
+    fn list(&self, topic: &[u8]) -> FrostResult<C, Vec<Vec<u8>>>;
+}
+
+/// A [`BulletinBoard`] held in memory, for testing and single-process simulations.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryBulletinBoard {
+    /// The content of all posts, by hash.
+    posts: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// The hashes of the posts of each topic, in posting order.
+    topics: BTreeMap<Vec<u8>, Vec<Vec<u8>>>,
+}
+
+impl MemoryBulletinBoard {
+    /// Create a new, empty [`MemoryBulletinBoard`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<C: CipherSuite> BulletinBoard<C> for MemoryBulletinBoard {
+    fn post(&mut self, topic: &[u8], content: &[u8]) -> FrostResult<C, Vec<u8>> {
+        let hash = hash_to_array::<C>((C::context_string() + "bulletin post").as_bytes(), content)?
+            .as_ref()
+            .to_vec();
+
+        let hashes = self.topics.entry(topic.to_vec()).or_default();
+        if !hashes.contains(&hash) {
+            hashes.push(hash.clone());
+        }
+        self.posts.insert(hash.clone(), content.to_vec());
+
+        Ok(hash)
+    }
+
+    fn get(&self, hash: &[u8]) -> FrostResult<C, Option<Vec<u8>>> {
+        Ok(self.posts.get(hash).cloned())
+    }
+
+    fn list(&self, topic: &[u8]) -> FrostResult<C, Vec<Vec<u8>>> {
+        Ok(self.topics.get(topic).cloned().unwrap_or_default())
+    }
+}
+
+/// The frames of a round, as polled from a [`BulletinBoard`].
+#[derive(Debug)]
+pub struct BulletinPoll<C: CipherSuite> {
+    /// The frames of the round, sorted by sender.
+    pub(crate) frames: Vec<ProtocolFrame<C>>,
+    /// The sorted indices of the senders having posted conflicting frames.
+    pub(crate) equivocators: Vec<u32>,
+}
+
+impl<C: CipherSuite> BulletinPoll<C> {
+    /// The frames of the round, one per sender, sorted by sender.
+    pub fn frames(&self) -> &[ProtocolFrame<C>] {
+        &self.frames
+    }
+
+    /// The sorted indices of the senders excluded for having posted conflicting
+    /// frames for the round.
+    pub fn equivocators(&self) -> &[u32] {
+        &self.equivocators
+    }
+
+    /// Unwrap the frames of the round.
+    pub fn into_frames(self) -> Vec<ProtocolFrame<C>> {
+        self.frames
+    }
+}
+
+/// The channel of a participant to a session run through a [`BulletinBoard`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BulletinChannel {
+    /// The identifier of the session.
+    pub(crate) session_id: Vec<u8>,
+    /// The index of the participant.
+    pub(crate) index: u32,
+}
+
+impl BulletinChannel {
+    /// Open the channel of the participant with the given `index` to the session
+    /// with the given `session_id`.
+    pub fn new(session_id: &[u8], index: u32) -> Self {
+        Self {
+            session_id: session_id.to_vec(),
+            index,
+        }
+    }
+
+    /// The topic under which the frames of the given `round` of this session are posted.
+    ///
+    /// It is the session identifier, followed by the tag of the round.
+    pub fn topic(&self, round: ProtocolRound) -> Vec<u8> {
+        let mut topic = self.session_id.clone();
+        topic.push(round.to_byte());
+
+        topic
+    }
+
+    /// Post the given `message` of this participant to the `board`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the hash of the post.
+    pub fn publish<C: CipherSuite>(
+        &self,
+        board: &mut impl BulletinBoard<C>,
+        message: ProtocolMessage<C>,
+    ) -> FrostResult<C, Vec<u8>> {
+        let round = message.round();
+        let frame = ProtocolFrame::new(self.index, &self.session_id, message);
+
+        board.post(&self.topic(round), &frame.to_bytes()?)
+    }
+
+    /// Poll the frames of the given `round` of this session from the `board`.
+    ///
+    /// Posts which cannot be retrieved or deserialized, or which belong to another
+    /// session or round, are skipped. Identical posts of a sender are only counted
+    /// once, while senders having posted different frames are excluded.
+    pub fn poll<C: CipherSuite>(
+        &self,
+        board: &impl BulletinBoard<C>,
+        round: ProtocolRound,
+    ) -> FrostResult<C, BulletinPoll<C>> {
+        let mut posts: BTreeMap<u32, Vec<Vec<u8>>> = BTreeMap::new();
+        for hash in board.list(&self.topic(round))?.iter() {
+            let content = match board.get(hash)? {
+                Some(content) => content,
+                None => continue,
+            };
+            let frame = match ProtocolFrame::<C>::from_bytes(&content) {
+                Ok(frame) => frame,
+                Err(_) => continue,
+            };
+            if frame.session_id != self.session_id || frame.round() != round {
+                continue;
+            }
+
+            let contents = posts.entry(frame.sender).or_default();
+            if !contents.contains(&content) {
+                contents.push(content);
+            }
+        }
+
+        let mut frames = Vec::new();
+        let mut equivocators = Vec::new();
+        for (sender, contents) in posts.iter() {
+            match contents.len() {
+                1 => frames.push(ProtocolFrame::from_bytes(&contents[0])?),
+                _ => equivocators.push(*sender),
+            }
+        }
+
+        Ok(BulletinPoll {
+            frames,
+            equivocators,
+        })
+    }
+
+    /// Poll the participants of the first round of a distributed key generation
+    /// session, once at least `expected` of them have been posted.
+    ///
+    /// Participants whose index differs from the sender of their frame are skipped.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the participants sorted by index, or
+    /// [`None`] if fewer than `expected` were posted so far.
+    pub fn poll_participants<C: CipherSuite>(
+        &self,
+        board: &impl BulletinBoard<C>,
+        expected: usize,
+    ) -> FrostResult<C, Option<Vec<Participant<C>>>> {
+        let participants: Vec<Participant<C>> = self
+            .poll(board, ProtocolRound::DkgRound1)?
+            .into_frames()
+            .into_iter()
+            .filter_map(|frame| match frame.message {
+                ProtocolMessage::DkgRound1(participant) if participant.index == frame.sender => {
+                    Some(participant)
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(at_least(participants, expected))
+    }
+
+    /// Poll the encrypted secret shares addressed to this participant during the
+    /// second round of a distributed key generation session, once at least `expected`
+    /// dealers have posted theirs.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the encrypted secret shares sorted by
+    /// dealer, or [`None`] if fewer than `expected` dealers posted a share for this
+    /// participant so far.
+    pub fn poll_encrypted_shares<C: CipherSuite>(
+        &self,
+        board: &impl BulletinBoard<C>,
+        expected: usize,
+    ) -> FrostResult<C, Option<Vec<EncryptedSecretShare<C>>>> {
+        let shares: Vec<EncryptedSecretShare<C>> = self
+            .poll(board, ProtocolRound::DkgRound2)?
+            .into_frames()
+            .into_iter()
+            .filter_map(|frame| match frame.message {
+                ProtocolMessage::DkgRound2(shares) => shares.into_iter().find(|share| {
+                    share.sender_index == frame.sender && share.receiver_index == self.index
+                }),
+                _ => None,
+            })
+            .collect();
+
+        Ok(at_least(shares, expected))
+    }
+
+    /// Poll the complaints posted during a distributed key generation session.
+    ///
+    /// Complaints whose maker differs from the sender of their frame are skipped.
+    pub fn poll_complaints<C: CipherSuite>(
+        &self,
+        board: &impl BulletinBoard<C>,
+    ) -> FrostResult<C, Vec<Complaint<C>>> {
+        Ok(self
+            .poll(board, ProtocolRound::Complaint)?
+            .into_frames()
+            .into_iter()
+            .filter_map(|frame| match frame.message {
+                ProtocolMessage::Complaint(complaint) if complaint.maker_index == frame.sender => {
+                    Some(complaint)
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Poll the published commitment shares of the signers of a session, once at
+    /// least `expected` of them have been posted.
+    ///
+    /// Commitment shares whose participant index differs from the sender of their
+    /// frame are skipped.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the commitment shares sorted by signer,
+    /// or [`None`] if fewer than `expected` were posted so far.
+    pub fn poll_commitments<C: CipherSuite>(
+        &self,
+        board: &impl BulletinBoard<C>,
+        expected: usize,
+    ) -> FrostResult<C, Option<Vec<PublicCommitmentShareList<C>>>> {
+        let commitments: Vec<PublicCommitmentShareList<C>> = self
+            .poll(board, ProtocolRound::SignCommit)?
+            .into_frames()
+            .into_iter()
+            .filter_map(|frame| match frame.message {
+                ProtocolMessage::SignCommit(commitments)
+                    if commitments.participant_index == frame.sender =>
+                {
+                    Some(commitments)
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(at_least(commitments, expected))
+    }
+
+    /// Poll the partial signatures of the signers of a session, once at least
+    /// `expected` of them have been posted.
+    ///
+    /// Partial signatures whose index differs from the sender of their frame are skipped.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the partial signatures sorted by signer,
+    /// or [`None`] if fewer than `expected` were posted so far.
+    pub fn poll_partial_signatures<C: CipherSuite>(
+        &self,
+        board: &impl BulletinBoard<C>,
+        expected: usize,
+    ) -> FrostResult<C, Option<Vec<PartialThresholdSignature<C>>>> {
+        let partial_signatures: Vec<PartialThresholdSignature<C>> = self
+            .poll(board, ProtocolRound::SignPartial)?
+            .into_frames()
+            .into_iter()
+            .filter_map(|frame| match frame.message {
+                ProtocolMessage::SignPartial(partial_signature)
+                    if partial_signature.index == frame.sender =>
+                {
+                    Some(partial_signature)
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(at_least(partial_signatures, expected))
+    }
+}
+
+/// Return the given `items` if there are at least `expected` of them.
+fn at_least<T>(items: Vec<T>, expected: usize) -> Option<Vec<T>> {
+    match items.len() >= expected {
+        true => Some(items),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ciphersuite::CipherSuite;
+    use crate::dkg::{DealerRole, DistributedKeyGeneration, RoundOne};
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator, Signer};
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    /// A [`BulletinBoard`] listing the posts of another board in reverse order.
+    struct ReversedBoard<'a>(&'a mut MemoryBulletinBoard);
+
+    impl<'a> BulletinBoard<Secp256k1Sha256> for ReversedBoard<'a> {
+        fn post(&mut self, topic: &[u8], content: &[u8]) -> FrostResult<Secp256k1Sha256, Vec<u8>> {
+            BulletinBoard::<Secp256k1Sha256>::post(self.0, topic, content)
+        }
+
+        fn get(&self, hash: &[u8]) -> FrostResult<Secp256k1Sha256, Option<Vec<u8>>> {
+            BulletinBoard::<Secp256k1Sha256>::get(&*self.0, hash)
+        }
+
+        fn list(&self, topic: &[u8]) -> FrostResult<Secp256k1Sha256, Vec<Vec<u8>>> {
+            let mut hashes = BulletinBoard::<Secp256k1Sha256>::list(&*self.0, topic)?;
+            hashes.reverse();
+            Ok(hashes)
+        }
+    }
+
+    #[test]
+    fn ceremony_through_bulletin_board_2_out_of_3() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+        let mut board = MemoryBulletinBoard::new();
+
+        // Round one of the distributed key generation.
+        let channels: Vec<BulletinChannel> = (1..=3)
+            .map(|i| BulletinChannel::new(b"dkg session", i))
+            .collect();
+        let mut secrets = Vec::new();
+        for channel in channels.iter() {
+            let (participant, coefficients, dh_private_key) =
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, channel.index, rng)
+                    .unwrap();
+            assert!(channels[0]
+                .poll_participants::<Secp256k1Sha256>(&board, 3)
+                .unwrap()
+                .is_none());
+            channel
+                .publish(
+                    &mut board,
+                    ProtocolMessage::DkgRound1(participant.into_any()),
+                )
+                .unwrap();
+            secrets.push((coefficients, dh_private_key));
+        }
+
+        // Round two, with the encrypted secret shares posted publicly.
+        let mut states = Vec::new();
+        for (channel, (coefficients, dh_private_key)) in channels.iter().zip(secrets.iter()) {
+            let participants = channel.poll_participants(&board, 3).unwrap().unwrap();
+            let (state, _) = DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                &params,
+                dh_private_key,
+                &channel.index,
+                coefficients,
+                &participants,
+                rng,
+            )
+            .unwrap();
+            channel
+                .publish(
+                    &mut board,
+                    ProtocolMessage::DkgRound2(
+                        state.their_encrypted_secret_shares().unwrap().clone(),
+                    ),
+                )
+                .unwrap();
+            states.push(state);
+        }
+
+        let mut keys = Vec::new();
+        for (channel, state) in channels.iter().zip(states) {
+            let shares = channel.poll_encrypted_shares(&board, 3).unwrap().unwrap();
+            assert!(shares
+                .iter()
+                .all(|share| share.receiver_index == channel.index));
+            keys.push(state.to_round_two(shares, rng).unwrap().finish().unwrap());
+        }
+        assert!(channels[0]
+            .poll_complaints::<Secp256k1Sha256>(&board)
+            .unwrap()
+            .is_empty());
+        let group_key = keys[0].0;
+        assert!(keys.iter().all(|(key, _)| *key == group_key));
+
+        // Signing, between participants 1 and 3.
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let channels: Vec<BulletinChannel> = [1, 3]
+            .iter()
+            .map(|i| BulletinChannel::new(b"signing session", *i))
+            .collect();
+        let signing_keys = [&keys[0].1, &keys[2].1];
+        let mut secret_comshares = Vec::new();
+        for (channel, signing_key) in channels.iter().zip(signing_keys.iter()) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, signing_key, 1).unwrap();
+            channel
+                .publish(&mut board, ProtocolMessage::SignCommit(public_comshares))
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let commitments = channels[0]
+            .poll_commitments::<Secp256k1Sha256>(&board, 2)
+            .unwrap()
+            .unwrap();
+        let signers: Vec<Signer<Secp256k1Sha256>> = commitments
+            .iter()
+            .map(|commitment| Signer {
+                participant_index: commitment.participant_index,
+                published_commitment_share: commitment.commitments[0],
+            })
+            .collect();
+        for (signer, signing_key) in signers.iter().zip(signing_keys.iter()) {
+            aggregator
+                .include_signer(
+                    signer.participant_index,
+                    signer.published_commitment_share,
+                    signing_key.to_public(),
+                )
+                .unwrap();
+        }
+
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+        for ((channel, signing_key), secret_comshare) in channels
+            .iter()
+            .zip(signing_keys.iter())
+            .zip(secret_comshares.iter_mut())
+        {
+            let partial_signature = signing_key
+                .sign(
+                    message_hash.as_ref(),
+                    &group_key,
+                    secret_comshare,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            channel
+                .publish(&mut board, ProtocolMessage::SignPartial(partial_signature))
+                .unwrap();
+        }
+        for partial_signature in channels[0]
+            .poll_partial_signatures(&board, 2)
+            .unwrap()
+            .unwrap()
+        {
+            aggregator
+                .include_partial_signature(partial_signature)
+                .unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, message_hash.as_ref()).is_ok());
+
+        // Polling does not depend on the order in which the posts are listed.
+        let polled = channels[0]
+            .poll::<Secp256k1Sha256>(&board, ProtocolRound::SignCommit)
+            .unwrap();
+        let reversed = channels[0]
+            .poll(&ReversedBoard(&mut board), ProtocolRound::SignCommit)
+            .unwrap();
+        assert_eq!(polled.frames(), reversed.frames());
+
+        // Reposts are counted once, while conflicting posts exclude their sender.
+        let (public_comshares, _) =
+            generate_commitment_share_lists(&mut OsRng, &keys[2].1, 1).unwrap();
+        channels[1]
+            .publish(&mut board, ProtocolMessage::SignCommit(public_comshares))
+            .unwrap();
+        let polled = channels[0]
+            .poll::<Secp256k1Sha256>(&board, ProtocolRound::SignCommit)
+            .unwrap();
+        assert_eq!(polled.equivocators(), &[3]);
+        assert_eq!(polled.frames().len(), 1);
+        assert!(channels[0]
+            .poll_commitments::<Secp256k1Sha256>(&board, 2)
+            .unwrap()
+            .is_none());
+
+        // Frames claiming another sender than their content are skipped.
+        let (public_comshares, _) =
+            generate_commitment_share_lists(&mut OsRng, &keys[1].1, 1).unwrap();
+        BulletinChannel::new(b"signing session", 4)
+            .publish(&mut board, ProtocolMessage::SignCommit(public_comshares))
+            .unwrap();
+        assert_eq!(
+            channels[0]
+                .poll_commitments::<Secp256k1Sha256>(&board, 1)
+                .unwrap()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -5,11 +5,20 @@ use core::marker::{Send, Sync};
 
 use zeroize::Zeroize;
 
-use ark_ec::CurveGroup;
+use ark_ec::{CurveGroup, Group, VariableBaseMSM};
+use ark_ff::field_hashers::HashToField;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Write};
 
-use crate::utils::{Scalar, String};
-use crate::FrostResult;
-use digest::{Digest, DynDigest};
+use crate::keys::GroupVerifyingKey;
+use crate::share_cipher::ShareCipher;
+use crate::sign::{compute_challenge, ThresholdSignature};
+use crate::spki::SpkiAlgorithm;
+use crate::testing::conformance::check_hash_functions;
+use crate::utils::{vec, Scalar, String, ToString, Vec};
+use crate::validation::PointValidationPolicy;
+use crate::{Element, Error, FrostResult, HASH_SEC_PARAM};
+use digest::{Digest, DynDigest, ExtendableOutput, Update, XofReader};
 
 /// A trait defining the prime-order group of operation and cryptographic hash function details
 /// of this ICE-FROST protocol instantiation.
@@ -27,6 +36,28 @@ pub trait CipherSuite: Copy + Clone + PartialEq + Eq + Debug + Send + Sync + Zer
     /// The underlying hasher used to construct all random oracles of this [`CipherSuite`] .
     type InnerHasher: Default + Clone + Digest + DynDigest;
 
+    /// The authenticated symmetric encryption scheme used to encrypt secret shares
+    /// during distributed key generation sessions of this [`CipherSuite`].
+    type ShareCipher: ShareCipher;
+
+    /// The policy for validating the group elements of deserialized ICE-FROST types.
+    ///
+    /// Defaults to [`PointValidationPolicy::Strict`].
+    const POINT_VALIDATION_POLICY: PointValidationPolicy = PointValidationPolicy::Strict;
+
+    /// The policy for fitting the digests of the `InnerHasher` into a `HashOutput`,
+    /// when their lengths differ.
+    ///
+    /// Defaults to [`HashOutputPolicy::Exact`].
+    const HASH_OUTPUT_POLICY: HashOutputPolicy = HashOutputPolicy::Exact;
+
+    /// The algorithm of the public keys of this [`CipherSuite`] when encoded as
+    /// X.509 `SubjectPublicKeyInfo` structures, if any.
+    ///
+    /// Defaults to [`None`], in which case group keys cannot be exported with
+    /// [`GroupVerifyingKey::to_spki_der`](crate::keys::GroupVerifyingKey::to_spki_der).
+    const SPKI_ALGORITHM: Option<SpkiAlgorithm> = None;
+
     //////////////////////////////////////////////////////////////////////////////////////////////
 
     // Required methods
@@ -39,70 +70,957 @@ pub trait CipherSuite: Copy + Clone + PartialEq + Eq + Debug + Send + Sync + Zer
 
     // Provided methods`
 
+    /// Hash the provided message `m` to an element of the scalar field of this
+    /// [`CipherSuite`], under the domain separation tag `dst`.
+    ///
+    /// It is used by all random oracles of this [`CipherSuite`] outputting scalars.
+    ///
+    /// The default implementation relies on `expand_message_xmd` instantiated with this
+    /// [`CipherSuite`]'s `InnerHasher`, which is suited for Merkle-Damgård hash functions
+    /// like SHA-2. Ciphersuites instantiated with an extendable-output function, such as
+    /// BLAKE3 or SHAKE, should override it to rely on an [`XofFieldHasher`] instead.
+    fn hash_to_field(dst: &[u8], m: &[u8]) -> FrostResult<Self, Scalar<Self>> {
+        crate::utils::hash_to_field::<Self>(dst, m)
+    }
+
+    /// Start hashing a message to an element of the scalar field of this [`CipherSuite`],
+    /// under the domain separation tag `dst`, with a [`ScalarHasher`] absorbing the
+    /// message incrementally.
+    ///
+    /// The output of the returned hasher must be the one of [`CipherSuite::hash_to_field`]
+    /// over the concatenation of all its inputs. The default implementation hence buffers
+    /// them until finalization, while the [`define_ciphersuite!`](crate::define_ciphersuite!)
+    /// macro streams them through [`ScalarHasher::xmd`] for ciphersuites relying on the
+    /// default [`CipherSuite::hash_to_field`].
+    fn scalar_hasher(dst: &[u8]) -> ScalarHasher<Self> {
+        ScalarHasher::buffered(dst)
+    }
+
+    /// The domain separation tag of the Non-Interactive Zero-Knowledge proofs of
+    /// Knowledge of the participants' private keys, used by [`CipherSuite::h0`].
+    ///
+    /// Defaults to this [`CipherSuite`]'s `CONTEXT_STRING`, concatenated with "nizkpok".
+    fn nizk_domain_tag() -> String {
+        Self::context_string() + "nizkpok"
+    }
+
+    /// The domain separation tag of the binding factors of signing nonces,
+    /// used by [`CipherSuite::h1`].
+    ///
+    /// Defaults to this [`CipherSuite`]'s `CONTEXT_STRING`, concatenated with "rho".
+    fn binding_factor_domain_tag() -> String {
+        Self::context_string() + "rho"
+    }
+
+    /// The domain separation tag of the signature challenges, used by [`CipherSuite::h2`].
+    ///
+    /// Defaults to this [`CipherSuite`]'s `CONTEXT_STRING`, concatenated with "challenge".
+    fn challenge_domain_tag() -> String {
+        Self::context_string() + "challenge"
+    }
+
+    /// The domain separation tag of the derivation of signing nonces, used by [`CipherSuite::h3`].
+    ///
+    /// Defaults to this [`CipherSuite`]'s `CONTEXT_STRING`, concatenated with "nonce".
+    fn nonce_domain_tag() -> String {
+        Self::context_string() + "nonce"
+    }
+
+    /// The domain separation tag of the messages to be signed, used by [`CipherSuite::h4`].
+    ///
+    /// Defaults to this [`CipherSuite`]'s `CONTEXT_STRING`, concatenated with "message".
+    fn message_domain_tag() -> String {
+        Self::context_string() + "message"
+    }
+
+    /// The domain separation tag of the group commitments, used by [`CipherSuite::h5`].
+    ///
+    /// Defaults to this [`CipherSuite`]'s `CONTEXT_STRING`, concatenated with "commitment".
+    fn commitment_domain_tag() -> String {
+        Self::context_string() + "commitment"
+    }
+
+    /// The domain separation tag of the derivation of the secret share encryption keys,
+    /// passed as info to HKDF.
+    ///
+    /// Defaults to the empty string.
+    fn share_encryption_domain_tag() -> String {
+        String::new()
+    }
+
+    /// The domain separation tag of the proofs of complaints against misbehaving dealers.
+    ///
+    /// Defaults to "Complaint Context", for compatibility with previously issued complaints.
+    fn complaint_domain_tag() -> String {
+        "Complaint Context".into()
+    }
+
     /// `h0` hash for this [`CipherSuite`] .
     ///
     /// This oracle is not part of the FROST IETF specification, and is
     /// aimed at being used during the distributed key generation phase.
     ///
-    /// The context string for `h0` is this [`CipherSuite`]'s `CONTEXT_STRING`,
-    /// concatenated with "nizkpok".
+    /// Its domain separation tag is given by [`CipherSuite::nizk_domain_tag`].
     ///
     /// It is used to compute the Non-Interactive Zero-Knowledge proofs
     /// of Knowledge of the participants' private keys.
     fn h0(m: &[u8]) -> FrostResult<Self, Scalar<Self>> {
-        crate::utils::hash_to_field::<Self>((Self::context_string() + "nizkpok").as_bytes(), m)
+        Self::hash_to_field(Self::nizk_domain_tag().as_bytes(), m)
     }
 
     /// `h1` hash for this [`CipherSuite`] .
     ///
-    /// The context string for `h1` is this [`CipherSuite`]'s `CONTEXT_STRING`,
-    /// concatenated with "rho".
+    /// Its domain separation tag is given by [`CipherSuite::binding_factor_domain_tag`].
     ///
     /// It is used to compute the binding factor during an ICE-FROST signing session.
     fn h1(m: &[u8]) -> FrostResult<Self, Scalar<Self>> {
-        crate::utils::hash_to_field::<Self>((Self::context_string() + "rho").as_bytes(), m)
+        Self::hash_to_field(Self::binding_factor_domain_tag().as_bytes(), m)
     }
 
     /// `h2` hash for this [`CipherSuite`] .
     ///
-    /// The context string for `h2` is this [`CipherSuite`]'s `CONTEXT_STRING`,
-    /// concatenated with "challenge".
+    /// Its domain separation tag is given by [`CipherSuite::challenge_domain_tag`].
     ///
-    /// It is used to compute the binding factor during an ICE-FROST signing session.
+    /// It is used to compute the challenge during an ICE-FROST signing session.
     fn h2(m: &[u8]) -> FrostResult<Self, Scalar<Self>> {
-        crate::utils::hash_to_field::<Self>((Self::context_string() + "challenge").as_bytes(), m)
+        Self::hash_to_field(Self::challenge_domain_tag().as_bytes(), m)
+    }
+
+    /// Incremental `h2` hasher for this [`CipherSuite`], whose output over the
+    /// concatenation of all its inputs is the one of [`CipherSuite::h2`].
+    ///
+    /// Ciphersuites overriding [`CipherSuite::h2`] must override it as well.
+    fn h2_hasher() -> ScalarHasher<Self> {
+        Self::scalar_hasher(Self::challenge_domain_tag().as_bytes())
     }
 
     /// `h3` hash for this [`CipherSuite`] .
     ///
-    /// The context string for `h3` is this [`CipherSuite`]'s `CONTEXT_STRING`,
-    /// concatenated with "nonce".
+    /// Its domain separation tag is given by [`CipherSuite::nonce_domain_tag`].
     ///
     /// It is used to precompute the nonces to be shared during ICE-FROST signing sessions.
     fn h3(m: &[u8]) -> FrostResult<Self, Scalar<Self>> {
-        crate::utils::hash_to_field::<Self>((Self::context_string() + "nonce").as_bytes(), m)
+        Self::hash_to_field(Self::nonce_domain_tag().as_bytes(), m)
     }
 
     /// `h4` hash for this [`CipherSuite`] .
     ///
-    /// The context string for `h4` is this [`CipherSuite`]'s `CONTEXT_STRING`,
-    /// concatenated with "message".
+    /// Its domain separation tag is given by [`CipherSuite::message_domain_tag`].
     ///
     /// It is used to hash the message to sign during an ICE-FROST signing session.
     ///
     /// Signers of an ICE-FROST session should use this method to hash the original message
     /// before proceeding to computing their individual partial signatures.
     fn h4(m: &[u8]) -> FrostResult<Self, Self::HashOutput> {
-        crate::utils::hash_to_array::<Self>((Self::context_string() + "message").as_bytes(), m)
+        crate::utils::hash_to_array::<Self>(Self::message_domain_tag().as_bytes(), m)
     }
 
     /// `h5` hash for this [`CipherSuite`] .
     ///
-    /// The context string for `h5` is this [`CipherSuite`]'s `CONTEXT_STRING`,
-    /// concatenated with "commitment".
+    /// Its domain separation tag is given by [`CipherSuite::commitment_domain_tag`].
     ///
     /// It is used to hash the group commitment during an ICE-FROST signing session.
     fn h5(m: &[u8]) -> FrostResult<Self, Self::HashOutput> {
-        crate::utils::hash_to_array::<Self>((Self::context_string() + "commitment").as_bytes(), m)
+        crate::utils::hash_to_array::<Self>(Self::commitment_domain_tag().as_bytes(), m)
+    }
+
+    /// Compute the challenge of a signature with the given `group_commitment`, under
+    /// the given `group_key`, on the message with the given `message_hash`.
+    ///
+    /// The default implementation hashes with [`CipherSuite::h2`] the compressed encodings
+    /// of the group commitment and of the group key, followed by the message hash. They are
+    /// serialized straight into the [`CipherSuite::h2_hasher`], without being collected in
+    /// an intermediate buffer.
+    /// Ciphersuites may override it to produce signatures verifiable by other schemes
+    /// over the same group, such as RedDSA ones with the `interop::reddsa` module.
+    fn challenge(
+        group_commitment: &Self::G,
+        group_key: &Self::G,
+        message_hash: &[u8],
+    ) -> FrostResult<Self, Scalar<Self>> {
+        let mut hasher = Self::h2_hasher();
+        group_commitment
+            .serialize_compressed(&mut hasher)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        group_key
+            .serialize_compressed(&mut hasher)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        hasher.update(message_hash);
+
+        hasher.finalize()
+    }
+
+    /// Compute the multi-scalar multiplication \\( \sum\_i s\_i \cdot P\_i \\) of the
+    /// given `bases` \\( P\_i \\) and `scalars` \\( s\_i \\).
+    ///
+    /// It is used on the hot paths of this [`CipherSuite`], such as the computation of
+    /// the group commitment of signing sessions and the verification of signatures.
+    ///
+    /// The default implementation relies on the Pippenger implementation of `arkworks`,
+    /// which is run on multiple threads with the `parallel` feature. Ciphersuites may
+    /// override it to rely on a backend optimized for their curve.
+    fn msm(
+        bases: &[<Self::G as CurveGroup>::Affine],
+        scalars: &[Scalar<Self>],
+    ) -> FrostResult<Self, Self::G> {
+        Self::G::msm(bases, scalars).map_err(|_| Error::InvalidMSMParameters)
+    }
+}
+
+/// The policy for fitting the digests of a [`CipherSuite`]'s `InnerHasher` into
+/// its `HashOutput`, used by all random oracles of the ciphersuite outputting bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashOutputPolicy {
+    /// Require the digests to have the exact length of the `HashOutput`.
+    Exact,
+    /// Keep the leading bytes of digests longer than the `HashOutput`, as done for
+    /// SHA-512/256.
+    Truncate,
+    /// Stretch the digests to the length of the `HashOutput` with MGF1, as specified
+    /// in [RFC 8017](https://www.rfc-editor.org/rfc/rfc8017#appendix-B.2.1), seeded
+    /// with the digest and instantiated with the `InnerHasher`.
+    ///
+    /// Digests at least as long as the `HashOutput` are truncated.
+    Expand,
+}
+
+impl HashOutputPolicy {
+    /// Whether this policy can fit digests of `digest_length` bytes into hash outputs
+    /// of `output_length` bytes.
+    ///
+    /// This is evaluated at compile time by the [`define_ciphersuite!`](crate::define_ciphersuite!) macro.
+    pub const fn is_compatible(self, output_length: usize, digest_length: usize) -> bool {
+        match self {
+            Self::Exact => output_length == digest_length,
+            Self::Truncate => output_length <= digest_length,
+            Self::Expand => digest_length > 0,
+        }
+    }
+}
+
+/// A hasher to field elements based on an extendable-output function (XOF), like BLAKE3 or SHAKE.
+///
+/// It implements `expand_message_xof` as specified in
+/// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html#name-expand_message_xof),
+/// and reduces the resulting uniform bytes modulo the order of a prime field, with `SEC_PARAM`
+/// bits of additional output per field element to keep the bias negligible.
+///
+/// Only prime fields are supported, as the scalar fields of ciphersuites always are.
+#[derive(Clone, Debug)]
+pub struct XofFieldHasher<
+    X: Default + Clone + Update + ExtendableOutput,
+    const SEC_PARAM: usize = 128,
+> {
+    xof: X,
+    dst_prime: Vec<u8>,
+    len_per_elem: usize,
+}
+
+impl<X: Default + Clone + Update + ExtendableOutput, const SEC_PARAM: usize>
+    XofFieldHasher<X, SEC_PARAM>
+{
+    /// The prefix used to compress domain separation tags longer than 255 bytes.
+    const LONG_DST_PREFIX: &'static [u8] = b"H2C-OVERSIZE-DST-";
+
+    fn expand(&self, msg: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        let mut xof = self.xof.clone();
+        xof.update(msg);
+        xof.update(&(len_in_bytes as u16).to_be_bytes());
+        xof.update(&self.dst_prime);
+
+        let mut uniform_bytes = vec![0u8; len_in_bytes];
+        xof.finalize_xof().read(&mut uniform_bytes);
+
+        uniform_bytes
+    }
+}
+
+impl<F: PrimeField, X: Default + Clone + Update + ExtendableOutput, const SEC_PARAM: usize>
+    HashToField<F> for XofFieldHasher<X, SEC_PARAM>
+{
+    fn new(dst: &[u8]) -> Self {
+        let mut dst_prime = if dst.len() > 255 {
+            let mut xof = X::default();
+            xof.update(Self::LONG_DST_PREFIX);
+            xof.update(dst);
+
+            let mut compressed_dst = vec![0u8; (2 * SEC_PARAM + 7) >> 3];
+            xof.finalize_xof().read(&mut compressed_dst);
+            compressed_dst
+        } else {
+            dst.to_vec()
+        };
+        dst_prime.push(dst_prime.len() as u8);
+
+        // ceil((ceil(log2(p)) + k) / 8)
+        let len_per_elem = (F::MODULUS_BIT_SIZE as usize + SEC_PARAM + 7) >> 3;
+
+        Self {
+            xof: X::default(),
+            dst_prime,
+            len_per_elem,
+        }
+    }
+
+    fn hash_to_field(&self, msg: &[u8], count: usize) -> Vec<F> {
+        let uniform_bytes = self.expand(msg, count * self.len_per_elem);
+
+        uniform_bytes
+            .chunks_exact(self.len_per_elem)
+            .map(F::from_be_bytes_mod_order)
+            .collect()
+    }
+}
+
+/// An incremental hasher to the scalar field of a [`CipherSuite`], whose output over
+/// the concatenation of all its inputs is the one of [`CipherSuite::hash_to_field`].
+///
+/// It implements [`Write`], so that random oracles can absorb serialized group elements
+/// and messages as they are produced, instead of first collecting them in a buffer.
+/// Hashers are obtained with [`CipherSuite::scalar_hasher`].
+pub struct ScalarHasher<C: CipherSuite>(ScalarHasherState<C>);
+
+enum ScalarHasherState<C: CipherSuite> {
+    /// `expand_message_xmd` instantiated with the `InnerHasher`, which has absorbed
+    /// the zero padding and the inputs so far.
+    Xmd {
+        hasher: C::InnerHasher,
+        dst: Vec<u8>,
+    },
+    /// The inputs so far, to be hashed with [`CipherSuite::hash_to_field`].
+    Buffered { dst: Vec<u8>, message: Vec<u8> },
+}
+
+impl<C: CipherSuite> Debug for ScalarHasher<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mode = match self.0 {
+            ScalarHasherState::Xmd { .. } => "xmd",
+            ScalarHasherState::Buffered { .. } => "buffered",
+        };
+        f.debug_struct("ScalarHasher").field("mode", &mode).finish()
+    }
+}
+
+impl<C: CipherSuite> ScalarHasher<C> {
+    /// Start hashing under the domain separation tag `dst` with `expand_message_xmd`
+    /// instantiated with the `InnerHasher` of `C`, as the default implementation of
+    /// [`CipherSuite::hash_to_field`], absorbing the inputs as they come.
+    pub fn xmd(dst: &[u8]) -> Self {
+        let mut hasher = C::InnerHasher::new();
+        // As with `arkworks`, the message is prefixed with as many zero bytes as are
+        // expanded per scalar.
+        for _ in 0..Self::len_per_scalar() {
+            Digest::update(&mut hasher, [0u8]);
+        }
+
+        Self(ScalarHasherState::Xmd {
+            hasher,
+            dst: dst.to_vec(),
+        })
+    }
+
+    /// Start hashing under the domain separation tag `dst` with [`CipherSuite::hash_to_field`],
+    /// buffering the inputs until finalization.
+    ///
+    /// This supports any ciphersuite, including those whose hasher to field elements
+    /// cannot absorb its inputs incrementally.
+    pub fn buffered(dst: &[u8]) -> Self {
+        Self(ScalarHasherState::Buffered {
+            dst: dst.to_vec(),
+            message: Vec::new(),
+        })
+    }
+
+    /// Absorb the given `data`.
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.0 {
+            ScalarHasherState::Xmd { hasher, .. } => Digest::update(hasher, data),
+            ScalarHasherState::Buffered { message, .. } => message.extend_from_slice(data),
+        }
+    }
+
+    /// Finish hashing, and return the resulting scalar.
+    pub fn finalize(self) -> FrostResult<C, Scalar<C>> {
+        let (mut hasher, dst) = match self.0 {
+            ScalarHasherState::Xmd { hasher, dst } => (hasher, dst),
+            ScalarHasherState::Buffered { dst, message } => {
+                return C::hash_to_field(&dst, &message)
+            }
+        };
+
+        let len_in_bytes = Self::len_per_scalar();
+        let dst_prime = Self::xmd_dst_prime(&dst);
+
+        Digest::update(&mut hasher, (len_in_bytes as u16).to_be_bytes());
+        Digest::update(&mut hasher, [0u8]);
+        Digest::update(&mut hasher, &dst_prime);
+        let b_0 = hasher.finalize();
+
+        let mut hasher = C::InnerHasher::new();
+        Digest::update(&mut hasher, &b_0);
+        Digest::update(&mut hasher, [1u8]);
+        Digest::update(&mut hasher, &dst_prime);
+        let mut b_i = hasher.finalize();
+
+        let mut uniform_bytes = Vec::with_capacity(len_in_bytes + b_i.len());
+        uniform_bytes.extend_from_slice(&b_i);
+        let mut i = 2u8;
+        while uniform_bytes.len() < len_in_bytes {
+            let mut block = b_0.clone();
+            for (byte, b_i_byte) in block.iter_mut().zip(b_i.iter()) {
+                *byte ^= b_i_byte;
+            }
+
+            let mut hasher = C::InnerHasher::new();
+            Digest::update(&mut hasher, &block);
+            Digest::update(&mut hasher, [i]);
+            Digest::update(&mut hasher, &dst_prime);
+            b_i = hasher.finalize();
+
+            uniform_bytes.extend_from_slice(&b_i);
+            i += 1;
+        }
+
+        Ok(Scalar::<C>::from_be_bytes_mod_order(
+            &uniform_bytes[..len_in_bytes],
+        ))
+    }
+
+    /// The number of bytes expanded per scalar: ceil((ceil(log2(p)) + k) / 8).
+    fn len_per_scalar() -> usize {
+        (Scalar::<C>::MODULUS_BIT_SIZE as usize + HASH_SEC_PARAM + 7) >> 3
+    }
+
+    /// The domain separation tag of `expand_message_xmd`, followed by its length.
+    fn xmd_dst_prime(dst: &[u8]) -> Vec<u8> {
+        let mut dst_prime = if dst.len() > 255 {
+            let mut hasher = C::InnerHasher::new();
+            Digest::update(&mut hasher, b"H2C-OVERSIZE-DST-");
+            Digest::update(&mut hasher, dst);
+            hasher.finalize().to_vec()
+        } else {
+            dst.to_vec()
+        };
+        dst_prime.push(dst_prime.len() as u8);
+
+        dst_prime
+    }
+}
+
+impl<C: CipherSuite> Write for ScalarHasher<C> {
+    fn write(&mut self, buf: &[u8]) -> ark_std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> ark_std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Define a new unit struct implementing [`CipherSuite`].
+///
+/// The group, the inner hasher and the size of its output, the [`ShareCipher`] and the
+/// context string of the ciphersuite must be provided. The size of the hash output is
+/// checked at compile time against the output size of the inner hasher. Optionally:
+///
+/// * `field_hasher` overrides [`CipherSuite::hash_to_field`] with the given
+///   `ark_ff` hasher to field elements, e.g. an [`XofFieldHasher`];
+/// * `hash_output_policy` overrides [`CipherSuite::HASH_OUTPUT_POLICY`], to fit the
+///   digests of the inner hasher into a hash output of a different size;
+/// * `spki_algorithm` sets [`CipherSuite::SPKI_ALGORITHM`], to export group keys as
+///   X.509 `SubjectPublicKeyInfo` structures;
+/// * `conformance_tests` names a test module to generate, running all the checks of
+///   [`run_all`](crate::testing::conformance::run_all) against the new ciphersuite.
+///
+/// # Example
+///
+/// ```
+/// use ice_frost::{define_ciphersuite, Aes256Gcm};
+///
+/// define_ciphersuite! {
+///     /// An instance of ICE-FROST over Secp256k1 with SHA-256 as underlying hasher.
+///     pub struct MySecp256k1Sha256 {
+///         group: ark_secp256k1::Projective,
+///         hasher: sha2::Sha256,
+///         hash_output: [u8; 32],
+///         share_cipher: Aes256Gcm,
+///         context_string: "MY-APP_SECP256K1_SHA256",
+///         conformance_tests: my_secp256k1_sha256_conformance,
+///     }
+/// }
+///
+/// ice_frost::testing::conformance::run_all::<MySecp256k1Sha256>().unwrap();
+/// ```
+#[macro_export]
+macro_rules! define_ciphersuite {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            group: $group:ty,
+            hasher: $hasher:ty,
+            hash_output: $hash_output:ty,
+            share_cipher: $share_cipher:ty,
+            context_string: $context_string:expr
+            $(, field_hasher: $field_hasher:ty)?
+            $(, hash_output_policy: $hash_output_policy:expr)?
+            $(, spki_algorithm: $spki_algorithm:expr)?
+            $(, conformance_tests: $tests:ident)?
+            $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+        $vis struct $name;
+
+        impl $crate::__private::Zeroize for $name {
+            fn zeroize(&mut self) {}
+        }
+
+        impl $crate::CipherSuite for $name {
+            type G = $group;
+
+            type HashOutput = $hash_output;
+
+            type InnerHasher = $hasher;
+
+            type ShareCipher = $share_cipher;
+
+            $(
+                const HASH_OUTPUT_POLICY: $crate::HashOutputPolicy = $hash_output_policy;
+            )?
+
+            $(
+                const SPKI_ALGORITHM: ::core::option::Option<$crate::spki::SpkiAlgorithm> =
+                    ::core::option::Option::Some($spki_algorithm);
+            )?
+
+            fn context_string() -> $crate::__private::String {
+                $crate::__private::ToOwned::to_owned($context_string)
+            }
+
+            $(
+                fn hash_to_field(
+                    dst: &[u8],
+                    m: &[u8],
+                ) -> $crate::FrostResult<Self, $crate::__private::Scalar<Self>> {
+                    $crate::__private::hash_to_field_with::<Self, $field_hasher>(dst, m)
+                }
+            )?
+
+            $crate::__define_scalar_hasher!($($field_hasher)?);
+        }
+
+        const _: () = assert!(
+            <$name as $crate::CipherSuite>::HASH_OUTPUT_POLICY.is_compatible(
+                ::core::mem::size_of::<$hash_output>(),
+                <<$hasher as $crate::__private::OutputSizeUser>::OutputSize as $crate::__private::Unsigned>::USIZE,
+            ),
+            "The hash output size is incompatible with the hasher and its hash output policy",
+        );
+
+        $(
+            #[cfg(test)]
+            mod $tests {
+                #[test]
+                fn conformance() {
+                    $crate::testing::conformance::run_all::<super::$name>().unwrap();
+                }
+            }
+        )?
+    };
+}
+
+/// Define [`CipherSuite::scalar_hasher`] within [`define_ciphersuite!`], streaming
+/// the inputs unless a custom `field_hasher` is provided.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_scalar_hasher {
+    () => {
+        fn scalar_hasher(dst: &[u8]) -> $crate::ScalarHasher<Self> {
+            $crate::ScalarHasher::xmd(dst)
+        }
+    };
+    ($field_hasher:ty) => {};
+}
+
+/// Hash the provided message `m` to an element of the scalar field of the
+/// ciphersuite `C`, under the domain separation tag `dst`, with the hasher `H`.
+pub fn hash_to_field_with<C: CipherSuite, H: HashToField<Scalar<C>>>(
+    dst: &[u8],
+    m: &[u8],
+) -> FrostResult<C, Scalar<C>> {
+    let hasher = H::new(dst);
+
+    Ok(hasher.hash_to_field(m, 1)[0])
+}
+
+/// A known-answer vector for the [`CipherSuite::hash_to_field`] method of a ciphersuite.
+#[derive(Clone, Copy, Debug)]
+pub struct HashToFieldVector<'a> {
+    /// The domain separation tag.
+    pub dst: &'a [u8],
+    /// The message to be hashed.
+    pub message: &'a [u8],
+    /// The compressed serialization of the expected scalar.
+    pub expected: &'a [u8],
+}
+
+/// Run self-tests of the arithmetic and hash functions underlying the ciphersuite `C`.
+///
+/// These are meant to be run once at process start, so that deployments on exotic targets,
+/// such as WASM, embedded or cross-compiled arkworks backends, detect miscompiled field
+/// arithmetic before handling real keys. They check:
+///
+/// * the big integer arithmetic of the scalar field, against its modulus,
+/// * the order of the generator, and the consistency of the group operations,
+/// * the determinism and domain separation of the hash functions,
+/// * a signature and verification roundtrip with a fixed key and nonce.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value indicates that all self-tests succeeded,
+/// otherwise an [`Error::Custom`](crate::Error::Custom) describing the first failure.
+pub fn self_test<C: CipherSuite>() -> FrostResult<C, ()> {
+    self_test_with_vectors::<C>(&[])
+}
+
+/// Run the self-tests of [`self_test`], along with the given known-answer `vectors`
+/// for the [`CipherSuite::hash_to_field`] method of the ciphersuite `C`.
+pub fn self_test_with_vectors<C: CipherSuite>(
+    vectors: &[HashToFieldVector<'_>],
+) -> FrostResult<C, ()> {
+    check_big_integers::<C>()?;
+    check_group::<C>()?;
+    check_hash_functions::<C>()?;
+    for vector in vectors {
+        let mut scalar = Vec::new();
+        C::hash_to_field(vector.dst, vector.message)?
+            .serialize_compressed(&mut scalar)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        if scalar != vector.expected {
+            return Err(Error::Custom(
+                "Hashing to the scalar field does not match its known answer".to_string(),
+            ));
+        }
+    }
+
+    check_signature_roundtrip::<C>()
+}
+
+fn check_big_integers<C: CipherSuite>() -> FrostResult<C, ()> {
+    let modulus = Scalar::<C>::MODULUS;
+    let mut modulus_minus_one = modulus;
+    modulus_minus_one.sub_with_borrow(&<Scalar<C> as PrimeField>::BigInt::from(1u64));
+
+    let a = Scalar::<C>::from(0x0123_4567_89ab_cdefu64);
+    let b = Scalar::<C>::from_le_bytes_mod_order(&[0xff; 64]);
+
+    let is_consistent = Scalar::<C>::from_bigint(modulus).is_none()
+        && (-Scalar::<C>::ONE).into_bigint() == modulus_minus_one
+        && Scalar::<C>::from_le_bytes_mod_order(&[0xff; 8]) == Scalar::<C>::from(u64::MAX)
+        && a.pow(modulus_minus_one) == Scalar::<C>::ONE
+        && b.inverse().map(|b_inv| b * b_inv) == Some(Scalar::<C>::ONE)
+        && (a + b).square() == a.square() + a * b.double() + b.square();
+
+    match is_consistent {
+        true => Ok(()),
+        false => Err(Error::Custom(
+            "The scalar field arithmetic is inconsistent".to_string(),
+        )),
+    }
+}
+
+fn check_group<C: CipherSuite>() -> FrostResult<C, ()> {
+    let generator = C::G::generator();
+    let mut order_minus_one = Scalar::<C>::MODULUS;
+    order_minus_one.sub_with_borrow(&<Scalar<C> as PrimeField>::BigInt::from(1u64));
+
+    let a = C::h0(b"ICE-FROST self-test")?;
+    let b = C::h1(b"ICE-FROST self-test")?;
+
+    let mut bytes = Vec::new();
+    (generator * a)
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+    let deserialized = C::G::deserialize_compressed(&bytes[..])
+        .map_err(|e| Error::DeserializationError(e.into()))?;
+
+    let is_consistent = !generator.is_zero()
+        && generator.mul_bigint(Scalar::<C>::MODULUS).is_zero()
+        && generator.mul_bigint(order_minus_one) == -generator
+        && generator * a + generator * b == generator * (a + b)
+        && (generator * a) * b == generator * (a * b)
+        && deserialized == generator * a;
+
+    match is_consistent {
+        true => Ok(()),
+        false => Err(Error::Custom(
+            "The group arithmetic is inconsistent".to_string(),
+        )),
+    }
+}
+
+fn check_signature_roundtrip<C: CipherSuite>() -> FrostResult<C, ()> {
+    let secret_key = C::h3(b"ICE-FROST self-test secret key")?;
+    let nonce = C::h3(b"ICE-FROST self-test nonce")?;
+    let message_hash = C::h4(b"ICE-FROST self-test message")?;
+
+    let group_key = GroupVerifyingKey::<C>::new(Element(C::G::generator() * secret_key));
+    let group_commitment = C::G::generator() * nonce;
+    let challenge = compute_challenge::<C>(&group_commitment, &group_key, message_hash.as_ref())?;
+
+    let signature = ThresholdSignature::<C> {
+        group_commitment,
+        z: nonce + challenge * secret_key,
+    };
+    let forged_signature = ThresholdSignature::<C> {
+        group_commitment,
+        z: signature.z + Scalar::<C>::ONE,
+    };
+
+    match group_key
+        .verify_signature(&signature, message_hash.as_ref())
+        .is_ok()
+        && group_key
+            .verify_signature(&forged_signature, message_hash.as_ref())
+            .is_err()
+    {
+        true => Ok(()),
+        false => Err(Error::Custom("The signature roundtrip failed".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::Fr;
+    use sha3::Shake128;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn self_test_secp256k1_sha256() {
+        // Computed independently, with the `expand_message_xmd` variant of arkworks
+        // padding messages to the length of a scalar rather than to the block size.
+        let expected_scalars = [
+            from_hex("6f110bd6d38404a52a8fedf40beee176f05eb623013cb1ab26df346a6b178640"),
+            from_hex("cc60ece0e32fe23d0d5f062783421728c0a3dbf90cbf68d306646df8a9947d58"),
+        ];
+        let vectors = [
+            HashToFieldVector {
+                dst: b"ICE-FROST self-test",
+                message: b"",
+                expected: &expected_scalars[0],
+            },
+            HashToFieldVector {
+                dst: b"ICE-FROST self-test",
+                message: b"abc",
+                expected: &expected_scalars[1],
+            },
+        ];
+
+        assert!(self_test::<Secp256k1Sha256>().is_ok());
+        assert!(self_test_with_vectors::<Secp256k1Sha256>(&vectors).is_ok());
+
+        let wrong_vector = HashToFieldVector {
+            message: b"abd",
+            ..vectors[1]
+        };
+        assert!(self_test_with_vectors::<Secp256k1Sha256>(&[wrong_vector]).is_err());
+    }
+
+    #[test]
+    fn expand_message_xof_test_vectors() {
+        // Test vectors from RFC 9380, Appendix K.5.
+        let short_dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let long_dst = [
+            &b"QUUX-V01-CS02-with-expander-SHAKE128-long-DST-"[..],
+            &[b'1'; 210][..],
+        ]
+        .concat();
+
+        let test_vectors: [(&[u8], &[u8], &str); 4] = [
+            (
+                short_dst,
+                b"",
+                "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2",
+            ),
+            (
+                short_dst,
+                b"abc",
+                "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468",
+            ),
+            (
+                &long_dst,
+                b"",
+                "827c6216330a122352312bccc0c8d6e7a146c5257a776dbd9ad9d75cd880fc53",
+            ),
+            (
+                &long_dst,
+                b"abc",
+                "690c8d82c7213b4282c6cb41c00e31ea1d3e2005f93ad19bbf6da40f15790c5c",
+            ),
+        ];
+
+        for (dst, msg, uniform_bytes) in test_vectors {
+            let hasher = <XofFieldHasher<Shake128> as HashToField<Fr>>::new(dst);
+            assert_eq!(hasher.expand(msg, 32), from_hex(uniform_bytes));
+        }
+    }
+
+    #[test]
+    fn xof_hash_to_field_is_deterministic() {
+        let hasher = <XofFieldHasher<Shake128> as HashToField<Fr>>::new(b"ICE-FROST");
+
+        let elems: Vec<Fr> = hasher.hash_to_field(b"message", 2);
+        assert_eq!(elems.len(), 2);
+        assert_ne!(elems[0], elems[1]);
+        let same_elems: Vec<Fr> = hasher.hash_to_field(b"message", 2);
+        let other_elems: Vec<Fr> = hasher.hash_to_field(b"other message", 2);
+        assert_eq!(same_elems, elems);
+        assert_ne!(other_elems, elems);
+    }
+
+    #[test]
+    fn streamed_hash_to_field() {
+        let long_dst = [b'd'; 300];
+        let message: Vec<u8> = (0..200u8).collect();
+
+        for dst in [&b"ICE-FROST"[..], &long_dst[..]] {
+            let expected = Secp256k1Sha256::hash_to_field(dst, &message).unwrap();
+
+            for split in [0, 1, 64, message.len()] {
+                let mut hasher = ScalarHasher::<Secp256k1Sha256>::xmd(dst);
+                hasher.update(&message[..split]);
+                hasher.write_all(&message[split..]).unwrap();
+                assert_eq!(hasher.finalize().unwrap(), expected);
+
+                let mut hasher = ScalarHasher::<Secp256k1Sha256>::buffered(dst);
+                hasher.update(&message[..split]);
+                hasher.write_all(&message[split..]).unwrap();
+                assert_eq!(hasher.finalize().unwrap(), expected);
+            }
+        }
+
+        // The challenge is the `h2` hash of the serialized commitment and key, followed by the message.
+        let group_commitment = ark_secp256k1::Projective::generator();
+        let group_key = group_commitment.double();
+        let mut challenge_input = Vec::new();
+        group_commitment
+            .serialize_compressed(&mut challenge_input)
+            .unwrap();
+        group_key
+            .serialize_compressed(&mut challenge_input)
+            .unwrap();
+        challenge_input.extend_from_slice(&message);
+
+        assert_eq!(
+            Secp256k1Sha256::challenge(&group_commitment, &group_key, &message).unwrap(),
+            Secp256k1Sha256::h2(&challenge_input).unwrap()
+        );
+    }
+
+    #[test]
+    fn overridden_domain_tags() {
+        use crate::testing::conformance::{check_hash_functions, run_all};
+        use crate::testing::Secp256k1Sha256;
+        use crate::Aes256Gcm;
+
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Zeroize)]
+        struct ExternalSpec<const COLLIDING: bool>;
+
+        impl<const COLLIDING: bool> CipherSuite for ExternalSpec<COLLIDING> {
+            type G = ark_secp256k1::Projective;
+
+            type HashOutput = [u8; 32];
+
+            type InnerHasher = sha2::Sha256;
+
+            type ShareCipher = Aes256Gcm;
+
+            fn context_string() -> String {
+                "ICE-FROST_SECP256K1_SHA256".into()
+            }
+
+            fn challenge_domain_tag() -> String {
+                match COLLIDING {
+                    true => Self::binding_factor_domain_tag(),
+                    false => "EXTERNAL-SPEC-challenge".into(),
+                }
+            }
+
+            fn share_encryption_domain_tag() -> String {
+                Self::context_string() + "share encryption"
+            }
+        }
+
+        let message = b"message";
+        assert_eq!(
+            ExternalSpec::<false>::h1(message).unwrap(),
+            Secp256k1Sha256::h1(message).unwrap()
+        );
+        assert_ne!(
+            ExternalSpec::<false>::h2(message).unwrap(),
+            Secp256k1Sha256::h2(message).unwrap()
+        );
+        assert!(run_all::<ExternalSpec<false>>().is_ok());
+        assert!(check_hash_functions::<ExternalSpec<true>>().is_err());
+    }
+
+    #[test]
+    fn conformance_detects_truncated_domain_tags() {
+        use crate::testing::conformance::check_hash_to_field;
+        use crate::Aes256Gcm;
+
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Zeroize)]
+        struct TruncatingSpec;
+
+        impl CipherSuite for TruncatingSpec {
+            type G = ark_secp256k1::Projective;
+
+            type HashOutput = [u8; 32];
+
+            type InnerHasher = sha2::Sha256;
+
+            type ShareCipher = Aes256Gcm;
+
+            fn context_string() -> String {
+                "TRUNCATING-SPEC".into()
+            }
+
+            fn hash_to_field(dst: &[u8], m: &[u8]) -> FrostResult<Self, Scalar<Self>> {
+                crate::utils::hash_to_field::<Self>(&dst[..dst.len().min(255)], m)
+            }
+        }
+
+        assert!(self_test::<TruncatingSpec>().is_ok());
+        assert!(check_hash_to_field::<TruncatingSpec>().is_err());
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_proofs_of_knowledge() {
+        use crate::dkg::NizkPokOfSecretKey;
+        use crate::testing::Secp256k1Blake3;
+
+        use rand::rngs::OsRng;
+
+        let secret_key = crate::Scalar::random(OsRng);
+        let public_key = crate::Element::generator() * secret_key;
+
+        let proof =
+            NizkPokOfSecretKey::<Secp256k1Blake3>::prove(1, &secret_key, &public_key, OsRng)
+                .unwrap();
+        assert!(proof.verify(1, &public_key).is_ok());
+        assert!(proof.verify(2, &public_key).is_err());
     }
 }
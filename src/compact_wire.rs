@@ -0,0 +1,493 @@
+//! The compact wire module, encoding [`ProtocolFrame`]s with `postcard` for
+//! constrained links, with the `compact-wire` feature.
+//!
+//! The canonical encoding of `ark-serialize` writes indices as 4-byte and vector
+//! lengths as 8-byte little-endian integers. The compact encoding instead writes all
+//! integers, lengths and round tags as the LEB128 varints of `postcard`, while points,
+//! scalars and nonces keep their compressed `ark-serialize` encoding, written as
+//! fixed-size arrays without length prefix. A frame carrying a round-one participant
+//! of a DKG session hence shrinks by about thirty bytes, and the encrypted secret shares
+//! of a dealer by thirteen bytes per share, which matters on LoRa, satellite or BLE links.
+//!
+//! The encoding is deterministic, and decoding is canonical: a byte string is only
+//! accepted if it is the exact encoding of the decoded frame, so that overlong varints
+//! or trailing bytes are rejected and a frame has a single valid encoding. Decoded
+//! points are validated as with [`ProtocolFrame::from_bytes`].
+//!
+//! [`ProtocolFrame`]: crate::message::ProtocolFrame
+//! [`ProtocolFrame::from_bytes`]: crate::message::ProtocolFrame::from_bytes
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::{Error as _, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::participant::DealerMaterial;
+use crate::dkg::{
+    Complaint, ComplaintProof, EncryptedSecretShare, NizkPokOfSecretKey, Participant,
+    VerifiableSecretSharingCommitment,
+};
+use crate::fingerprint::FINGERPRINT_LENGTH;
+use crate::keys::DiffieHellmanPublicKey;
+use crate::message::{ProtocolFrame, ProtocolMessage};
+use crate::share_cipher::ShareCipher;
+use crate::sign::{
+    EpochTag, Heartbeat, PartialThresholdSignature, PublicCommitmentShareList, ReRequest,
+};
+use crate::utils::{Scalar, String, Vec};
+use crate::validation::ValidatePoints;
+use crate::{Element, Error, FrostResult};
+
+/// A value written with its compressed `ark-serialize` encoding, as a fixed-size
+/// array whose size is the one of the encoding of the default value of its type.
+struct Fixed<T>(T);
+
+impl<T: CanonicalSerialize> Serialize for Fixed<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = Vec::new();
+        self.0
+            .serialize_compressed(&mut bytes)
+            .map_err(S::Error::custom)?;
+
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for byte in bytes.iter() {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de, T: CanonicalSerialize + CanonicalDeserialize + Default> Deserialize<'de> for Fixed<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FixedVisitor<T>(usize, PhantomData<T>);
+
+        impl<'de, T: CanonicalDeserialize> Visitor<'de> for FixedVisitor<T> {
+            type Value = Fixed<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an encoding of {} bytes", self.0)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(self.0);
+                for i in 0..self.0 {
+                    let byte = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                    bytes.push(byte);
+                }
+
+                T::deserialize_compressed(&bytes[..])
+                    .map(Fixed)
+                    .map_err(A::Error::custom)
+            }
+        }
+
+        let size = T::default().compressed_size();
+        deserializer.deserialize_tuple(size, FixedVisitor(size, PhantomData))
+    }
+}
+
+/// A Schnorr proof of knowledge, as its two scalars.
+type CompactProof<C> = (Fixed<Scalar<C>>, Fixed<Scalar<C>>);
+
+/// The compact form of a [`Participant`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct CompactParticipant<C: CipherSuite> {
+    index: u32,
+    dh_public_key: Fixed<C::G>,
+    commitments: Option<(u32, Vec<Fixed<C::G>>)>,
+    proof_of_secret_key: Option<CompactProof<C>>,
+    proof_of_dh_private_key: CompactProof<C>,
+}
+
+/// The compact form of an [`EncryptedSecretShare`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct CompactShare<C: CipherSuite> {
+    sender_index: u32,
+    receiver_index: u32,
+    nonce: Fixed<<C::ShareCipher as ShareCipher>::Nonce>,
+    encrypted_polynomial_evaluation: Vec<u8>,
+    salt: Vec<u8>,
+}
+
+/// The compact form of a [`Complaint`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct CompactComplaint<C: CipherSuite> {
+    maker_index: u32,
+    accused_index: u32,
+    dh_shared_key: Fixed<C::G>,
+    a1: Fixed<C::G>,
+    a2: Fixed<C::G>,
+    z: Fixed<Scalar<C>>,
+}
+
+/// The compact form of a [`Heartbeat`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct CompactHeartbeat<C: CipherSuite> {
+    index: u32,
+    timestamp: u64,
+    epoch: u64,
+    remaining_commitments: u64,
+    software_version: String,
+    r: Fixed<C::G>,
+    s: Fixed<Scalar<C>>,
+}
+
+/// The compact form of a [`ProtocolMessage`], whose variants are numbered as the
+/// tags of their [`ProtocolRound`](crate::message::ProtocolRound).
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+enum CompactMessage<C: CipherSuite> {
+    DkgRound1(CompactParticipant<C>),
+    DkgRound2(Vec<CompactShare<C>>),
+    Complaint(CompactComplaint<C>),
+    SignCommit(u32, Vec<(Fixed<C::G>, Fixed<C::G>)>),
+    SignPartial(
+        u32,
+        Fixed<Scalar<C>>,
+        Option<(u64, [u8; FINGERPRINT_LENGTH])>,
+    ),
+    SignReRequest(Vec<u8>),
+    Abort(String),
+    Heartbeat(CompactHeartbeat<C>),
+}
+
+/// The compact form of a [`ProtocolFrame`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct CompactFrame<C: CipherSuite> {
+    message: CompactMessage<C>,
+    sender: u32,
+    epoch: u64,
+    session_id: Vec<u8>,
+}
+
+/// An error for byte strings which are not compact encodings of a frame.
+fn invalid_data<C: CipherSuite>() -> Error<C> {
+    Error::DeserializationError(SerializationError::InvalidData.into())
+}
+
+fn proof<C: CipherSuite>(proof: &NizkPokOfSecretKey<C>) -> CompactProof<C> {
+    (Fixed(proof.s), Fixed(proof.r))
+}
+
+fn from_proof<C: CipherSuite>((s, r): CompactProof<C>) -> NizkPokOfSecretKey<C> {
+    NizkPokOfSecretKey { s: s.0, r: r.0 }
+}
+
+impl<C: CipherSuite> CompactMessage<C> {
+    fn new(message: &ProtocolMessage<C>) -> FrostResult<C, Self> {
+        let message = match message {
+            ProtocolMessage::DkgRound1(participant) => Self::DkgRound1(CompactParticipant {
+                index: participant.index,
+                dh_public_key: Fixed(participant.dh_public_key.key),
+                commitments: participant.dealer_commitments().map(|commitments| {
+                    (
+                        commitments.index,
+                        commitments.points.iter().copied().map(Fixed).collect(),
+                    )
+                }),
+                proof_of_secret_key: participant.dealer_proof_of_secret_key().map(proof),
+                proof_of_dh_private_key: proof(&participant.proof_of_dh_private_key),
+            }),
+            ProtocolMessage::DkgRound2(shares) => Self::DkgRound2(
+                shares
+                    .iter()
+                    .map(|share| CompactShare {
+                        sender_index: share.sender_index,
+                        receiver_index: share.receiver_index,
+                        nonce: Fixed(share.nonce),
+                        encrypted_polynomial_evaluation: share
+                            .encrypted_polynomial_evaluation
+                            .clone(),
+                        salt: share.salt.clone(),
+                    })
+                    .collect(),
+            ),
+            ProtocolMessage::Complaint(complaint) => Self::Complaint(CompactComplaint {
+                maker_index: complaint.maker_index,
+                accused_index: complaint.accused_index,
+                dh_shared_key: Fixed(complaint.dh_shared_key),
+                a1: Fixed(complaint.proof.a1),
+                a2: Fixed(complaint.proof.a2),
+                z: Fixed(complaint.proof.z),
+            }),
+            ProtocolMessage::SignCommit(commitments) => Self::SignCommit(
+                commitments.participant_index,
+                commitments
+                    .commitments
+                    .iter()
+                    .map(|(hiding, binding)| (Fixed(*hiding), Fixed(*binding)))
+                    .collect(),
+            ),
+            ProtocolMessage::SignPartial(partial_signature) => Self::SignPartial(
+                partial_signature.index,
+                Fixed(partial_signature.z),
+                partial_signature
+                    .epoch_tag
+                    .map(|tag| (tag.epoch, tag.key_fingerprint)),
+            ),
+            ProtocolMessage::SignReRequest(request) => Self::SignReRequest(request.to_bytes()?),
+            ProtocolMessage::Abort(reason) => Self::Abort(reason.clone()),
+            ProtocolMessage::Heartbeat(heartbeat) => Self::Heartbeat(CompactHeartbeat {
+                index: heartbeat.index,
+                timestamp: heartbeat.timestamp,
+                epoch: heartbeat.epoch,
+                remaining_commitments: heartbeat.remaining_commitments,
+                software_version: heartbeat.software_version.clone(),
+                r: Fixed(heartbeat.r),
+                s: Fixed(heartbeat.s),
+            }),
+        };
+
+        Ok(message)
+    }
+
+    fn into_message(self) -> FrostResult<C, ProtocolMessage<C>> {
+        let message = match self {
+            Self::DkgRound1(participant) => ProtocolMessage::DkgRound1(Participant {
+                index: participant.index,
+                dh_public_key: DiffieHellmanPublicKey::new(Element(participant.dh_public_key.0)),
+                material: DealerMaterial::from_parts(
+                    participant.commitments.map(|(index, points)| {
+                        VerifiableSecretSharingCommitment {
+                            index,
+                            points: points.into_iter().map(|point| point.0).collect(),
+                        }
+                    }),
+                    participant.proof_of_secret_key.map(from_proof),
+                )
+                .map_err(|e| Error::DeserializationError(e.into()))?,
+                proof_of_dh_private_key: from_proof(participant.proof_of_dh_private_key),
+                _role: PhantomData,
+            }),
+            Self::DkgRound2(shares) => ProtocolMessage::DkgRound2(
+                shares
+                    .into_iter()
+                    .map(|share| {
+                        EncryptedSecretShare::new(
+                            share.sender_index,
+                            share.receiver_index,
+                            share.nonce.0,
+                            share.encrypted_polynomial_evaluation,
+                            share.salt,
+                        )
+                    })
+                    .collect(),
+            ),
+            Self::Complaint(complaint) => ProtocolMessage::Complaint(Complaint {
+                maker_index: complaint.maker_index,
+                accused_index: complaint.accused_index,
+                dh_shared_key: complaint.dh_shared_key.0,
+                proof: ComplaintProof {
+                    a1: complaint.a1.0,
+                    a2: complaint.a2.0,
+                    z: complaint.z.0,
+                },
+            }),
+            Self::SignCommit(participant_index, commitments) => {
+                ProtocolMessage::SignCommit(PublicCommitmentShareList {
+                    participant_index,
+                    commitments: commitments
+                        .into_iter()
+                        .map(|(hiding, binding)| (hiding.0, binding.0))
+                        .collect(),
+                })
+            }
+            Self::SignPartial(index, z, epoch_tag) => {
+                ProtocolMessage::SignPartial(PartialThresholdSignature {
+                    index,
+                    z: z.0,
+                    epoch_tag: epoch_tag.map(|(epoch, key_fingerprint)| EpochTag {
+                        epoch,
+                        key_fingerprint,
+                    }),
+                })
+            }
+            Self::SignReRequest(request) => {
+                ProtocolMessage::SignReRequest(ReRequest::from_bytes(&request)?)
+            }
+            Self::Abort(reason) => ProtocolMessage::Abort(reason),
+            Self::Heartbeat(heartbeat) => ProtocolMessage::Heartbeat(Heartbeat {
+                index: heartbeat.index,
+                timestamp: heartbeat.timestamp,
+                epoch: heartbeat.epoch,
+                remaining_commitments: heartbeat.remaining_commitments,
+                software_version: heartbeat.software_version,
+                r: heartbeat.r.0,
+                s: heartbeat.s.0,
+            }),
+        };
+
+        Ok(message)
+    }
+}
+
+impl<C: CipherSuite> ProtocolFrame<C> {
+    /// Serialize this [`ProtocolFrame`] to its compact `postcard` encoding.
+    pub fn to_compact_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let frame = CompactFrame {
+            message: CompactMessage::new(&self.message)?,
+            sender: self.sender,
+            epoch: self.epoch,
+            session_id: self.session_id.clone(),
+        };
+
+        postcard::to_allocvec(&frame)
+            .map_err(|_| Error::SerializationError(SerializationError::InvalidData.into()))
+    }
+
+    /// Attempt to deserialize a [`ProtocolFrame`] from its compact `postcard` encoding.
+    pub fn from_compact_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let (frame, rest): (CompactFrame<C>, _) =
+            postcard::take_from_bytes(bytes).map_err(|_| invalid_data())?;
+        if !rest.is_empty() {
+            return Err(invalid_data());
+        }
+
+        let message = frame.message.into_message()?;
+        message.validate_points()?;
+        let frame = Self {
+            sender: frame.sender,
+            session_id: frame.session_id,
+            epoch: frame.epoch,
+            message,
+        };
+
+        // Only the canonical encoding of a frame is accepted.
+        match frame.to_compact_bytes()? == bytes {
+            true => Ok(frame),
+            false => Err(invalid_data()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dkg::DealerRole;
+    use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::{generate_commitment_share_lists, ReRequestReason};
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::ToString;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    /// Check that the compact encoding of `message` round-trips, and agrees with the
+    /// canonical encoding, returning the sizes of both encodings.
+    fn round_trip(message: ProtocolMessage<Secp256k1Sha256>) -> (usize, usize) {
+        let frame = ProtocolFrame::new(3, b"session", message).with_epoch(2);
+        let compact = frame.to_compact_bytes().unwrap();
+        assert_eq!(ProtocolFrame::from_compact_bytes(&compact).unwrap(), frame);
+
+        // Converting through the canonical encoding yields the same compact encoding.
+        let canonical = frame.to_bytes().unwrap();
+        let converted = ProtocolFrame::<Secp256k1Sha256>::from_bytes(&canonical).unwrap();
+        assert_eq!(converted.to_compact_bytes().unwrap(), compact);
+
+        // Truncated and extended encodings are rejected.
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_compact_bytes(
+            &compact[..compact.len() - 1]
+        )
+        .is_err());
+        let mut extended = compact.clone();
+        extended.push(0);
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_compact_bytes(&extended).is_err());
+
+        (compact.len(), canonical.len())
+    }
+
+    #[test]
+    fn compact_frame_serialization() {
+        let params = ThresholdParameters::new(5, 3).unwrap();
+        let rng = OsRng;
+
+        let (dealer, _, dh_private_key) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        let (compact, canonical) = round_trip(ProtocolMessage::DkgRound1(dealer.into_any()));
+        assert!(compact + 30 <= canonical);
+
+        let shares = (2..=5)
+            .map(|receiver| {
+                EncryptedSecretShare::new(1, receiver, [7; 12], vec![9; 48], vec![5; 32])
+            })
+            .collect();
+        let (compact, canonical) = round_trip(ProtocolMessage::DkgRound2(shares));
+        assert!(compact + 4 * 13 <= canonical);
+
+        let signing_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Fr::rand(&mut OsRng),
+        };
+        let (public_comshares, _) =
+            generate_commitment_share_lists(OsRng, &signing_key, 2).unwrap();
+        round_trip(ProtocolMessage::SignCommit(public_comshares));
+
+        let partial_signature = PartialThresholdSignature {
+            index: 1,
+            z: Fr::rand(&mut OsRng),
+            epoch_tag: None,
+        };
+        round_trip(ProtocolMessage::SignPartial(partial_signature.clone()));
+        let group_key = GroupVerifyingKey::new(Element::random(OsRng));
+        round_trip(ProtocolMessage::SignPartial(
+            partial_signature.with_epoch(3, &group_key).unwrap(),
+        ));
+        round_trip(ProtocolMessage::SignReRequest(
+            ReRequest::new(b"message", &[], 2, ReRequestReason::Invalid).unwrap(),
+        ));
+        round_trip(ProtocolMessage::Abort("Timeout".to_string()));
+        round_trip(ProtocolMessage::Heartbeat(
+            Heartbeat::new(1, 42, 0, 8, "1.0.0", &dh_private_key, OsRng).unwrap(),
+        ));
+    }
+
+    #[test]
+    fn non_canonical_compact_frames() {
+        let frame = ProtocolFrame::<Secp256k1Sha256>::new(
+            1,
+            b"session",
+            ProtocolMessage::Abort("Timeout".to_string()),
+        );
+        let compact = frame.to_compact_bytes().unwrap();
+        assert_eq!(compact[..2], [6, 7]);
+
+        // An overlong varint for the length of the abort reason is rejected.
+        let mut overlong = vec![6, 0x87, 0x00];
+        overlong.extend_from_slice(&compact[2..]);
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_compact_bytes(&overlong).is_err());
+
+        // As are unknown rounds and points off the curve.
+        let mut unknown_round = compact;
+        unknown_round[0] = 8;
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_compact_bytes(&unknown_round).is_err());
+
+        let partial = ProtocolFrame::<Secp256k1Sha256>::new(
+            1,
+            b"session",
+            ProtocolMessage::SignCommit(PublicCommitmentShareList {
+                participant_index: 1,
+                commitments: vec![(
+                    <Secp256k1Sha256 as CipherSuite>::G::default(),
+                    <Secp256k1Sha256 as CipherSuite>::G::default(),
+                )],
+            }),
+        );
+        let mut off_curve = partial.to_compact_bytes().unwrap();
+        off_curve[3..36].copy_from_slice(&[0x42; 33]);
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_compact_bytes(&off_curve).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! The coordinator daemon of an ICE-FROST group, running the ROAST loop.
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::PublicKeyPackage;
+use crate::sign::{Initial, SignatureAggregator, ThresholdSignature};
+use crate::utils::{BTreeMap, Vec};
+use crate::{Error, FrostResult};
+
+use std::collections::BTreeSet;
+
+use super::{send_with_retry, DaemonConfig, DaemonMessage, Transport};
+
+/// A signing session started by a [`CoordinatorService`].
+#[derive(Debug)]
+struct Session<'m, C: CipherSuite> {
+    /// The aggregator of the session.
+    aggregator: SignatureAggregator<C, Initial<'m>>,
+    /// The signers of the session who did not respond yet.
+    pending: BTreeSet<u32>,
+}
+
+/// A daemon coordinating signing sessions among the [`SignerService`](super::SignerService)s
+/// of a group, with the public key material given by a [`PublicKeyPackage`].
+#[derive(Debug)]
+pub struct CoordinatorService<C: CipherSuite, T: Transport<C>> {
+    transport: T,
+    package: PublicKeyPackage<C>,
+    config: DaemonConfig,
+    /// The signers available for signing, along with their fresh commitment share.
+    ready: BTreeMap<u32, (C::G, C::G)>,
+    /// The signers waiting for a signing session to complete, along with its identifier.
+    busy: BTreeMap<u32, u64>,
+    /// The signers excluded after providing invalid partial signatures.
+    misbehaving: BTreeSet<u32>,
+    /// The identifier of the next signing session.
+    next_session_id: u64,
+}
+
+impl<C: CipherSuite, T: Transport<C>> CoordinatorService<C, T> {
+    /// Construct a new [`CoordinatorService`] for the group with the given public key `package`.
+    pub fn new(transport: T, package: PublicKeyPackage<C>, config: DaemonConfig) -> Self {
+        Self {
+            transport,
+            package,
+            config,
+            ready: BTreeMap::new(),
+            busy: BTreeMap::new(),
+            misbehaving: BTreeSet::new(),
+            next_session_id: 0,
+        }
+    }
+
+    /// The sorted indices of the signers excluded for misbehaving.
+    pub fn misbehaving_signers(&self) -> Vec<u32> {
+        self.misbehaving.iter().copied().collect()
+    }
+
+    /// Obtain a [`ThresholdSignature`] of the group on a `message`.
+    ///
+    /// Sessions are started with every threshold of ready signers, until one of them
+    /// completes with a valid signature, signers providing invalid partial signatures
+    /// being excluded from later sessions.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ThresholdSignature`], otherwise an
+    /// [`Error::Timeout`] if no session completed within the signing timeout of the
+    /// [`DaemonConfig`], or an [`Error::TooManyInvalidParticipants`] if not enough
+    /// signers remain to reach the threshold.
+    pub async fn sign(&mut self, message: &[u8]) -> FrostResult<C, ThresholdSignature<C>> {
+        let deadline = tokio::time::Instant::now() + self.config.signing_timeout;
+        let threshold = self.package.parameters().t as usize;
+        let mut sessions: BTreeMap<u64, Session<'_, C>> = BTreeMap::new();
+
+        // The sessions of previous calls are abandoned, releasing their signers.
+        self.busy.clear();
+        let first_session_id = self.next_session_id;
+
+        loop {
+            if self
+                .package
+                .verifying_keys()
+                .len()
+                .saturating_sub(self.misbehaving.len())
+                < threshold
+            {
+                return Err(Error::TooManyInvalidParticipants(
+                    self.misbehaving_signers(),
+                ));
+            }
+
+            while self.ready.len() >= threshold {
+                let (session_id, session) = self.start_session(message, threshold).await?;
+                sessions.insert(session_id, session);
+            }
+
+            let (sender, response) =
+                match tokio::time::timeout_at(deadline, self.transport.receive()).await {
+                    Ok(received) => received?,
+                    Err(_) => return Err(Error::Timeout),
+                };
+            if self.misbehaving.contains(&sender) {
+                continue;
+            }
+
+            match response {
+                DaemonMessage::Ready { commitment }
+                    if !self.busy.contains_key(&sender)
+                        && self.package.verifying_key(sender).is_some() =>
+                {
+                    self.ready.insert(sender, commitment);
+                }
+                DaemonMessage::SignResponse {
+                    session_id,
+                    partial_signature,
+                    next_commitment,
+                } => {
+                    if self.busy.get(&sender) != Some(&session_id) {
+                        // Signers responding to an abandoned session of a previous call
+                        // are available again, with their next commitment share.
+                        if session_id < first_session_id && !self.busy.contains_key(&sender) {
+                            self.ready.insert(sender, next_commitment);
+                        }
+                        continue;
+                    }
+                    self.busy.remove(&sender);
+
+                    if partial_signature.index != sender {
+                        self.misbehaving.insert(sender);
+                        continue;
+                    }
+                    self.ready.insert(sender, next_commitment);
+
+                    let completed = match sessions.get_mut(&session_id) {
+                        Some(session) => {
+                            session.pending.remove(&sender);
+                            if session
+                                .aggregator
+                                .include_partial_signature(partial_signature)
+                                .is_err()
+                            {
+                                self.ready.remove(&sender);
+                                self.misbehaving.insert(sender);
+                            }
+                            session.pending.is_empty()
+                        }
+                        None => false,
+                    };
+                    if !completed {
+                        continue;
+                    }
+
+                    if let Some(session) = sessions.remove(&session_id) {
+                        let result = session
+                            .aggregator
+                            .finalize()
+                            .and_then(|aggregator| aggregator.aggregate());
+                        match result {
+                            Ok(signature) => return Ok(signature),
+                            Err(Error::MisbehavingParticipants(indices)) => {
+                                // The aggregator itself is reported as "participant 0".
+                                for index in indices.into_iter().filter(|index| *index != 0) {
+                                    self.ready.remove(&index);
+                                    self.misbehaving.insert(index);
+                                }
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Request all the signers of the group to stop.
+    pub async fn shutdown(&self) -> FrostResult<C, ()> {
+        for verifying_key in self.package.verifying_keys() {
+            send_with_retry(
+                &self.transport,
+                &self.config,
+                verifying_key.index,
+                DaemonMessage::Shutdown,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Start a signing session on `message` with `threshold` of the ready signers.
+    async fn start_session<'m>(
+        &mut self,
+        message: &'m [u8],
+        threshold: usize,
+    ) -> FrostResult<C, (u64, Session<'m, C>)> {
+        let session_id = self.next_session_id;
+        self.next_session_id += 1;
+
+        let chosen: Vec<u32> = self.ready.keys().take(threshold).copied().collect();
+        let mut aggregator = SignatureAggregator::new(
+            *self.package.parameters(),
+            *self.package.group_key(),
+            message,
+            None,
+        );
+        for index in chosen.iter() {
+            if let (Some(commitment), Some(verifying_key)) =
+                (self.ready.remove(index), self.package.verifying_key(*index))
+            {
+                aggregator.include_signer(*index, commitment, verifying_key.clone())?;
+                self.busy.insert(*index, session_id);
+            }
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for index in chosen.iter() {
+            let request = DaemonMessage::SignRequest {
+                session_id,
+                message: message.to_vec(),
+                signers: signers.clone(),
+            };
+            // Unreachable signers are treated as unresponsive, and never complete the session.
+            let _ = send_with_retry(&self.transport, &self.config, *index, request).await;
+        }
+
+        Ok((
+            session_id,
+            Session {
+                aggregator,
+                pending: chosen.into_iter().collect(),
+            },
+        ))
+    }
+}
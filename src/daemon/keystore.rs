@@ -0,0 +1,65 @@
+//! The keystore abstraction holding the secret material of a signer daemon.
+
+use rand::rngs::OsRng;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::IndividualSigningKey;
+use crate::sign::{
+    generate_commitment_share_lists, LocalShareSigner, SecretCommitmentShareList, ShareSigner,
+};
+use crate::utils::vec;
+use crate::{FrostResult, Scalar};
+
+/// A store of the signing share and secret nonces of a signer, which may be backed
+/// by an external device, such as a PKCS#11 token or a TPM.
+pub trait KeyStore<C: CipherSuite>: ShareSigner<C> + Send {
+    /// Generate and persist fresh secret nonces, returning their published commitment share.
+    fn new_commitment(&mut self) -> FrostResult<C, (C::G, C::G)>;
+}
+
+/// A [`KeyStore`] holding its secret material in memory.
+#[derive(Debug)]
+pub struct MemoryKeyStore<C: CipherSuite> {
+    signing_key: IndividualSigningKey<C>,
+    secret_commitment_share_list: SecretCommitmentShareList<C>,
+}
+
+impl<C: CipherSuite> MemoryKeyStore<C> {
+    /// Construct a new [`MemoryKeyStore`] holding the given `signing_key`.
+    pub fn new(signing_key: IndividualSigningKey<C>) -> Self {
+        Self {
+            signing_key,
+            secret_commitment_share_list: SecretCommitmentShareList {
+                commitments: vec![],
+            },
+        }
+    }
+}
+
+impl<C: CipherSuite> ShareSigner<C> for MemoryKeyStore<C> {
+    fn index(&self) -> u32 {
+        self.signing_key.index
+    }
+
+    fn compute_response(
+        &mut self,
+        published_commitment_share: &(C::G, C::G),
+        binding_factor: &Scalar<C>,
+        lambda_challenge: &Scalar<C>,
+    ) -> FrostResult<C, Scalar<C>> {
+        LocalShareSigner::new(&self.signing_key, &mut self.secret_commitment_share_list)
+            .compute_response(published_commitment_share, binding_factor, lambda_challenge)
+    }
+}
+
+impl<C: CipherSuite> KeyStore<C> for MemoryKeyStore<C> {
+    fn new_commitment(&mut self) -> FrostResult<C, (C::G, C::G)> {
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, &self.signing_key, 1)?;
+        self.secret_commitment_share_list
+            .commitments
+            .append(&mut secret_comshares.commitments);
+
+        Ok(public_comshares.commitments[0])
+    }
+}
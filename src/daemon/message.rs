@@ -0,0 +1,152 @@
+//! The messages exchanged between the ICE-FROST daemons.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::sign::{PartialThresholdSignature, Signer};
+use crate::utils::{ToString, Vec};
+use crate::validation::{validate_point, ValidatePoints};
+use crate::{Error, FrostResult};
+
+/// A message exchanged between a [`CoordinatorService`](super::CoordinatorService)
+/// and the [`SignerService`](super::SignerService)s of a group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DaemonMessage<C: CipherSuite> {
+    /// A signer announces a fresh commitment share, making it available for signing.
+    Ready {
+        /// The published commitment share of the signer.
+        commitment: (C::G, C::G),
+    },
+    /// The coordinator requests a partial signature on a message.
+    SignRequest {
+        /// The identifier of the signing session.
+        session_id: u64,
+        /// The message to be signed.
+        message: Vec<u8>,
+        /// The signers of the session, along with their chosen commitment shares.
+        signers: Vec<Signer<C>>,
+    },
+    /// A signer responds to a [`DaemonMessage::SignRequest`].
+    SignResponse {
+        /// The identifier of the signing session.
+        session_id: u64,
+        /// The partial signature of the signer.
+        partial_signature: PartialThresholdSignature<C>,
+        /// A fresh commitment share of the signer, making it available for signing again.
+        next_commitment: (C::G, C::G),
+    },
+    /// The coordinator requests the signers to stop.
+    Shutdown,
+}
+
+impl<C: CipherSuite> DaemonMessage<C> {
+    const READY: u8 = 0;
+    const SIGN_REQUEST: u8 = 1;
+    const SIGN_RESPONSE: u8 = 2;
+    const SHUTDOWN: u8 = 3;
+
+    /// Serialize this [`DaemonMessage`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        match self {
+            Self::Ready { commitment } => {
+                bytes.push(Self::READY);
+                commitment
+                    .serialize_compressed(&mut bytes)
+                    .map_err(|e| Error::SerializationError(e.into()))?;
+            }
+            Self::SignRequest {
+                session_id,
+                message,
+                signers,
+            } => {
+                bytes.push(Self::SIGN_REQUEST);
+                session_id
+                    .serialize_compressed(&mut bytes)
+                    .and_then(|_| message.serialize_compressed(&mut bytes))
+                    .and_then(|_| signers.serialize_compressed(&mut bytes))
+                    .map_err(|e| Error::SerializationError(e.into()))?;
+            }
+            Self::SignResponse {
+                session_id,
+                partial_signature,
+                next_commitment,
+            } => {
+                bytes.push(Self::SIGN_RESPONSE);
+                session_id
+                    .serialize_compressed(&mut bytes)
+                    .and_then(|_| partial_signature.serialize_compressed(&mut bytes))
+                    .and_then(|_| next_commitment.serialize_compressed(&mut bytes))
+                    .map_err(|e| Error::SerializationError(e.into()))?;
+            }
+            Self::Shutdown => bytes.push(Self::SHUTDOWN),
+        }
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`DaemonMessage`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let (tag, mut reader) = bytes
+            .split_first()
+            .ok_or_else(|| Error::Custom("Empty daemon message".to_string()))?;
+
+        let message = match *tag {
+            Self::READY => Self::Ready {
+                commitment: CanonicalDeserialize::deserialize_compressed(&mut reader)
+                    .map_err(|e| Error::DeserializationError(e.into()))?,
+            },
+            Self::SIGN_REQUEST => {
+                let (session_id, message, signers) =
+                    CanonicalDeserialize::deserialize_compressed(&mut reader)
+                        .map_err(|e| Error::DeserializationError(e.into()))?;
+                Self::SignRequest {
+                    session_id,
+                    message,
+                    signers,
+                }
+            }
+            Self::SIGN_RESPONSE => {
+                let (session_id, partial_signature, next_commitment) =
+                    CanonicalDeserialize::deserialize_compressed(&mut reader)
+                        .map_err(|e| Error::DeserializationError(e.into()))?;
+                Self::SignResponse {
+                    session_id,
+                    partial_signature,
+                    next_commitment,
+                }
+            }
+            Self::SHUTDOWN => Self::Shutdown,
+            _ => return Err(Error::Custom("Unknown daemon message".to_string())),
+        };
+
+        if !reader.is_empty() {
+            return Err(Error::Custom(
+                "Trailing bytes in daemon message".to_string(),
+            ));
+        }
+        message.validate_points()?;
+
+        Ok(message)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for DaemonMessage<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        match self {
+            Self::Ready { commitment }
+            | Self::SignResponse {
+                next_commitment: commitment,
+                ..
+            } => {
+                validate_point::<C>(&commitment.0)?;
+                validate_point::<C>(&commitment.1)
+            }
+            Self::SignRequest { signers, .. } => {
+                signers.iter().try_for_each(ValidatePoints::validate_points)
+            }
+            Self::Shutdown => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! The daemon module providing reusable building blocks for long-running
+//! ICE-FROST signing services, built on top of the `tokio` runtime.
+//!
+//! A [`CoordinatorService`] drives signing sessions with the [`SignerService`]s of
+//! a group, through a pluggable [`Transport`]. Signers hold their secret material in
+//! a pluggable [`KeyStore`], which may be backed by an external device.
+//!
+//! Signing follows the ROAST approach for robust asynchronous signing: each signer
+//! announces a fresh commitment share once ready, and the coordinator starts a new
+//! session whenever a threshold of signers is ready. Each response carries the next
+//! commitment share of its signer, making it ready again, while signers found to be
+//! misbehaving are excluded. Hence, a signature is obtained as long as a threshold of
+//! honest signers remains responsive, regardless of unresponsive or malicious ones.
+//!
+//! Messages are sent with a timeout, and retried with an exponential backoff
+//! as configured by the [`DaemonConfig`].
+//!
+//! [`CoordinatorService`]: crate::daemon::CoordinatorService
+//! [`SignerService`]: crate::daemon::SignerService
+//! [`Transport`]: crate::daemon::Transport
+//! [`KeyStore`]: crate::daemon::KeyStore
+//! [`DaemonConfig`]: crate::daemon::DaemonConfig
+
+mod coordinator;
+mod keystore;
+mod message;
+mod signer;
+mod transport;
+
+pub use coordinator::CoordinatorService;
+pub use keystore::{KeyStore, MemoryKeyStore};
+pub use message::DaemonMessage;
+pub use signer::SignerService;
+pub use transport::{MemoryTransport, Transport};
+
+use core::future::Future;
+use core::pin::Pin;
+use core::time::Duration;
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::Box;
+use crate::{Error, FrostResult};
+
+/// The index used to address the coordinator, distinct from all participant indices.
+pub const COORDINATOR_INDEX: u32 = 0;
+
+/// An owned, dynamically typed future, as returned by [`Transport`] methods.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// The timing configuration of the ICE-FROST daemons.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DaemonConfig {
+    /// The maximum duration of a single attempt at sending a message.
+    pub request_timeout: Duration,
+    /// The maximum number of attempts at sending a message.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled after each failed attempt.
+    pub retry_backoff: Duration,
+    /// The maximum duration of a signing operation of the coordinator,
+    /// including all the sessions it started.
+    pub signing_timeout: Duration,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(5),
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(100),
+            signing_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Send a `message` to the `recipient` through the `transport`, retrying failed
+/// or timed out attempts as specified by the `config`.
+pub(crate) async fn send_with_retry<C: CipherSuite, T: Transport<C>>(
+    transport: &T,
+    config: &DaemonConfig,
+    recipient: u32,
+    message: DaemonMessage<C>,
+) -> FrostResult<C, ()> {
+    let mut backoff = config.retry_backoff;
+    let mut last_error = Error::Timeout;
+
+    for attempt in 0..config.max_attempts {
+        if attempt > 0 {
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+
+        match tokio::time::timeout(
+            config.request_timeout,
+            transport.send(recipient, message.clone()),
+        )
+        .await
+        {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(e)) => last_error = e,
+            Err(_) => last_error = Error::Timeout,
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keys::PublicKeyPackage;
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::ShareSigner;
+    use crate::testing::conformance::generate_keys;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::Vec;
+    use crate::Scalar;
+
+    /// A [`KeyStore`] producing invalid partial signatures.
+    struct MaliciousKeyStore(MemoryKeyStore<Secp256k1Sha256>);
+
+    impl ShareSigner<Secp256k1Sha256> for MaliciousKeyStore {
+        fn index(&self) -> u32 {
+            self.0.index()
+        }
+
+        fn compute_response(
+            &mut self,
+            published_commitment_share: &(
+                <Secp256k1Sha256 as CipherSuite>::G,
+                <Secp256k1Sha256 as CipherSuite>::G,
+            ),
+            binding_factor: &Scalar<Secp256k1Sha256>,
+            lambda_challenge: &Scalar<Secp256k1Sha256>,
+        ) -> FrostResult<Secp256k1Sha256, Scalar<Secp256k1Sha256>> {
+            let response = self.0.compute_response(
+                published_commitment_share,
+                binding_factor,
+                lambda_challenge,
+            )?;
+
+            Ok(response + Scalar::one())
+        }
+    }
+
+    impl KeyStore<Secp256k1Sha256> for MaliciousKeyStore {
+        fn new_commitment(
+            &mut self,
+        ) -> FrostResult<
+            Secp256k1Sha256,
+            (
+                <Secp256k1Sha256 as CipherSuite>::G,
+                <Secp256k1Sha256 as CipherSuite>::G,
+            ),
+        > {
+            self.0.new_commitment()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roast_signing() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(5, 3).unwrap();
+        let (group_key, signing_keys) = generate_keys(&params).unwrap();
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|key| key.to_public()).collect(),
+        );
+
+        let mut transports =
+            MemoryTransport::<Secp256k1Sha256>::network(&[COORDINATOR_INDEX, 1, 2, 3, 4, 5]);
+        let config = DaemonConfig {
+            signing_timeout: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let mut coordinator = CoordinatorService::new(transports.remove(0), package, config);
+
+        let mut handles = Vec::new();
+        for (transport, signing_key) in transports.into_iter().zip(signing_keys) {
+            let keystore = MemoryKeyStore::new(signing_key);
+            // Participant 1 is malicious, and participant 2 never answers signing requests.
+            let handle = match keystore.index() {
+                1 => tokio::spawn(async move {
+                    SignerService::new(transport, MaliciousKeyStore(keystore), group_key, config)
+                        .run()
+                        .await
+                        .map(|_| ())
+                }),
+                2 => tokio::spawn(async move {
+                    SignerService::new(transport, keystore, group_key, config)
+                        .with_policy(|_| false)
+                        .run()
+                        .await
+                        .map(|_| ())
+                }),
+                _ => tokio::spawn(async move {
+                    SignerService::new(transport, keystore, group_key, config)
+                        .run()
+                        .await
+                        .map(|_| ())
+                }),
+            };
+            handles.push(handle);
+        }
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let signature = coordinator.sign(message).await.unwrap();
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+        assert!(coordinator
+            .misbehaving_signers()
+            .iter()
+            .all(|index| *index == 1));
+
+        // Sessions abandoned by the previous call must not prevent signing again.
+        let message = b"This is a second test of the tsunami alert system.";
+        let signature = coordinator.sign(message).await.unwrap();
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+
+        coordinator.shutdown().await.unwrap();
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_daemon_message_serialization() {
+        let message = DaemonMessage::<Secp256k1Sha256>::SignRequest {
+            session_id: 7,
+            message: b"message".to_vec(),
+            signers: Vec::new(),
+        };
+        let bytes = message.to_bytes().unwrap();
+        assert_eq!(message, DaemonMessage::from_bytes(&bytes).unwrap());
+
+        let mut bytes = DaemonMessage::<Secp256k1Sha256>::Shutdown
+            .to_bytes()
+            .unwrap();
+        assert_eq!(
+            DaemonMessage::<Secp256k1Sha256>::Shutdown,
+            DaemonMessage::from_bytes(&bytes).unwrap()
+        );
+        bytes.push(0);
+        assert!(DaemonMessage::<Secp256k1Sha256>::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! The signer daemon of an ICE-FROST group.
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::GroupVerifyingKey;
+use crate::sign::{sign_with, Signer};
+use crate::utils::Box;
+use crate::FrostResult;
+
+use super::{send_with_retry, DaemonConfig, DaemonMessage, KeyStore, Transport, COORDINATOR_INDEX};
+
+/// A policy deciding whether a signer accepts to sign a message.
+type SigningPolicy = Box<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+/// A daemon answering the signing requests of a [`CoordinatorService`](super::CoordinatorService)
+/// on behalf of a participant, whose secret material is held by a [`KeyStore`].
+pub struct SignerService<C: CipherSuite, T: Transport<C>, K: KeyStore<C>> {
+    transport: T,
+    keystore: K,
+    group_key: GroupVerifyingKey<C>,
+    config: DaemonConfig,
+    policy: Option<SigningPolicy>,
+}
+
+impl<C, T, K> core::fmt::Debug for SignerService<C, T, K>
+where
+    C: CipherSuite,
+    T: Transport<C> + core::fmt::Debug,
+    K: KeyStore<C> + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SignerService")
+            .field("transport", &self.transport)
+            .field("keystore", &self.keystore)
+            .field("group_key", &self.group_key)
+            .field("config", &self.config)
+            .field("policy", &self.policy.is_some())
+            .finish()
+    }
+}
+
+impl<C: CipherSuite, T: Transport<C>, K: KeyStore<C>> SignerService<C, T, K> {
+    /// Construct a new [`SignerService`] for the group with the given `group_key`.
+    pub fn new(
+        transport: T,
+        keystore: K,
+        group_key: GroupVerifyingKey<C>,
+        config: DaemonConfig,
+    ) -> Self {
+        Self {
+            transport,
+            keystore,
+            group_key,
+            config,
+            policy: None,
+        }
+    }
+
+    /// Only sign the messages accepted by the given `policy`, leaving the other
+    /// signing requests unanswered.
+    pub fn with_policy(mut self, policy: impl Fn(&[u8]) -> bool + Send + Sync + 'static) -> Self {
+        self.policy = Some(Box::new(policy));
+        self
+    }
+
+    /// The [`KeyStore`] of this [`SignerService`].
+    pub fn keystore(&self) -> &K {
+        &self.keystore
+    }
+
+    /// Announce a fresh commitment share to the coordinator, to be available for signing.
+    pub async fn announce(&mut self) -> FrostResult<C, ()> {
+        let commitment = self.keystore.new_commitment()?;
+
+        send_with_retry(
+            &self.transport,
+            &self.config,
+            COORDINATOR_INDEX,
+            DaemonMessage::Ready { commitment },
+        )
+        .await
+    }
+
+    /// Handle a `message` from the coordinator.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates whether this [`SignerService`]
+    /// should keep running.
+    pub async fn handle(&mut self, message: DaemonMessage<C>) -> FrostResult<C, bool> {
+        match message {
+            DaemonMessage::SignRequest {
+                session_id,
+                message,
+                signers,
+            } => {
+                self.respond(session_id, &message, &signers).await?;
+                Ok(true)
+            }
+            DaemonMessage::Shutdown => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// Run this [`SignerService`] until the coordinator requests it to stop.
+    pub async fn run(mut self) -> FrostResult<C, Self> {
+        self.announce().await?;
+
+        loop {
+            let (sender, message) = self.transport.receive().await?;
+            if sender != COORDINATOR_INDEX {
+                continue;
+            }
+            if !self.handle(message).await? {
+                return Ok(self);
+            }
+        }
+    }
+
+    async fn respond(
+        &mut self,
+        session_id: u64,
+        message: &[u8],
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, ()> {
+        if let Some(policy) = &self.policy {
+            if !policy(message) {
+                return Ok(());
+            }
+        }
+
+        // Requests for commitment shares which are unknown or already used are left
+        // unanswered, the coordinator treating this signer as unresponsive for them.
+        let message_hash = C::h4(message)?;
+        let partial_signature = match sign_with(
+            &mut self.keystore,
+            message_hash.as_ref(),
+            &self.group_key,
+            signers,
+        ) {
+            Ok(partial_signature) => partial_signature,
+            Err(_) => return Ok(()),
+        };
+        let next_commitment = self.keystore.new_commitment()?;
+
+        send_with_retry(
+            &self.transport,
+            &self.config,
+            COORDINATOR_INDEX,
+            DaemonMessage::SignResponse {
+                session_id,
+                partial_signature,
+                next_commitment,
+            },
+        )
+        .await
+    }
+}
@@ -0,0 +1,85 @@
+//! The transport abstraction connecting the ICE-FROST daemons.
+
+use tokio::sync::{mpsc, Mutex};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{BTreeMap, Box, ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::{BoxFuture, DaemonMessage};
+
+/// A transport delivering [`DaemonMessage`]s between the daemons of a group, addressed
+/// by participant index, or by [`COORDINATOR_INDEX`](super::COORDINATOR_INDEX) for the
+/// coordinator.
+///
+/// Implementors are responsible for authenticating the sender of received messages.
+pub trait Transport<C: CipherSuite>: Send + Sync {
+    /// Send a `message` to the daemon with index `recipient`.
+    fn send(&self, recipient: u32, message: DaemonMessage<C>) -> BoxFuture<'_, FrostResult<C, ()>>;
+
+    /// Receive the next message addressed to this daemon, along with the index of its sender.
+    fn receive(&self) -> BoxFuture<'_, FrostResult<C, (u32, DaemonMessage<C>)>>;
+}
+
+/// The queue of messages received by a [`MemoryTransport`], along with their sender index.
+type Inbox<C> = mpsc::UnboundedReceiver<(u32, DaemonMessage<C>)>;
+
+/// A [`Transport`] connecting daemons running in the same process, for testing
+/// and prototyping purposes.
+#[derive(Debug)]
+pub struct MemoryTransport<C: CipherSuite> {
+    /// The index of the daemon using this transport.
+    index: u32,
+    /// The queues of the other daemons of the network.
+    outboxes: BTreeMap<u32, mpsc::UnboundedSender<(u32, DaemonMessage<C>)>>,
+    /// The queue of received messages.
+    inbox: Mutex<Inbox<C>>,
+}
+
+impl<C: CipherSuite> MemoryTransport<C> {
+    /// Construct a network of [`MemoryTransport`]s connecting the daemons with the
+    /// given `indices`, returned in the same order.
+    pub fn network(indices: &[u32]) -> Vec<Self> {
+        let (outboxes, inboxes): (BTreeMap<_, _>, Vec<_>) = indices
+            .iter()
+            .map(|index| {
+                let (outbox, inbox) = mpsc::unbounded_channel();
+                ((*index, outbox), inbox)
+            })
+            .unzip();
+
+        indices
+            .iter()
+            .zip(inboxes)
+            .map(|(index, inbox)| Self {
+                index: *index,
+                outboxes: outboxes.clone(),
+                inbox: Mutex::new(inbox),
+            })
+            .collect()
+    }
+}
+
+impl<C: CipherSuite> Transport<C> for MemoryTransport<C> {
+    fn send(&self, recipient: u32, message: DaemonMessage<C>) -> BoxFuture<'_, FrostResult<C, ()>> {
+        let result = match self.outboxes.get(&recipient) {
+            Some(outbox) => outbox
+                .send((self.index, message))
+                .map_err(|_| Error::Custom("The recipient is disconnected".to_string())),
+            None => Err(Error::Custom("Unknown recipient".to_string())),
+        };
+
+        Box::pin(async move { result })
+    }
+
+    fn receive(&self) -> BoxFuture<'_, FrostResult<C, (u32, DaemonMessage<C>)>> {
+        Box::pin(async move {
+            self.inbox
+                .lock()
+                .await
+                .recv()
+                .await
+                .ok_or_else(|| Error::Custom("The transport is closed".to_string()))
+        })
+    }
+}
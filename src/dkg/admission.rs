@@ -0,0 +1,559 @@
+//! The admission module for adding a single participant to an existing group,
+//! without resharing the secret keys of all the other participants.
+//!
+//! The secret shares of the group are the evaluations \\( s\_i = f(i) \\) of an implicit
+//! polynomial \\( f \\) of degree \\( t - 1 \\). A set \\( S \\) of at least \\( t \\) existing
+//! members, called helpers, can jointly deliver the share \\( s\_j = f(j) \\) of a new
+//! participant of index \\( j \\), as \\( s\_j = \sum\_{i \in S} \lambda\_i(j) \cdot s\_i \\),
+//! where \\( \lambda\_i(j) \\) is the Lagrange coefficient of \\( i \\) evaluated at \\( j \\):
+//!
+//! 1. Each helper \\( i \\) splits \\( \lambda\_i(j) \cdot s\_i \\) into random additive parts
+//!    \\( \delta\_{i \rightarrow k} \\), one for each helper \\( k \\), and publishes an
+//!    [`AdmissionContribution`] holding the commitments \\( \delta\_{i \rightarrow k} \cdot G \\)
+//!    along with each part encrypted to its recipient. Anyone can check that the committed
+//!    parts sum to \\( \lambda\_i(j) \cdot Y\_i \\), with \\( Y\_i \\) the public verification
+//!    share of the helper.
+//! 2. Each helper \\( k \\) decrypts and verifies the parts it received, and sends their sum
+//!    \\( \sigma\_k \\) to the new participant in an encrypted [`AdmissionShare`].
+//! 3. The new participant verifies each \\( \sigma\_k \\) against the published commitments,
+//!    and sums them into its secret share \\( s\_j \\).
+//!
+//! No helper learns anything about the shares of the other helpers, nor about the share
+//! of the new participant, and the shares of the existing members are left untouched.
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use core::ops::Mul;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{
+    DhKeyProvider, DiffieHellmanPrivateKey, DiffieHellmanPublicKey, IndividualSigningKey,
+    IndividualVerifyingKey, PublicKeyPackage,
+};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{vec, Scalar, Vec};
+use crate::validation::{
+    deserialize_validated, validate_dh_public_key, validate_points, ValidatePoints,
+};
+use crate::{Error, FrostResult};
+
+use super::participant::Participant;
+use super::role::Role;
+use super::secret_share::{
+    decrypt_share_for_round, encrypt_share_for_round, serialize_dh_shared_key, share_session_id,
+    EncryptedSecretShare, SecretShare,
+};
+
+/// The round under which the parts exchanged between helpers are encrypted.
+const ADMISSION_PART_ROUND: u32 = 0x2000_0000;
+/// The round under which the sums of parts are encrypted to the new participant.
+const ADMISSION_SHARE_ROUND: u32 = 0x2000_0001;
+/// The length of the random salts separating the encryptions of distinct admissions.
+const SALT_LENGTH: usize = 32;
+
+/// The first message of a helper during the admission of a new participant, holding
+/// the parts of its weighted secret share encrypted to every helper.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AdmissionContribution<C: CipherSuite> {
+    /// The index of the helper who issued this contribution.
+    pub(crate) helper_index: u32,
+    /// The salt of the encryption of the parts.
+    pub(crate) salt: Vec<u8>,
+    /// The commitments to the parts, in the order of the helpers.
+    pub(crate) commitments: Vec<C::G>,
+    /// The encrypted parts, in the order of the helpers.
+    pub(crate) encrypted_parts: Vec<EncryptedSecretShare<C>>,
+}
+
+impl<C: CipherSuite> AdmissionContribution<C> {
+    /// The index of the helper who issued this [`AdmissionContribution`].
+    pub fn helper_index(&self) -> u32 {
+        self.helper_index
+    }
+
+    /// Serialize this [`AdmissionContribution`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`AdmissionContribution`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for AdmissionContribution<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>(self.commitments.iter())
+    }
+}
+
+/// The second message of a helper during the admission of a new participant,
+/// holding the sum of the parts it received encrypted to the new participant.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AdmissionShare<C: CipherSuite> {
+    /// The salt of the encryption of the sum.
+    pub(crate) salt: Vec<u8>,
+    /// The encrypted sum of parts.
+    pub(crate) encrypted_sum: EncryptedSecretShare<C>,
+}
+
+impl<C: CipherSuite> AdmissionShare<C> {
+    /// The index of the helper who issued this [`AdmissionShare`].
+    pub fn helper_index(&self) -> u32 {
+        self.encrypted_sum.sender_index
+    }
+
+    /// Serialize this [`AdmissionShare`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`AdmissionShare`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+/// The public description of the admission of a new participant to an existing
+/// group, shared by the helpers and the new participant.
+#[derive(Clone, Debug)]
+pub struct ParticipantAdmission<C: CipherSuite> {
+    /// The public key material of the existing group.
+    public_key_package: PublicKeyPackage<C>,
+    /// The new participant.
+    new_participant: Participant<C>,
+    /// The helpers, sorted by index.
+    helpers: Vec<Participant<C>>,
+}
+
+impl<C: CipherSuite> ParticipantAdmission<C> {
+    /// Describe the admission of the `new_participant` to the group with the given
+    /// `public_key_package`, by the given `helpers`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ParticipantAdmission`], otherwise
+    /// an [`Error`] if the new participant is already a member of the group or has an
+    /// invalid Diffie-Hellman key or proof thereof, or if the helpers are fewer than the
+    /// threshold, are duplicated or are not members of the group.
+    pub fn new<R1: Role<C>, R2: Role<C>>(
+        public_key_package: &PublicKeyPackage<C>,
+        new_participant: &Participant<C, R1>,
+        helpers: &[Participant<C, R2>],
+    ) -> FrostResult<C, Self> {
+        if new_participant.index == 0 {
+            return Err(Error::IndexIsZero);
+        }
+        if public_key_package
+            .verifying_key(new_participant.index)
+            .is_some()
+        {
+            return Err(Error::ExistingParticipant(new_participant.index));
+        }
+        validate_dh_public_key::<C>(new_participant.index, &new_participant.dh_public_key.key)?;
+        new_participant.proof_of_dh_private_key.verify(
+            new_participant.index,
+            &new_participant.dh_public_key.element(),
+        )?;
+
+        let threshold = public_key_package.parameters().t;
+        if helpers.len() < threshold as usize {
+            return Err(Error::InvalidNumberOfParticipants(helpers.len(), threshold));
+        }
+
+        let mut helpers: Vec<Participant<C>> = helpers
+            .iter()
+            .map(|helper| helper.clone().into_any())
+            .collect();
+        helpers.sort_by_key(|helper| helper.index);
+        for pair in helpers.windows(2) {
+            if pair[0].index == pair[1].index {
+                return Err(Error::DuplicateIndices);
+            }
+        }
+        if let Some(helper) = helpers
+            .iter()
+            .find(|helper| public_key_package.verifying_key(helper.index).is_none())
+        {
+            return Err(Error::UnknownParticipant(helper.index));
+        }
+
+        Ok(Self {
+            public_key_package: public_key_package.clone(),
+            new_participant: new_participant.clone().into_any(),
+            helpers,
+        })
+    }
+
+    /// The [`IndividualVerifyingKey`] of the new participant, which anyone can compute
+    /// from the public verification shares of the helpers.
+    pub fn new_verifying_key(&self) -> FrostResult<C, IndividualVerifyingKey<C>> {
+        let mut share = C::G::zero();
+        for helper in self.helpers.iter() {
+            share +=
+                self.verification_share(helper.index)? * self.lagrange_coefficient(helper.index)?;
+        }
+
+        Ok(IndividualVerifyingKey {
+            index: self.new_participant.index,
+            share,
+        })
+    }
+
+    /// The [`PublicKeyPackage`] of the group once the new participant was admitted.
+    pub fn admitted_package(&self) -> FrostResult<C, PublicKeyPackage<C>> {
+        let parameters = self.public_key_package.parameters();
+        let parameters = ThresholdParameters::new(parameters.n + 1, parameters.t)?;
+
+        let mut verifying_keys = self.public_key_package.verifying_keys().to_vec();
+        verifying_keys.push(self.new_verifying_key()?);
+
+        Ok(PublicKeyPackage::new(
+            parameters,
+            *self.public_key_package.group_key(),
+            verifying_keys,
+        ))
+    }
+
+    /// Issue the [`AdmissionContribution`] of the helper with the given `signing_key`
+    /// and Diffie-Hellman private key, to be broadcast to the other helpers.
+    pub fn contribute(
+        &self,
+        signing_key: &IndividualSigningKey<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, AdmissionContribution<C>> {
+        self.helper_position(signing_key.index)?;
+        let dh_public_key = dh_private_key.dh_public_key()?;
+
+        let mut weighted_share = self.lagrange_coefficient(signing_key.index)? * signing_key.key;
+        let mut parts: Vec<Scalar<C>> = (1..self.helpers.len())
+            .map(|_| Scalar::<C>::rand(&mut csprng))
+            .collect();
+        let last_part = parts.iter().fold(weighted_share, |acc, part| acc - part);
+        parts.push(last_part);
+        weighted_share.zeroize();
+
+        let mut salt = vec![0u8; SALT_LENGTH];
+        csprng.fill_bytes(&mut salt);
+
+        let mut commitments = Vec::with_capacity(parts.len());
+        let mut encrypted_parts = Vec::with_capacity(parts.len());
+        for (helper, part) in self.helpers.iter().zip(parts.iter()) {
+            commitments.push(C::G::generator().mul(part));
+            encrypted_parts.push(encrypt_for(
+                &SecretShare {
+                    sender_index: signing_key.index,
+                    receiver_index: helper.index,
+                    polynomial_evaluation: crate::Scalar(*part),
+                },
+                dh_private_key,
+                &dh_public_key,
+                &helper.dh_public_key,
+                &salt,
+                ADMISSION_PART_ROUND,
+            )?);
+        }
+        parts.zeroize();
+
+        Ok(AdmissionContribution {
+            helper_index: signing_key.index,
+            salt,
+            commitments,
+            encrypted_parts,
+        })
+    }
+
+    /// Check that an [`AdmissionContribution`] is well-formed, and that its committed
+    /// parts sum to the weighted verification share of its helper.
+    ///
+    /// This only relies on public information, and can be performed by anyone.
+    pub fn verify_contribution(
+        &self,
+        contribution: &AdmissionContribution<C>,
+    ) -> FrostResult<C, ()> {
+        let is_valid = self.helper_position(contribution.helper_index).is_ok()
+            && contribution.commitments.len() == self.helpers.len()
+            && contribution.encrypted_parts.len() == self.helpers.len()
+            && self
+                .helpers
+                .iter()
+                .zip(contribution.encrypted_parts.iter())
+                .all(|(helper, part)| {
+                    part.sender_index == contribution.helper_index
+                        && part.receiver_index == helper.index
+                })
+            && contribution.commitments.iter().sum::<C::G>()
+                == self.verification_share(contribution.helper_index)?
+                    * self.lagrange_coefficient(contribution.helper_index)?;
+
+        if !is_valid {
+            return Err(Error::InvalidAdmissionContributions(vec![
+                contribution.helper_index,
+            ]));
+        }
+
+        Ok(())
+    }
+
+    /// Compute the [`AdmissionShare`] of the helper with the given `signing_key` and
+    /// Diffie-Hellman private key, from the [`AdmissionContribution`]s of all the
+    /// helpers, to be sent to the new participant.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`AdmissionShare`], otherwise an
+    /// [`Error::InvalidAdmissionContributions`] listing the helpers whose contribution
+    /// is invalid or holds an encrypted part not matching its commitment.
+    pub fn respond(
+        &self,
+        signing_key: &IndividualSigningKey<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        contributions: &[AdmissionContribution<C>],
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, AdmissionShare<C>> {
+        let position = self.helper_position(signing_key.index)?;
+        let dh_public_key = dh_private_key.dh_public_key()?;
+        let contributions = self.sort_contributions(contributions)?;
+
+        let mut sum = Scalar::<C>::ZERO;
+        let mut misbehaving_helpers = Vec::new();
+        for (helper, contribution) in self.helpers.iter().zip(contributions.iter()) {
+            let part = self.verify_contribution(contribution).and_then(|_| {
+                decrypt_from(
+                    &contribution.encrypted_parts[position],
+                    dh_private_key,
+                    &helper.dh_public_key,
+                    &dh_public_key,
+                    &contribution.salt,
+                    ADMISSION_PART_ROUND,
+                )
+            });
+
+            match part {
+                Ok(part)
+                    if C::G::generator().mul(part.polynomial_evaluation.0)
+                        == contribution.commitments[position] =>
+                {
+                    sum += part.polynomial_evaluation.0
+                }
+                _ => misbehaving_helpers.push(helper.index),
+            }
+        }
+        if !misbehaving_helpers.is_empty() {
+            sum.zeroize();
+            return Err(Error::InvalidAdmissionContributions(misbehaving_helpers));
+        }
+
+        let mut salt = vec![0u8; SALT_LENGTH];
+        csprng.fill_bytes(&mut salt);
+
+        let encrypted_sum = encrypt_for(
+            &SecretShare {
+                sender_index: signing_key.index,
+                receiver_index: self.new_participant.index,
+                polynomial_evaluation: crate::Scalar(sum),
+            },
+            dh_private_key,
+            &dh_public_key,
+            &self.new_participant.dh_public_key,
+            &salt,
+            ADMISSION_SHARE_ROUND,
+        );
+        sum.zeroize();
+
+        Ok(AdmissionShare {
+            salt,
+            encrypted_sum: encrypted_sum?,
+        })
+    }
+
+    /// Recover the [`IndividualSigningKey`] of the new participant with the given
+    /// Diffie-Hellman private key, from the [`AdmissionContribution`]s and the
+    /// [`AdmissionShare`]s of all the helpers.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`IndividualSigningKey`] of the new
+    /// participant, matching [`ParticipantAdmission::new_verifying_key`], otherwise an
+    /// [`Error::InvalidAdmissionContributions`] listing the helpers whose contribution
+    /// or share is invalid.
+    pub fn finish(
+        &self,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        contributions: &[AdmissionContribution<C>],
+        shares: &[AdmissionShare<C>],
+    ) -> FrostResult<C, IndividualSigningKey<C>> {
+        let contributions = self.sort_contributions(contributions)?;
+        let misbehaving_contributors: Vec<u32> = contributions
+            .iter()
+            .filter(|contribution| self.verify_contribution(contribution).is_err())
+            .map(|contribution| contribution.helper_index)
+            .collect();
+        if !misbehaving_contributors.is_empty() {
+            return Err(Error::InvalidAdmissionContributions(
+                misbehaving_contributors,
+            ));
+        }
+
+        let mut key = Scalar::<C>::ZERO;
+        let mut misbehaving_helpers = Vec::new();
+        for (position, helper) in self.helpers.iter().enumerate() {
+            let share = shares
+                .iter()
+                .find(|share| share.helper_index() == helper.index)
+                .ok_or(Error::MissingShares)?;
+            let expected_commitment: C::G = contributions
+                .iter()
+                .map(|contribution| contribution.commitments[position])
+                .sum();
+
+            let sum =
+                (share.encrypted_sum.receiver_index == self.new_participant.index).then(|| {
+                    decrypt_from(
+                        &share.encrypted_sum,
+                        dh_private_key,
+                        &helper.dh_public_key,
+                        &self.new_participant.dh_public_key,
+                        &share.salt,
+                        ADMISSION_SHARE_ROUND,
+                    )
+                });
+
+            match sum {
+                Some(Ok(sum))
+                    if C::G::generator().mul(sum.polynomial_evaluation.0)
+                        == expected_commitment =>
+                {
+                    key += sum.polynomial_evaluation.0
+                }
+                _ => misbehaving_helpers.push(helper.index),
+            }
+        }
+        if !misbehaving_helpers.is_empty() {
+            key.zeroize();
+            return Err(Error::InvalidAdmissionContributions(misbehaving_helpers));
+        }
+
+        let signing_key = IndividualSigningKey {
+            index: self.new_participant.index,
+            key,
+        };
+        if C::G::generator().mul(signing_key.key) != self.new_verifying_key()?.share {
+            return Err(Error::ShareVerificationError);
+        }
+
+        Ok(signing_key)
+    }
+
+    /// The position of the helper with the given `index`.
+    fn helper_position(&self, index: u32) -> FrostResult<C, usize> {
+        self.helpers
+            .binary_search_by_key(&index, |helper| helper.index)
+            .map_err(|_| Error::UnknownHelper(index))
+    }
+
+    /// The public verification share of the member with the given `index`.
+    fn verification_share(&self, index: u32) -> FrostResult<C, C::G> {
+        self.public_key_package
+            .verifying_key(index)
+            .map(|key| key.share)
+            .ok_or(Error::InvalidGroupKey)
+    }
+
+    /// The Lagrange coefficient of the helper with the given `index`,
+    /// evaluated at the index of the new participant.
+    fn lagrange_coefficient(&self, index: u32) -> FrostResult<C, Scalar<C>> {
+        let x = Scalar::<C>::from(self.new_participant.index);
+        let my_index = Scalar::<C>::from(index);
+
+        let mut numerator = Scalar::<C>::ONE;
+        let mut denominator = Scalar::<C>::ONE;
+        for helper in self.helpers.iter().filter(|helper| helper.index != index) {
+            let other_index = Scalar::<C>::from(helper.index);
+
+            numerator *= x - other_index;
+            denominator *= my_index - other_index;
+        }
+
+        Ok(numerator * denominator.inverse().ok_or(Error::DuplicateIndices)?)
+    }
+
+    /// Order the given `contributions` as the helpers, checking that
+    /// each helper issued exactly one of them.
+    fn sort_contributions<'a>(
+        &self,
+        contributions: &'a [AdmissionContribution<C>],
+    ) -> FrostResult<C, Vec<&'a AdmissionContribution<C>>> {
+        if contributions.len() != self.helpers.len() {
+            return Err(Error::MissingShares);
+        }
+
+        self.helpers
+            .iter()
+            .map(|helper| {
+                contributions
+                    .iter()
+                    .find(|contribution| contribution.helper_index == helper.index)
+                    .ok_or(Error::MissingShares)
+            })
+            .collect()
+    }
+}
+
+/// Encrypt a `share` from the owner of the `sender_dh_private_key` to the owner of
+/// the `receiver_dh_public_key`, under the given `salt` and DKG `round`.
+fn encrypt_for<C: CipherSuite>(
+    share: &SecretShare<C>,
+    sender_dh_private_key: &DiffieHellmanPrivateKey<C>,
+    sender_dh_public_key: &DiffieHellmanPublicKey<C>,
+    receiver_dh_public_key: &DiffieHellmanPublicKey<C>,
+    salt: &[u8],
+    round: u32,
+) -> FrostResult<C, EncryptedSecretShare<C>> {
+    let mut session_id = share_session_id(sender_dh_public_key, receiver_dh_public_key)?;
+    session_id.extend_from_slice(salt);
+
+    let dh_shared_key = sender_dh_private_key.dh_shared_key(receiver_dh_public_key)?;
+    let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+    let encrypted_share = encrypt_share_for_round(share, &dh_key_bytes, &session_id, &[], round);
+    dh_key_bytes.zeroize();
+
+    encrypted_share
+}
+
+/// Decrypt an `encrypted_share` sent by the owner of the `sender_dh_public_key` to the
+/// owner of the `receiver_dh_private_key`, under the given `salt` and DKG `round`.
+fn decrypt_from<C: CipherSuite>(
+    encrypted_share: &EncryptedSecretShare<C>,
+    receiver_dh_private_key: &DiffieHellmanPrivateKey<C>,
+    sender_dh_public_key: &DiffieHellmanPublicKey<C>,
+    receiver_dh_public_key: &DiffieHellmanPublicKey<C>,
+    salt: &[u8],
+    round: u32,
+) -> FrostResult<C, SecretShare<C>> {
+    let mut session_id = share_session_id(sender_dh_public_key, receiver_dh_public_key)?;
+    session_id.extend_from_slice(salt);
+
+    let dh_shared_key = receiver_dh_private_key.dh_shared_key(sender_dh_public_key)?;
+    let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+    let share = decrypt_share_for_round(encrypted_share, &dh_key_bytes, &session_id, round);
+    dh_key_bytes.zeroize();
+
+    share
+}
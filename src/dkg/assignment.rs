@@ -0,0 +1,268 @@
+//! The assignment module, deriving the indices of the participants of an
+//! ICE-FROST Distributed Key Generation session from their Diffie-Hellman
+//! public keys.
+//!
+//! Decentralized groups without a coordinator to hand out indices can instead
+//! exchange their [`DiffieHellmanPublicKey`]s, and each derive the same indices
+//! with [`assign_indices`]: the keys are sorted by their hash, and assigned
+//! consecutive indices starting from 1. The resulting [`IndexAssignment`] is its
+//! own proof, as anyone holding it can recompute it, and can be passed to
+//! [`DistributedKeyGeneration::bootstrap_with_assignment`](super::DistributedKeyGeneration::bootstrap_with_assignment)
+//! to reject the participants claiming an index which is not theirs.
+
+use core::ops::Mul;
+
+use ark_ec::Group;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{DiffieHellmanPrivateKey, DiffieHellmanPublicKey};
+use crate::utils::{hash_to_array, BTreeMap, ToString, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Element, Error, FrostResult};
+
+use super::participant::Participant;
+use super::role::Role;
+
+/// Derive collision-free indices for the participants owning the given
+/// `dh_public_keys`, as given by [`IndexAssignment::new`].
+pub fn assign_indices<C: CipherSuite>(
+    dh_public_keys: &[DiffieHellmanPublicKey<C>],
+) -> FrostResult<C, BTreeMap<DiffieHellmanPublicKey<C>, u32>> {
+    Ok(IndexAssignment::new(dh_public_keys)?.to_map())
+}
+
+/// The assignment of participant indices to Diffie-Hellman public keys.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IndexAssignment<C: CipherSuite> {
+    /// The public keys of the participants, sorted by their assigned index.
+    pub(crate) public_keys: Vec<DiffieHellmanPublicKey<C>>,
+}
+
+impl<C: CipherSuite> IndexAssignment<C> {
+    /// Deterministically assign an index to each of the given `dh_public_keys`.
+    ///
+    /// The keys are sorted by their hash, ties being broken by their encoding,
+    /// and the key at position \\( i \\) is assigned the index \\( i + 1 \\). The
+    /// assignment thus does not depend on the order of the given keys.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`IndexAssignment`], otherwise an
+    /// [`Error::Custom`] if the same key is given twice.
+    pub fn new(dh_public_keys: &[DiffieHellmanPublicKey<C>]) -> FrostResult<C, Self> {
+        if u32::try_from(dh_public_keys.len()).is_err() {
+            return Err(Error::Custom("Too many public keys to assign".to_string()));
+        }
+
+        let mut hashed_keys = Vec::with_capacity(dh_public_keys.len());
+        for key in dh_public_keys {
+            let mut bytes = Vec::new();
+            key.serialize_compressed(&mut bytes)
+                .map_err(|e| Error::SerializationError(e.into()))?;
+            let hash = hash_to_array::<C>(
+                (C::context_string() + "index assignment").as_bytes(),
+                &bytes,
+            )?;
+            hashed_keys.push((hash.as_ref().to_vec(), bytes, key));
+        }
+        hashed_keys.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        if hashed_keys.windows(2).any(|pair| pair[0].1 == pair[1].1) {
+            return Err(Error::Custom(
+                "The same public key cannot be assigned twice".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            public_keys: hashed_keys
+                .into_iter()
+                .map(|(_, _, key)| key.clone())
+                .collect(),
+        })
+    }
+
+    /// The number of participants in this assignment.
+    pub fn len(&self) -> usize {
+        self.public_keys.len()
+    }
+
+    /// Whether this assignment has no participant.
+    pub fn is_empty(&self) -> bool {
+        self.public_keys.is_empty()
+    }
+
+    /// The index assigned to the given `dh_public_key`, if any.
+    pub fn index_of(&self, dh_public_key: &DiffieHellmanPublicKey<C>) -> Option<u32> {
+        self.public_keys
+            .iter()
+            .position(|key| key == dh_public_key)
+            .map(|position| position as u32 + 1)
+    }
+
+    /// The public key assigned the given `index`, if any.
+    pub fn public_key(&self, index: u32) -> Option<&DiffieHellmanPublicKey<C>> {
+        match index {
+            0 => None,
+            _ => self.public_keys.get(index as usize - 1),
+        }
+    }
+
+    /// The index assigned to the owner of the given `dh_private_key`.
+    pub(crate) fn index_of_private_key(
+        &self,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+    ) -> FrostResult<C, u32> {
+        let dh_public_key =
+            DiffieHellmanPublicKey::<C>::new(Element(C::G::generator().mul(dh_private_key.0)));
+
+        self.index_of(&dh_public_key).ok_or(Error::Custom(
+            "The Diffie-Hellman public key was not assigned an index".to_string(),
+        ))
+    }
+
+    /// Convert this assignment to a map from public keys to their index.
+    pub fn to_map(&self) -> BTreeMap<DiffieHellmanPublicKey<C>, u32> {
+        self.public_keys
+            .iter()
+            .enumerate()
+            .map(|(position, key)| (key.clone(), position as u32 + 1))
+            .collect()
+    }
+
+    /// Check that each of the given `participants` holds the index assigned
+    /// to its Diffie-Hellman public key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is empty if all participants hold their
+    /// assigned index, otherwise an [`Error::MisbehavingParticipants`] listing the
+    /// indices claimed by the other participants.
+    pub fn verify<R: Role<C>>(&self, participants: &[Participant<C, R>]) -> FrostResult<C, ()> {
+        let misbehaving_participants: Vec<u32> = participants
+            .iter()
+            .filter(|p| self.index_of(&p.dh_public_key) != Some(p.index))
+            .map(|p| p.index)
+            .collect();
+
+        match misbehaving_participants.is_empty() {
+            true => Ok(()),
+            false => Err(Error::MisbehavingParticipants(misbehaving_participants)),
+        }
+    }
+
+    /// Serialize this [`IndexAssignment`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`IndexAssignment`] from a vector of bytes.
+    ///
+    /// The assignment is recomputed from the deserialized public keys, and
+    /// rejected if it differs.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let assignment: Self = deserialize_validated(bytes)?;
+
+        if Self::new(&assignment.public_keys)? != assignment {
+            return Err(Error::DeserializationError(
+                ark_serialize::SerializationError::InvalidData.into(),
+            ));
+        }
+
+        Ok(assignment)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for IndexAssignment<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.public_keys
+            .iter()
+            .try_for_each(|key| key.validate_points())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dkg::{DealerRole, DistributedKeyGeneration, RoundOne};
+    use crate::keys::DhKeyProvider;
+    use crate::parameters::ThresholdParameters;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn deterministic_index_assignment() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let dh_private_keys: Vec<DiffieHellmanPrivateKey<Secp256k1Sha256>> =
+            (0..3).map(|_| DiffieHellmanPrivateKey::new(rng)).collect();
+        let dh_public_keys: Vec<_> = dh_private_keys
+            .iter()
+            .map(|sk| sk.dh_public_key().unwrap())
+            .collect();
+
+        // Every participant derives the same indices, whatever the order of the keys.
+        let indices = assign_indices(&dh_public_keys).unwrap();
+        let reversed: Vec<_> = dh_public_keys.iter().rev().cloned().collect();
+        assert_eq!(assign_indices(&reversed).unwrap(), indices);
+        let mut assigned: Vec<u32> = indices.values().copied().collect();
+        assigned.sort();
+        assert_eq!(assigned, vec![1, 2, 3]);
+
+        let assignment = IndexAssignment::new(&dh_public_keys).unwrap();
+        assert_eq!(
+            IndexAssignment::from_bytes(&assignment.to_bytes().unwrap()).unwrap(),
+            assignment
+        );
+        assert!(
+            IndexAssignment::new(&[dh_public_keys[0].clone(), dh_public_keys[0].clone()]).is_err()
+        );
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        for sk in dh_private_keys.iter() {
+            let (p, c) = Participant::<Secp256k1Sha256, DealerRole>::new_dealer_with_assignment(
+                &params,
+                &assignment,
+                sk.clone(),
+                rng,
+            )
+            .unwrap();
+            assert_eq!(Some(p.index), indices.get(&p.dh_public_key).copied());
+            participants.push(p);
+            coefficients.push(c);
+        }
+        assert!(assignment.verify(&participants).is_ok());
+
+        // A participant claiming the index of another one is rejected.
+        let (impostor, _, _) =
+            Participant::new_dealer(&params, participants[1].index, rng).unwrap();
+        let mut tampered = participants.clone();
+        tampered[1] = impostor;
+        assert!(assignment.verify(&tampered).is_err());
+
+        let (_, participant_lists) =
+            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap_with_assignment(
+                &params,
+                &dh_private_keys[0],
+                &coefficients[0],
+                &tampered,
+                &assignment,
+                rng,
+            )
+            .unwrap();
+        assert_eq!(participant_lists.valid_participants.len(), 2);
+        assert_eq!(
+            participant_lists.misbehaving_participants,
+            Some(vec![tampered[1].index])
+        );
+    }
+}
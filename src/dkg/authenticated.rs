@@ -0,0 +1,403 @@
+//! The authenticated module for running an ICE-FROST Distributed Key Generation
+//! session over a broadcast channel authenticated by external keys.
+//!
+//! Participants of a DKG session are otherwise only identified by the Diffie-Hellman
+//! keys they generate for it. When they already hold long-lived keys, such as the
+//! validator keys of a blockchain validator set, each of their messages can also be
+//! signed with these keys, and checked by an [`ExternalAuthenticator`] before being
+//! processed by the state machine.
+//!
+//! An [`AuthenticatedSession`] commits to the external keys of all the senders of
+//! the session in its [`AuthenticatedSession::session_hash`], which is itself bound
+//! to every signed message, so that messages cannot be replayed in another session
+//! or attributed to another key. A sender signs the
+//! [`AuthenticatedSession::signing_message`] of each of its messages with its external
+//! key, and broadcasts it as an [`AuthenticatedMessage`].
+//!
+//! Each signed message also commits to the [`RoundTag`] of its round, session and epoch,
+//! which is carried along the message and checked first, so that a message replayed
+//! into another round, or from a previous epoch, is rejected with an
+//! [`Error::UnexpectedRoundTag`] before its signature is even verified.
+//!
+//! Participants whose messages of the first round fail to authenticate are treated as
+//! misbehaving, while encrypted secret shares failing to authenticate are rejected
+//! before being decrypted.
+
+use core::marker::PhantomData;
+
+use ark_serialize::CanonicalSerialize;
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::DiffieHellmanPrivateKey;
+use crate::message::{ProtocolRound, RoundTag};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{hash_to_array, vec, BTreeMap, Vec};
+use crate::{Error, FrostResult};
+
+use super::bundle::ShareBundle;
+use super::complaint::Complaint;
+use super::key_generation::{DKGParticipantList, DistributedKeyGeneration, RoundTwoProcessor};
+use super::participant::Participant;
+use super::receipt::ShareReceipt;
+use super::role::Role;
+use super::round_types::RoundOne;
+use super::secret_share::{Coefficients, EncryptedSecretShare};
+
+/// A verifier of the signatures made by the participants of a DKG session with their
+/// external keys, such as the validator keys of a blockchain validator set.
+pub trait ExternalAuthenticator {
+    /// The canonical encoding of the external public key of the participant with
+    /// the given `index`, or [`None`] if it is unknown.
+    fn external_key(&self, index: u32) -> Option<Vec<u8>>;
+
+    /// Whether `signature` is a valid signature of `message` under the external key
+    /// of the participant with the given `index`.
+    fn verify(&self, index: u32, message: &[u8], signature: &[u8]) -> bool;
+}
+
+/// A message of a DKG session which can be authenticated with the external key of
+/// its sender.
+pub trait AuthenticatedPayload: CanonicalSerialize {
+    /// The label separating the signed messages of this type from those of other types.
+    const LABEL: &'static [u8];
+
+    /// The round of the session messages of this type are sent in.
+    const ROUND: ProtocolRound;
+
+    /// The index of the sender of this message.
+    fn sender_index(&self) -> u32;
+}
+
+impl<C: CipherSuite, R: Role<C>> AuthenticatedPayload for Participant<C, R> {
+    const LABEL: &'static [u8] = b"participant";
+    const ROUND: ProtocolRound = ProtocolRound::DkgRound1;
+
+    fn sender_index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl<C: CipherSuite> AuthenticatedPayload for EncryptedSecretShare<C> {
+    const LABEL: &'static [u8] = b"encrypted secret share";
+    const ROUND: ProtocolRound = ProtocolRound::DkgRound2;
+
+    fn sender_index(&self) -> u32 {
+        self.sender_index
+    }
+}
+
+impl<C: CipherSuite> AuthenticatedPayload for ShareBundle<C> {
+    const LABEL: &'static [u8] = b"share bundle";
+    const ROUND: ProtocolRound = ProtocolRound::DkgRound2;
+
+    fn sender_index(&self) -> u32 {
+        self.sender_index
+    }
+}
+
+impl<C: CipherSuite> AuthenticatedPayload for Complaint<C> {
+    const LABEL: &'static [u8] = b"complaint";
+    const ROUND: ProtocolRound = ProtocolRound::Complaint;
+
+    fn sender_index(&self) -> u32 {
+        self.maker_index
+    }
+}
+
+impl<C: CipherSuite> AuthenticatedPayload for ShareReceipt<C> {
+    const LABEL: &'static [u8] = b"share receipt";
+    const ROUND: ProtocolRound = ProtocolRound::DkgRound2;
+
+    fn sender_index(&self) -> u32 {
+        self.recipient_index
+    }
+}
+
+/// A message of a DKG session, along with the signature of its sender made with
+/// its external key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthenticatedMessage<T: AuthenticatedPayload> {
+    /// The round, session and epoch the message was sent for.
+    pub tag: RoundTag,
+    /// The message.
+    pub payload: T,
+    /// The signature of the [`AuthenticatedSession::signing_message`] of the message.
+    pub signature: Vec<u8>,
+}
+
+impl<T: AuthenticatedPayload> AuthenticatedMessage<T> {
+    /// Attach the `signature` of its sender to the given `payload`, sent for the round,
+    /// session and epoch of the given `tag`.
+    pub fn new(tag: RoundTag, payload: T, signature: Vec<u8>) -> Self {
+        Self {
+            tag,
+            payload,
+            signature,
+        }
+    }
+}
+
+/// A DKG session between senders authenticated by their external keys.
+#[derive(Clone, Debug)]
+pub struct AuthenticatedSession<C: CipherSuite, A: ExternalAuthenticator> {
+    /// The verifier of the signatures of the senders.
+    authenticator: A,
+    /// The identifier of the session.
+    session_id: Vec<u8>,
+    /// The epoch of the key material of the session.
+    epoch: u64,
+    /// The hash of the session, committing to the external keys of its senders.
+    session_hash: Vec<u8>,
+    /// The external keys of the senders, by index.
+    external_keys: BTreeMap<u32, Vec<u8>>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite, A: ExternalAuthenticator> AuthenticatedSession<C, A> {
+    /// Start an authenticated session with the given `session_id`, for instance a block
+    /// height, between the senders with the given `indices` and `parameters`.
+    ///
+    /// For a resharing session, the `indices` are the ones of the dealers. The signers,
+    /// whose indices may overlap with the dealers', authenticate their own messages
+    /// of the first round within a distinct session.
+    ///
+    /// The session starts at epoch 0, which can be changed with
+    /// [`AuthenticatedSession::with_epoch`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`AuthenticatedSession`], otherwise
+    /// an [`Error::InvalidExternalAuthentication`] listing the senders whose external
+    /// key is unknown to the `authenticator`.
+    pub fn new(
+        parameters: &ThresholdParameters<C>,
+        session_id: &[u8],
+        indices: &[u32],
+        authenticator: A,
+    ) -> FrostResult<C, Self> {
+        let mut external_keys = BTreeMap::new();
+        let mut unknown_senders = Vec::new();
+        for &index in indices.iter() {
+            match authenticator.external_key(index) {
+                Some(key) => {
+                    external_keys.insert(index, key);
+                }
+                None => unknown_senders.push(index),
+            }
+        }
+        if !unknown_senders.is_empty() {
+            return Err(Error::InvalidExternalAuthentication(unknown_senders));
+        }
+
+        let mut bytes = Vec::new();
+        (session_id, parameters.n, parameters.t)
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        for (index, key) in external_keys.iter() {
+            bytes.extend_from_slice(&index.to_le_bytes());
+            key.serialize_compressed(&mut bytes)
+                .map_err(|e| Error::SerializationError(e.into()))?;
+        }
+        let session_hash = hash_to_array::<C>(
+            (C::context_string() + "authenticated session").as_bytes(),
+            &bytes,
+        )?
+        .as_ref()
+        .to_vec();
+
+        Ok(Self {
+            authenticator,
+            session_id: session_id.to_vec(),
+            epoch: 0,
+            session_hash,
+            external_keys,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The hash of this session, committing to its parameters and to the external
+    /// keys of its senders.
+    pub fn session_hash(&self) -> &[u8] {
+        &self.session_hash
+    }
+
+    /// Set the epoch of the key material of this session, e.g. the number of times
+    /// the group key was reshared.
+    pub fn with_epoch(mut self, epoch: u64) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// The [`RoundTag`] of the messages of type `T` within this session.
+    pub fn round_tag<T: AuthenticatedPayload>(&self) -> RoundTag {
+        RoundTag::new(T::ROUND, &self.session_id, self.epoch)
+    }
+
+    /// Attach the `signature` of its sender to the given `payload`, tagged with the
+    /// round of this session it is sent in.
+    pub fn authenticated_message<T: AuthenticatedPayload>(
+        &self,
+        payload: T,
+        signature: Vec<u8>,
+    ) -> AuthenticatedMessage<T> {
+        AuthenticatedMessage::new(self.round_tag::<T>(), payload, signature)
+    }
+
+    /// Compute the message to be signed by the sender of `payload` with its external key.
+    pub fn signing_message<T: AuthenticatedPayload>(&self, payload: &T) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = self.session_hash.clone();
+        bytes.extend_from_slice(&self.round_tag::<T>().to_bytes());
+        bytes.extend_from_slice(T::LABEL);
+        bytes.extend_from_slice(&payload.sender_index().to_le_bytes());
+        payload
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(hash_to_array::<C>(
+            (C::context_string() + "authenticated message").as_bytes(),
+            &bytes,
+        )?
+        .as_ref()
+        .to_vec())
+    }
+
+    /// Check that the given `message` was signed by its sender with its external key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the message is authentic,
+    /// otherwise an [`Error::UnexpectedRoundTag`] if it was sent for another round,
+    /// session or epoch, or an [`Error::InvalidExternalAuthentication`] with the index
+    /// of its sender if its signature is invalid.
+    pub fn verify<T: AuthenticatedPayload>(
+        &self,
+        message: &AuthenticatedMessage<T>,
+    ) -> FrostResult<C, ()> {
+        self.round_tag::<T>().check(&message.tag)?;
+
+        let index = message.payload.sender_index();
+        let is_authentic = self.external_keys.contains_key(&index)
+            && self.authenticator.verify(
+                index,
+                &self.signing_message(&message.payload)?,
+                &message.signature,
+            );
+
+        match is_authentic {
+            true => Ok(()),
+            false => Err(Error::InvalidExternalAuthentication(vec![index])),
+        }
+    }
+
+    /// Split the authentic `messages` of the first round from the indices of the
+    /// senders of the other ones.
+    fn authenticate_participants<R: Role<C>>(
+        &self,
+        messages: &[AuthenticatedMessage<Participant<C, R>>],
+    ) -> FrostResult<C, (Vec<Participant<C, R>>, Vec<u32>)> {
+        let mut unauthenticated = Vec::new();
+        for message in messages.iter() {
+            match self.verify(message) {
+                Ok(()) => {}
+                Err(Error::InvalidExternalAuthentication(_))
+                | Err(Error::UnexpectedRoundTag(_, _)) => {
+                    unauthenticated.push(message.payload.index)
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        let participants = messages
+            .iter()
+            .map(|message| message.payload.clone())
+            .collect();
+
+        Ok((participants, unauthenticated))
+    }
+}
+
+impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
+    /// Bootstrap an ICE-FROST instance as with [`DistributedKeyGeneration::bootstrap`],
+    /// from the `participants` authenticated within the given `session`.
+    ///
+    /// Participants whose message fails to authenticate are considered misbehaving,
+    /// and no secret share is encrypted for them.
+    pub fn bootstrap_authenticated<R: Role<C>, A: ExternalAuthenticator>(
+        parameters: &ThresholdParameters<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        my_index: &u32,
+        my_coefficients: &Coefficients<C>,
+        participants: &[AuthenticatedMessage<Participant<C, R>>],
+        session: &AuthenticatedSession<C, A>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
+        let (participants, unauthenticated) = session.authenticate_participants(participants)?;
+        let (state, participant_list) = Self::bootstrap(
+            parameters,
+            dh_private_key,
+            my_index,
+            my_coefficients,
+            &participants,
+            rng,
+        )?;
+
+        state.exclude_participants(participant_list, &unauthenticated)
+    }
+
+    /// Initiate a new DKG session as with [`DistributedKeyGeneration::new`], from the
+    /// `dealers` authenticated within the given `session`.
+    ///
+    /// Dealers whose message fails to authenticate are considered misbehaving.
+    pub fn new_authenticated<R: Role<C>, A: ExternalAuthenticator>(
+        parameters: &ThresholdParameters<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        my_index: &u32,
+        dealers: &[AuthenticatedMessage<Participant<C, R>>],
+        session: &AuthenticatedSession<C, A>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
+        let (dealers, unauthenticated) = session.authenticate_participants(dealers)?;
+        let (state, participant_list) =
+            Self::new(parameters, dh_private_key, my_index, &dealers, rng)?;
+
+        state.exclude_participants(participant_list, &unauthenticated)
+    }
+}
+
+impl<C: CipherSuite> RoundTwoProcessor<C> {
+    /// Decrypt and verify an encrypted secret share addressed to this participant
+    /// as with [`RoundTwoProcessor::process_share`], once its `message` has been
+    /// authenticated within the given `session`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is as with [`RoundTwoProcessor::process_share`],
+    /// otherwise an error as with [`AuthenticatedSession::verify`] if the share is not
+    /// authentic, in which case it is not processed.
+    pub fn process_authenticated_share<A: ExternalAuthenticator>(
+        &mut self,
+        message: &AuthenticatedMessage<EncryptedSecretShare<C>>,
+        session: &AuthenticatedSession<C, A>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Option<Complaint<C>>> {
+        session.verify(message)?;
+
+        self.process_share(&message.payload, rng)
+    }
+
+    /// Decrypt and verify the encrypted secret share addressed to this participant
+    /// within a [`ShareBundle`] as with [`RoundTwoProcessor::process_bundle`], once its
+    /// `message` has been authenticated within the given `session`.
+    pub fn process_authenticated_bundle<A: ExternalAuthenticator>(
+        &mut self,
+        message: &AuthenticatedMessage<ShareBundle<C>>,
+        session: &AuthenticatedSession<C, A>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Option<Complaint<C>>> {
+        session.verify(message)?;
+
+        self.process_bundle(&message.payload, rng)
+    }
+}
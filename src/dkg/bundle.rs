@@ -0,0 +1,137 @@
+//! The bundle module for addressing the encrypted secret shares of a dealer to
+//! their recipients during an ICE-FROST Distributed Key Generation session.
+//!
+//! A dealer produces one [`EncryptedSecretShare`] per recipient, which callers must
+//! route to the right participant. A [`ShareBundle`] keys them by recipient index,
+//! along with the hash of the Diffie-Hellman public key of their recipient, so that
+//! each recipient retrieves its own share with [`ShareBundle::for_recipient`], and a
+//! share routed to the wrong participant is detected on reception, instead of being
+//! blamed on its dealer.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::DiffieHellmanPublicKey;
+use crate::utils::{hash_to_array, BTreeMap, Vec};
+use crate::{Error, FrostResult};
+
+use super::participant::Participant;
+use super::role::Role;
+use super::secret_share::EncryptedSecretShare;
+
+/// The encrypted secret shares of a dealer, keyed by the index of their recipient.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ShareBundle<C: CipherSuite> {
+    /// The index of the dealer of the shares.
+    pub(crate) sender_index: u32,
+    /// The encrypted shares, along with the hash of the Diffie-Hellman public key of
+    /// their recipient.
+    pub(crate) shares: BTreeMap<u32, (Vec<u8>, EncryptedSecretShare<C>)>,
+}
+
+impl<C: CipherSuite> ShareBundle<C> {
+    /// Bundle the `encrypted_shares` of the dealer with index `sender_index`, addressed
+    /// to the given `recipients`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ShareBundle`], otherwise an
+    /// [`Error::MisroutedShare`] if a share was not made by this dealer, or is not
+    /// addressed to any of the `recipients`.
+    pub fn new<R: Role<C>>(
+        sender_index: u32,
+        encrypted_shares: &[EncryptedSecretShare<C>],
+        recipients: &[Participant<C, R>],
+    ) -> FrostResult<C, Self> {
+        let mut shares = BTreeMap::new();
+        for share in encrypted_shares.iter() {
+            let recipient = recipients
+                .iter()
+                .find(|recipient| recipient.index == share.receiver_index)
+                .filter(|_| share.sender_index == sender_index)
+                .ok_or(Error::MisroutedShare(
+                    share.sender_index,
+                    share.receiver_index,
+                ))?;
+
+            shares.insert(
+                share.receiver_index,
+                (recipient_key_hash(&recipient.dh_public_key)?, share.clone()),
+            );
+        }
+
+        Ok(Self {
+            sender_index,
+            shares,
+        })
+    }
+
+    /// The index of the dealer of the shares of this [`ShareBundle`].
+    pub fn sender_index(&self) -> u32 {
+        self.sender_index
+    }
+
+    /// The sorted indices of the recipients of the shares of this [`ShareBundle`].
+    pub fn recipients(&self) -> Vec<u32> {
+        self.shares.keys().copied().collect()
+    }
+
+    /// The encrypted secret share addressed to the recipient with the given `index`, if any.
+    pub fn for_recipient(&self, index: u32) -> Option<&EncryptedSecretShare<C>> {
+        self.shares.get(&index).map(|(_, share)| share)
+    }
+
+    /// Retrieve the encrypted secret share addressed to the recipient with the given
+    /// `index` and `dh_public_key`, checking that it was routed to it.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the encrypted secret share, otherwise an
+    /// [`Error::MisroutedShare`] if the bundle holds no share for this recipient, or if
+    /// the share was addressed to another participant.
+    pub(crate) fn routed_share(
+        &self,
+        index: u32,
+        dh_public_key: &DiffieHellmanPublicKey<C>,
+    ) -> FrostResult<C, &EncryptedSecretShare<C>> {
+        let (key_hash, share) = self
+            .shares
+            .get(&index)
+            .ok_or(Error::MisroutedShare(self.sender_index, index))?;
+
+        match *key_hash == recipient_key_hash(dh_public_key)?
+            && share.sender_index == self.sender_index
+            && share.receiver_index == index
+        {
+            true => Ok(share),
+            false => Err(Error::MisroutedShare(self.sender_index, index)),
+        }
+    }
+
+    /// Serialize this [`ShareBundle`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`ShareBundle`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+/// Hash the Diffie-Hellman public key of the recipient of a share.
+pub(crate) fn recipient_key_hash<C: CipherSuite>(
+    dh_public_key: &DiffieHellmanPublicKey<C>,
+) -> FrostResult<C, Vec<u8>> {
+    Ok(hash_to_array::<C>(
+        (C::context_string() + "share recipient").as_bytes(),
+        &dh_public_key.to_bytes()?,
+    )?
+    .as_ref()
+    .to_vec())
+}
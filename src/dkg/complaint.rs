@@ -2,19 +2,30 @@
 //! Distributed Key Generation session.
 
 use crate::utils::{Scalar, Vec};
-use crate::{Error, FrostResult};
+use crate::validation::{deserialize_validated, validate_point, validate_points, ValidatePoints};
+use crate::{CipherErrorKind, Element, Error, FrostResult, SerializationErrorKind};
 
 use core::ops::Mul;
 
 use rand::{CryptoRng, RngCore};
-use sha2::Sha256;
 
 use crate::ciphersuite::CipherSuite;
+use crate::keys::{DhKeyProvider, DiffieHellmanPublicKey};
+use crate::share_cipher::{deserialize_nonce, ShareCipher};
 
 use ark_ec::Group;
-use ark_ff::field_hashers::{DefaultFieldHasher, HashToField};
 use ark_ff::UniformRand;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+};
+
+use zeroize::Zeroize;
+
+use super::secret_share::{derive_share_cipher_key, share_session_id, EncryptedSecretShare};
+
+/// The pseudo-round under which the nonces of sealed complaints are derived,
+/// distinct from the rounds of the share distribution.
+const COMPLAINT_DISCLOSURE_ROUND: u32 = 0x4000_0000;
 
 /// A complaint generated when a participant receives an invalid share.
 #[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
@@ -44,27 +55,23 @@ impl<C: CipherSuite> Complaint<C> {
         let a1 = C::G::generator().mul(r);
         let a2 = accused_pk.mul(r);
 
-        let hasher = <DefaultFieldHasher<Sha256, 128> as HashToField<Scalar<C>>>::new(
-            "Complaint Context".as_bytes(),
-        );
-
         let mut message = my_index.to_le_bytes().to_vec();
         message.extend(&accused_index.to_le_bytes());
         dh_pkey
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         accused_pk
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         dh_shared_key
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         a1.serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         a2.serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
 
-        let h: Scalar<C> = hasher.hash_to_field(&message[..], 1)[0];
+        let h = C::hash_to_field(C::complaint_domain_tag().as_bytes(), &message[..])?;
         Ok(Self {
             maker_index: my_index,
             accused_index,
@@ -80,30 +87,27 @@ impl<C: CipherSuite> Complaint<C> {
     /// A complaint is valid if:
     /// --  a1 + h.pk_i = z.g
     /// --  a2 + h.k_il = z.pk_l
-    pub fn verify(&self, pk_i: &C::G, pk_l: &C::G) -> FrostResult<C, ()> {
-        let hasher = <DefaultFieldHasher<Sha256, 128> as HashToField<Scalar<C>>>::new(
-            "Complaint Context".as_bytes(),
-        );
-
+    pub fn verify(&self, pk_i: &Element<C>, pk_l: &Element<C>) -> FrostResult<C, ()> {
+        let (pk_i, pk_l) = (&pk_i.0, &pk_l.0);
         let mut message = self.maker_index.to_le_bytes().to_vec();
         message.extend(&self.accused_index.to_le_bytes());
         pk_i.serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         pk_l.serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         self.dh_shared_key
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         self.proof
             .a1
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         self.proof
             .a2
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
 
-        let h: Scalar<C> = hasher.hash_to_field(&message[..], 1)[0];
+        let h = C::hash_to_field(C::complaint_domain_tag().as_bytes(), &message[..])?;
 
         if self.proof.a1 + pk_i.mul(h) != C::G::generator() * self.proof.z {
             return Err(Error::ComplaintVerificationError);
@@ -116,19 +120,75 @@ impl<C: CipherSuite> Complaint<C> {
         Ok(())
     }
 
+    /// Seal this [`Complaint`] for the adjudicator with the given `adjudicator_public_key`.
+    ///
+    /// A [`Complaint`] discloses the Diffie-Hellman shared secret of the complaint maker and
+    /// the accused participant, which allows anyone to decrypt the share sent by the latter
+    /// to the former. Sealing it instead encrypts the whole complaint, along with its evidence,
+    /// to a designated adjudicator, with an ephemeral Diffie-Hellman key and this
+    /// [`CipherSuite`]'s [`ShareCipher`]. The adjudicator recovers the complaint with
+    /// [`SealedComplaint::open`], and can then determine the misbehaving party as usual.
+    pub fn seal(
+        &self,
+        adjudicator_public_key: &DiffieHellmanPublicKey<C>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, SealedComplaint<C>> {
+        let mut ephemeral_secret = Scalar::<C>::rand(&mut rng);
+        let ephemeral_key =
+            DiffieHellmanPublicKey::new(Element(C::G::generator() * ephemeral_secret));
+        let shared_key = adjudicator_public_key.key * ephemeral_secret;
+        ephemeral_secret.zeroize();
+
+        let session_id = share_session_id(&ephemeral_key, adjudicator_public_key)?;
+        let nonce = EncryptedSecretShare::<C>::derive_nonce(
+            &session_id,
+            &[],
+            self.maker_index,
+            self.accused_index,
+            COMPLAINT_DISCLOSURE_ROUND,
+        )?;
+
+        let mut shared_key_bytes = Vec::new();
+        shared_key
+            .serialize_compressed(&mut shared_key_bytes)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        let mut final_key = derive_share_cipher_key::<C>(&shared_key_bytes)?;
+        shared_key_bytes.zeroize();
+
+        let mut complaint_bytes = self.to_bytes()?;
+        let ciphertext = C::ShareCipher::encrypt::<C>(&final_key, &nonce, &complaint_bytes);
+        final_key.zeroize();
+        complaint_bytes.zeroize();
+
+        Ok(SealedComplaint {
+            maker_index: self.maker_index,
+            accused_index: self.accused_index,
+            ephemeral_key,
+            nonce,
+            ciphertext: ciphertext?,
+        })
+    }
+
     /// Serialize this [`Complaint`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`Complaint`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for Complaint<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.dh_shared_key)?;
+        self.proof.validate_points()
     }
 }
 
@@ -149,13 +209,139 @@ impl<C: CipherSuite> ComplaintProof<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`ComplaintProof`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for ComplaintProof<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>([&self.a1, &self.a2])
+    }
+}
+
+/// A [`Complaint`] encrypted to a designated adjudicator, which hides the disclosed
+/// Diffie-Hellman shared secret, and hence the disputed share, from other parties.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize)]
+pub struct SealedComplaint<C: CipherSuite> {
+    /// The index of the complaint maker.
+    pub maker_index: u32,
+    /// The index of the alleged misbehaving participant.
+    pub accused_index: u32,
+    /// The ephemeral Diffie-Hellman public key of the complaint maker.
+    pub ephemeral_key: DiffieHellmanPublicKey<C>,
+    /// The nonce used for encryption with this [`CipherSuite`]'s [`ShareCipher`].
+    pub nonce: <C::ShareCipher as ShareCipher>::Nonce,
+    /// The encrypted [`Complaint`].
+    pub(crate) ciphertext: Vec<u8>,
+}
+
+impl<C: CipherSuite> Valid for SealedComplaint<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.ephemeral_key.check()
+    }
+}
+
+impl<C: CipherSuite> CanonicalDeserialize for SealedComplaint<C> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let complaint = Self {
+            maker_index: u32::deserialize_with_mode(&mut reader, compress, validate)?,
+            accused_index: u32::deserialize_with_mode(&mut reader, compress, validate)?,
+            ephemeral_key: DiffieHellmanPublicKey::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            nonce: deserialize_nonce::<C::ShareCipher, _>(&mut reader, compress, validate)?,
+            ciphertext: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+
+        if let Validate::Yes = validate {
+            complaint.check()?;
+        }
+
+        Ok(complaint)
+    }
+}
+
+impl<C: CipherSuite> SealedComplaint<C> {
+    /// Decrypt this [`SealedComplaint`] with the Diffie-Hellman key held by the
+    /// adjudicator's `dh_key_provider`, which may be backed by an external device.
+    ///
+    /// The recovered [`Complaint`] still has to be checked, for instance with
+    /// [`DistributedKeyGeneration::blame`](super::DistributedKeyGeneration::blame).
+    pub fn open(&self, dh_key_provider: &impl DhKeyProvider<C>) -> FrostResult<C, Complaint<C>> {
+        let session_id = share_session_id(&self.ephemeral_key, &dh_key_provider.dh_public_key()?)?;
+        let expected_nonce = EncryptedSecretShare::<C>::derive_nonce(
+            &session_id,
+            &[],
+            self.maker_index,
+            self.accused_index,
+            COMPLAINT_DISCLOSURE_ROUND,
+        )?;
+        if self.nonce != expected_nonce {
+            return Err(Error::InvalidNonce);
+        }
+
+        let shared_key = dh_key_provider.dh_shared_key(&self.ephemeral_key)?;
+        let mut shared_key_bytes = Vec::new();
+        shared_key
+            .serialize_compressed(&mut shared_key_bytes)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        let mut final_key = derive_share_cipher_key::<C>(&shared_key_bytes)?;
+        shared_key_bytes.zeroize();
+
+        let bytes = C::ShareCipher::decrypt::<C>(&final_key, &self.nonce, &self.ciphertext);
+        final_key.zeroize();
+        let mut bytes = bytes?;
+
+        let complaint = Complaint::from_bytes(&bytes);
+        bytes.zeroize();
+        let complaint = complaint.map_err(|error| {
+            let kind = match error {
+                Error::DeserializationError(kind) => kind,
+                _ => SerializationErrorKind::InvalidData,
+            };
+            Error::DecryptionError(CipherErrorKind::MalformedPlaintext(kind))
+        })?;
+
+        if complaint.maker_index != self.maker_index
+            || complaint.accused_index != self.accused_index
+        {
+            return Err(Error::DecryptionError(CipherErrorKind::UnexpectedPlaintext));
+        }
+
+        Ok(complaint)
+    }
+
+    /// Serialize this [`SealedComplaint`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`SealedComplaint`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for SealedComplaint<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.ephemeral_key.validate_points()
     }
 }
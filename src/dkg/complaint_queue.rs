@@ -0,0 +1,293 @@
+//! The complaint queue module, buffering the complaints broadcast during hostile
+//! ICE-FROST Distributed Key Generation sessions before their adjudication.
+//!
+//! A malicious participant can flood a coordinator with complaints. A
+//! [`ComplaintQueue`], obtained from a [`QualificationTracker`], only admits complaints
+//! whose proof verifies against the Diffie-Hellman public keys of their maker and of
+//! the accused dealer, keeps a single complaint per maker, accused dealer and round,
+//! and bounds both the number of queued complaints and the number of complaints per
+//! maker, as given by its [`ComplaintQueueLimits`].
+//!
+//! Complaints are processed by increasing round, maker index and accused index, and
+//! when a bound is reached, the complaint coming last in this order is dropped. Among
+//! several valid complaints for the same key, the one with the smallest encoding is
+//! kept. The queued complaints, and hence the verdicts of the
+//! [`QualificationTracker`], therefore do not depend on the order in which
+//! complaints were received.
+
+use ark_serialize::CanonicalSerialize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::DiffieHellmanPublicKey;
+use crate::utils::{BTreeMap, Vec};
+use crate::{Element, Error, FrostResult};
+
+use super::complaint::Complaint;
+use super::qualification::QualificationTracker;
+use super::secret_share::EncryptedSecretShare;
+
+/// The bounds on the complaints held by a [`ComplaintQueue`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ComplaintQueueLimits {
+    /// The maximum number of queued complaints.
+    pub max_queued: usize,
+    /// The maximum number of queued complaints made by the same participant.
+    pub max_per_maker: usize,
+}
+
+impl Default for ComplaintQueueLimits {
+    /// At most 1024 queued complaints, and 32 per maker.
+    fn default() -> Self {
+        Self {
+            max_queued: 1024,
+            max_per_maker: 32,
+        }
+    }
+}
+
+impl ComplaintQueueLimits {
+    /// Create new [`ComplaintQueueLimits`].
+    pub const fn new(max_queued: usize, max_per_maker: usize) -> Self {
+        Self {
+            max_queued,
+            max_per_maker,
+        }
+    }
+}
+
+/// The outcome of pushing a complaint to a [`ComplaintQueue`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComplaintAdmission {
+    /// The complaint was queued.
+    Queued,
+    /// The complaint replaced a queued complaint with the same key and a larger encoding.
+    Replaced,
+    /// A complaint with the same key and a smaller or equal encoding is already queued.
+    Duplicate,
+    /// The complaint was dropped, as a bound of the queue was reached by complaints
+    /// coming earlier in the processing order.
+    Dropped,
+}
+
+/// The counters of a [`ComplaintQueue`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ComplaintQueueMetrics {
+    /// The number of complaints pushed to the queue.
+    pub received: usize,
+    /// The number of complaints rejected as misrouted or unverifiable.
+    pub rejected: usize,
+    /// The number of complaints ignored or replaced as duplicates of another one.
+    pub duplicates: usize,
+    /// The number of complaints dropped or evicted because of a bound of the queue.
+    pub dropped: usize,
+    /// The number of complaints currently queued.
+    pub queued: usize,
+    /// The number of complaints processed by the [`QualificationTracker`].
+    pub processed: usize,
+}
+
+/// The verdict on a complaint processed by a [`ComplaintQueue`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ComplaintVerdict {
+    /// The round the complaint was made in.
+    pub round: u32,
+    /// The index of the maker of the complaint.
+    pub maker_index: u32,
+    /// The index of the accused dealer.
+    pub accused_index: u32,
+    /// The index of the blamed participant.
+    pub blamed: u32,
+}
+
+/// A queued complaint, along with its encoding and the share it was made against.
+#[derive(Clone, Debug)]
+struct QueuedComplaint<C: CipherSuite> {
+    encoding: Vec<u8>,
+    encrypted_share: EncryptedSecretShare<C>,
+    complaint: Complaint<C>,
+}
+
+/// A bounded and deduplicated queue of the complaints of a DKG session, obtained with
+/// [`QualificationTracker::complaint_queue`].
+#[derive(Clone, Debug)]
+pub struct ComplaintQueue<C: CipherSuite> {
+    limits: ComplaintQueueLimits,
+    their_dh_public_keys: Vec<(u32, DiffieHellmanPublicKey<C>)>,
+    /// The queued complaints, by round, maker index and accused index.
+    complaints: BTreeMap<(u32, u32, u32), QueuedComplaint<C>>,
+    metrics: ComplaintQueueMetrics,
+}
+
+impl<C: CipherSuite> QualificationTracker<C> {
+    /// Start queueing the complaints of this session within the given `limits`, to be
+    /// adjudicated by this tracker with [`ComplaintQueue::process`].
+    pub fn complaint_queue(&self, limits: ComplaintQueueLimits) -> ComplaintQueue<C> {
+        ComplaintQueue {
+            limits,
+            their_dh_public_keys: self.their_dh_public_keys.clone(),
+            complaints: BTreeMap::new(),
+            metrics: ComplaintQueueMetrics::default(),
+        }
+    }
+}
+
+impl<C: CipherSuite> ComplaintQueue<C> {
+    /// Push a `complaint` broadcast during the given `round`, along with the
+    /// `encrypted_share` it was made against.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ComplaintAdmission`] of the
+    /// complaint, otherwise an [`Error::MisroutedShare`] if the `encrypted_share` was not
+    /// sent by the accused dealer to the maker of the complaint, or an
+    /// [`Error::ComplaintVerificationError`] if the proof of the complaint is invalid.
+    pub fn push(
+        &mut self,
+        round: u32,
+        encrypted_share: &EncryptedSecretShare<C>,
+        complaint: &Complaint<C>,
+    ) -> FrostResult<C, ComplaintAdmission> {
+        self.metrics.received += 1;
+        if let Err(error) = self.check(encrypted_share, complaint) {
+            self.metrics.rejected += 1;
+            return Err(error);
+        }
+
+        let mut encoding = Vec::new();
+        encrypted_share
+            .serialize_compressed(&mut encoding)
+            .and_then(|_| complaint.serialize_compressed(&mut encoding))
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        let key = (round, complaint.maker_index, complaint.accused_index);
+
+        if let Some(queued) = self.complaints.get_mut(&key) {
+            self.metrics.duplicates += 1;
+            if encoding >= queued.encoding {
+                return Ok(ComplaintAdmission::Duplicate);
+            }
+            *queued = QueuedComplaint {
+                encoding,
+                encrypted_share: encrypted_share.clone(),
+                complaint: complaint.clone(),
+            };
+            return Ok(ComplaintAdmission::Replaced);
+        }
+
+        // The last complaint of the maker, and the last complaint of the queue, are
+        // evicted in favor of a complaint coming earlier in the processing order.
+        let maker_keys: Vec<&(u32, u32, u32)> = self
+            .complaints
+            .keys()
+            .filter(|(_, maker_index, _)| *maker_index == complaint.maker_index)
+            .collect();
+        if maker_keys.len() >= self.limits.max_per_maker {
+            match maker_keys.last() {
+                Some(&&last) if key < last => self.evict(&last),
+                _ => return Ok(self.drop_complaint()),
+            }
+        }
+        if self.complaints.len() >= self.limits.max_queued {
+            match self.complaints.keys().next_back() {
+                Some(&last) if key < last => self.evict(&last),
+                _ => return Ok(self.drop_complaint()),
+            }
+        }
+
+        self.complaints.insert(
+            key,
+            QueuedComplaint {
+                encoding,
+                encrypted_share: encrypted_share.clone(),
+                complaint: complaint.clone(),
+            },
+        );
+        self.metrics.queued = self.complaints.len();
+
+        Ok(ComplaintAdmission::Queued)
+    }
+
+    /// Adjudicate all the queued complaints with the given `tracker`, by increasing
+    /// round, maker index and accused index, emptying this queue.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ComplaintVerdict`] of each
+    /// complaint in processing order, otherwise an [`Error`] as with
+    /// [`QualificationTracker::record_complaint`].
+    pub fn process(
+        &mut self,
+        tracker: &mut QualificationTracker<C>,
+    ) -> FrostResult<C, Vec<ComplaintVerdict>> {
+        let complaints = core::mem::take(&mut self.complaints);
+        self.metrics.queued = 0;
+
+        let mut verdicts = Vec::with_capacity(complaints.len());
+        for ((round, maker_index, accused_index), queued) in complaints.into_iter() {
+            let blamed = tracker.record_complaint(&queued.encrypted_share, &queued.complaint)?;
+            self.metrics.processed += 1;
+            verdicts.push(ComplaintVerdict {
+                round,
+                maker_index,
+                accused_index,
+                blamed,
+            });
+        }
+
+        Ok(verdicts)
+    }
+
+    /// The number of queued complaints.
+    pub fn len(&self) -> usize {
+        self.complaints.len()
+    }
+
+    /// Whether no complaint is queued.
+    pub fn is_empty(&self) -> bool {
+        self.complaints.is_empty()
+    }
+
+    /// The counters of this queue.
+    pub fn metrics(&self) -> ComplaintQueueMetrics {
+        self.metrics
+    }
+
+    /// Check that a `complaint` is made against the share of the accused dealer to its
+    /// maker, and that its proof is valid.
+    fn check(
+        &self,
+        encrypted_share: &EncryptedSecretShare<C>,
+        complaint: &Complaint<C>,
+    ) -> FrostResult<C, ()> {
+        if encrypted_share.sender_index != complaint.accused_index
+            || encrypted_share.receiver_index != complaint.maker_index
+        {
+            return Err(Error::MisroutedShare(
+                encrypted_share.sender_index,
+                encrypted_share.receiver_index,
+            ));
+        }
+
+        let dh_public_key = |index: u32| {
+            self.their_dh_public_keys
+                .iter()
+                .find(|(i, _)| *i == index)
+                .map(|(_, key)| Element(key.key))
+                .ok_or(Error::ComplaintVerificationError)
+        };
+
+        complaint.verify(
+            &dh_public_key(complaint.maker_index)?,
+            &dh_public_key(complaint.accused_index)?,
+        )
+    }
+
+    fn evict(&mut self, key: &(u32, u32, u32)) {
+        self.complaints.remove(key);
+        self.metrics.dropped += 1;
+    }
+
+    fn drop_complaint(&mut self) -> ComplaintAdmission {
+        self.metrics.dropped += 1;
+        ComplaintAdmission::Dropped
+    }
+}
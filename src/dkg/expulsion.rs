@@ -0,0 +1,255 @@
+//! The expulsion module for removing a single participant from an existing group.
+//!
+//! Expelling the participant of index \\( e \\) from a group of \\( n \\) participants
+//! with threshold \\( t \\) is a resharing session, in which the \\( n - 1 \\) remaining
+//! members act as dealers of their current secret shares, and as signers of a new
+//! \\( t \\)-out-of-\\( (n - 1) \\) setting. The remaining members are renumbered
+//! from \\( 1 \\) to \\( n - 1 \\) in the order of their current indices, and the group
+//! key is left unchanged.
+//!
+//! A [`ParticipantExpulsion`] drives this session:
+//!
+//! 1. Each remaining member generates its signer of the new epoch with
+//!    [`ParticipantExpulsion::new_signer`], and broadcasts it.
+//! 2. Each remaining member reshares its secret share to these signers with
+//!    [`ParticipantExpulsion::reshare`], and broadcasts its dealer along with
+//!    the encrypted secret shares.
+//! 3. Each remaining member starts its distributed key generation of the new epoch
+//!    with [`ParticipantExpulsion::start`], which checks that every dealer reshared
+//!    the secret share matching its public verification share, and completes it
+//!    as a regular resharing.
+//!
+//! The [`PublicKeyPackage`] of the new epoch can be computed by anyone from the
+//! broadcast dealers with [`ParticipantExpulsion::new_package`], and the group can
+//! then attest the expulsion to external registries with a
+//! [`RevocationRecord`](crate::epoch::RevocationRecord).
+//!
+//! Note that the expelled participant keeps its former secret share, which remains
+//! a valid share of the group key for the previous epoch: the remaining members
+//! must erase their own former secret shares once the new epoch is active, so that
+//! the expelled participant cannot collude with them afterwards.
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{
+    DiffieHellmanPrivateKey, IndividualSigningKey, IndividualVerifyingKey, PublicKeyPackage,
+};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::key_generation::{DKGParticipantList, DistributedKeyGeneration};
+use super::participant::Participant;
+use super::role::{DealerRole, SignerRole};
+use super::round_types::RoundOne;
+use super::secret_share::EncryptedSecretShare;
+
+/// The public description of the expulsion of a participant from an existing group,
+/// shared by the remaining members and by any observer of the resharing session.
+#[derive(Clone, Debug)]
+pub struct ParticipantExpulsion<C: CipherSuite> {
+    /// The public key material of the group before the expulsion.
+    public_key_package: PublicKeyPackage<C>,
+    /// The index of the expelled participant.
+    expelled_index: u32,
+    /// The current indices of the remaining members, sorted.
+    remaining_indices: Vec<u32>,
+    /// The parameters of the group after the expulsion.
+    parameters: ThresholdParameters<C>,
+}
+
+impl<C: CipherSuite> ParticipantExpulsion<C> {
+    /// Describe the expulsion of the participant with the given `expelled_index`
+    /// from the group with the given `public_key_package`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ParticipantExpulsion`], otherwise
+    /// an [`Error`] if the participant is not a member of the group, or an
+    /// [`Error::UnsupportedParameters`] if the remaining members are fewer than the threshold.
+    pub fn new(
+        public_key_package: &PublicKeyPackage<C>,
+        expelled_index: u32,
+    ) -> FrostResult<C, Self> {
+        if public_key_package.verifying_key(expelled_index).is_none() {
+            return Err(Error::Custom(
+                "The expelled participant is not a member of the group".to_string(),
+            ));
+        }
+
+        let mut remaining_indices: Vec<u32> = public_key_package
+            .verifying_keys()
+            .iter()
+            .map(|verifying_key| verifying_key.index)
+            .filter(|&index| index != expelled_index)
+            .collect();
+        remaining_indices.sort_unstable();
+        remaining_indices.dedup();
+
+        let parameters = ThresholdParameters::new(
+            remaining_indices.len() as u32,
+            public_key_package.parameters().t,
+        )?;
+
+        Ok(Self {
+            public_key_package: public_key_package.clone(),
+            expelled_index,
+            remaining_indices,
+            parameters,
+        })
+    }
+
+    /// The index of the expelled participant.
+    pub fn expelled_index(&self) -> u32 {
+        self.expelled_index
+    }
+
+    /// The [`IndividualVerifyingKey`] of the expelled participant.
+    pub fn expelled_verifying_key(&self) -> FrostResult<C, &IndividualVerifyingKey<C>> {
+        self.public_key_package
+            .verifying_key(self.expelled_index)
+            .ok_or(Error::MissingShares)
+    }
+
+    /// The current indices of the remaining members, sorted.
+    pub fn remaining_indices(&self) -> &[u32] {
+        &self.remaining_indices
+    }
+
+    /// The [`ThresholdParameters`] of the group after the expulsion, used both
+    /// by the remaining members as dealers and as signers of the new epoch.
+    pub fn parameters(&self) -> &ThresholdParameters<C> {
+        &self.parameters
+    }
+
+    /// The index in the new epoch of the remaining member with the given current `index`,
+    /// or [`None`] if it is not a remaining member.
+    pub fn new_index(&self, index: u32) -> Option<u32> {
+        self.remaining_indices
+            .binary_search(&index)
+            .ok()
+            .map(|position| position as u32 + 1)
+    }
+
+    /// Generate the signer of the new epoch of the remaining member with the given
+    /// current `index`, along with its Diffie-Hellman private key.
+    pub fn new_signer(
+        &self,
+        index: u32,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Participant<C, SignerRole>, DiffieHellmanPrivateKey<C>)> {
+        let new_index = self.new_index(index).ok_or_else(|| {
+            Error::Custom("The participant is not a remaining member of the group".to_string())
+        })?;
+
+        Participant::new_signer(&self.parameters, new_index, rng)
+    }
+
+    /// Reshare the `secret_key` of a remaining member to the `signers` of the new epoch,
+    /// as with [`Participant::reshare`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the dealer of the remaining member
+    /// and the secret shares encrypted to each signer, otherwise an [`Error`] if the
+    /// `secret_key` does not belong to a remaining member or does not match its
+    /// public verification share.
+    pub fn reshare(
+        &self,
+        secret_key: IndividualSigningKey<C>,
+        signers: &[Participant<C, SignerRole>],
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Participant<C, DealerRole>, Vec<EncryptedSecretShare<C>>)> {
+        if self.new_index(secret_key.index).is_none()
+            || self.public_key_package.verifying_key(secret_key.index)
+                != Some(&secret_key.to_public())
+        {
+            return Err(Error::Custom(
+                "The secret key does not belong to a remaining member of the group".to_string(),
+            ));
+        }
+
+        let (dealer, encrypted_shares, _participant_lists) =
+            Participant::reshare(&self.parameters, secret_key, signers, rng)?;
+
+        Ok((dealer, encrypted_shares))
+    }
+
+    /// Check that the `dealers` are exactly the remaining members, and that each of
+    /// them reshared the secret share matching its public verification share.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the dealers are valid,
+    /// otherwise an [`Error::InvalidNumberOfParticipants`] if some remaining members
+    /// are missing, or an [`Error::MisbehavingParticipants`] listing the invalid dealers.
+    pub fn verify_dealers(&self, dealers: &[Participant<C, DealerRole>]) -> FrostResult<C, ()> {
+        let mut misbehaving_dealers: Vec<u32> = dealers
+            .iter()
+            .filter(|dealer| {
+                self.new_index(dealer.index).is_none()
+                    || dealers.iter().filter(|d| d.index == dealer.index).count() > 1
+                    || dealer.public_key().map(|key| key.0)
+                        != self
+                            .public_key_package
+                            .verifying_key(dealer.index)
+                            .map(|verifying_key| verifying_key.share)
+            })
+            .map(|dealer| dealer.index)
+            .collect();
+        if !misbehaving_dealers.is_empty() {
+            misbehaving_dealers.sort_unstable();
+            misbehaving_dealers.dedup();
+            return Err(Error::MisbehavingParticipants(misbehaving_dealers));
+        }
+
+        if dealers.len() != self.remaining_indices.len() {
+            return Err(Error::InvalidNumberOfParticipants(
+                dealers.len(),
+                self.parameters.n,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Start the distributed key generation of the new epoch of the signer with the
+    /// given Diffie-Hellman private key and new `index`, from the broadcast `dealers`,
+    /// as with [`DistributedKeyGeneration::new`] once the dealers were verified with
+    /// [`ParticipantExpulsion::verify_dealers`].
+    pub fn start(
+        &self,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        index: u32,
+        dealers: &[Participant<C, DealerRole>],
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (DistributedKeyGeneration<RoundOne, C>, DKGParticipantList<C>)> {
+        self.verify_dealers(dealers)?;
+
+        DistributedKeyGeneration::new(&self.parameters, dh_private_key, &index, dealers, rng)
+    }
+
+    /// Compute the [`PublicKeyPackage`] of the new epoch from the broadcast `dealers`,
+    /// once verified with [`ParticipantExpulsion::verify_dealers`].
+    pub fn new_package(
+        &self,
+        dealers: &[Participant<C, DealerRole>],
+    ) -> FrostResult<C, PublicKeyPackage<C>> {
+        self.verify_dealers(dealers)?;
+
+        let commitments: Vec<_> = dealers
+            .iter()
+            .map(|dealer| dealer.commitments().clone())
+            .collect();
+        let indices: Vec<u32> = (1..=self.parameters.n).collect();
+        let verifying_keys =
+            IndividualVerifyingKey::generate_all_from_commitments(&indices, &commitments)?;
+
+        Ok(PublicKeyPackage::new(
+            self.parameters,
+            *self.public_key_package.group_key(),
+            verifying_keys,
+        ))
+    }
+}
@@ -55,13 +55,13 @@
 //! use ice_frost::parameters::ThresholdParameters;
 //! use ice_frost::FrostResult;
 //! use ice_frost::testing::Secp256k1Sha256;
-//! use ice_frost::dkg::Participant;
+//! use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! use rand::rngs::OsRng;
 //!
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
 //! // Set up key shares for a threshold signature scheme which needs at least
 //! // 2-out-of-3 signers.
-//! let params = ThresholdParameters::new(3,2);
+//! let params = ThresholdParameters::new(3,2)?;
 //! let mut rng = OsRng;
 //!
 //! // Alice, Bob, and Carol each generate their secret polynomial coefficients
@@ -85,7 +85,7 @@
 //! //
 //!
 //! // Alice enters round one of the distributed key generation protocol.
-//! let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! let (alice_state, participant_lists) =
 //!     DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(
 //!         &params,
@@ -187,13 +187,13 @@
 //! use ice_frost::parameters::ThresholdParameters;
 //! use ice_frost::FrostResult;
 //! use ice_frost::testing::Secp256k1Sha256;
-//! use ice_frost::dkg::Participant;
+//! use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! use rand::rngs::OsRng;
 //!
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
 //! // Set up key shares for a threshold signature scheme which needs at least
 //! // 2-out-of-3 signers.
-//! let params = ThresholdParameters::new(3,2);
+//! let params = ThresholdParameters::new(3,2)?;
 //! let mut rng = OsRng;
 //!
 //! // Alice, Bob, and Carol each generate their secret polynomial coefficients
@@ -216,7 +216,7 @@
 //! //       the `alice_coefficients`, etc.
 //!
 //! // Alice enters round one of the distributed key generation protocol.
-//! let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! let (alice_state, participant_lists) =
 //!     DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(
 //!         &params,
@@ -298,7 +298,7 @@
 //! assert!(carol_group_key == bob_group_key);
 //!
 //! // Instantiate another configuration of threshold signature.
-//! let new_params = ThresholdParameters::new(4,3);
+//! let new_params = ThresholdParameters::new(4,3)?;
 //!
 //! // Alexis, Barbara, Claire and David each generate their Diffie-Hellman
 //! // private key, as well as a zero-knowledge proof to it.
@@ -344,7 +344,7 @@
 //! // Alice, Bob and Carol compute new secret shares of their long-lived secret signing key,
 //! // encrypted for Alexis, Barbara, Claire and David respectively.
 //!
-//! let signers: Vec<Participant<Secp256k1Sha256>> =
+//! let signers: Vec<Participant<Secp256k1Sha256, SignerRole>> =
 //!     vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //!     Participant::reshare(&new_params, alice_secret_key, &signers, &mut rng)?;
@@ -359,7 +359,7 @@
 //!
 //! // Alexis, Barbara, Claire and Carol instantiate their DKG session with the set of dealers
 //! // who will compute their shares. They don't need to provide any coefficients.
-//! let dealers: Vec<Participant<Secp256k1Sha256>> =
+//! let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> =
 //!     vec!(alice_as_dealer.clone(), bob_as_dealer.clone(), carol_as_dealer.clone());
 //! let (alexis_state, participant_lists) =
 //!     DistributedKeyGeneration::<_, Secp256k1Sha256>::new(
@@ -459,21 +459,25 @@ use zeroize::Zeroize;
 
 use crate::ciphersuite::CipherSuite;
 use crate::dkg::{
+    participant::canonical_order,
     round_types::{DkgState, RoundOne, RoundTwo},
     secret_share::{
-        decrypt_share, encrypt_share, Coefficients, EncryptedSecretShare, SecretShare,
-        VerifiableSecretSharingCommitment,
+        decrypt_share, encrypt_share, serialize_dh_shared_key, share_session_id, Coefficients,
+        EncryptedSecretShare, SecretShare, VerifiableSecretSharingCommitment, SHARE_SALT_LENGTH,
     },
-    Complaint, Participant,
+    Complaint, IndexAssignment, Participant, QualificationPolicy, QualificationTracker, Role,
+    ShareBundle, ShareReceipt,
 };
 use crate::keys::{
-    DiffieHellmanPrivateKey, DiffieHellmanPublicKey, GroupVerifyingKey, IndividualSigningKey,
+    DhKeyProvider, DiffieHellmanPrivateKey, DiffieHellmanPublicKey, GroupVerifyingKey,
+    IndividualSigningKey,
 };
 use crate::parameters::ThresholdParameters;
-use crate::{Error, FrostResult};
+use crate::validation::{deserialize_validated, validate_dh_public_key, ValidatePoints};
+use crate::{Element, Error, FrostResult};
 
-use crate::utils::calculate_lagrange_coefficients;
-use crate::utils::{Box, Scalar, ToString, Vec};
+use crate::utils::{calculate_lagrange_coefficients, try_reserve, try_with_capacity};
+use crate::utils::{vec, BTreeMap, Box, Scalar, ToString, Vec};
 
 /// State machine structures for holding intermediate values during a
 /// distributed key generation protocol run, to prevent misuse.
@@ -484,7 +488,7 @@ pub struct DistributedKeyGeneration<S: DkgState, C: CipherSuite> {
 }
 
 /// Shared state which occurs across all rounds of a threshold signing protocol run.
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 struct ActualState<C: CipherSuite> {
     /// The parameters for this instantiation of a threshold signature.
     parameters: ThresholdParameters<C>,
@@ -509,6 +513,40 @@ struct ActualState<C: CipherSuite> {
     my_secret_shares: Option<Vec<SecretShare<C>>>,
 }
 
+// The secret shares are move-only, hence they need to be explicitly duplicated.
+impl<C: CipherSuite> Clone for ActualState<C> {
+    fn clone(&self) -> Self {
+        Self {
+            parameters: self.parameters,
+            index: self.index,
+            dh_private_key: self.dh_private_key.clone(),
+            dh_public_key: self.dh_public_key.clone(),
+            their_commitments: self.their_commitments.clone(),
+            their_dh_public_keys: self.their_dh_public_keys.clone(),
+            their_encrypted_secret_shares: self.their_encrypted_secret_shares.clone(),
+            my_secret_shares: self
+                .my_secret_shares
+                .as_ref()
+                .map(|shares| shares.iter().map(SecretShare::duplicate).collect()),
+        }
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for ActualState<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.dh_public_key.validate_points()?;
+        for (index, dh_public_key) in self.their_dh_public_keys.iter() {
+            validate_dh_public_key::<C>(*index, &dh_public_key.key)?;
+        }
+        match &self.their_commitments {
+            Some(commitments) => commitments
+                .iter()
+                .try_for_each(ValidatePoints::validate_points),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Output of the first round of the Distributed Key Generation.
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct DKGParticipantList<C: CipherSuite> {
@@ -518,6 +556,36 @@ pub struct DKGParticipantList<C: CipherSuite> {
     pub misbehaving_participants: Option<Vec<u32>>,
 }
 
+impl<S: DkgState, C: CipherSuite> DistributedKeyGeneration<S, C> {
+    /// Start tracking the complaints and share receipts broadcast during this session,
+    /// to decide which dealers are qualified under the given `policy`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is a [`QualificationTracker`], otherwise an
+    /// [`Error::MissingShares`] if this participant did not receive the commitments of
+    /// the dealers, as is the case for dealers of a resharing session.
+    pub fn qualification_tracker(
+        &self,
+        policy: QualificationPolicy,
+    ) -> FrostResult<C, QualificationTracker<C>> {
+        let their_commitments = self
+            .state
+            .their_commitments
+            .clone()
+            .ok_or(Error::MissingShares)?;
+
+        Ok(QualificationTracker {
+            parameters: self.state.parameters,
+            policy,
+            their_commitments,
+            their_dh_public_keys: self.state.their_dh_public_keys.clone(),
+            complaints: BTreeMap::new(),
+            confirmations: BTreeMap::new(),
+        })
+    }
+}
+
 impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
     /// Serialize this [`DistributedKeyGeneration<RoundOne, _>`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
@@ -525,23 +593,21 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
 
         self.state
             .serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         self.data
             .serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`DistributedKeyGeneration<RoundOne, _>`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        let state = Box::new(
-            ActualState::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)?,
-        );
+        let state = Box::new(deserialize_validated::<C, ActualState<C>>(bytes)?);
 
-        let data =
-            RoundOne::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)?;
+        let data = RoundOne::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
 
         Ok(Self { state, data })
     }
@@ -561,20 +627,22 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
     /// * This participant's `index`.
     /// * This participant's secret `coefficients` making up their long-lived secret key.
     /// * The list of `participants` for this ICE-FROST session.
-    /// * A cryptographically secure pseudo-random generator.
+    /// * A cryptographically secure pseudo-random generator, drawing the salt of
+    ///   this ceremony from which the secret shares encryption nonces are derived.
     ///
     /// # Returns
     ///
     /// An updated state machine for the distributed key generation protocol if
     /// all of the zero-knowledge proofs verified successfully, otherwise a
-    /// vector of participants whose proofs were incorrect.
-    pub fn bootstrap(
+    /// vector of participants whose proofs were incorrect, or an
+    /// [`Error::ConsumedCoefficients`] if the `coefficients` were already wiped.
+    pub fn bootstrap<R: Role<C>>(
         parameters: &ThresholdParameters<C>,
         dh_private_key: &DiffieHellmanPrivateKey<C>,
         my_index: &u32,
         my_coefficients: &Coefficients<C>,
-        participants: &[Participant<C>],
-        mut rng: impl RngCore + CryptoRng,
+        participants: &[Participant<C, R>],
+        rng: impl RngCore + CryptoRng,
     ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
         Self::new_state_internal(
             parameters,
@@ -584,7 +652,42 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
             participants,
             true,
             true,
-            &mut rng,
+            None,
+            rng,
+        )
+    }
+
+    /// Bootstrap an ICE-FROST instance as with [`DistributedKeyGeneration::bootstrap`],
+    /// between participants whose indices were derived from their Diffie-Hellman
+    /// public keys in the given `assignment`.
+    ///
+    /// This participant's index is the one assigned to its Diffie-Hellman public key,
+    /// and the participants claiming an index other than the one assigned to their
+    /// Diffie-Hellman public key are considered misbehaving.
+    ///
+    /// As the Diffie-Hellman keys of an assignment may be reused across ceremonies,
+    /// the secret shares are encrypted with nonces derived from the salt drawn from
+    /// `rng`, which is fresh for each ceremony.
+    pub fn bootstrap_with_assignment<R: Role<C>>(
+        parameters: &ThresholdParameters<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        my_coefficients: &Coefficients<C>,
+        participants: &[Participant<C, R>],
+        assignment: &IndexAssignment<C>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
+        let my_index = assignment.index_of_private_key(dh_private_key)?;
+
+        Self::new_state_internal(
+            parameters,
+            dh_private_key,
+            &my_index,
+            Some(my_coefficients),
+            participants,
+            true,
+            true,
+            Some(assignment),
+            rng,
         )
     }
 
@@ -603,19 +706,20 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
     /// * This participant's `index`.
     /// * The list of `dealers`. These are the participants of the previous ICE-FROST
     ///   session from which the individual secret shares are being redistributed.
-    /// * A cryptographically secure pseudo-random generator.
+    /// * A cryptographically secure pseudo-random generator, drawing the salt of
+    ///   this ceremony if this participant distributes secret shares.
     ///
     /// # Returns
     ///
     /// An updated state machine for the distributed key generation protocol if
     /// all of the zero-knowledge proofs verified successfully, otherwise a
     /// vector of participants whose zero-knowledge proofs were incorrect.
-    pub fn new(
+    pub fn new<R: Role<C>>(
         parameters: &ThresholdParameters<C>,
         dh_private_key: &DiffieHellmanPrivateKey<C>,
         my_index: &u32,
-        dealers: &[Participant<C>],
-        mut rng: impl RngCore + CryptoRng,
+        dealers: &[Participant<C, R>],
+        rng: impl RngCore + CryptoRng,
     ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
         Self::new_state_internal(
             parameters,
@@ -625,29 +729,33 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
             dealers,
             false,
             true,
-            &mut rng,
+            None,
+            rng,
         )
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn new_state_internal(
+    pub(crate) fn new_state_internal<R: Role<C>>(
         parameters: &ThresholdParameters<C>,
         dh_private_key: &DiffieHellmanPrivateKey<C>,
         my_index: &u32,
         my_coefficients: Option<&Coefficients<C>>,
-        participants: &[Participant<C>],
+        participants: &[Participant<C, R>],
         from_dealer: bool,
         from_signer: bool,
+        assignment: Option<&IndexAssignment<C>>,
         mut rng: impl RngCore + CryptoRng,
     ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
         let mut their_commitments: Vec<VerifiableSecretSharingCommitment<C>> =
-            Vec::with_capacity(parameters.t as usize);
+            try_with_capacity::<C, _>(parameters.t as usize)?;
         let mut their_dh_public_keys: Vec<(u32, DiffieHellmanPublicKey<C>)> =
-            Vec::with_capacity(parameters.t as usize);
-        let mut valid_participants: Vec<Participant<C>> = Vec::with_capacity(parameters.n as usize);
+            try_with_capacity::<C, _>(parameters.t as usize)?;
+        let mut valid_participants: Vec<Participant<C>> =
+            try_with_capacity::<C, _>(parameters.n as usize)?;
         let mut misbehaving_participants: Vec<u32> = Vec::new();
 
-        let dh_public_key = DiffieHellmanPublicKey::new(C::G::generator().mul(dh_private_key.0));
+        let dh_public_key =
+            DiffieHellmanPublicKey::<C>::new(Element(C::G::generator().mul(dh_private_key.0)));
 
         // Bail if we didn't get enough participants.
         if participants.len() != parameters.n as usize {
@@ -657,10 +765,39 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
             ));
         }
 
+        // Process the participants in their canonical order, so that every node
+        // derives identical lists from the same roster, regardless of its ordering.
+        let participants = canonical_order(participants);
+
         // Check the public keys and the DH keys of the participants.
-        for p in participants.iter() {
+        for &p in participants.iter() {
+            // Participants sharing the same index cannot be told apart, hence
+            // they are all considered misbehaving.
+            if participants.iter().filter(|q| q.index == p.index).count() > 1 {
+                misbehaving_participants.push(p.index);
+                continue;
+            }
+
+            // Participants must hold the index assigned to their DH key, if any.
+            if let Some(assignment) = assignment {
+                if assignment.index_of(&p.dh_public_key) != Some(p.index) {
+                    misbehaving_participants.push(p.index);
+                    continue;
+                }
+            }
+
+            // A valid proof of knowledge of the DH private key does not rule out
+            // the identity, whose private key is zero, nor keys of small order.
+            if validate_dh_public_key::<C>(p.index, &p.dh_public_key.key).is_err() {
+                misbehaving_participants.push(p.index);
+                continue;
+            }
+
             // Always check the DH keys of the participants
-            match p.proof_of_dh_private_key.verify(p.index, &p.dh_public_key) {
+            match p
+                .proof_of_dh_private_key
+                .verify(p.index, &p.dh_public_key.element())
+            {
                 Ok(_) => {
                     // Signers additionally check the public keys of the signers
                     if from_signer {
@@ -671,21 +808,30 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
                                 continue;
                             }
                         };
-                        match p
-                            .proof_of_secret_key
-                            .as_ref()
-                            .unwrap()
-                            .verify(p.index, public_key)
-                        {
+                        let (commitments, proof_of_secret_key) = match p.dealer_material() {
+                            Some(material) => {
+                                (&material.commitments, &material.proof_of_secret_key)
+                            }
+                            None => {
+                                misbehaving_participants.push(p.index);
+                                continue;
+                            }
+                        };
+                        // When bootstrapping, every dealer's polynomial must be of degree t-1.
+                        if from_dealer && commitments.points.len() != parameters.t as usize {
+                            misbehaving_participants.push(p.index);
+                            continue;
+                        }
+                        match proof_of_secret_key.verify(p.index, &public_key) {
                             Ok(_) => {
-                                valid_participants.push(p.clone());
-                                their_commitments.push(p.commitments.as_ref().unwrap().clone());
+                                valid_participants.push(p.clone().into_any());
+                                their_commitments.push(commitments.clone());
                                 their_dh_public_keys.push((p.index, p.dh_public_key.clone()));
                             }
                             Err(_) => misbehaving_participants.push(p.index),
                         }
                     } else {
-                        valid_participants.push(p.clone());
+                        valid_participants.push(p.clone().into_any());
                         their_dh_public_keys.push((p.index, p.dh_public_key.clone()));
                     }
                 }
@@ -693,10 +839,25 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
             }
         }
 
+        for index in misbehaving_participants.iter() {
+            trace!(
+                warn,
+                "ice-frost: dkg participant {} rejected participant {}",
+                *my_index,
+                *index
+            );
+        }
+
         // If too many participants were misbehaving, return an error along their indices.
         if valid_participants.len() < parameters.t as usize {
             return Err(Error::TooManyInvalidParticipants(misbehaving_participants));
         }
+        trace!(
+            info,
+            "ice-frost: dkg participant {} entered round one with {} valid participants",
+            *my_index,
+            valid_participants.len()
+        );
 
         if !from_dealer && from_signer {
             let state = ActualState {
@@ -734,19 +895,29 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
         // Step 1: Each P_i securely sends to each other participant P_l a secret share
         //         (l, f_i(l)) and keeps (i, f_i(i)) for themselves.
         let mut their_encrypted_secret_shares: Vec<EncryptedSecretShare<C>> =
-            Vec::with_capacity(parameters.n as usize - 1);
+            try_with_capacity::<C, _>(parameters.n as usize - 1)?;
+
+        let my_coefficients = my_coefficients.ok_or(Error::MissingCoefficients)?;
+        if my_coefficients.is_consumed() {
+            return Err(Error::ConsumedCoefficients);
+        }
+
+        // The salt of this ceremony, sent along each share, separates the encryption
+        // nonces of ceremonies reusing the same Diffie-Hellman keys.
+        let mut salt = vec![0u8; SHARE_SALT_LENGTH];
+        rng.fill_bytes(&mut salt);
 
         for p in participants.iter() {
-            let share =
-                SecretShare::<C>::evaluate_polynomial(my_index, &p.index, my_coefficients.unwrap());
+            let share = SecretShare::<C>::evaluate_polynomial(my_index, &p.index, my_coefficients)?;
 
             let dh_key = p.dh_public_key.key * dh_private_key.0;
-            let mut dh_key_bytes = Vec::new();
-            dh_key
-                .serialize_compressed(&mut dh_key_bytes)
-                .map_err(|_| Error::CompressionError)?;
+            let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_key)?;
+
+            let session_id = share_session_id(&dh_public_key, &p.dh_public_key)?;
 
-            their_encrypted_secret_shares.push(encrypt_share(&share, &dh_key_bytes[..], &mut rng)?);
+            let encrypted_share = encrypt_share(&share, &dh_key_bytes[..], &session_id, &salt);
+            dh_key_bytes.zeroize();
+            their_encrypted_secret_shares.push(encrypted_share?);
         }
 
         let state = ActualState {
@@ -780,8 +951,51 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
         ))
     }
 
+    /// Exclude the participants with the given `indices` from this session, as if they
+    /// had been found misbehaving upon starting it with the given `participant_list`,
+    /// and drop the secret shares encrypted for them.
+    pub(crate) fn exclude_participants(
+        mut self,
+        mut participant_list: DKGParticipantList<C>,
+        indices: &[u32],
+    ) -> FrostResult<C, (Self, DKGParticipantList<C>)> {
+        if indices.is_empty() {
+            return Ok((self, participant_list));
+        }
+
+        if let Some(commitments) = self.state.their_commitments.as_mut() {
+            commitments.retain(|commitment| !indices.contains(&commitment.index));
+        }
+        self.state
+            .their_dh_public_keys
+            .retain(|(index, _)| !indices.contains(index));
+        if let Some(shares) = self.state.their_encrypted_secret_shares.as_mut() {
+            shares.retain(|share| !indices.contains(&share.receiver_index));
+        }
+
+        participant_list
+            .valid_participants
+            .retain(|p| !indices.contains(&p.index));
+        let mut misbehaving_participants = participant_list
+            .misbehaving_participants
+            .take()
+            .unwrap_or_default();
+        misbehaving_participants.extend_from_slice(indices);
+        misbehaving_participants.sort_unstable();
+        misbehaving_participants.dedup();
+
+        if participant_list.valid_participants.len() < self.state.parameters.t as usize {
+            return Err(Error::TooManyInvalidParticipants(misbehaving_participants));
+        }
+        participant_list.misbehaving_participants = Some(misbehaving_participants);
+
+        Ok((self, participant_list))
+    }
+
     /// Retrieve an encrypted secret share for each other participant, to be given to them
     /// at the end of [`DistributedKeyGeneration::<RoundOne, C>`] .
+    ///
+    /// The shares are sorted by increasing [`ParticipantId`](crate::dkg::ParticipantId) of their recipient.
     pub fn their_encrypted_secret_shares(&self) -> FrostResult<C, &Vec<EncryptedSecretShare<C>>> {
         self.state
             .their_encrypted_secret_shares
@@ -789,15 +1003,59 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
             .ok_or(Error::NoEncryptedShares)
     }
 
+    /// Wipe the secret polynomial `coefficients` of this dealer, once its secret
+    /// shares have been encrypted for the other participants.
+    ///
+    /// The coefficients are overwritten with zeroes, and cannot be used afterwards,
+    /// which is checked by debug assertions.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is empty if the coefficients were wiped,
+    /// otherwise an [`Error::NoEncryptedShares`] if this participant did not encrypt
+    /// any secret share, in which case the coefficients are left untouched.
+    pub fn consume_coefficients(&self, coefficients: &mut Coefficients<C>) -> FrostResult<C, ()> {
+        self.their_encrypted_secret_shares()?;
+        coefficients.zeroize();
+        trace!(
+            debug,
+            "ice-frost: dkg participant {} zeroized its polynomial coefficients",
+            self.state.index
+        );
+
+        Ok(())
+    }
+
     /// Progress to round two of the Dkg protocol once we have sent each encrypted share
     /// from [`DistributedKeyGeneration::<RoundOne, C>::their_encrypted_secret_shares()`] to its
     /// respective other participant, and collected our shares from the other
     /// participants in turn.
     pub fn to_round_two(
-        mut self,
+        self,
         my_encrypted_secret_shares: Vec<EncryptedSecretShare<C>>,
         mut rng: impl RngCore + CryptoRng,
     ) -> FrostResult<C, DistributedKeyGeneration<RoundTwo, C>> {
+        if my_encrypted_secret_shares.len() != self.state.parameters.n as usize {
+            return Err(Error::MissingShares);
+        }
+
+        let mut processor = self.begin_round_two();
+        for encrypted_share in my_encrypted_secret_shares.iter() {
+            processor.process_share(encrypted_share, &mut rng)?;
+        }
+
+        processor.finish()
+    }
+
+    /// Start processing the encrypted secret shares addressed to this participant
+    /// incrementally, once we have sent each encrypted share from
+    /// [`DistributedKeyGeneration::<RoundOne, C>::their_encrypted_secret_shares()`]
+    /// to its respective other participant.
+    ///
+    /// Unlike [`DistributedKeyGeneration::<RoundOne, C>::to_round_two()`], the shares
+    /// can be fed to the returned [`RoundTwoProcessor`] as they are received, for
+    /// instance from a network stream, without holding all of them in memory at once.
+    pub fn begin_round_two(mut self) -> RoundTwoProcessor<C> {
         // Sanity check
         assert_eq!(self.data, RoundOne {});
 
@@ -805,69 +1063,209 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundOne, C> {
         if self.state.their_encrypted_secret_shares.is_some() {
             self.state.their_encrypted_secret_shares = None;
         }
+        trace!(
+            debug,
+            "ice-frost: dkg participant {} started processing its secret shares",
+            self.state.index
+        );
 
-        let mut complaints: Vec<Complaint<C>> = Vec::new();
-
-        if my_encrypted_secret_shares.len() != self.state.parameters.n as usize {
-            return Err(Error::MissingShares);
+        RoundTwoProcessor {
+            state: self.state,
+            my_secret_shares: Vec::new(),
+            processed_senders: Vec::new(),
+            complaints: Vec::new(),
         }
+    }
+}
 
-        let mut my_secret_shares: Vec<SecretShare<C>> = Vec::new();
+/// An incremental processor of the encrypted secret shares received by a participant
+/// at the end of [`DistributedKeyGeneration::<RoundOne, C>`], obtained with
+/// [`DistributedKeyGeneration::<RoundOne, C>::begin_round_two()`].
+///
+/// Only the decrypted shares and the indices of their senders are retained, each
+/// encrypted share being dropped by the caller once processed.
+#[derive(Debug)]
+pub struct RoundTwoProcessor<C: CipherSuite> {
+    state: Box<ActualState<C>>,
+    /// The secret shares decrypted so far.
+    my_secret_shares: Vec<SecretShare<C>>,
+    /// The sorted indices of the senders of the shares processed so far.
+    processed_senders: Vec<u32>,
+    /// The complaints raised so far.
+    complaints: Vec<Complaint<C>>,
+}
+
+impl<C: CipherSuite> RoundTwoProcessor<C> {
+    /// Decrypt and verify an `encrypted_share` addressed to this participant.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is a [`Complaint`] against the sender of the
+    /// `encrypted_share` if it is invalid, to be broadcast right away, or [`None`] otherwise.
+    /// An [`Error`] is returned if a share from the same sender was already processed.
+    pub fn process_share(
+        &mut self,
+        encrypted_share: &EncryptedSecretShare<C>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Option<Complaint<C>>> {
+        let position = match self
+            .processed_senders
+            .binary_search(&encrypted_share.sender_index)
+        {
+            Ok(_) => return Err(Error::DuplicateIndices),
+            Err(position) => position,
+        };
+        try_reserve::<C, _>(&mut self.processed_senders, 1)?;
+        try_reserve::<C, _>(&mut self.my_secret_shares, 1)?;
+
+        // Shares from unknown senders are ignored.
+        let pk = match self
+            .state
+            .their_dh_public_keys
+            .binary_search_by_key(&encrypted_share.sender_index, |(index, _)| *index)
+        {
+            Ok(position) => self.state.their_dh_public_keys[position].1.clone(),
+            Err(_) => return Ok(None),
+        };
+        self.processed_senders
+            .insert(position, encrypted_share.sender_index);
 
         // Step 2.1: Each P_i decrypts their shares with
         //           key k_il = pk_l^sk_i
-        for encrypted_share in my_encrypted_secret_shares.iter() {
-            for pk in self.state.their_dh_public_keys.iter() {
-                if pk.0 == encrypted_share.sender_index {
-                    let dh_shared_key = *pk.1 * self.state.dh_private_key.0;
-                    let mut dh_key_bytes = Vec::new();
-                    dh_shared_key
-                        .serialize_compressed(&mut dh_key_bytes)
-                        .map_err(|_| Error::CompressionError)?;
-
-                    // Step 2.2: Each share is verified by calculating:
-                    //           g^{f_l(i)} ?= \Prod_{k=0}^{t-1} \phi_{lk}^{i^{k} mod q},
-                    //           creating a complaint if the check fails.
-                    let decrypted_share = decrypt_share(encrypted_share, &dh_key_bytes);
-                    let decrypted_share_ref = &decrypted_share;
-
-                    for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
-                        if commitment.index == encrypted_share.sender_index {
-                            // If the decrypted share is incorrect, P_i builds
-                            // a complaint
-
-                            if decrypted_share.is_err()
-                                || decrypted_share_ref
-                                    .as_ref()
-                                    .unwrap()
-                                    .verify(commitment)
-                                    .is_err()
-                            {
-                                complaints.push(Complaint::<C>::new(
-                                    encrypted_share.receiver_index,
-                                    encrypted_share.sender_index,
-                                    &pk.1,
-                                    &self.state.dh_private_key.0,
-                                    &self.state.dh_public_key.key,
-                                    &dh_shared_key,
-                                    &mut rng,
-                                )?);
-                                break;
-                            }
-                        }
-                    }
-                    if let Ok(share) = decrypted_share {
-                        my_secret_shares.push(share);
-                    }
-                }
+        let dh_shared_key = self.state.dh_private_key.dh_shared_key(&pk)?;
+        let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+
+        // Step 2.2: Each share is verified by calculating:
+        //           g^{f_l(i)} ?= \Prod_{k=0}^{t-1} \phi_{lk}^{i^{k} mod q},
+        //           creating a complaint if the check fails.
+        let session_id = share_session_id(&pk, &self.state.dh_public_key)?;
+        let decrypted_share = decrypt_share(encrypted_share, &dh_key_bytes, &session_id);
+        dh_key_bytes.zeroize();
+
+        let their_commitments = self
+            .state
+            .their_commitments
+            .as_ref()
+            .ok_or(Error::MissingShares)?;
+        let commitment = their_commitments
+            .binary_search_by_key(&encrypted_share.sender_index, |commitment| commitment.index)
+            .ok()
+            .map(|position| &their_commitments[position]);
+
+        let mut complaint = None;
+        if let Some(commitment) = commitment {
+            // If the decrypted share is incorrect, P_i builds a complaint
+            let is_valid = match &decrypted_share {
+                Ok(share) => share.verify(commitment).is_ok(),
+                Err(_) => false,
+            };
+            if !is_valid {
+                let new_complaint = Complaint::<C>::new(
+                    encrypted_share.receiver_index,
+                    encrypted_share.sender_index,
+                    &pk,
+                    &self.state.dh_private_key.0,
+                    &self.state.dh_public_key.key,
+                    &dh_shared_key,
+                    &mut rng,
+                )?;
+                trace!(
+                    warn,
+                    "ice-frost: dkg participant {} raised a complaint against dealer {}",
+                    self.state.index,
+                    encrypted_share.sender_index
+                );
+                self.complaints.push(new_complaint.clone());
+                complaint = Some(new_complaint);
+            }
+        }
+
+        if let Ok(share) = decrypted_share {
+            self.my_secret_shares.push(share);
+        }
+
+        Ok(complaint)
+    }
+
+    /// Process a stream of `encrypted_shares` addressed to this participant.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value lists the [`Complaint`]s raised against
+    /// the senders of invalid shares among the `encrypted_shares`.
+    pub fn process_shares(
+        &mut self,
+        encrypted_shares: impl IntoIterator<Item = EncryptedSecretShare<C>>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Vec<Complaint<C>>> {
+        let mut complaints = Vec::new();
+        for encrypted_share in encrypted_shares {
+            if let Some(complaint) = self.process_share(&encrypted_share, &mut rng)? {
+                complaints.push(complaint);
             }
         }
 
-        if !complaints.is_empty() {
-            return Err(Error::Complaint(complaints));
+        Ok(complaints)
+    }
+
+    /// Decrypt and verify the encrypted secret share addressed to this participant
+    /// within the [`ShareBundle`] of a dealer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is as with [`RoundTwoProcessor::process_share`],
+    /// otherwise an [`Error::MisroutedShare`] if the `bundle` holds no share addressed to
+    /// this participant and its Diffie-Hellman public key. In this case, the dealer is not
+    /// blamed, and the bundle should be requested again.
+    pub fn process_bundle(
+        &mut self,
+        bundle: &ShareBundle<C>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Option<Complaint<C>>> {
+        let encrypted_share = bundle.routed_share(self.state.index, &self.state.dh_public_key)?;
+
+        self.process_share(encrypted_share, rng)
+    }
+
+    /// The number of shares processed so far.
+    pub fn processed_shares(&self) -> usize {
+        self.processed_senders.len()
+    }
+
+    /// Progress to round two of the Dkg protocol once all the shares addressed to
+    /// this participant have been processed.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`DistributedKeyGeneration<RoundTwo, C>`],
+    /// otherwise an [`Error::Complaint`] listing all the complaints raised while processing
+    /// the shares, or an [`Error::MissingShares`] if some shares were not processed.
+    pub fn finish(mut self) -> FrostResult<C, DistributedKeyGeneration<RoundTwo, C>> {
+        if !self.complaints.is_empty() {
+            trace!(
+                warn,
+                "ice-frost: dkg participant {} cannot enter round two with {} complaints",
+                self.state.index,
+                self.complaints.len()
+            );
+            return Err(Error::Complaint(self.complaints));
+        }
+        if self.processed_senders.len() != self.state.their_dh_public_keys.len() {
+            trace!(
+                warn,
+                "ice-frost: dkg participant {} is missing {} secret shares",
+                self.state.index,
+                self.state.their_dh_public_keys.len() - self.processed_senders.len()
+            );
+            return Err(Error::MissingShares);
         }
+        trace!(
+            info,
+            "ice-frost: dkg participant {} entered round two",
+            self.state.index
+        );
 
-        self.state.my_secret_shares = Some(my_secret_shares);
+        self.state.my_secret_shares = Some(self.my_secret_shares);
 
         Ok(DistributedKeyGeneration::<RoundTwo, C> {
             state: self.state,
@@ -883,23 +1281,21 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundTwo, C> {
 
         self.state
             .serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         self.data
             .serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`DistributedKeyGeneration<RoundTwo, _>`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        let state = Box::new(
-            ActualState::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)?,
-        );
+        let state = Box::new(deserialize_validated::<C, ActualState<C>>(bytes)?);
 
-        let data =
-            RoundTwo::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)?;
+        let data = RoundTwo::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
 
         Ok(Self { state, data })
     }
@@ -917,10 +1313,88 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundTwo, C> {
         let group_key = self.calculate_group_key()?;
 
         self.state.my_secret_shares.zeroize();
+        trace!(
+            info,
+            "ice-frost: dkg participant {} derived its signing key and zeroized its secret shares",
+            self.state.index
+        );
 
         Ok((group_key, secret_key))
     }
 
+    /// Issue a [`ShareReceipt`] to each dealer whose secret share has been
+    /// received, decrypted and verified by this participant.
+    ///
+    /// The receipts are signed with this participant's Diffie-Hellman private key,
+    /// and should be sent to their respective dealers before finishing the session.
+    pub fn share_receipts(
+        &self,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Vec<ShareReceipt<C>>> {
+        let my_secret_shares = self.state.my_secret_shares.as_ref().ok_or_else(|| {
+            Error::Custom("Could not retrieve participant's secret shares".to_string())
+        })?;
+        let their_commitments = self
+            .state
+            .their_commitments
+            .as_ref()
+            .ok_or(Error::MissingShares)?;
+
+        let mut receipts = try_with_capacity::<C, _>(my_secret_shares.len())?;
+        for share in my_secret_shares.iter() {
+            let commitment = their_commitments
+                .iter()
+                .find(|commitment| commitment.index == share.sender_index)
+                .ok_or(Error::MissingShares)?;
+
+            receipts.push(ShareReceipt::new(
+                self.state.index,
+                commitment,
+                &self.state.dh_private_key,
+                &self.state.dh_public_key,
+                &mut rng,
+            )?);
+        }
+
+        Ok(receipts)
+    }
+
+    /// Verify the [`ShareReceipt`]s collected by this participant as a dealer,
+    /// ensuring that every participant acknowledged the secret share it received.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that all participants provided
+    /// a valid receipt, otherwise an [`Error::MisbehavingParticipants`] listing the
+    /// participants whose receipt is missing or invalid.
+    pub fn verify_share_receipts(&self, receipts: &[ShareReceipt<C>]) -> FrostResult<C, ()> {
+        let my_commitment = self
+            .state
+            .their_commitments
+            .as_ref()
+            .and_then(|commitments| {
+                commitments
+                    .iter()
+                    .find(|commitment| commitment.index == self.state.index)
+            })
+            .ok_or(Error::InvalidShareReceipt)?;
+
+        let mut misbehaving_participants = Vec::new();
+        for (index, dh_public_key) in self.state.their_dh_public_keys.iter() {
+            if !receipts.iter().any(|receipt| {
+                receipt.recipient_index == *index
+                    && receipt.verify(dh_public_key, my_commitment).is_ok()
+            }) {
+                misbehaving_participants.push(*index);
+            }
+        }
+
+        match misbehaving_participants.is_empty() {
+            true => Ok(()),
+            false => Err(Error::MisbehavingParticipants(misbehaving_participants)),
+        }
+    }
+
     /// Calculate this threshold signing participant's long-lived secret signing
     /// key by interpolating all of the polynomial evaluations from the other
     /// participants.
@@ -943,7 +1417,7 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundTwo, C> {
                     Ok(s) => s,
                     Err(error) => return Err(Error::Custom(error.to_string())),
                 };
-            key += share.polynomial_evaluation * coeff;
+            key += coeff * share.polynomial_evaluation.0;
         }
 
         Ok(IndividualSigningKey {
@@ -961,86 +1435,238 @@ impl<C: CipherSuite> DistributedKeyGeneration<RoundTwo, C> {
     /// my_commitment is needed for now, but won't be when the distinction
     /// dealers/signers is implemented.
     pub(crate) fn calculate_group_key(&self) -> FrostResult<C, GroupVerifyingKey<C>> {
+        let their_commitments = self
+            .state
+            .their_commitments
+            .as_ref()
+            .ok_or(Error::MissingShares)?;
         let mut index_vector: Vec<u32> = Vec::new();
 
-        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
+        for commitment in their_commitments.iter() {
             index_vector.push(commitment.index);
         }
 
         let mut group_key = <C as CipherSuite>::G::zero();
 
         // The group key is the interpolation at 0 of all index 0 of the dealers' commitments.
-        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
+        for commitment in their_commitments.iter() {
             let coeff = match calculate_lagrange_coefficients::<C>(commitment.index, &index_vector)
             {
                 Ok(s) => s,
                 Err(error) => return Err(Error::Custom(error.to_string())),
             };
 
-            group_key += commitment.public_key().unwrap().mul(coeff);
+            group_key += commitment
+                .public_key()
+                .ok_or(Error::InvalidGroupKey)?
+                .0
+                .mul(coeff);
         }
 
-        Ok(GroupVerifyingKey::new(group_key))
+        Ok(GroupVerifyingKey::new(Element(group_key)))
     }
 
     /// Every participant can verify a complaint and determine who is the malicious
     /// party. The relevant encrypted share is assumed to exist and publicly retrievable
     /// by any participant.
+    ///
+    /// Participants who did not receive the commitments of the dealers, as is the case
+    /// for dealers of a resharing session, cannot verify complaints, and always blame
+    /// their maker.
     pub fn blame(
         &self,
         encrypted_share: &EncryptedSecretShare<C>,
         complaint: &Complaint<C>,
     ) -> u32 {
-        let mut pk_maker = <C as CipherSuite>::G::zero();
-        let mut pk_accused = <C as CipherSuite>::G::zero();
-        let mut commitment_accused = VerifiableSecretSharingCommitment {
-            index: 0,
-            points: Vec::new(),
-        };
+        blame_complaint(
+            self.state.their_commitments.as_deref().unwrap_or(&[]),
+            &self.state.their_dh_public_keys,
+            encrypted_share,
+            complaint,
+        )
+    }
+}
 
-        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
-            if commitment.index == complaint.accused_index {
-                commitment_accused = commitment.clone();
-            }
+/// Determine who is the malicious party between the maker of a `complaint` and the
+/// dealer it accuses, given the public commitments and Diffie-Hellman public keys
+/// of the session.
+pub(crate) fn blame_complaint<C: CipherSuite>(
+    their_commitments: &[VerifiableSecretSharingCommitment<C>],
+    their_dh_public_keys: &[(u32, DiffieHellmanPublicKey<C>)],
+    encrypted_share: &EncryptedSecretShare<C>,
+    complaint: &Complaint<C>,
+) -> u32 {
+    let mut pk_maker = <C as CipherSuite>::G::zero();
+    let mut pk_accused = <C as CipherSuite>::G::zero();
+    let mut commitment_accused = VerifiableSecretSharingCommitment {
+        index: 0,
+        points: Vec::new(),
+    };
+
+    for commitment in their_commitments.iter() {
+        if commitment.index == complaint.accused_index {
+            commitment_accused = commitment.clone();
         }
+    }
+
+    if commitment_accused.points.is_empty() {
+        return complaint.maker_index;
+    }
 
-        if commitment_accused.points.is_empty() {
-            return complaint.maker_index;
+    for (index, pk) in their_dh_public_keys.iter() {
+        if index == &complaint.maker_index {
+            pk_maker = **pk;
+        } else if index == &complaint.accused_index {
+            pk_accused = **pk;
         }
+    }
+
+    if pk_maker == <C as CipherSuite>::G::zero() || pk_accused == <C as CipherSuite>::G::zero() {
+        return complaint.maker_index;
+    }
+
+    if complaint
+        .verify(&Element(pk_maker), &Element(pk_accused))
+        .is_err()
+    {
+        return complaint.maker_index;
+    }
+
+    let dh_key_bytes = match serialize_dh_shared_key::<C>(&complaint.dh_shared_key) {
+        Ok(dh_key_bytes) => dh_key_bytes,
+        Err(_) => return complaint.maker_index,
+    };
+
+    let session_id = match share_session_id(
+        &DiffieHellmanPublicKey::<C>::new(Element(pk_accused)),
+        &DiffieHellmanPublicKey::new(Element(pk_maker)),
+    ) {
+        Ok(session_id) => session_id,
+        Err(_) => return complaint.maker_index,
+    };
+
+    match decrypt_share(encrypted_share, &dh_key_bytes[..], &session_id) {
+        Ok(share) => match share.verify(&commitment_accused) {
+            Ok(()) => complaint.maker_index,
+            Err(_) => complaint.accused_index,
+        },
+        Err(_) => complaint.accused_index,
+    }
+}
 
-        for (index, pk) in self.state.their_dh_public_keys.iter() {
-            if index == &complaint.maker_index {
-                pk_maker = **pk;
-            } else if index == &complaint.accused_index {
-                pk_accused = **pk;
+/// Verify that the dealers of a resharing session collectively shared the secret key
+/// of the previous group, as seen by the new set of participants.
+///
+/// During a resharing, the constant term of each dealer's polynomial is its former
+/// secret share, hence the constant terms committed to by honest dealers all lie on the
+/// secret polynomial of the previous group, whose evaluation at 0 is committed to by
+/// `previous_group_key`. A dealer is consistent if its committed constant term lies on
+/// this polynomial, that is if interpolating it together with \( t - 1 \) consistent
+/// dealers yields `previous_group_key`, with \( t \) the threshold of `previous_parameters`.
+///
+/// Identifying inconsistent dealers requires searching for \( t - 1 \) honest ones,
+/// which is immediate when all dealers are honest but can take up to
+/// \( inom{n}{t - 1} \) attempts otherwise.
+///
+/// # Returns
+///
+/// The sorted indices of the inconsistent dealers, which should be excluded from the
+/// resharing session, or [`Error::TooManyInvalidParticipants`] if fewer than \( t \)
+/// dealers are consistent with the previous group key.
+pub fn verify_resharing<C: CipherSuite>(
+    previous_parameters: &ThresholdParameters<C>,
+    dealers_commitments: &[VerifiableSecretSharingCommitment<C>],
+    previous_group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, Vec<u32>> {
+    let threshold = previous_parameters.t as usize;
+
+    let mut inconsistent_dealers = Vec::new();
+    let mut candidates: Vec<(u32, C::G)> = Vec::with_capacity(dealers_commitments.len());
+    for commitment in dealers_commitments.iter() {
+        match commitment.public_key() {
+            Some(public_key) if commitment.index != 0 => {
+                candidates.push((commitment.index, public_key.0))
             }
+            _ => inconsistent_dealers.push(commitment.index),
         }
+    }
 
-        if pk_maker == <C as CipherSuite>::G::zero() || pk_accused == <C as CipherSuite>::G::zero()
-        {
-            return complaint.maker_index;
-        }
+    // Dealers sharing an index cannot be told apart.
+    candidates.sort_by_key(|(index, _)| *index);
+    let mut duplicates: Vec<u32> = candidates
+        .windows(2)
+        .filter(|w| w[0].0 == w[1].0)
+        .map(|w| w[0].0)
+        .collect();
+    duplicates.dedup();
+    candidates.retain(|(index, _)| duplicates.binary_search(index).is_err());
+    inconsistent_dealers.extend(duplicates);
+
+    if threshold == 0 || candidates.len() < threshold {
+        inconsistent_dealers.extend(candidates.iter().map(|(index, _)| *index));
+        inconsistent_dealers.sort_unstable();
+        return Err(Error::TooManyInvalidParticipants(inconsistent_dealers));
+    }
 
-        if complaint.verify(&pk_maker, &pk_accused).is_err() {
-            return complaint.maker_index;
+    // Enumerate the subsets of t - 1 candidates, until finding one that t - 1 other dealers
+    // at least are consistent with. Starting with the first candidates makes the search
+    // immediate when all dealers are honest.
+    let mut basis: Vec<usize> = (0..threshold - 1).collect();
+    loop {
+        let mut consistent = vec![false; candidates.len()];
+        for &i in basis.iter() {
+            consistent[i] = true;
         }
 
-        let mut dh_key_bytes = Vec::new();
-        if complaint
-            .dh_shared_key
-            .serialize_compressed(&mut dh_key_bytes)
-            .is_err()
-        {
-            return complaint.maker_index;
-        };
+        for (j, (index, public_key)) in candidates.iter().enumerate() {
+            if consistent[j] {
+                continue;
+            }
+
+            let mut indices: Vec<u32> = basis.iter().map(|&i| candidates[i].0).collect();
+            indices.push(*index);
+
+            let mut interpolated_key = <C as CipherSuite>::G::zero();
+            for (i, point) in basis
+                .iter()
+                .map(|&i| &candidates[i])
+                .chain(core::iter::once(&(*index, *public_key)))
+            {
+                let coeff = calculate_lagrange_coefficients::<C>(*i, &indices)?;
+                interpolated_key += point.mul(coeff);
+            }
 
-        let share = decrypt_share(encrypted_share, &dh_key_bytes[..]);
-        if share.is_err() {
-            return complaint.accused_index;
+            consistent[j] = interpolated_key == previous_group_key.key;
         }
-        match share.unwrap().verify(&commitment_accused) {
-            Ok(()) => complaint.maker_index,
-            Err(_) => complaint.accused_index,
+
+        if consistent.iter().filter(|&&c| c).count() >= threshold {
+            inconsistent_dealers.extend(
+                candidates
+                    .iter()
+                    .zip(consistent)
+                    .filter(|(_, c)| !c)
+                    .map(|((index, _), _)| *index),
+            );
+            inconsistent_dealers.sort_unstable();
+
+            return Ok(inconsistent_dealers);
+        }
+
+        // Move to the next subset of candidates, in lexicographic order.
+        let n = candidates.len();
+        let k = basis.len();
+        match (0..k).rev().find(|&i| basis[i] != i + n - k) {
+            Some(i) => {
+                basis[i] += 1;
+                for j in i + 1..k {
+                    basis[j] = basis[j - 1] + 1;
+                }
+            }
+            None => {
+                inconsistent_dealers.extend(candidates.iter().map(|(index, _)| *index));
+                inconsistent_dealers.sort_unstable();
+                return Err(Error::TooManyInvalidParticipants(inconsistent_dealers));
+            }
         }
     }
 }
@@ -1050,8 +1676,15 @@ mod test {
     use core::ops::Mul;
 
     use super::*;
-    use crate::dkg::{ComplaintProof, NizkPokOfSecretKey};
-    use crate::keys::IndividualVerifyingKey;
+    use crate::dkg::secret_share::SHARE_DISTRIBUTION_ROUND;
+    use crate::dkg::{
+        AdmissionContribution, AdmissionShare, AuthenticatedSession, ComplaintAdmission,
+        ComplaintProof, ComplaintQueueLimits, DealerRole, ExternalAuthenticator,
+        NizkPokOfSecretKey, ParticipantAdmission, ResharingAccumulator, ResharingShare,
+        SealedComplaint, SignerRole,
+    };
+    use crate::keys::{IndividualVerifyingKey, PublicKeyPackage};
+    use crate::message::{ProtocolRound, RoundTag};
     use crate::testing::Secp256k1Sha256;
 
     use ark_ec::Group;
@@ -1063,15 +1696,14 @@ mod test {
 
     #[test]
     fn nizk_of_secret_key() {
-        let params = ThresholdParameters::new(3, 2);
+        let params = ThresholdParameters::new(3, 2).unwrap();
         let rng = OsRng;
 
-        let (p, _, _) = Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+        let (p, _, _) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
         let result = p
-            .proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p.index, p.public_key().unwrap());
+            .proof_of_secret_key()
+            .verify(p.index, &p.public_key().unwrap());
 
         assert!(result.is_ok());
     }
@@ -1085,9 +1717,10 @@ mod test {
         }
 
         let coefficients = Coefficients::<Secp256k1Sha256>(coeffs);
-        let share = SecretShare::<Secp256k1Sha256>::evaluate_polynomial(&1, &1, &coefficients);
+        let share =
+            SecretShare::<Secp256k1Sha256>::evaluate_polynomial(&1, &1, &coefficients).unwrap();
 
-        assert!(share.polynomial_evaluation == Fr::from(5u8));
+        assert!(*share.expose_secret() == crate::Scalar::from(5u64));
 
         let mut commitments = VerifiableSecretSharingCommitment {
             index: 1,
@@ -1112,9 +1745,9 @@ mod test {
         }
 
         let coefficients = Coefficients::<Secp256k1Sha256>(coeffs);
-        let share = SecretShare::evaluate_polynomial(&1, &0, &coefficients);
+        let share = SecretShare::evaluate_polynomial(&1, &0, &coefficients).unwrap();
 
-        assert!(share.polynomial_evaluation == Fr::ONE);
+        assert!(*share.expose_secret() == crate::Scalar::one());
 
         let mut commitments = VerifiableSecretSharingCommitment {
             index: 1,
@@ -1132,19 +1765,17 @@ mod test {
 
     #[test]
     fn single_party_keygen() {
-        let params = ThresholdParameters::new(1, 1);
+        let params = ThresholdParameters::new(1, 1).unwrap();
         let rng = OsRng;
 
         let (p1, p1coeffs, p1_dh_sk) =
-            Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
 
-        p1.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p1.index, p1.public_key().unwrap())
+        p1.proof_of_secret_key()
+            .verify(p1.index, &p1.public_key().unwrap())
             .unwrap();
 
-        let participants: Vec<Participant<Secp256k1Sha256>> = vec![p1.clone()];
+        let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec![p1.clone()];
         let (p1_state, _participant_lists) =
             DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
                 &params,
@@ -1170,49 +1801,16 @@ mod test {
     }
 
     #[test]
-    fn keygen_3_out_of_5() {
-        let params = ThresholdParameters::<Secp256k1Sha256>::new(5, 3);
+    fn consume_coefficients() {
+        let params = ThresholdParameters::new(2, 2).unwrap();
         let rng = OsRng;
 
-        let (p1, p1coeffs, p1_dh_sk) =
-            Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
-        let (p2, p2coeffs, p2_dh_sk) =
-            Participant::<Secp256k1Sha256>::new_dealer(&params, 2, rng).unwrap();
-        let (p3, p3coeffs, p3_dh_sk) =
-            Participant::<Secp256k1Sha256>::new_dealer(&params, 3, rng).unwrap();
-        let (p4, p4coeffs, p4_dh_sk) =
-            Participant::<Secp256k1Sha256>::new_dealer(&params, 4, rng).unwrap();
-        let (p5, p5coeffs, p5_dh_sk) =
-            Participant::<Secp256k1Sha256>::new_dealer(&params, 5, rng).unwrap();
-
-        p1.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p1.index, p1.public_key().unwrap())
-            .unwrap();
-        p2.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p2.index, p2.public_key().unwrap())
-            .unwrap();
-        p3.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p3.index, p3.public_key().unwrap())
-            .unwrap();
-        p4.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p4.index, p4.public_key().unwrap())
-            .unwrap();
-        p5.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(p5.index, p5.public_key().unwrap())
-            .unwrap();
+        let (p1, mut p1coeffs, p1_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        let (p2, _p2coeffs, _p2_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
+        let participants = vec![p1.clone(), p2.clone()];
 
-        let participants: Vec<Participant<Secp256k1Sha256>> =
-            vec![p1.clone(), p2.clone(), p3.clone(), p4.clone(), p5.clone()];
         let (p1_state, _participant_lists) =
             DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
                 &params,
@@ -1223,25 +1821,106 @@ mod test {
                 rng,
             )
             .unwrap();
-        let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
 
-        let (p2_state, _participant_lists) =
-            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+        // Signers do not encrypt shares, hence have no coefficients to consume.
+        let (s1, s1_dh_sk) =
+            Participant::<Secp256k1Sha256, SignerRole>::new_signer(&params, 1, rng).unwrap();
+        let (s1_state, _participant_lists) =
+            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::new(
                 &params,
-                &p2_dh_sk,
-                &p2.index,
-                &p2coeffs,
+                &s1_dh_sk,
+                &s1.index,
                 &participants,
                 rng,
             )
             .unwrap();
-        let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+        assert_eq!(
+            s1_state.consume_coefficients(&mut p1coeffs),
+            Err(Error::NoEncryptedShares)
+        );
+        assert!(!p1coeffs.is_consumed());
 
-        let (p3_state, _participant_lists) =
+        assert!(p1_state.consume_coefficients(&mut p1coeffs).is_ok());
+        assert!(p1coeffs.is_consumed());
+
+        assert_eq!(
             DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
                 &params,
-                &p3_dh_sk,
-                &p3.index,
+                &p1_dh_sk,
+                &p1.index,
+                &p1coeffs,
+                &participants,
+                rng,
+            )
+            .unwrap_err(),
+            Error::ConsumedCoefficients
+        );
+        assert_eq!(p1coeffs.to_bytes(), Err(Error::ConsumedCoefficients));
+    }
+
+    #[test]
+    fn keygen_3_out_of_5() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(5, 3).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1coeffs, p1_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        let (p2, p2coeffs, p2_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
+        let (p3, p3coeffs, p3_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
+        let (p4, p4coeffs, p4_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 4, rng).unwrap();
+        let (p5, p5coeffs, p5_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 5, rng).unwrap();
+
+        p1.proof_of_secret_key()
+            .verify(p1.index, &p1.public_key().unwrap())
+            .unwrap();
+        p2.proof_of_secret_key()
+            .verify(p2.index, &p2.public_key().unwrap())
+            .unwrap();
+        p3.proof_of_secret_key()
+            .verify(p3.index, &p3.public_key().unwrap())
+            .unwrap();
+        p4.proof_of_secret_key()
+            .verify(p4.index, &p4.public_key().unwrap())
+            .unwrap();
+        p5.proof_of_secret_key()
+            .verify(p5.index, &p5.public_key().unwrap())
+            .unwrap();
+
+        let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> =
+            vec![p1.clone(), p2.clone(), p3.clone(), p4.clone(), p5.clone()];
+        let (p1_state, _participant_lists) =
+            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                &params,
+                &p1_dh_sk,
+                &p1.index,
+                &p1coeffs,
+                &participants,
+                rng,
+            )
+            .unwrap();
+        let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
+
+        let (p2_state, _participant_lists) =
+            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                &params,
+                &p2_dh_sk,
+                &p2.index,
+                &p2coeffs,
+                &participants,
+                rng,
+            )
+            .unwrap();
+        let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+
+        let (p3_state, _participant_lists) =
+            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                &params,
+                &p3_dh_sk,
+                &p3.index,
                 &p3coeffs,
                 &participants,
                 rng,
@@ -1359,7 +2038,8 @@ mod test {
             .unwrap()
             * p5_secret_key.key;
 
-        let group_key = GroupVerifyingKey::new(Projective::generator().mul(group_secret_key));
+        let group_key =
+            GroupVerifyingKey::new(Element(Projective::generator().mul(group_secret_key)));
 
         assert!(p5_group_key == group_key)
     }
@@ -1367,30 +2047,24 @@ mod test {
     #[test]
     fn keygen_2_out_of_3() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params = ThresholdParameters::new(3, 2);
+            let params = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (p1, p1coeffs, p1_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
             let (p2, p2coeffs, p2_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 2, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
             let (p3, p3coeffs, p3_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 3, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
 
-            p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p1.index, p1.public_key().unwrap())?;
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p2.index, p2.public_key().unwrap())?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p3.index, p3.public_key().unwrap())?;
+            p1.proof_of_secret_key()
+                .verify(p1.index, &p1.public_key().unwrap())?;
+            p2.proof_of_secret_key()
+                .verify(p2.index, &p2.public_key().unwrap())?;
+            p3.proof_of_secret_key()
+                .verify(p3.index, &p3.public_key().unwrap())?;
 
-            let participants: Vec<Participant<Secp256k1Sha256>> =
+            let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![p1.clone(), p2.clone(), p3.clone()];
             let (p1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -1457,36 +2131,77 @@ mod test {
         assert!(do_test().is_ok());
     }
 
+    #[test]
+    fn keygen_with_stripped_participants() {
+        let params = ThresholdParameters::new(4, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1coeffs, p1_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        let (p2, _, _) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
+        let (p3, _, _) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
+        let (p4, _, _) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 4, rng).unwrap();
+
+        // Participants missing their commitments and proof of knowledge are
+        // reported as misbehaving, instead of aborting the session.
+        let mut p2 = p2.into_any();
+        let mut p3 = p3.into_any();
+        p2.material = None;
+        p3.material = None;
+        assert!(p2.public_key().is_none());
+
+        let participants = vec![p1.clone().into_any(), p2, p3, p4.into_any()];
+        let (_p1_state, participant_lists) =
+            DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                &params,
+                &p1_dh_sk,
+                &p1.index,
+                &p1coeffs,
+                &participants,
+                rng,
+            )
+            .unwrap();
+        assert_eq!(participant_lists.misbehaving_participants, Some(vec![2, 3]));
+
+        // Garbage bytes are rejected by deserialization.
+        for length in 0..256 {
+            let bytes: Vec<u8> = (0..length).map(|_| OsRng.gen()).collect();
+
+            let _ = Participant::<Secp256k1Sha256, DealerRole>::from_bytes(&bytes);
+            let _ = EncryptedSecretShare::<Secp256k1Sha256>::from_bytes(&bytes);
+            let _ = Complaint::<Secp256k1Sha256>::from_bytes(&bytes);
+            let _ = SealedComplaint::<Secp256k1Sha256>::from_bytes(&bytes);
+            let _ = DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::from_bytes(&bytes);
+        }
+    }
+
     #[test]
     fn keygen_static_2_out_of_3_with_common_participants() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params = ThresholdParameters::new(3, 2);
+            let params = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (dealer1, dealer1coeffs, dealer1_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
             let (dealer2, dealer2coeffs, dealer2_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 2, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
             let (dealer3, dealer3coeffs, dealer3_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 3, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
 
             dealer1
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(dealer1.index, dealer1.public_key().unwrap())?;
+                .proof_of_secret_key()
+                .verify(dealer1.index, &dealer1.public_key().unwrap())?;
             dealer2
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(dealer2.index, dealer2.public_key().unwrap())?;
+                .proof_of_secret_key()
+                .verify(dealer2.index, &dealer2.public_key().unwrap())?;
             dealer3
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(dealer3.index, dealer3.public_key().unwrap())?;
+                .proof_of_secret_key()
+                .verify(dealer3.index, &dealer3.public_key().unwrap())?;
 
-            let dealers: Vec<Participant<Secp256k1Sha256>> =
+            let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![dealer1.clone(), dealer2.clone(), dealer3.clone()];
             let (dealer1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -1559,8 +2274,11 @@ mod test {
             // Dealer 3 is also a participant of the next set of signers
             let (signer3, signer3_dh_sk) = (dealer3, dealer3_dh_sk);
 
-            let signers: Vec<Participant<Secp256k1Sha256>> =
-                vec![signer1.clone(), signer2.clone(), signer3.clone()];
+            let signers: Vec<Participant<Secp256k1Sha256>> = vec![
+                signer1.clone().into_any(),
+                signer2.clone().into_any(),
+                signer3.clone().into_any(),
+            ];
 
             let (dealer1_for_signers, dealer1_encrypted_shares_for_signers, _participant_lists) =
                 Participant::reshare(&params, dealer1_secret_key, &signers, rng)?;
@@ -1569,7 +2287,7 @@ mod test {
             let (dealer3_for_signers, dealer3_encrypted_shares_for_signers, _participant_lists) =
                 Participant::reshare(&params, dealer3_secret_key, &signers, rng)?;
 
-            let dealers: Vec<Participant<Secp256k1Sha256>> = vec![
+            let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec![
                 dealer1_for_signers,
                 dealer2_for_signers,
                 dealer3_for_signers,
@@ -1641,33 +2359,30 @@ mod test {
     #[test]
     fn keygen_static_2_out_of_3_into_3_out_of_5() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params_dealers = ThresholdParameters::new(3, 2);
+            let params_dealers = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (dealer1, dealer1coeffs, dealer1_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params_dealers, 1, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params_dealers, 1, rng)
+                    .unwrap();
             let (dealer2, dealer2coeffs, dealer2_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params_dealers, 2, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params_dealers, 2, rng)
+                    .unwrap();
             let (dealer3, dealer3coeffs, dealer3_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params_dealers, 3, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params_dealers, 3, rng)
+                    .unwrap();
 
             dealer1
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(dealer1.index, dealer1.public_key().unwrap())?;
+                .proof_of_secret_key()
+                .verify(dealer1.index, &dealer1.public_key().unwrap())?;
             dealer2
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(dealer2.index, dealer2.public_key().unwrap())?;
+                .proof_of_secret_key()
+                .verify(dealer2.index, &dealer2.public_key().unwrap())?;
             dealer3
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(dealer3.index, dealer3.public_key().unwrap())?;
+                .proof_of_secret_key()
+                .verify(dealer3.index, &dealer3.public_key().unwrap())?;
 
-            let dealers: Vec<Participant<Secp256k1Sha256>> =
+            let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![dealer1.clone(), dealer2.clone(), dealer3.clone()];
             let (dealer1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -1735,7 +2450,7 @@ mod test {
             assert!(dealer1_group_key == dealer2_group_key);
             assert!(dealer2_group_key == dealer3_group_key);
 
-            let params_signers = ThresholdParameters::<Secp256k1Sha256>::new(5, 3);
+            let params_signers = ThresholdParameters::<Secp256k1Sha256>::new(5, 3).unwrap();
             let (signer1, signer1_dh_sk) =
                 Participant::new_signer(&params_signers, 1, rng).unwrap();
             let (signer2, signer2_dh_sk) =
@@ -1747,7 +2462,7 @@ mod test {
             let (signer5, signer5_dh_sk) =
                 Participant::new_signer(&params_signers, 5, rng).unwrap();
 
-            let signers: Vec<Participant<Secp256k1Sha256>> = vec![
+            let signers: Vec<Participant<Secp256k1Sha256, SignerRole>> = vec![
                 signer1.clone(),
                 signer2.clone(),
                 signer3.clone(),
@@ -1762,7 +2477,7 @@ mod test {
             let (dealer3_for_signers, dealer3_encrypted_shares_for_signers, _participant_lists) =
                 Participant::reshare(&params_signers, dealer3_secret_key, &signers, rng)?;
 
-            let dealers: Vec<Participant<Secp256k1Sha256>> = vec![
+            let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec![
                 dealer1_for_signers,
                 dealer2_for_signers,
                 dealer3_for_signers,
@@ -1874,48 +2589,97 @@ mod test {
         let original_share = SecretShare::<Secp256k1Sha256> {
             sender_index: 1,
             receiver_index: 2,
-            polynomial_evaluation: Fr::rand(&mut rng),
+            polynomial_evaluation: crate::Scalar(Fr::rand(&mut rng)),
         };
 
         let mut key = [0u8; 32];
         rng.fill(&mut key);
 
-        let encrypted_share = encrypt_share(&original_share, &key, rng).unwrap();
-        let decrypted_share = decrypt_share::<Secp256k1Sha256>(&encrypted_share, &key);
+        let mut session_id = [0u8; 32];
+        rng.fill(&mut session_id);
+
+        let mut salt = [0u8; 32];
+        rng.fill(&mut salt);
+
+        let encrypted_share = encrypt_share(&original_share, &key, &session_id, &salt).unwrap();
+        assert_eq!(encrypted_share.salt, salt);
+        let decrypted_share = decrypt_share::<Secp256k1Sha256>(&encrypted_share, &key, &session_id);
 
         assert!(decrypted_share.is_ok());
-        assert!(
-            original_share.polynomial_evaluation == decrypted_share.unwrap().polynomial_evaluation
+        assert!(original_share.expose_secret() == decrypted_share.unwrap().expose_secret());
+
+        // The nonce is deterministically derived from the session context and salt.
+        let expected_nonce = EncryptedSecretShare::<Secp256k1Sha256>::derive_nonce(
+            &session_id,
+            &salt,
+            1,
+            2,
+            SHARE_DISTRIBUTION_ROUND,
+        )
+        .unwrap();
+        assert_eq!(encrypted_share.nonce, expected_nonce);
+        assert_eq!(
+            encrypt_share(&original_share, &key, &session_id, &salt)
+                .unwrap()
+                .nonce,
+            expected_nonce
+        );
+
+        // Another ceremony with the same keys draws another salt, hence another nonce.
+        let mut other_salt = salt;
+        other_salt[0] ^= 1;
+        assert_ne!(
+            encrypt_share(&original_share, &key, &session_id, &other_salt)
+                .unwrap()
+                .nonce,
+            expected_nonce
+        );
+
+        // Shares whose salt was altered are rejected.
+        let mut wrong_salt_share = encrypted_share.clone();
+        wrong_salt_share.salt = other_salt.to_vec();
+        assert_eq!(
+            decrypt_share::<Secp256k1Sha256>(&wrong_salt_share, &key, &session_id),
+            Err(Error::InvalidNonce)
+        );
+
+        // Shares with a nonce not matching the session context are rejected.
+        let mut other_session_id = session_id;
+        other_session_id[0] ^= 1;
+        assert_eq!(
+            decrypt_share::<Secp256k1Sha256>(&encrypted_share, &key, &other_session_id),
+            Err(Error::InvalidNonce)
+        );
+
+        let mut wrong_nonce_share = encrypted_share.clone();
+        wrong_nonce_share.nonce[0] ^= 1;
+        assert_eq!(
+            decrypt_share::<Secp256k1Sha256>(&wrong_nonce_share, &key, &session_id),
+            Err(Error::InvalidNonce)
         );
     }
 
     #[test]
     fn keygen_2_out_of_3_with_random_keys() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params = ThresholdParameters::new(3, 2);
+            let params = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (p1, p1coeffs, dh_sk1) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
             let (p2, p2coeffs, dh_sk2) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 2, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
             let (p3, p3coeffs, dh_sk3) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 3, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
 
-            p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p1.index, p1.public_key().unwrap())?;
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p2.index, p2.public_key().unwrap())?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p3.index, p3.public_key().unwrap())?;
+            p1.proof_of_secret_key()
+                .verify(p1.index, &p1.public_key().unwrap())?;
+            p2.proof_of_secret_key()
+                .verify(p2.index, &p2.public_key().unwrap())?;
+            p3.proof_of_secret_key()
+                .verify(p3.index, &p3.public_key().unwrap())?;
 
-            let participants: Vec<Participant<Secp256k1Sha256>> =
+            let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![p1.clone(), p2.clone(), p3.clone()];
             let (p1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -1985,30 +2749,24 @@ mod test {
     #[test]
     fn keygen_verify_complaint() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params = ThresholdParameters::new(3, 2);
+            let params = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (p1, p1coeffs, dh_sk1) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
             let (p2, p2coeffs, dh_sk2) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 2, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
             let (p3, p3coeffs, dh_sk3) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 3, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
 
-            p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p1.index, p1.public_key().unwrap())?;
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p2.index, p2.public_key().unwrap())?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p3.index, p3.public_key().unwrap())?;
+            p1.proof_of_secret_key()
+                .verify(p1.index, &p1.public_key().unwrap())?;
+            p2.proof_of_secret_key()
+                .verify(p2.index, &p2.public_key().unwrap())?;
+            p3.proof_of_secret_key()
+                .verify(p3.index, &p3.public_key().unwrap())?;
 
-            let participants: Vec<Participant<Secp256k1Sha256>> =
+            let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![p1.clone(), p2.clone(), p3.clone()];
             let (p1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -2048,7 +2806,7 @@ mod test {
             // Wrong decryption from nonce
             {
                 let mut wrong_encrypted_secret_share = p1_their_encrypted_secret_shares[1].clone();
-                wrong_encrypted_secret_share.nonce = [42; 16];
+                wrong_encrypted_secret_share.nonce = [42; 12];
                 let p1_my_encrypted_secret_shares = vec![
                     p1_their_encrypted_secret_shares[0].clone(),
                     p2_their_encrypted_secret_shares[0].clone(),
@@ -2154,10 +2912,11 @@ mod test {
                     &SecretShare::<Secp256k1Sha256> {
                         sender_index: 1,
                         receiver_index: 2,
-                        polynomial_evaluation: Fr::from(42u32),
+                        polynomial_evaluation: crate::Scalar(Fr::from(42u32)),
                     },
                     &dh_key_bytes[..],
-                    rng,
+                    &share_session_id(&p1.dh_public_key, &p2.dh_public_key)?,
+                    &[],
                 )
                 .unwrap();
                 let p1_my_encrypted_secret_shares = vec![
@@ -2238,27 +2997,21 @@ mod test {
     #[test]
     fn test_serialization() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params = ThresholdParameters::new(3, 2);
+            let params = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, rng).unwrap();
             let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, rng).unwrap();
             let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, rng).unwrap();
 
-            p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p1.index, p1.public_key().unwrap())?;
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p2.index, p2.public_key().unwrap())?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p3.index, p3.public_key().unwrap())?;
+            p1.proof_of_secret_key()
+                .verify(p1.index, &p1.public_key().unwrap())?;
+            p2.proof_of_secret_key()
+                .verify(p2.index, &p2.public_key().unwrap())?;
+            p3.proof_of_secret_key()
+                .verify(p3.index, &p3.public_key().unwrap())?;
 
-            let participants: Vec<Participant<Secp256k1Sha256>> =
+            let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![p1.clone(), p2.clone(), p3.clone()];
             let (p1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -2325,9 +3078,9 @@ mod test {
                 let bytes = p1_dh_sk.to_bytes()?;
                 assert_eq!(p1_dh_sk, DiffieHellmanPrivateKey::from_bytes(&bytes)?);
 
-                let bytes = p1.proof_of_secret_key.as_ref().unwrap().to_bytes()?;
+                let bytes = p1.proof_of_secret_key().to_bytes()?;
                 assert_eq!(
-                    p1.proof_of_secret_key.unwrap(),
+                    p1.proof_of_secret_key().clone(),
                     NizkPokOfSecretKey::from_bytes(&bytes)?
                 );
 
@@ -2377,7 +3130,7 @@ mod test {
 
             {
                 let wrong_encrypted_secret_share =
-                    EncryptedSecretShare::new(1, 2, [0; 16], vec![0]);
+                    EncryptedSecretShare::new(1, 2, [0; 12], vec![0], vec![]);
 
                 let p1_my_encrypted_secret_shares = vec![
                     p1_their_encrypted_secret_shares[0].clone(),
@@ -2408,6 +3161,25 @@ mod test {
 
                     assert!(bad_index == 1);
 
+                    // Complaints can be sealed to a designated adjudicator.
+                    let adjudicator_key =
+                        DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut OsRng));
+                    let adjudicator_public_key = adjudicator_key.dh_public_key()?;
+                    let sealed_complaint = complaints[0].seal(&adjudicator_public_key, rng)?;
+                    let bytes = sealed_complaint.to_bytes()?;
+                    assert_eq!(sealed_complaint, SealedComplaint::from_bytes(&bytes)?);
+
+                    let opened_complaint = sealed_complaint.open(&adjudicator_key)?;
+                    assert_eq!(opened_complaint, complaints[0]);
+                    assert_eq!(
+                        p3_state.blame(&wrong_encrypted_secret_share, &opened_complaint),
+                        1
+                    );
+
+                    let other_key =
+                        DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut OsRng));
+                    assert!(sealed_complaint.open(&other_key).is_err());
+
                     let (p1_group_key, _p1_secret_key) = p1_state.finish()?;
                     let (p3_group_key, _p3_secret_key) = p3_state.finish()?;
 
@@ -2436,30 +3208,24 @@ mod test {
     #[test]
     fn individual_public_key_share() {
         fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-            let params = ThresholdParameters::new(3, 2);
+            let params = ThresholdParameters::new(3, 2).unwrap();
             let rng = OsRng;
 
             let (p1, p1coeffs, p1_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 1, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
             let (p2, p2coeffs, p2_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 2, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 2, rng).unwrap();
             let (p3, p3coeffs, p3_dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, 3, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 3, rng).unwrap();
 
-            p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p1.index, p1.public_key().unwrap())?;
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p2.index, p2.public_key().unwrap())?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(p3.index, p3.public_key().unwrap())?;
+            p1.proof_of_secret_key()
+                .verify(p1.index, &p1.public_key().unwrap())?;
+            p2.proof_of_secret_key()
+                .verify(p2.index, &p2.public_key().unwrap())?;
+            p3.proof_of_secret_key()
+                .verify(p3.index, &p3.public_key().unwrap())?;
 
-            let participants: Vec<Participant<Secp256k1Sha256>> =
+            let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> =
                 vec![p1.clone(), p2.clone(), p3.clone()];
             let (p1_state, _participant_lists) =
                 DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
@@ -2529,9 +3295,9 @@ mod test {
 
             // The order does not matter
             let commitments = [
-                p2.commitments.unwrap(),
-                p3.commitments.unwrap(),
-                p1.commitments.unwrap(),
+                p2.commitments().clone(),
+                p3.commitments().clone(),
+                p1.commitments().clone(),
             ];
 
             assert!(p1_public_key.verify(&commitments).is_ok());
@@ -2540,20 +3306,1037 @@ mod test {
 
             assert!(p1_public_key.verify(&commitments[1..]).is_err());
 
+            // All verifying keys can be checked at once.
+            let mut verifying_keys = vec![
+                p1_public_key.clone(),
+                p2_public_key.clone(),
+                p3_public_key.clone(),
+            ];
+            assert!(IndividualVerifyingKey::verify_all(&verifying_keys, &commitments).is_ok());
+            verifying_keys[1].share = verifying_keys[0].share;
+            assert_eq!(
+                IndividualVerifyingKey::verify_all(&verifying_keys, &commitments),
+                Err(Error::MisbehavingParticipants(vec![2]))
+            );
+
             // Check that the generated IndividualVerifyingKey from other participants match
             let p1_recovered_public_key =
-                IndividualVerifyingKey::generate_from_commitments(1, &commitments);
+                IndividualVerifyingKey::generate_from_commitments(1, &commitments).unwrap();
             let p2_recovered_public_key =
-                IndividualVerifyingKey::generate_from_commitments(2, &commitments);
+                IndividualVerifyingKey::generate_from_commitments(2, &commitments).unwrap();
             let p3_recovered_public_key =
-                IndividualVerifyingKey::generate_from_commitments(3, &commitments);
+                IndividualVerifyingKey::generate_from_commitments(3, &commitments).unwrap();
 
             assert_eq!(p1_public_key, p1_recovered_public_key);
             assert_eq!(p2_public_key, p2_recovered_public_key);
             assert_eq!(p3_public_key, p3_recovered_public_key);
 
+            assert_eq!(
+                IndividualVerifyingKey::generate_all_from_commitments(&[1, 2, 3], &commitments)
+                    .unwrap(),
+                vec![p1_public_key, p2_public_key, p3_public_key]
+            );
+
             Ok(())
         }
         assert!(do_test().is_ok());
     }
+
+    #[test]
+    fn share_receipts_2_out_of_3() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                let my_encrypted_secret_shares = states
+                    .iter()
+                    .map(|state| state.their_encrypted_secret_shares().unwrap()[i].clone())
+                    .collect();
+                states[i]
+                    .clone()
+                    .to_round_two(my_encrypted_secret_shares, rng)
+                    .unwrap()
+            })
+            .collect();
+
+        let receipts: Vec<ShareReceipt<Secp256k1Sha256>> = states
+            .iter()
+            .flat_map(|state| state.share_receipts(rng).unwrap())
+            .collect();
+        assert_eq!(receipts.len(), 9);
+
+        for (i, state) in states.iter().enumerate() {
+            let my_receipts: Vec<_> = receipts
+                .iter()
+                .filter(|receipt| receipt.dealer_index() == i as u32 + 1)
+                .cloned()
+                .collect();
+            assert!(state.verify_share_receipts(&my_receipts).is_ok());
+
+            // Receipts can be verified by an external registry.
+            for receipt in my_receipts.iter() {
+                let recipient = &participants[receipt.recipient_index() as usize - 1];
+                let bytes = receipt.to_bytes().unwrap();
+                assert!(ShareReceipt::from_bytes(&bytes)
+                    .unwrap()
+                    .verify(&recipient.dh_public_key, participants[i].commitments())
+                    .is_ok());
+            }
+        }
+
+        // Missing or forged receipts are attributed to their recipient.
+        let mut p1_receipts: Vec<_> = receipts
+            .iter()
+            .filter(|receipt| receipt.dealer_index() == 1 && receipt.recipient_index() != 2)
+            .cloned()
+            .collect();
+        p1_receipts[1].s += Fr::from(1u8);
+        assert_eq!(
+            states[0].verify_share_receipts(&p1_receipts),
+            Err(Error::MisbehavingParticipants(vec![2, 3]))
+        );
+    }
+
+    #[test]
+    fn qualified_set_2_out_of_3() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let my_encrypted_secret_shares = |i: usize| -> Vec<EncryptedSecretShare<Secp256k1Sha256>> {
+            states
+                .iter()
+                .map(|state| state.their_encrypted_secret_shares().unwrap()[i].clone())
+                .collect()
+        };
+
+        // Confirmations are counted once per recipient, in any order.
+        let receipts: Vec<ShareReceipt<Secp256k1Sha256>> = (0..3)
+            .flat_map(|i| {
+                states[i]
+                    .clone()
+                    .to_round_two(my_encrypted_secret_shares(i), rng)
+                    .unwrap()
+                    .share_receipts(rng)
+                    .unwrap()
+            })
+            .collect();
+        let policy = QualificationPolicy::new(1, 3);
+        let mut tracker = states[0].qualification_tracker(policy).unwrap();
+        let mut other_tracker = states[2].qualification_tracker(policy).unwrap();
+        for receipt in receipts.iter() {
+            tracker.record_receipt(receipt).unwrap();
+        }
+        for receipt in receipts.iter().rev().chain(receipts.iter()) {
+            other_tracker.record_receipt(receipt).unwrap();
+        }
+        assert_eq!(other_tracker.confirmation_count(1), 3);
+        let qualified_set = tracker.qualified_set().unwrap();
+        assert_eq!(qualified_set.qualified(), &[1, 2, 3]);
+        assert_eq!(other_tracker.qualified_set().unwrap(), qualified_set);
+
+        let mut forged_receipt = receipts[0].clone();
+        forged_receipt.s += Fr::from(1u8);
+        assert!(tracker.record_receipt(&forged_receipt).is_err());
+
+        let mut tracker = states[1].qualification_tracker(policy).unwrap();
+        for receipt in receipts
+            .iter()
+            .filter(|receipt| !(receipt.dealer_index() == 3 && receipt.recipient_index() == 1))
+        {
+            tracker.record_receipt(receipt).unwrap();
+        }
+        let qualified_set = tracker.qualified_set().unwrap();
+        assert_eq!(qualified_set.qualified(), &[1, 2]);
+        assert_eq!(qualified_set.disqualified(), &[3]);
+        assert!(!qualified_set.is_qualified(3));
+
+        // Valid complaints disqualify their accused dealer, unfounded ones their maker.
+        let mut shares = my_encrypted_secret_shares(1);
+        let honest_share = shares[0].clone();
+        shares[0].encrypted_polynomial_evaluation[0] ^= 1;
+        let mut processor = states[1].clone().begin_round_two();
+        let complaint = processor.process_share(&shares[0], rng).unwrap().unwrap();
+
+        let mut tracker = states[2]
+            .qualification_tracker(QualificationPolicy::default())
+            .unwrap();
+        assert_eq!(tracker.record_complaint(&shares[0], &complaint), Ok(1));
+        assert_eq!(tracker.record_complaint(&shares[0], &complaint), Ok(1));
+        assert_eq!(tracker.complaint_count(1), 1);
+        assert_eq!(tracker.qualified_set().unwrap().qualified(), &[2, 3]);
+        assert_eq!(
+            tracker.record_complaint(&shares[1], &complaint),
+            Err(Error::MisroutedShare(2, 2))
+        );
+
+        let mut tracker = states[0]
+            .qualification_tracker(QualificationPolicy::new(2, 0))
+            .unwrap();
+        assert_eq!(tracker.record_complaint(&shares[0], &complaint), Ok(1));
+        assert_eq!(tracker.qualified_set().unwrap().qualified(), &[1, 2, 3]);
+
+        let mut tracker = states[2]
+            .qualification_tracker(QualificationPolicy::default())
+            .unwrap();
+        assert_eq!(tracker.record_complaint(&honest_share, &complaint), Ok(2));
+        assert_eq!(tracker.qualified_set().unwrap().disqualified(), &[2]);
+
+        // Sessions with fewer than t qualified dealers fail.
+        let tracker = states[0]
+            .qualification_tracker(QualificationPolicy::new(1, 1))
+            .unwrap();
+        assert_eq!(
+            tracker.qualified_set(),
+            Err(Error::TooManyInvalidParticipants(vec![1, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn complaint_queue_2_out_of_4() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(4, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=4 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..4)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+
+        // Dealers 1 and 2 send corrupted shares to participants 3 and 4, which complain
+        // twice against each of them, in two rounds.
+        let mut complaints = Vec::new();
+        for receiver in 2..4 {
+            for dealer in 0..2 {
+                let mut share =
+                    states[dealer].their_encrypted_secret_shares().unwrap()[receiver].clone();
+                share.encrypted_polynomial_evaluation[0] ^= 1;
+                for round in 1..=2 {
+                    for _ in 0..2 {
+                        let mut processor = states[receiver].clone().begin_round_two();
+                        let complaint = processor.process_share(&share, rng).unwrap().unwrap();
+                        complaints.push((round, share.clone(), complaint));
+                    }
+                }
+            }
+        }
+
+        let tracker = states[0]
+            .qualification_tracker(QualificationPolicy::default())
+            .unwrap();
+        let limits = ComplaintQueueLimits::new(5, 3);
+        let process = |complaints: &[(u32, EncryptedSecretShare<_>, Complaint<_>)]| {
+            let mut tracker = tracker.clone();
+            let mut queue = tracker.complaint_queue(limits);
+            for (round, share, complaint) in complaints.iter() {
+                queue.push(*round, share, complaint).unwrap();
+            }
+            assert_eq!(queue.len(), 5);
+            let metrics = queue.metrics();
+            let verdicts = queue.process(&mut tracker).unwrap();
+            assert!(queue.is_empty());
+
+            (verdicts, metrics, tracker.qualified_set())
+        };
+
+        // Complaints are queued, deduplicated and processed regardless of their order.
+        let (verdicts, metrics, qualified_set) = process(&complaints);
+        assert_eq!(
+            verdicts
+                .iter()
+                .map(|v| (v.round, v.maker_index, v.accused_index, v.blamed))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 3, 1, 1),
+                (1, 3, 2, 2),
+                (1, 4, 1, 1),
+                (1, 4, 2, 2),
+                (2, 3, 1, 1)
+            ]
+        );
+        assert_eq!(metrics.received, 16);
+        assert_eq!(metrics.queued, 5);
+        assert_eq!(metrics.duplicates + metrics.dropped, 11);
+        assert_eq!(qualified_set.unwrap().qualified(), &[3, 4]);
+
+        let mut reversed = complaints.clone();
+        reversed.reverse();
+        let mut interleaved = complaints.clone();
+        interleaved.sort_by_key(|(round, _, complaint)| {
+            (complaint.accused_index, core::cmp::Reverse(*round))
+        });
+        for permutation in [reversed, interleaved] {
+            let (other_verdicts, _, _) = process(&permutation);
+            assert_eq!(other_verdicts, verdicts);
+        }
+
+        // Misrouted and unverifiable complaints are rejected.
+        let mut queue = tracker.complaint_queue(limits);
+        let (_, share, complaint) = &complaints[0];
+        let mut forged = complaint.clone();
+        forged.proof.z += Fr::from(1u8);
+        assert_eq!(
+            queue.push(1, share, &forged),
+            Err(Error::ComplaintVerificationError)
+        );
+        let mut misrouted = complaint.clone();
+        misrouted.maker_index = 4;
+        assert_eq!(
+            queue.push(1, share, &misrouted),
+            Err(Error::MisroutedShare(1, 3))
+        );
+        assert_eq!(
+            queue.push(1, share, complaint),
+            Ok(ComplaintAdmission::Queued)
+        );
+        assert_eq!(
+            queue.push(1, share, complaint),
+            Ok(ComplaintAdmission::Duplicate)
+        );
+        assert_eq!(queue.metrics().rejected, 2);
+    }
+
+    #[test]
+    fn streaming_round_two_2_out_of_3() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let my_encrypted_secret_shares = |i: usize| -> Vec<EncryptedSecretShare<Secp256k1Sha256>> {
+            states
+                .iter()
+                .map(|state| state.their_encrypted_secret_shares().unwrap()[i].clone())
+                .collect()
+        };
+
+        // Shares can be processed one at a time, in any order.
+        let mut processor = states[0].clone().begin_round_two();
+        let complaints = processor
+            .process_shares(my_encrypted_secret_shares(0).into_iter().rev(), rng)
+            .unwrap();
+        assert!(complaints.is_empty());
+        assert_eq!(processor.processed_shares(), 3);
+        assert!(processor
+            .process_share(&my_encrypted_secret_shares(0)[0], rng)
+            .is_err());
+        let (group_key, _) = processor.finish().unwrap().finish().unwrap();
+
+        let (other_group_key, _) = states[2]
+            .clone()
+            .to_round_two(my_encrypted_secret_shares(2), rng)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(group_key, other_group_key);
+
+        // Complaints are emitted as soon as an invalid share is processed.
+        let mut shares = my_encrypted_secret_shares(1);
+        shares[2].encrypted_polynomial_evaluation[0] ^= 1;
+        let mut processor = states[1].clone().begin_round_two();
+        assert!(processor.process_share(&shares[0], rng).unwrap().is_none());
+        let complaint = processor.process_share(&shares[2], rng).unwrap().unwrap();
+        assert_eq!(complaint.accused_index, 3);
+        assert!(complaint
+            .verify(
+                &participants[1].dh_public_key.element(),
+                &participants[2].dh_public_key.element()
+            )
+            .is_ok());
+
+        // Sessions with missing shares or complaints cannot progress.
+        assert!(
+            matches!(processor.finish(), Err(Error::Complaint(complaints)) if complaints == vec![complaint])
+        );
+        let mut processor = states[1].clone().begin_round_two();
+        processor.process_share(&shares[0], rng).unwrap();
+        assert_eq!(processor.finish().unwrap_err(), Error::MissingShares);
+    }
+
+    #[test]
+    fn share_bundles_2_out_of_3() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let bundles: Vec<_> = states
+            .iter()
+            .zip(participants.iter())
+            .map(|(state, participant)| {
+                ShareBundle::new(
+                    participant.index,
+                    state.their_encrypted_secret_shares().unwrap(),
+                    &participants,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        assert_eq!(bundles[0].sender_index(), 1);
+        assert_eq!(bundles[0].recipients(), vec![1, 2, 3]);
+        assert_eq!(
+            bundles[0].for_recipient(2),
+            Some(&states[0].their_encrypted_secret_shares().unwrap()[1])
+        );
+        assert!(bundles[0].for_recipient(4).is_none());
+        assert_eq!(
+            ShareBundle::from_bytes(&bundles[1].to_bytes().unwrap()).unwrap(),
+            bundles[1]
+        );
+
+        // Shares can only be bundled for their own recipients.
+        assert_eq!(
+            ShareBundle::new(
+                2,
+                states[0].their_encrypted_secret_shares().unwrap(),
+                &participants
+            )
+            .unwrap_err(),
+            Error::MisroutedShare(1, 1)
+        );
+        assert_eq!(
+            ShareBundle::new(
+                1,
+                states[0].their_encrypted_secret_shares().unwrap(),
+                &participants[..2]
+            )
+            .unwrap_err(),
+            Error::MisroutedShare(1, 3)
+        );
+
+        let mut group_keys = Vec::new();
+        for state in states.iter() {
+            let mut processor = state.clone().begin_round_two();
+            for bundle in bundles.iter() {
+                assert!(processor.process_bundle(bundle, rng).unwrap().is_none());
+            }
+            group_keys.push(processor.finish().unwrap().finish().unwrap().0);
+        }
+        assert!(group_keys.iter().all(|key| *key == group_keys[0]));
+
+        // A share addressed to the right index but another DH public key is detected
+        // as misrouted, without blaming its dealer.
+        let mut other_participants = participants.clone();
+        other_participants[1] = Participant::new_dealer(&params, 2, rng).unwrap().0;
+        let misrouted_bundle = ShareBundle::new(
+            1,
+            states[0].their_encrypted_secret_shares().unwrap(),
+            &other_participants,
+        )
+        .unwrap();
+        let mut processor = states[1].clone().begin_round_two();
+        assert_eq!(
+            processor
+                .process_bundle(&misrouted_bundle, rng)
+                .unwrap_err(),
+            Error::MisroutedShare(1, 2)
+        );
+        assert_eq!(processor.processed_shares(), 0);
+        assert!(processor
+            .process_bundle(&bundles[0], rng)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn verify_resharing_identifies_inconsistent_dealers() {
+        let mut rng = OsRng;
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(5, 3).unwrap();
+
+        // The secret polynomial of the previous group, and the resulting dealers' commitments.
+        let coefficients: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let group_key =
+            GroupVerifyingKey::new(Element(Projective::generator().mul(coefficients[0])));
+        let commitments: Vec<VerifiableSecretSharingCommitment<Secp256k1Sha256>> = (1..=5u32)
+            .map(|index| {
+                let x = Fr::from(index);
+                let secret_share = coefficients[0] + coefficients[1] * x + coefficients[2] * x * x;
+
+                VerifiableSecretSharingCommitment {
+                    index,
+                    points: vec![
+                        Projective::generator().mul(secret_share),
+                        Projective::rand(&mut rng),
+                    ],
+                }
+            })
+            .collect();
+
+        assert_eq!(
+            verify_resharing(&params, &commitments, &group_key),
+            Ok(vec![])
+        );
+
+        // Dealers 1 and 4 do not reshare their former secret share.
+        let mut tampered_commitments = commitments.clone();
+        tampered_commitments[0].points[0] = Projective::rand(&mut rng);
+        tampered_commitments[3].points[0] = Projective::rand(&mut rng);
+        assert_eq!(
+            verify_resharing(&params, &tampered_commitments, &group_key),
+            Ok(vec![1, 4])
+        );
+
+        // Fewer than t consistent dealers cannot reconstruct the previous group key.
+        tampered_commitments[1].points[0] = Projective::rand(&mut rng);
+        assert_eq!(
+            verify_resharing(&params, &tampered_commitments, &group_key),
+            Err(Error::TooManyInvalidParticipants(vec![1, 2, 3, 4, 5]))
+        );
+
+        // Dealers reusing an index are inconsistent as well.
+        let mut duplicated_commitments = commitments.clone();
+        duplicated_commitments.push(commitments[2].clone());
+        assert_eq!(
+            verify_resharing(&params, &duplicated_commitments, &group_key),
+            Ok(vec![3])
+        );
+    }
+
+    #[test]
+    fn participant_admission_2_out_of_3() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let mut group_key = None;
+        let mut signing_keys = Vec::new();
+        for i in 0..3 {
+            let my_encrypted_secret_shares = states
+                .iter()
+                .map(|state| state.their_encrypted_secret_shares().unwrap()[i].clone())
+                .collect();
+            let (key, signing_key) = states[i]
+                .clone()
+                .to_round_two(my_encrypted_secret_shares, rng)
+                .unwrap()
+                .finish()
+                .unwrap();
+            group_key = Some(key);
+            signing_keys.push(signing_key);
+        }
+        let group_key = group_key.unwrap();
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+
+        // Participants 1 and 3 admit a new participant of index 4.
+        let (new_participant, new_dh_sk) = Participant::<Secp256k1Sha256, SignerRole>::new_signer(
+            &ThresholdParameters::new(4, 2).unwrap(),
+            4,
+            rng,
+        )
+        .unwrap();
+        let helpers = [participants[0].clone(), participants[2].clone()];
+        let admission = ParticipantAdmission::new(&package, &new_participant, &helpers).unwrap();
+
+        let contributions: Vec<_> = [0, 2]
+            .iter()
+            .map(|&i| {
+                let contribution = admission
+                    .contribute(&signing_keys[i], &dh_secret_keys[i], rng)
+                    .unwrap();
+                AdmissionContribution::from_bytes(&contribution.to_bytes().unwrap()).unwrap()
+            })
+            .collect();
+        for contribution in contributions.iter() {
+            assert!(admission.verify_contribution(contribution).is_ok());
+        }
+        let shares: Vec<_> = [0, 2]
+            .iter()
+            .map(|&i| {
+                let share = admission
+                    .respond(&signing_keys[i], &dh_secret_keys[i], &contributions, rng)
+                    .unwrap();
+                AdmissionShare::from_bytes(&share.to_bytes().unwrap()).unwrap()
+            })
+            .collect();
+        let new_signing_key = admission
+            .finish(&new_dh_sk, &contributions, &shares)
+            .unwrap();
+        assert_eq!(new_signing_key.index, 4);
+        assert_eq!(
+            new_signing_key.to_public(),
+            admission.new_verifying_key().unwrap()
+        );
+
+        // The new share is consistent with the shares of the other members.
+        let lambda_2 = calculate_lagrange_coefficients::<Secp256k1Sha256>(2, &[2, 4]).unwrap();
+        let lambda_4 = calculate_lagrange_coefficients::<Secp256k1Sha256>(4, &[2, 4]).unwrap();
+        assert_eq!(
+            Projective::generator()
+                * (lambda_2 * signing_keys[1].key + lambda_4 * new_signing_key.key),
+            group_key.key
+        );
+        let admitted_package = admission.admitted_package().unwrap();
+        assert_eq!(admitted_package.parameters().n, 4);
+        assert_eq!(
+            admitted_package.verifying_key(4),
+            Some(&new_signing_key.to_public())
+        );
+
+        // Tampered parts are attributed to their helper.
+        let mut tampered_contributions = contributions.clone();
+        tampered_contributions[1].encrypted_parts[0].encrypted_polynomial_evaluation[0] ^= 1;
+        assert_eq!(
+            admission
+                .respond(
+                    &signing_keys[0],
+                    &dh_secret_keys[0],
+                    &tampered_contributions,
+                    rng
+                )
+                .unwrap_err(),
+            Error::InvalidAdmissionContributions(vec![3])
+        );
+        tampered_contributions[1].commitments[0] = Projective::generator();
+        assert_eq!(
+            admission
+                .finish(&new_dh_sk, &tampered_contributions, &shares)
+                .unwrap_err(),
+            Error::InvalidAdmissionContributions(vec![3])
+        );
+
+        // Members cannot be admitted again, and helpers must reach the threshold.
+        assert!(ParticipantAdmission::new(&package, &participants[1], &helpers).is_err());
+        assert!(ParticipantAdmission::new(&package, &new_participant, &helpers[..1]).is_err());
+    }
+
+    #[test]
+    fn resharing_with_proofs_2_out_of_3_into_3_out_of_4() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states: Vec<_> = (0..3)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let mut group_key = None;
+        let mut signing_keys = Vec::new();
+        for i in 0..3 {
+            let my_encrypted_secret_shares = states
+                .iter()
+                .map(|state| state.their_encrypted_secret_shares().unwrap()[i].clone())
+                .collect();
+            let (key, signing_key) = states[i]
+                .clone()
+                .to_round_two(my_encrypted_secret_shares, rng)
+                .unwrap()
+                .finish()
+                .unwrap();
+            group_key = Some(key);
+            signing_keys.push(signing_key);
+        }
+        let group_key = group_key.unwrap();
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+
+        let new_params = ThresholdParameters::<Secp256k1Sha256>::new(4, 3).unwrap();
+        let mut signers = Vec::new();
+        let mut signers_dh_sk = Vec::new();
+        for i in 1..=4 {
+            let (signer, dh_sk) =
+                Participant::<Secp256k1Sha256, SignerRole>::new_signer(&new_params, i, rng)
+                    .unwrap();
+            signers.push(signer);
+            signers_dh_sk.push(dh_sk);
+        }
+
+        let shares: Vec<Vec<ResharingShare<Secp256k1Sha256>>> = signing_keys
+            .iter()
+            .map(|signing_key| {
+                Participant::reshare_with_proofs(&new_params, signing_key.clone(), &signers, rng)
+                    .unwrap()
+                    .1
+            })
+            .collect();
+        assert_eq!(
+            ResharingShare::from_bytes(&shares[0][1].to_bytes().unwrap()).unwrap(),
+            shares[0][1]
+        );
+
+        let mut accumulators: Vec<_> = (0..4)
+            .map(|i| {
+                ResharingAccumulator::new(
+                    package.clone(),
+                    new_params,
+                    i as u32 + 1,
+                    signers_dh_sk[i].clone(),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        // Shares of dealers 1 and 3 are accepted as they arrive, before the ones of dealer 2.
+        for (i, accumulator) in accumulators.iter_mut().enumerate() {
+            accumulator.accept(&shares[2][i]).unwrap();
+            assert!(!accumulator.is_ready());
+            accumulator.accept(&shares[0][i]).unwrap();
+            accumulator.accept(&shares[0][i]).unwrap();
+            assert!(accumulator.is_ready());
+            assert_eq!(accumulator.accepted_dealers(), vec![1, 3]);
+        }
+
+        // Misrouted, forged or inconsistent shares are rejected on their own.
+        assert_eq!(
+            accumulators[0].accept(&shares[1][1]),
+            Err(Error::MisroutedShare(2, 1))
+        );
+        let mut forged = shares[1][0].clone();
+        forged.r = Projective::rand(&mut OsRng);
+        assert_eq!(
+            accumulators[0].accept(&forged),
+            Err(Error::InvalidProofOfKnowledge)
+        );
+        let mut inconsistent = shares[1][0].clone();
+        inconsistent.commitment.points[0] = Projective::rand(&mut OsRng);
+        assert_eq!(
+            accumulators[0].accept(&inconsistent),
+            Err(Error::ShareVerificationError)
+        );
+        assert_eq!(
+            accumulators[0].finish(&[1]).unwrap_err(),
+            Error::InvalidNumberOfParticipants(1, 2)
+        );
+        assert_eq!(
+            accumulators[0].finish(&[1, 2]).unwrap_err(),
+            Error::MissingShares
+        );
+
+        // Signers agreeing on dealers 1 and 3 obtain a sharing of the same group key.
+        let new_signing_keys: Vec<_> = accumulators
+            .iter()
+            .map(|accumulator| {
+                let (new_group_key, signing_key) = accumulator.finish(&[1, 3]).unwrap();
+                assert_eq!(new_group_key, group_key);
+                signing_key
+            })
+            .collect();
+
+        for subset in [[1u32, 2, 3], [2, 3, 4]] {
+            let mut secret_key = Fr::ZERO;
+            for index in subset {
+                let coeff =
+                    calculate_lagrange_coefficients::<Secp256k1Sha256>(index, &subset).unwrap();
+                secret_key += coeff * new_signing_keys[index as usize - 1].key;
+            }
+            assert_eq!(
+                GroupVerifyingKey::new(Element(Projective::generator().mul(secret_key))),
+                group_key
+            );
+        }
+    }
+
+    #[test]
+    fn authenticated_keygen_2_out_of_3() {
+        /// Keyed hashes standing in for the signatures of validator keys.
+        #[derive(Clone)]
+        struct KeyedHashAuthenticator(BTreeMap<u32, Vec<u8>>);
+
+        impl KeyedHashAuthenticator {
+            fn sign(&self, index: u32, message: &[u8]) -> Vec<u8> {
+                let mut input = self.0[&index].clone();
+                input.extend_from_slice(message);
+                Secp256k1Sha256::h5(&input).unwrap().to_vec()
+            }
+        }
+
+        impl ExternalAuthenticator for KeyedHashAuthenticator {
+            fn external_key(&self, index: u32) -> Option<Vec<u8>> {
+                self.0.get(&index).cloned()
+            }
+
+            fn verify(&self, index: u32, message: &[u8], signature: &[u8]) -> bool {
+                self.sign(index, message) == signature
+            }
+        }
+
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+        let authenticator = KeyedHashAuthenticator(
+            (1..=3)
+                .map(|i| (i, OsRng.gen::<[u8; 32]>().to_vec()))
+                .collect(),
+        );
+        let session =
+            AuthenticatedSession::new(&params, b"block 42", &[1, 2, 3], authenticator.clone())
+                .unwrap();
+        let authenticate = |index: u32, message: Vec<u8>| authenticator.sign(index, &message);
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=3 {
+            let (p, c, dh_sk) = Participant::new_dealer(&params, i, rng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        // The third participant did not sign its message with its own validator key.
+        let mut messages: Vec<_> = participants
+            .iter()
+            .map(|p| {
+                let signature = authenticate(p.index, session.signing_message(p).unwrap());
+                session.authenticated_message(p.clone(), signature)
+            })
+            .collect();
+        messages[2].signature = authenticate(2, session.signing_message(&participants[2]).unwrap());
+
+        // A message of the first round cannot be replayed into another epoch.
+        let next_epoch = session.clone().with_epoch(1);
+        assert_eq!(
+            next_epoch.verify(&messages[0]),
+            Err(Error::UnexpectedRoundTag(
+                RoundTag::new(ProtocolRound::DkgRound1, b"block 42", 1),
+                RoundTag::new(ProtocolRound::DkgRound1, b"block 42", 0)
+            ))
+        );
+
+        // Nor can it be passed off as a message of another round.
+        let mut replayed = messages[0].clone();
+        replayed.tag.round = ProtocolRound::DkgRound2;
+        assert!(matches!(
+            session.verify(&replayed),
+            Err(Error::UnexpectedRoundTag(_, _))
+        ));
+
+        let states: Vec<_> = (0..2)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap_authenticated(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &messages,
+                    &session,
+                    rng,
+                )
+                .unwrap()
+            })
+            .collect();
+        for (state, participant_list) in states.iter() {
+            assert_eq!(participant_list.misbehaving_participants, Some(vec![3]));
+            assert_eq!(participant_list.valid_participants.len(), 2);
+            assert!(state
+                .their_encrypted_secret_shares()
+                .unwrap()
+                .iter()
+                .all(|share| share.receiver_index != 3));
+        }
+
+        let share_message = |session: &AuthenticatedSession<_, _>, dealer: usize, receiver: u32| {
+            let share = states[dealer]
+                .0
+                .their_encrypted_secret_shares()
+                .unwrap()
+                .iter()
+                .find(|share| share.receiver_index == receiver)
+                .unwrap()
+                .clone();
+            let signature =
+                authenticate(share.sender_index, session.signing_message(&share).unwrap());
+            session.authenticated_message(share, signature)
+        };
+
+        // Shares authenticated within another session are rejected.
+        let other_session =
+            AuthenticatedSession::new(&params, b"block 43", &[1, 2, 3], authenticator.clone())
+                .unwrap();
+        let mut processor = states[0].0.clone().begin_round_two();
+        assert_eq!(
+            processor.process_authenticated_share(
+                &share_message(&other_session, 1, 1),
+                &session,
+                rng
+            ),
+            Err(Error::UnexpectedRoundTag(
+                session.round_tag::<EncryptedSecretShare<Secp256k1Sha256>>(),
+                other_session.round_tag::<EncryptedSecretShare<Secp256k1Sha256>>()
+            ))
+        );
+
+        // Even when carrying the tag of this session, their signature is invalid.
+        let mut message = share_message(&other_session, 1, 1);
+        message.tag = session.round_tag::<EncryptedSecretShare<Secp256k1Sha256>>();
+        assert_eq!(
+            processor.process_authenticated_share(&message, &session, rng),
+            Err(Error::InvalidExternalAuthentication(vec![2]))
+        );
+        assert_eq!(processor.processed_shares(), 0);
+
+        let mut group_keys = Vec::new();
+        for (receiver, (state, _)) in (1..).zip(states.iter()) {
+            let mut processor = state.clone().begin_round_two();
+            for dealer in 0..2 {
+                let message = share_message(&session, dealer, receiver);
+                assert!(processor
+                    .process_authenticated_share(&message, &session, rng)
+                    .unwrap()
+                    .is_none());
+            }
+            group_keys.push(processor.finish().unwrap().finish().unwrap().0);
+        }
+        assert_eq!(group_keys[0], group_keys[1]);
+    }
 }
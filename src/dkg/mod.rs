@@ -1,13 +1,48 @@
+pub(crate) mod admission;
+pub(crate) mod assignment;
+pub(crate) mod authenticated;
+pub(crate) mod bundle;
 pub(crate) mod complaint;
+pub(crate) mod complaint_queue;
+pub(crate) mod expulsion;
 pub(crate) mod key_generation;
 pub(crate) mod nizkpok;
 pub(crate) mod participant;
+pub(crate) mod pedersen;
+pub(crate) mod qualification;
+pub(crate) mod receipt;
+pub(crate) mod resharing;
+pub(crate) mod role;
+pub(crate) mod roster;
 pub(crate) mod round_types;
 pub(crate) mod secret_share;
 
-pub use complaint::{Complaint, ComplaintProof};
+pub use admission::{AdmissionContribution, AdmissionShare, ParticipantAdmission};
+pub use assignment::{assign_indices, IndexAssignment};
+pub use authenticated::{
+    AuthenticatedMessage, AuthenticatedPayload, AuthenticatedSession, ExternalAuthenticator,
+};
+pub use bundle::ShareBundle;
+pub use complaint::{Complaint, ComplaintProof, SealedComplaint};
+pub use complaint_queue::{
+    ComplaintAdmission, ComplaintQueue, ComplaintQueueLimits, ComplaintQueueMetrics,
+    ComplaintVerdict,
+};
+pub use expulsion::ParticipantExpulsion;
 pub use key_generation::*;
 pub use nizkpok::NizkPokOfSecretKey;
-pub use participant::Participant;
+pub use participant::{Participant, ParticipantId};
+pub use pedersen::{
+    pedersen_generator, EncryptedPedersenShare, NizkPokOfOpening, PedersenCoefficients,
+    PedersenCommitment, PedersenDistributedKeyGeneration, PedersenParticipant,
+};
+pub use qualification::{QualificationPolicy, QualificationTracker, QualifiedSet};
+pub use receipt::ShareReceipt;
+pub use resharing::{ResharingAccumulator, ResharingShare};
+pub use role::{AnyRole, CoordinatorRole, DealerRole, Role, SignerRole};
+pub use roster::{MembershipProof, ParticipantRoster};
 pub use round_types::{RoundOne, RoundTwo};
-pub use secret_share::{Coefficients, EncryptedSecretShare, SecretShare};
+pub use secret_share::{
+    Coefficients, EncryptedSecretShare, SecretShare, ShareVerification,
+    VerifiableSecretSharingCommitment,
+};
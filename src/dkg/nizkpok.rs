@@ -1,13 +1,13 @@
 //! The proof of knowledge module for proving knowledge of secret keys
 //! when performing an ICE-FROST Distributed Key Generation session.
 
-use ark_ec::{AffineRepr, CurveGroup, Group, VariableBaseMSM};
+use ark_ec::{AffineRepr, CurveGroup, Group};
 use ark_ff::UniformRand;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use crate::ciphersuite::CipherSuite;
 use crate::utils::{Scalar, Vec};
-use crate::{Error, FrostResult};
+use crate::{Element, Error, FrostResult};
 
 use rand::CryptoRng;
 use rand::Rng;
@@ -29,9 +29,9 @@ use rand::Rng;
 #[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct NizkPokOfSecretKey<C: CipherSuite> {
     /// The scalar portion of the Schnorr signature encoding the context.
-    s: Scalar<C>,
+    pub(crate) s: Scalar<C>,
     /// The scalar portion of the Schnorr signature which is the actual signature.
-    r: Scalar<C>,
+    pub(crate) r: Scalar<C>,
 }
 
 impl<C: CipherSuite> NizkPokOfSecretKey<C> {
@@ -40,21 +40,21 @@ impl<C: CipherSuite> NizkPokOfSecretKey<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`NizkPokOfSecretKey`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
     }
 
     /// Prove knowledge of a secret key.
     pub fn prove(
         index: u32,
-        secret_key: &Scalar<C>,
-        public_key: &C::G,
+        secret_key: &crate::Scalar<C>,
+        public_key: &Element<C>,
         mut csprng: impl Rng + CryptoRng,
     ) -> FrostResult<C, Self> {
         let k = Scalar::<C>::rand(&mut csprng);
@@ -63,34 +63,33 @@ impl<C: CipherSuite> NizkPokOfSecretKey<C> {
         let mut message = index.to_le_bytes().to_vec();
         public_key
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         m.serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
 
         let s = C::h0(&message)?;
-        let r = k + (*secret_key * s);
+        let r = k + (secret_key.0 * s);
 
         Ok(NizkPokOfSecretKey { s, r })
     }
 
     /// Verify that the prover does indeed know the secret key.
-    pub fn verify(&self, index: u32, public_key: &C::G) -> FrostResult<C, ()> {
-        let retrieved_m: C::G = <C as CipherSuite>::G::msm(
+    pub fn verify(&self, index: u32, public_key: &Element<C>) -> FrostResult<C, ()> {
+        let retrieved_m = C::msm(
             &[
                 <C::G as CurveGroup>::Affine::generator(),
-                public_key.into_affine(),
+                public_key.0.into_affine(),
             ],
             &[self.r, -self.s],
-        )
-        .map_err(|_| Error::InvalidMSMParameters)?;
+        )?;
 
         let mut message = index.to_le_bytes().to_vec();
         public_key
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
         retrieved_m
             .serialize_compressed(&mut message)
-            .map_err(|_| Error::CompressionError)?;
+            .map_err(|e| Error::CompressionError(e.into()))?;
 
         let s_prime = C::h0(&message)?;
 
@@ -107,9 +106,7 @@ mod test {
     use super::*;
     use crate::testing::Secp256k1Sha256;
 
-    use ark_ec::Group;
-    use ark_secp256k1::{Fr, Projective};
-    use core::ops::Mul;
+    use ark_secp256k1::Fr;
     use rand::{rngs::OsRng, RngCore};
 
     #[test]
@@ -135,8 +132,8 @@ mod test {
         let mut rng = OsRng;
 
         let index = rng.next_u32();
-        let sk = Fr::rand(&mut rng);
-        let pk = Projective::generator().mul(sk);
+        let sk = crate::Scalar::random(rng);
+        let pk = Element::generator() * sk;
 
         let nizk = NizkPokOfSecretKey::<Secp256k1Sha256>::prove(index, &sk, &pk, rng);
         assert!(nizk.is_ok());
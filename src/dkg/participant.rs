@@ -3,10 +3,14 @@
 
 use ark_ec::Group;
 use ark_ff::UniformRand;
-use ark_serialize::CanonicalDeserialize;
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 
 use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
 use core::ops::Mul;
 use rand::CryptoRng;
 use rand::RngCore;
@@ -18,34 +22,113 @@ use crate::dkg::{
 };
 use crate::keys::{DiffieHellmanPrivateKey, DiffieHellmanPublicKey, IndividualSigningKey};
 use crate::parameters::ThresholdParameters;
-use crate::{Error, FrostResult};
+use crate::validation::{deserialize_validated, validate_dh_public_key, ValidatePoints};
+use crate::{Element, Error, FrostResult};
 
-use crate::utils::{Scalar, Vec};
+use crate::utils::{try_with_capacity, Scalar, ToString, Vec};
 
+use super::assignment::IndexAssignment;
+use super::role::{AnyRole, CoordinatorRole, DealerRole, Role, SignerRole};
 use super::DKGParticipantList;
 use super::DistributedKeyGeneration;
 
+/// The identifier of a participant in an ICE-FROST session.
+///
+/// Unlike [`Participant`], a [`ParticipantId`] is totally ordered and hashable,
+/// and can hence be used as key in ordered or hashed collections to manage rosters.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
+pub struct ParticipantId(pub u32);
+
+impl ParticipantId {
+    /// Retrieve the participant index wrapped by this [`ParticipantId`].
+    pub const fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for ParticipantId {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl From<ParticipantId> for u32 {
+    fn from(id: ParticipantId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for ParticipantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The public material published by a dealer, namely the commitments to the
+/// coefficients of its secret polynomial and the proof of knowledge of its secret key.
+#[derive(Clone, Debug)]
+pub struct DealerMaterial<C: CipherSuite> {
+    /// A vector of Pedersen commitments to the coefficients of this
+    /// dealer's private polynomial.
+    pub(crate) commitments: VerifiableSecretSharingCommitment<C>,
+    /// The zero-knowledge proof of knowledge of the secret key (a.k.a. the
+    /// first coefficient in the private polynomial).  It is constructed as a
+    /// Schnorr signature using \\( a_{i0} \\) as the signing key.
+    pub(crate) proof_of_secret_key: NizkPokOfSecretKey<C>,
+}
+
+impl<C: CipherSuite> DealerMaterial<C> {
+    /// Gather the optional `commitments` and `proof_of_secret_key` of a participant,
+    /// which must either be both present if it is a dealer, or both absent.
+    pub(crate) fn from_parts(
+        commitments: Option<VerifiableSecretSharingCommitment<C>>,
+        proof_of_secret_key: Option<NizkPokOfSecretKey<C>>,
+    ) -> Result<Option<Self>, SerializationError> {
+        match (commitments, proof_of_secret_key) {
+            (Some(commitments), Some(proof_of_secret_key)) => Ok(Some(Self {
+                commitments,
+                proof_of_secret_key,
+            })),
+            (None, None) => Ok(None),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
 /// A participant in a threshold signing.
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Participant<C: CipherSuite> {
+///
+/// The role `P` of the participant, such as [`DealerRole`] or [`SignerRole`], is tracked
+/// at the type level, and defaults to [`AnyRole`] for participants whose role is only
+/// known at runtime. Dealers always carry the commitments to their secret polynomial
+/// and the proof of knowledge of their secret key, which other roles never carry.
+#[derive(Clone, Debug)]
+pub struct Participant<C: CipherSuite, P: Role<C> = AnyRole> {
     /// The index of this participant, to keep the participants in order.
     pub index: u32,
     /// The public key used to derive symmetric keys for encrypting and
     /// decrypting shares via DH.
     pub dh_public_key: DiffieHellmanPublicKey<C>,
-    /// A vector of Pedersen commitments to the coefficients of this
-    /// participant's private polynomial.
-    pub commitments: Option<VerifiableSecretSharingCommitment<C>>,
-    /// The zero-knowledge proof of knowledge of the secret key (a.k.a. the
-    /// first coefficient in the private polynomial).  It is constructed as a
-    /// Schnorr signature using \\( a_{i0} \\) as the signing key.
-    pub proof_of_secret_key: Option<NizkPokOfSecretKey<C>>,
+    /// The public material of this participant if it is a dealer, as stored by its role.
+    pub(crate) material: P::Material,
     /// The zero-knowledge proof of knowledge of the DH private key.
-    /// It is computed similarly to the proof_of_secret_key.
+    /// It is computed similarly to the proof of knowledge of the secret key.
     pub proof_of_dh_private_key: NizkPokOfSecretKey<C>,
+    pub(crate) _role: PhantomData<P>,
 }
 
-impl<C: CipherSuite> Participant<C> {
+impl<C: CipherSuite> Participant<C, DealerRole> {
     /// Construct a new dealer for the distributed key generation protocol,
     /// who will generate shares for a group of participants. Dealers are regular
     /// signers with the additional ability to redistribute their secret shares,
@@ -64,10 +147,9 @@ impl<C: CipherSuite> Participant<C> {
     ///
     /// # Usage
     ///
-    /// After a new participant is constructed, the [`participant.index`],
-    /// [`participant.commitments`], [`participant.proof_of_secret_key`] and
-    /// [`participant.proof_of_dh_private_key`] should be sent to every
-    /// other participant in the protocol.
+    /// After a new participant is constructed, it should be sent to every other
+    /// participant in the protocol, along with its [`Participant::commitments`] and
+    /// [`Participant::proof_of_secret_key`], e.g. serialized with [`Participant::to_bytes`].
     ///
     /// # Returns
     ///
@@ -78,136 +160,33 @@ impl<C: CipherSuite> Participant<C> {
     pub fn new_dealer(
         parameters: &ThresholdParameters<C>,
         index: u32,
-        mut rng: impl RngCore + CryptoRng,
+        rng: impl RngCore + CryptoRng,
     ) -> FrostResult<C, (Self, Coefficients<C>, DiffieHellmanPrivateKey<C>)> {
-        let (dealer, coeff_option, dh_private_key) =
-            Self::new_internal(parameters, false, index, None, &mut rng)?;
-        Ok((dealer, coeff_option.unwrap(), dh_private_key))
+        Self::new_dealer_internal(parameters, index, None, None, rng)
     }
 
-    /// Construct a new signer for the distributed key generation protocol.
-    ///
-    /// A signer only combines shares from a previous set of dealers and
-    /// computes a private signing key from it. In particular, signers do
-    /// not have the ability to redistribute secret share to other participants.
+    /// Construct a new dealer as with [`Participant::new_dealer`], owning the given
+    /// `dh_private_key`, with the index it was assigned in the given `assignment`.
     ///
-    /// # Inputs
-    ///
-    /// * The protocol instance [`ThresholdParameters`],
-    /// * This participant's `index`,
-    /// * A cryptographically secure pseudo-random generator.
-    ///
-    /// # Usage
-    ///
-    /// After a new participant is constructed, the `participant.index`
-    /// and [`participant.proof_of_dh_private_key`] should be sent to every
-    /// other participant in the protocol.
+    /// The `dh_private_key` may be reused across ceremonies, as each of them encrypts
+    /// the secret shares under a fresh salt, drawn by
+    /// [`DistributedKeyGeneration::bootstrap_with_assignment`].
     ///
     /// # Returns
     ///
-    /// A distributed key generation protocol [`Participant`] along the
-    /// signers's Diffie-Hellman private key for secret shares encryption
-    /// which must be kept private.
-    pub fn new_signer(
-        parameters: &ThresholdParameters<C>,
-        index: u32,
-        mut rng: impl RngCore + CryptoRng,
-    ) -> FrostResult<C, (Self, DiffieHellmanPrivateKey<C>)> {
-        let (signer, _coeff_option, dh_private_key) =
-            Self::new_internal(parameters, true, index, None, &mut rng)?;
-        Ok((signer, dh_private_key))
-    }
-
-    fn new_internal(
+    /// A distributed key generation protocol [`Participant`] and that
+    /// dealer's secret polynomial `coefficients`.
+    pub fn new_dealer_with_assignment(
         parameters: &ThresholdParameters<C>,
-        is_signer: bool,
-        index: u32,
-        secret_key: Option<Scalar<C>>,
-        mut rng: impl RngCore + CryptoRng,
-    ) -> FrostResult<C, (Self, Option<Coefficients<C>>, DiffieHellmanPrivateKey<C>)> {
-        if index == 0 {
-            return Err(Error::IndexIsZero);
-        }
-
-        // Step 1: Every participant P_i samples t random values (a_{i0}, ..., a_{i(t-1)})
-        //         uniformly in ZZ_q, and uses these values as coefficients to define a
-        //         polynomial f_i(x) = \sum_{j=0}^{t-1} a_{ij} x^{j} of degree t-1 over
-        //         ZZ_q.
-        let t: usize = parameters.t as usize;
-
-        // Every participant samples a random pair of keys (dh_private_key, dh_public_key)
-        // and generates a proof of knowledge of dh_private_key.
-        // This will be used for secret shares encryption and for complaint generation.
-        let dh_private_key = DiffieHellmanPrivateKey(Scalar::<C>::rand(&mut rng));
-        let dh_public_key = DiffieHellmanPublicKey::new(C::G::generator().mul(dh_private_key.0));
-
-        // Compute a proof of knowledge of dh_secret_key
-        let proof_of_dh_private_key =
-            NizkPokOfSecretKey::<C>::prove(index, &dh_private_key.0, &dh_public_key, &mut rng)?;
-
-        if is_signer {
-            // Signers don't need coefficients, commitments or proofs of secret key.
-            Ok((
-                Participant {
-                    index,
-                    dh_public_key,
-                    commitments: None,
-                    proof_of_secret_key: None,
-                    proof_of_dh_private_key,
-                },
-                None,
-                dh_private_key,
-            ))
-        } else {
-            let mut coefficients: Vec<Scalar<C>> = Vec::with_capacity(t);
-            let mut commitments = VerifiableSecretSharingCommitment {
-                index,
-                points: Vec::with_capacity(t),
-            };
-
-            match secret_key {
-                Some(sk) => coefficients.push(sk),
-                None => coefficients.push(Scalar::<C>::rand(&mut rng)),
-            }
-
-            for _ in 1..t {
-                coefficients.push(Scalar::<C>::rand(&mut rng));
-            }
-
-            let coefficients = Coefficients(coefficients);
-
-            // Step 3: Every dealer computes a public commitment
-            //         C_i = [\phi_{i0}, ..., \phi_{i(t-1)}], where \phi_{ij} = g^{a_{ij}},
-            //         0 ≤ j ≤ t-1.
-            for j in 0..t {
-                commitments
-                    .points
-                    .push(C::G::generator() * coefficients.0[j]);
-            }
-
-            // The steps are out of order, in order to save one scalar multiplication.
-
-            // Step 2: Every dealer computes a proof of knowledge to the corresponding secret
-            //         a_{i0} by calculating a Schnorr signature \alpha_i = (s, group_commitment).
-            let proof_of_secret_key: NizkPokOfSecretKey<C> = NizkPokOfSecretKey::prove(
-                index,
-                &coefficients.0[0],
-                commitments.public_key().unwrap(),
-                rng,
-            )?;
-
-            Ok((
-                Participant {
-                    index,
-                    dh_public_key,
-                    commitments: Some(commitments),
-                    proof_of_secret_key: Some(proof_of_secret_key),
-                    proof_of_dh_private_key,
-                },
-                Some(coefficients),
-                dh_private_key,
-            ))
-        }
+        assignment: &IndexAssignment<C>,
+        dh_private_key: DiffieHellmanPrivateKey<C>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, Coefficients<C>)> {
+        let index = assignment.index_of_private_key(&dh_private_key)?;
+        let (dealer, coefficients, _dh_private_key) =
+            Self::new_dealer_internal(parameters, index, None, Some(dh_private_key), rng)?;
+
+        Ok((dealer, coefficients))
     }
 
     /// Reshare this dealer's secret key to a new set of participants.
@@ -220,9 +199,7 @@ impl<C: CipherSuite> Participant<C> {
     ///
     /// # Usage
     ///
-    /// After a new participant is constructed, the `participant.index`,
-    /// `participant.commitments`, `participant.proof_of_secret_key` and
-    /// `participant.proof_of_dh_private_key` should be sent to every other
+    /// After a new participant is constructed, it should be sent to every other
     /// participant in the protocol along with their dedicated secret share.
     ///
     /// # Returns
@@ -232,23 +209,20 @@ impl<C: CipherSuite> Participant<C> {
     /// of the new set accordingly.
     /// It also returns a list of the valid / misbehaving participants
     /// of the new set for handling outside of this crate.
-    pub fn reshare(
+    pub fn reshare<R: Role<C>>(
         parameters: &ThresholdParameters<C>,
         secret_key: IndividualSigningKey<C>,
-        signers: &[Participant<C>],
+        signers: &[Participant<C, R>],
         mut rng: impl RngCore + CryptoRng,
     ) -> FrostResult<C, (Self, Vec<EncryptedSecretShare<C>>, DKGParticipantList<C>)> {
-        let (dealer, coeff_option, dh_private_key) = Self::new_internal(
+        let (dealer, coefficients, dh_private_key) = Self::new_dealer_internal(
             parameters,
-            false,
             secret_key.index,
             Some(secret_key.key),
+            None,
             &mut rng,
         )?;
 
-        // Unwrapping cannot panic here
-        let coefficients = coeff_option.unwrap();
-
         let (participant_state, participant_lists) = DistributedKeyGeneration::new_state_internal(
             parameters,
             &dh_private_key,
@@ -257,74 +231,544 @@ impl<C: CipherSuite> Participant<C> {
             signers,
             true,
             false,
+            None,
             &mut rng,
         )?;
 
-        // Unwrapping cannot panic here
-        let encrypted_shares = participant_state
-            .their_encrypted_secret_shares()
-            .unwrap()
-            .clone();
+        let encrypted_shares = participant_state.their_encrypted_secret_shares()?.clone();
 
         Ok((dealer, encrypted_shares, participant_lists))
     }
 
+    /// The commitments to the coefficients of this dealer's secret polynomial.
+    pub fn commitments(&self) -> &VerifiableSecretSharingCommitment<C> {
+        &self.material.commitments
+    }
+
+    /// The proof of knowledge of this dealer's secret key.
+    pub fn proof_of_secret_key(&self) -> &NizkPokOfSecretKey<C> {
+        &self.material.proof_of_secret_key
+    }
+
+    fn new_dealer_internal(
+        parameters: &ThresholdParameters<C>,
+        index: u32,
+        secret_key: Option<Scalar<C>>,
+        dh_private_key: Option<DiffieHellmanPrivateKey<C>>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, Coefficients<C>, DiffieHellmanPrivateKey<C>)> {
+        let (dh_private_key, dh_public_key, proof_of_dh_private_key) =
+            new_dh_key_pair(parameters, index, dh_private_key, &mut rng)?;
+
+        // Step 1: Every participant P_i samples t random values (a_{i0}, ..., a_{i(t-1)})
+        //         uniformly in ZZ_q, and uses these values as coefficients to define a
+        //         polynomial f_i(x) = \sum_{j=0}^{t-1} a_{ij} x^{j} of degree t-1 over
+        //         ZZ_q.
+        let t: usize = parameters.t as usize;
+        let mut coefficients: Vec<Scalar<C>> = try_with_capacity::<C, _>(t)?;
+        let mut commitments = VerifiableSecretSharingCommitment {
+            index,
+            points: try_with_capacity::<C, _>(t)?,
+        };
+
+        match secret_key {
+            Some(sk) => coefficients.push(sk),
+            None => coefficients.push(Scalar::<C>::rand(&mut rng)),
+        }
+
+        for _ in 1..t {
+            coefficients.push(Scalar::<C>::rand(&mut rng));
+        }
+
+        let coefficients = Coefficients(coefficients);
+
+        // Step 3: Every dealer computes a public commitment
+        //         C_i = [\phi_{i0}, ..., \phi_{i(t-1)}], where \phi_{ij} = g^{a_{ij}},
+        //         0 ≤ j ≤ t-1.
+        for j in 0..t {
+            commitments
+                .points
+                .push(C::G::generator() * coefficients.0[j]);
+        }
+
+        // The steps are out of order, in order to save one scalar multiplication.
+
+        // Step 2: Every dealer computes a proof of knowledge to the corresponding secret
+        //         a_{i0} by calculating a Schnorr signature \alpha_i = (s, group_commitment).
+        let proof_of_secret_key: NizkPokOfSecretKey<C> = NizkPokOfSecretKey::prove(
+            index,
+            &crate::Scalar(coefficients.0[0]),
+            &commitments.public_key().ok_or(Error::EmptyCommitment)?,
+            rng,
+        )?;
+
+        Ok((
+            Participant {
+                index,
+                dh_public_key,
+                material: DealerMaterial {
+                    commitments,
+                    proof_of_secret_key,
+                },
+                proof_of_dh_private_key,
+                _role: PhantomData,
+            },
+            coefficients,
+            dh_private_key,
+        ))
+    }
+}
+
+impl<C: CipherSuite> Participant<C, SignerRole> {
+    /// Construct a new signer for the distributed key generation protocol.
+    ///
+    /// A signer only combines shares from a previous set of dealers and
+    /// computes a private signing key from it. In particular, signers do
+    /// not have the ability to redistribute secret share to other participants.
+    ///
+    /// # Inputs
+    ///
+    /// * The protocol instance [`ThresholdParameters`],
+    /// * This participant's `index`,
+    /// * A cryptographically secure pseudo-random generator.
+    ///
+    /// # Usage
+    ///
+    /// After a new participant is constructed, the `participant.index`
+    /// and [`participant.proof_of_dh_private_key`] should be sent to every
+    /// other participant in the protocol.
+    ///
+    /// # Returns
+    ///
+    /// A distributed key generation protocol [`Participant`] along the
+    /// signers's Diffie-Hellman private key for secret shares encryption
+    /// which must be kept private.
+    pub fn new_signer(
+        parameters: &ThresholdParameters<C>,
+        index: u32,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, DiffieHellmanPrivateKey<C>)> {
+        Self::new_internal(parameters, index, None, rng)
+    }
+
+    /// Construct a new signer as with [`Participant::new_signer`], owning the given
+    /// `dh_private_key`, with the index it was assigned in the given `assignment`.
+    ///
+    /// The `dh_private_key` may be reused across ceremonies, as each of them encrypts
+    /// the secret shares under a fresh salt, drawn by their dealers.
+    pub fn new_signer_with_assignment(
+        parameters: &ThresholdParameters<C>,
+        assignment: &IndexAssignment<C>,
+        dh_private_key: DiffieHellmanPrivateKey<C>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Self> {
+        let index = assignment.index_of_private_key(&dh_private_key)?;
+        let (signer, _dh_private_key) =
+            Self::new_internal(parameters, index, Some(dh_private_key), rng)?;
+
+        Ok(signer)
+    }
+}
+
+impl<C: CipherSuite> Participant<C, CoordinatorRole> {
+    /// Construct a new coordinator for the distributed key generation protocol.
+    ///
+    /// A coordinator neither deals nor receives secret shares, but publishes its
+    /// Diffie-Hellman public key along with a proof of knowledge of the private key,
+    /// so that participants can for instance seal their complaints to it.
+    ///
+    /// # Returns
+    ///
+    /// A distributed key generation protocol [`Participant`] along the
+    /// coordinator's Diffie-Hellman private key which must be kept private.
+    pub fn new_coordinator(
+        parameters: &ThresholdParameters<C>,
+        index: u32,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, DiffieHellmanPrivateKey<C>)> {
+        Self::new_internal(parameters, index, None, rng)
+    }
+}
+
+/// Sample the Diffie-Hellman key pair of a new participant, unless its `dh_private_key`
+/// is provided, along with a proof of knowledge of the private key.
+fn new_dh_key_pair<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    index: u32,
+    dh_private_key: Option<DiffieHellmanPrivateKey<C>>,
+    mut rng: impl RngCore + CryptoRng,
+) -> FrostResult<
+    C,
+    (
+        DiffieHellmanPrivateKey<C>,
+        DiffieHellmanPublicKey<C>,
+        NizkPokOfSecretKey<C>,
+    ),
+> {
+    if index == 0 {
+        return Err(Error::IndexIsZero);
+    }
+    parameters.validate()?;
+
+    // Every participant samples a random pair of keys (dh_private_key, dh_public_key)
+    // and generates a proof of knowledge of dh_private_key.
+    // This will be used for secret shares encryption and for complaint generation.
+    let dh_private_key = dh_private_key.unwrap_or_else(|| DiffieHellmanPrivateKey::new(&mut rng));
+    let dh_public_key =
+        DiffieHellmanPublicKey::<C>::new(Element(C::G::generator().mul(dh_private_key.0)));
+
+    // Compute a proof of knowledge of dh_secret_key
+    let proof_of_dh_private_key = NizkPokOfSecretKey::<C>::prove(
+        index,
+        &crate::Scalar(dh_private_key.0),
+        &dh_public_key.element(),
+        &mut rng,
+    )?;
+
+    Ok((dh_private_key, dh_public_key, proof_of_dh_private_key))
+}
+
+impl<C: CipherSuite, R: Role<C, Material = ()>> Participant<C, R> {
+    /// Construct a new participant without dealer material, such as a signer or
+    /// a coordinator.
+    fn new_internal(
+        parameters: &ThresholdParameters<C>,
+        index: u32,
+        dh_private_key: Option<DiffieHellmanPrivateKey<C>>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, DiffieHellmanPrivateKey<C>)> {
+        let (dh_private_key, dh_public_key, proof_of_dh_private_key) =
+            new_dh_key_pair(parameters, index, dh_private_key, rng)?;
+
+        // Signers don't need coefficients, commitments or proofs of secret key.
+        Ok((
+            Participant {
+                index,
+                dh_public_key,
+                material: (),
+                proof_of_dh_private_key,
+                _role: PhantomData,
+            },
+            dh_private_key,
+        ))
+    }
+}
+
+impl<C: CipherSuite, R: Role<C>> Participant<C, R> {
     /// Serialize this [`Participant`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`Participant`] from a vector of bytes.
+    ///
+    /// The deserialized participant must match the role `R`.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        let participant: Participant<C> = deserialize_validated(bytes)?;
+
+        participant.into_role()
+    }
+
+    /// Retrieve the [`ParticipantId`] of this [`Participant`].
+    pub const fn id(&self) -> ParticipantId {
+        ParticipantId(self.index)
     }
 
     /// Retrieve \\( \alpha_{i0} * B \\), where \\( B \\) is the prime-order basepoint.
     ///
     /// This is used to pass into the final call to [`DistributedKeyGeneration::<RoundTwo, C>::finish()`] .
-    pub fn public_key(&self) -> Option<&C::G> {
-        if self.commitments.is_some() {
-            return self.commitments.as_ref().unwrap().public_key();
+    pub fn public_key(&self) -> Option<Element<C>> {
+        self.dealer_material()
+            .and_then(|material| material.commitments.public_key())
+    }
+
+    /// Erase the role of this [`Participant`], for instance to gather it
+    /// with participants of other roles.
+    pub fn into_any(self) -> Participant<C> {
+        Participant {
+            index: self.index,
+            dh_public_key: self.dh_public_key,
+            material: R::into_material(self.material),
+            proof_of_dh_private_key: self.proof_of_dh_private_key,
+            _role: PhantomData,
         }
+    }
 
-        None
+    /// The public material of this [`Participant`], if it is a dealer.
+    pub(crate) fn dealer_material(&self) -> Option<&DealerMaterial<C>> {
+        R::material(&self.material)
     }
 }
 
-impl<C: CipherSuite> PartialOrd for Participant<C> {
-    fn partial_cmp(&self, other: &Participant<C>) -> Option<Ordering> {
-        match self.index.cmp(&other.index) {
-            Ordering::Less => Some(Ordering::Less),
-            Ordering::Equal => None, // Participants cannot have the same index.
-            Ordering::Greater => Some(Ordering::Greater),
+impl<C: CipherSuite> Participant<C> {
+    /// Assign the role `R` to this [`Participant`], whose role was only known at runtime.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the typed participant, otherwise an
+    /// [`Error::Custom`] if its public material does not match the role `R`.
+    pub fn into_role<R: Role<C>>(self) -> FrostResult<C, Participant<C, R>> {
+        let is_dealer = self.material.is_some();
+        let material = R::from_material(self.material).ok_or_else(|| match is_dealer {
+            true => Error::Custom("The participant is a dealer".to_string()),
+            false => Error::Custom("The participant is not a dealer".to_string()),
+        })?;
+
+        Ok(Participant {
+            index: self.index,
+            dh_public_key: self.dh_public_key,
+            material,
+            proof_of_dh_private_key: self.proof_of_dh_private_key,
+            _role: PhantomData,
+        })
+    }
+
+    /// The commitments to the coefficients of the secret polynomial of this
+    /// [`Participant`], if it is a dealer.
+    pub fn dealer_commitments(&self) -> Option<&VerifiableSecretSharingCommitment<C>> {
+        self.material.as_ref().map(|material| &material.commitments)
+    }
+
+    /// The proof of knowledge of the secret key of this [`Participant`], if it is a dealer.
+    pub fn dealer_proof_of_secret_key(&self) -> Option<&NizkPokOfSecretKey<C>> {
+        self.material
+            .as_ref()
+            .map(|material| &material.proof_of_secret_key)
+    }
+}
+
+// The dealer material is encoded as two optional fields, so that the encoding of a
+// participant does not depend on its role.
+impl<C: CipherSuite, R: Role<C>> CanonicalSerialize for Participant<C, R> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let material = self.dealer_material();
+
+        self.index.serialize_with_mode(&mut writer, compress)?;
+        self.dh_public_key
+            .serialize_with_mode(&mut writer, compress)?;
+        material
+            .is_some()
+            .serialize_with_mode(&mut writer, compress)?;
+        if let Some(material) = material {
+            material
+                .commitments
+                .serialize_with_mode(&mut writer, compress)?;
+        }
+        material
+            .is_some()
+            .serialize_with_mode(&mut writer, compress)?;
+        if let Some(material) = material {
+            material
+                .proof_of_secret_key
+                .serialize_with_mode(&mut writer, compress)?;
         }
+        self.proof_of_dh_private_key
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let material_size = match self.dealer_material() {
+            Some(material) => {
+                material.commitments.serialized_size(compress)
+                    + material.proof_of_secret_key.serialized_size(compress)
+            }
+            None => 0,
+        };
+
+        self.index.serialized_size(compress)
+            + self.dh_public_key.serialized_size(compress)
+            + 2
+            + material_size
+            + self.proof_of_dh_private_key.serialized_size(compress)
     }
 }
 
-impl<C: CipherSuite> PartialEq for Participant<C> {
-    fn eq(&self, other: &Participant<C>) -> bool {
-        self.index == other.index
+impl<C: CipherSuite, R: Role<C>> Valid for Participant<C, R> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.dh_public_key.check()?;
+        if let Some(material) = self.dealer_material() {
+            material.commitments.check()?;
+            material.proof_of_secret_key.check()?;
+        }
+        self.proof_of_dh_private_key.check()
     }
 }
+
+impl<C: CipherSuite, R: Role<C>> CanonicalDeserialize for Participant<C, R> {
+    fn deserialize_with_mode<Rd: Read>(
+        mut reader: Rd,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let index = u32::deserialize_with_mode(&mut reader, compress, validate)?;
+        let dh_public_key =
+            DiffieHellmanPublicKey::deserialize_with_mode(&mut reader, compress, validate)?;
+        let commitments = Option::<VerifiableSecretSharingCommitment<C>>::deserialize_with_mode(
+            &mut reader,
+            compress,
+            validate,
+        )?;
+        let proof_of_secret_key = Option::<NizkPokOfSecretKey<C>>::deserialize_with_mode(
+            &mut reader,
+            compress,
+            validate,
+        )?;
+        let proof_of_dh_private_key =
+            NizkPokOfSecretKey::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        let material = DealerMaterial::from_parts(commitments, proof_of_secret_key)?;
+        let participant = Self {
+            index,
+            dh_public_key,
+            material: R::from_material(material).ok_or(SerializationError::InvalidData)?,
+            proof_of_dh_private_key,
+            _role: PhantomData,
+        };
+
+        if let Validate::Yes = validate {
+            participant.check()?;
+        }
+
+        Ok(participant)
+    }
+}
+
+impl<C: CipherSuite, R: Role<C>> ValidatePoints<C> for Participant<C, R> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_dh_public_key::<C>(self.index, &self.dh_public_key.key)?;
+        match self.dealer_material() {
+            Some(material) => material.commitments.validate_points(),
+            None => Ok(()),
+        }
+    }
+}
+
+// Participants are ordered by their identifier only, consistently with their equality.
+impl<C: CipherSuite, R: Role<C>> Ord for Participant<C, R> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id().cmp(&other.id())
+    }
+}
+
+impl<C: CipherSuite, R: Role<C>> PartialOrd for Participant<C, R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: CipherSuite, R: Role<C>> PartialEq for Participant<C, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl<C: CipherSuite, R: Role<C>> Eq for Participant<C, R> {}
+
+/// Return references to the provided `participants` in their canonical order,
+/// i.e. sorted by increasing [`ParticipantId`].
+///
+/// The sort is stable, so that participants sharing an identifier keep their
+/// relative order, and every node processing the same roster obtains the same output.
+pub(crate) fn canonical_order<C: CipherSuite, R: Role<C>>(
+    participants: &[Participant<C, R>],
+) -> Vec<&Participant<C, R>> {
+    let mut ordered: Vec<&Participant<C, R>> = participants.iter().collect();
+    ordered.sort_by_key(|p| p.id());
+    ordered
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::dkg::{CoordinatorRole, DealerRole, SignerRole};
     use crate::testing::Secp256k1Sha256;
 
     use rand::rngs::OsRng;
+    use std::collections::BTreeSet;
 
     #[test]
     fn index_zero_is_invalid() {
-        let params = ThresholdParameters::new(3, 2);
+        let params = ThresholdParameters::new(3, 2).unwrap();
         let rng = OsRng;
 
-        let result = Participant::<Secp256k1Sha256>::new_dealer(&params, 0, rng);
+        let result = Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 0, rng);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), Error::IndexIsZero);
     }
+
+    #[test]
+    fn participants_are_totally_ordered() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, _) =
+            Participant::<Secp256k1Sha256, SignerRole>::new_signer(&params, 1, rng).unwrap();
+        let (p2, _) =
+            Participant::<Secp256k1Sha256, SignerRole>::new_signer(&params, 2, rng).unwrap();
+        let (p3, _) =
+            Participant::<Secp256k1Sha256, SignerRole>::new_signer(&params, 3, rng).unwrap();
+        let (other_p2, _) =
+            Participant::<Secp256k1Sha256, SignerRole>::new_signer(&params, 2, rng).unwrap();
+
+        assert_eq!(p2.partial_cmp(&other_p2), Some(Ordering::Equal));
+        assert!(p1 < p2 && p2 < p3);
+
+        let roster = vec![p3.clone(), p1.clone(), p2.clone()];
+        let ordered: Vec<ParticipantId> = canonical_order(&roster).iter().map(|p| p.id()).collect();
+        assert_eq!(
+            ordered,
+            vec![ParticipantId(1), ParticipantId(2), ParticipantId(3)]
+        );
+
+        let set: BTreeSet<ParticipantId> = roster.iter().map(|p| p.id()).collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().next(), Some(&ParticipantId(1)));
+    }
+
+    #[test]
+    fn participant_roles() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (dealer, _, _) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        let (signer, _) =
+            Participant::<Secp256k1Sha256, SignerRole>::new_signer(&params, 2, rng).unwrap();
+        let (coordinator, _) =
+            Participant::<Secp256k1Sha256, CoordinatorRole>::new_coordinator(&params, 3, rng)
+                .unwrap();
+
+        assert_eq!(dealer.commitments().index, 1);
+        assert_eq!(dealer.public_key(), dealer.commitments().public_key());
+        assert!(signer.public_key().is_none());
+        assert!(coordinator.public_key().is_none());
+
+        // Deserialization checks the public material against the expected role.
+        let dealer_bytes = dealer.to_bytes().unwrap();
+        let signer_bytes = signer.to_bytes().unwrap();
+        assert_eq!(
+            Participant::<Secp256k1Sha256, DealerRole>::from_bytes(&dealer_bytes).unwrap(),
+            dealer
+        );
+        assert!(Participant::<Secp256k1Sha256, SignerRole>::from_bytes(&dealer_bytes).is_err());
+        assert!(Participant::<Secp256k1Sha256, DealerRole>::from_bytes(&signer_bytes).is_err());
+        assert!(Participant::<Secp256k1Sha256, CoordinatorRole>::from_bytes(&signer_bytes).is_ok());
+
+        let any = Participant::<Secp256k1Sha256>::from_bytes(&signer_bytes).unwrap();
+        assert!(any.clone().into_role::<DealerRole>().is_err());
+        assert_eq!(any.into_role::<SignerRole>().unwrap(), signer);
+        assert_eq!(
+            dealer.clone().into_any().into_role::<DealerRole>().unwrap(),
+            dealer
+        );
+    }
 }
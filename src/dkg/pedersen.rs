@@ -0,0 +1,877 @@
+//! The Pedersen module for running ICE-FROST Distributed Key Generation sessions
+//! with Pedersen's verifiable secret sharing scheme instead of Feldman's.
+//!
+//! With Feldman's scheme, the commitments \\( \phi\_{ik} = g^{a\_{ik}} \\) published by
+//! each dealer reveal its contribution to the group key from the start of the session.
+//! With Pedersen's scheme, dealers instead publish hiding commitments
+//! \\( C\_{ik} = g^{a\_{ik}} h^{b\_{ik}} \\) to two polynomials, for a second generator
+//! \\( h \\) whose discrete logarithm is unknown, and send to each participant the
+//! evaluations of both polynomials.
+//!
+//! Once all participants have verified their shares, dealers unblind their commitments
+//! by publishing the Feldman commitments \\( \phi\_{ik} \\) of their secret polynomial,
+//! against which each participant checks its share before deriving its signing key.
+//! The transcripts of the session hence leak nothing about the final group key
+//! until this extraction step.
+//!
+//! Unlike [`DistributedKeyGeneration`](super::DistributedKeyGeneration), this mode
+//! does not exclude misbehaving participants: any invalid proof or share aborts
+//! the session, listing the culprits in an [`Error::MisbehavingParticipants`].
+
+use core::ops::Mul;
+
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{Field, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{
+    DiffieHellmanPrivateKey, DiffieHellmanPublicKey, GroupVerifyingKey, IndividualSigningKey,
+};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{
+    calculate_lagrange_coefficients, hash_to_array, vec, Box, Scalar, ToString, Vec,
+};
+use crate::validation::{
+    deserialize_validated, validate_dh_public_key, validate_point, validate_points, ValidatePoints,
+};
+use crate::{Element, Error, FrostResult};
+
+use super::nizkpok::NizkPokOfSecretKey;
+use super::round_types::{DkgState, RoundOne, RoundTwo};
+use super::secret_share::{
+    decrypt_share_for_round, encrypt_share_for_round, serialize_dh_shared_key, share_session_id,
+    Coefficients, EncryptedSecretShare, SecretShare, VerifiableSecretSharingCommitment,
+    SHARE_DISTRIBUTION_ROUND, SHARE_SALT_LENGTH,
+};
+
+/// The nonce derivation round of the blinding shares, distinct from the one of
+/// the secret shares so that both are never encrypted under the same nonce.
+const BLINDING_SHARE_DISTRIBUTION_ROUND: u32 = SHARE_DISTRIBUTION_ROUND | 0x8000_0000;
+
+/// Derive the second generator \\( h \\) of Pedersen commitments for this [`CipherSuite`].
+///
+/// It is obtained by hashing this [`CipherSuite`]'s context string along with an
+/// incrementing counter until the output maps to a valid group element, so that
+/// its discrete logarithm in base \\( g \\) is unknown.
+pub fn pedersen_generator<C: CipherSuite>() -> FrostResult<C, Element<C>> {
+    let dst = C::context_string() + "pedersen generator";
+
+    for counter in 0u32..=u32::MAX {
+        let bytes = hash_to_array::<C>(dst.as_bytes(), &counter.to_le_bytes())?;
+        if let Some(point) = <C::G as CurveGroup>::Affine::from_random_bytes(bytes.as_ref()) {
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return Ok(Element(point.into()));
+            }
+        }
+    }
+
+    Err(Error::Custom(
+        "Could not derive a Pedersen generator".to_string(),
+    ))
+}
+
+/// A proof of knowledge of an opening \\( (a, b) \\) of a Pedersen commitment
+/// \\( C = g^a h^b \\), without revealing \\( g^a \\).
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct NizkPokOfOpening<C: CipherSuite> {
+    /// The commitment to the random nonces of the proof.
+    r: C::G,
+    /// The response for the committed secret.
+    z_secret: Scalar<C>,
+    /// The response for the blinding factor.
+    z_blinding: Scalar<C>,
+}
+
+impl<C: CipherSuite> NizkPokOfOpening<C> {
+    /// Prove knowledge of the opening `(secret, blinding)` of `commitment`.
+    pub(crate) fn prove(
+        index: u32,
+        secret: &Scalar<C>,
+        blinding: &Scalar<C>,
+        commitment: &C::G,
+        h: &C::G,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Self> {
+        let k_secret = Scalar::<C>::rand(&mut csprng);
+        let k_blinding = Scalar::<C>::rand(&mut csprng);
+        let r = C::G::generator() * k_secret + h.mul(k_blinding);
+
+        let challenge = Self::challenge(index, commitment, &r)?;
+
+        Ok(Self {
+            r,
+            z_secret: k_secret + *secret * challenge,
+            z_blinding: k_blinding + *blinding * challenge,
+        })
+    }
+
+    /// Verify that the prover knows an opening of `commitment`.
+    pub(crate) fn verify(&self, index: u32, commitment: &C::G, h: &C::G) -> FrostResult<C, ()> {
+        let challenge = Self::challenge(index, commitment, &self.r)?;
+
+        match C::G::generator() * self.z_secret + h.mul(self.z_blinding)
+            == self.r + commitment.mul(challenge)
+        {
+            true => Ok(()),
+            false => Err(Error::InvalidProofOfKnowledge),
+        }
+    }
+
+    fn challenge(index: u32, commitment: &C::G, r: &C::G) -> FrostResult<C, Scalar<C>> {
+        let mut message = index.to_le_bytes().to_vec();
+        commitment
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        r.serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+
+        C::hash_to_field(
+            (C::context_string() + "pedersen opening").as_bytes(),
+            &message,
+        )
+    }
+}
+
+/// A commitment to a dealer's secret and blinding polynomial coefficients for
+/// Pedersen's verifiable secret sharing scheme.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenCommitment<C: CipherSuite> {
+    /// The index of this participant.
+    pub index: u32,
+    /// The hiding commitments to the participant's secret coefficients.
+    pub points: Vec<C::G>,
+}
+
+impl<C: CipherSuite> PedersenCommitment<C> {
+    /// Serialize this [`PedersenCommitment`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PedersenCommitment`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Evaluate \\( g^{f(x)} h^{f'(x)} \\) without knowing the coefficients of the polynomials.
+    pub(crate) fn evaluate(&self, term: &Scalar<C>) -> C::G {
+        let mut sum = C::G::zero();
+
+        // Evaluate using Horner's method.
+        for (k, coefficient) in self.points.iter().rev().enumerate() {
+            sum += coefficient;
+
+            if k != (self.points.len() - 1) {
+                sum *= term;
+            }
+        }
+
+        sum
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PedersenCommitment<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>(&self.points)
+    }
+}
+
+/// The secret and blinding polynomial coefficients of a Pedersen dealer.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenCoefficients<C: CipherSuite> {
+    /// The index of the dealer.
+    pub(crate) index: u32,
+    /// The coefficients of the secret polynomial.
+    pub(crate) secret: Coefficients<C>,
+    /// The coefficients of the blinding polynomial.
+    pub(crate) blinding: Coefficients<C>,
+}
+
+impl<C: CipherSuite> PedersenCoefficients<C> {
+    /// Serialize these [`PedersenCoefficients`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize [`PedersenCoefficients`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+
+    /// Unblind the commitments of this dealer, by computing the Feldman commitment
+    /// to its secret polynomial.
+    ///
+    /// This must only be published once all participants have verified their shares,
+    /// i.e. have moved to [`RoundTwo`].
+    pub fn unblind(&self) -> VerifiableSecretSharingCommitment<C> {
+        VerifiableSecretSharingCommitment {
+            index: self.index,
+            points: self
+                .secret
+                .0
+                .iter()
+                .map(|coefficient| C::G::generator() * coefficient)
+                .collect(),
+        }
+    }
+}
+
+/// A participant of a Pedersen Distributed Key Generation session.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenParticipant<C: CipherSuite> {
+    /// The index of this participant, to keep the participants in order.
+    pub index: u32,
+    /// The public key used to derive symmetric keys for encrypting and
+    /// decrypting shares.
+    pub dh_public_key: DiffieHellmanPublicKey<C>,
+    /// The hiding commitments to the participant's secret and blinding coefficients.
+    pub commitments: PedersenCommitment<C>,
+    /// The zero-knowledge proof of knowledge of the opening of the first commitment.
+    pub proof_of_opening: NizkPokOfOpening<C>,
+    /// The zero-knowledge proof of knowledge of the DH private key.
+    pub proof_of_dh_private_key: NizkPokOfSecretKey<C>,
+}
+
+impl<C: CipherSuite> PedersenParticipant<C> {
+    /// Construct a new dealer for a Pedersen distributed key generation session.
+    ///
+    /// # Returns
+    ///
+    /// A [`PedersenParticipant`] to be sent to every other participant, along with
+    /// the dealer's [`PedersenCoefficients`] and Diffie-Hellman private key, which
+    /// must be kept private.
+    pub fn new_dealer(
+        parameters: &ThresholdParameters<C>,
+        index: u32,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, PedersenCoefficients<C>, DiffieHellmanPrivateKey<C>)> {
+        if index == 0 {
+            return Err(Error::IndexIsZero);
+        }
+
+        let t = parameters.t as usize;
+        let h = pedersen_generator::<C>()?.0;
+
+        let dh_private_key = DiffieHellmanPrivateKey(Scalar::<C>::rand(&mut rng));
+        let dh_public_key =
+            DiffieHellmanPublicKey::<C>::new(Element(C::G::generator().mul(dh_private_key.0)));
+        let proof_of_dh_private_key = NizkPokOfSecretKey::<C>::prove(
+            index,
+            &crate::Scalar(dh_private_key.0),
+            &dh_public_key.element(),
+            &mut rng,
+        )?;
+
+        let secret = Coefficients((0..t).map(|_| Scalar::<C>::rand(&mut rng)).collect());
+        let blinding = Coefficients((0..t).map(|_| Scalar::<C>::rand(&mut rng)).collect());
+
+        let commitments = PedersenCommitment {
+            index,
+            points: secret
+                .0
+                .iter()
+                .zip(blinding.0.iter())
+                .map(|(a, b)| C::G::generator() * a + h.mul(b))
+                .collect(),
+        };
+
+        let proof_of_opening = NizkPokOfOpening::prove(
+            index,
+            &secret.0[0],
+            &blinding.0[0],
+            &commitments.points[0],
+            &h,
+            &mut rng,
+        )?;
+
+        Ok((
+            Self {
+                index,
+                dh_public_key,
+                commitments,
+                proof_of_opening,
+                proof_of_dh_private_key,
+            },
+            PedersenCoefficients {
+                index,
+                secret,
+                blinding,
+            },
+            dh_private_key,
+        ))
+    }
+
+    /// Serialize this [`PedersenParticipant`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PedersenParticipant`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PedersenParticipant<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_dh_public_key::<C>(self.index, &self.dh_public_key.key)?;
+        self.commitments.validate_points()?;
+        validate_point::<C>(&self.proof_of_opening.r)
+    }
+}
+
+/// The encrypted evaluations of a dealer's secret and blinding polynomials
+/// for a given participant.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EncryptedPedersenShare<C: CipherSuite> {
+    /// The encrypted evaluation of the secret polynomial.
+    pub share: EncryptedSecretShare<C>,
+    /// The encrypted evaluation of the blinding polynomial.
+    pub blinding: EncryptedSecretShare<C>,
+}
+
+impl<C: CipherSuite> EncryptedPedersenShare<C> {
+    /// Serialize this [`EncryptedPedersenShare`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`EncryptedPedersenShare`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+/// State machine structures for holding intermediate values during a
+/// Pedersen distributed key generation protocol run.
+#[derive(Debug)]
+pub struct PedersenDistributedKeyGeneration<S: DkgState, C: CipherSuite> {
+    state: Box<PedersenState<C>>,
+    data: S,
+}
+
+/// Shared state which occurs across all rounds of a Pedersen distributed key generation.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+struct PedersenState<C: CipherSuite> {
+    /// The parameters for this instantiation of a threshold signature.
+    parameters: ThresholdParameters<C>,
+    /// The index of the participant.
+    index: u32,
+    /// The DH private key for deriving symmetric keys to decrypt secret shares.
+    dh_private_key: DiffieHellmanPrivateKey<C>,
+    /// The DH public key of the participant.
+    dh_public_key: DiffieHellmanPublicKey<C>,
+    /// The hiding commitments of all participants, sorted by index.
+    their_commitments: Vec<PedersenCommitment<C>>,
+    /// The DH public keys of all participants, sorted by index.
+    their_dh_public_keys: Vec<(u32, DiffieHellmanPublicKey<C>)>,
+    /// The encrypted shares this participant has calculated for all the participants.
+    their_encrypted_shares: Option<Vec<EncryptedPedersenShare<C>>>,
+    /// The verified secret shares this participant has received from all the participants.
+    my_secret_shares: Option<Vec<SecretShare<C>>>,
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PedersenState<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.dh_public_key.validate_points()?;
+        for (index, dh_public_key) in self.their_dh_public_keys.iter() {
+            validate_dh_public_key::<C>(*index, &dh_public_key.key)?;
+        }
+        self.their_commitments
+            .iter()
+            .try_for_each(ValidatePoints::validate_points)
+    }
+}
+
+impl<S: DkgState, C: CipherSuite> PedersenDistributedKeyGeneration<S, C> {
+    /// Serialize this [`PedersenDistributedKeyGeneration`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.state
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        self.data
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PedersenDistributedKeyGeneration`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let state = Box::new(deserialize_validated::<C, PedersenState<C>>(bytes)?);
+
+        // The round marker is empty, hence does not consume any byte.
+        let data = S::deserialize_compressed(&[][..])
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        Ok(Self { state, data })
+    }
+}
+
+impl<C: CipherSuite> PedersenDistributedKeyGeneration<RoundOne, C> {
+    /// Start a new Pedersen distributed key generation session among `participants`.
+    ///
+    /// This checks the proofs of knowledge of all participants, and computes the
+    /// encrypted shares of this dealer for all of them.
+    ///
+    /// As the Diffie-Hellman keys of the participants may be reused across sessions,
+    /// the shares are encrypted with nonces derived from a salt drawn from `rng`,
+    /// which is fresh for each session.
+    ///
+    /// # Returns
+    ///
+    /// An updated state machine for the distributed key generation protocol if all
+    /// the proofs verified successfully, otherwise an [`Error::MisbehavingParticipants`]
+    /// listing the participants whose proofs were incorrect.
+    pub fn bootstrap(
+        parameters: &ThresholdParameters<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        my_coefficients: &PedersenCoefficients<C>,
+        participants: &[PedersenParticipant<C>],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Self> {
+        if participants.len() != parameters.n as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                participants.len(),
+                parameters.n,
+            ));
+        }
+
+        let h = pedersen_generator::<C>()?.0;
+        let dh_public_key =
+            DiffieHellmanPublicKey::<C>::new(Element(C::G::generator().mul(dh_private_key.0)));
+
+        let mut participants: Vec<&PedersenParticipant<C>> = participants.iter().collect();
+        participants.sort_by_key(|p| p.index);
+
+        let mut misbehaving_participants = Vec::new();
+        for (i, p) in participants.iter().enumerate() {
+            let is_duplicate = (i > 0 && participants[i - 1].index == p.index)
+                || (i + 1 < participants.len() && participants[i + 1].index == p.index);
+
+            if is_duplicate
+                || p.index == 0
+                || p.commitments.index != p.index
+                || p.commitments.points.len() != parameters.t as usize
+                || p.proof_of_opening
+                    .verify(p.index, &p.commitments.points[0], &h)
+                    .is_err()
+                || validate_dh_public_key::<C>(p.index, &p.dh_public_key.key).is_err()
+                || p.proof_of_dh_private_key
+                    .verify(p.index, &p.dh_public_key.element())
+                    .is_err()
+            {
+                misbehaving_participants.push(p.index);
+            }
+        }
+        if !misbehaving_participants.is_empty() {
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        // The salt of this session, sent along each share, separates the encryption
+        // nonces of sessions reusing the same Diffie-Hellman keys.
+        let mut salt = vec![0u8; SHARE_SALT_LENGTH];
+        rng.fill_bytes(&mut salt);
+
+        let mut their_encrypted_shares = Vec::with_capacity(participants.len());
+        for p in participants.iter() {
+            let share = SecretShare::evaluate_polynomial(
+                &my_coefficients.index,
+                &p.index,
+                &my_coefficients.secret,
+            )?;
+            let blinding = SecretShare::evaluate_polynomial(
+                &my_coefficients.index,
+                &p.index,
+                &my_coefficients.blinding,
+            )?;
+
+            let dh_key = p.dh_public_key.key * dh_private_key.0;
+            let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_key)?;
+            let session_id = share_session_id(&dh_public_key, &p.dh_public_key)?;
+
+            their_encrypted_shares.push(EncryptedPedersenShare {
+                share: encrypt_share_for_round(
+                    &share,
+                    &dh_key_bytes,
+                    &session_id,
+                    &salt,
+                    SHARE_DISTRIBUTION_ROUND,
+                )?,
+                blinding: encrypt_share_for_round(
+                    &blinding,
+                    &dh_key_bytes,
+                    &session_id,
+                    &salt,
+                    BLINDING_SHARE_DISTRIBUTION_ROUND,
+                )?,
+            });
+
+            dh_key_bytes.zeroize();
+        }
+
+        Ok(Self {
+            state: Box::new(PedersenState {
+                parameters: *parameters,
+                index: my_coefficients.index,
+                dh_private_key: dh_private_key.clone(),
+                dh_public_key,
+                their_commitments: participants.iter().map(|p| p.commitments.clone()).collect(),
+                their_dh_public_keys: participants
+                    .iter()
+                    .map(|p| (p.index, p.dh_public_key.clone()))
+                    .collect(),
+                their_encrypted_shares: Some(their_encrypted_shares),
+                my_secret_shares: None,
+            }),
+            data: RoundOne {},
+        })
+    }
+
+    /// Retrieve the encrypted shares this dealer computed for all participants,
+    /// sorted by participant index.
+    pub fn their_encrypted_shares(&self) -> FrostResult<C, &Vec<EncryptedPedersenShare<C>>> {
+        self.state
+            .their_encrypted_shares
+            .as_ref()
+            .ok_or(Error::NoEncryptedShares)
+    }
+
+    /// Progress to round two of the Pedersen distributed key generation protocol,
+    /// by decrypting and verifying the shares received from all participants.
+    ///
+    /// # Returns
+    ///
+    /// An updated state machine for the distributed key generation protocol if all
+    /// the shares verified successfully, otherwise an [`Error::MisbehavingParticipants`]
+    /// listing the dealers whose shares were incorrect.
+    pub fn to_round_two(
+        mut self,
+        my_encrypted_shares: Vec<EncryptedPedersenShare<C>>,
+    ) -> FrostResult<C, PedersenDistributedKeyGeneration<RoundTwo, C>> {
+        self.state.their_encrypted_shares = None;
+
+        if my_encrypted_shares.len() != self.state.parameters.n as usize {
+            return Err(Error::MissingShares);
+        }
+
+        let h = pedersen_generator::<C>()?.0;
+        let term = Scalar::<C>::from(self.state.index);
+
+        let mut my_secret_shares = Vec::with_capacity(my_encrypted_shares.len());
+        let mut misbehaving_participants = Vec::new();
+        for (commitment, (dealer_index, dealer_dh_public_key)) in self
+            .state
+            .their_commitments
+            .iter()
+            .zip(self.state.their_dh_public_keys.iter())
+        {
+            let encrypted_share = match my_encrypted_shares.iter().find(|encrypted| {
+                encrypted.share.sender_index == *dealer_index
+                    && encrypted.blinding.sender_index == *dealer_index
+                    && encrypted.share.receiver_index == self.state.index
+                    && encrypted.blinding.receiver_index == self.state.index
+            }) {
+                Some(encrypted_share) => encrypted_share,
+                None => {
+                    misbehaving_participants.push(*dealer_index);
+                    continue;
+                }
+            };
+
+            let dh_key = dealer_dh_public_key.key * self.state.dh_private_key.0;
+            let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_key)?;
+            let session_id = share_session_id(dealer_dh_public_key, &self.state.dh_public_key)?;
+
+            let share = decrypt_share_for_round(
+                &encrypted_share.share,
+                &dh_key_bytes,
+                &session_id,
+                SHARE_DISTRIBUTION_ROUND,
+            );
+            let blinding = decrypt_share_for_round(
+                &encrypted_share.blinding,
+                &dh_key_bytes,
+                &session_id,
+                BLINDING_SHARE_DISTRIBUTION_ROUND,
+            );
+            dh_key_bytes.zeroize();
+
+            match (share, blinding) {
+                (Ok(share), Ok(blinding))
+                    if C::G::generator() * share.polynomial_evaluation.0
+                        + h.mul(blinding.polynomial_evaluation.0)
+                        == commitment.evaluate(&term) =>
+                {
+                    my_secret_shares.push(share)
+                }
+                _ => misbehaving_participants.push(*dealer_index),
+            }
+        }
+
+        if !misbehaving_participants.is_empty() {
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        self.state.my_secret_shares = Some(my_secret_shares);
+
+        Ok(PedersenDistributedKeyGeneration {
+            state: self.state,
+            data: RoundTwo {},
+        })
+    }
+}
+
+impl<C: CipherSuite> PedersenDistributedKeyGeneration<RoundTwo, C> {
+    /// Complete the session with the `unblinded_commitments` published by all
+    /// dealers through [`PedersenCoefficients::unblind`], and calculate this
+    /// participant's secret signing key and the group's public verification key.
+    ///
+    /// # Returns
+    ///
+    /// The [`GroupVerifyingKey`] and this participant's [`IndividualSigningKey`] if
+    /// all unblinded commitments are consistent with the shares received, otherwise
+    /// an [`Error::MisbehavingParticipants`] listing the inconsistent dealers.
+    pub fn finish(
+        mut self,
+        unblinded_commitments: &[VerifiableSecretSharingCommitment<C>],
+    ) -> FrostResult<C, (GroupVerifyingKey<C>, IndividualSigningKey<C>)> {
+        let my_secret_shares = self.state.my_secret_shares.as_ref().ok_or_else(|| {
+            Error::Custom("Could not retrieve participant's secret shares".to_string())
+        })?;
+
+        let mut dealer_keys = Vec::with_capacity(my_secret_shares.len());
+        let mut misbehaving_participants = Vec::new();
+        for share in my_secret_shares.iter() {
+            match unblinded_commitments
+                .iter()
+                .find(|commitment| commitment.index == share.sender_index)
+            {
+                Some(commitment)
+                    if commitment.points.len() == self.state.parameters.t as usize
+                        && share.verify(commitment).is_ok() =>
+                {
+                    dealer_keys.push((share.sender_index, commitment.points[0]))
+                }
+                _ => misbehaving_participants.push(share.sender_index),
+            }
+        }
+
+        if !misbehaving_participants.is_empty() {
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        let index_vector: Vec<u32> = my_secret_shares.iter().map(|s| s.sender_index).collect();
+
+        let mut key = Scalar::<C>::ZERO;
+        let mut group_key = C::G::zero();
+        for (share, (index, dealer_key)) in my_secret_shares.iter().zip(dealer_keys.iter()) {
+            let coeff = calculate_lagrange_coefficients::<C>(*index, &index_vector)?;
+            key += coeff * share.polynomial_evaluation.0;
+            group_key += dealer_key.mul(coeff);
+        }
+
+        self.state.my_secret_shares.zeroize();
+
+        Ok((
+            GroupVerifyingKey::new(Element(group_key)),
+            IndividualSigningKey {
+                index: self.state.index,
+                key,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    type Dkg<S> = PedersenDistributedKeyGeneration<S, Secp256k1Sha256>;
+
+    fn do_round_one(
+        params: &ThresholdParameters<Secp256k1Sha256>,
+    ) -> (
+        Vec<PedersenParticipant<Secp256k1Sha256>>,
+        Vec<PedersenCoefficients<Secp256k1Sha256>>,
+        Vec<Dkg<RoundOne>>,
+    ) {
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, c, dh_sk) = PedersenParticipant::new_dealer(params, i, OsRng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let states = coefficients
+            .iter()
+            .zip(dh_secret_keys.iter())
+            .map(|(c, dh_sk)| Dkg::bootstrap(params, dh_sk, c, &participants, OsRng).unwrap())
+            .collect();
+
+        (participants, coefficients, states)
+    }
+
+    fn my_encrypted_shares(
+        states: &[Dkg<RoundOne>],
+        i: usize,
+    ) -> Vec<EncryptedPedersenShare<Secp256k1Sha256>> {
+        states
+            .iter()
+            .map(|state| state.their_encrypted_shares().unwrap()[i].clone())
+            .collect()
+    }
+
+    #[test]
+    fn pedersen_keygen_2_out_of_3() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let (participants, coefficients, states) = do_round_one(&params);
+
+        // The commitments do not reveal the dealers' public keys.
+        for (p, c) in participants.iter().zip(coefficients.iter()) {
+            assert_ne!(p.commitments.points[0], c.unblind().points[0]);
+        }
+
+        let states: Vec<Dkg<RoundTwo>> = (0..3)
+            .map(|i| {
+                let bytes = states[i].to_bytes().unwrap();
+                Dkg::<RoundOne>::from_bytes(&bytes)
+                    .unwrap()
+                    .to_round_two(my_encrypted_shares(&states, i))
+                    .unwrap()
+            })
+            .collect();
+
+        let unblinded: Vec<_> = coefficients.iter().map(|c| c.unblind()).collect();
+        let keys: Vec<_> = states
+            .into_iter()
+            .map(|state| state.finish(&unblinded).unwrap())
+            .collect();
+
+        assert!(keys.iter().all(|(group_key, _)| *group_key == keys[0].0));
+
+        // Any two participants can recover the group secret key.
+        let indices = [keys[0].1.index, keys[2].1.index];
+        let secret: Scalar<Secp256k1Sha256> = [&keys[0].1, &keys[2].1]
+            .iter()
+            .map(|sk| {
+                calculate_lagrange_coefficients::<Secp256k1Sha256>(sk.index, &indices).unwrap()
+                    * sk.key
+            })
+            .sum();
+        assert_eq!(
+            keys[0].0.key,
+            <Secp256k1Sha256 as CipherSuite>::G::generator() * secret
+        );
+    }
+
+    #[test]
+    fn pedersen_keygen_salts_nonces_per_session() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, c, dh_sk) = PedersenParticipant::new_dealer(&params, i, OsRng).unwrap();
+            participants.push(p);
+            coefficients.push(c);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        // Two sessions reusing the same Diffie-Hellman keys and coefficients.
+        let first = Dkg::bootstrap(
+            &params,
+            &dh_secret_keys[0],
+            &coefficients[0],
+            &participants,
+            OsRng,
+        )
+        .unwrap();
+        let second = Dkg::bootstrap(
+            &params,
+            &dh_secret_keys[0],
+            &coefficients[0],
+            &participants,
+            OsRng,
+        )
+        .unwrap();
+
+        for (first, second) in first
+            .their_encrypted_shares()
+            .unwrap()
+            .iter()
+            .zip(second.their_encrypted_shares().unwrap())
+        {
+            assert_ne!(first.share.nonce, second.share.nonce);
+            assert_ne!(first.blinding.nonce, second.blinding.nonce);
+            assert_ne!(first.share.nonce, first.blinding.nonce);
+        }
+    }
+
+    #[test]
+    fn pedersen_keygen_detects_inconsistent_unblinding() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let (_, coefficients, states) = do_round_one(&params);
+
+        let state = Dkg::<RoundOne>::from_bytes(&states[0].to_bytes().unwrap())
+            .unwrap()
+            .to_round_two(my_encrypted_shares(&states, 0))
+            .unwrap();
+
+        let mut unblinded: Vec<_> = coefficients.iter().map(|c| c.unblind()).collect();
+        unblinded[1].points[0] += <Secp256k1Sha256 as CipherSuite>::G::generator();
+
+        assert_eq!(
+            state.finish(&unblinded).unwrap_err(),
+            Error::MisbehavingParticipants(vec![2])
+        );
+    }
+
+    #[test]
+    fn pedersen_keygen_detects_invalid_shares() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let (_, _, states) = do_round_one(&params);
+
+        let mut shares = my_encrypted_shares(&states, 0);
+        shares[2].blinding = shares[1].blinding.clone();
+
+        let state = Dkg::<RoundOne>::from_bytes(&states[0].to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            state.to_round_two(shares).unwrap_err(),
+            Error::MisbehavingParticipants(vec![3])
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! The qualification module for deciding which dealers of an ICE-FROST Distributed
+//! Key Generation session are qualified, from the complaints and share receipts
+//! broadcast by the participants.
+//!
+//! Each participant feeds the complaints and [`ShareReceipt`]s it observes to a
+//! [`QualificationTracker`], obtained from its DKG state along with a
+//! [`QualificationPolicy`]. Complaints are adjudicated as with
+//! [`DistributedKeyGeneration::blame`](super::DistributedKeyGeneration::blame), and
+//! only distinct complaints and receipts are counted, so that the resulting
+//! [`QualifiedSet`] does not depend on the order in which they were observed. Honest
+//! participants observing the same broadcast messages hence compute the same set of
+//! qualified dealers, without an additional agreement round.
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::DiffieHellmanPublicKey;
+use crate::parameters::ThresholdParameters;
+use crate::utils::{BTreeMap, Vec};
+use crate::{Error, FrostResult};
+
+use super::complaint::Complaint;
+use super::key_generation::blame_complaint;
+use super::receipt::ShareReceipt;
+use super::secret_share::{EncryptedSecretShare, VerifiableSecretSharingCommitment};
+
+/// The rules by which dealers are disqualified at the end of a DKG session.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QualificationPolicy {
+    /// The number of distinct valid complaints after which a dealer is disqualified.
+    pub disqualify_after_k_valid_complaints: usize,
+    /// The number of distinct recipients that must confirm having received a valid
+    /// share from a dealer for it to be qualified.
+    pub require_m_confirmations: usize,
+}
+
+impl Default for QualificationPolicy {
+    /// A single valid complaint disqualifies a dealer, and no confirmation is required.
+    fn default() -> Self {
+        Self {
+            disqualify_after_k_valid_complaints: 1,
+            require_m_confirmations: 0,
+        }
+    }
+}
+
+impl QualificationPolicy {
+    /// Create a new [`QualificationPolicy`].
+    pub const fn new(
+        disqualify_after_k_valid_complaints: usize,
+        require_m_confirmations: usize,
+    ) -> Self {
+        Self {
+            disqualify_after_k_valid_complaints,
+            require_m_confirmations,
+        }
+    }
+}
+
+/// The decision of a [`QualificationTracker`] on the dealers of a DKG session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualifiedSet {
+    /// The sorted indices of the qualified dealers.
+    pub(crate) qualified: Vec<u32>,
+    /// The sorted indices of the disqualified dealers.
+    pub(crate) disqualified: Vec<u32>,
+}
+
+impl QualifiedSet {
+    /// The sorted indices of the qualified dealers.
+    pub fn qualified(&self) -> &[u32] {
+        &self.qualified
+    }
+
+    /// The sorted indices of the disqualified dealers.
+    pub fn disqualified(&self) -> &[u32] {
+        &self.disqualified
+    }
+
+    /// Whether the dealer with the given `index` is qualified.
+    pub fn is_qualified(&self, index: u32) -> bool {
+        self.qualified.binary_search(&index).is_ok()
+    }
+}
+
+/// A tracker of the complaints and share receipts of a DKG session, obtained with
+/// [`DistributedKeyGeneration::qualification_tracker`](super::DistributedKeyGeneration::qualification_tracker).
+#[derive(Clone, Debug)]
+pub struct QualificationTracker<C: CipherSuite> {
+    pub(crate) parameters: ThresholdParameters<C>,
+    pub(crate) policy: QualificationPolicy,
+    pub(crate) their_commitments: Vec<VerifiableSecretSharingCommitment<C>>,
+    pub(crate) their_dh_public_keys: Vec<(u32, DiffieHellmanPublicKey<C>)>,
+    /// The sorted indices of the counterparts of the valid complaints blaming each
+    /// participant, being the makers of the complaints against a dealer, or the
+    /// accused dealers of the unfounded complaints of a participant.
+    pub(crate) complaints: BTreeMap<u32, Vec<u32>>,
+    /// The sorted indices of the recipients having confirmed their share from each dealer.
+    pub(crate) confirmations: BTreeMap<u32, Vec<u32>>,
+}
+
+impl<C: CipherSuite> QualificationTracker<C> {
+    /// Adjudicate a `complaint` broadcast by a participant, along with the
+    /// `encrypted_share` it was made against.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the index of the blamed participant,
+    /// being the accused dealer if the complaint is valid and its maker otherwise, or
+    /// an [`Error::MisroutedShare`] if the `encrypted_share` was not sent by the accused
+    /// dealer to the maker of the complaint.
+    pub fn record_complaint(
+        &mut self,
+        encrypted_share: &EncryptedSecretShare<C>,
+        complaint: &Complaint<C>,
+    ) -> FrostResult<C, u32> {
+        if encrypted_share.sender_index != complaint.accused_index
+            || encrypted_share.receiver_index != complaint.maker_index
+        {
+            return Err(Error::MisroutedShare(
+                encrypted_share.sender_index,
+                encrypted_share.receiver_index,
+            ));
+        }
+
+        let blamed = blame_complaint(
+            &self.their_commitments,
+            &self.their_dh_public_keys,
+            encrypted_share,
+            complaint,
+        );
+        let counterpart = match blamed == complaint.accused_index {
+            true => complaint.maker_index,
+            false => complaint.accused_index,
+        };
+        insert_sorted(self.complaints.entry(blamed).or_default(), counterpart);
+
+        Ok(blamed)
+    }
+
+    /// Record a [`ShareReceipt`] broadcast by a recipient, confirming the share of
+    /// its dealer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is empty if the receipt is valid, otherwise
+    /// an [`Error::InvalidShareReceipt`] if it cannot be verified against the public
+    /// commitment of its dealer and the Diffie-Hellman public key of its recipient.
+    pub fn record_receipt(&mut self, receipt: &ShareReceipt<C>) -> FrostResult<C, ()> {
+        let commitment = self
+            .their_commitments
+            .iter()
+            .find(|commitment| commitment.index == receipt.dealer_index)
+            .ok_or(Error::InvalidShareReceipt)?;
+        let (_, dh_public_key) = self
+            .their_dh_public_keys
+            .iter()
+            .find(|(index, _)| *index == receipt.recipient_index)
+            .ok_or(Error::InvalidShareReceipt)?;
+        receipt.verify(dh_public_key, commitment)?;
+
+        insert_sorted(
+            self.confirmations.entry(receipt.dealer_index).or_default(),
+            receipt.recipient_index,
+        );
+
+        Ok(())
+    }
+
+    /// The number of distinct valid complaints blaming the participant with the given `index`.
+    pub fn complaint_count(&self, index: u32) -> usize {
+        self.complaints.get(&index).map_or(0, Vec::len)
+    }
+
+    /// The number of distinct recipients having confirmed the share of the dealer
+    /// with the given `index`.
+    pub fn confirmation_count(&self, index: u32) -> usize {
+        self.confirmations.get(&index).map_or(0, Vec::len)
+    }
+
+    /// Decide which dealers are qualified according to the [`QualificationPolicy`] of
+    /// this tracker, once all complaints and receipts have been recorded.
+    ///
+    /// A dealer is disqualified if it is blamed by at least
+    /// `disqualify_after_k_valid_complaints` distinct complaints, including unfounded
+    /// complaints it made as a recipient, or if fewer than `require_m_confirmations`
+    /// distinct recipients confirmed its share.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`QualifiedSet`], otherwise an
+    /// [`Error::TooManyInvalidParticipants`] listing the disqualified dealers if fewer
+    /// than \\( t \\) dealers are qualified.
+    pub fn qualified_set(&self) -> FrostResult<C, QualifiedSet> {
+        let mut qualified = Vec::new();
+        let mut disqualified = Vec::new();
+        for commitment in self.their_commitments.iter() {
+            if self.complaint_count(commitment.index)
+                >= self.policy.disqualify_after_k_valid_complaints
+                || self.confirmation_count(commitment.index) < self.policy.require_m_confirmations
+            {
+                disqualified.push(commitment.index);
+            } else {
+                qualified.push(commitment.index);
+            }
+        }
+        qualified.sort_unstable();
+        disqualified.sort_unstable();
+
+        if qualified.len() < self.parameters.t as usize {
+            return Err(Error::TooManyInvalidParticipants(disqualified));
+        }
+
+        Ok(QualifiedSet {
+            qualified,
+            disqualified,
+        })
+    }
+}
+
+/// Insert `index` into the sorted `indices`, unless already present.
+fn insert_sorted(indices: &mut Vec<u32>, index: u32) {
+    if let Err(position) = indices.binary_search(&index) {
+        indices.insert(position, index);
+    }
+}
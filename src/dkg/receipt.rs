@@ -0,0 +1,141 @@
+//! The receipt module for acknowledging the correct distribution of
+//! secret shares during an ICE-FROST Distributed Key Generation session.
+//!
+//! Once a participant has decrypted and verified all the secret shares it received,
+//! it can issue a [`ShareReceipt`] to each dealer, signed with its Diffie-Hellman
+//! private key. Dealers can collect these receipts before finishing their session,
+//! and present them to an external registry as a proof that the ceremony completed.
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{DiffieHellmanPrivateKey, DiffieHellmanPublicKey};
+use crate::utils::{Scalar, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::secret_share::VerifiableSecretSharingCommitment;
+
+/// A receipt acknowledging that a recipient correctly decrypted its secret share
+/// from a dealer, and verified it against the dealer's public commitment.
+///
+/// It is a Schnorr signature made with the recipient's Diffie-Hellman private key over
+/// the indices of the dealer and the recipient, and the hash of the dealer's commitment.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ShareReceipt<C: CipherSuite> {
+    /// The index of the dealer who distributed the share.
+    pub(crate) dealer_index: u32,
+    /// The index of the recipient of the share.
+    pub(crate) recipient_index: u32,
+    /// The hash of the dealer's commitment the share was verified against.
+    pub(crate) commitment_hash: Vec<u8>,
+    /// The commitment of the Schnorr signature.
+    pub(crate) r: C::G,
+    /// The response of the Schnorr signature.
+    pub(crate) s: Scalar<C>,
+}
+
+impl<C: CipherSuite> ShareReceipt<C> {
+    /// Issue a new [`ShareReceipt`] for the share received by `recipient_index`
+    /// from the dealer with the given `commitment`.
+    pub(crate) fn new(
+        recipient_index: u32,
+        commitment: &VerifiableSecretSharingCommitment<C>,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        dh_public_key: &DiffieHellmanPublicKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Self> {
+        let commitment_hash = C::h5(&commitment.to_bytes()?)?.as_ref().to_vec();
+
+        let k = Scalar::<C>::rand(&mut csprng);
+        let r = C::G::generator() * k;
+
+        let mut receipt = Self {
+            dealer_index: commitment.index,
+            recipient_index,
+            commitment_hash,
+            r,
+            s: Scalar::<C>::ZERO,
+        };
+        let challenge = receipt.challenge(dh_public_key)?;
+        receipt.s = k + dh_private_key.0 * challenge;
+
+        Ok(receipt)
+    }
+
+    /// The index of the dealer who distributed the share.
+    pub fn dealer_index(&self) -> u32 {
+        self.dealer_index
+    }
+
+    /// The index of the recipient of the share.
+    pub fn recipient_index(&self) -> u32 {
+        self.recipient_index
+    }
+
+    /// Verify this [`ShareReceipt`] against the Diffie-Hellman public key of
+    /// its recipient and the public commitment of its dealer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the receipt is valid.
+    pub fn verify(
+        &self,
+        recipient_dh_public_key: &DiffieHellmanPublicKey<C>,
+        dealer_commitment: &VerifiableSecretSharingCommitment<C>,
+    ) -> FrostResult<C, ()> {
+        if self.dealer_index != dealer_commitment.index
+            || self.commitment_hash != C::h5(&dealer_commitment.to_bytes()?)?.as_ref()
+        {
+            return Err(Error::InvalidShareReceipt);
+        }
+
+        let challenge = self.challenge(recipient_dh_public_key)?;
+
+        match C::G::generator() * self.s == self.r + recipient_dh_public_key.key * challenge {
+            true => Ok(()),
+            false => Err(Error::InvalidShareReceipt),
+        }
+    }
+
+    /// Serialize this [`ShareReceipt`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`ShareReceipt`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the challenge of the Schnorr signature of this [`ShareReceipt`].
+    fn challenge(&self, dh_public_key: &DiffieHellmanPublicKey<C>) -> FrostResult<C, Scalar<C>> {
+        let mut message = Vec::new();
+        self.r
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        dh_public_key
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        message.extend(&self.dealer_index.to_le_bytes());
+        message.extend(&self.recipient_index.to_le_bytes());
+        message.extend(&self.commitment_hash);
+
+        C::hash_to_field((C::context_string() + "receipt").as_bytes(), &message)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for ShareReceipt<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.r)
+    }
+}
@@ -0,0 +1,334 @@
+//! The resharing module for accepting the secret shares of a resharing session
+//! asynchronously, one dealer at a time.
+//!
+//! In a regular resharing session, a signer of the new epoch can only check the secret
+//! shares it received once the commitments of every dealer are known. Here, each dealer
+//! \\( i \\) of the previous epoch instead attaches to every encrypted share a
+//! [`ResharingShare`] proof, made of its commitments and a Schnorr signature with its
+//! current secret share \\( s\_i \\) over these commitments and its Diffie-Hellman public
+//! key. As the constant term of the commitments must be the public verification share
+//! \\( Y\_i = s\_i \cdot G \\) of the dealer, a recipient can accept or reject each
+//! incoming share on its own, against the [`PublicKeyPackage`] of the previous epoch.
+//!
+//! A [`ResharingAccumulator`] collects the accepted shares of a recipient. Once the
+//! signers of the new epoch agreed on a set of at least \\( t \\) accepted dealers, each
+//! of them interpolates its new secret share from the shares of these dealers only,
+//! without waiting for the remaining ones.
+//!
+//! Note that a dealer can still send distinct commitments to distinct recipients: the
+//! [`ResharingShare::commitment_hash`] of the accepted dealers should be cross-checked
+//! by the signers while agreeing on the set of dealers to interpolate from.
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use core::ops::Mul;
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{
+    DhKeyProvider, DiffieHellmanPrivateKey, DiffieHellmanPublicKey, GroupVerifyingKey,
+    IndividualSigningKey, PublicKeyPackage,
+};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{calculate_lagrange_coefficients, vec, BTreeMap, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Element, Error, FrostResult};
+
+use super::key_generation::DKGParticipantList;
+use super::participant::Participant;
+use super::role::{DealerRole, Role};
+use super::secret_share::{
+    decrypt_share, serialize_dh_shared_key, share_session_id, EncryptedSecretShare, SecretShare,
+    VerifiableSecretSharingCommitment,
+};
+
+/// An encrypted secret share of a resharing session, carrying the proof that it is
+/// consistent with the public verification share of its dealer.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ResharingShare<C: CipherSuite> {
+    /// The commitments to the secret polynomial of the dealer.
+    pub(crate) commitment: VerifiableSecretSharingCommitment<C>,
+    /// The Diffie-Hellman public key of the dealer.
+    pub(crate) dealer_dh_public_key: DiffieHellmanPublicKey<C>,
+    /// The encrypted secret share.
+    pub(crate) encrypted_share: EncryptedSecretShare<C>,
+    /// The commitment of the Schnorr signature of the dealer.
+    pub(crate) r: C::G,
+    /// The response of the Schnorr signature of the dealer.
+    pub(crate) s: Scalar<C>,
+}
+
+impl<C: CipherSuite> ResharingShare<C> {
+    /// The index of the dealer who issued this [`ResharingShare`].
+    pub fn dealer_index(&self) -> u32 {
+        self.encrypted_share.sender_index
+    }
+
+    /// The index of the participant this [`ResharingShare`] is intended to.
+    pub fn recipient_index(&self) -> u32 {
+        self.encrypted_share.receiver_index
+    }
+
+    /// The commitments to the secret polynomial of the dealer.
+    pub fn commitment(&self) -> &VerifiableSecretSharingCommitment<C> {
+        &self.commitment
+    }
+
+    /// The hash of the commitments of the dealer, to be compared with the ones received
+    /// by the other recipients.
+    pub fn commitment_hash(&self) -> FrostResult<C, Vec<u8>> {
+        Ok(C::h5(&self.commitment.to_bytes()?)?.as_ref().to_vec())
+    }
+
+    /// Verify that this [`ResharingShare`] was issued by the dealer holding the public
+    /// verification share of its index in the given `public_key_package` of the previous
+    /// epoch, for the new [`ThresholdParameters`].
+    ///
+    /// This does not decrypt the secret share, which is checked against the commitments
+    /// of the dealer by [`ResharingAccumulator::accept`].
+    pub fn verify(
+        &self,
+        public_key_package: &PublicKeyPackage<C>,
+        parameters: &ThresholdParameters<C>,
+    ) -> FrostResult<C, ()> {
+        let verifying_key = public_key_package
+            .verifying_key(self.dealer_index())
+            .ok_or_else(|| Error::Custom("Unknown dealer".to_string()))?;
+
+        if self.commitment.index != self.dealer_index()
+            || self.commitment.points.len() != parameters.t as usize
+            || self.commitment.points[0] != verifying_key.share
+        {
+            return Err(Error::ShareVerificationError);
+        }
+
+        let challenge = Self::challenge(&self.r, &self.commitment, &self.dealer_dh_public_key)?;
+        match C::G::generator() * self.s == self.r + verifying_key.share * challenge {
+            true => Ok(()),
+            false => Err(Error::InvalidProofOfKnowledge),
+        }
+    }
+
+    /// Serialize this [`ResharingShare`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`ResharingShare`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the challenge of the Schnorr signature of a dealer over its
+    /// `commitment` and its `dealer_dh_public_key`.
+    fn challenge(
+        r: &C::G,
+        commitment: &VerifiableSecretSharingCommitment<C>,
+        dealer_dh_public_key: &DiffieHellmanPublicKey<C>,
+    ) -> FrostResult<C, Scalar<C>> {
+        let mut message = Vec::new();
+        r.serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        dealer_dh_public_key
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        message.extend(C::h5(&commitment.to_bytes()?)?.as_ref());
+
+        C::hash_to_field((C::context_string() + "resharing").as_bytes(), &message)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for ResharingShare<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.commitment.validate_points()?;
+        validate_point::<C>(&self.dealer_dh_public_key.key)?;
+        validate_point::<C>(&self.r)
+    }
+}
+
+impl<C: CipherSuite> Participant<C, DealerRole> {
+    /// Reshare this dealer's secret key to a new set of participants, as with
+    /// [`Participant::reshare`], attaching to each encrypted secret share the proof
+    /// that it is consistent with the public verification share of this dealer.
+    ///
+    /// # Returns
+    ///
+    /// A distributed key generation protocol [`Participant`], a
+    /// [`Vec<ResharingShare::<C>>`] to be sent to each participant of the new set
+    /// accordingly, and the list of the valid / misbehaving participants of the new set.
+    pub fn reshare_with_proofs<R: Role<C>>(
+        parameters: &ThresholdParameters<C>,
+        secret_key: IndividualSigningKey<C>,
+        signers: &[Participant<C, R>],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (Self, Vec<ResharingShare<C>>, DKGParticipantList<C>)> {
+        let mut key = secret_key.key;
+        let (dealer, encrypted_shares, participant_lists) =
+            Self::reshare(parameters, secret_key, signers, &mut rng)?;
+
+        let commitment = dealer.commitments().clone();
+        let k = Scalar::<C>::rand(&mut rng);
+        let r = C::G::generator() * k;
+        let challenge = ResharingShare::challenge(&r, &commitment, &dealer.dh_public_key);
+        let s = challenge.map(|challenge| k + key * challenge);
+        key.zeroize();
+
+        let s = s?;
+        let shares = encrypted_shares
+            .into_iter()
+            .map(|encrypted_share| ResharingShare {
+                commitment: commitment.clone(),
+                dealer_dh_public_key: dealer.dh_public_key.clone(),
+                encrypted_share,
+                r,
+                s,
+            })
+            .collect();
+
+        Ok((dealer, shares, participant_lists))
+    }
+}
+
+/// The secret shares of a resharing session accepted by one of its recipients, as
+/// they arrive, from which it derives its secret share of the new epoch.
+#[derive(Debug)]
+pub struct ResharingAccumulator<C: CipherSuite> {
+    /// The public key material of the group in the previous epoch.
+    public_key_package: PublicKeyPackage<C>,
+    /// The parameters of the group in the new epoch.
+    parameters: ThresholdParameters<C>,
+    /// The index of the recipient in the new epoch.
+    index: u32,
+    /// The Diffie-Hellman private key of the recipient.
+    dh_private_key: DiffieHellmanPrivateKey<C>,
+    /// The Diffie-Hellman public key of the recipient.
+    dh_public_key: DiffieHellmanPublicKey<C>,
+    /// The commitments of the accepted dealers, along with their decrypted shares.
+    accepted: BTreeMap<u32, (VerifiableSecretSharingCommitment<C>, SecretShare<C>)>,
+}
+
+impl<C: CipherSuite> ResharingAccumulator<C> {
+    /// Start accepting the secret shares intended to the participant with the given
+    /// `index` and `dh_private_key` in the new epoch with the given `parameters`, reshared
+    /// by the members of the group with the given `public_key_package`.
+    pub fn new(
+        public_key_package: PublicKeyPackage<C>,
+        parameters: ThresholdParameters<C>,
+        index: u32,
+        dh_private_key: DiffieHellmanPrivateKey<C>,
+    ) -> FrostResult<C, Self> {
+        if index == 0 {
+            return Err(Error::IndexIsZero);
+        }
+        let dh_public_key = dh_private_key.dh_public_key()?;
+
+        Ok(Self {
+            public_key_package,
+            parameters,
+            index,
+            dh_private_key,
+            dh_public_key,
+            accepted: BTreeMap::new(),
+        })
+    }
+
+    /// Verify and decrypt the given `share`, and accept it if it is valid.
+    ///
+    /// Accepting twice the same share has no effect.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the share was accepted,
+    /// otherwise an [`Error`] describing why it was rejected. In particular, a dealer
+    /// having issued distinct commitments is reported as an
+    /// [`Error::MisbehavingParticipants`].
+    pub fn accept(&mut self, share: &ResharingShare<C>) -> FrostResult<C, ()> {
+        if share.recipient_index() != self.index {
+            return Err(Error::MisroutedShare(share.dealer_index(), self.index));
+        }
+        share.verify(&self.public_key_package, &self.parameters)?;
+
+        if let Some((commitment, _)) = self.accepted.get(&share.dealer_index()) {
+            return match *commitment == share.commitment {
+                true => Ok(()),
+                false => Err(Error::MisbehavingParticipants(vec![share.dealer_index()])),
+            };
+        }
+
+        let dh_shared_key = self
+            .dh_private_key
+            .dh_shared_key(&share.dealer_dh_public_key)?;
+        let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+        let session_id = share_session_id(&share.dealer_dh_public_key, &self.dh_public_key)?;
+        let secret_share = decrypt_share(&share.encrypted_share, &dh_key_bytes, &session_id);
+        dh_key_bytes.zeroize();
+
+        let secret_share = secret_share?;
+        secret_share.verify(&share.commitment)?;
+        self.accepted.insert(
+            share.dealer_index(),
+            (share.commitment.clone(), secret_share),
+        );
+
+        Ok(())
+    }
+
+    /// The sorted indices of the dealers whose share was accepted.
+    pub fn accepted_dealers(&self) -> Vec<u32> {
+        self.accepted.keys().copied().collect()
+    }
+
+    /// Whether enough shares were accepted to derive the secret share of the new epoch,
+    /// i.e. as many as the threshold of the previous epoch.
+    pub fn is_ready(&self) -> bool {
+        self.accepted.len() >= self.public_key_package.parameters().t as usize
+    }
+
+    /// Derive the secret share of the new epoch from the accepted shares of the given
+    /// `dealers`, on which all the signers of the new epoch must agree.
+    ///
+    /// # Returns
+    ///
+    /// The [`GroupVerifyingKey`], which is checked to be left unchanged, along with the
+    /// [`IndividualSigningKey`] of the new epoch.
+    pub fn finish(
+        &self,
+        dealers: &[u32],
+    ) -> FrostResult<C, (GroupVerifyingKey<C>, IndividualSigningKey<C>)> {
+        let threshold = self.public_key_package.parameters().t;
+        if dealers.len() < threshold as usize {
+            return Err(Error::InvalidNumberOfParticipants(dealers.len(), threshold));
+        }
+
+        let mut key = Scalar::<C>::ZERO;
+        let mut group_key = C::G::zero();
+        for dealer in dealers {
+            let (commitment, share) = self.accepted.get(dealer).ok_or(Error::MissingShares)?;
+            let coeff = calculate_lagrange_coefficients::<C>(*dealer, dealers)?;
+
+            key += coeff * share.polynomial_evaluation.0;
+            group_key += commitment.points[0].mul(coeff);
+        }
+
+        let signing_key = IndividualSigningKey {
+            index: self.index,
+            key,
+        };
+        key.zeroize();
+
+        let group_key = GroupVerifyingKey::new(Element(group_key));
+        if group_key != *self.public_key_package.group_key() {
+            return Err(Error::InvalidGroupKey);
+        }
+
+        Ok((group_key, signing_key))
+    }
+}
@@ -0,0 +1,144 @@
+//! The role module, defining the zero-sized markers typing the role of a
+//! [`Participant`](super::Participant) in an ICE-FROST ceremony.
+//!
+//! A participant's role determines which public material it carries: dealers
+//! publish commitments to their secret polynomial along with a proof of knowledge
+//! of their secret key, while signers and coordinators only publish their
+//! Diffie-Hellman public key. Typing participants by role turns the misuse of
+//! dealer-only material, such as their commitments, into a compile error.
+
+use core::fmt::Debug;
+
+use crate::ciphersuite::CipherSuite;
+
+use super::participant::DealerMaterial;
+
+pub(crate) mod private {
+    use super::*;
+
+    /// The sealing trait of [`Role`], defining how participants with this role
+    /// store the public material of dealers.
+    pub trait Sealed<C: CipherSuite> {
+        /// The dealer material carried by participants with this role.
+        type Material: Clone + Debug + Send + Sync;
+
+        /// Convert the dealer material of a participant, if any, to this role.
+        ///
+        /// Returns [`None`] if the material does not match this role.
+        fn from_material(material: Option<DealerMaterial<C>>) -> Option<Self::Material>;
+
+        /// The dealer material of a participant with this role, if any.
+        fn material(material: &Self::Material) -> Option<&DealerMaterial<C>>;
+
+        /// Convert the dealer material of a participant with this role, if any.
+        fn into_material(material: Self::Material) -> Option<DealerMaterial<C>>;
+    }
+
+    impl<C: CipherSuite> Sealed<C> for DealerRole {
+        type Material = DealerMaterial<C>;
+
+        fn from_material(material: Option<DealerMaterial<C>>) -> Option<Self::Material> {
+            material
+        }
+
+        fn material(material: &Self::Material) -> Option<&DealerMaterial<C>> {
+            Some(material)
+        }
+
+        fn into_material(material: Self::Material) -> Option<DealerMaterial<C>> {
+            Some(material)
+        }
+    }
+
+    impl<C: CipherSuite> Sealed<C> for SignerRole {
+        type Material = ();
+
+        fn from_material(material: Option<DealerMaterial<C>>) -> Option<Self::Material> {
+            match material {
+                Some(_) => None,
+                None => Some(()),
+            }
+        }
+
+        fn material(_material: &Self::Material) -> Option<&DealerMaterial<C>> {
+            None
+        }
+
+        fn into_material(_material: Self::Material) -> Option<DealerMaterial<C>> {
+            None
+        }
+    }
+
+    impl<C: CipherSuite> Sealed<C> for CoordinatorRole {
+        type Material = ();
+
+        fn from_material(material: Option<DealerMaterial<C>>) -> Option<Self::Material> {
+            <SignerRole as Sealed<C>>::from_material(material)
+        }
+
+        fn material(_material: &Self::Material) -> Option<&DealerMaterial<C>> {
+            None
+        }
+
+        fn into_material(_material: Self::Material) -> Option<DealerMaterial<C>> {
+            None
+        }
+    }
+
+    impl<C: CipherSuite> Sealed<C> for AnyRole {
+        type Material = Option<DealerMaterial<C>>;
+
+        fn from_material(material: Option<DealerMaterial<C>>) -> Option<Self::Material> {
+            Some(material)
+        }
+
+        fn material(material: &Self::Material) -> Option<&DealerMaterial<C>> {
+            material.as_ref()
+        }
+
+        fn into_material(material: Self::Material) -> Option<DealerMaterial<C>> {
+            material
+        }
+    }
+}
+
+/// The role of a [`Participant`](super::Participant) in an ICE-FROST ceremony.
+///
+/// This trait is sealed, and implemented by [`DealerRole`], [`SignerRole`],
+/// [`CoordinatorRole`] and [`AnyRole`].
+pub trait Role<C: CipherSuite>:
+    private::Sealed<C> + Clone + Copy + Debug + Default + Send + Sync + 'static
+{
+}
+
+/// The role of a participant dealing secret shares to the other participants,
+/// either when bootstrapping a group key, or when resharing it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DealerRole;
+
+impl<C: CipherSuite> Role<C> for DealerRole {}
+
+/// The role of a participant only receiving secret shares from dealers,
+/// to compute its signing key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SignerRole;
+
+impl<C: CipherSuite> Role<C> for SignerRole {}
+
+/// The role of a participant neither dealing nor receiving secret shares, but
+/// publishing its Diffie-Hellman public key to take part in the ceremony, for
+/// instance as the adjudicator to which complaints are sealed with
+/// [`Complaint::seal`](super::Complaint::seal).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CoordinatorRole;
+
+impl<C: CipherSuite> Role<C> for CoordinatorRole {}
+
+/// The role of a participant whose role is only known at runtime, for instance
+/// when collected from the network alongside participants of other roles.
+///
+/// This is the default role of a [`Participant`](super::Participant).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AnyRole;
+
+impl<C: CipherSuite> Role<C> for AnyRole {}
@@ -0,0 +1,369 @@
+//! The roster module, committing to the participants of an ICE-FROST
+//! Distributed Key Generation session with a Merkle tree.
+//!
+//! A [`ParticipantRoster`] hashes the canonical encoding of each participant,
+//! i.e. its index, Diffie-Hellman public key and polynomial commitment, into
+//! the leaves of a Merkle tree, whose root is bound to the epoch of the session.
+//! Remote verifiers, such as on-chain contracts, can then check that a participant
+//! was part of the session from the [`ParticipantRoster::root_hash`] and a compact
+//! [`MembershipProof`], rather than from the full roster.
+
+use core::marker::PhantomData;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{hash_to_array, vec, ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::participant::{canonical_order, Participant};
+use super::role::Role;
+
+/// Compute the canonical encoding of a `participant`, hashed into its roster leaf.
+fn leaf_hash<C: CipherSuite, R: Role<C>>(
+    participant: &Participant<C, R>,
+) -> FrostResult<C, Vec<u8>> {
+    let mut encoding = participant.index.to_le_bytes().to_vec();
+    participant
+        .dh_public_key
+        .serialize_compressed(&mut encoding)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+    participant
+        .dealer_material()
+        .map(|material| material.commitments.clone())
+        .serialize_compressed(&mut encoding)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+
+    Ok(
+        hash_to_array::<C>((C::context_string() + "roster leaf").as_bytes(), &encoding)?
+            .as_ref()
+            .to_vec(),
+    )
+}
+
+/// Compute the parent of the `left` and `right` nodes of a roster tree.
+fn node_hash<C: CipherSuite>(left: &[u8], right: &[u8]) -> FrostResult<C, Vec<u8>> {
+    let mut children = left.to_vec();
+    children.extend_from_slice(right);
+
+    Ok(
+        hash_to_array::<C>((C::context_string() + "roster node").as_bytes(), &children)?
+            .as_ref()
+            .to_vec(),
+    )
+}
+
+/// Bind the Merkle root of a roster with `leaf_count` leaves to its `epoch`.
+fn bind_root<C: CipherSuite>(
+    epoch: u64,
+    leaf_count: u32,
+    merkle_root: &[u8],
+) -> FrostResult<C, Vec<u8>> {
+    let mut bytes = epoch.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&leaf_count.to_le_bytes());
+    bytes.extend_from_slice(merkle_root);
+
+    Ok(
+        hash_to_array::<C>((C::context_string() + "roster root").as_bytes(), &bytes)?
+            .as_ref()
+            .to_vec(),
+    )
+}
+
+/// A commitment to the participants of the Distributed Key Generation session
+/// of a given epoch, sorted in their canonical order.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ParticipantRoster<C: CipherSuite> {
+    /// The epoch of the session.
+    pub(crate) epoch: u64,
+    /// The participant indices along with their leaf hash, sorted by index.
+    pub(crate) leaves: Vec<(u32, Vec<u8>)>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> ParticipantRoster<C> {
+    /// Construct a new [`ParticipantRoster`] for the session of the given `epoch`,
+    /// from its `participants` in any order.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ParticipantRoster`], or an
+    /// [`Error`] if `participants` is empty or contains duplicate indices.
+    pub fn new<R: Role<C>>(epoch: u64, participants: &[Participant<C, R>]) -> FrostResult<C, Self> {
+        let ordered = canonical_order(participants);
+        if ordered.is_empty() {
+            return Err(Error::Custom("The roster is empty".to_string()));
+        }
+        if ordered.windows(2).any(|w| w[0].index == w[1].index) {
+            return Err(Error::DuplicateIndices);
+        }
+
+        let leaves = ordered
+            .iter()
+            .map(|participant| Ok((participant.index, leaf_hash(participant)?)))
+            .collect::<FrostResult<C, Vec<_>>>()?;
+
+        Ok(Self {
+            epoch,
+            leaves,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The epoch of the session of this [`ParticipantRoster`].
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The sorted indices of the participants of this [`ParticipantRoster`].
+    pub fn indices(&self) -> Vec<u32> {
+        self.leaves.iter().map(|(index, _)| *index).collect()
+    }
+
+    /// The number of participants of this [`ParticipantRoster`].
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Whether this [`ParticipantRoster`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Compute all the levels of the Merkle tree of this [`ParticipantRoster`],
+    /// from the leaves to the root. The last node of a level with an odd number
+    /// of nodes is promoted to the next level as is.
+    fn levels(&self) -> FrostResult<C, Vec<Vec<Vec<u8>>>> {
+        let mut levels = vec![self
+            .leaves
+            .iter()
+            .map(|(_, leaf)| leaf.clone())
+            .collect::<Vec<_>>()];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let pairs = levels[levels.len() - 1].chunks_exact(2);
+            let promoted = pairs.remainder().first().cloned();
+
+            let mut level = pairs
+                .map(|pair| node_hash::<C>(&pair[0], &pair[1]))
+                .collect::<FrostResult<C, Vec<_>>>()?;
+            level.extend(promoted);
+            levels.push(level);
+        }
+
+        Ok(levels)
+    }
+
+    /// Compute the root hash of this [`ParticipantRoster`], committing to its epoch
+    /// and to all of its participants.
+    pub fn root_hash(&self) -> FrostResult<C, Vec<u8>> {
+        let levels = self.levels()?;
+        let merkle_root = &levels[levels.len() - 1][0];
+
+        bind_root::<C>(self.epoch, self.leaves.len() as u32, merkle_root)
+    }
+
+    /// Prove that the participant with the given `index` is part of this [`ParticipantRoster`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is a [`MembershipProof`] against the
+    /// [`ParticipantRoster::root_hash`], or an [`Error::UnknownParticipant`] if the
+    /// participant is unknown.
+    pub fn prove_membership(&self, index: u32) -> FrostResult<C, MembershipProof<C>> {
+        let position = self
+            .leaves
+            .binary_search_by_key(&index, |(index, _)| *index)
+            .map_err(|_| Error::UnknownParticipant(index))?;
+
+        let levels = self.levels()?;
+        let mut siblings = Vec::new();
+        let mut node = position;
+        for level in levels.iter().take(levels.len() - 1) {
+            let sibling = node ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling].clone());
+            }
+            node /= 2;
+        }
+
+        Ok(MembershipProof {
+            epoch: self.epoch,
+            index,
+            position: position as u32,
+            leaf_count: self.leaves.len() as u32,
+            siblings,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Serialize this [`ParticipantRoster`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`ParticipantRoster`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+/// A proof that a participant is part of a [`ParticipantRoster`], i.e. of the
+/// Distributed Key Generation session of a given epoch.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MembershipProof<C: CipherSuite> {
+    /// The epoch of the session.
+    pub(crate) epoch: u64,
+    /// The index of the participant.
+    pub(crate) index: u32,
+    /// The position of the participant's leaf in the roster.
+    pub(crate) position: u32,
+    /// The number of participants in the roster.
+    pub(crate) leaf_count: u32,
+    /// The sibling nodes on the path from the participant's leaf to the root.
+    pub(crate) siblings: Vec<Vec<u8>>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> MembershipProof<C> {
+    /// The epoch of the session of this [`MembershipProof`].
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The index of the participant proven to be part of the roster.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Verify this [`MembershipProof`] for the given `participant`, against the
+    /// `root_hash` of a [`ParticipantRoster`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the `participant` was part
+    /// of the roster, otherwise an [`Error::InvalidMembershipProof`].
+    pub fn verify<R: Role<C>>(
+        &self,
+        root_hash: &[u8],
+        participant: &Participant<C, R>,
+    ) -> FrostResult<C, ()> {
+        if participant.index != self.index || self.position >= self.leaf_count {
+            return Err(Error::InvalidMembershipProof);
+        }
+
+        let mut node = leaf_hash(participant)?;
+        let mut position = self.position;
+        let mut level_length = self.leaf_count;
+        let mut siblings = self.siblings.iter();
+        while level_length > 1 {
+            // The last node of a level with an odd number of nodes has no sibling.
+            if position ^ 1 < level_length {
+                let sibling = siblings.next().ok_or(Error::InvalidMembershipProof)?;
+                node = match position % 2 {
+                    0 => node_hash::<C>(&node, sibling)?,
+                    _ => node_hash::<C>(sibling, &node)?,
+                };
+            }
+            position /= 2;
+            level_length = level_length / 2 + level_length % 2;
+        }
+
+        if siblings.next().is_some()
+            || bind_root::<C>(self.epoch, self.leaf_count, &node)? != root_hash
+        {
+            return Err(Error::InvalidMembershipProof);
+        }
+
+        Ok(())
+    }
+
+    /// Serialize this [`MembershipProof`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`MembershipProof`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dkg::DealerRole;
+    use crate::parameters::ThresholdParameters;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn roster_membership_proofs() {
+        let params = ThresholdParameters::new(5, 3).unwrap();
+        let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = [4, 2, 5, 1, 3]
+            .iter()
+            .map(|i| Participant::new_dealer(&params, *i, OsRng).unwrap().0)
+            .collect();
+
+        let roster = ParticipantRoster::new(7, &participants).unwrap();
+        assert_eq!(roster.indices(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            roster,
+            ParticipantRoster::from_bytes(&roster.to_bytes().unwrap()).unwrap()
+        );
+
+        let root_hash = roster.root_hash().unwrap();
+        let reversed: Vec<_> = participants.iter().rev().cloned().collect();
+        assert_eq!(
+            root_hash,
+            ParticipantRoster::new(7, &reversed)
+                .unwrap()
+                .root_hash()
+                .unwrap()
+        );
+        assert_ne!(
+            root_hash,
+            ParticipantRoster::new(8, &participants)
+                .unwrap()
+                .root_hash()
+                .unwrap()
+        );
+
+        for participant in participants.iter() {
+            let proof = roster.prove_membership(participant.index).unwrap();
+            assert_eq!(proof.epoch(), 7);
+            assert!(proof.verify(&root_hash, participant).is_ok());
+
+            let proof = MembershipProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+            assert!(proof.verify(&root_hash, participant).is_ok());
+        }
+
+        // A proof does not hold for another participant, nor for another roster.
+        let proof = roster.prove_membership(5).unwrap();
+        assert_eq!(
+            proof.verify(&root_hash, &participants[0]),
+            Err(Error::InvalidMembershipProof)
+        );
+        let other = Participant::new_dealer(&params, 5, OsRng).unwrap().0;
+        assert_eq!(
+            proof.verify(&root_hash, &other),
+            Err(Error::InvalidMembershipProof)
+        );
+        assert!(roster.prove_membership(6).is_err());
+
+        assert!(
+            ParticipantRoster::new(7, &[participants[0].clone(), participants[0].clone()]).is_err()
+        );
+    }
+}
@@ -2,60 +2,113 @@
 //! and their public commitments, along with their encrypted versions
 //! post Diffie-Hellman key exchange.
 //!
-//! This module currently only supports AES128-CTR with HKDF instantiated
-//! from SHA-256.
+//! The symmetric encryption scheme is parameterized by the [`ShareCipher`]
+//! of the [`CipherSuite`] in use, with its key derived through HKDF
+//! instantiated from SHA-256.
+//!
+//! The encryption nonces are not sampled at random, but deterministically derived
+//! from the session identifier, the random salt of the DKG ceremony, the indices of
+//! the dealer and recipient of the share, and the DKG round, as specified by
+//! [`EncryptedSecretShare::derive_nonce`]. This prevents nonce collisions between
+//! dealers sharing an identical RNG state, for instance when running in containers
+//! cloned from the same snapshot, while the salt, sent along each share, prevents
+//! them between ceremonies reusing the same Diffie-Hellman keys.
 
 use core::marker::PhantomData;
 
-use crate::utils::{Scalar, ToString, Vec};
-use crate::{Error, FrostResult};
+use crate::utils::{vec, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_points, ValidatePoints};
+use crate::{CipherErrorKind, Element, Error, FrostResult};
 
 use crate::ciphersuite::CipherSuite;
+use crate::keys::{DhKeyProvider, DiffieHellmanPrivateKey, DiffieHellmanPublicKey};
+use crate::share_cipher::{deserialize_nonce, ShareCipher};
 
-use ark_ec::{CurveGroup, Group};
+use ark_ec::Group;
 use ark_ff::{Field, Zero};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-
-use rand::{CryptoRng, RngCore};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+};
 
-use aes::cipher::{generic_array::GenericArray, FromBlockCipher, NewBlockCipher, StreamCipher};
-use aes::{Aes128, Aes128Ctr};
 use hkdf::Hkdf;
+use rand::{CryptoRng, RngCore};
 use sha2::Sha256;
 
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::Zeroize;
 
+use super::complaint::Complaint;
+
 /// A struct for holding a shard of the shared secret, in order to ensure that
 /// the shard is overwritten with zeroes when it falls out of scope.
+///
+/// Dealers can wipe their coefficients earlier, as soon as their secret shares are
+/// encrypted, with [`DistributedKeyGeneration::consume_coefficients`].
+///
+/// [`DistributedKeyGeneration::consume_coefficients`]: crate::dkg::DistributedKeyGeneration::consume_coefficients
 #[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
 pub struct Coefficients<C: CipherSuite>(pub(crate) Vec<Scalar<C>>);
 
 impl<C: CipherSuite> Coefficients<C> {
+    /// Whether these coefficients were wiped, and cannot be used anymore.
+    pub fn is_consumed(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Serialize this `coefficients` to a vector of bytes.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the serialized coefficients, otherwise an
+    /// [`Error::ConsumedCoefficients`] if they were wiped.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        if self.is_consumed() {
+            return Err(Error::ConsumedCoefficients);
+        }
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a `coefficients` from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
     }
 }
 
 impl<C: CipherSuite> Drop for Coefficients<C> {
     fn drop(&mut self) {
-        self.0.iter_mut().zeroize();
+        self.zeroize();
     }
 }
 
 /// A secret share calculated by evaluating a polynomial with secret
 /// coefficients for some indeterminant.
-#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+///
+/// A [`SecretShare`] cannot be cloned, so that its secret value is held in a single
+/// memory location, overwritten with zeroes when the share falls out of scope.
+/// The secret value can only be accessed through [`SecretShare::expose_secret`],
+/// and is redacted from the [`Debug`](core::fmt::Debug) output of the share.
+///
+/// ```compile_fail
+/// # use ice_frost::dkg::SecretShare;
+/// # use ice_frost::testing::Secp256k1Sha256;
+/// fn copy(share: &SecretShare<Secp256k1Sha256>) -> SecretShare<Secp256k1Sha256> {
+///     share.clone()
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use ice_frost::dkg::SecretShare;
+/// # use ice_frost::testing::Secp256k1Sha256;
+/// fn copy(share: &SecretShare<Secp256k1Sha256>) -> SecretShare<Secp256k1Sha256> {
+///     share.duplicate()
+/// }
+/// ```
+#[derive(CanonicalSerialize, CanonicalDeserialize, Zeroize)]
 pub struct SecretShare<C: CipherSuite> {
     /// The index of the share maker.
     pub sender_index: u32,
@@ -63,7 +116,17 @@ pub struct SecretShare<C: CipherSuite> {
     pub receiver_index: u32,
     /// The final evaluation of the polynomial for the participant-respective
     /// indeterminant.
-    pub(crate) polynomial_evaluation: Scalar<C>,
+    pub(crate) polynomial_evaluation: crate::Scalar<C>,
+}
+
+impl<C: CipherSuite> core::fmt::Debug for SecretShare<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecretShare")
+            .field("sender_index", &self.sender_index)
+            .field("receiver_index", &self.receiver_index)
+            .field("polynomial_evaluation", &format_args!("<redacted>"))
+            .finish()
+    }
 }
 
 impl<C: CipherSuite> Drop for SecretShare<C> {
@@ -72,28 +135,70 @@ impl<C: CipherSuite> Drop for SecretShare<C> {
     }
 }
 
+impl<C: CipherSuite> ConstantTimeEq for SecretShare<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.sender_index.ct_eq(&other.sender_index)
+            & self.receiver_index.ct_eq(&other.receiver_index)
+            & self
+                .polynomial_evaluation
+                .ct_eq(&other.polynomial_evaluation)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for SecretShare<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for SecretShare<C> {}
+
 impl<C: CipherSuite> SecretShare<C> {
     /// Serialize this [`SecretShare`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`SecretShare`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+
+    /// Expose the secret polynomial evaluation held by this [`SecretShare`].
+    ///
+    /// The value is borrowed rather than copied, so that it remains held in the single
+    /// memory location of this share. Care must be taken not to copy it in long-lived
+    /// memory locations, and to zeroize any copy once no longer needed.
+    pub fn expose_secret(&self) -> &crate::Scalar<C> {
+        &self.polynomial_evaluation
+    }
+
+    /// Explicitly duplicate this [`SecretShare`], including its secret value.
+    pub(crate) fn duplicate(&self) -> Self {
+        SecretShare {
+            sender_index: self.sender_index,
+            receiver_index: self.receiver_index,
+            polynomial_evaluation: self.polynomial_evaluation,
+        }
     }
 
     /// Evaluate the polynomial, `f(x)` for the secret coefficients at the value of `x` .
+    ///
+    /// Returns an [`Error::ConsumedCoefficients`] if the `coefficients` were wiped.
     pub(crate) fn evaluate_polynomial(
         sender_index: &u32,
         receiver_index: &u32,
         coefficients: &Coefficients<C>,
-    ) -> SecretShare<C> {
+    ) -> FrostResult<C, SecretShare<C>> {
+        if coefficients.is_consumed() {
+            return Err(Error::ConsumedCoefficients);
+        }
         let term: Scalar<C> = (*receiver_index).into();
         let mut sum = Scalar::<C>::ZERO;
 
@@ -106,11 +211,11 @@ impl<C: CipherSuite> SecretShare<C> {
                 sum *= term;
             }
         }
-        SecretShare {
+        Ok(SecretShare {
             sender_index: *sender_index,
             receiver_index: *receiver_index,
-            polynomial_evaluation: sum,
-        }
+            polynomial_evaluation: crate::Scalar(sum),
+        })
     }
 
     /// Verify that this secret share was correctly computed w.r.t. some secret
@@ -119,7 +224,7 @@ impl<C: CipherSuite> SecretShare<C> {
         &self,
         commitment: &VerifiableSecretSharingCommitment<C>,
     ) -> FrostResult<C, ()> {
-        let lhs = C::G::generator() * self.polynomial_evaluation;
+        let lhs = C::G::generator() * self.polynomial_evaluation.0;
         let term: Scalar<C> = self.receiver_index.into();
         let mut rhs: C::G = <C as CipherSuite>::G::zero();
 
@@ -131,7 +236,8 @@ impl<C: CipherSuite> SecretShare<C> {
             }
         }
 
-        match lhs.into_affine() == rhs.into_affine() {
+        // Projective points are compared without normalizing them to affine coordinates.
+        match lhs == rhs {
             true => Ok(()),
             false => Err(Error::ShareVerificationError),
         }
@@ -139,16 +245,18 @@ impl<C: CipherSuite> SecretShare<C> {
 }
 
 /// A secret share encrypted with a participant's public key
-#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, Zeroize)]
 pub struct EncryptedSecretShare<C: CipherSuite> {
     /// The index of the share maker.
     pub sender_index: u32,
     /// The participant index that this secret share was calculated for.
     pub receiver_index: u32,
-    /// The nonce to be used for decryption with AES-CTR mode.
-    pub nonce: [u8; 16],
+    /// The nonce to be used for decryption with this [`CipherSuite`]'s [`ShareCipher`].
+    pub nonce: <C::ShareCipher as ShareCipher>::Nonce,
     /// The encrypted polynomial evaluation.
     pub(crate) encrypted_polynomial_evaluation: Vec<u8>,
+    /// The random salt of the DKG ceremony, from which the nonce is derived.
+    pub salt: Vec<u8>,
     #[zeroize(skip)]
     _phantom: PhantomData<C>,
 }
@@ -159,39 +267,176 @@ impl<C: CipherSuite> Drop for EncryptedSecretShare<C> {
     }
 }
 
+impl<C: CipherSuite> Valid for EncryptedSecretShare<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<C: CipherSuite> CanonicalDeserialize for EncryptedSecretShare<C> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            sender_index: u32::deserialize_with_mode(&mut reader, compress, validate)?,
+            receiver_index: u32::deserialize_with_mode(&mut reader, compress, validate)?,
+            nonce: deserialize_nonce::<C::ShareCipher, _>(&mut reader, compress, validate)?,
+            encrypted_polynomial_evaluation: Vec::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            salt: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
 impl<C: CipherSuite> EncryptedSecretShare<C> {
     /// Constructs a new [`EncryptedSecretShare`] from the provided inputs.
     pub fn new(
         sender_index: u32,
         receiver_index: u32,
-        nonce: [u8; 16],
+        nonce: <C::ShareCipher as ShareCipher>::Nonce,
         encrypted_polynomial_evaluation: Vec<u8>,
+        salt: Vec<u8>,
     ) -> Self {
         Self {
             sender_index,
             receiver_index,
             nonce,
             encrypted_polynomial_evaluation,
+            salt,
             _phantom: PhantomData,
         }
     }
 
+    /// Derive the nonce used to encrypt the share sent by the participant of index
+    /// `sender_index` to the participant of index `receiver_index` during the DKG
+    /// `round` of the session identified by `session_id`, salted with `salt`.
+    ///
+    /// The nonce is expanded with HKDF instantiated from SHA-256, with `salt` as salt,
+    /// `session_id` as input keying material, and this [`CipherSuite`]'s context string
+    /// followed by the little-endian encodings of `sender_index`, `receiver_index` and
+    /// `round` as info. An empty `salt` is equivalent to HKDF without salt.
+    pub fn derive_nonce(
+        session_id: &[u8],
+        salt: &[u8],
+        sender_index: u32,
+        receiver_index: u32,
+        round: u32,
+    ) -> FrostResult<C, <C::ShareCipher as ShareCipher>::Nonce> {
+        let mut info = C::context_string().into_bytes();
+        info.extend_from_slice(&sender_index.to_le_bytes());
+        info.extend_from_slice(&receiver_index.to_le_bytes());
+        info.extend_from_slice(&round.to_le_bytes());
+
+        let mut nonce = <C::ShareCipher as ShareCipher>::Nonce::default();
+        Hkdf::<Sha256>::new(Some(salt), session_id)
+            .expand(&info, nonce.as_mut())
+            .map_err(|_| Error::Custom("KDF expansion failed unexpectedly".to_string()))?;
+
+        Ok(nonce)
+    }
+
+    /// Decrypt this [`EncryptedSecretShare`], sent by the dealer with the given
+    /// `dealer_dh_public_key`, with the Diffie-Hellman shared key computed by the
+    /// recipient's `dh_key_provider`, which may be backed by an external device.
+    pub fn decrypt_with(
+        &self,
+        dh_key_provider: &impl DhKeyProvider<C>,
+        dealer_dh_public_key: &DiffieHellmanPublicKey<C>,
+    ) -> FrostResult<C, SecretShare<C>> {
+        let dh_shared_key = dh_key_provider.dh_shared_key(dealer_dh_public_key)?;
+        let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+
+        let session_id = share_session_id(dealer_dh_public_key, &dh_key_provider.dh_public_key()?)?;
+        let share = decrypt_share(self, &dh_key_bytes, &session_id);
+        dh_key_bytes.zeroize();
+
+        share
+    }
+
+    /// Decrypt this [`EncryptedSecretShare`], sent by the dealer with the given
+    /// `dealer_dh_public_key`, with the recipient's `dh_private_key`, and verify it
+    /// against the `dealer_commitment`.
+    ///
+    /// This lets orchestration flows validate each share as soon as it arrives,
+    /// instead of at the end of the second round of the distributed key generation.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is a [`ShareVerification`], holding either
+    /// the decrypted share if it is valid, or a [`Complaint`] against its dealer to be
+    /// broadcast otherwise. An [`Error::MisroutedShare`] is returned if the
+    /// `dealer_commitment` is not the one of the sender of this share.
+    pub fn decrypt_and_verify(
+        &self,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        dealer_dh_public_key: &DiffieHellmanPublicKey<C>,
+        dealer_commitment: &VerifiableSecretSharingCommitment<C>,
+        rng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, ShareVerification<C>> {
+        if dealer_commitment.index != self.sender_index {
+            return Err(Error::MisroutedShare(
+                self.sender_index,
+                self.receiver_index,
+            ));
+        }
+
+        let dh_public_key = dh_private_key.dh_public_key()?;
+        let dh_shared_key = dh_private_key.dh_shared_key(dealer_dh_public_key)?;
+        let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+
+        let session_id = share_session_id(dealer_dh_public_key, &dh_public_key)?;
+        let share = decrypt_share(self, &dh_key_bytes, &session_id);
+        dh_key_bytes.zeroize();
+
+        match share {
+            Ok(share) if share.verify(dealer_commitment).is_ok() => {
+                Ok(ShareVerification::Valid(share))
+            }
+            _ => Ok(ShareVerification::Invalid(Complaint::new(
+                self.receiver_index,
+                self.sender_index,
+                &dealer_dh_public_key.key,
+                &dh_private_key.0,
+                &dh_public_key.key,
+                &dh_shared_key,
+                rng,
+            )?)),
+        }
+    }
+
     /// Serialize this [`EncryptedSecretShare`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`EncryptedSecretShare`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
     }
 }
 
+/// The outcome of the verification of an [`EncryptedSecretShare`] by its recipient,
+/// with [`EncryptedSecretShare::decrypt_and_verify`].
+#[derive(Debug)]
+pub enum ShareVerification<C: CipherSuite> {
+    /// The share was decrypted, and matches the commitment of its dealer.
+    Valid(SecretShare<C>),
+    /// The share could not be decrypted, or does not match the commitment of its
+    /// dealer, along with the [`Complaint`] to be broadcast against the dealer.
+    Invalid(Complaint<C>),
+}
+
 /// A commitment to a participant's secret polynomial coefficients for Feldman's
 /// verifiable secret sharing scheme.
 #[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
@@ -208,27 +453,27 @@ impl<C: CipherSuite> VerifiableSecretSharingCommitment<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`VerifiableSecretSharingCommitment`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
     }
 
     /// Retrieve \\( \alpha_{i0} * B \\), where \\( B \\) is the prime-order basepoint.
-    pub fn public_key(&self) -> Option<&C::G> {
+    pub fn public_key(&self) -> Option<Element<C>> {
         if !self.points.is_empty() {
-            return Some(&self.points[0]);
+            return Some(Element(self.points[0]));
         }
 
         None
     }
 
     /// Evaluate g^P(i) without knowing the secret coefficients of the polynomial
-    pub fn evaluate_hiding(&self, term: &Scalar<C>) -> C::G {
+    pub fn evaluate_hiding(&self, term: &crate::Scalar<C>) -> Element<C> {
         let mut sum = <C as CipherSuite>::G::zero();
 
         // Evaluate using Horner's method.
@@ -237,85 +482,217 @@ impl<C: CipherSuite> VerifiableSecretSharingCommitment<C> {
             sum += coefficient;
 
             if k != (self.points.len() - 1) {
-                sum *= term;
+                sum *= term.0;
             }
         }
 
-        sum
+        Element(sum)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for VerifiableSecretSharingCommitment<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>(&self.points)
     }
 }
 
+/// Derive a symmetric key for this [`CipherSuite`]'s [`ShareCipher`] from a
+/// Diffie-Hellman shared secret, under the [`CipherSuite::share_encryption_domain_tag`].
+pub(crate) fn derive_share_cipher_key<C: CipherSuite>(dh_key: &[u8]) -> FrostResult<C, Vec<u8>> {
+    let hkdf = Hkdf::<Sha256>::new(None, dh_key);
+    let mut final_key = vec![0u8; <C::ShareCipher as ShareCipher>::KEY_LENGTH];
+    hkdf.expand(C::share_encryption_domain_tag().as_bytes(), &mut final_key)
+        .map_err(|_| Error::Custom("KDF expansion failed unexpectedly".to_string()))?;
+
+    Ok(final_key)
+}
+
+/// Serialize a Diffie-Hellman shared key into a buffer allocated at its final size,
+/// so that no partial copy of the key is left in memory released while growing it.
+///
+/// The returned bytes must be zeroized once no longer needed.
+pub(crate) fn serialize_dh_shared_key<C: CipherSuite>(
+    dh_shared_key: &C::G,
+) -> FrostResult<C, Vec<u8>> {
+    let mut dh_key_bytes = Vec::with_capacity(dh_shared_key.compressed_size());
+    dh_shared_key
+        .serialize_compressed(&mut dh_key_bytes)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+
+    Ok(dh_key_bytes)
+}
+
+/// The DKG round during which the secret shares are distributed.
+pub(crate) const SHARE_DISTRIBUTION_ROUND: u32 = 1;
+
+/// The length of the random salts separating the share encryptions of distinct
+/// DKG ceremonies.
+pub(crate) const SHARE_SALT_LENGTH: usize = 32;
+
+/// Compute the identifier of the session in which a dealer and a recipient exchange
+/// a secret share, binding the Diffie-Hellman public keys of both parties.
+///
+/// As these keys may be reused across DKG ceremonies, for instance when assigned
+/// through an [`IndexAssignment`](crate::dkg::IndexAssignment), this identifier is not
+/// unique to a ceremony: the encryption nonces are made unique by the random salt
+/// drawn by each dealer for each ceremony, and sent along its shares.
+///
+/// The [`ThresholdParameters`](crate::parameters::ThresholdParameters) are not bound,
+/// as dealers and signers of a resharing session may be configured with distinct ones.
+pub(crate) fn share_session_id<C: CipherSuite>(
+    dealer_dh_public_key: &DiffieHellmanPublicKey<C>,
+    recipient_dh_public_key: &DiffieHellmanPublicKey<C>,
+) -> FrostResult<C, Vec<u8>> {
+    let mut session_id = Vec::new();
+    dealer_dh_public_key
+        .serialize_compressed(&mut session_id)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+    recipient_dh_public_key
+        .serialize_compressed(&mut session_id)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+
+    Ok(session_id)
+}
+
 pub(crate) fn encrypt_share<C: CipherSuite>(
     share: &SecretShare<C>,
-    aes_key: &[u8],
-    mut rng: impl RngCore + CryptoRng,
+    dh_key: &[u8],
+    session_id: &[u8],
+    salt: &[u8],
 ) -> FrostResult<C, EncryptedSecretShare<C>> {
-    let hkdf = Hkdf::<Sha256>::new(None, aes_key);
-    let mut final_aes_key = [0u8; 16];
-    hkdf.expand(&[], &mut final_aes_key)
-        .map_err(|_| Error::Custom("KDF expansion failed unexpectedly".to_string()))?;
+    encrypt_share_for_round(share, dh_key, session_id, salt, SHARE_DISTRIBUTION_ROUND)
+}
 
-    let mut nonce_array = [0u8; 16];
-    rng.fill_bytes(&mut nonce_array);
+/// Encrypt a secret `share` with a nonce derived from the given `salt`, for the
+/// given DKG `round`.
+pub(crate) fn encrypt_share_for_round<C: CipherSuite>(
+    share: &SecretShare<C>,
+    dh_key: &[u8],
+    session_id: &[u8],
+    salt: &[u8],
+    round: u32,
+) -> FrostResult<C, EncryptedSecretShare<C>> {
+    let nonce = EncryptedSecretShare::<C>::derive_nonce(
+        session_id,
+        salt,
+        share.sender_index,
+        share.receiver_index,
+        round,
+    )?;
 
-    let final_aes_key = GenericArray::from_slice(&final_aes_key);
-    let nonce = GenericArray::from_slice(&nonce_array);
-    let cipher = Aes128::new(final_aes_key);
-    let mut cipher = Aes128Ctr::from_block_cipher(cipher, nonce);
+    let mut final_key = derive_share_cipher_key::<C>(dh_key)?;
 
     let mut share_bytes = Vec::new();
     share
         .polynomial_evaluation
         .serialize_compressed(&mut share_bytes)
-        .map_err(|_| Error::CompressionError)?;
-    cipher.apply_keystream(&mut share_bytes);
+        .map_err(|e| Error::CompressionError(e.into()))?;
+    let encrypted_share = C::ShareCipher::encrypt::<C>(&final_key, &nonce, &share_bytes);
+
+    final_key.zeroize();
+    share_bytes.zeroize();
 
     Ok(EncryptedSecretShare::<C> {
         sender_index: share.sender_index,
         receiver_index: share.receiver_index,
-        nonce: nonce_array,
-        encrypted_polynomial_evaluation: share_bytes,
+        nonce,
+        encrypted_polynomial_evaluation: encrypted_share?,
+        salt: salt.to_vec(),
         _phantom: PhantomData,
     })
 }
 
 pub(crate) fn decrypt_share<C: CipherSuite>(
     encrypted_share: &EncryptedSecretShare<C>,
-    aes_key: &[u8],
+    dh_key: &[u8],
+    session_id: &[u8],
 ) -> FrostResult<C, SecretShare<C>> {
-    let hkdf = Hkdf::<Sha256>::new(None, aes_key);
-    let mut final_aes_key = [0u8; 16];
-    hkdf.expand(&[], &mut final_aes_key)
-        .expect("KDF expansion failed unexpectedly");
+    decrypt_share_for_round(
+        encrypted_share,
+        dh_key,
+        session_id,
+        SHARE_DISTRIBUTION_ROUND,
+    )
+}
 
-    let final_aes_key = GenericArray::from_slice(&final_aes_key);
+/// Decrypt an `encrypted_share` whose nonce was derived for the given DKG `round`.
+pub(crate) fn decrypt_share_for_round<C: CipherSuite>(
+    encrypted_share: &EncryptedSecretShare<C>,
+    dh_key: &[u8],
+    session_id: &[u8],
+    round: u32,
+) -> FrostResult<C, SecretShare<C>> {
+    let expected_nonce = EncryptedSecretShare::<C>::derive_nonce(
+        session_id,
+        &encrypted_share.salt,
+        encrypted_share.sender_index,
+        encrypted_share.receiver_index,
+        round,
+    )?;
+    if encrypted_share.nonce != expected_nonce {
+        return Err(Error::InvalidNonce);
+    }
 
-    let nonce = GenericArray::from_slice(&encrypted_share.nonce);
-    let cipher = Aes128::new(final_aes_key);
-    let mut cipher = Aes128Ctr::from_block_cipher(cipher, nonce);
+    let mut final_key = derive_share_cipher_key::<C>(dh_key)?;
 
-    let mut bytes = encrypted_share.encrypted_polynomial_evaluation.clone();
-    cipher.apply_keystream(&mut bytes);
+    let bytes = C::ShareCipher::decrypt::<C>(
+        &final_key,
+        &encrypted_share.nonce,
+        &encrypted_share.encrypted_polynomial_evaluation,
+    );
+    final_key.zeroize();
+    let mut bytes = bytes?;
 
-    let evaluation =
-        Scalar::<C>::deserialize_compressed(&bytes[..]).map_err(|_| Error::DecryptionError)?;
+    let evaluation = Scalar::<C>::deserialize_compressed(&bytes[..])
+        .map_err(|e| Error::DecryptionError(CipherErrorKind::MalformedPlaintext(e.into())));
+    bytes.zeroize();
 
     Ok(SecretShare {
         sender_index: encrypted_share.sender_index,
         receiver_index: encrypted_share.receiver_index,
-        polynomial_evaluation: evaluation,
+        polynomial_evaluation: crate::Scalar(evaluation?),
     })
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::keys::DiffieHellmanPrivateKey;
     use crate::testing::Secp256k1Sha256;
 
     use ark_ff::UniformRand;
-    use ark_secp256k1::Fr;
+    use ark_secp256k1::{Fr, Projective};
     use rand::{rngs::OsRng, RngCore};
 
+    #[test]
+    fn secret_share_debug_redacts_secret() {
+        let evaluation = Fr::rand(&mut OsRng);
+        let share = SecretShare::<Secp256k1Sha256> {
+            sender_index: 1,
+            receiver_index: 2,
+            polynomial_evaluation: crate::Scalar(evaluation),
+        };
+
+        let debug = format!("{:?}", share);
+        assert!(debug.contains("sender_index: 1"));
+        assert!(debug.contains("receiver_index: 2"));
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains(&format!("{:?}", evaluation)));
+        assert!(!debug.contains(&evaluation.to_string()));
+
+        // Explicit duplicates hold the same secret, still only reachable by reference.
+        let duplicate = share.duplicate();
+        assert!(duplicate == share);
+        assert!(core::ptr::eq(
+            share.expose_secret(),
+            &share.polynomial_evaluation
+        ));
+        assert!(!core::ptr::eq(
+            share.expose_secret(),
+            duplicate.expose_secret()
+        ));
+    }
+
     #[test]
     fn test_serialization() {
         let mut rng = OsRng;
@@ -324,7 +701,7 @@ mod test {
             let secret_share = SecretShare::<Secp256k1Sha256> {
                 sender_index: rng.next_u32(),
                 receiver_index: rng.next_u32(),
-                polynomial_evaluation: Fr::rand(&mut rng),
+                polynomial_evaluation: crate::Scalar(Fr::rand(&mut rng)),
             };
             let mut bytes = Vec::new();
             secret_share.serialize_compressed(&mut bytes).unwrap();
@@ -335,15 +712,18 @@ mod test {
         }
 
         for _ in 0..100 {
-            let mut nonce = [0u8; 16];
-            let mut encrypted_polynomial_evaluation = vec![0u8; 16];
+            let mut nonce = [0u8; 12];
+            let mut encrypted_polynomial_evaluation = vec![0u8; 48];
+            let mut salt = [0u8; 32];
             rng.fill_bytes(&mut nonce);
+            rng.fill_bytes(&mut salt);
             rng.fill_bytes(&mut encrypted_polynomial_evaluation);
             let encrypted_secret_share = EncryptedSecretShare::<Secp256k1Sha256>::new(
                 rng.next_u32(),
                 rng.next_u32(),
                 nonce,
                 encrypted_polynomial_evaluation,
+                salt.to_vec(),
             );
             let mut bytes = Vec::new();
             encrypted_secret_share
@@ -355,4 +735,128 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_decrypt_with_dh_key_provider() {
+        let mut rng = OsRng;
+
+        let dealer_dh_private_key = DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut rng));
+        let recipient_dh_private_key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut rng));
+        let dealer_dh_public_key = dealer_dh_private_key.dh_public_key().unwrap();
+        let recipient_dh_public_key = recipient_dh_private_key.dh_public_key().unwrap();
+
+        let share = SecretShare::<Secp256k1Sha256> {
+            sender_index: 1,
+            receiver_index: 2,
+            polynomial_evaluation: crate::Scalar(Fr::rand(&mut rng)),
+        };
+
+        let mut dh_key_bytes = Vec::new();
+        dealer_dh_private_key
+            .dh_shared_key(&recipient_dh_public_key)
+            .unwrap()
+            .serialize_compressed(&mut dh_key_bytes)
+            .unwrap();
+        let session_id = share_session_id(&dealer_dh_public_key, &recipient_dh_public_key).unwrap();
+        let encrypted_share = encrypt_share(&share, &dh_key_bytes, &session_id, &[]).unwrap();
+
+        assert_eq!(
+            encrypted_share
+                .decrypt_with(&recipient_dh_private_key, &dealer_dh_public_key)
+                .unwrap(),
+            share
+        );
+        assert!(encrypted_share
+            .decrypt_with(&dealer_dh_private_key, &recipient_dh_public_key)
+            .is_err());
+    }
+
+    #[test]
+    fn test_decrypt_and_verify() {
+        let mut rng = OsRng;
+
+        let dealer_dh_private_key = DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut rng));
+        let recipient_dh_private_key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut rng));
+        let dealer_dh_public_key = dealer_dh_private_key.dh_public_key().unwrap();
+        let recipient_dh_public_key = recipient_dh_private_key.dh_public_key().unwrap();
+
+        let coefficients =
+            Coefficients::<Secp256k1Sha256>((0..2).map(|_| Fr::rand(&mut rng)).collect());
+        let mut commitment = VerifiableSecretSharingCommitment {
+            index: 1,
+            points: coefficients
+                .0
+                .iter()
+                .map(|coefficient| Projective::generator() * coefficient)
+                .collect(),
+        };
+
+        let mut dh_key_bytes = Vec::new();
+        dealer_dh_private_key
+            .dh_shared_key(&recipient_dh_public_key)
+            .unwrap()
+            .serialize_compressed(&mut dh_key_bytes)
+            .unwrap();
+        let session_id = share_session_id(&dealer_dh_public_key, &recipient_dh_public_key).unwrap();
+
+        let share = SecretShare::evaluate_polynomial(&1, &2, &coefficients).unwrap();
+        let encrypted_share = encrypt_share(&share, &dh_key_bytes, &session_id, &[]).unwrap();
+        match encrypted_share
+            .decrypt_and_verify(
+                &recipient_dh_private_key,
+                &dealer_dh_public_key,
+                &commitment,
+                rng,
+            )
+            .unwrap()
+        {
+            ShareVerification::Valid(decrypted_share) => assert_eq!(decrypted_share, share),
+            ShareVerification::Invalid(_) => panic!("The share should be valid"),
+        }
+
+        // A share inconsistent with the commitment of its dealer yields a valid complaint.
+        let wrong_share = SecretShare {
+            sender_index: 1,
+            receiver_index: 2,
+            polynomial_evaluation: crate::Scalar(share.polynomial_evaluation.0 + Fr::ONE),
+        };
+        let wrong_encrypted_share =
+            encrypt_share(&wrong_share, &dh_key_bytes, &session_id, &[]).unwrap();
+        match wrong_encrypted_share
+            .decrypt_and_verify(
+                &recipient_dh_private_key,
+                &dealer_dh_public_key,
+                &commitment,
+                rng,
+            )
+            .unwrap()
+        {
+            ShareVerification::Valid(_) => panic!("The share should be invalid"),
+            ShareVerification::Invalid(complaint) => {
+                assert_eq!((complaint.maker_index, complaint.accused_index), (2, 1));
+                assert!(complaint
+                    .verify(
+                        &recipient_dh_public_key.element(),
+                        &dealer_dh_public_key.element()
+                    )
+                    .is_ok());
+            }
+        }
+
+        // The commitment of another dealer is rejected.
+        commitment.index = 3;
+        assert_eq!(
+            encrypted_share
+                .decrypt_and_verify(
+                    &recipient_dh_private_key,
+                    &dealer_dh_public_key,
+                    &commitment,
+                    rng,
+                )
+                .unwrap_err(),
+            Error::MisroutedShare(1, 2)
+        );
+    }
 }
@@ -0,0 +1,607 @@
+//! The epoch module for managing the rotation of an ICE-FROST group's public key
+//! material across resharing sessions.
+//!
+//! Resharing the secret shares of a group to a new set of participants yields
+//! a new [`PublicKeyPackage`], while the participants of the previous set may
+//! still be completing signing sessions with their former shares. To avoid any
+//! signing outage, an [`EpochedKeyManager`] keeps track of both the current and
+//! the next packages, and accepts signing under either of them during a configurable
+//! overlap window following the cutover to the next epoch.
+//!
+//! Time is measured with a monotonic logical clock provided by the caller, such as
+//! a block height, which must be consistent across all participants of the group.
+//! For a next epoch staged with cutover point \\( h_c \\) and an overlap window of
+//! \\( w \\), the packages are valid for signing as follows:
+//!
+//! * for \\( h < h_c \\), only the current package is valid;
+//! * for \\( h_c \le h < h_c + w \\), both packages are valid, the next one being preferred;
+//! * for \\( h \ge h_c + w \\), only the next package is valid.
+//!
+//! To let light clients, such as bridges, follow the rotations of a group without
+//! processing the resharing transcripts, the group of each epoch can attest its
+//! successor with an [`EpochTransitionProof`], a threshold signature over the next
+//! group key and a digest of the next parameters and verification shares. Such proofs
+//! are verified with the sole group key of the previous epoch.
+//!
+//! Similarly, when a participant is expelled from the group with a
+//! [`ParticipantExpulsion`](crate::dkg::ParticipantExpulsion), the group can attest
+//! to registries that the expelled participant no longer takes part in the next
+//! epoch with a [`RevocationRecord`].
+//!
+//! [`PublicKeyPackage`]: crate::keys::PublicKeyPackage
+//! [`EpochedKeyManager`]: crate::epoch::EpochedKeyManager
+//! [`EpochTransitionProof`]: crate::epoch::EpochTransitionProof
+//! [`RevocationRecord`]: crate::epoch::RevocationRecord
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualVerifyingKey, PublicKeyPackage};
+use crate::sign::ThresholdSignature;
+use crate::utils::{hash_to_array, Vec};
+use crate::validation::{deserialize_validated, validate_points, ValidatePoints};
+use crate::{Error, FrostResult};
+
+/// A [`PublicKeyPackage`] scheduled to replace the current one.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+struct PendingEpoch<C: CipherSuite> {
+    /// The public key material of the next epoch.
+    package: PublicKeyPackage<C>,
+    /// The logical time from which the next epoch takes over.
+    cutover: u64,
+}
+
+/// A manager of the public key material of an ICE-FROST group across epochs,
+/// supporting the overlap of two consecutive epochs during a key rotation.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EpochedKeyManager<C: CipherSuite> {
+    /// The number of the current epoch.
+    epoch: u64,
+    /// The public key material of the current epoch.
+    current: PublicKeyPackage<C>,
+    /// The public key material of the next epoch, if any has been staged.
+    next: Option<PendingEpoch<C>>,
+    /// The duration during which the current epoch remains valid after the cutover.
+    overlap: u64,
+}
+
+impl<C: CipherSuite> EpochedKeyManager<C> {
+    /// Instantiates a new [`EpochedKeyManager`] starting at epoch 0 with the
+    /// provided `package`, and with an overlap window of `overlap` units of
+    /// logical time between consecutive epochs.
+    pub fn new(package: PublicKeyPackage<C>, overlap: u64) -> Self {
+        Self {
+            epoch: 0,
+            current: package,
+            next: None,
+            overlap,
+        }
+    }
+
+    /// The number of the current epoch.
+    pub fn current_epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The [`PublicKeyPackage`] of the current epoch.
+    pub fn current(&self) -> &PublicKeyPackage<C> {
+        &self.current
+    }
+
+    /// The [`PublicKeyPackage`] of the next epoch, if one has been staged.
+    pub fn next(&self) -> Option<&PublicKeyPackage<C>> {
+        self.next.as_ref().map(|pending| &pending.package)
+    }
+
+    /// The duration of the overlap window between consecutive epochs.
+    pub fn overlap(&self) -> u64 {
+        self.overlap
+    }
+
+    /// The logical time at which the next epoch takes over, if one has been staged.
+    pub fn cutover(&self) -> Option<u64> {
+        self.next.as_ref().map(|pending| pending.cutover)
+    }
+
+    /// Stage the `package` obtained from a resharing session as the next epoch,
+    /// taking over from the logical time `cutover`.
+    ///
+    /// Only one epoch can be staged at a time: [`EpochedKeyManager::advance`] must
+    /// have completed the previous transition before staging another one.
+    pub fn stage_next(&mut self, package: PublicKeyPackage<C>, cutover: u64) -> FrostResult<C, ()> {
+        if self.next.is_some() {
+            return Err(Error::EpochTransitionInProgress);
+        }
+
+        self.next = Some(PendingEpoch { package, cutover });
+
+        Ok(())
+    }
+
+    /// Retrieve the [`PublicKeyPackage`] to be preferred for new signing sessions at
+    /// the logical time `now`, along with its epoch number.
+    pub fn signing_package(&self, now: u64) -> (u64, &PublicKeyPackage<C>) {
+        match &self.next {
+            Some(pending) if now >= pending.cutover => (self.epoch + 1, &pending.package),
+            _ => (self.epoch, &self.current),
+        }
+    }
+
+    /// Retrieve all [`PublicKeyPackage`]s valid for signing at the logical time `now`,
+    /// along with their epoch number, by increasing epoch.
+    pub fn valid_packages(&self, now: u64) -> Vec<(u64, &PublicKeyPackage<C>)> {
+        let mut packages = Vec::with_capacity(2);
+
+        if self.is_current_valid(now) {
+            packages.push((self.epoch, &self.current));
+        }
+        if let Some(pending) = &self.next {
+            if now >= pending.cutover {
+                packages.push((self.epoch + 1, &pending.package));
+            }
+        }
+
+        packages
+    }
+
+    /// Retrieve the [`PublicKeyPackage`] of the given `epoch`, provided that
+    /// it is valid for signing at the logical time `now`.
+    pub fn package(&self, epoch: u64, now: u64) -> FrostResult<C, &PublicKeyPackage<C>> {
+        self.valid_packages(now)
+            .into_iter()
+            .find(|(e, _)| *e == epoch)
+            .map(|(_, package)| package)
+            .ok_or(Error::InactiveEpoch(epoch))
+    }
+
+    /// Complete the transition to the next epoch if its overlap window has elapsed
+    /// at the logical time `now`, discarding the public key material of the current epoch.
+    ///
+    /// Returns `true` if the manager moved to a new epoch.
+    pub fn advance(&mut self, now: u64) -> bool {
+        if self.is_current_valid(now) {
+            return false;
+        }
+
+        match self.next.take() {
+            Some(pending) => {
+                self.current = pending.package;
+                self.epoch += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Serialize this [`EpochedKeyManager`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`EpochedKeyManager`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    fn is_current_valid(&self, now: u64) -> bool {
+        match &self.next {
+            Some(pending) => now < pending.cutover.saturating_add(self.overlap),
+            None => true,
+        }
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for EpochedKeyManager<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.current.validate_points()?;
+        match &self.next {
+            Some(pending) => pending.package.validate_points(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A succinct proof of the transition of an ICE-FROST group to a new epoch, consisting
+/// of a [`ThresholdSignature`] of the group of the previous epoch over the
+/// [`GroupVerifyingKey`] of the new epoch and a digest of its public key material.
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EpochTransitionProof<C: CipherSuite> {
+    /// The number of the new epoch.
+    pub(crate) epoch: u64,
+    /// The group key of the new epoch.
+    pub(crate) group_key: GroupVerifyingKey<C>,
+    /// The digest of the parameters and verification shares of the new epoch.
+    pub(crate) parameters_digest: Vec<u8>,
+    /// The signature of the group of the previous epoch over this transition.
+    pub(crate) signature: ThresholdSignature<C>,
+}
+
+impl<C: CipherSuite> EpochTransitionProof<C> {
+    /// Compute the message to be signed by the group of the previous epoch to attest the
+    /// transition to the new `epoch`, whose public key material is given by `package`.
+    ///
+    /// This message is to be signed in a regular signing session, and its `h4` hash
+    /// passed to the signers.
+    pub fn message(epoch: u64, package: &PublicKeyPackage<C>) -> FrostResult<C, Vec<u8>> {
+        Self::encode_message(epoch, package.group_key(), Self::digest(package)?.as_ref())
+    }
+
+    /// Construct a new [`EpochTransitionProof`] for the new `epoch`, whose public key
+    /// material is given by `package`, from the `signature` of the group of the previous
+    /// epoch over the corresponding [`EpochTransitionProof::message`].
+    pub fn new(
+        epoch: u64,
+        package: &PublicKeyPackage<C>,
+        signature: ThresholdSignature<C>,
+    ) -> FrostResult<C, Self> {
+        Ok(Self {
+            epoch,
+            group_key: *package.group_key(),
+            parameters_digest: Self::digest(package)?.as_ref().to_vec(),
+            signature,
+        })
+    }
+
+    /// The number of the new epoch.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The [`GroupVerifyingKey`] of the new epoch.
+    pub fn group_key(&self) -> &GroupVerifyingKey<C> {
+        &self.group_key
+    }
+
+    /// The digest of the parameters and verification shares of the new epoch.
+    pub fn parameters_digest(&self) -> &[u8] {
+        &self.parameters_digest
+    }
+
+    /// Verify this [`EpochTransitionProof`] against the `previous_group_key` of the
+    /// group of the previous epoch.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the group of the previous
+    /// epoch attested the transition to [`EpochTransitionProof::group_key`].
+    pub fn verify(&self, previous_group_key: &GroupVerifyingKey<C>) -> FrostResult<C, ()> {
+        let message = Self::encode_message(self.epoch, &self.group_key, &self.parameters_digest)?;
+
+        self.signature
+            .verify(previous_group_key, C::h4(&message)?.as_ref())
+            .map_err(|_| Error::InvalidEpochTransitionProof)
+    }
+
+    /// Check that the full public key material `package` of the new epoch matches
+    /// this [`EpochTransitionProof`].
+    pub fn verify_package(&self, package: &PublicKeyPackage<C>) -> FrostResult<C, ()> {
+        match *package.group_key() == self.group_key
+            && Self::digest(package)?.as_ref() == self.parameters_digest.as_slice()
+        {
+            true => Ok(()),
+            false => Err(Error::InvalidEpochTransitionProof),
+        }
+    }
+
+    /// Follow a chain of consecutive [`EpochTransitionProof`]s from the `initial_group_key`
+    /// of the epoch `initial_epoch`.
+    ///
+    /// # Returns
+    ///
+    /// The [`GroupVerifyingKey`] of the last epoch of the chain, along with its number,
+    /// otherwise an [`Error::InvalidEpochTransitionProof`] if any proof is invalid or
+    /// does not follow the previous epoch.
+    pub fn verify_chain(
+        initial_epoch: u64,
+        initial_group_key: &GroupVerifyingKey<C>,
+        proofs: &[Self],
+    ) -> FrostResult<C, (u64, GroupVerifyingKey<C>)> {
+        let (mut epoch, mut group_key) = (initial_epoch, *initial_group_key);
+
+        for proof in proofs.iter() {
+            if Some(proof.epoch) != epoch.checked_add(1) {
+                return Err(Error::InvalidEpochTransitionProof);
+            }
+            proof.verify(&group_key)?;
+
+            (epoch, group_key) = (proof.epoch, proof.group_key);
+        }
+
+        Ok((epoch, group_key))
+    }
+
+    /// Serialize this [`EpochTransitionProof`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`EpochTransitionProof`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the digest of the parameters and verification shares of a `package`.
+    fn digest(package: &PublicKeyPackage<C>) -> FrostResult<C, C::HashOutput> {
+        let mut bytes = Vec::new();
+        package
+            .parameters()
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        package
+            .verifying_keys()
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+
+        hash_to_array::<C>(
+            (C::context_string() + "epoch parameters").as_bytes(),
+            &bytes,
+        )
+    }
+
+    fn encode_message(
+        epoch: u64,
+        group_key: &GroupVerifyingKey<C>,
+        parameters_digest: &[u8],
+    ) -> FrostResult<C, Vec<u8>> {
+        let mut message = (C::context_string() + "epoch transition").into_bytes();
+        message.extend_from_slice(&epoch.to_le_bytes());
+        group_key
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        message.extend_from_slice(parameters_digest);
+
+        Ok(message)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for EpochTransitionProof<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.group_key.validate_points()?;
+        self.signature.validate_points()
+    }
+}
+
+/// A record of the expulsion of a participant from an ICE-FROST group, consisting of a
+/// [`ThresholdSignature`] of the group over the public verification share of the expelled
+/// participant and the public key material of the epoch from which it is excluded.
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RevocationRecord<C: CipherSuite> {
+    /// The number of the epoch from which the participant is excluded.
+    pub(crate) epoch: u64,
+    /// The public verification share of the expelled participant.
+    pub(crate) expelled: IndividualVerifyingKey<C>,
+    /// The group key of the new epoch.
+    pub(crate) group_key: GroupVerifyingKey<C>,
+    /// The digest of the parameters and verification shares of the new epoch.
+    pub(crate) parameters_digest: Vec<u8>,
+    /// The signature of the group over this revocation.
+    pub(crate) signature: ThresholdSignature<C>,
+}
+
+impl<C: CipherSuite> RevocationRecord<C> {
+    /// Compute the message to be signed by the group to attest the exclusion of the
+    /// `expelled` participant from the new `epoch`, whose public key material is given
+    /// by `package`.
+    ///
+    /// This message is to be signed in a regular signing session, and its `h4` hash
+    /// passed to the signers.
+    pub fn message(
+        epoch: u64,
+        expelled: &IndividualVerifyingKey<C>,
+        package: &PublicKeyPackage<C>,
+    ) -> FrostResult<C, Vec<u8>> {
+        Self::encode_message(
+            epoch,
+            expelled,
+            package.group_key(),
+            EpochTransitionProof::digest(package)?.as_ref(),
+        )
+    }
+
+    /// Construct a new [`RevocationRecord`] of the `expelled` participant for the new
+    /// `epoch`, whose public key material is given by `package`, from the `signature`
+    /// of the group over the corresponding [`RevocationRecord::message`].
+    pub fn new(
+        epoch: u64,
+        expelled: &IndividualVerifyingKey<C>,
+        package: &PublicKeyPackage<C>,
+        signature: ThresholdSignature<C>,
+    ) -> FrostResult<C, Self> {
+        Ok(Self {
+            epoch,
+            expelled: expelled.clone(),
+            group_key: *package.group_key(),
+            parameters_digest: EpochTransitionProof::digest(package)?.as_ref().to_vec(),
+            signature,
+        })
+    }
+
+    /// The number of the epoch from which the participant is excluded.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The public verification share of the expelled participant.
+    pub fn expelled(&self) -> &IndividualVerifyingKey<C> {
+        &self.expelled
+    }
+
+    /// The [`GroupVerifyingKey`] of the new epoch.
+    pub fn group_key(&self) -> &GroupVerifyingKey<C> {
+        &self.group_key
+    }
+
+    /// The digest of the parameters and verification shares of the new epoch.
+    pub fn parameters_digest(&self) -> &[u8] {
+        &self.parameters_digest
+    }
+
+    /// Verify this [`RevocationRecord`] against the `group_key` of the group
+    /// which expelled the participant.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the group attested the
+    /// exclusion of [`RevocationRecord::expelled`] from [`RevocationRecord::epoch`].
+    pub fn verify(&self, group_key: &GroupVerifyingKey<C>) -> FrostResult<C, ()> {
+        let message = Self::encode_message(
+            self.epoch,
+            &self.expelled,
+            &self.group_key,
+            &self.parameters_digest,
+        )?;
+
+        self.signature
+            .verify(group_key, C::h4(&message)?.as_ref())
+            .map_err(|_| Error::InvalidRevocationRecord)
+    }
+
+    /// Check that the full public key material `package` of the new epoch matches
+    /// this [`RevocationRecord`], and does not hold the public verification share
+    /// of the expelled participant.
+    pub fn verify_package(&self, package: &PublicKeyPackage<C>) -> FrostResult<C, ()> {
+        match *package.group_key() == self.group_key
+            && EpochTransitionProof::digest(package)?.as_ref() == self.parameters_digest.as_slice()
+            && package
+                .verifying_keys()
+                .iter()
+                .all(|verifying_key| verifying_key.share != self.expelled.share)
+        {
+            true => Ok(()),
+            false => Err(Error::InvalidRevocationRecord),
+        }
+    }
+
+    /// Serialize this [`RevocationRecord`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`RevocationRecord`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    fn encode_message(
+        epoch: u64,
+        expelled: &IndividualVerifyingKey<C>,
+        group_key: &GroupVerifyingKey<C>,
+        parameters_digest: &[u8],
+    ) -> FrostResult<C, Vec<u8>> {
+        let mut message = (C::context_string() + "revocation").into_bytes();
+        message.extend_from_slice(&epoch.to_le_bytes());
+        expelled
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        group_key
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        message.extend_from_slice(parameters_digest);
+
+        Ok(message)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for RevocationRecord<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>([&self.expelled.share])?;
+        self.group_key.validate_points()?;
+        self.signature.validate_points()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keys::{GroupVerifyingKey, IndividualVerifyingKey};
+    use crate::parameters::ThresholdParameters;
+    use crate::testing::Secp256k1Sha256;
+    use crate::Element;
+
+    use ark_ff::UniformRand;
+    use rand::rngs::OsRng;
+
+    fn random_package(n: u32, t: u32) -> PublicKeyPackage<Secp256k1Sha256> {
+        let mut rng = OsRng;
+        let verifying_keys = (1..=n)
+            .map(|index| IndividualVerifyingKey {
+                index,
+                share: UniformRand::rand(&mut rng),
+            })
+            .collect();
+
+        PublicKeyPackage::new(
+            ThresholdParameters::new(n, t).unwrap(),
+            GroupVerifyingKey::new(Element::random(rng)),
+            verifying_keys,
+        )
+    }
+
+    #[test]
+    fn epochs_overlap_after_cutover() {
+        let old = random_package(3, 2);
+        let new = random_package(4, 3);
+
+        let mut manager = EpochedKeyManager::new(old.clone(), 10);
+        assert_eq!(manager.cutover(), None);
+        assert_eq!(manager.valid_packages(1000), vec![(0, &old)]);
+
+        manager.stage_next(new.clone(), 100).unwrap();
+        assert_eq!(manager.cutover(), Some(100));
+        assert_eq!(
+            manager.stage_next(new.clone(), 200),
+            Err(Error::EpochTransitionInProgress)
+        );
+
+        // Before the cutover, only the current epoch can be used.
+        assert_eq!(manager.signing_package(99), (0, &old));
+        assert_eq!(manager.valid_packages(99), vec![(0, &old)]);
+        assert_eq!(manager.package(1, 99), Err(Error::InactiveEpoch(1)));
+        assert!(!manager.advance(99));
+
+        // During the overlap window, both epochs can be used.
+        assert_eq!(manager.signing_package(100), (1, &new));
+        assert_eq!(manager.valid_packages(109), vec![(0, &old), (1, &new)]);
+        assert_eq!(manager.package(0, 109), Ok(&old));
+        assert!(!manager.advance(109));
+
+        // Past the overlap window, only the next epoch can be used.
+        assert_eq!(manager.valid_packages(110), vec![(1, &new)]);
+        assert!(manager.advance(110));
+        assert_eq!(manager.current_epoch(), 1);
+        assert_eq!(manager.current(), &new);
+        assert_eq!(manager.next(), None);
+        assert_eq!(manager.package(0, 110), Err(Error::InactiveEpoch(0)));
+    }
+
+    #[test]
+    fn package_lookup_and_serialization() {
+        let package = random_package(5, 3);
+        assert_eq!(package.verifying_key(4).unwrap().index, 4);
+        assert!(package.verifying_key(6).is_none());
+
+        let bytes = package.to_bytes().unwrap();
+        assert_eq!(PublicKeyPackage::from_bytes(&bytes).unwrap(), package);
+
+        let mut manager = EpochedKeyManager::new(package, 5);
+        manager.stage_next(random_package(3, 2), 42).unwrap();
+
+        let bytes = manager.to_bytes().unwrap();
+        assert_eq!(EpochedKeyManager::from_bytes(&bytes).unwrap(), manager);
+    }
+}
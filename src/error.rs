@@ -3,21 +3,154 @@
 use crate::ciphersuite::CipherSuite;
 
 use crate::dkg::Complaint;
+use crate::message::RoundTag;
+use crate::sign::ReRequestRefusal;
 use crate::utils::{String, Vec};
 
+/// The underlying cause of a (de)serialization failure, as reported by
+/// the arkworks serialization library.
+#[derive(Debug)]
+pub enum SerializationErrorKind {
+    /// Not enough space was available to write extra info.
+    NotEnoughSpace,
+    /// The data was invalid.
+    InvalidData,
+    /// Non-empty flags were given where none were expected.
+    UnexpectedFlags,
+    /// An I/O error was encountered, carried as the source of this error.
+    IoError(ark_std::io::Error),
+}
+
+impl SerializationErrorKind {
+    /// An I/O error caused by a truncated input.
+    pub(crate) fn unexpected_eof() -> Self {
+        Self::IoError(ark_std::io::ErrorKind::UnexpectedEof.into())
+    }
+}
+
+impl From<ark_serialize::SerializationError> for SerializationErrorKind {
+    fn from(error: ark_serialize::SerializationError) -> Self {
+        match error {
+            ark_serialize::SerializationError::NotEnoughSpace => Self::NotEnoughSpace,
+            ark_serialize::SerializationError::InvalidData => Self::InvalidData,
+            ark_serialize::SerializationError::UnexpectedFlags => Self::UnexpectedFlags,
+            ark_serialize::SerializationError::IoError(error) => Self::IoError(error),
+        }
+    }
+}
+
+/// I/O errors are compared by kind, as they do not implement equality.
+impl PartialEq for SerializationErrorKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::NotEnoughSpace, Self::NotEnoughSpace)
+            | (Self::InvalidData, Self::InvalidData)
+            | (Self::UnexpectedFlags, Self::UnexpectedFlags) => true,
+            (Self::IoError(error), Self::IoError(other_error)) => {
+                error.kind() == other_error.kind()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SerializationErrorKind {}
+
+impl core::fmt::Display for SerializationErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            SerializationErrorKind::NotEnoughSpace => {
+                write!(f, "Not enough space to write extra info.")
+            }
+            SerializationErrorKind::InvalidData => {
+                write!(f, "The data was invalid.")
+            }
+            SerializationErrorKind::UnexpectedFlags => {
+                write!(f, "Non-empty flags were given where none were expected.")
+            }
+            SerializationErrorKind::IoError(_) => {
+                write!(f, "An I/O error was encountered.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerializationErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SerializationErrorKind::IoError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The underlying cause of a failure to encrypt or decrypt a secret share
+/// or a complaint with the [`ShareCipher`](crate::share_cipher::ShareCipher)
+/// of a ciphersuite.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CipherErrorKind {
+    /// The symmetric key does not have the length expected by the cipher.
+    InvalidKeyLength,
+    /// The authenticated encryption failed, for instance on a tampered ciphertext.
+    Aead(aes_gcm::aead::Error),
+    /// The decrypted plaintext could not be deserialized.
+    MalformedPlaintext(SerializationErrorKind),
+    /// The decrypted plaintext does not match the metadata of its ciphertext.
+    UnexpectedPlaintext,
+}
+
+impl core::fmt::Display for CipherErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CipherErrorKind::InvalidKeyLength => {
+                write!(f, "The symmetric key has an invalid length.")
+            }
+            CipherErrorKind::Aead(_) => {
+                write!(f, "The authenticated encryption failed.")
+            }
+            CipherErrorKind::MalformedPlaintext(_) => {
+                write!(f, "The decrypted plaintext is malformed.")
+            }
+            CipherErrorKind::UnexpectedPlaintext => {
+                write!(f, "The decrypted plaintext does not match its ciphertext.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CipherErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CipherErrorKind::Aead(error) => Some(error),
+            CipherErrorKind::MalformedPlaintext(kind) => Some(kind),
+            CipherErrorKind::InvalidKeyLength | CipherErrorKind::UnexpectedPlaintext => None,
+        }
+    }
+}
+
 /// Errors that may happen during Key Generation
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error<C: CipherSuite> {
     /// Serialisation error
-    SerializationError,
+    SerializationError(SerializationErrorKind),
     /// Deserialisation error
-    DeserializationError,
+    DeserializationError(SerializationErrorKind),
     /// Point compression error
-    CompressionError,
+    CompressionError(SerializationErrorKind),
     /// Point decompression error
-    DecompressionError,
+    DecompressionError(SerializationErrorKind),
+    /// A deserialized point is the identity
+    IdentityPoint,
+    /// A deserialized point is not on the curve or not in its prime-order subgroup
+    InvalidPoint,
+    /// Secret share encryption failure
+    EncryptionError(CipherErrorKind),
     /// Encrypted secret share decryption failure
-    DecryptionError,
+    DecryptionError(CipherErrorKind),
+    /// Encrypted secret share nonce mismatch
+    InvalidNonce,
     /// Secret share verification failure
     ShareVerificationError,
     /// Complaint verification failure
@@ -36,6 +169,8 @@ pub enum Error<C: CipherSuite> {
     Complaint(Vec<Complaint<C>>),
     /// Not all participants have been included
     InvalidNumberOfParticipants(usize, u32),
+    /// The threshold parameters, as `(n, t)`, are not supported by the operation
+    UnsupportedParameters(u32, u32),
     /// The provided slices for the MSM don't match in lenth
     InvalidMSMParameters,
     /// Too many invalid participants, with their indices
@@ -50,6 +185,112 @@ pub enum Error<C: CipherSuite> {
     InvalidSignature,
     /// Misbehaving Participants
     MisbehavingParticipants(Vec<u32>),
+    /// Invalid signing quorum certificate
+    InvalidQuorumCertificate,
+    /// Invalid attributed signature
+    InvalidAttributedSignature,
+    /// The requested key epoch is not active
+    InactiveEpoch(u64),
+    /// A key epoch transition is already in progress
+    EpochTransitionInProgress,
+    /// Invalid epoch transition proof
+    InvalidEpochTransitionProof,
+    /// Invalid share receipt
+    InvalidShareReceipt,
+    /// Invalid roster membership proof
+    InvalidMembershipProof,
+    /// Invalid combination of key shares
+    InvalidShareCombination,
+    /// Invalid key share escrow
+    InvalidEscrow,
+    /// Invalid signing package
+    InvalidSigningPackage,
+    /// Inconsistent views of a signing package
+    InconsistentSigningPackage(Vec<u32>),
+    /// Signers not sorted by strictly increasing participant index
+    UnsortedSigners,
+    /// Signer rejected by the signer policy of the aggregator
+    ForbiddenSigner(u32),
+    /// Signers not spanning enough distinct organizations
+    InsufficientOrganizations(usize, usize),
+    /// Commitment randomizers not matching the signers of the session
+    InvalidRandomizers,
+    /// A commitment randomizer was already derived from the commitment share
+    CommitmentShareRandomized,
+    /// Inconsistent signing transcript
+    InvalidSigningTranscript,
+    /// Encrypted secret share routed to the wrong recipient, with the
+    /// indices of its sender and of the participant who received it
+    MisroutedShare(u32, u32),
+    /// Incompatible protocol versions or ciphersuites
+    IncompatibleVersions(Vec<u32>),
+    /// Refused partial signature re-request
+    ReRequestRefused(ReRequestRefusal),
+    /// Timeout
+    Timeout,
+    /// Session pseudonym not matching any participant
+    UnknownPseudonym,
+    /// Invalid contributions to the admission of a new participant,
+    /// with the indices of the helpers who issued them
+    InvalidAdmissionContributions(Vec<u32>),
+    /// Invalid revocation record
+    InvalidRevocationRecord,
+    /// Session handle not matching any suspended signing session
+    UnknownSession,
+    /// Invalid external authentication of the messages of some participants,
+    /// with their indices
+    InvalidExternalAuthentication(Vec<u32>),
+    /// Invalid or outdated liveness heartbeat
+    InvalidHeartbeat,
+    /// Blinded partial signatures missing or altered for the given signers
+    IncompleteBlindedPartialSignatures(Vec<u32>),
+    /// Message tagged for another round, session or epoch than the expected one,
+    /// with the expected and received tags
+    UnexpectedRoundTag(RoundTag, RoundTag),
+    /// Memory allocation failure of a buffer
+    AllocationFailure,
+    /// A partial signature was produced under another key epoch than expected
+    EpochMismatch {
+        /// The index of the signer of the partial signature
+        signer: u32,
+        /// The key epoch of the partial signature
+        got: u64,
+        /// The key epoch of the signing session
+        expected: u64,
+    },
+    /// A partial signature was produced under another group key
+    KeyFingerprintMismatch(u32),
+    /// A partial signature lacks the epoch tag expected by the aggregator
+    MissingEpochTag(u32),
+    /// Participant index not part of the group or of the signers of a session
+    UnknownParticipant(u32),
+    /// Verifying key of a signer issued for another participant, with the
+    /// indices of the signer and of the key
+    SignerKeyMismatch(u32, u32),
+    /// The secret polynomial coefficients of a dealer are missing
+    MissingCoefficients,
+    /// The secret polynomial coefficients of a dealer were consumed, and cannot be used anymore
+    ConsumedCoefficients,
+    /// A polynomial commitment has no coefficient to derive a public key from
+    EmptyCommitment,
+    /// Diffie-Hellman public key of a participant being the identity, with its index
+    IdentityDhPublicKey(u32),
+    /// Diffie-Hellman public key of a participant of small order, with its index
+    LowOrderDhPublicKey(u32),
+    /// Participant indices provided more than once
+    DuplicateIndices,
+    /// Secret seed shorter than required, with its length and the minimum length
+    SeedTooShort(usize, usize),
+    /// More commitment shares requested at once than allowed, with the number
+    /// requested and the maximum
+    TooManyCommitmentShares(u64, u64),
+    /// New participant already a member of the group, with its index
+    ExistingParticipant(u32),
+    /// Participant index not part of the helpers of an admission
+    UnknownHelper(u32),
+    /// Pre-hashed message not matching the hash output size of the ciphersuite,
+    /// with its length and the expected one
+    InvalidMessageHashLength(usize, usize),
     /// Custom error
     Custom(String),
 }
@@ -57,21 +298,39 @@ pub enum Error<C: CipherSuite> {
 impl<C: CipherSuite> core::fmt::Display for Error<C> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
-            Error::SerializationError => {
+            Error::SerializationError(_) => {
                 write!(f, "An error happened while serializing.")
             }
-            Error::DeserializationError => {
+            Error::DeserializationError(_) => {
                 write!(f, "An error happened while deserializing.")
             }
-            Error::CompressionError => {
+            Error::CompressionError(_) => {
                 write!(f, "An error happened while compressing a point.")
             }
-            Error::DecompressionError => {
+            Error::DecompressionError(_) => {
                 write!(f, "An error happened while decompressing a point.")
             }
-            Error::DecryptionError => {
+            Error::IdentityPoint => {
+                write!(f, "A deserialized point is the identity.")
+            }
+            Error::InvalidPoint => {
+                write!(
+                    f,
+                    "A deserialized point does not belong to the prime-order subgroup."
+                )
+            }
+            Error::EncryptionError(_) => {
+                write!(f, "Could not encrypt secret share.")
+            }
+            Error::DecryptionError(_) => {
                 write!(f, "Could not decrypt encrypted share.")
             }
+            Error::InvalidNonce => {
+                write!(
+                    f,
+                    "The encrypted share nonce does not match its derivation rule."
+                )
+            }
             Error::ShareVerificationError => {
                 write!(f, "The secret share is not correct.")
             }
@@ -100,7 +359,13 @@ impl<C: CipherSuite> core::fmt::Display for Error<C> {
                 write!(f, "Could not retrieve encrypted shares.")
             }
             Error::Complaint(complaints) => {
-                write!(f, "{:?}", complaints)
+                let accused: Vec<u32> = complaints.iter().map(|c| c.accused_index).collect();
+                write!(
+                    f,
+                    "{} complaint(s) issued against participants {:?}.",
+                    complaints.len(),
+                    accused
+                )
             }
             Error::InvalidMSMParameters => {
                 write!(
@@ -115,6 +380,13 @@ impl<C: CipherSuite> core::fmt::Display for Error<C> {
                     nb, n_params
                 )
             }
+            Error::UnsupportedParameters(n, t) => {
+                write!(
+                    f,
+                    "The threshold parameters t = {} out of n = {} are not supported.",
+                    t, n
+                )
+            }
             Error::TooManyInvalidParticipants(indices) => {
                 write!(
                     f,
@@ -144,12 +416,314 @@ impl<C: CipherSuite> core::fmt::Display for Error<C> {
                     indices
                 )
             }
+            Error::InvalidQuorumCertificate => {
+                write!(f, "The signing quorum certificate is not correct.")
+            }
+            Error::InvalidAttributedSignature => {
+                write!(f, "The attributed threshold signature is not correct.")
+            }
+            Error::InactiveEpoch(epoch) => {
+                write!(f, "The key epoch {} is not active.", epoch)
+            }
+            Error::EpochTransitionInProgress => {
+                write!(f, "A key epoch transition is already in progress.")
+            }
+            Error::InvalidEpochTransitionProof => {
+                write!(f, "The epoch transition proof is not correct.")
+            }
+            Error::InvalidShareReceipt => {
+                write!(f, "The secret share receipt is not correct.")
+            }
+            Error::InvalidMembershipProof => {
+                write!(f, "The roster membership proof is not correct.")
+            }
+            Error::InvalidShareCombination => {
+                write!(f, "The key shares cannot be combined.")
+            }
+            Error::InvalidEscrow => {
+                write!(f, "The escrowed key share is not correct.")
+            }
+            Error::InvalidSigningPackage => {
+                write!(f, "The signing package is not correct.")
+            }
+            Error::UnsortedSigners => {
+                write!(
+                    f,
+                    "The signers are not sorted by strictly increasing participant index."
+                )
+            }
+            Error::ForbiddenSigner(index) => {
+                write!(
+                    f,
+                    "The signer with index {} is rejected by the signer policy.",
+                    index
+                )
+            }
+            Error::InsufficientOrganizations(count, minimum) => {
+                write!(
+                    f,
+                    "The signers span {} distinct organizations, but at least {} are required.",
+                    count, minimum
+                )
+            }
+            Error::CommitmentShareRandomized => {
+                write!(
+                    f,
+                    "A commitment randomizer was already derived from the commitment share."
+                )
+            }
+            Error::InvalidRandomizers => {
+                write!(
+                    f,
+                    "The commitment randomizers do not match the signers of the session."
+                )
+            }
+            Error::InvalidSigningTranscript => {
+                write!(f, "The signing transcript is inconsistent.")
+            }
+            Error::MisroutedShare(sender, receiver) => {
+                write!(
+                    f,
+                    "The share of participant {} was not routed to participant {}.",
+                    sender, receiver
+                )
+            }
+            Error::InconsistentSigningPackage(indices) => {
+                write!(
+                    f,
+                    "These participants reported a different signing package: {:?}",
+                    indices
+                )
+            }
+            Error::IncompatibleVersions(indices) => {
+                write!(
+                    f,
+                    "These participants advertised incompatible protocol versions: {:?}",
+                    indices
+                )
+            }
+            Error::ReRequestRefused(refusal) => {
+                write!(
+                    f,
+                    "The partial signature re-request was refused: {:?}",
+                    refusal
+                )
+            }
+            Error::Timeout => {
+                write!(f, "The operation timed out.")
+            }
+            Error::UnknownPseudonym => {
+                write!(f, "The session pseudonym does not match any participant.")
+            }
+            Error::InvalidAdmissionContributions(indices) => {
+                write!(
+                    f,
+                    "These helpers provided invalid admission contributions: {:?}",
+                    indices
+                )
+            }
+            Error::InvalidRevocationRecord => {
+                write!(f, "The revocation record is not correct.")
+            }
+            Error::UnknownSession => {
+                write!(
+                    f,
+                    "The session handle does not match any suspended session."
+                )
+            }
+            Error::InvalidExternalAuthentication(indices) => {
+                write!(
+                    f,
+                    "The messages of participants {:?} are not correctly authenticated.",
+                    indices
+                )
+            }
+            Error::InvalidHeartbeat => {
+                write!(f, "The liveness heartbeat is invalid or outdated.")
+            }
+            Error::AllocationFailure => {
+                write!(f, "Could not allocate the memory of a buffer.")
+            }
+            Error::EpochMismatch {
+                signer,
+                got,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "Signer {} signed under key epoch {} instead of {}.",
+                    signer, got, expected
+                )
+            }
+            Error::KeyFingerprintMismatch(signer) => {
+                write!(f, "Signer {} signed under another group key.", signer)
+            }
+            Error::MissingEpochTag(signer) => {
+                write!(
+                    f,
+                    "Signer {} did not tag its partial signature with an epoch.",
+                    signer
+                )
+            }
+            Error::UnknownParticipant(index) => {
+                write!(f, "The participant with index {} is unknown.", index)
+            }
+            Error::SignerKeyMismatch(signer, key) => {
+                write!(
+                    f,
+                    "Signer {} was included with the verifying key of participant {}.",
+                    signer, key
+                )
+            }
+            Error::MissingCoefficients => {
+                write!(f, "The secret polynomial coefficients are missing.")
+            }
+            Error::ConsumedCoefficients => {
+                write!(f, "The secret polynomial coefficients were consumed.")
+            }
+            Error::EmptyCommitment => {
+                write!(f, "The polynomial commitment has no coefficient.")
+            }
+            Error::IdentityDhPublicKey(index) => {
+                write!(
+                    f,
+                    "The Diffie-Hellman public key of participant {} is the identity.",
+                    index
+                )
+            }
+            Error::LowOrderDhPublicKey(index) => {
+                write!(
+                    f,
+                    "The Diffie-Hellman public key of participant {} has a small order.",
+                    index
+                )
+            }
+            Error::DuplicateIndices => {
+                write!(f, "Duplicate indices provided.")
+            }
+            Error::SeedTooShort(length, minimum) => {
+                write!(
+                    f,
+                    "The seed is {} bytes long, at least {} bytes are required.",
+                    length, minimum
+                )
+            }
+            Error::TooManyCommitmentShares(requested, maximum) => {
+                write!(
+                    f,
+                    "{} commitment shares were requested, at most {} are allowed.",
+                    requested, maximum
+                )
+            }
+            Error::ExistingParticipant(index) => {
+                write!(
+                    f,
+                    "The participant with index {} is already a member of the group.",
+                    index
+                )
+            }
+            Error::UnknownHelper(index) => {
+                write!(
+                    f,
+                    "The participant with index {} is not a helper of this admission.",
+                    index
+                )
+            }
+            Error::InvalidMessageHashLength(length, expected) => {
+                write!(
+                    f,
+                    "The message hash is {} bytes long, instead of {} bytes.",
+                    length, expected
+                )
+            }
+            Error::UnexpectedRoundTag(expected, received) => {
+                write!(
+                    f,
+                    "Expected a message of {}, received one of {}.",
+                    expected, received
+                )
+            }
+            Error::IncompleteBlindedPartialSignatures(indices) => {
+                write!(
+                    f,
+                    "The blinded partial signatures of signers {:?} are missing or altered.",
+                    indices
+                )
+            }
             Error::Custom(string) => {
-                write!(f, "{:?}", string)
+                write!(f, "{}", string)
             }
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl<C: CipherSuite> std::error::Error for Error<C> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::SerializationError(kind)
+            | Error::DeserializationError(kind)
+            | Error::CompressionError(kind)
+            | Error::DecompressionError(kind) => Some(kind),
+            Error::EncryptionError(kind) | Error::DecryptionError(kind) => Some(kind),
+            _ => None,
+        }
+    }
+}
+
 /// Type alias for a Result returning an ICE-FROST-related error on failure.
 pub type FrostResult<C, T> = Result<T, Error<C>>;
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::keys::GroupVerifyingKey;
+    use crate::share_cipher::{Aes256Gcm, ShareCipher};
+    use crate::testing::Secp256k1Sha256;
+
+    use std::error::Error as _;
+    use std::string::ToString;
+
+    #[test]
+    fn deserialization_error_source() {
+        let error = GroupVerifyingKey::<Secp256k1Sha256>::from_bytes(&[0u8; 2]).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::DeserializationError(SerializationErrorKind::unexpected_eof())
+        );
+        assert_eq!(error.to_string(), "An error happened while deserializing.");
+        let kind = error.source().unwrap();
+        assert_eq!(kind.to_string(), "An I/O error was encountered.");
+
+        // The underlying I/O error is carried along.
+        let io_error = kind
+            .source()
+            .unwrap()
+            .downcast_ref::<std::io::Error>()
+            .unwrap();
+        assert_eq!(io_error.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert!(Error::<Secp256k1Sha256>::InvalidSignature
+            .source()
+            .is_none());
+    }
+
+    #[test]
+    fn decryption_error_source() {
+        let key = [0u8; 32];
+        let nonce = [0u8; 12];
+        let error = Aes256Gcm::decrypt::<Secp256k1Sha256>(&key, &nonce, &[0u8; 32]).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::DecryptionError(CipherErrorKind::Aead(aes_gcm::aead::Error))
+        );
+        let kind = error.source().unwrap();
+        assert_eq!(kind.to_string(), "The authenticated encryption failed.");
+        assert!(kind
+            .source()
+            .unwrap()
+            .downcast_ref::<aes_gcm::aead::Error>()
+            .is_some());
+    }
+}
@@ -0,0 +1,383 @@
+//! The escrow module, depositing the signing key share of a participant with
+//! an auditor, as a verifiable encryption of the share.
+//!
+//! An [`EscrowedShare`] is encrypted to the Diffie-Hellman public key of the
+//! auditor, who can recover the [`IndividualSigningKey`] of the participant from it.
+//! Anyone holding the [`IndividualVerifyingKey`] of the participant can check,
+//! without decrypting, that the ciphertext indeed contains the matching share.
+//!
+//! The proof follows the cut-and-choose approach of Camenisch and Damgård. In each
+//! of the [`ESCROW_ROUNDS`] rounds, the participant samples a mask \\( r\_j \\), commits
+//! to it as \\( T\_j = r\_j \cdot G \\), and encrypts both \\( r\_j \\) and \\( r\_j + s \\)
+//! to the auditor, where \\( s \\) is its share. The challenge, derived by hashing all
+//! rounds, selects which of the two encryptions is opened in each round: either
+//! reveals a uniformly random scalar, but a participant whose rounds do not all hold
+//! two consistent encryptions gets caught with probability \\( 1 - 2^{-128} \\). The
+//! auditor decrypts both values of any round, and recovers \\( s \\) as their difference.
+//!
+//! An [`EscrowedShare`] takes about 25 kB on 256-bit curves.
+//!
+//! [`EscrowedShare`]: crate::escrow::EscrowedShare
+//! [`IndividualSigningKey`]: crate::keys::IndividualSigningKey
+//! [`IndividualVerifyingKey`]: crate::keys::IndividualVerifyingKey
+//! [`ESCROW_ROUNDS`]: crate::escrow::ESCROW_ROUNDS
+
+use ark_ec::Group;
+use ark_ff::{UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{
+    DhKeyProvider, DiffieHellmanPublicKey, IndividualSigningKey, IndividualVerifyingKey,
+};
+use crate::utils::{hash_to_array, points_to_affine_batch, vec, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_points, ValidatePoints};
+use crate::{Element, Error, FrostResult};
+
+/// The number of cut-and-choose rounds of an [`EscrowedShare`], each of
+/// which halves the probability of a cheating participant to go unnoticed.
+pub const ESCROW_ROUNDS: usize = 128;
+
+/// A round of the cut-and-choose proof of an [`EscrowedShare`].
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub(crate) struct EscrowRound<C: CipherSuite> {
+    /// The commitment \\( T\_j = r\_j \cdot G \\) to the mask of this round.
+    pub(crate) commitment: C::G,
+    /// The ephemeral public keys of the encryptions of \\( r\_j \\) and \\( r\_j + s \\).
+    pub(crate) ephemeral_keys: (C::G, C::G),
+    /// The encryptions of \\( r\_j \\) and \\( r\_j + s \\).
+    pub(crate) ciphertexts: (Scalar<C>, Scalar<C>),
+    /// The ephemeral private key of the encryption selected by the challenge.
+    pub(crate) opening: Scalar<C>,
+}
+
+/// The signing key share of a participant, verifiably encrypted to an auditor.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EscrowedShare<C: CipherSuite> {
+    /// The index of the participant whose share is escrowed.
+    pub(crate) participant_index: u32,
+    /// The rounds of the cut-and-choose proof.
+    pub(crate) rounds: Vec<EscrowRound<C>>,
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Escrow this [`IndividualSigningKey`] with the auditor owning the given
+    /// Diffie-Hellman public key `auditor_key`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`EscrowedShare`], to be checked with
+    /// [`EscrowedShare::verify`] before being handed over to the auditor.
+    pub fn escrow(
+        &self,
+        auditor_key: &DiffieHellmanPublicKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, EscrowedShare<C>> {
+        let mut rounds = Vec::with_capacity(ESCROW_ROUNDS);
+        let mut ephemeral_secrets = Vec::with_capacity(ESCROW_ROUNDS);
+
+        for round in 0..ESCROW_ROUNDS {
+            let mut mask = Scalar::<C>::rand(&mut csprng);
+            let ephemeral_secret = (
+                Scalar::<C>::rand(&mut csprng),
+                Scalar::<C>::rand(&mut csprng),
+            );
+            let ephemeral_keys = (
+                C::G::generator() * ephemeral_secret.0,
+                C::G::generator() * ephemeral_secret.1,
+            );
+
+            let mut masked_share = mask + self.key;
+            let ciphertexts = (
+                mask + pad::<C>(&(auditor_key.key * ephemeral_secret.0), round, 0)?,
+                masked_share + pad::<C>(&(auditor_key.key * ephemeral_secret.1), round, 1)?,
+            );
+
+            rounds.push(EscrowRound {
+                commitment: C::G::generator() * mask,
+                ephemeral_keys,
+                ciphertexts,
+                opening: Scalar::<C>::zero(),
+            });
+            ephemeral_secrets.push(ephemeral_secret);
+
+            mask.zeroize();
+            masked_share.zeroize();
+        }
+
+        let mut escrowed_share = EscrowedShare {
+            participant_index: self.index,
+            rounds,
+        };
+
+        let verifying_key = self.to_public();
+        let challenge = escrowed_share.challenge(&verifying_key, auditor_key)?;
+        for (index, (round, ephemeral_secret)) in escrowed_share
+            .rounds
+            .iter_mut()
+            .zip(ephemeral_secrets.iter_mut())
+            .enumerate()
+        {
+            round.opening = match challenge_bit(&challenge, index) {
+                false => ephemeral_secret.0,
+                true => ephemeral_secret.1,
+            };
+            ephemeral_secret.0.zeroize();
+            ephemeral_secret.1.zeroize();
+        }
+
+        Ok(escrowed_share)
+    }
+}
+
+impl<C: CipherSuite> EscrowedShare<C> {
+    /// The index of the participant whose share is escrowed.
+    pub fn participant_index(&self) -> u32 {
+        self.participant_index
+    }
+
+    /// Verify that this [`EscrowedShare`] contains the share of the participant with
+    /// the given `verifying_key`, encrypted to the auditor with the given `auditor_key`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the escrow is valid,
+    /// otherwise an [`Error::InvalidEscrow`].
+    pub fn verify(
+        &self,
+        verifying_key: &IndividualVerifyingKey<C>,
+        auditor_key: &DiffieHellmanPublicKey<C>,
+    ) -> FrostResult<C, ()> {
+        if verifying_key.index != self.participant_index || self.rounds.len() != ESCROW_ROUNDS {
+            return Err(Error::InvalidEscrow);
+        }
+
+        let challenge = self.challenge(verifying_key, auditor_key)?;
+        for (index, round) in self.rounds.iter().enumerate() {
+            let bit = challenge_bit(&challenge, index);
+            let (ephemeral_key, ciphertext) = match bit {
+                false => (round.ephemeral_keys.0, round.ciphertexts.0),
+                true => (round.ephemeral_keys.1, round.ciphertexts.1),
+            };
+            if C::G::generator() * round.opening != ephemeral_key {
+                return Err(Error::InvalidEscrow);
+            }
+
+            let value =
+                ciphertext - pad::<C>(&(auditor_key.key * round.opening), index, bit as u8)?;
+            let expected = match bit {
+                false => round.commitment,
+                true => round.commitment + verifying_key.share,
+            };
+            if C::G::generator() * value != expected {
+                return Err(Error::InvalidEscrow);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recover the escrowed [`IndividualSigningKey`] of the participant with the given
+    /// `verifying_key`, with the Diffie-Hellman private key of the auditor held by the
+    /// given `auditor` provider.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`IndividualSigningKey`], otherwise an
+    /// [`Error::InvalidEscrow`] if no round decrypts to the share matching `verifying_key`.
+    pub fn recover(
+        &self,
+        auditor: &impl DhKeyProvider<C>,
+        verifying_key: &IndividualVerifyingKey<C>,
+    ) -> FrostResult<C, IndividualSigningKey<C>> {
+        if verifying_key.index != self.participant_index {
+            return Err(Error::InvalidEscrow);
+        }
+
+        for (index, round) in self.rounds.iter().enumerate() {
+            let decrypt = |ephemeral_key: &C::G, ciphertext: &Scalar<C>, side: u8| {
+                let shared_key =
+                    auditor.dh_shared_key(&DiffieHellmanPublicKey::new(Element(*ephemeral_key)))?;
+                Ok(*ciphertext - pad::<C>(&shared_key, index, side)?)
+            };
+
+            let mut mask = decrypt(&round.ephemeral_keys.0, &round.ciphertexts.0, 0)?;
+            let mut masked_share = decrypt(&round.ephemeral_keys.1, &round.ciphertexts.1, 1)?;
+            let key = masked_share - mask;
+            mask.zeroize();
+            masked_share.zeroize();
+
+            if C::G::generator() * key == verifying_key.share {
+                return Ok(IndividualSigningKey {
+                    index: self.participant_index,
+                    key,
+                });
+            }
+        }
+
+        Err(Error::InvalidEscrow)
+    }
+
+    /// Serialize this [`EscrowedShare`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`EscrowedShare`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the challenge of the cut-and-choose proof, from all rounds
+    /// excluding their openings.
+    fn challenge(
+        &self,
+        verifying_key: &IndividualVerifyingKey<C>,
+        auditor_key: &DiffieHellmanPublicKey<C>,
+    ) -> FrostResult<C, C::HashOutput> {
+        if C::HashOutput::default().as_ref().len() * 8 < ESCROW_ROUNDS {
+            return Err(Error::Custom(
+                "The hash output is too short to derive the escrow challenge".to_string(),
+            ));
+        }
+
+        let mut transcript = Vec::new();
+        transcript.extend(&self.participant_index.to_le_bytes());
+
+        let mut points = vec![verifying_key.share, auditor_key.key];
+        for round in self.rounds.iter() {
+            points.extend([
+                round.commitment,
+                round.ephemeral_keys.0,
+                round.ephemeral_keys.1,
+            ]);
+        }
+        points_to_affine_batch::<C>(&points)
+            .serialize_compressed(&mut transcript)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        for round in self.rounds.iter() {
+            (round.ciphertexts.0, round.ciphertexts.1)
+                .serialize_compressed(&mut transcript)
+                .map_err(|e| Error::SerializationError(e.into()))?;
+        }
+
+        hash_to_array::<C>(
+            (C::context_string() + "key escrow challenge").as_bytes(),
+            &transcript,
+        )
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for EscrowedShare<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>(self.rounds.iter().flat_map(|round| {
+            [
+                &round.commitment,
+                &round.ephemeral_keys.0,
+                &round.ephemeral_keys.1,
+            ]
+        }))
+    }
+}
+
+/// The bit of the `challenge` selecting the encryption opened in the given `round`.
+fn challenge_bit(challenge: &impl AsRef<[u8]>, round: usize) -> bool {
+    (challenge.as_ref()[round / 8] >> (round % 8)) & 1 == 1
+}
+
+/// Derive the one-time pad of the given `side` of a `round` from its Diffie-Hellman `shared_key`.
+fn pad<C: CipherSuite>(shared_key: &C::G, round: usize, side: u8) -> FrostResult<C, Scalar<C>> {
+    let mut pad_input = Vec::new();
+    shared_key
+        .serialize_compressed(&mut pad_input)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+    pad_input.extend(&(round as u32).to_le_bytes());
+    pad_input.push(side);
+
+    let pad = C::hash_to_field(
+        (C::context_string() + "key escrow pad").as_bytes(),
+        &pad_input,
+    );
+    pad_input.zeroize();
+
+    pad
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keys::DiffieHellmanPrivateKey;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn escrow_and_recover() {
+        let signing_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 2,
+            key: Scalar::<Secp256k1Sha256>::rand(&mut OsRng),
+        };
+        let verifying_key = signing_key.to_public();
+
+        let auditor = DiffieHellmanPrivateKey::<Secp256k1Sha256>::new(OsRng);
+        let auditor_key = auditor.dh_public_key().unwrap();
+
+        let escrowed_share = signing_key.escrow(&auditor_key, OsRng).unwrap();
+        assert!(escrowed_share.verify(&verifying_key, &auditor_key).is_ok());
+        assert_eq!(
+            escrowed_share.recover(&auditor, &verifying_key).unwrap(),
+            signing_key
+        );
+
+        let bytes = escrowed_share.to_bytes().unwrap();
+        assert_eq!(EscrowedShare::from_bytes(&bytes).unwrap(), escrowed_share);
+
+        // The escrow is bound to the auditor and to the share of the participant.
+        let other_auditor = DiffieHellmanPrivateKey::<Secp256k1Sha256>::new(OsRng);
+        let other_auditor_key = other_auditor.dh_public_key().unwrap();
+        assert_eq!(
+            escrowed_share.verify(&verifying_key, &other_auditor_key),
+            Err(Error::InvalidEscrow)
+        );
+        assert_eq!(
+            escrowed_share.recover(&other_auditor, &verifying_key),
+            Err(Error::InvalidEscrow)
+        );
+
+        let mut other_verifying_key = verifying_key.clone();
+        other_verifying_key.share += <Secp256k1Sha256 as CipherSuite>::G::generator();
+        assert_eq!(
+            escrowed_share.verify(&other_verifying_key, &auditor_key),
+            Err(Error::InvalidEscrow)
+        );
+
+        // Tampering with any ciphertext, opened or not, is detected.
+        for side in [0, 1] {
+            let mut tampered = escrowed_share.clone();
+            match side {
+                0 => tampered.rounds[7].ciphertexts.0 += Scalar::<Secp256k1Sha256>::from(1u64),
+                _ => tampered.rounds[7].ciphertexts.1 += Scalar::<Secp256k1Sha256>::from(1u64),
+            }
+            assert_eq!(
+                tampered.verify(&verifying_key, &auditor_key),
+                Err(Error::InvalidEscrow)
+            );
+        }
+
+        let mut truncated = escrowed_share;
+        truncated.rounds.pop();
+        assert_eq!(
+            truncated.verify(&verifying_key, &auditor_key),
+            Err(Error::InvalidEscrow)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! The fingerprint module, rendering short digests of public key material
+//! for human verification ceremonies.
+//!
+//! Once a Distributed Key Generation session completes, operators can read out
+//! the [`Fingerprint`] of their group key or [`PublicKeyPackage`] to each other,
+//! for instance on a call, either as hexadecimal digits or as a list of words.
+//! Matching fingerprints show that all operators derived the same public material,
+//! and that no one tampered with the broadcast channel, e.g. by substituting the
+//! Diffie-Hellman public key of a participant.
+//!
+//! Fingerprints are domain-separated by ciphersuite and by type of key material,
+//! so that the fingerprints of different objects cannot be confused.
+//!
+//! [`PublicKeyPackage`]: crate::keys::PublicKeyPackage
+//! [`Fingerprint`]: crate::fingerprint::Fingerprint
+
+use core::fmt;
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{hash_to_array, String, Vec};
+use crate::FrostResult;
+
+/// The length in bytes of a [`Fingerprint`].
+pub const FINGERPRINT_LENGTH: usize = 8;
+
+/// The words encoding each byte of a [`Fingerprint`], taken as every eighth word of
+/// the BIP-39 English word list, so that no two words share their first four letters.
+const WORDS: [&str; 256] = [
+    "abandon", "absurd", "acoustic", "adapt", "advice", "agree", "alcohol", "alpha", "amount",
+    "animal", "anxiety", "arch", "army", "artist", "asthma", "audit", "avoid", "baby", "bamboo",
+    "basic", "beef", "bench", "bid", "blade", "blossom", "boil", "borrow", "brand", "bright",
+    "brown", "bulk", "business", "cactus", "canal", "capital", "cart", "catch", "celery",
+    "champion", "check", "chimney", "cinnamon", "claw", "climb", "clown", "code", "come",
+    "congress", "copy", "country", "craft", "credit", "cross", "crush", "current", "damage", "day",
+    "decorate", "deliver", "deposit", "despair", "dial", "dignity", "disease", "divorce", "donate",
+    "dragon", "drink", "dune", "eagle", "ecology", "either", "elite", "empower", "energy",
+    "enrich", "equal", "escape", "evoke", "excuse", "exotic", "extra", "faith", "fantasy",
+    "favorite", "fence", "figure", "finish", "fix", "flip", "foam", "force", "foster", "fringe",
+    "fun", "gallery", "gas", "genre", "ginger", "glide", "goat", "govern", "gravity", "grow",
+    "gym", "harbor", "head", "help", "hire", "home", "host", "humor", "hybrid", "illegal",
+    "improve", "indoor", "inject", "insect", "invite", "jacket", "job", "jungle", "key", "kitchen",
+    "lab", "laptop", "lawn", "lecture", "length", "library", "link", "loan", "lottery", "lunar",
+    "maid", "mandate", "marine", "math", "meat", "mention", "metal", "minimum", "mix", "monitor",
+    "mosquito", "much", "must", "narrow", "neglect", "never", "noodle", "novel", "object",
+    "october", "old", "only", "orchard", "other", "own", "pair", "parade", "patient", "pear",
+    "perfect", "piano", "pink", "plastic", "poem", "pool", "poverty", "present", "prison",
+    "project", "public", "puppy", "pyramid", "quote", "rain", "rare", "reason", "reduce", "relax",
+    "render", "require", "retreat", "ribbon", "ring", "roast", "rose", "rug", "safe", "sample",
+    "scale", "scissors", "search", "seek", "series", "shallow", "ship", "shove", "siege", "simple",
+    "skate", "slam", "slot", "snack", "sock", "someone", "source", "speed", "spirit", "spread",
+    "staff", "steak", "stock", "strike", "submit", "suit", "sure", "swallow", "swing", "tackle",
+    "task", "tenant", "theme", "three", "tilt", "toast", "tomato", "topic", "toward", "train",
+    "trend", "trouble", "tube", "twelve", "ugly", "unfair", "unlock", "upper", "useless", "valve",
+    "velvet", "vessel", "village", "vital", "voyage", "warfare", "way", "weekend", "wheel", "wild",
+    "winter", "wonder", "wrap", "yellow",
+];
+
+/// A short digest of public key material, to be compared by humans.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Fingerprint([u8; FINGERPRINT_LENGTH]);
+
+impl Fingerprint {
+    /// Compute the fingerprint of the given serialized key material, under
+    /// the given `label` identifying its type.
+    pub(crate) fn compute<C: CipherSuite>(label: &str, bytes: &[u8]) -> FrostResult<C, Self> {
+        let hash = hash_to_array::<C>((C::context_string() + label).as_bytes(), bytes)?;
+
+        let mut fingerprint = [0u8; FINGERPRINT_LENGTH];
+        fingerprint.copy_from_slice(&hash.as_ref()[..FINGERPRINT_LENGTH]);
+
+        Ok(Self(fingerprint))
+    }
+
+    /// The bytes of this fingerprint.
+    pub fn as_bytes(&self) -> &[u8; FINGERPRINT_LENGTH] {
+        &self.0
+    }
+
+    /// Render this fingerprint as lowercase hexadecimal digits.
+    pub fn to_hex(&self) -> String {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        self.0
+            .iter()
+            .flat_map(|byte| {
+                [
+                    HEX_DIGITS[(byte >> 4) as usize] as char,
+                    HEX_DIGITS[(byte & 0x0f) as usize] as char,
+                ]
+            })
+            .collect()
+    }
+
+    /// Render this fingerprint as a list of words, one per byte.
+    pub fn to_words(&self) -> Vec<&'static str> {
+        self.0.iter().map(|byte| WORDS[*byte as usize]).collect()
+    }
+}
+
+/// Renders the fingerprint as groups of four hexadecimal digits, easier to read out.
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, pair) in self.0.chunks(2).enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            for byte in pair {
+                write!(f, "{byte:02x}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keys::{DiffieHellmanPublicKey, GroupVerifyingKey};
+    use crate::testing::Secp256k1Sha256;
+    use crate::Element;
+
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn fingerprint_rendering() {
+        let words: BTreeSet<&str> = WORDS
+            .iter()
+            .map(|word| &word[..word.len().min(4)])
+            .collect();
+        assert_eq!(words.len(), WORDS.len());
+
+        let fingerprint = Fingerprint([0x00, 0x01, 0x7f, 0x80, 0xab, 0xcd, 0xef, 0xff]);
+        assert_eq!(fingerprint.to_hex(), "00017f80abcdefff");
+        assert_eq!(fingerprint.to_string(), "0001 7f80 abcd efff");
+        assert_eq!(fingerprint.to_words()[0], "abandon");
+        assert_eq!(fingerprint.to_words()[1], "absurd");
+        assert_eq!(fingerprint.to_words().len(), FINGERPRINT_LENGTH);
+
+        // Fingerprints are deterministic, and separated by type of key material.
+        let point = Element::<Secp256k1Sha256>::generator();
+        let group_key = GroupVerifyingKey::<Secp256k1Sha256>::new(point);
+        let dh_public_key = DiffieHellmanPublicKey::<Secp256k1Sha256>::new(point);
+        assert_eq!(
+            group_key.fingerprint().unwrap(),
+            GroupVerifyingKey::<Secp256k1Sha256>::new(point)
+                .fingerprint()
+                .unwrap()
+        );
+        assert_ne!(
+            group_key.fingerprint().unwrap(),
+            dh_public_key.fingerprint().unwrap()
+        );
+        assert_ne!(
+            group_key.fingerprint().unwrap(),
+            GroupVerifyingKey::<Secp256k1Sha256>::new(point + point)
+                .fingerprint()
+                .unwrap()
+        );
+    }
+}
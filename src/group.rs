@@ -0,0 +1,318 @@
+//! The group module defining the [`Scalar`] and [`Element`] wrapper types,
+//! around the scalars and elements of the group of a [`CipherSuite`].
+//!
+//! These types expose the arithmetic, serialization and random-generation
+//! operations needed to integrate ICE-FROST, without requiring integrators to
+//! name the underlying `arkworks` types or import their traits.
+
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{ct_eq_encoded, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Error, FrostResult};
+
+/// A scalar of the prime-order group of a [`CipherSuite`].
+#[derive(Clone, Copy, Debug, Default, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct Scalar<C: CipherSuite>(pub(crate) crate::utils::Scalar<C>);
+
+impl<C: CipherSuite> ConstantTimeEq for Scalar<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_eq_encoded(&self.0, &other.0)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for Scalar<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for Scalar<C> {}
+
+impl<C: CipherSuite> Scalar<C> {
+    /// The additive identity.
+    pub fn zero() -> Self {
+        Self(crate::utils::Scalar::<C>::ZERO)
+    }
+
+    /// The multiplicative identity.
+    pub fn one() -> Self {
+        Self(crate::utils::Scalar::<C>::ONE)
+    }
+
+    /// Sample a uniformly random [`Scalar`].
+    pub fn random(mut rng: impl RngCore + CryptoRng) -> Self {
+        Self(crate::utils::Scalar::<C>::rand(&mut rng))
+    }
+
+    /// Whether this scalar is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// The multiplicative inverse of this scalar, or [`None`] if it is zero.
+    pub fn invert(&self) -> Option<Self> {
+        self.0.inverse().map(Self)
+    }
+
+    /// Wrap a scalar of the underlying `arkworks` field.
+    pub fn from_inner(scalar: crate::utils::Scalar<C>) -> Self {
+        Self(scalar)
+    }
+
+    /// Unwrap this scalar into the underlying `arkworks` field.
+    pub fn into_inner(self) -> crate::utils::Scalar<C> {
+        self.0
+    }
+
+    /// Serialize this [`Scalar`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`Scalar`] from a vector of bytes, rejecting
+    /// non-canonical encodings.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+impl<C: CipherSuite> From<u64> for Scalar<C> {
+    fn from(value: u64) -> Self {
+        Self(crate::utils::Scalar::<C>::from(value))
+    }
+}
+
+impl<C: CipherSuite> Add for Scalar<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl<C: CipherSuite> AddAssign for Scalar<C> {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl<C: CipherSuite> Sub for Scalar<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl<C: CipherSuite> SubAssign for Scalar<C> {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+    }
+}
+
+impl<C: CipherSuite> Mul for Scalar<C> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+}
+
+impl<C: CipherSuite> MulAssign for Scalar<C> {
+    fn mul_assign(&mut self, other: Self) {
+        self.0 *= other.0;
+    }
+}
+
+impl<C: CipherSuite> Neg for Scalar<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl<C: CipherSuite> Sum for Scalar<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+/// An element of the prime-order group of a [`CipherSuite`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Element<C: CipherSuite>(pub(crate) C::G);
+
+impl<C: CipherSuite> Element<C> {
+    /// The identity element of the group.
+    pub fn identity() -> Self {
+        Self(C::G::zero())
+    }
+
+    /// The generator of the group.
+    pub fn generator() -> Self {
+        Self(C::G::generator())
+    }
+
+    /// Sample a uniformly random [`Element`].
+    pub fn random(mut rng: impl RngCore + CryptoRng) -> Self {
+        Self(C::G::rand(&mut rng))
+    }
+
+    /// Whether this element is the identity of the group.
+    pub fn is_identity(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Wrap an element of the underlying `arkworks` group.
+    pub fn from_inner(element: C::G) -> Self {
+        Self(element)
+    }
+
+    /// Unwrap this element into the underlying `arkworks` group.
+    pub fn into_inner(self) -> C::G {
+        self.0
+    }
+
+    /// Serialize this [`Element`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`Element`] from a vector of bytes.
+    ///
+    /// The element is validated according to the [`PointValidationPolicy`](crate::PointValidationPolicy)
+    /// of the [`CipherSuite`], and rejected if it is the identity.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for Element<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.0)
+    }
+}
+
+impl<C: CipherSuite> Add for Element<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl<C: CipherSuite> AddAssign for Element<C> {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl<C: CipherSuite> Sub for Element<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl<C: CipherSuite> SubAssign for Element<C> {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+    }
+}
+
+impl<C: CipherSuite> Neg for Element<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl<C: CipherSuite> Mul<Scalar<C>> for Element<C> {
+    type Output = Self;
+
+    fn mul(self, scalar: Scalar<C>) -> Self {
+        Self(self.0 * scalar.0)
+    }
+}
+
+impl<C: CipherSuite> MulAssign<Scalar<C>> for Element<C> {
+    fn mul_assign(&mut self, scalar: Scalar<C>) {
+        self.0 *= scalar.0;
+    }
+}
+
+impl<C: CipherSuite> Sum for Element<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::identity(), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn scalar_and_element_arithmetic() {
+        let a = Scalar::<Secp256k1Sha256>::random(OsRng);
+        let b = Scalar::<Secp256k1Sha256>::from(7u64);
+
+        assert_eq!(a * a.invert().unwrap(), Scalar::one());
+        assert_eq!(a - a, Scalar::zero());
+        assert_eq!(-a + a, Scalar::zero());
+        assert!(Scalar::<Secp256k1Sha256>::zero().invert().is_none());
+        assert_eq!(
+            [a, b, Scalar::one()].into_iter().sum::<Scalar<_>>(),
+            a + b + Scalar::one()
+        );
+
+        let g = Element::<Secp256k1Sha256>::generator();
+        assert_eq!(g * a + g * b, g * (a + b));
+        assert_eq!((g * a) * b, g * (a * b));
+        assert!((g - g).is_identity());
+        assert_eq!(
+            [g, g, g].into_iter().sum::<Element<_>>(),
+            g * Scalar::from(3u64)
+        );
+
+        assert_eq!(Scalar::from_bytes(&a.to_bytes().unwrap()).unwrap(), a);
+        assert!(Scalar::<Secp256k1Sha256>::from_bytes(&[0xff; 32]).is_err());
+
+        let h = Element::<Secp256k1Sha256>::random(OsRng);
+        assert_eq!(Element::from_bytes(&h.to_bytes().unwrap()).unwrap(), h);
+        assert_eq!(
+            Element::<Secp256k1Sha256>::from_bytes(
+                &Element::<Secp256k1Sha256>::identity().to_bytes().unwrap()
+            ),
+            Err(Error::IdentityPoint)
+        );
+        assert_eq!(Element::from_inner(h.into_inner()), h);
+    }
+}
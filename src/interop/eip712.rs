@@ -0,0 +1,448 @@
+//! The EIP-712 module for signing Ethereum typed structured data
+//! with ICE-FROST.
+//!
+//! The EIP-712 digest of some typed data is computed as
+//! \\( \mathsf{keccak256}(\texttt{0x1901} \Vert \mathsf{domainSeparator} \Vert \mathsf{hashStruct}(\mathsf{message})) \\),
+//! either from its JSON representation with [`typed_data_digest`], or from a prebuilt
+//! domain separator and struct hash with [`eip712_digest`].
+//!
+//! This digest is then mapped to a message hash under a dedicated domain of the
+//! [`CipherSuite`] with [`eip712_message_hash`], to be signed through the pre-hashed
+//! signing path, i.e. with [`SignatureAggregator::new_prehashed`] on the aggregator
+//! side and [`IndividualSigningKey::sign`](crate::keys::IndividualSigningKey::sign)
+//! on the signers side.
+//!
+//! [`SignatureAggregator::new_prehashed`]: crate::sign::SignatureAggregator::new_prehashed
+
+use digest::Digest;
+use serde_json::{Map, Value};
+use sha3::Keccak256;
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{hash_to_array, String, ToString, Vec};
+use crate::{Error, FrostResult};
+
+/// The domain under which EIP-712 digests are mapped to message hashes.
+pub const EIP712_DOMAIN: &str = "eip712";
+
+/// The prefix of EIP-712 encoded typed data.
+const EIP712_PREFIX: [u8; 2] = [0x19, 0x01];
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+fn invalid_typed_data<C: CipherSuite>(reason: &str) -> Error<C> {
+    Error::Custom(reason.to_string())
+}
+
+/// Compute the EIP-712 digest of some typed data, given its prebuilt
+/// `domain_separator` and the `struct_hash` of its message.
+pub fn eip712_digest(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(66);
+    encoded.extend(&EIP712_PREFIX);
+    encoded.extend(domain_separator);
+    encoded.extend(struct_hash);
+
+    keccak256(&encoded)
+}
+
+/// Compute the EIP-712 digest of some typed data, given as a JSON object
+/// with `types`, `primaryType`, `domain` and `message` fields, as specified
+/// for the `eth_signTypedData_v4` JSON-RPC method.
+pub fn typed_data_digest<C: CipherSuite>(typed_data: &str) -> FrostResult<C, [u8; 32]> {
+    let typed_data: Value = serde_json::from_str(typed_data)
+        .map_err(|_| invalid_typed_data::<C>("The typed data is not valid JSON"))?;
+
+    let encoder = TypedDataEncoder {
+        types: typed_data
+            .get("types")
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid_typed_data::<C>("Missing typed data types"))?,
+    };
+    let primary_type = typed_data
+        .get("primaryType")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid_typed_data::<C>("Missing typed data primary type"))?;
+    let domain = typed_data
+        .get("domain")
+        .ok_or_else(|| invalid_typed_data::<C>("Missing typed data domain"))?;
+    let message = typed_data
+        .get("message")
+        .ok_or_else(|| invalid_typed_data::<C>("Missing typed data message"))?;
+
+    let domain_separator = encoder.hash_struct::<C>("EIP712Domain", domain)?;
+    let struct_hash = encoder.hash_struct::<C>(primary_type, message)?;
+
+    Ok(eip712_digest(&domain_separator, &struct_hash))
+}
+
+/// Map an EIP-712 `digest` to the message hash to be signed by the participants,
+/// under the [`EIP712_DOMAIN`] of this [`CipherSuite`].
+pub fn eip712_message_hash<C: CipherSuite>(digest: &[u8; 32]) -> FrostResult<C, C::HashOutput> {
+    hash_to_array::<C>((C::context_string() + EIP712_DOMAIN).as_bytes(), digest)
+}
+
+/// An encoder of structured data according to a set of EIP-712 struct types.
+struct TypedDataEncoder<'a> {
+    types: &'a Map<String, Value>,
+}
+
+impl<'a> TypedDataEncoder<'a> {
+    /// Retrieve the `(name, type)` pairs of the fields of the struct type `type_name`.
+    fn fields<C: CipherSuite>(&self, type_name: &str) -> FrostResult<C, Vec<(&'a str, &'a str)>> {
+        self.types
+            .get(type_name)
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid_typed_data::<C>("Undefined struct type"))?
+            .iter()
+            .map(|field| {
+                match (
+                    field.get("name").and_then(Value::as_str),
+                    field.get("type").and_then(Value::as_str),
+                ) {
+                    (Some(name), Some(field_type)) => Ok((name, field_type)),
+                    _ => Err(invalid_typed_data::<C>("Invalid struct field")),
+                }
+            })
+            .collect()
+    }
+
+    /// Collect the struct types referenced by `field_type`, including itself.
+    fn dependencies<C: CipherSuite>(
+        &self,
+        field_type: &'a str,
+        dependencies: &mut Vec<&'a str>,
+    ) -> FrostResult<C, ()> {
+        let base_type = field_type.split('[').next().unwrap_or(field_type);
+        if dependencies.contains(&base_type) || !self.types.contains_key(base_type) {
+            return Ok(());
+        }
+
+        dependencies.push(base_type);
+        for (_, field_type) in self.fields::<C>(base_type)? {
+            self.dependencies::<C>(field_type, dependencies)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encode the struct type `type_name`, followed by the struct types
+    /// it references sorted by name.
+    fn encode_type<C: CipherSuite>(&self, type_name: &'a str) -> FrostResult<C, String> {
+        let mut dependencies = Vec::new();
+        self.dependencies::<C>(type_name, &mut dependencies)?;
+        if dependencies.first() != Some(&type_name) {
+            return Err(invalid_typed_data::<C>("Undefined struct type"));
+        }
+        dependencies[1..].sort_unstable();
+
+        let mut encoded = String::new();
+        for dependency in dependencies {
+            encoded.push_str(dependency);
+            encoded.push('(');
+            for (i, (name, field_type)) in self.fields::<C>(dependency)?.iter().enumerate() {
+                if i > 0 {
+                    encoded.push(',');
+                }
+                encoded.push_str(field_type);
+                encoded.push(' ');
+                encoded.push_str(name);
+            }
+            encoded.push(')');
+        }
+
+        Ok(encoded)
+    }
+
+    /// Compute the `hashStruct` of a `value` of the struct type `type_name`.
+    fn hash_struct<C: CipherSuite>(
+        &self,
+        type_name: &'a str,
+        value: &Value,
+    ) -> FrostResult<C, [u8; 32]> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid_typed_data::<C>("Struct value is not an object"))?;
+
+        let fields = self.fields::<C>(type_name)?;
+        let mut encoded = Vec::with_capacity(32 * (fields.len() + 1));
+        encoded.extend(keccak256(self.encode_type::<C>(type_name)?.as_bytes()));
+        for (name, field_type) in fields {
+            let field = object
+                .get(name)
+                .ok_or_else(|| invalid_typed_data::<C>("Missing struct field"))?;
+            encoded.extend(self.encode_value::<C>(field_type, field)?);
+        }
+
+        Ok(keccak256(&encoded))
+    }
+
+    /// Encode a `value` of type `field_type` into a 32-byte word.
+    fn encode_value<C: CipherSuite>(
+        &self,
+        field_type: &'a str,
+        value: &Value,
+    ) -> FrostResult<C, [u8; 32]> {
+        if let Some(array_type) = field_type.strip_suffix(']') {
+            let (element_type, length) = array_type
+                .rsplit_once('[')
+                .ok_or_else(|| invalid_typed_data::<C>("Invalid array type"))?;
+            let elements = value
+                .as_array()
+                .ok_or_else(|| invalid_typed_data::<C>("Array value is not an array"))?;
+            if !length.is_empty() && length.parse::<usize>() != Ok(elements.len()) {
+                return Err(invalid_typed_data::<C>("Invalid array length"));
+            }
+
+            let mut encoded = Vec::with_capacity(32 * elements.len());
+            for element in elements {
+                encoded.extend(self.encode_value::<C>(element_type, element)?);
+            }
+
+            return Ok(keccak256(&encoded));
+        }
+
+        if self.types.contains_key(field_type) {
+            return self.hash_struct::<C>(field_type, value);
+        }
+
+        let mut word = [0u8; 32];
+        match field_type {
+            "string" => {
+                let string = value
+                    .as_str()
+                    .ok_or_else(|| invalid_typed_data::<C>("Invalid string value"))?;
+                word = keccak256(string.as_bytes());
+            }
+            "bytes" => word = keccak256(&decode_hex::<C>(value)?),
+            "bool" => {
+                let boolean = value
+                    .as_bool()
+                    .ok_or_else(|| invalid_typed_data::<C>("Invalid bool value"))?;
+                word[31] = boolean as u8;
+            }
+            "address" => {
+                let address = decode_hex::<C>(value)?;
+                if address.len() != 20 {
+                    return Err(invalid_typed_data::<C>("Invalid address value"));
+                }
+                word[12..].copy_from_slice(&address);
+            }
+            _ => {
+                if let Some(size) = field_type.strip_prefix("bytes") {
+                    let bytes = decode_hex::<C>(value)?;
+                    match size.parse::<usize>() {
+                        Ok(size) if (1..=32).contains(&size) && bytes.len() == size => {
+                            word[..size].copy_from_slice(&bytes)
+                        }
+                        _ => return Err(invalid_typed_data::<C>("Invalid fixed bytes value")),
+                    }
+                } else if let Some(bits) = field_type.strip_prefix("uint") {
+                    word = encode_integer::<C>(value, bits, false)?;
+                } else if let Some(bits) = field_type.strip_prefix("int") {
+                    word = encode_integer::<C>(value, bits, true)?;
+                } else {
+                    return Err(invalid_typed_data::<C>("Unsupported field type"));
+                }
+            }
+        }
+
+        Ok(word)
+    }
+}
+
+/// Decode a `0x`-prefixed hexadecimal string.
+fn decode_hex<C: CipherSuite>(value: &Value) -> FrostResult<C, Vec<u8>> {
+    let digits = value
+        .as_str()
+        .and_then(|s| s.strip_prefix("0x"))
+        .filter(|digits| digits.len() % 2 == 0)
+        .ok_or_else(|| invalid_typed_data::<C>("Invalid hexadecimal value"))?;
+
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            core::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| invalid_typed_data::<C>("Invalid hexadecimal value"))
+        })
+        .collect()
+}
+
+/// Encode an integer `value` of the given `bits` size as a big-endian
+/// two's complement 32-byte word.
+///
+/// The value may be a JSON number, or a decimal or `0x`-prefixed
+/// hexadecimal string, optionally negative for signed integers.
+fn encode_integer<C: CipherSuite>(
+    value: &Value,
+    bits: &str,
+    signed: bool,
+) -> FrostResult<C, [u8; 32]> {
+    let invalid = || invalid_typed_data::<C>("Invalid integer value");
+
+    let bits = match bits.parse::<usize>() {
+        Ok(bits) if bits % 8 == 0 && (8..=256).contains(&bits) => bits,
+        _ => return Err(invalid_typed_data::<C>("Unsupported field type")),
+    };
+
+    let mut magnitude = [0u8; 32];
+    let negative = match value {
+        Value::Number(number) => {
+            let (negative, absolute) = match (number.as_u64(), number.as_i64()) {
+                (Some(absolute), _) => (false, absolute),
+                (None, Some(signed)) => (true, signed.unsigned_abs()),
+                _ => return Err(invalid()),
+            };
+            magnitude[24..].copy_from_slice(&absolute.to_be_bytes());
+            negative
+        }
+        Value::String(string) => {
+            let (negative, digits) = match string.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, string.as_str()),
+            };
+            let (radix, digits) = match digits.strip_prefix("0x") {
+                Some(digits) => (16, digits),
+                None => (10, digits),
+            };
+            if digits.is_empty() {
+                return Err(invalid());
+            }
+
+            for digit in digits.chars() {
+                let mut carry = digit.to_digit(radix).ok_or_else(invalid)?;
+                for byte in magnitude.iter_mut().rev() {
+                    let product = *byte as u32 * radix + carry;
+                    *byte = product as u8;
+                    carry = product >> 8;
+                }
+                if carry != 0 {
+                    return Err(invalid());
+                }
+            }
+            negative
+        }
+        _ => return Err(invalid()),
+    };
+
+    let is_zero = magnitude.iter().all(|byte| *byte == 0);
+    if negative && !signed && !is_zero {
+        return Err(invalid());
+    }
+
+    let mut word = magnitude;
+    if negative {
+        // Two's complement negation.
+        let mut carry = 1u16;
+        for byte in word.iter_mut().rev() {
+            let sum = (!*byte) as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+    }
+
+    // All bits above the value, including the sign bit for signed
+    // integers, must be equal to the expected sign.
+    let sign = negative && !is_zero;
+    let first_high_bit = if signed { bits - 1 } else { bits };
+    let fits =
+        (first_high_bit..256).all(|bit| ((word[31 - bit / 8] >> (bit % 8)) & 1 == 1) == sign);
+
+    match fits {
+        true => Ok(word),
+        false => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    fn from_hex(hex: &str) -> [u8; 32] {
+        let bytes = decode_hex::<Secp256k1Sha256>(&Value::String(hex.to_string())).unwrap();
+        bytes.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_eip712_mail_example() {
+        let digest = typed_data_digest::<Secp256k1Sha256>(MAIL).unwrap();
+        assert_eq!(
+            digest,
+            from_hex("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+
+        let domain_separator =
+            from_hex("0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+        let struct_hash =
+            from_hex("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+        assert_eq!(eip712_digest(&domain_separator, &struct_hash), digest);
+
+        // The message hash is domain-separated from the hash of regular messages.
+        assert_ne!(
+            eip712_message_hash::<Secp256k1Sha256>(&digest).unwrap(),
+            Secp256k1Sha256::h4(&digest).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eip712_integers() {
+        let encode = |value: Value, field_type: &str| {
+            let (bits, signed) = match field_type.strip_prefix("uint") {
+                Some(bits) => (bits, false),
+                None => (&field_type[3..], true),
+            };
+            encode_integer::<Secp256k1Sha256>(&value, bits, signed)
+        };
+
+        assert_eq!(encode(Value::from(-1), "int8").unwrap(), [0xff; 32]);
+        assert_eq!(encode(Value::from("-128"), "int8").unwrap()[31], 0x80);
+        assert_eq!(encode(Value::from("0xff"), "uint8").unwrap()[31], 0xff);
+        assert_eq!(
+            encode(Value::from(u64::MAX.to_string()), "uint256").unwrap()[24..],
+            [0xff; 8]
+        );
+
+        assert!(encode(Value::from(128), "int8").is_err());
+        assert!(encode(Value::from("-129"), "int8").is_err());
+        assert!(encode(Value::from(256), "uint8").is_err());
+        assert!(encode(Value::from(-1), "uint256").is_err());
+        assert!(encode(Value::from(1.5), "uint256").is_err());
+    }
+}
@@ -0,0 +1,6 @@
+#[cfg(feature = "eip712")]
+pub mod eip712;
+#[cfg(feature = "reddsa")]
+pub mod reddsa;
+pub mod ssh;
+pub mod tendermint;
@@ -0,0 +1,287 @@
+//! The RedDSA module for producing Zcash spend authorization signatures, i.e.
+//! RedJubjub and RedPallas signatures, with an ICE-FROST group.
+//!
+//! RedDSA signatures are Schnorr signatures whose challenge is the BLAKE2b-512 digest,
+//! with a personalization specific to each [`RedDsaVariant`], of the encodings of the
+//! group commitment, of the verification key and of the message, reduced modulo the
+//! order of the group. A ciphersuite over Jubjub or Pallas produces them by overriding
+//! [`CipherSuite::challenge`] with [`challenge`], and by signing the message itself,
+//! such as a transaction sighash, instead of its [`CipherSuite::h4`] hash, with
+//! [`SignatureAggregator::new_prehashed`]. Its group must moreover have the spend authorization base point of the Zcash protocol as
+//! generator, e.g. through a curve configuration overriding the one of `arkworks`.
+//!
+//! As required for spend authorizations, the keys of the group are randomized with
+//! a [`KeyRandomizer`]: signers sign with
+//! [`IndividualSigningKey::sign_with_randomized_key`], the aggregator checks their
+//! partial signatures against [`PublicKeyPackage::randomize`], and the resulting
+//! [`ThresholdSignature`] verifies under [`GroupVerifyingKey::randomize`], which is
+//! exported with [`verification_key_bytes`] along with [`signature_bytes`].
+//!
+//! [`IndividualSigningKey::sign_with_randomized_key`]: crate::keys::IndividualSigningKey::sign_with_randomized_key
+//! [`PublicKeyPackage::randomize`]: crate::keys::PublicKeyPackage::randomize
+//! [`SignatureAggregator::new_prehashed`]: crate::sign::SignatureAggregator::new_prehashed
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::GroupVerifyingKey;
+#[cfg(doc)]
+use crate::keys::KeyRandomizer;
+use crate::sign::ThresholdSignature;
+use crate::spki::sign_bit_encoding;
+use crate::utils::{Scalar, ToString};
+use crate::{Error, FrostResult};
+
+/// The length of the encodings of RedDSA verification keys.
+pub const VERIFICATION_KEY_LENGTH: usize = 32;
+
+/// The length of the encodings of RedDSA signatures.
+pub const SIGNATURE_LENGTH: usize = 64;
+
+/// The RedDSA instantiations of the Zcash protocol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedDsaVariant {
+    /// RedJubjub, over the twisted Edwards curve Jubjub, whose points are encoded as
+    /// their \\( v \\) coordinate along with the parity of their \\( u \\) coordinate.
+    RedJubjub,
+    /// RedPallas, over the short Weierstrass curve Pallas, whose points are encoded as
+    /// their \\( x \\) coordinate along with the parity of their \\( y \\) coordinate.
+    RedPallas,
+}
+
+impl RedDsaVariant {
+    /// The BLAKE2b personalization of the challenges of this [`RedDsaVariant`].
+    pub const fn personalization(&self) -> &'static [u8; 16] {
+        match self {
+            RedDsaVariant::RedJubjub => b"Zcash_RedJubjubH",
+            RedDsaVariant::RedPallas => b"Zcash_RedPallasH",
+        }
+    }
+}
+
+/// Compute the RedDSA challenge of the given `variant` for a signature with the given
+/// `group_commitment`, under the given `group_key`, on the given `message`.
+///
+/// Ciphersuites producing RedDSA signatures return it from [`CipherSuite::challenge`].
+pub fn challenge<C: CipherSuite>(
+    variant: RedDsaVariant,
+    group_commitment: &C::G,
+    group_key: &C::G,
+    message: &[u8],
+) -> FrostResult<C, Scalar<C>> {
+    let digest = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(variant.personalization())
+        .to_state()
+        .update(&encode_point::<C>(variant, group_commitment)?)
+        .update(&encode_point::<C>(variant, group_key)?)
+        .update(message)
+        .finalize();
+
+    Ok(Scalar::<C>::from_le_bytes_mod_order(digest.as_bytes()))
+}
+
+/// Encode the given `group_key`, or its randomization, as a RedDSA verification key
+/// of the given `variant`.
+pub fn verification_key_bytes<C: CipherSuite>(
+    variant: RedDsaVariant,
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, [u8; VERIFICATION_KEY_LENGTH]> {
+    encode_point::<C>(variant, &group_key.key)
+}
+
+/// Encode the given `signature` as a RedDSA signature of the given `variant`, i.e. as
+/// the encoding of its group commitment followed by the little-endian encoding of its
+/// response.
+pub fn signature_bytes<C: CipherSuite>(
+    variant: RedDsaVariant,
+    signature: &ThresholdSignature<C>,
+) -> FrostResult<C, [u8; SIGNATURE_LENGTH]> {
+    let response = signature.z.into_bigint().to_bytes_le();
+    if response.len() < 32 || response[32..].iter().any(|byte| *byte != 0) {
+        return Err(Error::Custom(
+            "RedDSA signatures require 32-byte scalars".to_string(),
+        ));
+    }
+
+    let mut bytes = [0u8; SIGNATURE_LENGTH];
+    bytes[..32].copy_from_slice(&encode_point::<C>(variant, &signature.group_commitment)?);
+    bytes[32..].copy_from_slice(&response[..32]);
+
+    Ok(bytes)
+}
+
+/// The RedDSA encoding of the given `point`.
+fn encode_point<C: CipherSuite>(
+    variant: RedDsaVariant,
+    point: &C::G,
+) -> FrostResult<C, [u8; VERIFICATION_KEY_LENGTH]> {
+    let affine = point.into_affine();
+    let (x, y) = affine.xy().ok_or(Error::InvalidPoint)?;
+    let encoding = match variant {
+        RedDsaVariant::RedJubjub => sign_bit_encoding::<C>(y, x)?,
+        RedDsaVariant::RedPallas => sign_bit_encoding::<C>(x, y)?,
+    };
+
+    encoding.try_into().map_err(|_| Error::InvalidPoint)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keys::{KeyRandomizer, PublicKeyPackage};
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::conformance::{generate_keys, run_all};
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::{String, Vec};
+    use crate::Aes256Gcm;
+
+    use ark_ec::Group;
+    use rand::rngs::OsRng;
+    use zeroize::Zeroize;
+
+    macro_rules! reddsa_ciphersuite {
+        ($name:ident, $group:ty, $variant:expr) => {
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Zeroize)]
+            struct $name;
+
+            impl CipherSuite for $name {
+                type G = $group;
+
+                type HashOutput = [u8; 32];
+
+                type InnerHasher = sha2::Sha256;
+
+                type ShareCipher = Aes256Gcm;
+
+                fn context_string() -> String {
+                    "ICE-FROST_BANDERSNATCH_REDDSA".into()
+                }
+
+                fn challenge(
+                    group_commitment: &Self::G,
+                    group_key: &Self::G,
+                    message_hash: &[u8],
+                ) -> FrostResult<Self, Scalar<Self>> {
+                    challenge::<Self>($variant, group_commitment, group_key, message_hash)
+                }
+            }
+        };
+    }
+
+    // Bandersnatch is defined over the base field of Jubjub, in both curve models.
+    reddsa_ciphersuite!(
+        EdwardsRedDsa,
+        ark_ed_on_bls12_381_bandersnatch::EdwardsProjective,
+        RedDsaVariant::RedJubjub
+    );
+    reddsa_ciphersuite!(
+        WeierstrassRedDsa,
+        ark_ed_on_bls12_381_bandersnatch::SWProjective,
+        RedDsaVariant::RedPallas
+    );
+
+    #[test]
+    fn point_encodings() {
+        let point = ark_ed_on_bls12_381_bandersnatch::EdwardsProjective::generator();
+        let encoding = encode_point::<EdwardsRedDsa>(RedDsaVariant::RedJubjub, &point).unwrap();
+        let mut negated = encode_point::<EdwardsRedDsa>(RedDsaVariant::RedJubjub, &-point).unwrap();
+        negated[31] ^= 0x80;
+        assert_eq!(encoding, negated);
+
+        let point = ark_ed_on_bls12_381_bandersnatch::SWProjective::generator();
+        let encoding = encode_point::<WeierstrassRedDsa>(RedDsaVariant::RedPallas, &point).unwrap();
+        let mut negated =
+            encode_point::<WeierstrassRedDsa>(RedDsaVariant::RedPallas, &-point).unwrap();
+        negated[31] ^= 0x80;
+        assert_eq!(encoding, negated);
+
+        // The coordinates of secp256k1 leave no room for a sign bit.
+        assert!(encode_point::<Secp256k1Sha256>(
+            RedDsaVariant::RedPallas,
+            &ark_secp256k1::Projective::generator()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn randomized_reddsa_signing() {
+        assert!(run_all::<EdwardsRedDsa>().is_ok());
+        assert!(run_all::<WeierstrassRedDsa>().is_ok());
+
+        let params = ThresholdParameters::<EdwardsRedDsa>::new(3, 2).unwrap();
+        let (group_key, signing_keys) = generate_keys(&params).unwrap();
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+
+        let randomizer = KeyRandomizer::new(OsRng);
+        assert_eq!(
+            KeyRandomizer::from_bytes(&randomizer.to_bytes().unwrap()).unwrap(),
+            randomizer
+        );
+        let randomized_package = package.randomize(&randomizer);
+        let randomized_key = *randomized_package.group_key();
+        assert_ne!(randomized_key, group_key);
+        assert!(randomized_package.verify_consistency().is_ok());
+
+        // A transaction sighash is signed as is.
+        let sighash = [7u8; 32];
+        let mut aggregator =
+            SignatureAggregator::new_prehashed(params, randomized_key, &sighash[..], None).unwrap();
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(
+                    sk.index,
+                    public_comshares.commitments[0],
+                    randomized_package.verifying_key(sk.index).unwrap().clone(),
+                )
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign_with_randomized_key(
+                    &randomizer,
+                    &sighash,
+                    &group_key,
+                    secret_comshare,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&randomized_key, &sighash).is_ok());
+        assert!(signature.verify(&group_key, &sighash).is_err());
+
+        // Check the RedDSA verification equation from the encodings.
+        let key_bytes = verification_key_bytes(RedDsaVariant::RedJubjub, &randomized_key).unwrap();
+        let encoded_signature = signature_bytes(RedDsaVariant::RedJubjub, &signature).unwrap();
+        let digest = blake2b_simd::Params::new()
+            .hash_length(64)
+            .personal(b"Zcash_RedJubjubH")
+            .to_state()
+            .update(&encoded_signature[..32])
+            .update(&key_bytes)
+            .update(&sighash)
+            .finalize();
+        let c = Scalar::<EdwardsRedDsa>::from_le_bytes_mod_order(digest.as_bytes());
+        let s = Scalar::<EdwardsRedDsa>::from_le_bytes_mod_order(&encoded_signature[32..]);
+        assert_eq!(
+            ark_ed_on_bls12_381_bandersnatch::EdwardsProjective::generator() * s,
+            signature.group_commitment + randomized_key.key * c
+        );
+    }
+}
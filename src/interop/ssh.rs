@@ -0,0 +1,471 @@
+//! The SSH module for operating an ICE-FROST group as a threshold OpenSSH
+//! certificate authority.
+//!
+//! The [`GroupVerifyingKey`] of a group over edwards25519, i.e. whose ciphersuite has
+//! [`SpkiAlgorithm::Ed25519`] as [`CipherSuite::SPKI_ALGORITHM`], is exported as an
+//! `ssh-ed25519` public key with [`openssh_public_key`], to be listed as a
+//! `TrustedUserCAKeys` or `@cert-authority` entry. An [`SshCertificate`] of a user or
+//! host key is then built as specified by OpenSSH's
+//! [PROTOCOL.certkeys](https://cvsweb.openbsd.org/src/usr.bin/ssh/PROTOCOL.certkeys),
+//! its [`SshCertificate::signing_payload`] is signed by the group as the message of a
+//! signing session, and the resulting [`ThresholdSignature`] is embedded into the
+//! certificate with [`SshCertificate::to_openssh`].
+//!
+//! OpenSSH verifies `ssh-ed25519` signatures as
+//! [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.7) signatures over the
+//! signing payload itself. Certificates are hence only accepted by OpenSSH if the
+//! ciphersuite of the group computes its challenges over the payload as Ed25519 does;
+//! this module only takes care of the SSH wire encodings.
+
+use ark_serialize::CanonicalSerialize;
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::GroupVerifyingKey;
+use crate::sign::ThresholdSignature;
+use crate::spki::{encode_ed25519_point, SpkiAlgorithm};
+use crate::utils::{vec, String, ToString, Vec};
+use crate::{Error, FrostResult};
+
+/// The SSH name of Ed25519 public keys and signatures.
+pub const SSH_ED25519: &str = "ssh-ed25519";
+
+/// The suffix of the SSH names of certificates, appended to the name of their key type.
+pub const SSH_CERTIFICATE_SUFFIX: &str = "-cert-v01@openssh.com";
+
+/// The alphabet of the standard base64 encoding of RFC 4648.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The type of an [`SshCertificate`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SshCertificateType {
+    /// A certificate of a user key, authenticating the user to hosts.
+    User,
+    /// A certificate of a host key, authenticating the host to users.
+    Host,
+}
+
+impl SshCertificateType {
+    /// The SSH encoding of this certificate type.
+    pub const fn to_u32(self) -> u32 {
+        match self {
+            SshCertificateType::User => 1,
+            SshCertificateType::Host => 2,
+        }
+    }
+}
+
+/// An OpenSSH certificate of a user or host key, to be signed by the group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SshCertificate {
+    /// The SSH wire encoding of the certified public key.
+    pub(crate) subject_public_key: Vec<u8>,
+    /// The random nonce of the certificate.
+    pub(crate) nonce: Vec<u8>,
+    /// The serial number of the certificate.
+    pub(crate) serial: u64,
+    /// The type of the certificate.
+    pub(crate) certificate_type: SshCertificateType,
+    /// The identifier of the certificate, logged by hosts.
+    pub(crate) key_id: String,
+    /// The user or host names the certificate is valid for, or all of them if empty.
+    pub(crate) valid_principals: Vec<String>,
+    /// The start of the validity period, in seconds since the UNIX epoch.
+    pub(crate) valid_after: u64,
+    /// The end of the validity period, in seconds since the UNIX epoch.
+    pub(crate) valid_before: u64,
+    /// The critical options of the certificate, by name.
+    pub(crate) critical_options: Vec<(String, String)>,
+    /// The extensions of the certificate, by name.
+    pub(crate) extensions: Vec<(String, String)>,
+}
+
+impl SshCertificate {
+    /// Start a certificate of the given `certificate_type` with identifier `key_id`,
+    /// for the public key whose SSH wire encoding is `subject_public_key`.
+    ///
+    /// The certificate has a fresh random nonce, serial number 0, no principal
+    /// restriction, an unbounded validity period and neither critical options nor
+    /// extensions, which can all be changed with the methods of [`SshCertificate`].
+    pub fn new(
+        subject_public_key: &[u8],
+        certificate_type: SshCertificateType,
+        key_id: &str,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let mut nonce = vec![0u8; 32];
+        csprng.fill_bytes(&mut nonce);
+
+        Self {
+            subject_public_key: subject_public_key.to_vec(),
+            nonce,
+            serial: 0,
+            certificate_type,
+            key_id: key_id.to_string(),
+            valid_principals: Vec::new(),
+            valid_after: 0,
+            valid_before: u64::MAX,
+            critical_options: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Set the serial number of this certificate.
+    pub fn with_serial(mut self, serial: u64) -> Self {
+        self.serial = serial;
+        self
+    }
+
+    /// Restrict this certificate to the given user or host names.
+    pub fn with_principals(mut self, principals: &[&str]) -> Self {
+        self.valid_principals = principals.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Restrict this certificate to the period from `valid_after` to `valid_before`,
+    /// in seconds since the UNIX epoch.
+    pub fn with_validity(mut self, valid_after: u64, valid_before: u64) -> Self {
+        self.valid_after = valid_after;
+        self.valid_before = valid_before;
+        self
+    }
+
+    /// Add a critical option, such as `force-command`, to this certificate.
+    pub fn with_critical_option(mut self, name: &str, value: &str) -> Self {
+        self.critical_options
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add an extension, such as `permit-pty` with an empty value, to this certificate.
+    pub fn with_extension(mut self, name: &str, value: &str) -> Self {
+        self.extensions.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Compute the payload of this certificate to be signed by the group with key
+    /// `ca_key`, i.e. its encoding up to and including the signature key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the payload, otherwise an [`Error`] if
+    /// the group key is not an Ed25519 key or if the certified public key is malformed.
+    pub fn signing_payload<C: CipherSuite>(
+        &self,
+        ca_key: &GroupVerifyingKey<C>,
+    ) -> FrostResult<C, Vec<u8>> {
+        self.encode_payload(&ssh_public_key_blob(ca_key)?)
+    }
+
+    /// Encode this certificate, signed by the group with key `ca_key`, in the SSH
+    /// wire format.
+    pub fn to_bytes<C: CipherSuite>(
+        &self,
+        ca_key: &GroupVerifyingKey<C>,
+        signature: &ThresholdSignature<C>,
+    ) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = self.signing_payload(ca_key)?;
+        write_string(&mut bytes, &ssh_signature_blob(signature)?);
+
+        Ok(bytes)
+    }
+
+    /// Encode this certificate, signed by the group with key `ca_key`, as a line of
+    /// an OpenSSH `-cert.pub` file ending with the given `comment`.
+    pub fn to_openssh<C: CipherSuite>(
+        &self,
+        ca_key: &GroupVerifyingKey<C>,
+        signature: &ThresholdSignature<C>,
+        comment: &str,
+    ) -> FrostResult<C, String> {
+        let bytes = self.to_bytes(ca_key, signature)?;
+        let (key_type, _) = read_string::<C>(&bytes)?;
+
+        Ok(openssh_line(key_type, &bytes, comment))
+    }
+
+    /// Encode the payload of this certificate, given the SSH wire encoding of the
+    /// public key of its certificate authority.
+    fn encode_payload<C: CipherSuite>(&self, ca_public_key: &[u8]) -> FrostResult<C, Vec<u8>> {
+        let (key_type, key_fields) = read_string::<C>(&self.subject_public_key)?;
+
+        let mut bytes = Vec::new();
+        write_string(
+            &mut bytes,
+            &[key_type, SSH_CERTIFICATE_SUFFIX.as_bytes()].concat(),
+        );
+        write_string(&mut bytes, &self.nonce);
+        bytes.extend_from_slice(key_fields);
+        bytes.extend_from_slice(&self.serial.to_be_bytes());
+        bytes.extend_from_slice(&self.certificate_type.to_u32().to_be_bytes());
+        write_string(&mut bytes, self.key_id.as_bytes());
+
+        let mut principals = Vec::new();
+        for principal in self.valid_principals.iter() {
+            write_string(&mut principals, principal.as_bytes());
+        }
+        write_string(&mut bytes, &principals);
+
+        bytes.extend_from_slice(&self.valid_after.to_be_bytes());
+        bytes.extend_from_slice(&self.valid_before.to_be_bytes());
+        write_string(&mut bytes, &encode_options(&self.critical_options));
+        write_string(&mut bytes, &encode_options(&self.extensions));
+        // The reserved field.
+        write_string(&mut bytes, &[]);
+        write_string(&mut bytes, ca_public_key);
+
+        Ok(bytes)
+    }
+}
+
+/// Encode the given `group_key` of an Ed25519 group as an `ssh-ed25519` public key
+/// in the SSH wire format.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is the encoding, otherwise an [`Error`] if the
+/// ciphersuite of the group key is not over edwards25519.
+pub fn ssh_public_key_blob<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, Vec<u8>> {
+    check_ed25519::<C>()?;
+
+    Ok(ed25519_key_blob(&encode_ed25519_point::<C>(
+        &group_key.key,
+    )?))
+}
+
+/// Encode the given `group_key` of an Ed25519 group as a line of an OpenSSH public
+/// key file, such as `authorized_keys`, ending with the given `comment`.
+pub fn openssh_public_key<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+    comment: &str,
+) -> FrostResult<C, String> {
+    Ok(openssh_line(
+        SSH_ED25519.as_bytes(),
+        &ssh_public_key_blob(group_key)?,
+        comment,
+    ))
+}
+
+/// Encode the given `signature` of an Ed25519 group as an `ssh-ed25519` signature
+/// in the SSH wire format.
+pub fn ssh_signature_blob<C: CipherSuite>(
+    signature: &ThresholdSignature<C>,
+) -> FrostResult<C, Vec<u8>> {
+    check_ed25519::<C>()?;
+
+    let mut raw_signature = encode_ed25519_point::<C>(&signature.group_commitment)?;
+    signature
+        .z
+        .serialize_compressed(&mut raw_signature)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+
+    let mut bytes = Vec::new();
+    write_string(&mut bytes, SSH_ED25519.as_bytes());
+    write_string(&mut bytes, &raw_signature);
+
+    Ok(bytes)
+}
+
+/// Check that the ciphersuite `C` is over edwards25519.
+fn check_ed25519<C: CipherSuite>() -> FrostResult<C, ()> {
+    match C::SPKI_ALGORITHM {
+        Some(SpkiAlgorithm::Ed25519) => Ok(()),
+        _ => Err(Error::Custom(
+            "SSH keys and signatures require an Ed25519 ciphersuite".to_string(),
+        )),
+    }
+}
+
+/// The SSH wire encoding of the `ssh-ed25519` public key with the RFC 8032
+/// encoding `public_key`.
+fn ed25519_key_blob(public_key: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_string(&mut bytes, SSH_ED25519.as_bytes());
+    write_string(&mut bytes, public_key);
+
+    bytes
+}
+
+/// Encode the given options, sorted by name as required by OpenSSH, each with its
+/// value wrapped in a string, or with empty data for flags without value.
+fn encode_options(options: &[(String, String)]) -> Vec<u8> {
+    let mut options: Vec<&(String, String)> = options.iter().collect();
+    options.sort();
+
+    let mut bytes = Vec::new();
+    for (name, value) in options {
+        write_string(&mut bytes, name.as_bytes());
+        let mut data = Vec::new();
+        if !value.is_empty() {
+            write_string(&mut data, value.as_bytes());
+        }
+        write_string(&mut bytes, &data);
+    }
+
+    bytes
+}
+
+/// Append the SSH `string` encoding of `content` to `bytes`.
+fn write_string(bytes: &mut Vec<u8>, content: &[u8]) {
+    bytes.extend_from_slice(&(content.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(content);
+}
+
+/// Read an SSH `string` from `bytes`, returning its content and the remaining bytes.
+fn read_string<C: CipherSuite>(bytes: &[u8]) -> FrostResult<C, (&[u8], &[u8])> {
+    let malformed = || Error::Custom("Malformed SSH string".to_string());
+
+    if bytes.len() < 4 {
+        return Err(malformed());
+    }
+    let (length, rest) = bytes.split_at(4);
+    let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize;
+
+    match length <= rest.len() {
+        true => Ok(rest.split_at(length)),
+        false => Err(malformed()),
+    }
+}
+
+/// Format a line of an OpenSSH key file, with the given `key_type`, encoded `key`
+/// and `comment`.
+fn openssh_line(key_type: &[u8], key: &[u8], comment: &str) -> String {
+    let mut line = String::from_utf8_lossy(key_type).to_string();
+    line.push(' ');
+    line.push_str(&base64(key));
+    if !comment.is_empty() {
+        line.push(' ');
+        line.push_str(comment);
+    }
+
+    line
+}
+
+/// The padded standard base64 encoding of `bytes`, as specified in RFC 4648.
+fn base64(bytes: &[u8]) -> String {
+    let mut encoding = String::with_capacity(4 * bytes.chunks(3).len());
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let group = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => {
+                    encoding.push(BASE64_ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char)
+                }
+                false => encoding.push('='),
+            }
+        }
+    }
+
+    encoding
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+    use crate::Element;
+
+    use rand::rngs::OsRng;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn base64_encoding() {
+        // The test vectors of RFC 4648.
+        for (input, output) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(input.as_bytes()), output);
+        }
+    }
+
+    #[test]
+    fn ed25519_public_key() {
+        // The public key of the first test vector of RFC 8032, as output by
+        // `ssh-keygen -y` for the matching private key.
+        let public_key =
+            from_hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        assert_eq!(
+            openssh_line(SSH_ED25519.as_bytes(), &ed25519_key_blob(&public_key), "ca"),
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAINdamAGCsQq31Uv+08lkBzoO4XLz2qYjJa8CGmj3B1Ea ca"
+        );
+
+        // Keys of other curves have no SSH encoding.
+        let key = GroupVerifyingKey::<Secp256k1Sha256>::new(Element::random(OsRng));
+        assert!(openssh_public_key(&key, "ca").is_err());
+    }
+
+    #[test]
+    fn certificate_payload() {
+        let subject_key = ed25519_key_blob(&[7; 32]);
+        let ca_key = ed25519_key_blob(&[9; 32]);
+        let certificate =
+            SshCertificate::new(&subject_key, SshCertificateType::User, "alice", OsRng)
+                .with_serial(42)
+                .with_principals(&["alice", "root"])
+                .with_validity(1_700_000_000, 1_700_086_400)
+                .with_extension("permit-pty", "")
+                .with_extension("permit-agent-forwarding", "")
+                .with_critical_option("force-command", "uptime");
+
+        let payload = certificate
+            .encode_payload::<Secp256k1Sha256>(&ca_key)
+            .unwrap();
+
+        let (key_type, rest) = read_string::<Secp256k1Sha256>(&payload).unwrap();
+        assert_eq!(key_type, b"ssh-ed25519-cert-v01@openssh.com");
+        let (nonce, rest) = read_string::<Secp256k1Sha256>(rest).unwrap();
+        assert_eq!(nonce, &certificate.nonce[..]);
+        let (public_key, rest) = read_string::<Secp256k1Sha256>(rest).unwrap();
+        assert_eq!(public_key, &[7; 32]);
+        assert_eq!(rest[..8], 42u64.to_be_bytes());
+        assert_eq!(rest[8..12], 1u32.to_be_bytes());
+        let (key_id, rest) = read_string::<Secp256k1Sha256>(&rest[12..]).unwrap();
+        assert_eq!(key_id, b"alice");
+        let (principals, rest) = read_string::<Secp256k1Sha256>(rest).unwrap();
+        assert_eq!(principals, b"\0\0\0\x05alice\0\0\0\x04root");
+        assert_eq!(rest[..8], 1_700_000_000u64.to_be_bytes());
+        assert_eq!(rest[8..16], 1_700_086_400u64.to_be_bytes());
+        let (critical_options, rest) = read_string::<Secp256k1Sha256>(&rest[16..]).unwrap();
+        assert_eq!(
+            critical_options,
+            b"\0\0\0\x0dforce-command\0\0\0\x0a\0\0\0\x06uptime"
+        );
+
+        // Extensions are sorted by name, with empty data for flags.
+        let (extensions, rest) = read_string::<Secp256k1Sha256>(rest).unwrap();
+        assert_eq!(
+            extensions,
+            b"\0\0\0\x17permit-agent-forwarding\0\0\0\0\0\0\0\x0apermit-pty\0\0\0\0"
+        );
+        let (reserved, rest) = read_string::<Secp256k1Sha256>(rest).unwrap();
+        assert!(reserved.is_empty());
+        let (signature_key, rest) = read_string::<Secp256k1Sha256>(rest).unwrap();
+        assert_eq!(signature_key, &ca_key[..]);
+        assert!(rest.is_empty());
+
+        // Malformed subject keys are rejected.
+        let certificate = SshCertificate::new(&[0, 0, 0, 9], SshCertificateType::Host, "", OsRng);
+        assert!(certificate
+            .encode_payload::<Secp256k1Sha256>(&ca_key)
+            .is_err());
+    }
+}
@@ -0,0 +1,504 @@
+//! The Tendermint module for operating an ICE-FROST group as a threshold remote
+//! signer of a Cosmos / Tendermint (CometBFT) validator.
+//!
+//! The [`GroupVerifyingKey`] of a group over edwards25519, i.e. whose ciphersuite has
+//! [`SpkiAlgorithm::Ed25519`] as [`CipherSuite::SPKI_ALGORITHM`], is registered as the
+//! consensus key of the validator, encoded with [`protobuf_public_key`] or, for chains
+//! still using Amino JSON or binary encodings, with [`amino_public_key`]. For each
+//! [`Vote`] or [`Proposal`] the validator must sign, the group signs its canonical
+//! [`Vote::sign_bytes`] or [`Proposal::sign_bytes`] as the message of a signing session,
+//! and the resulting [`ThresholdSignature`] is returned to the node in the 64-byte
+//! Ed25519 format of [`signature_bytes`].
+//!
+//! Tendermint verifies consensus signatures as
+//! [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.7) signatures over
+//! the sign-bytes themselves. A ciphersuite over edwards25519 with SHA-512 produces
+//! them by overriding [`CipherSuite::challenge`] with [`challenge`], and by signing the
+//! sign-bytes as is with [`SignatureAggregator::new_prehashed`].
+//!
+//! As with any remote signer, protecting the validator against double signing, by
+//! refusing to sign a second vote or proposal at the same height, round and step, is
+//! left to the signers of the group.
+//!
+//! [`SignatureAggregator::new_prehashed`]: crate::sign::SignatureAggregator::new_prehashed
+
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::GroupVerifyingKey;
+use crate::sign::ThresholdSignature;
+use crate::spki::{encode_ed25519_point, SpkiAlgorithm};
+use crate::utils::{Scalar, String, ToString, Vec};
+use crate::{Error, FrostResult};
+
+/// The length of the encodings of Ed25519 public keys.
+pub const PUBLIC_KEY_LENGTH: usize = 32;
+
+/// The length of the encodings of Ed25519 signatures.
+pub const SIGNATURE_LENGTH: usize = 64;
+
+/// The length of the addresses of validators.
+pub const ADDRESS_LENGTH: usize = 20;
+
+/// The Amino prefix of `tendermint/PubKeyEd25519` public keys, followed by the length
+/// of the key.
+const AMINO_ED25519_PREFIX: [u8; 5] = [0x16, 0x24, 0xde, 0x64, 0x20];
+
+/// The `SignedMsgType` of proposals.
+const PROPOSAL_TYPE: u64 = 32;
+
+/// The protobuf wire type of varints.
+const WIRE_VARINT: u8 = 0;
+/// The protobuf wire type of 64-bit fixed-size values.
+const WIRE_FIXED64: u8 = 1;
+/// The protobuf wire type of length-delimited values.
+const WIRE_LENGTH_DELIMITED: u8 = 2;
+
+/// The type of a consensus [`Vote`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteType {
+    /// A vote of the first voting step of a round.
+    Prevote,
+    /// A vote of the second voting step of a round, committing to a block.
+    Precommit,
+}
+
+impl VoteType {
+    /// The `SignedMsgType` of this vote type.
+    pub const fn to_u64(self) -> u64 {
+        match self {
+            VoteType::Prevote => 1,
+            VoteType::Precommit => 2,
+        }
+    }
+}
+
+/// A point in time, as a `google.protobuf.Timestamp`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timestamp {
+    /// The number of seconds since the UNIX epoch.
+    pub seconds: i64,
+    /// The number of nanoseconds within the second, from 0 to 999,999,999.
+    pub nanos: i32,
+}
+
+impl Timestamp {
+    /// A timestamp of `seconds` and `nanos` since the UNIX epoch.
+    pub const fn new(seconds: i64, nanos: i32) -> Self {
+        Self { seconds, nanos }
+    }
+
+    /// Append the protobuf encoding of this timestamp to `bytes`.
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        write_varint_field(bytes, 1, self.seconds as u64);
+        // Negative int32 values are sign-extended to 64 bits.
+        write_varint_field(bytes, 2, self.nanos as i64 as u64);
+    }
+}
+
+/// The header of the parts of a block, as gossiped by the consensus.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PartSetHeader {
+    /// The number of parts of the block.
+    pub total: u32,
+    /// The Merkle root of the parts of the block.
+    pub hash: Vec<u8>,
+}
+
+/// The identifier of a block.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockId {
+    /// The hash of the header of the block.
+    pub hash: Vec<u8>,
+    /// The header of the parts of the block.
+    pub part_set_header: PartSetHeader,
+}
+
+impl BlockId {
+    /// A block identifier of the given `hash` and `part_set_header`.
+    pub fn new(hash: &[u8], part_set_header: PartSetHeader) -> Self {
+        Self {
+            hash: hash.to_vec(),
+            part_set_header,
+        }
+    }
+
+    /// Whether this block identifier is empty, as in votes for no block.
+    pub fn is_zero(&self) -> bool {
+        self.hash.is_empty()
+            && self.part_set_header.total == 0
+            && self.part_set_header.hash.is_empty()
+    }
+
+    /// Append the encoding of this block identifier as a `CanonicalBlockID` to `bytes`.
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        write_bytes_field(bytes, 1, &self.hash);
+
+        let mut part_set_header = Vec::new();
+        write_varint_field(
+            &mut part_set_header,
+            1,
+            u64::from(self.part_set_header.total),
+        );
+        write_bytes_field(&mut part_set_header, 2, &self.part_set_header.hash);
+        write_message_field(bytes, 2, &part_set_header);
+    }
+}
+
+/// A consensus vote of a validator, to be signed by the group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Vote {
+    /// The type of the vote.
+    pub vote_type: VoteType,
+    /// The height of the vote.
+    pub height: i64,
+    /// The round of the vote.
+    pub round: i32,
+    /// The block voted for, or [`None`] for a vote for no block.
+    pub block_id: Option<BlockId>,
+    /// The time of the vote.
+    pub timestamp: Timestamp,
+    /// The identifier of the chain.
+    pub chain_id: String,
+}
+
+impl Vote {
+    /// Compute the canonical sign-bytes of this vote, i.e. the length-delimited
+    /// protobuf encoding of its `CanonicalVote`.
+    pub fn sign_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_varint_field(&mut bytes, 1, self.vote_type.to_u64());
+        write_fixed64_field(&mut bytes, 2, self.height);
+        write_fixed64_field(&mut bytes, 3, i64::from(self.round));
+        encode_block_id(&mut bytes, 4, &self.block_id);
+        encode_timestamp(&mut bytes, 5, &self.timestamp);
+        write_bytes_field(&mut bytes, 6, self.chain_id.as_bytes());
+
+        length_delimited(&bytes)
+    }
+}
+
+/// A block proposal of a validator, to be signed by the group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    /// The height of the proposal.
+    pub height: i64,
+    /// The round of the proposal.
+    pub round: i32,
+    /// The round of the proof-of-lock of the proposed block, or -1 if none.
+    pub pol_round: i32,
+    /// The proposed block.
+    pub block_id: Option<BlockId>,
+    /// The time of the proposal.
+    pub timestamp: Timestamp,
+    /// The identifier of the chain.
+    pub chain_id: String,
+}
+
+impl Proposal {
+    /// Compute the canonical sign-bytes of this proposal, i.e. the length-delimited
+    /// protobuf encoding of its `CanonicalProposal`.
+    pub fn sign_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_varint_field(&mut bytes, 1, PROPOSAL_TYPE);
+        write_fixed64_field(&mut bytes, 2, self.height);
+        write_fixed64_field(&mut bytes, 3, i64::from(self.round));
+        write_varint_field(&mut bytes, 4, i64::from(self.pol_round) as u64);
+        encode_block_id(&mut bytes, 5, &self.block_id);
+        encode_timestamp(&mut bytes, 6, &self.timestamp);
+        write_bytes_field(&mut bytes, 7, self.chain_id.as_bytes());
+
+        length_delimited(&bytes)
+    }
+}
+
+/// Compute the Ed25519 challenge of a signature with the given `group_commitment`,
+/// under the given `group_key`, on the given `message`, i.e. the SHA-512 digest of
+/// their encodings reduced modulo the order of the group.
+///
+/// Ciphersuites producing Tendermint consensus signatures return it from
+/// [`CipherSuite::challenge`].
+pub fn challenge<C: CipherSuite>(
+    group_commitment: &C::G,
+    group_key: &C::G,
+    message: &[u8],
+) -> FrostResult<C, Scalar<C>> {
+    let digest = Sha512::new()
+        .chain_update(encode_ed25519_point::<C>(group_commitment)?)
+        .chain_update(encode_ed25519_point::<C>(group_key)?)
+        .chain_update(message)
+        .finalize();
+
+    Ok(Scalar::<C>::from_le_bytes_mod_order(&digest))
+}
+
+/// Encode the given `group_key` of an Ed25519 group as a raw Ed25519 public key.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is the encoding, otherwise an [`Error`] if the
+/// ciphersuite of the group key is not over edwards25519.
+pub fn public_key_bytes<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, [u8; PUBLIC_KEY_LENGTH]> {
+    check_ed25519::<C>()?;
+
+    encode_ed25519_point::<C>(&group_key.key)?
+        .try_into()
+        .map_err(|_| Error::InvalidPoint)
+}
+
+/// Encode the given `group_key` of an Ed25519 group as a protobuf `PublicKey`, with
+/// the key in its `ed25519` field.
+pub fn protobuf_public_key<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, Vec<u8>> {
+    let mut bytes = Vec::new();
+    write_bytes_field(&mut bytes, 1, &public_key_bytes(group_key)?);
+
+    Ok(bytes)
+}
+
+/// Encode the given `group_key` of an Ed25519 group as an Amino
+/// `tendermint/PubKeyEd25519` public key.
+pub fn amino_public_key<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, Vec<u8>> {
+    Ok([&AMINO_ED25519_PREFIX[..], &public_key_bytes(group_key)?].concat())
+}
+
+/// The address of the validator whose consensus key is the given `group_key`, i.e.
+/// the first 20 bytes of the SHA-256 digest of its raw public key.
+pub fn validator_address<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, [u8; ADDRESS_LENGTH]> {
+    let digest = Sha256::digest(public_key_bytes(group_key)?);
+
+    let mut address = [0u8; ADDRESS_LENGTH];
+    address.copy_from_slice(&digest[..ADDRESS_LENGTH]);
+
+    Ok(address)
+}
+
+/// Encode the given `signature` of an Ed25519 group as a raw Ed25519 signature, i.e.
+/// as the encoding of its group commitment followed by the little-endian encoding
+/// of its response, as carried by the `signature` fields of votes and proposals.
+pub fn signature_bytes<C: CipherSuite>(
+    signature: &ThresholdSignature<C>,
+) -> FrostResult<C, [u8; SIGNATURE_LENGTH]> {
+    check_ed25519::<C>()?;
+
+    let mut raw_signature = encode_ed25519_point::<C>(&signature.group_commitment)?;
+    signature
+        .z
+        .serialize_compressed(&mut raw_signature)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+
+    raw_signature
+        .try_into()
+        .map_err(|_| Error::Custom("Ed25519 signatures require 32-byte scalars".to_string()))
+}
+
+/// Check that the ciphersuite `C` is over edwards25519.
+fn check_ed25519<C: CipherSuite>() -> FrostResult<C, ()> {
+    match C::SPKI_ALGORITHM {
+        Some(SpkiAlgorithm::Ed25519) => Ok(()),
+        _ => Err(Error::Custom(
+            "Tendermint keys and signatures require an Ed25519 ciphersuite".to_string(),
+        )),
+    }
+}
+
+/// Append the `CanonicalBlockID` of `block_id` as field `field` to `bytes`, unless the
+/// vote or proposal is for no block.
+fn encode_block_id(bytes: &mut Vec<u8>, field: u8, block_id: &Option<BlockId>) {
+    if let Some(block_id) = block_id {
+        if !block_id.is_zero() {
+            let mut encoding = Vec::new();
+            block_id.encode(&mut encoding);
+            write_message_field(bytes, field, &encoding);
+        }
+    }
+}
+
+/// Append `timestamp` as field `field` to `bytes`. Timestamps are not nullable, and
+/// are hence always encoded.
+fn encode_timestamp(bytes: &mut Vec<u8>, field: u8, timestamp: &Timestamp) {
+    let mut encoding = Vec::new();
+    timestamp.encode(&mut encoding);
+    write_message_field(bytes, field, &encoding);
+}
+
+/// Prefix `message` with its length, as a varint.
+fn length_delimited(message: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(message.len() + 2);
+    write_varint(&mut bytes, message.len() as u64);
+    bytes.extend_from_slice(message);
+
+    bytes
+}
+
+/// Append the protobuf varint encoding of `value` to `bytes`.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Append the key of field `field` with wire type `wire_type` to `bytes`.
+fn write_key(bytes: &mut Vec<u8>, field: u8, wire_type: u8) {
+    bytes.push(field << 3 | wire_type);
+}
+
+/// Append the varint field `field` to `bytes`, unless `value` is the default one.
+fn write_varint_field(bytes: &mut Vec<u8>, field: u8, value: u64) {
+    if value != 0 {
+        write_key(bytes, field, WIRE_VARINT);
+        write_varint(bytes, value);
+    }
+}
+
+/// Append the `sfixed64` field `field` to `bytes`, unless `value` is the default one.
+fn write_fixed64_field(bytes: &mut Vec<u8>, field: u8, value: i64) {
+    if value != 0 {
+        write_key(bytes, field, WIRE_FIXED64);
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// Append the `bytes` or `string` field `field` to `bytes`, unless `value` is empty.
+fn write_bytes_field(bytes: &mut Vec<u8>, field: u8, value: &[u8]) {
+    if !value.is_empty() {
+        write_message_field(bytes, field, value);
+    }
+}
+
+/// Append the embedded message field `field`, with encoding `message`, to `bytes`.
+fn write_message_field(bytes: &mut Vec<u8>, field: u8, message: &[u8]) {
+    write_key(bytes, field, WIRE_LENGTH_DELIMITED);
+    write_varint(bytes, message.len() as u64);
+    bytes.extend_from_slice(message);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+    use crate::Element;
+
+    use rand::rngs::OsRng;
+
+    /// The seconds since the UNIX epoch of the zero `time.Time` of Go, used by the
+    /// test vectors of Tendermint.
+    const GO_ZERO_TIME: i64 = -62_135_596_800;
+
+    /// The encoding of the timestamp of the zero `time.Time` as field 5.
+    const GO_ZERO_TIMESTAMP: [u8; 13] = [
+        0x2a, 0x0b, 0x08, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x01,
+    ];
+
+    fn vote(vote_type: VoteType, height: i64, round: i32, chain_id: &str) -> Vote {
+        Vote {
+            vote_type,
+            height,
+            round,
+            block_id: None,
+            timestamp: Timestamp::new(GO_ZERO_TIME, 0),
+            chain_id: chain_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn vote_sign_bytes() {
+        // The test vectors of `TestVoteSignBytesTestVectors` in Tendermint.
+        let sign_bytes = vote(VoteType::Precommit, 1, 1, "").sign_bytes();
+        assert_eq!(
+            sign_bytes,
+            [
+                &[0x21, 0x08, 0x02][..],
+                &[0x11, 0x01, 0, 0, 0, 0, 0, 0, 0],
+                &[0x19, 0x01, 0, 0, 0, 0, 0, 0, 0],
+                &GO_ZERO_TIMESTAMP,
+            ]
+            .concat()
+        );
+
+        let sign_bytes = vote(VoteType::Prevote, 1, 1, "").sign_bytes();
+        assert_eq!(sign_bytes[..3], [0x21, 0x08, 0x01]);
+
+        let sign_bytes = vote(VoteType::Prevote, 1, 1, "test_chain_id").sign_bytes();
+        assert_eq!(sign_bytes[0], 0x30);
+        assert_eq!(
+            sign_bytes[sign_bytes.len() - 15..sign_bytes.len() - 13],
+            [0x32, 0x0d]
+        );
+        assert!(sign_bytes.ends_with(b"test_chain_id"));
+
+        // A vote for a block encodes its identifier, unlike a vote for no block.
+        let mut block_vote = vote(VoteType::Precommit, 1, 1, "");
+        block_vote.block_id = Some(BlockId::new(
+            &[0xaa; 32],
+            PartSetHeader {
+                total: 1,
+                hash: [0xbb; 32].to_vec(),
+            },
+        ));
+        let sign_bytes = block_vote.sign_bytes();
+        assert_eq!(sign_bytes[0] as usize, sign_bytes.len() - 1);
+        assert_eq!(sign_bytes[21..25], [0x22, 0x48, 0x0a, 0x20]);
+        assert_eq!(sign_bytes[57..62], [0x12, 0x24, 0x08, 0x01, 0x12]);
+
+        block_vote.block_id = Some(BlockId::default());
+        assert_eq!(
+            block_vote.sign_bytes(),
+            vote(VoteType::Precommit, 1, 1, "").sign_bytes()
+        );
+    }
+
+    #[test]
+    fn proposal_sign_bytes() {
+        let proposal = Proposal {
+            height: 1,
+            round: 0,
+            pol_round: -1,
+            block_id: None,
+            timestamp: Timestamp::new(GO_ZERO_TIME, 0),
+            chain_id: String::new(),
+        };
+
+        // A proof-of-lock round of -1 is encoded as a sign-extended varint.
+        assert_eq!(
+            proposal.sign_bytes(),
+            [
+                &[0x23, 0x08, 0x20][..],
+                &[0x11, 0x01, 0, 0, 0, 0, 0, 0, 0],
+                &[0x20, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+                &[0x32],
+                &GO_ZERO_TIMESTAMP[1..],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn ed25519_encodings() {
+        // Keys and signatures of other curves have no Tendermint encoding.
+        let key = GroupVerifyingKey::<Secp256k1Sha256>::new(Element::random(OsRng));
+        assert!(public_key_bytes(&key).is_err());
+        assert!(protobuf_public_key(&key).is_err());
+        assert!(amino_public_key(&key).is_err());
+        assert!(validator_address(&key).is_err());
+
+        let mut bytes = Vec::new();
+        write_bytes_field(&mut bytes, 1, &[7; PUBLIC_KEY_LENGTH]);
+        assert_eq!(bytes[..2], [0x0a, 0x20]);
+
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 300);
+        assert_eq!(bytes, [0xac, 0x02]);
+    }
+}
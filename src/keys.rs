@@ -4,37 +4,47 @@ use core::marker::PhantomData;
 use core::ops::{Deref, Mul};
 
 use crate::dkg::secret_share::VerifiableSecretSharingCommitment;
+use crate::fingerprint::Fingerprint;
+use crate::parameters::ThresholdParameters;
 use crate::sign::{compute_challenge, ThresholdSignature};
-use crate::utils::calculate_lagrange_coefficients;
-use crate::utils::{ToString, Vec};
-use crate::{Error, FrostResult};
+use crate::utils::{calculate_lagrange_coefficients, ct_eq_encoded, points_to_affine_batch};
+use crate::utils::{try_with_capacity, vec, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Element, Error, FrostResult};
 
 use crate::ciphersuite::CipherSuite;
 
-use ark_ec::{CurveGroup, Group, VariableBaseMSM};
-use ark_ff::Zero;
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{Field, UniformRand, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::Zeroize;
 
 /// A Diffie-Hellman private key wrapper type around a PrimeField.
-#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
 pub struct DiffieHellmanPrivateKey<C: CipherSuite>(pub(crate) <C::G as Group>::ScalarField);
 
 impl<C: CipherSuite> DiffieHellmanPrivateKey<C> {
+    /// Sample a new random [`DiffieHellmanPrivateKey`].
+    pub fn new(mut rng: impl RngCore + CryptoRng) -> Self {
+        Self(Scalar::<C>::rand(&mut rng))
+    }
+
     /// Serialize this [`DiffieHellmanPrivateKey`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`DiffieHellmanPrivateKey`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
     }
 }
 
@@ -44,6 +54,45 @@ impl<C: CipherSuite> Drop for DiffieHellmanPrivateKey<C> {
     }
 }
 
+impl<C: CipherSuite> ConstantTimeEq for DiffieHellmanPrivateKey<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_eq_encoded(&self.0, &other.0)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for DiffieHellmanPrivateKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for DiffieHellmanPrivateKey<C> {}
+
+/// A provider of Diffie-Hellman operations, allowing the private key to be held
+/// by an external device, such as a PKCS#11 token or a TPM, which only exposes the
+/// derived shared keys.
+pub trait DhKeyProvider<C: CipherSuite> {
+    /// The [`DiffieHellmanPublicKey`] matching the private key held by this provider.
+    fn dh_public_key(&self) -> FrostResult<C, DiffieHellmanPublicKey<C>>;
+
+    /// Compute the Diffie-Hellman shared key with the given `peer_public_key`,
+    /// i.e. the peer's public key multiplied by the private key held by this provider.
+    fn dh_shared_key(&self, peer_public_key: &DiffieHellmanPublicKey<C>) -> FrostResult<C, C::G>;
+}
+
+impl<C: CipherSuite> DhKeyProvider<C> for DiffieHellmanPrivateKey<C> {
+    fn dh_public_key(&self) -> FrostResult<C, DiffieHellmanPublicKey<C>> {
+        Ok(DiffieHellmanPublicKey::new(Element(
+            C::G::generator().mul(self.0),
+        )))
+    }
+
+    fn dh_shared_key(&self, peer_public_key: &DiffieHellmanPublicKey<C>) -> FrostResult<C, C::G> {
+        Ok(peer_public_key.key.mul(self.0))
+    }
+}
+
 /// A Diffie-Hellman public key wrapper type around a CurveGroup.
 #[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct DiffieHellmanPublicKey<C: CipherSuite> {
@@ -53,26 +102,63 @@ pub struct DiffieHellmanPublicKey<C: CipherSuite> {
 
 impl<C: CipherSuite> DiffieHellmanPublicKey<C> {
     /// Instantiates a new [`DiffieHellmanPublicKey`] key.
-    pub fn new(key: C::G) -> Self {
+    pub fn new(key: Element<C>) -> Self {
         Self {
-            key,
+            key: key.0,
             _phantom: PhantomData,
         }
     }
 
+    /// The group element of this [`DiffieHellmanPublicKey`].
+    pub fn element(&self) -> Element<C> {
+        Element(self.key)
+    }
+
     /// Serialize this [`DiffieHellmanPublicKey`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`DiffieHellmanPublicKey`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the [`Fingerprint`] of this [`DiffieHellmanPublicKey`], for participants
+    /// to check out of band that their key was received untampered.
+    pub fn fingerprint(&self) -> FrostResult<C, Fingerprint> {
+        Fingerprint::compute::<C>("dh public key fingerprint", &self.to_bytes()?)
+    }
+}
+
+/// [`DiffieHellmanPublicKey`]s are ordered by their compressed encoding, so that
+/// they can index ordered collections.
+impl<C: CipherSuite> Ord for DiffieHellmanPublicKey<C> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let mut bytes = Vec::new();
+        let mut other_bytes = Vec::new();
+        // Serializing to a vector cannot fail.
+        let _ = self.key.serialize_compressed(&mut bytes);
+        let _ = other.key.serialize_compressed(&mut other_bytes);
+
+        bytes.cmp(&other_bytes)
+    }
+}
+
+impl<C: CipherSuite> PartialOrd for DiffieHellmanPublicKey<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for DiffieHellmanPublicKey<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.key)
     }
 }
 
@@ -102,14 +188,14 @@ impl<C: CipherSuite> IndividualVerifyingKey<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`IndividualVerifyingKey`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
     }
 
     /// Any participant can compute the public verification share of any other participant.
@@ -135,36 +221,52 @@ impl<C: CipherSuite> IndividualVerifyingKey<C> {
         &self,
         commitments: &[VerifiableSecretSharingCommitment<C>],
     ) -> FrostResult<C, ()> {
-        let mut rhs: C::G = <C as CipherSuite>::G::zero();
-        let term: <C::G as Group>::ScalarField = self.index.into();
+        let combined_commitment = combine_commitments(commitments)?;
 
-        let mut index_vector: Vec<u32> = Vec::new();
-        for commitment in commitments.iter() {
-            index_vector.push(commitment.index);
+        match self.share == evaluate_combined_commitment(&combined_commitment, self.index)? {
+            true => Ok(()),
+            false => Err(Error::ShareVerificationError),
         }
+    }
 
-        for commitment in commitments.iter() {
-            let mut tmp: C::G = <C as CipherSuite>::G::zero();
-            for (index, com) in commitment.points.iter().rev().enumerate() {
-                tmp += com;
-
-                if index != (commitment.points.len() - 1) {
-                    tmp *= term;
-                }
-            }
-
-            let coeff = match calculate_lagrange_coefficients::<C>(commitment.index, &index_vector)
-            {
-                Ok(s) => s,
-                Err(error) => return Err(Error::Custom(error.to_string())),
-            };
+    /// Verify all the given `verifying_keys` against the `commitments` of the dealers,
+    /// as with [`IndividualVerifyingKey::verify`], in a single pass.
+    ///
+    /// The commitments of the dealers are combined only once, after which each
+    /// verifying key is checked with a single multi-scalar multiplication.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is empty if all verifying keys are valid,
+    /// otherwise an [`Error::MisbehavingParticipants`] listing the indices of the
+    /// invalid verifying keys.
+    pub fn verify_all(
+        verifying_keys: &[Self],
+        commitments: &[VerifiableSecretSharingCommitment<C>],
+    ) -> FrostResult<C, ()> {
+        let combined_commitment = combine_commitments(commitments)?;
 
-            rhs += tmp.mul(coeff);
+        // The verifying keys and their expected values are normalized in a single batch.
+        let mut points = try_with_capacity::<C, _>(2 * verifying_keys.len())?;
+        for verifying_key in verifying_keys {
+            points.push(verifying_key.share);
+            points.push(evaluate_combined_commitment(
+                &combined_commitment,
+                verifying_key.index,
+            )?);
         }
+        let points = points_to_affine_batch::<C>(&points);
 
-        match self.share.into_affine() == rhs.into_affine() {
+        let misbehaving_participants: Vec<u32> = verifying_keys
+            .iter()
+            .zip(points.chunks_exact(2))
+            .filter(|(_, pair)| pair[0] != pair[1])
+            .map(|(verifying_key, _)| verifying_key.index)
+            .collect();
+
+        match misbehaving_participants.is_empty() {
             true => Ok(()),
-            false => Err(Error::ShareVerificationError),
+            false => Err(Error::MisbehavingParticipants(misbehaving_participants)),
         }
     }
 
@@ -186,43 +288,217 @@ impl<C: CipherSuite> IndividualVerifyingKey<C> {
     ///
     /// # Returns
     ///
-    /// An [`IndividualVerifyingKey`] .
+    /// A [`FrostResult`] whose [`Ok`] value is an [`IndividualVerifyingKey`], or an
+    /// [`Error`] if the `commitments` hold duplicate dealer indices.
     pub fn generate_from_commitments(
         participant_index: u32,
         commitments: &[VerifiableSecretSharingCommitment<C>],
-    ) -> Self {
-        let mut share: C::G = <C as CipherSuite>::G::zero();
-        let term: <C::G as Group>::ScalarField = participant_index.into();
+    ) -> FrostResult<C, Self> {
+        let combined_commitment = combine_commitments(commitments)?;
 
-        let mut index_vector: Vec<u32> = Vec::new();
-        for commitment in commitments.iter() {
-            index_vector.push(commitment.index);
-        }
+        Ok(IndividualVerifyingKey {
+            index: participant_index,
+            share: evaluate_combined_commitment::<C>(&combined_commitment, participant_index)?,
+        })
+    }
+
+    /// Compute the public verification shares of all the participants with the given
+    /// `participant_indices`, as with [`IndividualVerifyingKey::generate_from_commitments`],
+    /// combining the `commitments` of the dealers only once.
+    pub fn generate_all_from_commitments(
+        participant_indices: &[u32],
+        commitments: &[VerifiableSecretSharingCommitment<C>],
+    ) -> FrostResult<C, Vec<Self>> {
+        let combined_commitment = combine_commitments(commitments)?;
+
+        participant_indices
+            .iter()
+            .map(|&index| {
+                Ok(IndividualVerifyingKey {
+                    index,
+                    share: evaluate_combined_commitment::<C>(&combined_commitment, index)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Combine the `commitments` of the dealers into the commitment to the polynomial whose
+/// evaluation at a participant's index is its public verification share, i.e. into
+/// \\( \Phi\_k = \sum\_{j} \lambda\_j \phi\_{jk} \\), where \\( \lambda\_j \\) is the Lagrange
+/// coefficient of the dealer \\( j \\).
+fn combine_commitments<C: CipherSuite>(
+    commitments: &[VerifiableSecretSharingCommitment<C>],
+) -> FrostResult<C, Vec<<C::G as CurveGroup>::Affine>> {
+    let index_vector: Vec<u32> = commitments
+        .iter()
+        .map(|commitment| commitment.index)
+        .collect();
+    let degree = commitments
+        .iter()
+        .map(|commitment| commitment.points.len())
+        .max()
+        .unwrap_or(0);
 
-        for commitment in commitments.iter() {
-            let mut tmp: C::G = <C as CipherSuite>::G::zero();
-            for (index, com) in commitment.points.iter().rev().enumerate() {
-                tmp += com;
+    let coefficients = commitments
+        .iter()
+        .map(|commitment| {
+            calculate_lagrange_coefficients::<C>(commitment.index, &index_vector)
+                .map_err(|error| Error::Custom(error.to_string()))
+        })
+        .collect::<FrostResult<C, Vec<_>>>()?;
 
-                if index != (commitment.points.len() - 1) {
-                    tmp *= term;
-                }
-            }
+    // All the commitment lists are normalized in a single batch, so that each term
+    // of the combined commitment is computed with a multi-scalar multiplication.
+    let mut points: Vec<C::G> = try_with_capacity::<C, _>(
+        commitments
+            .iter()
+            .map(|commitment| commitment.points.len())
+            .sum(),
+    )?;
+    points.extend(
+        commitments
+            .iter()
+            .flat_map(|commitment| commitment.points.iter().copied()),
+    );
+    let mut points = points_to_affine_batch::<C>(&points).into_iter();
+    let affine_commitments: Vec<Vec<_>> = commitments
+        .iter()
+        .map(|commitment| points.by_ref().take(commitment.points.len()).collect())
+        .collect();
 
-            let coeff =
-                calculate_lagrange_coefficients::<C>(commitment.index, &index_vector).unwrap();
-            share += tmp * coeff;
+    let mut combined = vec![<C as CipherSuite>::G::zero(); degree];
+    for (k, combined_point) in combined.iter_mut().enumerate() {
+        let (bases, scalars): (Vec<_>, Vec<_>) = affine_commitments
+            .iter()
+            .zip(coefficients.iter())
+            .filter_map(|(points, coeff)| points.get(k).map(|point| (*point, *coeff)))
+            .unzip();
+        *combined_point = C::msm(&bases, &scalars)?;
+    }
+
+    Ok(points_to_affine_batch::<C>(&combined))
+}
+
+/// Evaluate a combined commitment at the given participant `index`, from the
+/// powers of the index computed once for all terms.
+fn evaluate_combined_commitment<C: CipherSuite>(
+    combined_commitment: &[<C::G as CurveGroup>::Affine],
+    index: u32,
+) -> FrostResult<C, C::G> {
+    // The polynomials of groups with a threshold of 1 are constant.
+    if let [constant_term] = combined_commitment {
+        return Ok((*constant_term).into());
+    }
+
+    let term = Scalar::<C>::from(index);
+    let mut powers = Vec::with_capacity(combined_commitment.len());
+    let mut power = Scalar::<C>::ONE;
+    for _ in 0..combined_commitment.len() {
+        powers.push(power);
+        power *= term;
+    }
+
+    C::msm(combined_commitment, &powers)
+}
+
+impl<C: CipherSuite> IndividualVerifyingKey<C> {
+    /// Add the `other` [`IndividualVerifyingKey`] of the same participant to this one,
+    /// yielding the public half of [`IndividualSigningKey::add_share`].
+    pub fn add_share(&self, other: &Self) -> FrostResult<C, Self> {
+        if self.index != other.index {
+            return Err(Error::InvalidShareCombination);
         }
 
-        IndividualVerifyingKey {
-            index: participant_index,
-            share,
+        Ok(Self {
+            index: self.index,
+            share: self.share + other.share,
+        })
+    }
+
+    /// Compute the linear combination of some [`IndividualVerifyingKey`]s of the same
+    /// participant with the given `coefficients`, yielding the public half of
+    /// [`IndividualSigningKey::linear_combination`].
+    pub fn linear_combination(
+        shares: &[Self],
+        coefficients: &[crate::Scalar<C>],
+    ) -> FrostResult<C, Self> {
+        let index = check_combination::<C>(shares.iter().map(|share| share.index), coefficients)?;
+
+        let share = shares
+            .iter()
+            .zip(coefficients)
+            .map(|(share, coefficient)| share.share * coefficient.0)
+            .sum();
+
+        Ok(Self { index, share })
+    }
+
+    /// Derive the [`IndividualVerifyingKey`] of this participant for the usage `domain`
+    /// of the group with the given root `group_key`, yielding the public half of
+    /// [`IndividualSigningKey::domain`].
+    pub fn domain(&self, group_key: &GroupVerifyingKey<C>, domain: &str) -> FrostResult<C, Self> {
+        let tweak = domain_tweak(group_key, domain)?;
+
+        Ok(Self {
+            index: self.index,
+            share: self.share + C::G::generator() * tweak,
+        })
+    }
+
+    /// Randomize this [`IndividualVerifyingKey`] with the given `randomizer`, yielding
+    /// the public half of [`IndividualSigningKey::randomize`].
+    pub fn randomize(&self, randomizer: &KeyRandomizer<C>) -> Self {
+        Self {
+            index: self.index,
+            share: self.share + C::G::generator() * randomizer.0,
         }
     }
 }
 
+/// Compute the tweak of the usage `domain` of the group with the given root `group_key`.
+///
+/// As Lagrange coefficients sum to one, adding the same tweak to every share of the
+/// group signing key yields a sharing of the group signing key plus this tweak.
+fn domain_tweak<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+    domain: &str,
+) -> FrostResult<C, Scalar<C>> {
+    let mut tweak_input = group_key.to_bytes()?;
+    tweak_input.extend(domain.as_bytes());
+
+    C::hash_to_field(
+        (C::context_string() + "group key domain").as_bytes(),
+        &tweak_input,
+    )
+}
+
+/// Check that the shares with the given `indices` can be combined with the given
+/// `coefficients`, returning their common participant index.
+fn check_combination<C: CipherSuite>(
+    mut indices: impl ExactSizeIterator<Item = u32>,
+    coefficients: &[crate::Scalar<C>],
+) -> FrostResult<C, u32> {
+    if indices.len() != coefficients.len() {
+        return Err(Error::InvalidShareCombination);
+    }
+
+    let index = indices.next().ok_or(Error::InvalidShareCombination)?;
+    if indices.any(|other| other != index) {
+        return Err(Error::InvalidShareCombination);
+    }
+
+    Ok(index)
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for IndividualVerifyingKey<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.share)
+    }
+}
+
 /// A secret key, used by one participant in a threshold signature scheme, to sign a message.
-#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
 pub struct IndividualSigningKey<C: CipherSuite> {
     /// The participant index to which this key belongs.
     pub(crate) index: u32,
@@ -230,20 +506,35 @@ pub struct IndividualSigningKey<C: CipherSuite> {
     pub(crate) key: <C::G as Group>::ScalarField,
 }
 
+impl<C: CipherSuite> ConstantTimeEq for IndividualSigningKey<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.index.ct_eq(&other.index) & ct_eq_encoded(&self.key, &other.key)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for IndividualSigningKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for IndividualSigningKey<C> {}
+
 impl<C: CipherSuite> IndividualSigningKey<C> {
     /// Serialize this [`IndividualSigningKey`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`IndividualSigningKey`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
     }
 }
 
@@ -263,6 +554,85 @@ impl<C: CipherSuite> IndividualSigningKey<C> {
             share,
         }
     }
+
+    /// Add the `other` [`IndividualSigningKey`] of the same participant to this one.
+    ///
+    /// When all participants add their shares of two groups with the same
+    /// [`ThresholdParameters`], the resulting shares are a sharing of the sum of both
+    /// group secret keys, whose public material is obtained through
+    /// [`PublicKeyPackage::linear_combination`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the new [`IndividualSigningKey`], or an
+    /// [`Error::InvalidShareCombination`] if both keys belong to distinct participants.
+    pub fn add_share(&self, other: &Self) -> FrostResult<C, Self> {
+        if self.index != other.index {
+            return Err(Error::InvalidShareCombination);
+        }
+
+        Ok(Self {
+            index: self.index,
+            key: self.key + other.key,
+        })
+    }
+
+    /// Compute the linear combination of some [`IndividualSigningKey`]s of the same
+    /// participant with the given `coefficients`.
+    ///
+    /// This allows higher-level protocols, such as key tweaking ceremonies or the
+    /// pre-processing of other threshold schemes, to derive new shares without access
+    /// to the underlying secrets. The matching public material is obtained through
+    /// [`PublicKeyPackage::linear_combination`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the new [`IndividualSigningKey`], or an
+    /// [`Error::InvalidShareCombination`] if the `shares` are empty, belong to distinct
+    /// participants, or do not match the number of `coefficients`.
+    pub fn linear_combination(
+        shares: &[Self],
+        coefficients: &[crate::Scalar<C>],
+    ) -> FrostResult<C, Self> {
+        let index = check_combination::<C>(shares.iter().map(|share| share.index), coefficients)?;
+
+        let key = shares
+            .iter()
+            .zip(coefficients)
+            .map(|(share, coefficient)| share.key * coefficient.0)
+            .sum();
+
+        Ok(Self { index, key })
+    }
+
+    /// Derive the [`IndividualSigningKey`] of this participant for the usage `domain`
+    /// of the group with the given root `group_key`.
+    ///
+    /// The derived keys of all participants are a sharing of the signing key of
+    /// [`GroupVerifyingKey::domain`], so that a single distributed key generation
+    /// session provides independent keys for several purposes. Signing with them is
+    /// done through [`IndividualSigningKey::sign_in_domain`].
+    pub fn domain(&self, group_key: &GroupVerifyingKey<C>, domain: &str) -> FrostResult<C, Self> {
+        let tweak = domain_tweak(group_key, domain)?;
+
+        Ok(Self {
+            index: self.index,
+            key: self.key + tweak,
+        })
+    }
+
+    /// Randomize the [`IndividualSigningKey`] of this participant with the given
+    /// `randomizer`.
+    ///
+    /// The randomized keys of all participants are a sharing of the signing key of
+    /// [`GroupVerifyingKey::randomize`]. Signing with them is done through
+    /// [`IndividualSigningKey::sign_with_randomized_key`].
+    pub fn randomize(&self, randomizer: &KeyRandomizer<C>) -> Self {
+        Self {
+            index: self.index,
+            key: self.key + randomizer.0,
+        }
+    }
 }
 
 impl<C: CipherSuite> From<&IndividualSigningKey<C>> for IndividualVerifyingKey<C> {
@@ -280,23 +650,27 @@ pub struct GroupVerifyingKey<C: CipherSuite> {
 
 impl<C: CipherSuite> GroupVerifyingKey<C> {
     /// Instantiates a new [`GroupVerifyingKey`] key.
-    pub fn new(key: C::G) -> Self {
+    pub fn new(key: Element<C>) -> Self {
         Self {
-            key,
+            key: key.0,
             _phantom: PhantomData,
         }
     }
 
+    /// The group element of this [`GroupVerifyingKey`].
+    pub fn element(&self) -> Element<C> {
+        Element(self.key)
+    }
+
     /// Verifies a [`ThresholdSignature`] for a given message.
     pub fn verify_signature(
         &self,
         signature: &ThresholdSignature<C>,
         message_hash: &[u8],
     ) -> FrostResult<C, ()> {
-        let challenge =
-            compute_challenge::<C>(&signature.group_commitment, self, message_hash).unwrap();
+        let challenge = compute_challenge::<C>(&signature.group_commitment, self, message_hash)?;
 
-        let retrieved_commitment: C::G = <C as CipherSuite>::G::msm(
+        let retrieved_commitment = C::msm(
             &[C::G::generator().into(), (-self.key).into()],
             &[signature.z, challenge],
         )
@@ -313,13 +687,372 @@ impl<C: CipherSuite> GroupVerifyingKey<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`GroupVerifyingKey`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+
+    /// Encode this [`GroupVerifyingKey`] as a DER X.509 `SubjectPublicKeyInfo`, with
+    /// the [`CipherSuite::SPKI_ALGORITHM`] of its ciphersuite.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the DER encoding, otherwise an [`Error`]
+    /// if the ciphersuite has no `SubjectPublicKeyInfo` algorithm.
+    pub fn to_spki_der(&self) -> FrostResult<C, Vec<u8>> {
+        crate::spki::encode::<C>(&self.key)
+    }
+
+    /// Attempt to decode a [`GroupVerifyingKey`] from a DER X.509 `SubjectPublicKeyInfo`,
+    /// whose algorithm must be the [`CipherSuite::SPKI_ALGORITHM`] of its ciphersuite.
+    pub fn from_spki_der(bytes: &[u8]) -> FrostResult<C, Self> {
+        let key = crate::spki::decode::<C>(bytes)?;
+        crate::validation::validate_point::<C>(&key)?;
+
+        Ok(Self::new(Element(key)))
+    }
+
+    /// Compute the [`Fingerprint`] of this [`GroupVerifyingKey`], for operators
+    /// to check out of band that they derived the same group key.
+    pub fn fingerprint(&self) -> FrostResult<C, Fingerprint> {
+        Fingerprint::compute::<C>("group key fingerprint", &self.to_bytes()?)
+    }
+
+    /// Derive the [`GroupVerifyingKey`] of the usage `domain`, such as `"treasury"`,
+    /// of the group with this root key.
+    ///
+    /// The derived key is this key tweaked by a hash of itself and of the `domain`,
+    /// hence signatures produced for one domain do not verify under the key of
+    /// any other domain, nor under the root key.
+    pub fn domain(&self, domain: &str) -> FrostResult<C, Self> {
+        let tweak = domain_tweak(self, domain)?;
+
+        Ok(Self::new(Element(self.key + C::G::generator() * tweak)))
+    }
+
+    /// Randomize this [`GroupVerifyingKey`] with the given `randomizer`, as done for
+    /// the spend authorization keys of Zcash.
+    ///
+    /// The randomized key is unlinkable to this key without the `randomizer`, while
+    /// remaining controlled by the same threshold of participants.
+    pub fn randomize(&self, randomizer: &KeyRandomizer<C>) -> Self {
+        Self::new(Element(self.key + C::G::generator() * randomizer.0))
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for GroupVerifyingKey<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.key)
+    }
+}
+
+/// The public material of an ICE-FROST group, i.e. the group's public key
+/// along with the individual verifying keys of all its participants.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PublicKeyPackage<C: CipherSuite> {
+    /// The parameters of the ICE-FROST session that produced these keys.
+    pub(crate) parameters: ThresholdParameters<C>,
+    /// The public key of the group.
+    pub(crate) group_key: GroupVerifyingKey<C>,
+    /// The individual verifying keys of the participants, sorted by index.
+    pub(crate) verifying_keys: Vec<IndividualVerifyingKey<C>>,
+}
+
+impl<C: CipherSuite> PublicKeyPackage<C> {
+    /// Instantiates a new [`PublicKeyPackage`] from the parameters and keys obtained
+    /// at the end of an ICE-FROST Distributed Key Generation session.
+    pub fn new(
+        parameters: ThresholdParameters<C>,
+        group_key: GroupVerifyingKey<C>,
+        mut verifying_keys: Vec<IndividualVerifyingKey<C>>,
+    ) -> Self {
+        verifying_keys.sort_by_key(|key| key.index);
+
+        Self {
+            parameters,
+            group_key,
+            verifying_keys,
+        }
+    }
+
+    /// The [`ThresholdParameters`] of this [`PublicKeyPackage`].
+    pub fn parameters(&self) -> &ThresholdParameters<C> {
+        &self.parameters
+    }
+
+    /// The [`GroupVerifyingKey`] of this [`PublicKeyPackage`].
+    pub fn group_key(&self) -> &GroupVerifyingKey<C> {
+        &self.group_key
+    }
+
+    /// The [`IndividualVerifyingKey`]s of this [`PublicKeyPackage`], sorted by participant index.
+    pub fn verifying_keys(&self) -> &[IndividualVerifyingKey<C>] {
+        &self.verifying_keys
+    }
+
+    /// Retrieve the [`IndividualVerifyingKey`] of the participant with the given `index`, if any.
+    pub fn verifying_key(&self, index: u32) -> Option<&IndividualVerifyingKey<C>> {
+        self.verifying_keys
+            .binary_search_by_key(&index, |key| key.index)
+            .ok()
+            .map(|position| &self.verifying_keys[position])
+    }
+
+    /// Verify that the verifying keys of this [`PublicKeyPackage`] are consistent with
+    /// its group key, as with [`verify_key_consistency`].
+    pub fn verify_consistency(&self) -> FrostResult<C, ()> {
+        verify_key_consistency(&self.group_key, &self.verifying_keys, &self.parameters)
+    }
+
+    /// Compute the linear combination of some [`PublicKeyPackage`]s of groups with the
+    /// same parameters and participants, with the given `coefficients`.
+    ///
+    /// The resulting [`PublicKeyPackage`] is the public material of the shares obtained
+    /// by each participant through [`IndividualSigningKey::linear_combination`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the new [`PublicKeyPackage`], or an
+    /// [`Error::InvalidShareCombination`] if the `packages` are empty, do not share the
+    /// same parameters and participants, or do not match the number of `coefficients`.
+    pub fn linear_combination(
+        packages: &[Self],
+        coefficients: &[crate::Scalar<C>],
+    ) -> FrostResult<C, Self> {
+        let first = packages.first().ok_or(Error::InvalidShareCombination)?;
+        if packages.len() != coefficients.len()
+            || packages.iter().any(|package| {
+                package.parameters != first.parameters
+                    || package.verifying_keys.len() != first.verifying_keys.len()
+            })
+        {
+            return Err(Error::InvalidShareCombination);
+        }
+
+        let group_key = packages
+            .iter()
+            .zip(coefficients)
+            .map(|(package, coefficient)| package.group_key.key * coefficient.0)
+            .sum();
+
+        let verifying_keys = (0..first.verifying_keys.len())
+            .map(|position| {
+                let shares: Vec<_> = packages
+                    .iter()
+                    .map(|package| package.verifying_keys[position].clone())
+                    .collect();
+                IndividualVerifyingKey::linear_combination(&shares, coefficients)
+            })
+            .collect::<FrostResult<C, Vec<_>>>()?;
+
+        Ok(Self {
+            parameters: first.parameters,
+            group_key: GroupVerifyingKey::new(Element(group_key)),
+            verifying_keys,
+        })
+    }
+
+    /// Serialize this [`PublicKeyPackage`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PublicKeyPackage`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the [`Fingerprint`] of this [`PublicKeyPackage`], for operators to
+    /// check out of band that they derived the same group and verifying keys.
+    pub fn fingerprint(&self) -> FrostResult<C, Fingerprint> {
+        Fingerprint::compute::<C>("public key package fingerprint", &self.to_bytes()?)
+    }
+
+    /// Derive the [`PublicKeyPackage`] of the usage `domain`, such as `"treasury"`,
+    /// of the group with this root package.
+    ///
+    /// Its group key is [`GroupVerifyingKey::domain`], and its verifying keys are the
+    /// public halves of the signing keys derived with [`IndividualSigningKey::domain`],
+    /// to be provided to the signature aggregator of sessions run in this `domain`.
+    pub fn domain(&self, domain: &str) -> FrostResult<C, Self> {
+        let verifying_keys = self
+            .verifying_keys
+            .iter()
+            .map(|key| key.domain(&self.group_key, domain))
+            .collect::<FrostResult<C, Vec<_>>>()?;
+
+        Ok(Self {
+            parameters: self.parameters,
+            group_key: self.group_key.domain(domain)?,
+            verifying_keys,
+        })
+    }
+
+    /// Randomize this [`PublicKeyPackage`] with the given `randomizer`.
+    ///
+    /// Its group key is [`GroupVerifyingKey::randomize`], and its verifying keys are
+    /// the public halves of the signing keys randomized with
+    /// [`IndividualSigningKey::randomize`], to be provided to the signature aggregator
+    /// of sessions signing under the randomized group key.
+    pub fn randomize(&self, randomizer: &KeyRandomizer<C>) -> Self {
+        Self {
+            parameters: self.parameters,
+            group_key: self.group_key.randomize(randomizer),
+            verifying_keys: self
+                .verifying_keys
+                .iter()
+                .map(|key| key.randomize(randomizer))
+                .collect(),
+        }
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PublicKeyPackage<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.group_key.validate_points()?;
+        self.verifying_keys
+            .iter()
+            .try_for_each(ValidatePoints::validate_points)
+    }
+}
+
+/// A randomizer \\( \alpha \\) of the keys of a group, shared by the signers and the
+/// verifier of a signing session under the randomized group key.
+///
+/// Randomizing adds \\( \alpha \\) to the signing key of every participant, and
+/// \\( \alpha \cdot G \\) to the group key and to the verifying keys. As Lagrange
+/// coefficients sum to one, the randomized signing keys are a sharing of the signing
+/// key of the randomized group key.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct KeyRandomizer<C: CipherSuite>(pub(crate) Scalar<C>);
+
+impl<C: CipherSuite> KeyRandomizer<C> {
+    /// Sample a new random [`KeyRandomizer`].
+    pub fn new(mut rng: impl RngCore + CryptoRng) -> Self {
+        Self(Scalar::<C>::rand(&mut rng))
+    }
+
+    /// Instantiates a [`KeyRandomizer`] from the given `randomizer`, such as one
+    /// chosen by a wallet for a specific transaction.
+    pub fn from_scalar(randomizer: crate::Scalar<C>) -> Self {
+        Self(randomizer.0)
+    }
+
+    /// Serialize this [`KeyRandomizer`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`KeyRandomizer`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+impl<C: CipherSuite> Drop for KeyRandomizer<C> {
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
+
+/// Verify that the `verifying_keys` of all the participants of a group with the given
+/// `parameters` are consistent with its `group_key`, so that registries accepting the
+/// public material of a group can validate it wholesale.
+///
+/// The verifying keys are consistent if they lie on a single polynomial of degree
+/// \\(t - 1\\) in the exponent whose value at zero is the group key, i.e. if any \\(t\\)
+/// of them interpolate to the group key. This is checked by interpolating the polynomial
+/// from the \\(t\\) verifying keys of smallest indices, and evaluating it at zero and at
+/// the indices of all the other verifying keys.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is empty if the verifying keys are consistent,
+/// otherwise an [`Error::InvalidNumberOfParticipants`] if there are not `n` of them, an
+/// [`Error::IndexIsZero`] or an [`Error::Custom`] if their indices are zero or duplicate,
+/// or an [`Error::InvalidGroupKey`] if they are not consistent with the `group_key`.
+pub fn verify_key_consistency<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+    verifying_keys: &[IndividualVerifyingKey<C>],
+    parameters: &ThresholdParameters<C>,
+) -> FrostResult<C, ()> {
+    parameters.validate()?;
+    if verifying_keys.len() != parameters.n as usize {
+        return Err(Error::InvalidNumberOfParticipants(
+            verifying_keys.len(),
+            parameters.n,
+        ));
+    }
+
+    let mut verifying_keys: Vec<&IndividualVerifyingKey<C>> = verifying_keys.iter().collect();
+    verifying_keys.sort_by_key(|key| key.index);
+    if verifying_keys[0].index == 0 {
+        return Err(Error::IndexIsZero);
+    }
+    if verifying_keys
+        .windows(2)
+        .any(|pair| pair[0].index == pair[1].index)
+    {
+        return Err(Error::DuplicateIndices);
+    }
+
+    let (basis, others) = verifying_keys.split_at(parameters.t as usize);
+    let basis_indices: Vec<Scalar<C>> = basis
+        .iter()
+        .map(|key| Scalar::<C>::from(key.index))
+        .collect();
+    let basis_points =
+        points_to_affine_batch::<C>(&basis.iter().map(|key| key.share).collect::<Vec<_>>());
+
+    // The barycentric weights 1 / prod_{k != i} (x_i - x_k) of the interpolation basis.
+    let mut weights: Vec<Scalar<C>> = basis_indices
+        .iter()
+        .map(|x_i| {
+            basis_indices
+                .iter()
+                .filter(|x_k| *x_k != x_i)
+                .map(|x_k| *x_i - x_k)
+                .product()
+        })
+        .collect();
+    ark_ff::batch_inversion(&mut weights);
+
+    let evaluations = core::iter::once((Scalar::<C>::zero(), group_key.key)).chain(
+        others
+            .iter()
+            .map(|key| (Scalar::<C>::from(key.index), key.share)),
+    );
+    for (x, expected) in evaluations {
+        // As x is not an index of the basis, its Lagrange coefficients are
+        // w_i * prod_k (x - x_k) / (x - x_i).
+        let mut differences: Vec<Scalar<C>> = basis_indices.iter().map(|x_k| x - x_k).collect();
+        let product: Scalar<C> = differences.iter().product();
+        ark_ff::batch_inversion(&mut differences);
+        let coefficients: Vec<Scalar<C>> = weights
+            .iter()
+            .zip(differences)
+            .map(|(weight, difference)| *weight * product * difference)
+            .collect();
+
+        if C::msm(&basis_points, &coefficients)? != expected {
+            return Err(Error::InvalidGroupKey);
+        }
+    }
+
+    Ok(())
+}
@@ -16,12 +16,13 @@
 //!
 //! For this, they need to define a [`CipherSuite`] to be used in the DKG and signing sessions.
 //! This CipherSuite is used to parameterize ICE-FROST over an arbitrary curve backend, with
-//! an arbitrary underlying hasher instantiating all random oracles.
+//! an arbitrary underlying hasher instantiating all random oracles, and an arbitrary
+//! authenticated encryption scheme for the secret shares exchanged during key generation.
 //! The following example creates an ICE-FROST CipherSuite over the Secp256k1 curve,
-//! with SHA-256 as internal hash function.
+//! with SHA-256 as internal hash function and AES-256-GCM as share cipher.
 //!
 //! ```rust
-//! use ice_frost::CipherSuite;
+//! use ice_frost::{Aes256Gcm, CipherSuite};
 //! use sha2::Sha256;
 //! use zeroize::Zeroize;
 //! use ark_secp256k1::Projective as G;
@@ -36,6 +37,8 @@
 //!
 //!     type InnerHasher = Sha256;
 //!
+//!     type ShareCipher = Aes256Gcm;
+//!
 //!     fn context_string() -> String {
 //!         "ICE-FROST_SECP256K1_SHA256".to_owned()
 //!     }
@@ -49,9 +52,12 @@
 //!
 //! ```rust
 //! # use ice_frost::testing::Secp256k1Sha256;
+//! # use ice_frost::FrostResult;
 //! use ice_frost::parameters::ThresholdParameters;
 //!
-//! let params = ThresholdParameters::<Secp256k1Sha256>::new(3,2);
+//! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
+//! let params = ThresholdParameters::<Secp256k1Sha256>::new(3,2)?;
+//! # Ok(()) } fn main() { assert!(do_test().is_ok()); }
 //! ```
 //!
 //! ## Distributed Key Generation
@@ -66,7 +72,7 @@
 //! ```rust
 //! # use ice_frost::parameters::ThresholdParameters;
 //! # use ice_frost::FrostResult;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use rand::rngs::OsRng;
 //! # use ark_secp256k1::Projective as G;
@@ -74,7 +80,7 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //!
 //! // All ICE-FROST methods requiring a source of entropy should use a cryptographic pseudorandom
@@ -96,7 +102,7 @@
 //! ```rust
 //! use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -105,14 +111,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //!
-//! let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! let (alice_state, participant_lists) =
 //!     DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(
 //!         &params,
@@ -130,7 +136,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -140,14 +146,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -159,7 +165,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -169,14 +175,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! let (bob_state, participant_lists) =
 //!     DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(
 //!         &params,
@@ -188,14 +194,14 @@
 //!     )?;
 //! # Ok(()) }
 //! # fn do_test2() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (bob_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &bob_dh_sk, &bob.index, &bob_coefficients,
 //! #                                                    &participants, &mut rng)?;
 //!
@@ -211,7 +217,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -221,14 +227,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! let (carol_state, participant_lists) =
 //!     DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(
 //!         &params,
@@ -240,14 +246,14 @@
 //!     )?;
 //! # Ok(()) }
 //! # fn do_test2() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (carol_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &carol_dh_sk, &carol.index, &carol_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //!
@@ -263,7 +269,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -273,14 +279,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -310,7 +316,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -320,14 +326,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -363,7 +369,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -373,14 +379,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -429,7 +435,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -439,7 +445,7 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
@@ -448,7 +454,7 @@
 //!
 //! // Perform regular 2-out-of-3 DKG...
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -482,14 +488,14 @@
 //! # assert!(carol_group_key == bob_group_key);
 //! #
 //! // Instantiate new configuration parameters and create a new set of signers
-//! let new_params = ThresholdParameters::new(4,3);
+//! let new_params = ThresholdParameters::new(4,3)?;
 //!
 //! let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, &mut rng)?;
 //! let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, &mut rng)?;
 //! let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, &mut rng)?;
 //! let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, &mut rng)?;
 //!
-//! let signers: Vec<Participant<Secp256k1Sha256>> =
+//! let signers: Vec<Participant<Secp256k1Sha256, SignerRole>> =
 //!     vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //!
 //! let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
@@ -509,7 +515,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -519,14 +525,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -560,14 +566,14 @@
 //! # assert!(carol_group_key == bob_group_key);
 //! #
 //! # // Instantiate new configuration parameters and create a set of signers
-//! # let new_params = ThresholdParameters::new(4,3);
+//! # let new_params = ThresholdParameters::new(4,3)?;
 //! #
 //! # let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, &mut rng)?;
 //! # let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, &mut rng)?;
 //! # let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, &mut rng)?;
 //! # let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, &mut rng)?;
 //! #
-//! # let signers: Vec<Participant<Secp256k1Sha256>> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
+//! # let signers: Vec<Participant<Secp256k1Sha256, SignerRole>> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! # let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, alice_secret_key, &signers, &mut rng)?;
 //! # let (bob_as_dealer, bob_encrypted_shares, participant_lists) =
@@ -575,7 +581,7 @@
 //! # let (carol_as_dealer, carol_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, carol_secret_key, &signers, &mut rng)?;
 //! #
-//! let dealers: Vec<Participant<Secp256k1Sha256>> =
+//! let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> =
 //!     vec!(alice_as_dealer.clone(), bob_as_dealer.clone(), carol_as_dealer.clone());
 //!
 //! let (alexis_state, participant_lists) =
@@ -624,7 +630,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -634,14 +640,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -675,14 +681,14 @@
 //! # assert!(carol_group_key == bob_group_key);
 //! #
 //! # // Instantiate new configuration parameters and create a set of signers
-//! # let new_params = ThresholdParameters::new(4,3);
+//! # let new_params = ThresholdParameters::new(4,3)?;
 //! #
 //! # let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, &mut rng)?;
 //! # let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, &mut rng)?;
 //! # let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, &mut rng)?;
 //! # let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, &mut rng)?;
 //! #
-//! # let signers: Vec<Participant<Secp256k1Sha256>> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
+//! # let signers: Vec<Participant<Secp256k1Sha256, SignerRole>> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! # let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, alice_secret_key, &signers, &mut rng)?;
 //! # let (bob_as_dealer, bob_encrypted_shares, participant_lists) =
@@ -690,7 +696,7 @@
 //! # let (carol_as_dealer, carol_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, carol_secret_key, &signers, &mut rng)?;
 //! #
-//! # let dealers: Vec<Participant<Secp256k1Sha256>> =
+//! # let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> =
 //! #     vec!(alice_as_dealer.clone(), bob_as_dealer.clone(), carol_as_dealer.clone());
 //! # let (alexis_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::new(&params, &alexis_dh_sk, &alexis.index,
 //! #                                                    &dealers, &mut rng)?;
@@ -731,7 +737,7 @@
 //! ```rust
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::CipherSuite;
 //! # use ice_frost::FrostResult;
 //! # use rand::rngs::OsRng;
@@ -741,14 +747,14 @@
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -781,14 +787,14 @@
 //! # assert!(alice_group_key == bob_group_key);
 //! # assert!(carol_group_key == bob_group_key);
 //! #
-//! # let new_params = ThresholdParameters::new(4,3);
+//! # let new_params = ThresholdParameters::new(4,3)?;
 //! #
 //! # let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, &mut rng)?;
 //! # let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, &mut rng)?;
 //! # let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, &mut rng)?;
 //! # let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, &mut rng)?;
 //! #
-//! # let signers: Vec<Participant<Secp256k1Sha256>> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
+//! # let signers: Vec<Participant<Secp256k1Sha256, SignerRole>> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! # let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, alice_secret_key, &signers, &mut rng)?;
 //! # let (bob_as_dealer, bob_encrypted_shares, participant_lists) =
@@ -796,7 +802,7 @@
 //! # let (carol_as_dealer, carol_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, carol_secret_key, &signers, &mut rng)?;
 //! #
-//! # let dealers: Vec<Participant<Secp256k1Sha256>> = vec!(alice_as_dealer.clone(), bob_as_dealer.clone(), carol_as_dealer.clone());
+//! # let dealers: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice_as_dealer.clone(), bob_as_dealer.clone(), carol_as_dealer.clone());
 //! # let (alexis_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::new(&params, &alexis_dh_sk, &alexis.index,
 //! #                                                    &dealers, &mut rng)?;
 //! #
@@ -854,20 +860,20 @@
 //! # use ice_frost::FrostResult;
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! use ice_frost::sign::SignatureAggregator;
 //!
 //! use rand::rngs::OsRng;
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -902,11 +908,11 @@
 //! # let carol_public_key = carol_secret_key.to_public();
 //!
 //! let (alice_public_comshares, mut alice_secret_comshares) =
-//!     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1);
+//!     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1)?;
 //! let (bob_public_comshares, mut bob_secret_comshares) =
-//!     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1);
+//!     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1)?;
 //! let (carol_public_comshares, mut carol_secret_comshares) =
-//!     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1);
+//!     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1)?;
 //!
 //! let message = b"This is a test of the tsunami alert system. This is only a test.";
 //!
@@ -916,6 +922,7 @@
 //!         params,
 //!         bob_group_key.clone(),
 //!         &message[..],
+//!         None,
 //!     );
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
@@ -931,21 +938,21 @@
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::keys::IndividualVerifyingKey;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! # use ice_frost::sign::SignatureAggregator;
 //! #
 //! # use rand::rngs::OsRng;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -980,18 +987,18 @@
 //! # let carol_public_key = carol_secret_key.to_public();
 //! #
 //! # let (alice_public_comshares, mut alice_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1)?;
 //! # let (bob_public_comshares, mut bob_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1)?;
 //! # let (carol_public_comshares, mut carol_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1)?;
 //! #
 //! # let message = b"This is a test of the tsunami alert system. This is only a test.";
 //! #
-//! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &message[..]);
+//! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &message[..], None);
 //! #
-//! aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key);
-//! aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key);
+//! aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key)?;
+//! aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key)?;
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -1005,21 +1012,21 @@
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::keys::IndividualVerifyingKey;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! # use ice_frost::sign::SignatureAggregator;
 //! #
 //! # use rand::rngs::OsRng;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;;
@@ -1054,18 +1061,18 @@
 //! # let carol_public_key = carol_secret_key.to_public();
 //! #
 //! # let (alice_public_comshares, mut alice_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1)?;
 //! # let (bob_public_comshares, mut bob_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1)?;
 //! # let (carol_public_comshares, mut carol_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1)?;
 //! #
 //! # let message = b"This is a test of the tsunami alert system. This is only a test.";
 //! #
-//! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &message[..]);
+//! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &message[..], None);
 //! #
-//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key);
-//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key);
+//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key)?;
+//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key)?;
 //! let signers = aggregator.get_signers();
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
@@ -1079,21 +1086,21 @@
 //! # use ice_frost::FrostResult;
 //! # use ice_frost::dkg::DistributedKeyGeneration;
 //! # use ice_frost::parameters::ThresholdParameters;
-//! # use ice_frost::dkg::Participant;
+//! # use ice_frost::dkg::{DealerRole, Participant, SignerRole};
 //! # use ice_frost::testing::Secp256k1Sha256;
 //! # use ice_frost::sign::SignatureAggregator;
 //! #
 //! # use rand::rngs::OsRng;
 //! #
 //! # fn do_test() -> FrostResult<Secp256k1Sha256, ()> {
-//! # let params = ThresholdParameters::new(3,2);
+//! # let params = ThresholdParameters::new(3,2)?;
 //! # let mut rng = OsRng;
 //! #
 //! # let (alice, alice_coefficients, alice_dh_sk) = Participant::new_dealer(&params, 1, &mut rng)?;
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, &mut rng)?;
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, &mut rng)?;
 //! #
-//! # let participants: Vec<Participant<Secp256k1Sha256>> = vec!(alice.clone(), bob.clone(), carol.clone());
+//! # let participants: Vec<Participant<Secp256k1Sha256, DealerRole>> = vec!(alice.clone(), bob.clone(), carol.clone());
 //! # let (alice_state, participant_lists) = DistributedKeyGeneration::<_, Secp256k1Sha256>::bootstrap(&params, &alice_dh_sk, &alice.index, &alice_coefficients,
 //! #                                                      &participants, &mut rng)?;
 //! # let alice_their_encrypted_secret_shares = alice_state.their_encrypted_secret_shares()?;
@@ -1128,18 +1135,18 @@
 //! # let carol_public_key = carol_secret_key.to_public();
 //! #
 //! # let (alice_public_comshares, mut alice_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &alice_secret_key, 1)?;
 //! # let (bob_public_comshares, mut bob_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &bob_secret_key, 1)?;
 //! # let (carol_public_comshares, mut carol_secret_comshares) =
-//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1);
+//! #     generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &carol_secret_key, 1)?;
 //! #
 //! # let message = b"This is a test of the tsunami alert system. This is only a test.";
 //! #
-//! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &message[..]);
+//! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &message[..], None);
 //! #
-//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], (&alice_secret_key).into());
-//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], (&carol_secret_key).into());
+//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], (&alice_secret_key).into())?;
+//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], (&carol_secret_key).into())?;
 //! #
 //! # let signers = aggregator.get_signers();
 //! # let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1149,8 +1156,8 @@
 //! let carol_partial = carol_secret_key.sign(&message_hash, &carol_group_key,
 //!                                           &mut carol_secret_comshares, 0, signers)?;
 //!
-//! aggregator.include_partial_signature(alice_partial);
-//! aggregator.include_partial_signature(carol_partial);
+//! aggregator.include_partial_signature(alice_partial)?;
+//! aggregator.include_partial_signature(carol_partial)?;
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -1195,6 +1202,15 @@
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable
+    )
+)]
 #![warn(future_incompatible)]
 #![allow(clippy::type_complexity)]
 
@@ -1207,8 +1223,14 @@ extern crate alloc;
 
 pub(crate) const HASH_SEC_PARAM: usize = 128;
 
+#[macro_use]
+mod trace;
+
+#[cfg(all(test, feature = "zeroize-audit"))]
+mod zeroize_audit;
+
 mod error;
-pub use error::{Error, FrostResult};
+pub use error::{CipherErrorKind, Error, FrostResult, SerializationErrorKind};
 
 /// A module defining the different key types used by an ICE-FROST instance.
 pub mod keys;
@@ -1216,10 +1238,27 @@ pub mod keys;
 pub mod parameters;
 
 mod ciphersuite;
-pub use ciphersuite::CipherSuite;
+pub use ciphersuite::{
+    self_test, self_test_with_vectors, CipherSuite, HashOutputPolicy, HashToFieldVector,
+    ScalarHasher, XofFieldHasher,
+};
+
+mod group;
+pub use group::{Element, Scalar};
+
+mod validation;
+pub use validation::PointValidationPolicy;
+
+mod share_cipher;
+pub use share_cipher::{Aes256Gcm, ShareCipher, XChaCha20Poly1305};
 
 pub(crate) mod utils;
 
+#[cfg(feature = "rand_core_09")]
+mod rng;
+#[cfg(feature = "rand_core_09")]
+pub use rng::RandCoreCompat;
+
 /// A module defining the logic of an ICE-FROST instance's distributed key generation session.
 ///
 /// This module is also used in the context of key resharing, between two (potentially disjoint)
@@ -1228,32 +1267,188 @@ pub mod dkg;
 /// A module defining the logic of an ICE-FROST signing session.
 pub mod sign;
 
+/// A module defining the [`EpochedKeyManager`](crate::epoch::EpochedKeyManager), for rotating
+/// the public key material of an ICE-FROST group across resharing sessions.
+pub mod epoch;
+
+/// A module for escrowing the signing key share of a participant with an auditor,
+/// as a publicly verifiable encryption of the share.
+pub mod escrow;
+
+/// A module rendering short fingerprints of public key material, for operators
+/// to compare them out of band after a distributed key generation session.
+pub mod fingerprint;
+
+/// A module framing the messages of ICE-FROST sessions, to carry distributed key
+/// generation and signing traffic over a single multiplexed network channel.
+pub mod message;
+
+/// A module encoding group public keys as X.509 `SubjectPublicKeyInfo` structures,
+/// to enroll ICE-FROST groups in existing PKI tooling.
+pub mod spki;
+
+/// A module for running ICE-FROST sessions entirely through a publicly readable
+/// bulletin board, without point-to-point messages between participants.
+pub mod bulletin;
+
+/// A module for negotiating the wire version and ciphersuite of an ICE-FROST
+/// session, with protection against downgrade attacks.
+pub mod negotiation;
+
+/// A module exposing the key shares of ICE-FROST groups to other threshold signature
+/// schemes, such as threshold ECDSA, through the [`ThresholdScheme`](crate::threshold::ThresholdScheme)
+/// trait.
+pub mod threshold;
+
+/// A module providing a threshold BLS signing mode, sharing the distributed key
+/// generation of ICE-FROST over pairing-friendly curves.
+#[cfg(feature = "bls")]
+pub mod bls;
+
+/// A module providing helpers for signing the messages of external protocols,
+/// such as Ethereum's EIP-712 typed structured data, OpenSSH certificates or
+/// Tendermint consensus votes, with ICE-FROST.
+pub mod interop;
+
+/// A module providing `tokio`-based reference coordinator and signer services,
+/// running the ROAST loop over pluggable transports and keystores.
+#[cfg(feature = "daemon")]
+pub mod daemon;
+
+/// A module for backing up the Diffie-Hellman private key of a participant as
+/// printable word-mnemonic shares, and for restoring it.
+#[cfg(feature = "backup")]
+pub mod backup;
+
+/// A module encoding protocol frames compactly with `postcard`, for constrained links.
+#[cfg(feature = "compact-wire")]
+pub mod compact_wire;
+
+/// A module describing the byte-level layouts of the serialized protocol types,
+/// emitted as JSON for implementations in other languages.
+#[cfg(feature = "wire-spec")]
+pub mod wire_spec;
+
+/// A module exposing distributed key generation and signing ceremonies as a Python
+/// extension module, with `bytes` inputs and outputs.
+#[cfg(feature = "python")]
+pub mod python;
+
 /// This module provides a concrete implementation of an ICE-FROST CipherSuite over Secp256k1,
-/// with SHA-256 as underlying base hash function.
+/// with SHA-256 as underlying base hash function and AES-256-GCM for secret shares encryption.
 /// It is made available for testing and benchmarking purposes.
+///
+/// With the `blake3` feature, an instantiation over Secp256k1 with BLAKE3 as underlying hash
+/// function is provided as well, and with the `p256` feature, an instantiation over
+/// secp256r1 (P-256) with SHA-256 as underlying hash function. With the `bls` feature, an
+/// instantiation over the first source group of BLS12-381 is provided, supporting the
+/// threshold BLS signing mode of the `bls` module.
+///
+/// The [`adversarial`](crate::testing::adversarial) submodule additionally provides builders
+/// for misbehaving participants, to test the handling of cheating entities, and the
+/// [`conformance`](crate::testing::conformance) submodule provides checks for custom ciphersuites.
+/// The [`simulator`](crate::testing::simulator) submodule simulates network partitions, and
+/// exposes the invariants checked against their outcomes.
 pub mod testing {
     use super::*;
 
-    use ark_secp256k1::Projective as G;
+    pub mod adversarial;
+    pub mod conformance;
+    pub mod simulator;
+
+    define_ciphersuite! {
+        /// An example instance of ICE-FROST over Secp256k1 with SHA-256 as underlying hasher.
+        pub struct Secp256k1Sha256 {
+            group: ark_secp256k1::Projective,
+            hasher: sha2::Sha256,
+            hash_output: [u8; 32],
+            share_cipher: Aes256Gcm,
+            context_string: "ICE-FROST_SECP256K1_SHA256",
+            spki_algorithm: crate::spki::SpkiAlgorithm::Secp256k1,
+            conformance_tests: secp256k1_sha256_conformance,
+        }
+    }
+
+    #[cfg(feature = "blake3")]
+    define_ciphersuite! {
+        /// An example instance of ICE-FROST over Secp256k1 with BLAKE3 as underlying hasher.
+        ///
+        /// Scalars are derived from BLAKE3 used as an extendable-output function,
+        /// through `expand_message_xof`.
+        pub struct Secp256k1Blake3 {
+            group: ark_secp256k1::Projective,
+            hasher: blake3::Hasher,
+            hash_output: [u8; 32],
+            share_cipher: Aes256Gcm,
+            context_string: "ICE-FROST_SECP256K1_BLAKE3",
+            field_hasher: XofFieldHasher<blake3::Hasher, { HASH_SEC_PARAM }>,
+            spki_algorithm: crate::spki::SpkiAlgorithm::Secp256k1,
+            conformance_tests: secp256k1_blake3_conformance,
+        }
+    }
 
-    use sha2::Sha256;
-    use utils::{String, ToOwned};
+    #[cfg(feature = "p256")]
+    define_ciphersuite! {
+        /// An example instance of ICE-FROST over secp256r1 (P-256) with SHA-256 as underlying hasher.
+        pub struct P256Sha256 {
+            group: ark_secp256r1::Projective,
+            hasher: sha2::Sha256,
+            hash_output: [u8; 32],
+            share_cipher: Aes256Gcm,
+            context_string: "ICE-FROST_P256_SHA256",
+            spki_algorithm: crate::spki::SpkiAlgorithm::P256,
+            conformance_tests: p256_sha256_conformance,
+        }
+    }
 
-    use zeroize::Zeroize;
+    #[cfg(feature = "bls")]
+    define_ciphersuite! {
+        /// An example instance of ICE-FROST over the first source group of BLS12-381 with
+        /// SHA-256 as underlying hasher, supporting threshold BLS signatures.
+        pub struct Bls12381G1Sha256 {
+            group: ark_bls12_381::G1Projective,
+            hasher: sha2::Sha256,
+            hash_output: [u8; 32],
+            share_cipher: Aes256Gcm,
+            context_string: "ICE-FROST_BLS12381G1_SHA256",
+            conformance_tests: bls12_381_g1_sha256_conformance,
+        }
+    }
 
-    #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Zeroize)]
-    /// An example instance of ICE-FROST over Secp256k1 with SHA-256 as underlying hasher.
-    pub struct Secp256k1Sha256;
+    #[cfg(feature = "bls")]
+    impl crate::bls::BlsCipherSuite for Bls12381G1Sha256 {
+        type Engine = ark_bls12_381::Bls12_381;
 
-    impl CipherSuite for Secp256k1Sha256 {
-        type G = G;
+        fn hash_to_g2(message: &[u8]) -> FrostResult<Self, ark_bls12_381::G2Projective> {
+            use ark_ec::hashing::curve_maps::wb::WBMap;
+            use ark_ec::hashing::map_to_curve_hasher::MapToCurveBasedHasher;
+            use ark_ec::hashing::HashToCurve;
+            use ark_ff::field_hashers::DefaultFieldHasher;
 
-        type HashOutput = [u8; 32];
+            use crate::utils::ToString;
 
-        type InnerHasher = Sha256;
+            let hasher = MapToCurveBasedHasher::<
+                ark_bls12_381::G2Projective,
+                DefaultFieldHasher<sha2::Sha256, HASH_SEC_PARAM>,
+                WBMap<ark_bls12_381::g2::Config>,
+            >::new((Self::context_string() + "bls").as_bytes())
+            .map_err(|_| Error::Custom("Invalid hash-to-curve parameters".to_string()))?;
 
-        fn context_string() -> String {
-            "ICE-FROST_SECP256K1_SHA256".to_owned()
+            hasher
+                .hash(message)
+                .map(Into::into)
+                .map_err(|_| Error::Custom("Could not hash the message to G2".to_string()))
         }
     }
 }
+
+#[doc(hidden)]
+pub mod __private {
+    //! Re-exports used by the [`define_ciphersuite!`](crate::define_ciphersuite!) macro.
+
+    pub use crate::ciphersuite::hash_to_field_with;
+    pub use crate::utils::{Scalar, String, ToOwned};
+    pub use digest::typenum::Unsigned;
+    pub use digest::OutputSizeUser;
+    pub use zeroize::Zeroize;
+}
@@ -0,0 +1,459 @@
+//! The message module, framing the messages of ICE-FROST distributed key
+//! generation and signing sessions for a single multiplexed network channel.
+//!
+//! Each [`ProtocolMessage`] is wrapped into a [`ProtocolFrame`], whose header
+//! carries the index of its sender, the identifier of the session it belongs to,
+//! and the [`ProtocolRound`] tag of the message. Receivers can thus route frames
+//! to the right session, and dispatch them with an exhaustive `match` on the
+//! deserialized [`ProtocolMessage`].
+//!
+//! The round, session and epoch of a frame form its [`RoundTag`], which state machines
+//! check against the tag they expect with [`ProtocolFrame::open`], so that a message
+//! replayed into another round, session or epoch is rejected before being processed.
+//!
+//! [`ProtocolMessage`]: crate::message::ProtocolMessage
+//! [`ProtocolFrame`]: crate::message::ProtocolFrame
+//! [`ProtocolRound`]: crate::message::ProtocolRound
+//! [`RoundTag`]: crate::message::RoundTag
+//! [`ProtocolFrame::open`]: crate::message::ProtocolFrame::open
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::{Complaint, EncryptedSecretShare, Participant};
+use crate::sign::{Heartbeat, PartialThresholdSignature, PublicCommitmentShareList, ReRequest};
+use crate::utils::{vec, String, ToString, Vec};
+use crate::validation::ValidatePoints;
+use crate::{Error, FrostResult};
+
+/// The round of an ICE-FROST session a [`ProtocolMessage`] belongs to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProtocolRound {
+    /// The broadcast of the participants of a distributed key generation session.
+    DkgRound1,
+    /// The distribution of the encrypted secret shares of a distributed key generation session.
+    DkgRound2,
+    /// The complaints against misbehaving dealers of a distributed key generation session.
+    Complaint,
+    /// The publication of the commitment shares of signers.
+    SignCommit,
+    /// The partial signatures of a signing session.
+    SignPartial,
+    /// The re-requests of partial signatures of a signing session.
+    SignReRequest,
+    /// The abort of a session.
+    Abort,
+    /// The liveness heartbeats of signers.
+    Heartbeat,
+}
+
+impl ProtocolRound {
+    /// The tag identifying this round on the wire.
+    pub const fn to_byte(self) -> u8 {
+        match self {
+            ProtocolRound::DkgRound1 => 0,
+            ProtocolRound::DkgRound2 => 1,
+            ProtocolRound::Complaint => 2,
+            ProtocolRound::SignCommit => 3,
+            ProtocolRound::SignPartial => 4,
+            ProtocolRound::SignReRequest => 5,
+            ProtocolRound::Abort => 6,
+            ProtocolRound::Heartbeat => 7,
+        }
+    }
+
+    /// The round identified by the given wire `tag`, if any.
+    pub const fn from_byte(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(ProtocolRound::DkgRound1),
+            1 => Some(ProtocolRound::DkgRound2),
+            2 => Some(ProtocolRound::Complaint),
+            3 => Some(ProtocolRound::SignCommit),
+            4 => Some(ProtocolRound::SignPartial),
+            5 => Some(ProtocolRound::SignReRequest),
+            6 => Some(ProtocolRound::Abort),
+            7 => Some(ProtocolRound::Heartbeat),
+            _ => None,
+        }
+    }
+}
+
+/// The typed token of the round of a session a message belongs to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundTag {
+    /// The round of the message.
+    pub round: ProtocolRound,
+    /// The identifier of the session of the message.
+    pub session_id: Vec<u8>,
+    /// The epoch of the key material of the session.
+    pub epoch: u64,
+}
+
+impl RoundTag {
+    /// Tag the given `round` of the session with the given `session_id` and `epoch`.
+    pub fn new(round: ProtocolRound, session_id: &[u8], epoch: u64) -> Self {
+        Self {
+            round,
+            session_id: session_id.to_vec(),
+            epoch,
+        }
+    }
+
+    /// Encode this [`RoundTag`], to be hashed or signed along with the message it tags.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.round.to_byte()];
+        bytes.extend_from_slice(&self.epoch.to_le_bytes());
+        bytes.extend_from_slice(&(self.session_id.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.session_id);
+
+        bytes
+    }
+
+    /// Check that the `received` tag of a message is this expected tag.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that both tags match, otherwise
+    /// an [`Error::UnexpectedRoundTag`] holding both tags.
+    pub fn check<C: CipherSuite>(&self, received: &RoundTag) -> FrostResult<C, ()> {
+        match self == received {
+            true => Ok(()),
+            false => Err(Error::UnexpectedRoundTag(self.clone(), received.clone())),
+        }
+    }
+}
+
+impl core::fmt::Display for RoundTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "round {:?} of session {:?} at epoch {}",
+            self.round, self.session_id, self.epoch
+        )
+    }
+}
+
+/// A message of an ICE-FROST distributed key generation or signing session.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ProtocolMessage<C: CipherSuite> {
+    /// A participant of a distributed key generation session.
+    DkgRound1(Participant<C>),
+    /// The encrypted secret shares of a dealer.
+    DkgRound2(Vec<EncryptedSecretShare<C>>),
+    /// A complaint against a misbehaving dealer.
+    Complaint(Complaint<C>),
+    /// The published commitment shares of a signer.
+    SignCommit(PublicCommitmentShareList<C>),
+    /// The partial signature of a signer.
+    SignPartial(PartialThresholdSignature<C>),
+    /// A re-request of a partial signature by a signature aggregator.
+    SignReRequest(ReRequest<C>),
+    /// The abort of a session by its sender, along with the reason.
+    Abort(String),
+    /// The liveness heartbeat of a signer.
+    Heartbeat(Heartbeat<C>),
+}
+
+impl<C: CipherSuite> ProtocolMessage<C> {
+    /// The round this message belongs to.
+    pub const fn round(&self) -> ProtocolRound {
+        match self {
+            Self::DkgRound1(_) => ProtocolRound::DkgRound1,
+            Self::DkgRound2(_) => ProtocolRound::DkgRound2,
+            Self::Complaint(_) => ProtocolRound::Complaint,
+            Self::SignCommit(_) => ProtocolRound::SignCommit,
+            Self::SignPartial(_) => ProtocolRound::SignPartial,
+            Self::SignReRequest(_) => ProtocolRound::SignReRequest,
+            Self::Abort(_) => ProtocolRound::Abort,
+            Self::Heartbeat(_) => ProtocolRound::Heartbeat,
+        }
+    }
+
+    fn serialize_payload(&self, bytes: &mut Vec<u8>) -> FrostResult<C, ()> {
+        match self {
+            Self::DkgRound1(participant) => participant.serialize_compressed(bytes),
+            Self::DkgRound2(shares) => shares.serialize_compressed(bytes),
+            Self::Complaint(complaint) => complaint.serialize_compressed(bytes),
+            Self::SignCommit(commitments) => commitments.serialize_compressed(bytes),
+            Self::SignPartial(partial_signature) => partial_signature.serialize_compressed(bytes),
+            Self::SignReRequest(request) => request.to_bytes()?.serialize_compressed(bytes),
+            Self::Abort(reason) => reason.serialize_compressed(bytes),
+            Self::Heartbeat(heartbeat) => heartbeat.serialize_compressed(bytes),
+        }
+        .map_err(|e| Error::SerializationError(e.into()))
+    }
+
+    fn deserialize_payload(round: ProtocolRound, reader: &mut &[u8]) -> FrostResult<C, Self> {
+        let message = match round {
+            ProtocolRound::DkgRound1 => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::DkgRound1)
+            }
+            ProtocolRound::DkgRound2 => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::DkgRound2)
+            }
+            ProtocolRound::Complaint => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::Complaint)
+            }
+            ProtocolRound::SignCommit => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::SignCommit)
+            }
+            ProtocolRound::SignPartial => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::SignPartial)
+            }
+            ProtocolRound::SignReRequest => {
+                let request = Vec::<u8>::deserialize_compressed(reader)
+                    .map_err(|e| Error::DeserializationError(e.into()))?;
+                return Ok(Self::SignReRequest(ReRequest::from_bytes(&request)?));
+            }
+            ProtocolRound::Abort => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::Abort)
+            }
+            ProtocolRound::Heartbeat => {
+                CanonicalDeserialize::deserialize_compressed(reader).map(Self::Heartbeat)
+            }
+        };
+
+        message.map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for ProtocolMessage<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        match self {
+            Self::DkgRound1(participant) => participant.validate_points(),
+            Self::Complaint(complaint) => complaint.validate_points(),
+            Self::SignCommit(commitments) => commitments.validate_points(),
+            Self::Heartbeat(heartbeat) => heartbeat.validate_points(),
+            Self::DkgRound2(_) | Self::SignPartial(_) | Self::SignReRequest(_) | Self::Abort(_) => {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A [`ProtocolMessage`] along with the header routing it to its session.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ProtocolFrame<C: CipherSuite> {
+    /// The index of the participant sending this frame.
+    pub(crate) sender: u32,
+    /// The identifier of the session this frame belongs to.
+    pub(crate) session_id: Vec<u8>,
+    /// The epoch of the key material of the session this frame belongs to.
+    pub(crate) epoch: u64,
+    /// The framed message.
+    pub(crate) message: ProtocolMessage<C>,
+}
+
+impl<C: CipherSuite> ProtocolFrame<C> {
+    /// Frame the given `message` of the participant with index `sender`, for
+    /// the session with the given `session_id`, at epoch 0.
+    pub fn new(sender: u32, session_id: &[u8], message: ProtocolMessage<C>) -> Self {
+        Self {
+            sender,
+            session_id: session_id.to_vec(),
+            epoch: 0,
+            message,
+        }
+    }
+
+    /// Set the epoch of the key material of the session this frame belongs to.
+    pub fn with_epoch(mut self, epoch: u64) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// The index of the participant sending this frame.
+    pub fn sender(&self) -> u32 {
+        self.sender
+    }
+
+    /// The identifier of the session this frame belongs to.
+    pub fn session_id(&self) -> &[u8] {
+        &self.session_id
+    }
+
+    /// The epoch of the key material of the session this frame belongs to.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The round of the framed message.
+    pub fn round(&self) -> ProtocolRound {
+        self.message.round()
+    }
+
+    /// The [`RoundTag`] of this frame.
+    pub fn round_tag(&self) -> RoundTag {
+        RoundTag::new(self.round(), &self.session_id, self.epoch)
+    }
+
+    /// Unwrap the framed message, provided that the [`RoundTag`] of this frame is
+    /// the `expected` one of the state machine receiving it.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the framed message, otherwise an
+    /// [`Error::UnexpectedRoundTag`] if the message was sent for another round,
+    /// session or epoch.
+    pub fn open(self, expected: &RoundTag) -> FrostResult<C, ProtocolMessage<C>> {
+        expected.check(&self.round_tag())?;
+
+        Ok(self.message)
+    }
+
+    /// The framed message.
+    pub fn message(&self) -> &ProtocolMessage<C> {
+        &self.message
+    }
+
+    /// Unwrap the framed message.
+    pub fn into_message(self) -> ProtocolMessage<C> {
+        self.message
+    }
+
+    /// Serialize this [`ProtocolFrame`] to a vector of bytes.
+    ///
+    /// The frame is encoded as the [`ProtocolRound`] tag of its message, followed
+    /// by the sender index, the epoch, the session identifier and the message itself.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = vec![self.round().to_byte()];
+
+        (self.sender, self.epoch, self.session_id.clone())
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        self.message.serialize_payload(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`ProtocolFrame`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let (tag, mut reader) = bytes
+            .split_first()
+            .ok_or_else(|| Error::Custom("Empty protocol frame".to_string()))?;
+        let round = ProtocolRound::from_byte(*tag)
+            .ok_or_else(|| Error::Custom("Unknown protocol round".to_string()))?;
+
+        let (sender, epoch, session_id) = CanonicalDeserialize::deserialize_compressed(&mut reader)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+        let message = ProtocolMessage::deserialize_payload(round, &mut reader)?;
+
+        if !reader.is_empty() {
+            return Err(Error::Custom(
+                "Trailing bytes in protocol frame".to_string(),
+            ));
+        }
+        message.validate_points()?;
+
+        Ok(Self {
+            sender,
+            session_id,
+            epoch,
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dkg::DealerRole;
+    use crate::keys::IndividualSigningKey;
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::{generate_commitment_share_lists, ReRequestReason};
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    fn round_trip(message: ProtocolMessage<Secp256k1Sha256>) {
+        let round = message.round();
+        let frame = ProtocolFrame::new(3, b"session", message).with_epoch(2);
+        let bytes = frame.to_bytes().unwrap();
+        assert_eq!(bytes[0], round.to_byte());
+
+        let deserialized = ProtocolFrame::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.sender(), 3);
+        assert_eq!(deserialized.session_id(), b"session");
+        assert_eq!(deserialized.epoch(), 2);
+        assert_eq!(deserialized.round(), round);
+        assert_eq!(
+            deserialized.round_tag(),
+            RoundTag::new(round, b"session", 2)
+        );
+        assert_eq!(deserialized, frame);
+
+        // Truncated and extended frames are rejected.
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_bytes(&extended).is_err());
+    }
+
+    #[test]
+    fn protocol_frame_serialization() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (dealer, _, dh_private_key) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        round_trip(ProtocolMessage::DkgRound1(dealer.into_any()));
+
+        let signing_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Fr::rand(&mut OsRng),
+        };
+        let (public_comshares, _) =
+            generate_commitment_share_lists(OsRng, &signing_key, 2).unwrap();
+        round_trip(ProtocolMessage::SignCommit(public_comshares));
+
+        round_trip(ProtocolMessage::SignPartial(PartialThresholdSignature {
+            index: 1,
+            z: Fr::rand(&mut OsRng),
+            epoch_tag: None,
+        }));
+        round_trip(ProtocolMessage::SignReRequest(
+            ReRequest::new(b"message", &[], 2, ReRequestReason::Invalid).unwrap(),
+        ));
+        round_trip(ProtocolMessage::Abort("Timeout".to_string()));
+        round_trip(ProtocolMessage::Heartbeat(
+            Heartbeat::new(1, 42, 0, 8, "1.0.0", &dh_private_key, OsRng).unwrap(),
+        ));
+
+        for tag in 0..=u8::MAX {
+            assert_eq!(
+                ProtocolRound::from_byte(tag).map(ProtocolRound::to_byte),
+                (tag <= 7).then_some(tag)
+            );
+        }
+        assert!(ProtocolFrame::<Secp256k1Sha256>::from_bytes(&[8, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn round_tags() {
+        let message = || ProtocolMessage::<Secp256k1Sha256>::Abort("Timeout".to_string());
+        let frame = || ProtocolFrame::new(1, b"session", message()).with_epoch(3);
+
+        let expected = RoundTag::new(ProtocolRound::Abort, b"session", 3);
+        assert_eq!(frame().open(&expected), Ok(message()));
+
+        // Messages replayed into another round, session or epoch are rejected.
+        for tag in [
+            RoundTag::new(ProtocolRound::DkgRound2, b"session", 3),
+            RoundTag::new(ProtocolRound::Abort, b"other session", 3),
+            RoundTag::new(ProtocolRound::Abort, b"session", 4),
+        ] {
+            assert_eq!(
+                frame().open(&tag),
+                Err(Error::UnexpectedRoundTag(tag.clone(), expected.clone()))
+            );
+        }
+
+        assert_ne!(
+            RoundTag::new(ProtocolRound::Abort, b"ab", 0).to_bytes(),
+            RoundTag::new(ProtocolRound::Abort, b"a", 0x62).to_bytes()
+        );
+    }
+}
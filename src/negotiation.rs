@@ -0,0 +1,259 @@
+//! The negotiation module for agreeing on the wire version and ciphersuite
+//! of an ICE-FROST session among participants of mixed-version fleets.
+//!
+//! Before starting a session, each participant broadcasts a [`VersionAdvert`] listing
+//! the wire versions and ciphersuites it supports. Each participant then runs
+//! [`negotiate`] over all the adverts it received, which selects the highest wire
+//! version supported by everyone, and fails with an [`Error::IncompatibleVersions`]
+//! listing the participants preventing an agreement otherwise.
+//!
+//! The resulting [`NegotiatedSession`] binds the negotiated version and ciphersuite,
+//! along with all the adverts, into a session identifier. As an attacker stripping
+//! versions from an advert alters the transcript seen by its recipients, comparing
+//! session identifiers with [`NegotiatedSession::check_session_ids`] detects such
+//! downgrade attacks.
+//!
+//! [`VersionAdvert`]: crate::negotiation::VersionAdvert
+//! [`negotiate`]: crate::negotiation::negotiate
+//! [`NegotiatedSession`]: crate::negotiation::NegotiatedSession
+//! [`NegotiatedSession::check_session_ids`]: crate::negotiation::NegotiatedSession::check_session_ids
+
+use core::marker::PhantomData;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{hash_to_array, vec, String, Vec};
+use crate::{Error, FrostResult};
+
+/// The current wire version of ICE-FROST messages.
+pub const WIRE_VERSION: u16 = 1;
+
+/// The wire versions supported by this implementation, by decreasing preference.
+pub const SUPPORTED_WIRE_VERSIONS: &[u16] = &[WIRE_VERSION];
+
+/// A message advertising the wire versions and ciphersuites supported by a participant.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VersionAdvert<C: CipherSuite> {
+    /// The index of the advertising participant.
+    pub participant_index: u32,
+    /// The supported wire versions.
+    pub wire_versions: Vec<u16>,
+    /// The context strings of the supported ciphersuites.
+    pub ciphersuites: Vec<String>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> VersionAdvert<C> {
+    /// Construct a new [`VersionAdvert`] for the participant with index `participant_index`,
+    /// advertising the [`SUPPORTED_WIRE_VERSIONS`] and the ciphersuite `C`.
+    pub fn new(participant_index: u32) -> Self {
+        Self::with_support(
+            participant_index,
+            SUPPORTED_WIRE_VERSIONS.to_vec(),
+            vec![C::context_string()],
+        )
+    }
+
+    /// Construct a new [`VersionAdvert`] for the participant with index `participant_index`,
+    /// advertising the given `wire_versions` and `ciphersuites` context strings.
+    pub fn with_support(
+        participant_index: u32,
+        wire_versions: Vec<u16>,
+        ciphersuites: Vec<String>,
+    ) -> Self {
+        Self {
+            participant_index,
+            wire_versions,
+            ciphersuites,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Serialize this [`VersionAdvert`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`VersionAdvert`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+/// The outcome of a successful negotiation among a set of participants.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NegotiatedSession<C: CipherSuite> {
+    /// The negotiated wire version.
+    wire_version: u16,
+    /// The identifier of the session, binding the negotiated version,
+    /// the ciphersuite and all the adverts.
+    session_id: Vec<u8>,
+    _phantom: PhantomData<C>,
+}
+
+/// Negotiate the wire version of a session of the ciphersuite `C` among the
+/// participants having sent the given `adverts`, including this participant's.
+///
+/// # Returns
+///
+/// A [`NegotiatedSession`] for the highest of the [`SUPPORTED_WIRE_VERSIONS`]
+/// supported by all participants, otherwise an [`Error::IncompatibleVersions`]
+/// listing the participants not supporting the ciphersuite `C`, or the preferred
+/// wire version if no version is supported by all of them.
+pub fn negotiate<C: CipherSuite>(
+    adverts: &[VersionAdvert<C>],
+) -> FrostResult<C, NegotiatedSession<C>> {
+    let mut adverts: Vec<&VersionAdvert<C>> = adverts.iter().collect();
+    adverts.sort_by_key(|advert| advert.participant_index);
+    if adverts
+        .windows(2)
+        .any(|w| w[0].participant_index == w[1].participant_index)
+    {
+        return Err(Error::DuplicateIndices);
+    }
+
+    let context_string = C::context_string();
+    let unsupported_ciphersuite: Vec<u32> = adverts
+        .iter()
+        .filter(|advert| !advert.ciphersuites.contains(&context_string))
+        .map(|advert| advert.participant_index)
+        .collect();
+    if !unsupported_ciphersuite.is_empty() {
+        return Err(Error::IncompatibleVersions(unsupported_ciphersuite));
+    }
+
+    let wire_version = SUPPORTED_WIRE_VERSIONS
+        .iter()
+        .find(|version| {
+            adverts
+                .iter()
+                .all(|advert| advert.wire_versions.contains(version))
+        })
+        .copied()
+        .ok_or_else(|| {
+            Error::IncompatibleVersions(
+                adverts
+                    .iter()
+                    .filter(|advert| !advert.wire_versions.contains(&SUPPORTED_WIRE_VERSIONS[0]))
+                    .map(|advert| advert.participant_index)
+                    .collect(),
+            )
+        })?;
+
+    let mut transcript = wire_version.to_le_bytes().to_vec();
+    context_string
+        .serialize_compressed(&mut transcript)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+    for advert in adverts {
+        advert
+            .serialize_compressed(&mut transcript)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+    }
+
+    let session_id = hash_to_array::<C>((context_string + "negotiation").as_bytes(), &transcript)?;
+
+    Ok(NegotiatedSession {
+        wire_version,
+        session_id: session_id.as_ref().to_vec(),
+        _phantom: PhantomData,
+    })
+}
+
+impl<C: CipherSuite> NegotiatedSession<C> {
+    /// The negotiated wire version.
+    pub fn wire_version(&self) -> u16 {
+        self.wire_version
+    }
+
+    /// The identifier of this session, binding the negotiated version,
+    /// the ciphersuite and the adverts of all participants.
+    pub fn session_id(&self) -> &[u8] {
+        &self.session_id
+    }
+
+    /// Check that the other participants derived the same session identifier,
+    /// given as pairs of participant index and session identifier.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that all participants saw
+    /// the same negotiation transcript, otherwise an [`Error::IncompatibleVersions`]
+    /// listing the participants whose view differs, which may indicate a downgrade attack.
+    pub fn check_session_ids(&self, session_ids: &[(u32, Vec<u8>)]) -> FrostResult<C, ()> {
+        let mismatching_participants: Vec<u32> = session_ids
+            .iter()
+            .filter(|(_, session_id)| *session_id != self.session_id)
+            .map(|(index, _)| *index)
+            .collect();
+
+        match mismatching_participants.is_empty() {
+            true => Ok(()),
+            false => Err(Error::IncompatibleVersions(mismatching_participants)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::ToString;
+
+    #[test]
+    fn test_version_negotiation() {
+        let adverts: Vec<VersionAdvert<Secp256k1Sha256>> = vec![
+            VersionAdvert::new(1),
+            VersionAdvert::with_support(
+                2,
+                vec![0, WIRE_VERSION],
+                vec!["OTHER".to_string(), Secp256k1Sha256::context_string()],
+            ),
+            VersionAdvert::from_bytes(
+                &VersionAdvert::<Secp256k1Sha256>::new(3).to_bytes().unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        let session = negotiate(&adverts).unwrap();
+        assert_eq!(session.wire_version(), WIRE_VERSION);
+
+        // The same adverts yield the same session, regardless of their order.
+        let reordered = [adverts[2].clone(), adverts[0].clone(), adverts[1].clone()];
+        let same_session = negotiate(&reordered).unwrap();
+        assert!(session
+            .check_session_ids(&[(2, same_session.session_id().to_vec())])
+            .is_ok());
+
+        // A participant whose view of an advert was tampered with is detected.
+        let mut tampered = adverts.clone();
+        tampered[1].wire_versions = vec![WIRE_VERSION];
+        let tampered_session = negotiate(&tampered).unwrap();
+        assert_eq!(
+            session.check_session_ids(&[
+                (2, same_session.session_id().to_vec()),
+                (3, tampered_session.session_id().to_vec())
+            ]),
+            Err(Error::IncompatibleVersions(vec![3]))
+        );
+
+        // Participants without a common version or ciphersuite prevent the negotiation.
+        let mut incompatible = adverts.clone();
+        incompatible[0].wire_versions = vec![0];
+        incompatible[2].ciphersuites = vec!["OTHER".to_string()];
+        assert_eq!(
+            negotiate(&incompatible),
+            Err(Error::IncompatibleVersions(vec![3]))
+        );
+        incompatible[2] = adverts[2].clone();
+        assert_eq!(
+            negotiate(&incompatible),
+            Err(Error::IncompatibleVersions(vec![1]))
+        );
+    }
+}
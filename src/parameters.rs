@@ -10,6 +10,17 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 /// The configuration parameters for conducting the process of creating a
 /// threshold signature.
+///
+/// Any \\( 1 \le t \le n \\) is supported, including the two degenerate configurations:
+///
+/// * \\( t = 1 \\): the secret polynomials of the dealers are constant, so that their
+///   commitments have length 1, and every participant receives the group signing key
+///   itself as its share. Any single participant can then sign on behalf of the group,
+///   with a Lagrange coefficient of 1.
+/// * \\( t = n \\): every participant must take part in every signing session, and
+///   the group key is lost along with any share. The Lagrange coefficients of the
+///   participants are then constant, which the [`crate::sign::MultiSignatureAggregator`]
+///   relies on to skip all interpolation.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ThresholdParameters<C: CipherSuite> {
     /// The number of participants in the scheme.
@@ -22,35 +33,125 @@ pub struct ThresholdParameters<C: CipherSuite> {
 impl<C: CipherSuite> ThresholdParameters<C> {
     /// Initialize a new set of threshold parameters.
     ///
-    /// Will panic if one of the following condition is met:
-    ///  - n equals 0
-    ///  - t equals 0
-    ///  - n < t
-    pub fn new(n: u32, t: u32) -> Self {
-        assert!(n > 0);
-        assert!(t > 0);
-        assert!(n >= t);
-
-        Self {
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ThresholdParameters`], otherwise an
+    /// [`Error::UnsupportedParameters`] if `n` or `t` equals 0, or if `n` < `t`.
+    pub fn new(n: u32, t: u32) -> FrostResult<C, Self> {
+        let parameters = Self {
             n,
             t,
             _phantom: PhantomData,
+        };
+        parameters.validate()?;
+
+        Ok(parameters)
+    }
+
+    /// Attempt to initialize a new set of threshold parameters.
+    #[deprecated(note = "use `ThresholdParameters::new`, which is now fallible")]
+    pub fn try_new(n: u32, t: u32) -> FrostResult<C, Self> {
+        Self::new(n, t)
+    }
+
+    /// Check that these parameters satisfy \\( 1 \le t \le n \\).
+    ///
+    /// # Returns
+    ///
+    /// An [`Error::UnsupportedParameters`] if they do not.
+    pub fn validate(&self) -> FrostResult<C, ()> {
+        match self.t > 0 && self.n >= self.t {
+            true => Ok(()),
+            false => Err(Error::UnsupportedParameters(self.n, self.t)),
         }
     }
 
+    /// Whether any single participant can sign on behalf of the group, i.e. \\( t = 1 \\).
+    pub fn is_single_signer(&self) -> bool {
+        self.t == 1
+    }
+
+    /// Whether all participants must sign, i.e. \\( t = n \\).
+    pub fn is_full_group(&self) -> bool {
+        self.t == self.n
+    }
+
     /// Serialize this [`ThresholdParameters`] to a vector of bytes.
     pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`ThresholdParameters`] from a vector of bytes.
+    ///
+    /// Parameters which are not supported are rejected with an [`Error::UnsupportedParameters`].
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        let parameters: Self = Self::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+        parameters.validate()?;
+
+        Ok(parameters)
+    }
+}
+
+/// Threshold parameters fixed at compile time, for a threshold of `T`
+/// signers out of `N` participants.
+///
+/// These allow signing sessions to keep their per-signer state in fixed-size
+/// arrays, through [`crate::sign::SignatureAggregatorConst`] and
+/// [`crate::keys::IndividualSigningKey::sign_const`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ThresholdParametersConst<C: CipherSuite, const T: usize, const N: usize> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite, const T: usize, const N: usize> ThresholdParametersConst<C, T, N> {
+    const VALID: () = assert!(
+        T > 0 && N >= T && N <= u32::MAX as usize,
+        "Invalid threshold parameters"
+    );
+
+    /// Initialize a new set of compile-time threshold parameters.
+    ///
+    /// Will fail to compile if one of the following condition is met:
+    ///  - N equals 0
+    ///  - T equals 0
+    ///  - N < T
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The equivalent runtime [`ThresholdParameters`].
+    pub fn parameters(&self) -> ThresholdParameters<C> {
+        // The parameters were validated at compile time.
+        ThresholdParameters {
+            n: N as u32,
+            t: T as u32,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: CipherSuite, const T: usize, const N: usize> Default for ThresholdParametersConst<C, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: CipherSuite, const T: usize, const N: usize> From<ThresholdParametersConst<C, T, N>>
+    for ThresholdParameters<C>
+{
+    fn from(parameters: ThresholdParametersConst<C, T, N>) -> Self {
+        parameters.parameters()
     }
 }
 
@@ -60,14 +161,43 @@ mod test {
     use crate::testing::Secp256k1Sha256;
     use rand::{rngs::OsRng, RngCore};
 
+    #[test]
+    fn degenerate_parameters() {
+        let single_signer = ThresholdParameters::<Secp256k1Sha256>::new(3, 1).unwrap();
+        assert!(single_signer.is_single_signer());
+        assert!(!single_signer.is_full_group());
+
+        let full_group = ThresholdParameters::<Secp256k1Sha256>::new(3, 3).unwrap();
+        assert!(full_group.is_full_group());
+        assert!(!full_group.is_single_signer());
+
+        let lone_participant = ThresholdParameters::<Secp256k1Sha256>::new(1, 1).unwrap();
+        assert!(lone_participant.is_single_signer() && lone_participant.is_full_group());
+
+        for (n, t) in [(0, 0), (3, 0), (2, 3)] {
+            assert_eq!(
+                ThresholdParameters::<Secp256k1Sha256>::new(n, t),
+                Err(Error::UnsupportedParameters(n, t))
+            );
+
+            let mut bytes = Vec::new();
+            n.serialize_compressed(&mut bytes).unwrap();
+            t.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(
+                ThresholdParameters::<Secp256k1Sha256>::from_bytes(&bytes),
+                Err(Error::UnsupportedParameters(n, t))
+            );
+        }
+    }
+
     #[test]
     fn test_serialisation() {
         let mut rng = OsRng;
 
         for _ in 0..100 {
-            let n = rng.next_u32();
+            let n = rng.next_u32().max(1);
             let t = core::cmp::min(n, rng.next_u32());
-            let params = ThresholdParameters::<Secp256k1Sha256>::new(n, t);
+            let params = ThresholdParameters::<Secp256k1Sha256>::new(n, t).unwrap();
             let bytes = params.to_bytes().unwrap();
             assert!(ThresholdParameters::<Secp256k1Sha256>::from_bytes(&bytes).is_ok());
             assert_eq!(params, ThresholdParameters::from_bytes(&bytes).unwrap());
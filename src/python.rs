@@ -0,0 +1,525 @@
+//! The python module, exposing ICE-FROST ceremonies as a Python extension module,
+//! for orchestration scripts and notebooks.
+//!
+//! All functions operate over the [`Secp256k1Sha256`] ciphersuite, and take and return
+//! the serialized ICE-FROST types as `bytes`, so that the state of each participant can
+//! be stored and forwarded between the steps of a ceremony. Errors are raised as
+//! `ValueError`s, with the description of the underlying [`Error`].
+//!
+//! The extension module is built with [maturin](https://www.maturin.rs), with the
+//! `python` feature, and is imported as `ice_frost`:
+//!
+//! ```python
+//! import ice_frost
+//!
+//! participant, coefficients, dh_private_key = ice_frost.new_dealer(3, 2, 1)
+//! ```
+//!
+//! A distributed key generation session is run with [`new_dealer`] or [`new_signer`],
+//! followed by [`dkg_round_one`], [`dkg_round_two`] and [`dkg_finish`], while dealers
+//! of an existing group reshare their keys with [`reshare`]. Signing sessions are run
+//! with [`generate_commitment_shares`], [`sign`] and [`aggregate`], the latter taking
+//! the individual verifying keys of the signers given by [`verifying_key`], and
+//! signatures are checked with [`verify`].
+//!
+//! [`Secp256k1Sha256`]: crate::testing::Secp256k1Sha256
+//! [`new_dealer`]: crate::python::new_dealer
+//! [`new_signer`]: crate::python::new_signer
+//! [`dkg_round_one`]: crate::python::dkg_round_one
+//! [`dkg_round_two`]: crate::python::dkg_round_two
+//! [`dkg_finish`]: crate::python::dkg_finish
+//! [`reshare`]: crate::python::reshare
+//! [`generate_commitment_shares`]: crate::python::generate_commitment_shares
+//! [`sign`]: crate::python::sign
+//! [`aggregate`]: crate::python::aggregate
+//! [`verifying_key`]: crate::python::verifying_key
+//! [`verify`]: crate::python::verify
+
+// The wrappers generated by `#[pyfunction]` convert errors into the same type.
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::dkg::{
+    Coefficients, DealerRole, DistributedKeyGeneration, EncryptedSecretShare, Participant,
+    RoundOne, RoundTwo,
+};
+use crate::keys::{
+    DiffieHellmanPrivateKey, GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey,
+};
+use crate::parameters::ThresholdParameters;
+use crate::sign::{
+    generate_commitment_share_lists, PartialThresholdSignature, PublicCommitmentShareList,
+    SecretCommitmentShareList, SignatureAggregator, Signer, ThresholdSignature,
+};
+use crate::testing::Secp256k1Sha256;
+use crate::utils::{vec, ToString, Vec};
+use crate::{CipherSuite, Error, FrostResult};
+
+use rand::rngs::OsRng;
+
+type C = Secp256k1Sha256;
+
+/// A signer of a session, as its index, its serialized [`PublicCommitmentShareList`],
+/// and the position of the commitment share it uses in this list.
+type PySigner<'py> = (u32, Bound<'py, PyBytes>, usize);
+
+/// Convert an ICE-FROST [`Error`] into a Python `ValueError`.
+fn to_py_err(error: Error<C>) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// Convert serialized bytes into a Python `bytes` object.
+fn to_py_bytes(py: Python<'_>, bytes: FrostResult<C, Vec<u8>>) -> PyResult<Py<PyBytes>> {
+    Ok(PyBytes::new_bound(py, &bytes.map_err(to_py_err)?).unbind())
+}
+
+fn parameters(n: u32, t: u32) -> PyResult<ThresholdParameters<C>> {
+    ThresholdParameters::new(n, t).map_err(to_py_err)
+}
+
+fn signers(signers: &[PySigner<'_>]) -> PyResult<Vec<Signer<C>>> {
+    let mut signers = signers
+        .iter()
+        .map(|(index, commitment_shares, position)| {
+            let commitment_shares =
+                PublicCommitmentShareList::<C>::from_bytes(commitment_shares.as_bytes())
+                    .map_err(to_py_err)?;
+            let published_commitment_share = *commitment_shares
+                .commitments
+                .get(*position)
+                .ok_or_else(|| to_py_err(Error::MissingCommitmentShares))?;
+
+            Ok(Signer {
+                participant_index: *index,
+                published_commitment_share,
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    signers.sort();
+
+    Ok(signers)
+}
+
+/// Create a new dealer with index `index`, for a group of `n` participants with
+/// threshold `t`.
+///
+/// Returns the serialized participant to broadcast, and the secret polynomial
+/// coefficients and Diffie-Hellman private key to keep private.
+#[pyfunction]
+pub fn new_dealer(
+    py: Python<'_>,
+    n: u32,
+    t: u32,
+    index: u32,
+) -> PyResult<(Py<PyBytes>, Py<PyBytes>, Py<PyBytes>)> {
+    let (dealer, coefficients, dh_private_key) =
+        Participant::<C, DealerRole>::new_dealer(&parameters(n, t)?, index, OsRng)
+            .map_err(to_py_err)?;
+
+    Ok((
+        to_py_bytes(py, dealer.to_bytes())?,
+        to_py_bytes(py, coefficients.to_bytes())?,
+        to_py_bytes(py, dh_private_key.to_bytes())?,
+    ))
+}
+
+/// Create a new signer with index `index`, receiving reshared keys for a group of
+/// `n` participants with threshold `t`.
+///
+/// Returns the serialized participant to broadcast, and the Diffie-Hellman private
+/// key to keep private.
+#[pyfunction]
+pub fn new_signer(
+    py: Python<'_>,
+    n: u32,
+    t: u32,
+    index: u32,
+) -> PyResult<(Py<PyBytes>, Py<PyBytes>)> {
+    let (signer, dh_private_key) =
+        Participant::<C, crate::dkg::SignerRole>::new_signer(&parameters(n, t)?, index, OsRng)
+            .map_err(to_py_err)?;
+
+    Ok((
+        to_py_bytes(py, signer.to_bytes())?,
+        to_py_bytes(py, dh_private_key.to_bytes())?,
+    ))
+}
+
+/// Run the first round of a distributed key generation session, as the participant
+/// with index `index` among the given serialized `participants`.
+///
+/// Dealers provide their secret polynomial `coefficients` to bootstrap a new group,
+/// while signers of a resharing session omit them, the `participants` being the
+/// dealers of the previous group, and `n` and `t` its parameters.
+///
+/// Returns the serialized state of the session, and the encrypted secret shares to
+/// send to the other participants, if any.
+#[pyfunction]
+#[pyo3(signature = (n, t, dh_private_key, index, coefficients, participants))]
+pub fn dkg_round_one(
+    py: Python<'_>,
+    n: u32,
+    t: u32,
+    dh_private_key: &[u8],
+    index: u32,
+    coefficients: Option<&[u8]>,
+    participants: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<(Py<PyBytes>, Vec<Py<PyBytes>>)> {
+    let parameters = parameters(n, t)?;
+    let dh_private_key =
+        DiffieHellmanPrivateKey::<C>::from_bytes(dh_private_key).map_err(to_py_err)?;
+    let participants = participants
+        .iter()
+        .map(|participant| Participant::<C>::from_bytes(participant.as_bytes()).map_err(to_py_err))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let (state, _) = match coefficients {
+        Some(coefficients) => {
+            let coefficients = Coefficients::<C>::from_bytes(coefficients).map_err(to_py_err)?;
+            DistributedKeyGeneration::<RoundOne, C>::bootstrap(
+                &parameters,
+                &dh_private_key,
+                &index,
+                &coefficients,
+                &participants,
+                OsRng,
+            )
+        }
+        None => DistributedKeyGeneration::<RoundOne, C>::new(
+            &parameters,
+            &dh_private_key,
+            &index,
+            &participants,
+            OsRng,
+        ),
+    }
+    .map_err(to_py_err)?;
+
+    let encrypted_shares = match state.their_encrypted_secret_shares() {
+        Ok(encrypted_shares) => encrypted_shares
+            .iter()
+            .map(|share| to_py_bytes(py, share.to_bytes()))
+            .collect::<PyResult<Vec<_>>>()?,
+        Err(_) => Vec::new(),
+    };
+
+    Ok((to_py_bytes(py, state.to_bytes())?, encrypted_shares))
+}
+
+/// Run the second round of a distributed key generation session, from the serialized
+/// `state` of the first round and the encrypted secret shares sent to this participant.
+///
+/// Returns the serialized state of the session.
+#[pyfunction]
+pub fn dkg_round_two(
+    py: Python<'_>,
+    state: &[u8],
+    encrypted_shares: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<Py<PyBytes>> {
+    let state = DistributedKeyGeneration::<RoundOne, C>::from_bytes(state).map_err(to_py_err)?;
+    let encrypted_shares = encrypted_shares
+        .iter()
+        .map(|share| EncryptedSecretShare::<C>::from_bytes(share.as_bytes()).map_err(to_py_err))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let state = state
+        .to_round_two(encrypted_shares, OsRng)
+        .map_err(to_py_err)?;
+
+    to_py_bytes(py, state.to_bytes())
+}
+
+/// Finish a distributed key generation session from the serialized `state` of the
+/// second round.
+///
+/// Returns the serialized group verifying key, and the signing key of this participant.
+#[pyfunction]
+pub fn dkg_finish(py: Python<'_>, state: &[u8]) -> PyResult<(Py<PyBytes>, Py<PyBytes>)> {
+    let state = DistributedKeyGeneration::<RoundTwo, C>::from_bytes(state).map_err(to_py_err)?;
+    let (group_key, signing_key) = state.finish().map_err(to_py_err)?;
+
+    Ok((
+        to_py_bytes(py, group_key.to_bytes())?,
+        to_py_bytes(py, signing_key.to_bytes())?,
+    ))
+}
+
+/// Reshare the serialized `signing_key` of this dealer to the serialized signers of a
+/// new group of `n` participants with threshold `t`.
+///
+/// Returns the serialized dealer to broadcast, and the encrypted secret shares to send
+/// to the signers.
+#[pyfunction]
+pub fn reshare(
+    py: Python<'_>,
+    n: u32,
+    t: u32,
+    signing_key: &[u8],
+    signers: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<(Py<PyBytes>, Vec<Py<PyBytes>>)> {
+    let signing_key = IndividualSigningKey::<C>::from_bytes(signing_key).map_err(to_py_err)?;
+    let signers = signers
+        .iter()
+        .map(|signer| Participant::<C>::from_bytes(signer.as_bytes()).map_err(to_py_err))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let (dealer, encrypted_shares, _) =
+        Participant::<C, DealerRole>::reshare(&parameters(n, t)?, signing_key, &signers, OsRng)
+            .map_err(to_py_err)?;
+
+    Ok((
+        to_py_bytes(py, dealer.to_bytes())?,
+        encrypted_shares
+            .iter()
+            .map(|share| to_py_bytes(py, share.to_bytes()))
+            .collect::<PyResult<Vec<_>>>()?,
+    ))
+}
+
+/// Derive the serialized individual verifying key of the serialized `signing_key`,
+/// to be published for the aggregation of its partial signatures.
+#[pyfunction]
+pub fn verifying_key(py: Python<'_>, signing_key: &[u8]) -> PyResult<Py<PyBytes>> {
+    let signing_key = IndividualSigningKey::<C>::from_bytes(signing_key).map_err(to_py_err)?;
+
+    to_py_bytes(py, signing_key.to_public().to_bytes())
+}
+
+/// Generate `count` commitment shares for the serialized `signing_key`.
+///
+/// Returns the serialized public commitment share list to publish, and the secret
+/// commitment share list to keep private.
+#[pyfunction]
+pub fn generate_commitment_shares(
+    py: Python<'_>,
+    signing_key: &[u8],
+    count: usize,
+) -> PyResult<(Py<PyBytes>, Py<PyBytes>)> {
+    let signing_key = IndividualSigningKey::<C>::from_bytes(signing_key).map_err(to_py_err)?;
+    let (public_shares, secret_shares) =
+        generate_commitment_share_lists(OsRng, &signing_key, count).map_err(to_py_err)?;
+
+    Ok((
+        to_py_bytes(py, public_shares.to_bytes())?,
+        to_py_bytes(py, secret_shares.to_bytes())?,
+    ))
+}
+
+/// Compute the partial signature of the serialized `signing_key` on a `message`,
+/// with the commitment share at `position` in its serialized `secret_shares`.
+///
+/// The `signers` of the session are given as tuples of their index, their serialized
+/// public commitment share list, and the position of the commitment share they use.
+///
+/// Returns the serialized partial signature, and the secret commitment share list
+/// without the consumed commitment share, which must replace the previous one.
+#[pyfunction]
+pub fn sign(
+    py: Python<'_>,
+    signing_key: &[u8],
+    group_key: &[u8],
+    message: &[u8],
+    secret_shares: &[u8],
+    position: usize,
+    signers: Vec<PySigner<'_>>,
+) -> PyResult<(Py<PyBytes>, Py<PyBytes>)> {
+    let signing_key = IndividualSigningKey::<C>::from_bytes(signing_key).map_err(to_py_err)?;
+    let group_key = GroupVerifyingKey::<C>::from_bytes(group_key).map_err(to_py_err)?;
+    let mut secret_shares =
+        SecretCommitmentShareList::<C>::from_bytes(secret_shares).map_err(to_py_err)?;
+    let message_hash = C::h4(message).map_err(to_py_err)?;
+
+    let partial_signature = signing_key
+        .sign(
+            &message_hash,
+            &group_key,
+            &mut secret_shares,
+            position,
+            &self::signers(&signers)?,
+        )
+        .map_err(to_py_err)?;
+
+    Ok((
+        to_py_bytes(py, partial_signature.to_bytes())?,
+        to_py_bytes(py, secret_shares.to_bytes())?,
+    ))
+}
+
+/// Aggregate the serialized `partial_signatures` of the `signers` on a `message` into
+/// the signature of the group with the serialized `group_key`, of `n` participants
+/// with threshold `t`.
+///
+/// The `signers` are given as for [`sign`], and the `verifying_keys` are their
+/// serialized individual verifying keys.
+///
+/// Returns the serialized signature.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub fn aggregate(
+    py: Python<'_>,
+    n: u32,
+    t: u32,
+    group_key: &[u8],
+    message: &[u8],
+    signers: Vec<PySigner<'_>>,
+    verifying_keys: Vec<Bound<'_, PyBytes>>,
+    partial_signatures: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<Py<PyBytes>> {
+    let group_key = GroupVerifyingKey::<C>::from_bytes(group_key).map_err(to_py_err)?;
+    let verifying_keys = verifying_keys
+        .iter()
+        .map(|key| IndividualVerifyingKey::<C>::from_bytes(key.as_bytes()).map_err(to_py_err))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let mut aggregator = SignatureAggregator::new(parameters(n, t)?, group_key, message, None);
+    for signer in self::signers(&signers)? {
+        let verifying_key = verifying_keys
+            .iter()
+            .find(|key| key.index == signer.participant_index)
+            .ok_or_else(|| {
+                to_py_err(Error::MisbehavingParticipants(vec![
+                    signer.participant_index,
+                ]))
+            })?;
+        aggregator
+            .include_signer(
+                signer.participant_index,
+                signer.published_commitment_share,
+                verifying_key.clone(),
+            )
+            .map_err(to_py_err)?;
+    }
+    for partial_signature in partial_signatures.iter() {
+        aggregator
+            .include_partial_signature(
+                PartialThresholdSignature::from_bytes(partial_signature.as_bytes())
+                    .map_err(to_py_err)?,
+            )
+            .map_err(to_py_err)?;
+    }
+
+    let signature = aggregator
+        .finalize()
+        .and_then(|aggregator| aggregator.aggregate())
+        .map_err(to_py_err)?;
+
+    to_py_bytes(py, signature.to_bytes())
+}
+
+/// Verify the serialized `signature` on a `message` against the serialized `group_key`.
+#[pyfunction]
+pub fn verify(group_key: &[u8], message: &[u8], signature: &[u8]) -> PyResult<bool> {
+    let group_key = GroupVerifyingKey::<C>::from_bytes(group_key).map_err(to_py_err)?;
+    let signature = ThresholdSignature::<C>::from_bytes(signature).map_err(to_py_err)?;
+    let message_hash = C::h4(message).map_err(to_py_err)?;
+
+    Ok(signature.verify(&group_key, &message_hash).is_ok())
+}
+
+/// The `ice_frost` Python module.
+#[pymodule]
+pub fn ice_frost(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(new_dealer, module)?)?;
+    module.add_function(wrap_pyfunction!(new_signer, module)?)?;
+    module.add_function(wrap_pyfunction!(dkg_round_one, module)?)?;
+    module.add_function(wrap_pyfunction!(dkg_round_two, module)?)?;
+    module.add_function(wrap_pyfunction!(dkg_finish, module)?)?;
+    module.add_function(wrap_pyfunction!(reshare, module)?)?;
+    module.add_function(wrap_pyfunction!(verifying_key, module)?)?;
+    module.add_function(wrap_pyfunction!(generate_commitment_shares, module)?)?;
+    module.add_function(wrap_pyfunction!(sign, module)?)?;
+    module.add_function(wrap_pyfunction!(aggregate, module)?)?;
+    module.add_function(wrap_pyfunction!(verify, module)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pyo3::types::IntoPyDict;
+
+    #[test]
+    fn python_ceremony_2_out_of_3() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "ice_frost").unwrap();
+            ice_frost(&module).unwrap();
+            let globals = [("ice_frost", module)].into_py_dict_bound(py);
+
+            py.run_bound(
+                r#"
+dealers = [ice_frost.new_dealer(3, 2, i) for i in range(1, 4)]
+participants = [dealer[0] for dealer in dealers]
+round_one = [
+    ice_frost.dkg_round_one(3, 2, dh_private_key, i + 1, coefficients, participants)
+    for i, (_, coefficients, dh_private_key) in enumerate(dealers)
+]
+keys = [
+    ice_frost.dkg_finish(
+        ice_frost.dkg_round_two(state, [shares[i] for _, shares in round_one])
+    )
+    for i, (state, _) in enumerate(round_one)
+]
+group_key = keys[0][0]
+assert all(key[0] == group_key for key in keys)
+
+def sign(signing_keys, verifying_keys, message):
+    commitments = [ice_frost.generate_commitment_shares(key, 1) for key in signing_keys]
+    signers = [(i + 1, public, 0) for i, (public, _) in enumerate(commitments)]
+    partials = [
+        ice_frost.sign(key, group_key, message, secret, 0, signers)[0]
+        for key, (_, secret) in zip(signing_keys, commitments)
+    ]
+    return ice_frost.aggregate(
+        3, 2, group_key, message, signers, verifying_keys, partials
+    )
+
+message = b"This is a test of the tsunami alert system."
+signing_keys = [key[1] for key in keys[:2]]
+verifying_keys = [ice_frost.verifying_key(key) for key in signing_keys]
+signature = sign(signing_keys, verifying_keys, message)
+assert ice_frost.verify(group_key, message, signature)
+assert not ice_frost.verify(group_key, b"another message", signature)
+
+# The dealers reshare their keys to a new group of signers.
+signers = [ice_frost.new_signer(3, 2, i) for i in range(1, 4)]
+resharings = [
+    ice_frost.reshare(3, 2, key[1], [signer[0] for signer in signers])
+    for key in keys
+]
+new_keys = [
+    ice_frost.dkg_finish(
+        ice_frost.dkg_round_two(
+            ice_frost.dkg_round_one(
+                3, 2, dh_private_key, i + 1, None, [dealer for dealer, _ in resharings]
+            )[0],
+            [shares[i] for _, shares in resharings],
+        )
+    )
+    for i, (_, dh_private_key) in enumerate(signers)
+]
+assert all(key[0] == group_key for key in new_keys)
+
+signing_keys = [key[1] for key in new_keys[:2]]
+verifying_keys = [ice_frost.verifying_key(key) for key in signing_keys]
+assert ice_frost.verify(group_key, message, sign(signing_keys, verifying_keys, message))
+
+try:
+    ice_frost.new_dealer(2, 3, 1)
+    assert False
+except ValueError:
+    pass
+"#,
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+        });
+    }
+}
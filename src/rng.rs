@@ -0,0 +1,106 @@
+//! The rng module, adapting the random number generators of `rand_core` 0.9 to
+//! the `rand` 0.8 traits expected by the constructors of this crate.
+//!
+//! All randomized constructors of ICE-FROST, such as
+//! [`Participant::new_dealer`](crate::dkg::Participant::new_dealer) or
+//! [`generate_commitment_share_lists`](crate::sign::generate_commitment_share_lists),
+//! take a `rand` 0.8 [`RngCore`] and [`CryptoRng`]. The traits of both releases cannot be
+//! accepted through a single bound, as blanket implementations over each of them would
+//! overlap, hence generators of `rand_core` 0.9 are passed wrapped in a [`RandCoreCompat`].
+//!
+//! Fallible generators, such as the `OsRng` of `rand_core` 0.9, are first turned into
+//! infallible ones with `TryRngCore::unwrap_err`, as done for the other generators of
+//! this crate.
+
+use rand::{CryptoRng, RngCore};
+
+/// A wrapper around a `rand_core` 0.9 cryptographically secure random number
+/// generator, implementing the `rand` 0.8 [`RngCore`] and [`CryptoRng`] traits.
+#[derive(Clone, Debug, Default)]
+pub struct RandCoreCompat<R>(pub R);
+
+impl<R> RandCoreCompat<R> {
+    /// Unwrap the underlying `rand_core` 0.9 random number generator.
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R: rand_core_09::RngCore> RngCore for RandCoreCompat<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<R: rand_core_09::CryptoRng> CryptoRng for RandCoreCompat<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dkg::{DealerRole, Participant};
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::generate_commitment_share_lists;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    /// A `rand_core` 0.9 generator, drawing from the operating system.
+    struct OsRng09;
+
+    impl rand_core_09::RngCore for OsRng09 {
+        fn next_u32(&mut self) -> u32 {
+            OsRng.next_u32()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            OsRng.next_u64()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            OsRng.fill_bytes(dest)
+        }
+    }
+
+    impl rand_core_09::CryptoRng for OsRng09 {}
+
+    #[test]
+    fn rand_core_09_generators() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let mut rng = OsRng09;
+
+        let (dealer, _, _) = Participant::<Secp256k1Sha256, DealerRole>::new_dealer(
+            &params,
+            1,
+            RandCoreCompat(&mut rng),
+        )
+        .unwrap();
+        let _ = dealer.proof_of_secret_key();
+
+        let mut bytes = [0u8; 64];
+        RandCoreCompat(&mut rng).fill_bytes(&mut bytes);
+        assert_ne!(bytes, [0u8; 64]);
+
+        let signing_key = crate::keys::IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: crate::utils::Scalar::<Secp256k1Sha256>::from(42u64),
+        };
+        let (public_shares, secret_shares) =
+            generate_commitment_share_lists(RandCoreCompat(rng), &signing_key, 2).unwrap();
+        assert_eq!(public_shares.commitments.len(), 2);
+        assert_ne!(secret_shares.commitments[0], secret_shares.commitments[1]);
+    }
+}
@@ -0,0 +1,192 @@
+//! The abort module defining the evidence exported by a signature
+//! aggregator when giving up on an ICE-FROST signing session.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{String, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::signature::{check_canonical_ordering, PartialThresholdSignature, Signer};
+
+/// The record of an aborted signing session, as seen by its [`SignatureAggregator`].
+///
+/// It lists which [`PartialThresholdSignature`]s were received, which of them were
+/// valid, and which expected signers did not respond in time, so that retry layers
+/// (such as ROAST) and monitoring tools can act on the outcome of the session.
+///
+/// [`SignatureAggregator`]: super::SignatureAggregator
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AbortedSession<C: CipherSuite> {
+    /// The reason provided by the aggregator for aborting the session.
+    pub(crate) reason: String,
+    /// The hashed context and message to be signed.
+    pub(crate) message_hash: Vec<u8>,
+    /// The sorted and deduplicated signers expected to take part in the session.
+    pub(crate) signers: Vec<Signer<C>>,
+    /// The partial signatures received by the aggregator, sorted by signer index.
+    pub(crate) partial_signatures: Vec<PartialThresholdSignature<C>>,
+    /// The sorted indices of the signers whose partial signature is valid.
+    pub(crate) valid_signers: Vec<u32>,
+    /// The sorted indices of the expected signers who did not provide a partial signature.
+    pub(crate) timed_out_signers: Vec<u32>,
+}
+
+impl<C: CipherSuite> AbortedSession<C> {
+    /// The reason provided by the aggregator for aborting the session.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// The hashed context and message to be signed.
+    pub fn message_hash(&self) -> &[u8] {
+        &self.message_hash
+    }
+
+    /// The signers expected to take part in the session, along with their published commitments.
+    pub fn signers(&self) -> &[Signer<C>] {
+        &self.signers
+    }
+
+    /// The partial signatures received by the aggregator, sorted by signer index.
+    pub fn partial_signatures(&self) -> &[PartialThresholdSignature<C>] {
+        &self.partial_signatures
+    }
+
+    /// The sorted indices of the participants who provided a partial signature.
+    pub fn received_signers(&self) -> Vec<u32> {
+        self.partial_signatures.iter().map(|p| p.index).collect()
+    }
+
+    /// The sorted indices of the signers whose partial signature is valid.
+    pub fn valid_signers(&self) -> &[u32] {
+        &self.valid_signers
+    }
+
+    /// The sorted indices of the participants whose partial signature is invalid,
+    /// including the ones who were not expected to sign.
+    pub fn invalid_signers(&self) -> Vec<u32> {
+        self.partial_signatures
+            .iter()
+            .map(|p| p.index)
+            .filter(|index| self.valid_signers.binary_search(index).is_err())
+            .collect()
+    }
+
+    /// The sorted indices of the expected signers who did not provide a partial signature.
+    pub fn timed_out_signers(&self) -> &[u32] {
+        &self.timed_out_signers
+    }
+
+    /// Serialize this [`AbortedSession`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`AbortedSession`] from a vector of bytes.
+    ///
+    /// Sessions whose signers are not sorted by strictly increasing participant
+    /// index are rejected.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let session: Self = deserialize_validated(bytes)?;
+        check_canonical_ordering(&session.signers)?;
+
+        Ok(session)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for AbortedSession<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.signers
+            .iter()
+            .try_for_each(ValidatePoints::validate_points)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn aborted_session_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in [&signing_keys[1], &signing_keys[2], &signing_keys[4]] {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+
+        // Participant 2 provides a valid partial signature.
+        let p2_partial = signing_keys[1]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        // Participant 3 provides an invalid partial signature.
+        let mut p3_partial = signing_keys[2]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[1],
+                0,
+                &signers,
+            )
+            .unwrap();
+        p3_partial.z += Fr::from(1u8);
+        aggregator.include_partial_signature(p3_partial).unwrap();
+
+        // Participant 4 was not expected to sign.
+        aggregator
+            .include_partial_signature(PartialThresholdSignature {
+                index: 4,
+                z: Fr::rand(&mut OsRng),
+                epoch_tag: None,
+            })
+            .unwrap();
+
+        // Participant 5 never responds.
+        let aborted = aggregator.abort("deadline elapsed").unwrap();
+
+        assert_eq!(aborted.reason(), "deadline elapsed");
+        assert_eq!(aborted.message_hash(), &message_hash[..]);
+        assert_eq!(aborted.signers(), &signers[..]);
+        assert_eq!(aborted.received_signers(), vec![2, 3, 4]);
+        assert_eq!(aborted.valid_signers(), &[2]);
+        assert_eq!(aborted.invalid_signers(), vec![3, 4]);
+        assert_eq!(aborted.timed_out_signers(), &[5]);
+
+        let bytes = aborted.to_bytes().unwrap();
+        assert_eq!(AbortedSession::from_bytes(&bytes).unwrap(), aborted);
+    }
+}
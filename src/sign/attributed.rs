@@ -0,0 +1,336 @@
+//! The attributed module defining threshold signatures which embed the set
+//! of participants who produced them.
+//!
+//! A plain [`ThresholdSignature`] hides which participants signed. An
+//! [`AttributedSignature`] additionally carries a bitmap of its signers, and the
+//! hash of the commitment shares they published for the session. Anyone holding
+//! the [`PublicKeyPackage`] of the group can check that the listed signers reach the
+//! threshold, and once the commitment shares are revealed, that the signature was
+//! produced from exactly these signers' nonces, so that none of them can repudiate it.
+
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::PublicKeyPackage;
+use crate::utils::{calculate_lagrange_coefficients, vec, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::signature::{
+    compute_binding_factors, compute_group_commitment, encode_group_commitment_list, Signer,
+    ThresholdSignature,
+};
+
+/// A [`ThresholdSignature`] attributed to the participants who produced it.
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AttributedSignature<C: CipherSuite> {
+    /// The bitmap of the signers, where the participant with index \\( i \\) is
+    /// represented by the bit \\( (i - 1) \bmod 8 \\) of the byte \\( (i - 1) / 8 \\).
+    pub(crate) signer_bitmap: Vec<u8>,
+    /// The hash of the commitment shares published by the signers.
+    pub(crate) commitments_hash: Vec<u8>,
+    /// The final aggregated signature.
+    pub(crate) signature: ThresholdSignature<C>,
+}
+
+impl<C: CipherSuite> AttributedSignature<C> {
+    /// Construct a new [`AttributedSignature`] from the sorted and deduplicated
+    /// `signers` of a session among `n` participants, and its resulting `signature`.
+    pub(crate) fn new(
+        n: u32,
+        signers: &[Signer<C>],
+        signature: ThresholdSignature<C>,
+    ) -> FrostResult<C, Self> {
+        let mut signer_bitmap = vec![0u8; bitmap_length(n)];
+        for signer in signers.iter() {
+            if signer.participant_index == 0 || signer.participant_index > n {
+                return Err(Error::InvalidAttributedSignature);
+            }
+            let position = signer.participant_index as usize - 1;
+            signer_bitmap[position / 8] |= 1 << (position % 8);
+        }
+
+        Ok(Self {
+            signer_bitmap,
+            commitments_hash: hash_commitments(signers)?,
+            signature,
+        })
+    }
+
+    /// The bitmap of the participants who signed.
+    pub fn signer_bitmap(&self) -> &[u8] {
+        &self.signer_bitmap
+    }
+
+    /// The sorted indices of the participants who signed, as given by the bitmap.
+    pub fn signer_indices(&self) -> Vec<u32> {
+        let mut signer_indices = Vec::new();
+        for (byte_index, byte) in self.signer_bitmap.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (1 << bit) != 0 {
+                    signer_indices.push((byte_index * 8 + bit) as u32 + 1);
+                }
+            }
+        }
+
+        signer_indices
+    }
+
+    /// The hash of the commitment shares published by the signers.
+    pub fn commitments_hash(&self) -> &[u8] {
+        &self.commitments_hash
+    }
+
+    /// The attributed [`ThresholdSignature`].
+    pub fn signature(&self) -> &ThresholdSignature<C> {
+        &self.signature
+    }
+
+    /// Drop the attribution of this signature, returning the compact [`ThresholdSignature`].
+    pub fn into_signature(self) -> ThresholdSignature<C> {
+        self.signature
+    }
+
+    /// Verify this [`AttributedSignature`] against the public key material of the group.
+    ///
+    /// This checks that:
+    ///
+    /// * the bitmap only lists participants of the group, and reaches its threshold;
+    /// * the attributed signature is valid for the group key and `message_hash`;
+    /// * the verification shares of the listed signers interpolate to the group key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signature is valid.
+    pub fn verify(
+        &self,
+        public_key_package: &PublicKeyPackage<C>,
+        message_hash: &[u8],
+    ) -> FrostResult<C, ()> {
+        let parameters = public_key_package.parameters();
+        let signer_indices = self.signer_indices();
+
+        if self.signer_bitmap.len() != bitmap_length(parameters.n)
+            || signer_indices.len() < parameters.t as usize
+            || signer_indices.iter().any(|index| *index > parameters.n)
+        {
+            return Err(Error::InvalidAttributedSignature);
+        }
+
+        self.signature
+            .verify(public_key_package.group_key(), message_hash)?;
+
+        let mut interpolated_key = C::G::zero();
+        for index in signer_indices.iter() {
+            let verifying_key = public_key_package
+                .verifying_key(*index)
+                .ok_or(Error::InvalidAttributedSignature)?;
+            let lambda = calculate_lagrange_coefficients::<C>(*index, &signer_indices)?;
+
+            interpolated_key += verifying_key.share * lambda;
+        }
+
+        match interpolated_key == public_key_package.group_key().key {
+            true => Ok(()),
+            false => Err(Error::InvalidAttributedSignature),
+        }
+    }
+
+    /// Verify this [`AttributedSignature`] as with [`AttributedSignature::verify`],
+    /// and check that it was produced from the commitment shares published by the
+    /// given `signers`.
+    ///
+    /// The `signers` must match the bitmap and the hash of their commitment shares,
+    /// and these commitment shares must combine into the nonce of the signature.
+    ///
+    /// As any \\( t \\) verification shares interpolate to the group key, the bitmap
+    /// alone does not bind the signature to its signers: the commitment shares of the
+    /// `signers` must be the ones they published during the pre-computation phase.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signature is valid
+    /// and was produced by the given `signers`.
+    pub fn verify_with_commitments(
+        &self,
+        public_key_package: &PublicKeyPackage<C>,
+        message_hash: &[u8],
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, ()> {
+        self.verify(public_key_package, message_hash)?;
+
+        let signer_indices: Vec<u32> = signers
+            .iter()
+            .map(|signer| signer.participant_index)
+            .collect();
+        if signer_indices != self.signer_indices()
+            || hash_commitments(signers)? != self.commitments_hash
+        {
+            return Err(Error::InvalidAttributedSignature);
+        }
+
+        let binding_factors = compute_binding_factors(message_hash, signers)?;
+        match compute_group_commitment(signers, &binding_factors)?
+            == self.signature.group_commitment
+        {
+            true => Ok(()),
+            false => Err(Error::InvalidAttributedSignature),
+        }
+    }
+
+    /// Serialize this [`AttributedSignature`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`AttributedSignature`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for AttributedSignature<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.signature.validate_points()
+    }
+}
+
+/// The number of bytes of the bitmap of a group of `n` participants.
+fn bitmap_length(n: u32) -> usize {
+    (n as usize + 7) >> 3
+}
+
+/// Hash the commitment shares published by the sorted `signers`.
+fn hash_commitments<C: CipherSuite>(signers: &[Signer<C>]) -> FrostResult<C, Vec<u8>> {
+    let commitment_list: Vec<(u32, C::G, C::G)> = signers
+        .iter()
+        .map(|signer| {
+            let (hiding, binding) = signer.published_commitment_share;
+            (signer.participant_index, hiding, binding)
+        })
+        .collect();
+
+    Ok(
+        C::h5(&encode_group_commitment_list::<C>(&commitment_list)?)?
+            .as_ref()
+            .to_vec(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn attributed_signature_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+        let p1_sk = signing_keys[0].clone();
+        let p4_sk = signing_keys[3].clone();
+        let p5_sk = signing_keys[4].clone();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in [&p1_sk, &p4_sk, &p5_sk] {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in [&p1_sk, &p4_sk, &p5_sk]
+            .into_iter()
+            .zip(secret_comshares.iter_mut())
+        {
+            let partial = sk
+                .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let aggregator = aggregator.finalize().unwrap();
+        let attributed = aggregator.aggregate_attributed().unwrap();
+
+        assert_eq!(attributed.signer_bitmap(), &[0b11001]);
+        assert_eq!(attributed.signer_indices(), vec![1, 4, 5]);
+
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+        assert!(attributed.verify(&package, &message_hash).is_ok());
+        assert!(attributed
+            .verify_with_commitments(&package, &message_hash, &signers)
+            .is_ok());
+
+        let bytes = attributed.to_bytes().unwrap();
+        assert_eq!(AttributedSignature::from_bytes(&bytes).unwrap(), attributed);
+
+        // The compact signature remains available.
+        let signature = AttributedSignature::from_bytes(&bytes)
+            .unwrap()
+            .into_signature();
+        assert_eq!(signature, aggregator.aggregate().unwrap());
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+
+        // The signature cannot be attributed to another quorum.
+        let mut tampered = AttributedSignature::from_bytes(&bytes).unwrap();
+        tampered.signer_bitmap = vec![0b00111];
+        assert_eq!(
+            tampered.verify_with_commitments(&package, &message_hash, &signers),
+            Err(Error::InvalidAttributedSignature)
+        );
+
+        // The listed signers must reach the threshold.
+        let mut tampered = AttributedSignature::from_bytes(&bytes).unwrap();
+        tampered.signer_bitmap = vec![0b11000];
+        assert_eq!(
+            tampered.verify(&package, &message_hash),
+            Err(Error::InvalidAttributedSignature)
+        );
+
+        // The listed signers must be part of the group.
+        let mut tampered = AttributedSignature::from_bytes(&bytes).unwrap();
+        tampered.signer_bitmap = vec![0b101001];
+        assert_eq!(
+            tampered.verify(&package, &message_hash),
+            Err(Error::InvalidAttributedSignature)
+        );
+
+        // The revealed commitment shares must match the attributed ones.
+        let mut other_signers = signers.clone();
+        other_signers[1].published_commitment_share = other_signers[2].published_commitment_share;
+        assert_eq!(
+            attributed.verify_with_commitments(&package, &message_hash, &other_signers),
+            Err(Error::InvalidAttributedSignature)
+        );
+        assert_eq!(
+            attributed.verify_with_commitments(&package, &message_hash, &signers[..2]),
+            Err(Error::InvalidAttributedSignature)
+        );
+
+        // The attributed signature must be valid.
+        assert_eq!(
+            attributed.verify(&package, &[0u8; 32]),
+            Err(Error::InvalidSignature)
+        );
+    }
+}
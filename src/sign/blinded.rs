@@ -0,0 +1,313 @@
+//! The blinded module for hiding the partial signatures of the signers from the
+//! signature aggregator until all of them were received.
+//!
+//! A signer blinds its partial signature \\( z\_i \\) with a one-time random scalar
+//! \\( \beta\_i \\), and sends the [`BlindedPartialSignature`] \\( z\_i + \beta\_i \\) along
+//! with a hash commitment to \\( \beta\_i \\). Once the blinded partial signatures of all
+//! the signers of the session were published, e.g. on a broadcast channel, each signer
+//! reveals its [`PartialSignatureBlinding`], from which the aggregator recovers the
+//! partial signatures and proceeds with their verification and aggregation.
+//!
+//! A curious aggregator hence cannot learn the individual partial signatures of early
+//! signers, nor correlate the response patterns of signers across sessions, before
+//! the whole set of signers committed to their response.
+
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{ct_eq_encoded, hash_to_array, vec, Scalar, Vec};
+use crate::{Error, FrostResult};
+
+use super::signature::{PartialThresholdSignature, Signer};
+
+/// A [`PartialThresholdSignature`] blinded with a one-time scalar, along with a
+/// commitment to this scalar.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BlindedPartialSignature<C: CipherSuite> {
+    /// The index of the signer.
+    pub(crate) index: u32,
+    /// The blinded partial signature.
+    pub(crate) blinded_z: Scalar<C>,
+    /// The commitment to the blinding scalar.
+    pub(crate) commitment: Vec<u8>,
+}
+
+/// The one-time scalar blinding a [`BlindedPartialSignature`], to be revealed to the
+/// signature aggregator once all the signers sent their blinded partial signature.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct PartialSignatureBlinding<C: CipherSuite> {
+    /// The index of the signer.
+    pub(crate) index: u32,
+    /// The blinding scalar.
+    pub(crate) blinding: Scalar<C>,
+    /// The blinded partial signature this scalar blinds.
+    pub(crate) blinded_z: Scalar<C>,
+}
+
+impl<C: CipherSuite> Drop for PartialSignatureBlinding<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> ConstantTimeEq for PartialSignatureBlinding<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.index.ct_eq(&other.index)
+            & ct_eq_encoded(&self.blinding, &other.blinding)
+            & ct_eq_encoded(&self.blinded_z, &other.blinded_z)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for PartialSignatureBlinding<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for PartialSignatureBlinding<C> {}
+
+impl<C: CipherSuite> PartialThresholdSignature<C> {
+    /// Blind this [`PartialThresholdSignature`] with a fresh one-time scalar.
+    ///
+    /// # Returns
+    ///
+    /// The [`BlindedPartialSignature`] to be sent to the signature aggregator instead
+    /// of this partial signature, and the [`PartialSignatureBlinding`] to be kept
+    /// until it is revealed with [`PartialSignatureBlinding::reveal`].
+    pub fn blind(
+        &self,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, (BlindedPartialSignature<C>, PartialSignatureBlinding<C>)> {
+        let blinding = Scalar::<C>::rand(&mut csprng);
+        let blinding = PartialSignatureBlinding {
+            index: self.index,
+            blinding,
+            blinded_z: self.z + blinding,
+        };
+
+        let blinded = BlindedPartialSignature {
+            index: self.index,
+            blinded_z: blinding.blinded_z,
+            commitment: blinding.commitment()?,
+        };
+
+        Ok((blinded, blinding))
+    }
+}
+
+impl<C: CipherSuite> BlindedPartialSignature<C> {
+    /// The index of the signer of this [`BlindedPartialSignature`].
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Recover the [`PartialThresholdSignature`] hidden in this [`BlindedPartialSignature`]
+    /// from its revealed `blinding`.
+    ///
+    /// The recovered partial signature must still be verified by the signature aggregator.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the partial signature, otherwise an
+    /// [`Error::MisbehavingParticipants`] listing the signer if the blinding does not
+    /// match its commitment.
+    pub fn unblind(
+        &self,
+        blinding: &PartialSignatureBlinding<C>,
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        if blinding.index != self.index
+            || blinding.blinded_z != self.blinded_z
+            || blinding.commitment()? != self.commitment
+        {
+            return Err(Error::MisbehavingParticipants(vec![self.index]));
+        }
+
+        Ok(PartialThresholdSignature {
+            index: self.index,
+            z: self.blinded_z - blinding.blinding,
+            epoch_tag: None,
+        })
+    }
+
+    /// Serialize this [`BlindedPartialSignature`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`BlindedPartialSignature`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+impl<C: CipherSuite> PartialSignatureBlinding<C> {
+    /// Reveal this [`PartialSignatureBlinding`], provided that the given `blinded_partials`
+    /// published by the signature aggregator hold the blinded partial signature of every
+    /// one of the `signers` of the session, including the one of this signer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the blinding to be sent to the signature
+    /// aggregator, otherwise an [`Error::IncompleteBlindedPartialSignatures`] listing
+    /// the signers whose blinded partial signature is missing or altered.
+    pub fn reveal(
+        &self,
+        blinded_partials: &[BlindedPartialSignature<C>],
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, Self> {
+        let mut missing_signers: Vec<u32> = signers
+            .iter()
+            .map(|signer| signer.participant_index)
+            .filter(|&index| {
+                let mut partials = blinded_partials
+                    .iter()
+                    .filter(|blinded| blinded.index == index);
+                match (partials.next(), partials.next()) {
+                    (Some(blinded), None) => {
+                        index == self.index && blinded.blinded_z != self.blinded_z
+                    }
+                    _ => true,
+                }
+            })
+            .collect();
+
+        if !signers
+            .iter()
+            .any(|signer| signer.participant_index == self.index)
+        {
+            missing_signers.push(self.index);
+        }
+        if !missing_signers.is_empty() {
+            return Err(Error::IncompleteBlindedPartialSignatures(missing_signers));
+        }
+
+        Ok(self.clone())
+    }
+
+    /// Serialize this [`PartialSignatureBlinding`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PartialSignatureBlinding`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+
+    /// Compute the commitment to this blinding, bound to the index of its signer and
+    /// to the blinded partial signature.
+    fn commitment(&self) -> FrostResult<C, Vec<u8>> {
+        let mut message = self.index.to_le_bytes().to_vec();
+        (self.blinding, self.blinded_z)
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+
+        let commitment = hash_to_array::<C>(
+            (C::context_string() + "partial signature blinding").as_bytes(),
+            &message,
+        );
+        message.zeroize();
+
+        Ok(commitment?.as_ref().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::Field;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn blinded_partial_signatures() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+        let message = b"Blind me";
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(3) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut blinded_partials = Vec::new();
+        let mut blindings = Vec::new();
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign(&message_hash, &group_key, comshares, 0, &signers)
+                .unwrap();
+            let (blinded, blinding) = partial.blind(OsRng).unwrap();
+            assert_ne!(blinded.blinded_z, partial.z);
+            assert_eq!(
+                BlindedPartialSignature::from_bytes(&blinded.to_bytes().unwrap()).unwrap(),
+                blinded
+            );
+            blinded_partials.push(blinded);
+            blindings.push(blinding);
+        }
+
+        // Blindings are only revealed once all the blinded partial signatures are published.
+        assert_eq!(
+            blindings[0].reveal(&blinded_partials[..2], &signers),
+            Err(Error::IncompleteBlindedPartialSignatures(vec![3]))
+        );
+        let mut altered = blinded_partials.clone();
+        altered[0].blinded_z += Fr::ONE;
+        assert_eq!(
+            blindings[0].reveal(&altered, &signers),
+            Err(Error::IncompleteBlindedPartialSignatures(vec![1]))
+        );
+        let revealed: Vec<_> = blindings
+            .iter()
+            .map(|blinding| blinding.reveal(&blinded_partials, &signers).unwrap())
+            .collect();
+
+        // Blindings not matching their commitment are rejected.
+        let mut forged = revealed[1].clone();
+        forged.blinding += Fr::ONE;
+        assert_eq!(revealed[1], blindings[1]);
+        assert_ne!(forged, revealed[1]);
+        assert_eq!(
+            blinded_partials[1].unblind(&forged),
+            Err(Error::MisbehavingParticipants(vec![2]))
+        );
+        assert!(blinded_partials[1].unblind(&revealed[0]).is_err());
+
+        for (blinded, blinding) in blinded_partials.iter().zip(revealed.iter()) {
+            let partial = blinded.unblind(blinding).unwrap();
+            aggregator.verify_partial_signature(&partial).unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+    }
+}
@@ -0,0 +1,262 @@
+//! The cache module for reusing the interpolation data of recurring
+//! subsets of signers across ICE-FROST signing sessions.
+//!
+//! Identifying misbehaving signers requires checking each partial signature against
+//! the signer's verification share, interpolated with its Lagrange coefficient in the
+//! current subset of signers. As the same subsets tend to recur across sessions of a
+//! given group, a [`SignerSubsetCache`] shared by successive [`SignatureAggregator`]s
+//! keeps the most recently used interpolations, keyed by the bitmap of the signer indices.
+//!
+//! [`SignatureAggregator`]: super::SignatureAggregator
+
+use core::ops::Mul;
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{calculate_lagrange_coefficients, Vec};
+
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "std")]
+use crate::utils::BTreeMap;
+
+/// Multiply the verification shares of the signers with the sorted and deduplicated
+/// `indices` by their Lagrange coefficients in this subset.
+///
+/// Unknown verification shares, or shares whose Lagrange coefficient cannot be
+/// computed, yield [`None`].
+pub(crate) fn interpolate_verification_shares<C: CipherSuite>(
+    indices: &[u32],
+    public_keys: &[Option<C::G>],
+) -> Vec<Option<C::G>> {
+    indices
+        .iter()
+        .zip(public_keys.iter())
+        .map(|(index, public_key)| {
+            let lambda = calculate_lagrange_coefficients::<C>(*index, indices).ok()?;
+            public_key.map(|public_key| public_key.mul(lambda))
+        })
+        .collect()
+}
+
+/// Compute the bitmap of a subset of signer `indices`, with the \\( i \\)-th
+/// least significant bit set for the participant of index \\( i + 1 \\).
+#[cfg(feature = "std")]
+fn subset_bitmap(indices: &[u32]) -> Vec<u64> {
+    let mut bitmap = Vec::new();
+    for index in indices.iter().filter(|index| **index > 0) {
+        let position = (*index - 1) as usize;
+        if bitmap.len() <= position / 64 {
+            bitmap.resize(position / 64 + 1, 0);
+        }
+        bitmap[position / 64] |= 1 << (position % 64);
+    }
+
+    bitmap
+}
+
+/// An entry of a [`SignerSubsetCache`].
+#[cfg(feature = "std")]
+type CacheEntry<C> = (
+    u64,
+    Vec<Option<<C as CipherSuite>::G>>,
+    Arc<Vec<Option<<C as CipherSuite>::G>>>,
+);
+
+/// The entries of a [`SignerSubsetCache`], along with their last use.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct CacheEntries<C: CipherSuite> {
+    /// The maximum number of entries.
+    capacity: usize,
+    /// A counter incremented on each access.
+    clock: u64,
+    /// The interpolated verification shares of the cached subsets, along with the
+    /// clock value of their last access and the verification shares they were
+    /// computed from.
+    entries: BTreeMap<Vec<u64>, CacheEntry<C>>,
+}
+
+/// A least-recently-used cache of the interpolation data of subsets of signers,
+/// to be shared by the [`SignatureAggregator`](super::SignatureAggregator)s of
+/// a group through [`SignatureAggregator::with_cache`](super::SignatureAggregator::with_cache).
+///
+/// Cloning a [`SignerSubsetCache`] yields a handle to the same underlying cache.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct SignerSubsetCache<C: CipherSuite>(Arc<Mutex<CacheEntries<C>>>);
+
+#[cfg(feature = "std")]
+impl<C: CipherSuite> SignerSubsetCache<C> {
+    /// Construct a new [`SignerSubsetCache`] holding at most `capacity` subsets.
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(CacheEntries {
+            capacity,
+            clock: 0,
+            entries: BTreeMap::new(),
+        })))
+    }
+
+    /// The number of subsets currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether no subset is currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the cached subsets.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Retrieve the interpolated verification shares of the signers with the sorted and
+    /// deduplicated `indices` and the given `public_keys`, computing them on a cache miss.
+    pub(crate) fn get_or_compute(
+        &self,
+        indices: &[u32],
+        public_keys: Vec<Option<C::G>>,
+    ) -> Arc<Vec<Option<C::G>>> {
+        let key = subset_bitmap(indices);
+
+        {
+            let mut cache = self.lock();
+            cache.clock += 1;
+            let clock = cache.clock;
+            if let Some((last_use, cached_public_keys, shares)) = cache.entries.get_mut(&key) {
+                // Entries are only valid for the verification shares they were computed from.
+                if *cached_public_keys == public_keys {
+                    *last_use = clock;
+                    return shares.clone();
+                }
+            }
+        }
+
+        let shares = Arc::new(interpolate_verification_shares::<C>(indices, &public_keys));
+
+        let mut cache = self.lock();
+        if cache.capacity == 0 {
+            return shares;
+        }
+        if !cache.entries.contains_key(&key) && cache.entries.len() >= cache.capacity {
+            let least_recently_used = cache
+                .entries
+                .iter()
+                .min_by_key(|(_, (last_use, _, _))| *last_use)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                cache.entries.remove(&least_recently_used);
+            }
+        }
+        let clock = cache.clock;
+        cache
+            .entries
+            .insert(key, (clock, public_keys, shares.clone()));
+
+        shares
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheEntries<C>> {
+        // The cache is left in a consistent state even if a thread panicked while holding it.
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+    use crate::Error;
+
+    use crate::utils::Scalar;
+
+    use ark_ec::Group;
+    use ark_secp256k1::{Fr, Projective};
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_signer_subset_cache() {
+        let cache = SignerSubsetCache::<Secp256k1Sha256>::new(2);
+        let public_keys = |indices: &[u32]| {
+            indices
+                .iter()
+                .map(|index| {
+                    Some(Projective::generator() * Scalar::<Secp256k1Sha256>::from(*index))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = cache.get_or_compute(&[1, 3], public_keys(&[1, 3]));
+        assert!(Arc::ptr_eq(
+            &first,
+            &cache.get_or_compute(&[1, 3], public_keys(&[1, 3]))
+        ));
+        let lambda = calculate_lagrange_coefficients::<Secp256k1Sha256>(3, &[1, 3]).unwrap();
+        assert_eq!(
+            first[1],
+            Some(Projective::generator() * Scalar::<Secp256k1Sha256>::from(3u32) * lambda)
+        );
+
+        // Distinct verification shares are not served from the cache.
+        let other = cache.get_or_compute(&[1, 3], public_keys(&[2, 3]));
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(cache.len(), 1);
+
+        // The least recently used subset is evicted.
+        cache.get_or_compute(&[2, 3], public_keys(&[2, 3]));
+        cache.get_or_compute(&[1, 3], public_keys(&[2, 3]));
+        cache.get_or_compute(&[1, 65], public_keys(&[1, 65]));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(subset_bitmap(&[1, 65]), vec![1, 1]);
+        assert!(cache.lock().entries.contains_key(&subset_bitmap(&[1, 3])));
+        assert!(!cache.lock().entries.contains_key(&subset_bitmap(&[2, 3])));
+    }
+
+    #[test]
+    fn cached_aggregation_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let cache = SignerSubsetCache::new(4);
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        for _ in 0..2 {
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &message[..], None).with_cache(&cache);
+            let mut secret_comshares = Vec::new();
+            for sk in signing_keys.iter().skip(1) {
+                let (public_comshares, secret_comshare) =
+                    generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+                aggregator
+                    .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                    .unwrap();
+                secret_comshares.push(secret_comshare);
+            }
+
+            let signers = aggregator.get_signers().clone();
+            for (sk, comshares) in signing_keys.iter().skip(1).zip(secret_comshares.iter_mut()) {
+                let mut partial = sk
+                    .sign(&message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap();
+                if sk.index == 3 {
+                    partial.z += Fr::from(1u8);
+                }
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            assert_eq!(
+                aggregator.finalize().unwrap().aggregate().unwrap_err(),
+                Error::MisbehavingParticipants(vec![3])
+            );
+        }
+
+        // Both sessions share the same subset of signers.
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,232 @@
+//! The certificate module defining externally verifiable attestations
+//! of successful ICE-FROST signing sessions.
+
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::PublicKeyPackage;
+use crate::utils::{calculate_lagrange_coefficients, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::signature::{encode_group_commitment_list, Signer, ThresholdSignature};
+
+/// A signing quorum certificate, attesting which participants took part in
+/// the creation of a [`ThresholdSignature`].
+///
+/// It allows external parties, such as consensus layers, to attribute the
+/// liveness of a signing session to the listed signers.
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct QuorumCertificate<C: CipherSuite> {
+    /// The sorted indices of the participants who signed.
+    pub(crate) signer_indices: Vec<u32>,
+    /// The hashes of the commitment shares published by each signer, in the same order.
+    pub(crate) commitment_hashes: Vec<Vec<u8>>,
+    /// The final aggregated signature.
+    pub(crate) signature: ThresholdSignature<C>,
+}
+
+impl<C: CipherSuite> QuorumCertificate<C> {
+    /// Construct a new [`QuorumCertificate`] from the sorted and deduplicated
+    /// `signers` of a session and its resulting `signature`.
+    pub(crate) fn new(
+        signers: &[Signer<C>],
+        signature: ThresholdSignature<C>,
+    ) -> FrostResult<C, Self> {
+        let mut signer_indices = Vec::with_capacity(signers.len());
+        let mut commitment_hashes = Vec::with_capacity(signers.len());
+
+        for signer in signers.iter() {
+            let (hiding, binding) = signer.published_commitment_share;
+            let encoded_commitment =
+                encode_group_commitment_list::<C>(&[(signer.participant_index, hiding, binding)])?;
+
+            signer_indices.push(signer.participant_index);
+            commitment_hashes.push(C::h5(&encoded_commitment)?.as_ref().to_vec());
+        }
+
+        Ok(Self {
+            signer_indices,
+            commitment_hashes,
+            signature,
+        })
+    }
+
+    /// The sorted indices of the participants who signed.
+    pub fn signer_indices(&self) -> &[u32] {
+        &self.signer_indices
+    }
+
+    /// The hashes of the commitment shares published by each signer, in the same
+    /// order as [`QuorumCertificate::signer_indices`].
+    pub fn commitment_hashes(&self) -> &[Vec<u8>] {
+        &self.commitment_hashes
+    }
+
+    /// The certified [`ThresholdSignature`].
+    pub fn signature(&self) -> &ThresholdSignature<C> {
+        &self.signature
+    }
+
+    /// Verify this [`QuorumCertificate`] against the public key material of the group.
+    ///
+    /// This checks that:
+    ///
+    /// * the listed signers are unique and reach the threshold of the group;
+    /// * the certified signature is valid for the group key and `message_hash`;
+    /// * the verification shares of the listed signers interpolate to the group key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the certificate is valid.
+    pub fn verify(
+        &self,
+        public_key_package: &PublicKeyPackage<C>,
+        message_hash: &[u8],
+    ) -> FrostResult<C, ()> {
+        if self.signer_indices.len() < public_key_package.parameters().t as usize
+            || self.signer_indices.len() != self.commitment_hashes.len()
+            || self.signer_indices.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(Error::InvalidQuorumCertificate);
+        }
+
+        self.signature
+            .verify(public_key_package.group_key(), message_hash)?;
+
+        let mut interpolated_key = C::G::zero();
+        for index in self.signer_indices.iter() {
+            let verifying_key = public_key_package
+                .verifying_key(*index)
+                .ok_or(Error::InvalidQuorumCertificate)?;
+            let lambda = calculate_lagrange_coefficients::<C>(*index, &self.signer_indices)?;
+
+            interpolated_key += verifying_key.share * lambda;
+        }
+
+        match interpolated_key == public_key_package.group_key().key {
+            true => Ok(()),
+            false => Err(Error::InvalidQuorumCertificate),
+        }
+    }
+
+    /// Serialize this [`QuorumCertificate`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`QuorumCertificate`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for QuorumCertificate<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.signature.validate_points()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Projective;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn quorum_certificate_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+        let p2_sk = signing_keys[1].clone();
+        let p3_sk = signing_keys[2].clone();
+        let p5_sk = signing_keys[4].clone();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in [&p2_sk, &p3_sk, &p5_sk] {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in [&p2_sk, &p3_sk, &p5_sk]
+            .into_iter()
+            .zip(secret_comshares.iter_mut())
+        {
+            let partial = sk
+                .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let aggregator = aggregator.finalize().unwrap();
+        let certificate = aggregator.aggregate_with_certificate().unwrap();
+
+        assert_eq!(certificate.signer_indices(), &[2, 3, 5]);
+        assert_eq!(certificate.commitment_hashes().len(), 3);
+        assert!(certificate
+            .signature()
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+        assert!(certificate.verify(&package, &message_hash).is_ok());
+
+        let bytes = certificate.to_bytes().unwrap();
+        assert_eq!(QuorumCertificate::from_bytes(&bytes).unwrap(), certificate);
+
+        // The listed signers must reach the threshold.
+        let mut tampered = QuorumCertificate::from_bytes(&bytes).unwrap();
+        tampered.signer_indices.pop();
+        tampered.commitment_hashes.pop();
+        assert_eq!(
+            tampered.verify(&package, &message_hash),
+            Err(Error::InvalidQuorumCertificate)
+        );
+
+        // The listed signers' verification shares must interpolate to the group key.
+        let mut verifying_keys: Vec<_> = signing_keys.iter().map(|sk| sk.to_public()).collect();
+        verifying_keys[4].share = Projective::rand(&mut OsRng);
+        let inconsistent_package = PublicKeyPackage::new(params, group_key, verifying_keys);
+        assert_eq!(
+            certificate.verify(&inconsistent_package, &message_hash),
+            Err(Error::InvalidQuorumCertificate)
+        );
+
+        // The listed signers must be part of the group.
+        let mut tampered = QuorumCertificate::from_bytes(&bytes).unwrap();
+        tampered.signer_indices[2] = 6;
+        assert_eq!(
+            tampered.verify(&package, &message_hash),
+            Err(Error::InvalidQuorumCertificate)
+        );
+
+        // The certified signature must be valid.
+        assert_eq!(
+            certificate.verify(&package, &[0u8; 32]),
+            Err(Error::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! The compact module for encoding ICE-FROST partial signatures over
+//! bandwidth-limited links.
+//!
+//! A compact [`PartialThresholdSignature`] consists of the signer index, encoded
+//! as an unsigned LEB128 varint, followed by the fixed-size compressed encoding
+//! of its response scalar. A [`PartialSigBundle`] carries the partial signatures
+//! of several signers over the same message, sharing a single header holding
+//! the message hash and the number of entries.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::error::SerializationErrorKind;
+use crate::utils::{Scalar, ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::signature::{Initial, PartialThresholdSignature, SignatureAggregator};
+
+/// Append `value` to `bytes` as an unsigned LEB128 varint.
+fn write_varint(mut value: u64, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Read an unsigned LEB128 varint from the front of `bytes`, advancing it.
+fn read_varint<C: CipherSuite>(bytes: &mut &[u8]) -> FrostResult<C, u64> {
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = bytes.split_first().ok_or(Error::DeserializationError(
+            SerializationErrorKind::unexpected_eof(),
+        ))?;
+        *bytes = rest;
+
+        let payload = u64::from(byte & 0x7f);
+        // Reject encodings overflowing 64 bits, or padded with trailing zero bytes.
+        if (shift == 63 && payload > 1) || (shift > 0 && *byte == 0) {
+            return Err(Error::DeserializationError(
+                SerializationErrorKind::InvalidData,
+            ));
+        }
+        value |= payload << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(Error::DeserializationError(
+        SerializationErrorKind::InvalidData,
+    ))
+}
+
+/// Read a signer index from the front of `bytes`, advancing it.
+fn read_index<C: CipherSuite>(bytes: &mut &[u8]) -> FrostResult<C, u32> {
+    u32::try_from(read_varint::<C>(bytes)?)
+        .map_err(|_| Error::DeserializationError(SerializationErrorKind::InvalidData))
+}
+
+impl<C: CipherSuite> PartialThresholdSignature<C> {
+    /// Serialize this [`PartialThresholdSignature`] to its compact encoding,
+    /// i.e. its varint-encoded signer index followed by its response scalar.
+    pub fn to_compact_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write_compact(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PartialThresholdSignature`] from its compact encoding.
+    pub fn from_compact_bytes(mut bytes: &[u8]) -> FrostResult<C, Self> {
+        let partial_signature = Self::read_compact(&mut bytes)?;
+
+        match bytes.is_empty() {
+            true => Ok(partial_signature),
+            false => Err(Error::DeserializationError(
+                SerializationErrorKind::InvalidData,
+            )),
+        }
+    }
+
+    fn write_compact(&self, bytes: &mut Vec<u8>) -> FrostResult<C, ()> {
+        write_varint(self.index.into(), bytes);
+        self.z
+            .serialize_compressed(bytes)
+            .map_err(|e| Error::SerializationError(e.into()))
+    }
+
+    fn read_compact(bytes: &mut &[u8]) -> FrostResult<C, Self> {
+        let index = read_index::<C>(bytes)?;
+        let z = Scalar::<C>::deserialize_compressed(&mut *bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        Ok(Self {
+            index,
+            z,
+            epoch_tag: None,
+        })
+    }
+}
+
+/// A batch of [`PartialThresholdSignature`]s over the same message, sharing
+/// a single header when transmitted in its compact encoding.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PartialSigBundle<C: CipherSuite> {
+    /// The hash of the message signed by all the partial signatures.
+    message_hash: Vec<u8>,
+    /// The bundled partial signatures.
+    partial_signatures: Vec<PartialThresholdSignature<C>>,
+}
+
+impl<C: CipherSuite> PartialSigBundle<C> {
+    /// Construct an empty [`PartialSigBundle`] for partial signatures over `message_hash`.
+    pub fn new(message_hash: &[u8]) -> Self {
+        Self {
+            message_hash: message_hash.to_vec(),
+            partial_signatures: Vec::new(),
+        }
+    }
+
+    /// Add a [`PartialThresholdSignature`] to this bundle.
+    pub fn push(&mut self, partial_signature: PartialThresholdSignature<C>) {
+        self.partial_signatures.push(partial_signature);
+    }
+
+    /// The hash of the message signed by the bundled partial signatures.
+    pub fn message_hash(&self) -> &[u8] {
+        &self.message_hash
+    }
+
+    /// The bundled partial signatures.
+    pub fn partial_signatures(&self) -> &[PartialThresholdSignature<C>] {
+        &self.partial_signatures
+    }
+
+    /// The number of bundled partial signatures.
+    pub fn len(&self) -> usize {
+        self.partial_signatures.len()
+    }
+
+    /// Whether this bundle holds no partial signature.
+    pub fn is_empty(&self) -> bool {
+        self.partial_signatures.is_empty()
+    }
+
+    /// Serialize this [`PartialSigBundle`] to a vector of bytes, holding a header
+    /// with the message hash and the number of entries, followed by the compact
+    /// encoding of each partial signature.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        write_varint(self.message_hash.len() as u64, &mut bytes);
+        bytes.extend(&self.message_hash);
+        write_varint(self.partial_signatures.len() as u64, &mut bytes);
+        for partial_signature in self.partial_signatures.iter() {
+            partial_signature.write_compact(&mut bytes)?;
+        }
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PartialSigBundle`] from a vector of bytes.
+    pub fn from_bytes(mut bytes: &[u8]) -> FrostResult<C, Self> {
+        let hash_length = read_varint::<C>(&mut bytes)?;
+        if hash_length > bytes.len() as u64 {
+            return Err(Error::DeserializationError(
+                SerializationErrorKind::unexpected_eof(),
+            ));
+        }
+        let (message_hash, mut bytes) = bytes.split_at(hash_length as usize);
+
+        // Each entry takes at least one byte, which bounds the allocation below.
+        let count = read_varint::<C>(&mut bytes)?;
+        if count > bytes.len() as u64 {
+            return Err(Error::DeserializationError(
+                SerializationErrorKind::unexpected_eof(),
+            ));
+        }
+
+        let mut partial_signatures = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            partial_signatures.push(PartialThresholdSignature::read_compact(&mut bytes)?);
+        }
+        if !bytes.is_empty() {
+            return Err(Error::DeserializationError(
+                SerializationErrorKind::InvalidData,
+            ));
+        }
+
+        Ok(Self {
+            message_hash: message_hash.to_vec(),
+            partial_signatures,
+        })
+    }
+}
+
+impl<C: CipherSuite> Extend<PartialThresholdSignature<C>> for PartialSigBundle<C> {
+    fn extend<I: IntoIterator<Item = PartialThresholdSignature<C>>>(&mut self, iter: I) {
+        self.partial_signatures.extend(iter);
+    }
+}
+
+impl<C: CipherSuite> IntoIterator for PartialSigBundle<C> {
+    type Item = PartialThresholdSignature<C>;
+    type IntoIter = <Vec<PartialThresholdSignature<C>> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.partial_signatures.into_iter()
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Add all the [`PartialThresholdSignature`]s of a [`PartialSigBundle`]
+    /// to be included in the aggregation.
+    ///
+    /// # Returns
+    ///
+    /// An [`Error::Custom`] if the bundle was made for another message.
+    pub fn include_partial_signature_bundle(
+        &mut self,
+        bundle: PartialSigBundle<C>,
+    ) -> FrostResult<C, ()> {
+        if bundle.message_hash() != self.message_hash()?.as_ref() {
+            return Err(Error::Custom(
+                "The bundle was made for another message".to_string(),
+            ));
+        }
+
+        for partial_signature in bundle {
+            self.include_partial_signature(partial_signature)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn compact_partial_signatures() {
+        let mut bundle = PartialSigBundle::<Secp256k1Sha256>::new(&[7u8; 32]);
+        for index in [1, 127, 128, 300, u32::MAX] {
+            let partial_signature = PartialThresholdSignature {
+                index,
+                z: Scalar::<Secp256k1Sha256>::rand(&mut OsRng),
+                epoch_tag: None,
+            };
+
+            let bytes = partial_signature.to_compact_bytes().unwrap();
+            assert!(bytes.len() <= partial_signature.to_bytes().unwrap().len() + 1);
+            assert_eq!(
+                PartialThresholdSignature::from_compact_bytes(&bytes).unwrap(),
+                partial_signature
+            );
+
+            bundle.push(partial_signature);
+        }
+        assert_eq!(
+            bundle.partial_signatures()[0]
+                .to_compact_bytes()
+                .unwrap()
+                .len(),
+            33
+        );
+
+        let bytes = bundle.to_bytes().unwrap();
+        assert_eq!(PartialSigBundle::from_bytes(&bytes).unwrap(), bundle);
+
+        // Truncated or padded encodings are rejected.
+        assert!(
+            PartialSigBundle::<Secp256k1Sha256>::from_bytes(&bytes[..bytes.len() - 1]).is_err()
+        );
+        assert!(PartialSigBundle::<Secp256k1Sha256>::from_bytes(
+            &[bytes.clone(), vec![0]].concat()
+        )
+        .is_err());
+        assert!(
+            PartialThresholdSignature::<Secp256k1Sha256>::from_compact_bytes(&[0x81, 0x00])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,590 @@
+//! The delegated module for outsourcing the precomputation and storage of the
+//! commitment shares of low-power signers to untrusted helpers.
+//!
+//! A [`DelegatedPrecompute`] helper samples and stores nonce pairs \\( (r\_i, s\_i) \\)
+//! on behalf of a signer, and hands out their commitments as [`DelegatedCommitments`].
+//! The signer only keeps a [`BlindingKey`], from which it derives the blinding factors
+//! \\( (\beta\_i, \gamma\_i) \\) of each position \\( i \\), and publishes the blinded
+//! commitments \\( (R\_i + \beta\_i \cdot G, S\_i + \gamma\_i \cdot G) \\) of its nonces
+//! \\( (r\_i + \beta\_i, s\_i + \gamma\_i) \\).
+//!
+//! When signing, the helper releases its stored nonce pair, which the signer unblinds.
+//! As the blinding factors are pseudorandom and specific to each position, the helper
+//! never learns the final nonces of the signer, even after observing its signatures.
+//!
+//! Each position **must** be released and signed with only once. The [`BlindingKey`]
+//! hence keeps track of the positions it blinded and of the ones it consumed, and
+//! refuses to unblind any position twice.
+
+use ark_ec::Group;
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{vec, BTreeMap, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_points, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::lazy::bitmap_length;
+use super::precomputation::{
+    CommitmentShare, NoncePair, PublicCommitmentShareList, SecretCommitmentShareList,
+};
+use super::signature::{PartialThresholdSignature, Signer};
+
+/// An untrusted helper precomputing and storing commitment shares on behalf of a signer.
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DelegatedPrecompute<C: CipherSuite> {
+    /// The index of the participant on whose behalf the commitment shares are stored.
+    pub(crate) participant_index: u32,
+    /// The position of the next commitment share to be generated.
+    pub(crate) next_position: u64,
+    /// The stored commitment shares which have not been released yet, by position.
+    pub(crate) shares: BTreeMap<u64, CommitmentShare<C>>,
+}
+
+impl<C: CipherSuite> DelegatedPrecompute<C> {
+    /// Construct a new [`DelegatedPrecompute`] helper for the participant with
+    /// the given `participant_index`.
+    pub fn new(participant_index: u32) -> Self {
+        Self {
+            participant_index,
+            next_position: 0,
+            shares: BTreeMap::new(),
+        }
+    }
+
+    /// The number of stored commitment shares which have not been released yet.
+    pub fn remaining(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Generate and store `number_of_shares` new commitment shares.
+    ///
+    /// # Returns
+    ///
+    /// The [`DelegatedCommitments`] of the new commitment shares, to be sent to the signer.
+    pub fn generate(
+        &mut self,
+        number_of_shares: usize,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> DelegatedCommitments<C> {
+        let first_position = self.next_position;
+        let mut commitments = Vec::with_capacity(number_of_shares);
+
+        for _ in 0..number_of_shares {
+            let share = CommitmentShare::from(NoncePair(
+                Scalar::<C>::rand(&mut csprng),
+                Scalar::<C>::rand(&mut csprng),
+            ));
+            commitments.push(share.publish());
+            self.shares.insert(self.next_position, share);
+            self.next_position += 1;
+        }
+
+        DelegatedCommitments {
+            participant_index: self.participant_index,
+            first_position,
+            commitments,
+        }
+    }
+
+    /// Release the commitment share stored at the given `position` to the signer,
+    /// deleting it from this helper.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`CommitmentShare`], otherwise
+    /// an [`Error::MissingCommitmentShares`] if there is none at this position.
+    pub fn release(&mut self, position: u64) -> FrostResult<C, CommitmentShare<C>> {
+        self.shares
+            .remove(&position)
+            .ok_or(Error::MissingCommitmentShares)
+    }
+
+    /// Serialize this [`DelegatedPrecompute`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`DelegatedPrecompute`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for DelegatedPrecompute<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.shares
+            .values()
+            .try_for_each(ValidatePoints::validate_points)
+    }
+}
+
+/// The unblinded commitments of consecutive commitment shares generated by a
+/// [`DelegatedPrecompute`] helper.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DelegatedCommitments<C: CipherSuite> {
+    /// The index of the participant on whose behalf the commitment shares are stored.
+    pub participant_index: u32,
+    /// The position of the first commitment share.
+    pub first_position: u64,
+    /// The unblinded commitments, by increasing position.
+    pub commitments: Vec<(C::G, C::G)>,
+}
+
+impl<C: CipherSuite> DelegatedCommitments<C> {
+    /// Serialize these [`DelegatedCommitments`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize [`DelegatedCommitments`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for DelegatedCommitments<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>(
+            self.commitments
+                .iter()
+                .flat_map(|(hiding, binding)| [hiding, binding]),
+        )
+    }
+}
+
+/// The key of a signer blinding the commitment shares stored by a [`DelegatedPrecompute`] helper.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct BlindingKey<C: CipherSuite> {
+    /// The index of the participant owning this key.
+    pub(crate) participant_index: u32,
+    /// The seed of the blinding factors.
+    pub(crate) seed: Vec<u8>,
+    /// The number of positions whose commitments were blinded.
+    pub(crate) blinded: u64,
+    /// A bitmap of the blinded positions which were consumed.
+    pub(crate) consumed: Vec<u8>,
+    #[zeroize(skip)]
+    _phantom: core::marker::PhantomData<C>,
+}
+
+impl<C: CipherSuite> Drop for BlindingKey<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> ConstantTimeEq for BlindingKey<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.participant_index.ct_eq(&other.participant_index)
+            & self.seed.as_slice().ct_eq(other.seed.as_slice())
+            & self.blinded.ct_eq(&other.blinded)
+            & self.consumed.as_slice().ct_eq(other.consumed.as_slice())
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for BlindingKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for BlindingKey<C> {}
+
+impl<C: CipherSuite> BlindingKey<C> {
+    /// Construct a new [`BlindingKey`] for the participant with the given
+    /// `signing_key`, from a freshly sampled seed.
+    pub fn new(
+        signing_key: &IndividualSigningKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let mut seed = vec![0u8; C::HashOutput::default().as_ref().len()];
+        csprng.fill_bytes(&mut seed);
+
+        Self {
+            participant_index: signing_key.index,
+            seed,
+            blinded: 0,
+            consumed: Vec::new(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// The number of positions whose commitments were blinded, which is also
+    /// the first position expected from the next [`DelegatedCommitments`].
+    pub fn blinded(&self) -> u64 {
+        self.blinded
+    }
+
+    /// Blind the `delegated_commitments` received from a [`DelegatedPrecompute`] helper.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PublicCommitmentShareList`] to be
+    /// published by this signer, otherwise an [`Error::Custom`] if the commitments were
+    /// generated on behalf of another participant, or skip positions which were not
+    /// blinded yet.
+    pub fn blind(
+        &mut self,
+        delegated_commitments: &DelegatedCommitments<C>,
+    ) -> FrostResult<C, PublicCommitmentShareList<C>> {
+        self.check_participant_index(delegated_commitments.participant_index)?;
+        let end = delegated_commitments
+            .first_position
+            .checked_add(delegated_commitments.commitments.len() as u64);
+        let end = match end {
+            Some(end) if delegated_commitments.first_position <= self.blinded => end,
+            _ => {
+                return Err(Error::Custom(
+                    "The delegated commitments skip some positions".to_string(),
+                ))
+            }
+        };
+
+        let mut commitments = Vec::with_capacity(delegated_commitments.commitments.len());
+        for (position, (hiding, binding)) in
+            (delegated_commitments.first_position..).zip(delegated_commitments.commitments.iter())
+        {
+            let (mut hiding_blinding, mut binding_blinding) = self.blinding_factors(position)?;
+            commitments.push((
+                *hiding + C::G::generator() * hiding_blinding,
+                *binding + C::G::generator() * binding_blinding,
+            ));
+            hiding_blinding.zeroize();
+            binding_blinding.zeroize();
+        }
+
+        if end > self.blinded {
+            self.blinded = end;
+            self.consumed.resize(bitmap_length(end) as usize, 0);
+        }
+
+        Ok(PublicCommitmentShareList {
+            participant_index: self.participant_index,
+            commitments,
+        })
+    }
+
+    /// Unblind the `released_share` of the given `position`, as released by a
+    /// [`DelegatedPrecompute`] helper, consuming this position.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the unblinded [`CommitmentShare`], otherwise
+    /// an [`Error::MissingCommitmentShares`] if the position was not blinded yet or
+    /// was already consumed.
+    pub fn unblind(
+        &mut self,
+        position: u64,
+        released_share: &CommitmentShare<C>,
+    ) -> FrostResult<C, CommitmentShare<C>> {
+        self.check_unconsumed(position)?;
+        let commitment_share = self.unblind_share(position, released_share)?;
+        self.mark_consumed(position);
+
+        Ok(commitment_share)
+    }
+
+    /// Create a [`PartialThresholdSignature`] as with [`IndividualSigningKey::sign`],
+    /// with the `released_share` of the given `position`, once unblinded.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PartialThresholdSignature`], otherwise
+    /// an [`Error::MissingCommitmentShares`] if the position was not blinded yet or was
+    /// already consumed, or if the unblinded commitment share does not match the one
+    /// published by this signer in `signers`.
+    pub fn sign(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        position: u64,
+        released_share: &CommitmentShare<C>,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        self.check_participant_index(signing_key.index)?;
+        self.check_unconsumed(position)?;
+        let commitment_share = self.unblind_share(position, released_share)?;
+
+        let is_published = signers.iter().any(|signer| {
+            signer.participant_index == signing_key.index
+                && signer.published_commitment_share == commitment_share.publish()
+        });
+        if !is_published {
+            return Err(Error::MissingCommitmentShares);
+        }
+        self.mark_consumed(position);
+
+        let mut secret_commitment_share_list = SecretCommitmentShareList {
+            commitments: vec![commitment_share],
+        };
+        signing_key.sign(
+            message_hash,
+            group_key,
+            &mut secret_commitment_share_list,
+            0,
+            signers,
+        )
+    }
+
+    /// Serialize this [`BlindingKey`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`BlindingKey`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let key = Self::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        if key.participant_index == 0
+            || key.seed.len() != C::HashOutput::default().as_ref().len()
+            || key.consumed.len() as u64 != bitmap_length(key.blinded)
+        {
+            return Err(Error::DeserializationError(
+                ark_serialize::SerializationError::InvalidData.into(),
+            ));
+        }
+
+        Ok(key)
+    }
+
+    /// Check that the given `position` was blinded and not consumed yet.
+    fn check_unconsumed(&self, position: u64) -> FrostResult<C, ()> {
+        if position >= self.blinded {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let (byte, mask) = ((position / 8) as usize, 1u8 << (position % 8));
+        if self.consumed[byte] & mask != 0 {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        Ok(())
+    }
+
+    /// Mark the given blinded `position` as consumed.
+    fn mark_consumed(&mut self, position: u64) {
+        self.consumed[(position / 8) as usize] |= 1u8 << (position % 8);
+    }
+
+    /// Unblind the `released_share` of the given `position`, without consuming it.
+    fn unblind_share(
+        &self,
+        position: u64,
+        released_share: &CommitmentShare<C>,
+    ) -> FrostResult<C, CommitmentShare<C>> {
+        let (hiding_blinding, binding_blinding) = self.blinding_factors(position)?;
+
+        Ok(CommitmentShare::from(NoncePair(
+            released_share.hiding.secret + hiding_blinding,
+            released_share.binding.secret + binding_blinding,
+        )))
+    }
+
+    fn check_participant_index(&self, participant_index: u32) -> FrostResult<C, ()> {
+        if participant_index != self.participant_index {
+            return Err(Error::Custom(
+                "The blinding key belongs to another participant".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Derive the blinding factors of the hiding and binding nonces at `position`.
+    fn blinding_factors(&self, position: u64) -> FrostResult<C, (Scalar<C>, Scalar<C>)> {
+        let derive_blinding = |domain: &str| -> FrostResult<C, Scalar<C>> {
+            let mut blinding_input = domain.as_bytes().to_vec();
+            blinding_input.extend(&self.seed);
+            blinding_input.extend(&position.to_le_bytes());
+            let blinding = C::h3(&blinding_input);
+            blinding_input.zeroize();
+
+            blinding
+        };
+
+        Ok((
+            derive_blinding("delegated hiding")?,
+            derive_blinding("delegated binding")?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn delegated_precomputation_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        // The first signer delegates its commitment shares to a helper.
+        let mut blinding_key = BlindingKey::new(&signing_keys[0], OsRng);
+        let mut helper = DelegatedPrecompute::<Secp256k1Sha256>::new(signing_keys[0].index);
+        let delegated_commitments = helper.generate(3, OsRng);
+        let delegated_commitments =
+            DelegatedCommitments::from_bytes(&delegated_commitments.to_bytes().unwrap()).unwrap();
+        let blinded = blinding_key.blind(&delegated_commitments).unwrap();
+        assert_eq!(blinded.commitments.len(), 3);
+        assert!(blinded
+            .commitments
+            .iter()
+            .zip(delegated_commitments.commitments.iter())
+            .all(|(blinded, unblinded)| blinded.0 != unblinded.0 && blinded.1 != unblinded.1));
+
+        let mut helper = DelegatedPrecompute::from_bytes(&helper.to_bytes().unwrap()).unwrap();
+        let mut blinding_key = BlindingKey::from_bytes(&blinding_key.to_bytes().unwrap()).unwrap();
+        assert_eq!(blinding_key.blinded(), 3);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, &signing_keys[1], 1).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        aggregator
+            .include_signer(1, blinded.commitments[1], (&signing_keys[0]).into())
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&signing_keys[1]).into(),
+            )
+            .unwrap();
+        let signers = aggregator.get_signers().clone();
+
+        // A share released for another position does not match the published commitment.
+        let wrong_share = helper.release(0).unwrap();
+        assert_eq!(
+            blinding_key
+                .sign(
+                    &signing_keys[0],
+                    &message_hash,
+                    &group_key,
+                    1,
+                    &wrong_share,
+                    &signers
+                )
+                .unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+
+        let released_share = helper.release(1).unwrap();
+        assert_eq!(helper.remaining(), 1);
+        assert_eq!(
+            helper.release(1).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+
+        let p1_partial = blinding_key
+            .sign(
+                &signing_keys[0],
+                &message_hash,
+                &group_key,
+                1,
+                &released_share,
+                &signers,
+            )
+            .unwrap();
+        let p2_partial = signing_keys[1]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // The blinding key only signs on behalf of its owner.
+        assert!(blinding_key
+            .sign(
+                &signing_keys[1],
+                &message_hash,
+                &group_key,
+                1,
+                &released_share,
+                &signers
+            )
+            .is_err());
+
+        // A consumed position cannot be signed with nor unblinded again,
+        // even after persisting the blinding key.
+        let mut blinding_key = BlindingKey::from_bytes(&blinding_key.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            blinding_key
+                .sign(
+                    &signing_keys[0],
+                    &message_hash,
+                    &group_key,
+                    1,
+                    &released_share,
+                    &signers
+                )
+                .unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+        assert_eq!(
+            blinding_key.unblind(1, &released_share).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+
+        // Positions which were not blinded cannot be unblinded.
+        let unknown_share = helper.release(2).unwrap();
+        assert!(blinding_key.unblind(2, &unknown_share).is_ok());
+        assert_eq!(
+            blinding_key.unblind(3, &unknown_share).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+
+        // Delegated commitments skipping positions are rejected.
+        let mut skipping_commitments = helper.generate(1, OsRng);
+        skipping_commitments.first_position = 4;
+        assert!(blinding_key.blind(&skipping_commitments).is_err());
+        assert_eq!(blinding_key.blinded(), 3);
+
+        // An inconsistent bitmap of consumed positions is rejected.
+        blinding_key.consumed.push(0);
+        assert!(
+            BlindingKey::<Secp256k1Sha256>::from_bytes(&blinding_key.to_bytes().unwrap()).is_err()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! The dry-run module for rehearsing ICE-FROST signing sessions.
+//!
+//! A dry-run session goes through the whole signing flow, from the generation
+//! of commitment shares to the aggregation of a [`ThresholdSignature`], without
+//! consuming any of the participants' precomputed [`CommitmentShare`]s. Signers
+//! use ephemeral nonces derived under a reserved domain, and sign a reserved
+//! message bound to the group key instead of an arbitrary one.
+//!
+//! The challenge of a dry-run signature is hashed under a domain reserved to
+//! dry-runs, hence the resulting signature is only valid with
+//! [`ThresholdSignature::verify_dry_run`], and can therefore not be mistaken
+//! for a signature over any message, even a pre-hashed one.
+//!
+//! Note that the dry-run nonces are not deterministic: they are still drawn from
+//! the provided RNG, as deterministic nonces reused across different sets of signers
+//! would leak the participants' signing keys.
+
+use ark_serialize::CanonicalSerialize;
+use rand::{CryptoRng, Rng};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{hash_to_array, vec, BTreeMap, Scalar, Vec};
+use crate::{Error, FrostResult};
+
+use super::precomputation::{CommitmentShare, NoncePair, SecretCommitmentShareList};
+use super::signature::{PartialThresholdSignature, Signer, ThresholdSignature};
+
+/// The domain tag reserved to dry-run signing sessions.
+const DRY_RUN_DOMAIN: &str = "dry-run";
+
+/// Compute the reserved message hash signed during dry-run sessions of
+/// the group with the given `group_key`.
+pub fn dry_run_message_hash<C: CipherSuite>(
+    group_key: &GroupVerifyingKey<C>,
+) -> FrostResult<C, C::HashOutput> {
+    hash_to_array::<C>(
+        (C::context_string() + DRY_RUN_DOMAIN).as_bytes(),
+        &group_key.to_bytes()?,
+    )
+}
+
+/// Compute the challenge of a dry-run signature with the given `group_commitment`,
+/// under the given `group_key`, on the message with the given `message_hash`.
+///
+/// It hashes the same input as [`CipherSuite::challenge`], under the challenge
+/// domain tag of the [`CipherSuite`] extended with the dry-run domain.
+pub(crate) fn compute_dry_run_challenge<C: CipherSuite>(
+    group_commitment: &C::G,
+    group_key: &GroupVerifyingKey<C>,
+    message_hash: &[u8],
+) -> FrostResult<C, Scalar<C>> {
+    let mut challenge_input = Vec::with_capacity(
+        group_commitment.compressed_size() + group_key.key.compressed_size() + message_hash.len(),
+    );
+    group_commitment
+        .serialize_compressed(&mut challenge_input)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+    group_key
+        .key
+        .serialize_compressed(&mut challenge_input)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+    challenge_input.extend(message_hash);
+
+    C::hash_to_field(
+        (C::challenge_domain_tag() + DRY_RUN_DOMAIN).as_bytes(),
+        &challenge_input,
+    )
+}
+
+/// An ephemeral commitment share, only usable to sign during a dry-run session.
+///
+/// It is consumed when signing, and cannot be cloned nor serialized.
+#[derive(Debug)]
+pub struct DryRunCommitmentShare<C: CipherSuite>(CommitmentShare<C>);
+
+impl<C: CipherSuite> DryRunCommitmentShare<C> {
+    /// Publish the public commitments in this [`DryRunCommitmentShare`].
+    pub fn publish(&self) -> (C::G, C::G) {
+        self.0.publish()
+    }
+}
+
+/// Generate an ephemeral [`DryRunCommitmentShare`] for a dry-run signing session,
+/// whose nonces are derived under a domain reserved to dry-runs.
+///
+/// # Returns
+///
+/// A tuple of the public commitments to publish to the aggregator, and of
+/// the [`DryRunCommitmentShare`] to later sign with, or an error if the
+/// nonce derivation failed.
+pub fn generate_dry_run_commitment_share<C: CipherSuite>(
+    csprng: impl CryptoRng + Rng,
+    participant_secret_key: &IndividualSigningKey<C>,
+) -> FrostResult<C, ((C::G, C::G), DryRunCommitmentShare<C>)> {
+    let share: CommitmentShare<C> =
+        NoncePair::new_with_domain(participant_secret_key, DRY_RUN_DOMAIN.as_bytes(), csprng)?
+            .into();
+
+    Ok((share.publish(), DryRunCommitmentShare(share)))
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Compute an individual signer's [`PartialThresholdSignature`] contribution to
+    /// a dry-run [`ThresholdSignature`] of the group with the given `group_key`.
+    ///
+    /// # Inputs
+    ///
+    /// * The `group_key` of the group performing the dry-run,
+    /// * This signer's ephemeral [`DryRunCommitmentShare`], which is consumed,
+    /// * The list of all the currently participating [`Signer`]s.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok value contains a [`PartialThresholdSignature`], which
+    /// should be sent to the dry-run signature aggregator.
+    pub fn dry_run_sign(
+        &self,
+        group_key: &GroupVerifyingKey<C>,
+        commitment_share: DryRunCommitmentShare<C>,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        let message_hash = dry_run_message_hash(group_key)?;
+        let mut commitment_share_list = SecretCommitmentShareList {
+            commitments: vec![commitment_share.0],
+        };
+
+        self.sign_inner(
+            message_hash.as_ref(),
+            group_key,
+            &mut commitment_share_list,
+            0,
+            signers,
+            &BTreeMap::new(),
+            true,
+        )
+    }
+}
+
+impl<C: CipherSuite> ThresholdSignature<C> {
+    /// Verify this [`ThresholdSignature`] as the outcome of a dry-run
+    /// signing session of the group with the given `group_key`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the dry-run signature was computed correctly.
+    pub fn verify_dry_run(&self, group_key: &GroupVerifyingKey<C>) -> FrostResult<C, ()> {
+        let challenge = compute_dry_run_challenge::<C>(
+            &self.group_commitment,
+            group_key,
+            dry_run_message_hash(group_key)?.as_ref(),
+        )?;
+
+        self.verify_with_challenge(group_key, challenge)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::SignatureAggregator;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn dry_run_signing_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let mut aggregator = SignatureAggregator::new_dry_run(params, group_key);
+        let mut dry_run_comshares = Vec::new();
+        for sk in [&signing_keys[0], &signing_keys[2], &signing_keys[3]] {
+            let (public_comshare, dry_run_comshare) =
+                generate_dry_run_commitment_share(&mut OsRng, sk).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshare, sk.into())
+                .unwrap();
+            dry_run_comshares.push(dry_run_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+
+        for (sk, comshare) in [&signing_keys[0], &signing_keys[2], &signing_keys[3]]
+            .into_iter()
+            .zip(dry_run_comshares)
+        {
+            let partial = sk.dry_run_sign(&group_key, comshare, &signers).unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+
+        assert!(threshold_signature.verify_dry_run(&group_key).is_ok());
+
+        // A dry-run signature is not valid for any regular message.
+        let message_hash = Secp256k1Sha256::h4(&group_key.to_bytes().unwrap()).unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_err());
+
+        // Nor for the reserved dry-run message itself, when verified as pre-hashed.
+        let message_hash = crate::sign::dry_run_message_hash(&group_key).unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_err());
+        assert!(threshold_signature
+            .verify_lowmem(&group_key, &message_hash)
+            .is_err());
+    }
+}
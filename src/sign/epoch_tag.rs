@@ -0,0 +1,232 @@
+//! The epoch tag module, letting signers stamp their partial signatures with the key
+//! epoch they sign under, so that aggregators detect signers left behind by a reshare.
+//!
+//! Resharing the secret shares of a group keeps its group key, and a participant which
+//! missed a resharing session hence keeps producing partial signatures with its former
+//! share, which only fail once aggregated. Signers instead attach an [`EpochTag`] to
+//! their [`PartialThresholdSignature`] with [`PartialThresholdSignature::with_epoch`],
+//! holding the number of their key epoch, e.g. as given by
+//! [`EpochedKeyManager::signing_package`](crate::epoch::EpochedKeyManager::signing_package),
+//! along with the [`Fingerprint`](crate::fingerprint::Fingerprint) of the group key.
+//!
+//! An aggregator expecting a given epoch, set with [`SignatureAggregator::with_epoch`],
+//! then rejects partial signatures which are untagged, or tagged with another epoch or
+//! group key, as soon as they are included, while other aggregators ignore epoch tags.
+//! Partial signatures recovered from pseudonymous or blinded ones must hence be tagged
+//! again before being included in such an aggregator.
+
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+};
+
+use crate::ciphersuite::CipherSuite;
+use crate::fingerprint::FINGERPRINT_LENGTH;
+use crate::keys::GroupVerifyingKey;
+use crate::{Error, FrostResult};
+
+use super::signature::{Initial, PartialThresholdSignature, SignatureAggregator};
+
+/// The key epoch and group key fingerprint a [`PartialThresholdSignature`] was
+/// produced under.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, CanonicalSerialize)]
+pub struct EpochTag {
+    /// The number of the key epoch of the signer.
+    pub(crate) epoch: u64,
+    /// The bytes of the fingerprint of the group key of the signer.
+    pub(crate) key_fingerprint: [u8; FINGERPRINT_LENGTH],
+}
+
+impl Valid for EpochTag {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+// The key fingerprint is read in full, as the arkworks deserialization
+// of fixed-size arrays panics instead of failing on truncated inputs.
+impl CanonicalDeserialize for EpochTag {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let epoch = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let mut key_fingerprint = [0u8; FINGERPRINT_LENGTH];
+        reader.read_exact(&mut key_fingerprint)?;
+
+        Ok(Self {
+            epoch,
+            key_fingerprint,
+        })
+    }
+}
+
+impl EpochTag {
+    /// The number of the key epoch of the signer.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The bytes of the fingerprint of the group key of the signer.
+    pub fn key_fingerprint(&self) -> &[u8; FINGERPRINT_LENGTH] {
+        &self.key_fingerprint
+    }
+}
+
+impl<C: CipherSuite> PartialThresholdSignature<C> {
+    /// Tag this [`PartialThresholdSignature`] with the key `epoch` of its signer and
+    /// the fingerprint of the `group_key` it was produced under.
+    pub fn with_epoch(
+        mut self,
+        epoch: u64,
+        group_key: &GroupVerifyingKey<C>,
+    ) -> FrostResult<C, Self> {
+        self.epoch_tag = Some(EpochTag {
+            epoch,
+            key_fingerprint: *group_key.fingerprint()?.as_bytes(),
+        });
+
+        Ok(self)
+    }
+
+    /// The [`EpochTag`] of this [`PartialThresholdSignature`], if any.
+    pub fn epoch_tag(&self) -> Option<&EpochTag> {
+        self.epoch_tag.as_ref()
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Expect all partial signatures of this session to be tagged with the given key
+    /// `epoch`, rejecting untagged ones.
+    pub fn with_epoch(mut self, epoch: u64) -> Self {
+        self.epoch = Some(epoch);
+
+        self
+    }
+
+    /// Check that the given `partial_signature` is tagged with the expected epoch of
+    /// this session and its group key, if an expected epoch was set.
+    pub(super) fn check_epoch_tag(
+        &self,
+        partial_signature: &PartialThresholdSignature<C>,
+    ) -> FrostResult<C, ()> {
+        let expected = match self.epoch {
+            Some(expected) => expected,
+            None => return Ok(()),
+        };
+        let tag = partial_signature
+            .epoch_tag
+            .as_ref()
+            .ok_or(Error::MissingEpochTag(partial_signature.index))?;
+
+        // Reshares keep the group key, hence only the epoch tells them apart.
+        if tag.epoch != expected {
+            return Err(Error::EpochMismatch {
+                signer: partial_signature.index,
+                got: tag.epoch,
+                expected,
+            });
+        }
+
+        match tag.key_fingerprint == *self.state.group_key.fingerprint()?.as_bytes() {
+            true => Ok(()),
+            false => Err(Error::KeyFingerprintMismatch(partial_signature.index)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+    use crate::Element;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn epoch_tagged_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &message[..], None).with_epoch(1);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+
+        let mut partials = Vec::new();
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            partials.push(
+                sk.sign(&message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap(),
+            );
+        }
+
+        // Participant 2 missed the resharing to epoch 1.
+        let stale_partial = partials[1].clone().with_epoch(0, &group_key).unwrap();
+        assert_eq!(
+            PartialThresholdSignature::from_bytes(&stale_partial.to_bytes().unwrap()).unwrap(),
+            stale_partial
+        );
+        // Truncated epoch tags fail to deserialize instead of panicking.
+        let stale_bytes = stale_partial.to_bytes().unwrap();
+        for length in 0..stale_bytes.len() {
+            assert!(PartialThresholdSignature::<Secp256k1Sha256>::from_bytes(
+                &stale_bytes[..length]
+            )
+            .is_err());
+        }
+        assert_eq!(stale_partial.epoch_tag().unwrap().epoch(), 0);
+        let expected_error = || Error::EpochMismatch {
+            signer: 2,
+            got: 0,
+            expected: 1,
+        };
+        assert_eq!(
+            aggregator.verify_partial_signature(&stale_partial),
+            Err(expected_error())
+        );
+        assert_eq!(
+            aggregator.include_partial_signature(stale_partial),
+            Err(expected_error())
+        );
+        assert_eq!(aggregator.get_remaining_signers().len(), 2);
+
+        // Partial signatures produced under another group key are rejected as well.
+        let other_group_key = GroupVerifyingKey::new(Element::random(OsRng));
+        let foreign_partial = partials[0].clone().with_epoch(1, &other_group_key).unwrap();
+        assert_eq!(
+            aggregator.include_partial_signature(foreign_partial),
+            Err(Error::KeyFingerprintMismatch(1))
+        );
+
+        // Untagged partial signatures are rejected as well.
+        assert_eq!(
+            aggregator.include_partial_signature(partials[1].clone()),
+            Err(Error::MissingEpochTag(2))
+        );
+
+        for partial in partials {
+            aggregator
+                .include_partial_signature(partial.with_epoch(1, &group_key).unwrap())
+                .unwrap();
+        }
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+}
@@ -0,0 +1,421 @@
+//! The external module for computing ICE-FROST partial signatures with signing
+//! shares held by external devices, such as PKCS#11 tokens or TPMs.
+//!
+//! The response of a signer \\( z_i = d_i + e_i \rho_i + \lambda_i s_i c \\) involves
+//! its secret nonces \\( (d_i, e_i) \\) and its signing share \\( s_i \\). Revealing
+//! any of them to the host would allow it to recover the signing share, hence a
+//! [`ShareSigner`] computes the whole response, while the host only computes the public
+//! binding factor, Lagrange coefficient and challenge of the session, through [`sign_with`].
+//!
+//! Devices computing the whole partial signature internally can instead report their
+//! response along with the [`signing_commitment_hash`] of the session they signed, from
+//! which the host builds a [`PartialThresholdSignature`] with
+//! [`PartialThresholdSignature::from_parts`], or which the aggregator checks and includes
+//! with [`SignatureAggregator::include_external_partial_signature`].
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{calculate_lagrange_coefficients, vec, Vec};
+use crate::{Error, FrostResult, Scalar};
+
+use super::precomputation::SecretCommitmentShareList;
+use super::signature::{
+    binding_factor_for_participant, check_canonical_ordering, compute_binding_factors,
+    compute_challenge, compute_group_commitment, encode_group_commitment_list, Initial,
+    PartialThresholdSignature, SignatureAggregator, Signer,
+};
+
+/// A signer holding its signing share and secret nonces, possibly on an external device.
+pub trait ShareSigner<C: CipherSuite> {
+    /// The index of the participant whose signing share is held by this signer.
+    fn index(&self) -> u32;
+
+    /// Compute the response \\( z_i = d_i + e_i \rho_i + \lambda_i s_i c \\) with the
+    /// secret nonces matching the `published_commitment_share`, given the `binding_factor`
+    /// \\( \rho_i \\) and the product `lambda_challenge` of the Lagrange coefficient
+    /// \\( \lambda_i \\) and the challenge \\( c \\).
+    ///
+    /// Implementors **must** discard the secret nonces once used, to prevent nonce reuse.
+    fn compute_response(
+        &mut self,
+        published_commitment_share: &(C::G, C::G),
+        binding_factor: &Scalar<C>,
+        lambda_challenge: &Scalar<C>,
+    ) -> FrostResult<C, Scalar<C>>;
+}
+
+/// A [`ShareSigner`] holding its [`IndividualSigningKey`] and [`SecretCommitmentShareList`]
+/// in memory.
+#[derive(Debug)]
+pub struct LocalShareSigner<'a, C: CipherSuite> {
+    signing_key: &'a IndividualSigningKey<C>,
+    secret_commitment_share_list: &'a mut SecretCommitmentShareList<C>,
+}
+
+impl<'a, C: CipherSuite> LocalShareSigner<'a, C> {
+    /// Construct a new [`LocalShareSigner`] from a `signing_key` and its
+    /// `secret_commitment_share_list`.
+    pub fn new(
+        signing_key: &'a IndividualSigningKey<C>,
+        secret_commitment_share_list: &'a mut SecretCommitmentShareList<C>,
+    ) -> Self {
+        Self {
+            signing_key,
+            secret_commitment_share_list,
+        }
+    }
+}
+
+impl<'a, C: CipherSuite> ShareSigner<C> for LocalShareSigner<'a, C> {
+    fn index(&self) -> u32 {
+        self.signing_key.index
+    }
+
+    fn compute_response(
+        &mut self,
+        published_commitment_share: &(C::G, C::G),
+        binding_factor: &Scalar<C>,
+        lambda_challenge: &Scalar<C>,
+    ) -> FrostResult<C, Scalar<C>> {
+        let commitment_share = self
+            .secret_commitment_share_list
+            .commitments
+            .iter()
+            .find(|share| share.publish() == *published_commitment_share)
+            .ok_or(Error::MissingCommitmentShares)?
+            .clone();
+
+        let z = commitment_share.hiding.secret
+            + (commitment_share.binding.secret * binding_factor.0)
+            + (self.signing_key.key * lambda_challenge.0);
+
+        // Zero out our secrets from memory to prevent nonce reuse.
+        self.secret_commitment_share_list
+            .drop_share(commitment_share);
+
+        Ok(Scalar(z))
+    }
+}
+
+/// Compute the [`PartialThresholdSignature`] of a [`ShareSigner`] on a message.
+///
+/// # Inputs
+///
+/// * The [`ShareSigner`] of this participant,
+/// * The `message_hash` to be signed by every individual signer,
+/// * The public [`GroupVerifyingKey`] for this group of signing participants,
+/// * The list of all the currently participating [`Signer`]s (including ourself).
+///
+/// # Returns
+///
+/// A Result whose [`Ok`] value contains a [`PartialThresholdSignature`], which
+/// should be sent to the [`SignatureAggregator`](super::SignatureAggregator),
+/// otherwise an [`Error::MissingCommitmentShares`] if the [`ShareSigner`] is not
+/// part of the `signers`.
+pub fn sign_with<C: CipherSuite>(
+    share_signer: &mut impl ShareSigner<C>,
+    message_hash: &[u8],
+    group_key: &GroupVerifyingKey<C>,
+    signers: &[Signer<C>],
+) -> FrostResult<C, PartialThresholdSignature<C>> {
+    let index = share_signer.index();
+    let my_signer = signers
+        .iter()
+        .find(|signer| signer.participant_index == index)
+        .ok_or(Error::MissingCommitmentShares)?;
+
+    let binding_factor_list = compute_binding_factors(message_hash, signers)?;
+    let binding_factor = binding_factor_for_participant::<C>(index, &binding_factor_list)?;
+
+    let group_commitment = compute_group_commitment(signers, &binding_factor_list)?;
+
+    let all_participant_indices: Vec<u32> = signers.iter().map(|x| x.participant_index).collect();
+    let lambda = calculate_lagrange_coefficients::<C>(index, &all_participant_indices)?;
+
+    let challenge = compute_challenge::<C>(&group_commitment, group_key, message_hash)?;
+
+    let z = share_signer.compute_response(
+        &my_signer.published_commitment_share,
+        &Scalar(binding_factor),
+        &Scalar(lambda * challenge),
+    )?;
+
+    Ok(PartialThresholdSignature {
+        index,
+        z: z.0,
+        epoch_tag: None,
+    })
+}
+
+/// Compute the hash of the commitment list of a signing session between the given
+/// `signers`, as hashed into their binding factors.
+///
+/// External devices report this hash along with their response, so that it can be
+/// checked against the session of the host with [`PartialThresholdSignature::from_parts`].
+///
+/// The `signers` must be in canonical order, otherwise an [`Error::UnsortedSigners`]
+/// is returned.
+pub fn signing_commitment_hash<C: CipherSuite>(signers: &[Signer<C>]) -> FrostResult<C, Vec<u8>> {
+    check_canonical_ordering(signers)?;
+
+    let commitment_list: Vec<(u32, C::G, C::G)> = signers
+        .iter()
+        .map(|signer| {
+            let (hiding, binding) = signer.published_commitment_share;
+            (signer.participant_index, hiding, binding)
+        })
+        .collect();
+
+    Ok(
+        C::h5(&encode_group_commitment_list::<C>(&commitment_list)?)?
+            .as_ref()
+            .to_vec(),
+    )
+}
+
+impl<C: CipherSuite> PartialThresholdSignature<C> {
+    /// Build the [`PartialThresholdSignature`] of the participant with the given `index`
+    /// from the response `z` computed by an external device, which signed the session
+    /// whose commitment list has the hash `expected_commitment_hash`.
+    ///
+    /// This only checks that the device signed the session between the given `signers`,
+    /// the response itself being verified by
+    /// [`SignatureAggregator::verify_partial_signature`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PartialThresholdSignature`], otherwise:
+    ///
+    /// * an [`Error::MissingCommitmentShares`] if the participant is not part of the `signers`;
+    /// * an [`Error::MisbehavingParticipants`] listing the participant if `z` is zero, as
+    ///   returned by some devices on failure;
+    /// * an [`Error::InconsistentSigningPackage`] listing the participant if the device
+    ///   signed another commitment list.
+    pub fn from_parts(
+        index: u32,
+        z: Scalar<C>,
+        expected_commitment_hash: &[u8],
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, Self> {
+        if !signers
+            .iter()
+            .any(|signer| signer.participant_index == index)
+        {
+            return Err(Error::MissingCommitmentShares);
+        }
+        if z.is_zero() {
+            return Err(Error::MisbehavingParticipants(vec![index]));
+        }
+        if signing_commitment_hash(signers)? != expected_commitment_hash {
+            return Err(Error::InconsistentSigningPackage(vec![index]));
+        }
+
+        Ok(Self {
+            index,
+            z: z.0,
+            epoch_tag: None,
+        })
+    }
+
+    /// The index of the participant who made this [`PartialThresholdSignature`].
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Build the [`PartialThresholdSignature`] of an external device with
+    /// [`PartialThresholdSignature::from_parts`] against the signers of this session,
+    /// verify it with [`SignatureAggregator::verify_partial_signature`], and include it
+    /// in the aggregation.
+    ///
+    /// The set of signers must be complete.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the partial signature was
+    /// included, otherwise the error of the failed check, in which case it is not.
+    pub fn include_external_partial_signature(
+        &mut self,
+        index: u32,
+        z: Scalar<C>,
+        expected_commitment_hash: &[u8],
+    ) -> FrostResult<C, ()> {
+        let partial_signature = PartialThresholdSignature::from_parts(
+            index,
+            z,
+            expected_commitment_hash,
+            self.get_signers(),
+        )?;
+        self.verify_partial_signature(&partial_signature)?;
+        self.include_partial_signature(partial_signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::Projective;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn share_signer_2_out_of_3() {
+        /// A [`ShareSigner`] emulating a hardware token, which only exposes responses.
+        struct Token(
+            IndividualSigningKey<Secp256k1Sha256>,
+            SecretCommitmentShareList<Secp256k1Sha256>,
+        );
+
+        impl ShareSigner<Secp256k1Sha256> for Token {
+            fn index(&self) -> u32 {
+                self.0.index
+            }
+
+            fn compute_response(
+                &mut self,
+                published_commitment_share: &(Projective, Projective),
+                binding_factor: &crate::Scalar<Secp256k1Sha256>,
+                lambda_challenge: &crate::Scalar<Secp256k1Sha256>,
+            ) -> FrostResult<Secp256k1Sha256, crate::Scalar<Secp256k1Sha256>> {
+                LocalShareSigner::new(&self.0, &mut self.1).compute_response(
+                    published_commitment_share,
+                    binding_factor,
+                    lambda_challenge,
+                )
+            }
+        }
+
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut tokens = Vec::new();
+        for sk in signing_keys.iter().skip(1) {
+            let (public_comshares, secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, sk, 2).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[1], sk.into())
+                .unwrap();
+            tokens.push(Token(sk.clone(), secret_comshares));
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for token in tokens.iter_mut() {
+            let partial_signature = sign_with(token, &message_hash, &group_key, &signers).unwrap();
+            aggregator
+                .include_partial_signature(partial_signature)
+                .unwrap();
+            assert_eq!(token.1.commitments.len(), 1);
+        }
+
+        // Used nonces are discarded by the token.
+        assert_eq!(
+            sign_with(&mut tokens[0], &message_hash, &group_key, &signers),
+            Err(Error::MissingCommitmentShares)
+        );
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // A signer outside of the session cannot sign.
+        let (_, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, &signing_keys[0], 1).unwrap();
+        assert_eq!(
+            sign_with(
+                &mut LocalShareSigner::new(&signing_keys[0], &mut secret_comshares),
+                &message_hash,
+                &group_key,
+                &signers
+            ),
+            Err(Error::MissingCommitmentShares)
+        );
+    }
+
+    #[test]
+    fn external_partial_signatures_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().skip(1) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+
+        // The devices report their raw responses along with the hash of the commitment
+        // list they signed.
+        let commitment_hash = signing_commitment_hash(&signers).unwrap();
+        let responses: Vec<crate::Scalar<Secp256k1Sha256>> = signing_keys
+            .iter()
+            .skip(1)
+            .zip(secret_comshares.iter_mut())
+            .map(|(sk, secret_comshare)| {
+                let partial_signature = sk
+                    .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                    .unwrap();
+                crate::Scalar::from_inner(partial_signature.z)
+            })
+            .collect();
+
+        let partial_signature =
+            PartialThresholdSignature::from_parts(2, responses[0], &commitment_hash, &signers)
+                .unwrap();
+        assert_eq!(partial_signature.index(), 2);
+        assert_eq!(
+            PartialThresholdSignature::from_parts(1, responses[0], &commitment_hash, &signers),
+            Err(Error::MissingCommitmentShares)
+        );
+        assert_eq!(
+            PartialThresholdSignature::from_parts(
+                2,
+                crate::Scalar::zero(),
+                &commitment_hash,
+                &signers
+            ),
+            Err(Error::MisbehavingParticipants(vec![2]))
+        );
+        assert_eq!(
+            PartialThresholdSignature::from_parts(2, responses[0], &commitment_hash[1..], &signers),
+            Err(Error::InconsistentSigningPackage(vec![2]))
+        );
+
+        // Invalid responses are rejected by the aggregator.
+        assert_eq!(
+            aggregator.include_external_partial_signature(
+                3,
+                responses[1] + crate::Scalar::one(),
+                &commitment_hash
+            ),
+            Err(Error::MisbehavingParticipants(vec![3]))
+        );
+        assert_eq!(aggregator.get_remaining_signers().len(), 2);
+
+        for (index, response) in [2, 3].iter().zip(responses.iter()) {
+            aggregator
+                .include_external_partial_signature(*index, *response, &commitment_hash)
+                .unwrap();
+        }
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+}
@@ -0,0 +1,362 @@
+//! The fixed-size module for ICE-FROST signing sessions whose threshold
+//! is known at compile time.
+//!
+//! Given some [`ThresholdParametersConst`], the per-signer state of a signing
+//! session, i.e. the signers' commitments, verifying keys and partial signatures,
+//! is held in fixed-size arrays rather than in heap-allocated collections growing
+//! with the number of signers. The binding factors, the group commitment and the
+//! Lagrange coefficients are computed with the same helpers as regular signing
+//! sessions, which may still use transient buffers, as may the hash functions of
+//! the [`CipherSuite`].
+
+use ark_ec::Group;
+use ark_ff::{Field, Zero};
+
+use core::ops::Mul;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey};
+use crate::parameters::ThresholdParametersConst;
+use crate::utils::{calculate_lagrange_coefficients, vec, BTreeMap, Scalar, Vec};
+use crate::{Error, FrostResult};
+
+use super::precomputation::SecretCommitmentShareList;
+use super::signature::{
+    binding_factor_for_participant, check_canonical_ordering, commitment_for_participant,
+    compute_binding_factors, compute_challenge, compute_group_commitment,
+    PartialThresholdSignature, Signer, ThresholdSignature,
+};
+
+/// Check that the `signers` are in canonical order, and that their indices
+/// lie within `1..=N`.
+fn check_signers<C: CipherSuite, const T: usize, const N: usize>(
+    signers: &[Signer<C>; T],
+) -> FrostResult<C, ()> {
+    if signers.iter().any(|signer| signer.participant_index == 0) {
+        return Err(Error::IndexIsZero);
+    }
+    check_canonical_ordering(&signers[..])?;
+    if let Some(signer) = signers
+        .iter()
+        .find(|signer| signer.participant_index as usize > N)
+    {
+        return Err(Error::UnknownParticipant(signer.participant_index));
+    }
+
+    Ok(())
+}
+
+/// Interpolate the Lagrange coefficient of the participant at `my_index`
+/// among the `signers`.
+fn lagrange_coefficient<C: CipherSuite, const T: usize>(
+    my_index: u32,
+    signers: &[Signer<C>; T],
+) -> FrostResult<C, Scalar<C>> {
+    let indices: [u32; T] = core::array::from_fn(|i| signers[i].participant_index);
+
+    calculate_lagrange_coefficients::<C>(my_index, &indices)
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Compute an individual signer's [`PartialThresholdSignature`] contribution to
+    /// a [`ThresholdSignature`] on a message, for a threshold fixed at compile time.
+    ///
+    /// This is equivalent to [`IndividualSigningKey::sign`], with the `signers`
+    /// provided as a fixed-size array sorted by increasing index. The `parameters`
+    /// are not read, and only fix the threshold `T` and number of participants `N`
+    /// against which the `signers` are checked.
+    pub fn sign_const<const T: usize, const N: usize>(
+        &self,
+        _parameters: &ThresholdParametersConst<C, T, N>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>; T],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        if my_commitment_share_index + 1 > my_secret_commitment_share_list.commitments.len() {
+            return Err(Error::MissingCommitmentShares);
+        }
+        check_signers::<C, T, N>(signers)?;
+
+        let binding_factor_list = compute_binding_factors(message_hash, &signers[..])?;
+        let binding_factor = binding_factor_for_participant::<C>(self.index, &binding_factor_list)?;
+
+        let group_commitment = compute_group_commitment(&signers[..], &binding_factor_list)?;
+
+        let lambda = lagrange_coefficient(self.index, signers)?;
+
+        let my_commitment_share =
+            my_secret_commitment_share_list.commitments[my_commitment_share_index].clone();
+
+        let challenge = compute_challenge::<C>(&group_commitment, group_key, message_hash)?;
+
+        let z = my_commitment_share.hiding.secret
+            + (my_commitment_share.binding.secret * binding_factor)
+            + (lambda * self.key * challenge);
+
+        // Zero out our secrets from memory to prevent nonce reuse.
+        my_secret_commitment_share_list.drop_share(my_commitment_share);
+
+        Ok(PartialThresholdSignature {
+            index: self.index,
+            z,
+            epoch_tag: None,
+        })
+    }
+}
+
+/// A signature aggregator for a threshold fixed at compile time, holding
+/// the state of exactly `T` signers in fixed-size arrays.
+///
+/// This is equivalent to a [`super::SignatureAggregator`], whose signers
+/// are all known upon instantiation.
+#[derive(Debug)]
+pub struct SignatureAggregatorConst<C: CipherSuite, const T: usize, const N: usize> {
+    /// The group public key for all the participants.
+    group_key: GroupVerifyingKey<C>,
+    /// The hash of the message to be signed.
+    message_hash: C::HashOutput,
+    /// The signers, sorted by increasing index.
+    signers: [Signer<C>; T],
+    /// The verifying keys of the signers, in the same order.
+    public_keys: [C::G; T],
+    /// The partial signatures received from the signers, in the same order.
+    partial_signatures: [Option<Scalar<C>>; T],
+}
+
+impl<C: CipherSuite, const T: usize, const N: usize> SignatureAggregatorConst<C, T, N> {
+    /// Construct a new signature aggregator from the given compile-time `parameters`,
+    /// the `group_key`, the `message` to be signed, and the `signers` along with
+    /// their `verifying_keys`, both sorted by increasing index.
+    ///
+    /// As with [`IndividualSigningKey::sign_const`], the `parameters` are not read,
+    /// and only fix the threshold `T` and number of participants `N`.
+    pub fn new(
+        _parameters: ThresholdParametersConst<C, T, N>,
+        group_key: GroupVerifyingKey<C>,
+        message: &[u8],
+        signers: [Signer<C>; T],
+        verifying_keys: [IndividualVerifyingKey<C>; T],
+    ) -> FrostResult<C, Self> {
+        check_signers::<C, T, N>(&signers)?;
+
+        let mut public_keys = [C::G::zero(); T];
+        for ((public_key, signer), verifying_key) in public_keys
+            .iter_mut()
+            .zip(signers.iter())
+            .zip(verifying_keys.iter())
+        {
+            if signer.participant_index != verifying_key.index {
+                return Err(Error::MisbehavingParticipants(vec![
+                    signer.participant_index,
+                ]));
+            }
+            *public_key = verifying_key.share;
+        }
+
+        Ok(Self {
+            group_key,
+            message_hash: C::h4(message)?,
+            signers,
+            public_keys,
+            partial_signatures: [None; T],
+        })
+    }
+
+    /// Get the sorted list of participating signers.
+    pub fn signers(&self) -> &[Signer<C>; T] {
+        &self.signers
+    }
+
+    /// Get the hash of the message to be signed by the participants.
+    pub fn message_hash(&self) -> &[u8] {
+        self.message_hash.as_ref()
+    }
+
+    /// Include a partial signature from a signer of this session.
+    pub fn include_partial_signature(
+        &mut self,
+        partial_signature: PartialThresholdSignature<C>,
+    ) -> FrostResult<C, ()> {
+        let position = self
+            .signers
+            .binary_search_by_key(&partial_signature.index, |signer| signer.participant_index)
+            .map_err(|_| Error::MisbehavingParticipants(vec![partial_signature.index]))?;
+
+        self.partial_signatures[position] = Some(partial_signature.z);
+
+        Ok(())
+    }
+
+    /// Aggregate a set of previously-collected partial signatures.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose [`Ok`] value is a [`ThresholdSignature`], otherwise a
+    /// [`Error::MisbehavingParticipants`] listing the signers whose partial
+    /// signature is missing or invalid. As with [`super::SignatureAggregator`],
+    /// missing partial signatures are also blamed on the aggregator, "participant 0".
+    pub fn aggregate(&self) -> FrostResult<C, ThresholdSignature<C>> {
+        if self.partial_signatures.iter().any(Option::is_none) {
+            let mut misbehaving_participants = vec![0];
+            misbehaving_participants.extend(
+                self.signers
+                    .iter()
+                    .zip(self.partial_signatures.iter())
+                    .filter(|(_, z)| z.is_none())
+                    .map(|(signer, _)| signer.participant_index),
+            );
+
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        let message_hash = self.message_hash.as_ref();
+        let binding_factor_list = compute_binding_factors(message_hash, &self.signers[..])?;
+        let group_commitment = compute_group_commitment(&self.signers[..], &binding_factor_list)?;
+
+        let z = self
+            .partial_signatures
+            .iter()
+            .flatten()
+            .fold(Scalar::<C>::ZERO, |acc, z| acc + z);
+
+        let signature = ThresholdSignature {
+            z,
+            group_commitment,
+        };
+
+        // Verify the obtained signature, listing malicious participants
+        // if the verification failed.
+        match signature.verify(&self.group_key, message_hash) {
+            Ok(()) => Ok(signature),
+            Err(_) => {
+                let challenge =
+                    compute_challenge::<C>(&group_commitment, &self.group_key, message_hash)?;
+
+                let mut misbehaving_participants = Vec::new();
+                for (position, signer) in self.signers.iter().enumerate() {
+                    let lambda = lagrange_coefficient(signer.participant_index, &self.signers)?;
+                    let commitment = commitment_for_participant(
+                        signer.participant_index,
+                        message_hash,
+                        &self.signers[..],
+                        &BTreeMap::new(),
+                    )?;
+                    // This cannot fail, as we checked that all partial signatures are present.
+                    let z_i = self.partial_signatures[position].unwrap_or_default();
+
+                    if C::G::generator() * z_i
+                        != commitment + self.public_keys[position].mul(challenge * lambda)
+                    {
+                        misbehaving_participants.push(signer.participant_index);
+                    }
+                }
+
+                Err(Error::MisbehavingParticipants(misbehaving_participants))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn const_signing_3_out_of_5() {
+        let (_, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+        let parameters = ThresholdParametersConst::<Secp256k1Sha256, 3, 5>::new();
+        assert_eq!(
+            parameters.parameters(),
+            ThresholdParameters::new(5, 3).unwrap()
+        );
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let signing_keys = [&signing_keys[0], &signing_keys[2], &signing_keys[4]];
+        let mut secret_comshares = Vec::new();
+        let mut signers = Vec::new();
+        for sk in signing_keys {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            signers.push(Signer {
+                participant_index: sk.index,
+                published_commitment_share: public_comshares.commitments[0],
+            });
+            secret_comshares.push(secret_comshare);
+        }
+        let signers: [Signer<Secp256k1Sha256>; 3] = signers.try_into().unwrap();
+
+        let mut aggregator = SignatureAggregatorConst::new(
+            parameters,
+            group_key,
+            &message[..],
+            signers,
+            signing_keys.map(|sk| sk.into()),
+        )
+        .unwrap();
+
+        // Signatures are only aggregated once all signers have contributed.
+        assert_eq!(
+            aggregator.aggregate(),
+            Err(Error::MisbehavingParticipants(vec![0, 1, 3, 5]))
+        );
+
+        // Signers are checked as in regular signing sessions.
+        let unsorted_signers = [signers[1], signers[0], signers[2]];
+        assert_eq!(
+            signing_keys[0].sign_const(
+                &parameters,
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &unsorted_signers,
+            ),
+            Err(Error::UnsortedSigners)
+        );
+
+        for (sk, secret_comshare) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign_const(
+                    &parameters,
+                    &message_hash,
+                    &group_key,
+                    secret_comshare,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let threshold_signature = aggregator.aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // An invalid partial signature is attributed to its signer.
+        aggregator
+            .include_partial_signature(PartialThresholdSignature {
+                index: 3,
+                z: Fr::rand(&mut OsRng),
+                epoch_tag: None,
+            })
+            .unwrap();
+        assert_eq!(
+            aggregator.aggregate(),
+            Err(Error::MisbehavingParticipants(vec![3]))
+        );
+    }
+}
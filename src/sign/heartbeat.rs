@@ -0,0 +1,362 @@
+//! The heartbeat module for monitoring the liveness of the signers of a group.
+//!
+//! Each signer periodically emits a [`Heartbeat`], signed with its Diffie-Hellman
+//! private key, announcing the number of precomputed commitment shares it has left,
+//! the epoch of its key material and the version of its software. The coordinator
+//! of the signing sessions records them in a [`LivenessTracker`], from which fleet
+//! monitoring can spot stale or outdated signers, and robust signing loops such as
+//! ROAST can select standby signers known to be online and able to sign.
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::{Participant, Role};
+use crate::keys::{DhKeyProvider, DiffieHellmanPrivateKey, DiffieHellmanPublicKey};
+use crate::utils::{BTreeMap, Scalar, String, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Error, FrostResult};
+
+/// A liveness announcement of a signer, signed with its Diffie-Hellman private key.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Heartbeat<C: CipherSuite> {
+    /// The index of the signer.
+    pub(crate) index: u32,
+    /// The time of emission, according to the clock of the signer.
+    pub(crate) timestamp: u64,
+    /// The epoch of the key material of the signer.
+    pub(crate) epoch: u64,
+    /// The number of precomputed commitment shares the signer has left.
+    pub(crate) remaining_commitments: u64,
+    /// The version of the software run by the signer.
+    pub(crate) software_version: String,
+    /// The commitment of the Schnorr signature.
+    pub(crate) r: C::G,
+    /// The response of the Schnorr signature.
+    pub(crate) s: Scalar<C>,
+}
+
+impl<C: CipherSuite> Heartbeat<C> {
+    /// Emit a new [`Heartbeat`] of the signer with the given `index` at the time
+    /// `timestamp`, signed with its `dh_private_key`.
+    pub fn new(
+        index: u32,
+        timestamp: u64,
+        epoch: u64,
+        remaining_commitments: u64,
+        software_version: &str,
+        dh_private_key: &DiffieHellmanPrivateKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Self> {
+        let k = Scalar::<C>::rand(&mut csprng);
+
+        let mut heartbeat = Self {
+            index,
+            timestamp,
+            epoch,
+            remaining_commitments,
+            software_version: software_version.into(),
+            r: C::G::generator() * k,
+            s: Scalar::<C>::ZERO,
+        };
+        let challenge = heartbeat.challenge(&dh_private_key.dh_public_key()?)?;
+        heartbeat.s = k + dh_private_key.0 * challenge;
+
+        Ok(heartbeat)
+    }
+
+    /// The index of the signer.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The time of emission, according to the clock of the signer.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// The epoch of the key material of the signer.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The number of precomputed commitment shares the signer has left.
+    pub fn remaining_commitments(&self) -> u64 {
+        self.remaining_commitments
+    }
+
+    /// The version of the software run by the signer.
+    pub fn software_version(&self) -> &str {
+        &self.software_version
+    }
+
+    /// Verify this [`Heartbeat`] against the Diffie-Hellman public key of its signer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the heartbeat is valid,
+    /// otherwise an [`Error::InvalidHeartbeat`].
+    pub fn verify(&self, dh_public_key: &DiffieHellmanPublicKey<C>) -> FrostResult<C, ()> {
+        let challenge = self.challenge(dh_public_key)?;
+
+        match C::G::generator() * self.s == self.r + dh_public_key.key * challenge {
+            true => Ok(()),
+            false => Err(Error::InvalidHeartbeat),
+        }
+    }
+
+    /// Serialize this [`Heartbeat`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`Heartbeat`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+
+    /// Compute the challenge of the Schnorr signature of this [`Heartbeat`].
+    fn challenge(&self, dh_public_key: &DiffieHellmanPublicKey<C>) -> FrostResult<C, Scalar<C>> {
+        let mut message = Vec::new();
+        self.r
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        dh_public_key
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        message.extend(&self.index.to_le_bytes());
+        message.extend(&self.timestamp.to_le_bytes());
+        message.extend(&self.epoch.to_le_bytes());
+        message.extend(&self.remaining_commitments.to_le_bytes());
+        message.extend(&(self.software_version.len() as u64).to_le_bytes());
+        message.extend(self.software_version.as_bytes());
+
+        C::hash_to_field((C::context_string() + "heartbeat").as_bytes(), &message)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for Heartbeat<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.r)
+    }
+}
+
+/// The latest [`Heartbeat`] of a signer, along with its time of reception.
+#[derive(Clone, Debug)]
+struct SignerLiveness<C: CipherSuite> {
+    /// The Diffie-Hellman public key of the signer.
+    dh_public_key: DiffieHellmanPublicKey<C>,
+    /// The latest heartbeat of the signer, and the time it was received at.
+    latest: Option<(u64, Heartbeat<C>)>,
+}
+
+/// A record of the latest [`Heartbeat`]s of the signers of a group, kept by the
+/// coordinator of their signing sessions.
+///
+/// Reception times are expressed in the same time unit as the current time given to
+/// the methods of this tracker, for instance in seconds since the UNIX epoch, and are
+/// only compared with each other, so that the clocks of the signers do not need to
+/// be synchronized with the one of the coordinator.
+#[derive(Clone, Debug)]
+pub struct LivenessTracker<C: CipherSuite> {
+    signers: BTreeMap<u32, SignerLiveness<C>>,
+}
+
+impl<C: CipherSuite> LivenessTracker<C> {
+    /// Start tracking the liveness of the given `participants`.
+    pub fn new<R: Role<C>>(participants: &[Participant<C, R>]) -> Self {
+        let signers = participants
+            .iter()
+            .map(|participant| {
+                (
+                    participant.index,
+                    SignerLiveness {
+                        dh_public_key: participant.dh_public_key.clone(),
+                        latest: None,
+                    },
+                )
+            })
+            .collect();
+
+        Self { signers }
+    }
+
+    /// Record a `heartbeat` received at the time `now`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the heartbeat was recorded,
+    /// otherwise an [`Error::InvalidHeartbeat`] if it is not signed by a tracked
+    /// signer, or if it is not more recent than the latest heartbeat of its signer,
+    /// e.g. because it was replayed.
+    pub fn record(&mut self, heartbeat: Heartbeat<C>, now: u64) -> FrostResult<C, ()> {
+        let signer = self
+            .signers
+            .get_mut(&heartbeat.index)
+            .ok_or(Error::InvalidHeartbeat)?;
+        heartbeat.verify(&signer.dh_public_key)?;
+
+        if let Some((_, latest)) = &signer.latest {
+            if heartbeat.timestamp <= latest.timestamp {
+                return Err(Error::InvalidHeartbeat);
+            }
+        }
+        signer.latest = Some((now, heartbeat));
+
+        Ok(())
+    }
+
+    /// The latest [`Heartbeat`] of the signer with the given `index`, if any.
+    pub fn latest(&self, index: u32) -> Option<&Heartbeat<C>> {
+        self.signers
+            .get(&index)
+            .and_then(|signer| signer.latest.as_ref())
+            .map(|(_, heartbeat)| heartbeat)
+    }
+
+    /// The sorted indices of the signers whose latest heartbeat was received no
+    /// earlier than `max_age` before the time `now`.
+    pub fn live_signers(&self, now: u64, max_age: u64) -> Vec<u32> {
+        self.live_heartbeats(now, max_age)
+            .map(|heartbeat| heartbeat.index)
+            .collect()
+    }
+
+    /// The sorted indices of the signers whose latest heartbeat was received earlier
+    /// than `max_age` before the time `now`, or which never sent any.
+    pub fn stale_signers(&self, now: u64, max_age: u64) -> Vec<u32> {
+        let live_signers = self.live_signers(now, max_age);
+
+        self.signers
+            .keys()
+            .filter(|index| live_signers.binary_search(index).is_err())
+            .copied()
+            .collect()
+    }
+
+    /// The indices of the live signers, as with [`LivenessTracker::live_signers`], which
+    /// hold key material of the given `epoch` and at least `minimum_commitments`
+    /// precomputed commitment shares, ordered by decreasing number of commitment shares.
+    ///
+    /// These are the best candidates to stand by for a signing session, for instance
+    /// to replace unresponsive signers in a ROAST loop.
+    pub fn standby_candidates(
+        &self,
+        now: u64,
+        max_age: u64,
+        epoch: u64,
+        minimum_commitments: u64,
+    ) -> Vec<u32> {
+        let mut candidates: Vec<&Heartbeat<C>> = self
+            .live_heartbeats(now, max_age)
+            .filter(|heartbeat| {
+                heartbeat.epoch == epoch && heartbeat.remaining_commitments >= minimum_commitments
+            })
+            .collect();
+        candidates.sort_by_key(|heartbeat| core::cmp::Reverse(heartbeat.remaining_commitments));
+
+        candidates
+            .into_iter()
+            .map(|heartbeat| heartbeat.index)
+            .collect()
+    }
+
+    /// The indices of the signers whose latest heartbeat announced the given
+    /// `software_version`, sorted.
+    pub fn signers_with_version(&self, software_version: &str) -> Vec<u32> {
+        self.signers
+            .values()
+            .filter_map(|signer| signer.latest.as_ref())
+            .filter(|(_, heartbeat)| heartbeat.software_version == software_version)
+            .map(|(_, heartbeat)| heartbeat.index)
+            .collect()
+    }
+
+    /// The latest heartbeats received no earlier than `max_age` before the time `now`,
+    /// by increasing index of their signer.
+    fn live_heartbeats(&self, now: u64, max_age: u64) -> impl Iterator<Item = &Heartbeat<C>> {
+        self.signers
+            .values()
+            .filter_map(|signer| signer.latest.as_ref())
+            .filter(move |(received_at, _)| now.saturating_sub(*received_at) <= max_age)
+            .map(|(_, heartbeat)| heartbeat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dkg::DealerRole;
+    use crate::parameters::ThresholdParameters;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn heartbeats_in_liveness_tracker() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(4, 2).unwrap();
+
+        let mut participants = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=4 {
+            let (participant, _, dh_sk) =
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, i, OsRng).unwrap();
+            participants.push(participant);
+            dh_secret_keys.push(dh_sk);
+        }
+        let mut tracker = LivenessTracker::new(&participants);
+        assert_eq!(tracker.stale_signers(100, 30), vec![1, 2, 3, 4]);
+
+        let heartbeat = |i: usize, timestamp: u64, epoch: u64, remaining: u64| {
+            Heartbeat::new(
+                i as u32 + 1,
+                timestamp,
+                epoch,
+                remaining,
+                "1.2.0",
+                &dh_secret_keys[i],
+                OsRng,
+            )
+            .unwrap()
+        };
+
+        let first = heartbeat(0, 10, 1, 5);
+        assert_eq!(
+            Heartbeat::from_bytes(&first.to_bytes().unwrap()).unwrap(),
+            first
+        );
+        assert!(first.verify(&participants[0].dh_public_key).is_ok());
+        assert!(first.verify(&participants[1].dh_public_key).is_err());
+
+        tracker.record(first.clone(), 90).unwrap();
+        tracker.record(heartbeat(1, 10, 1, 20), 95).unwrap();
+        tracker.record(heartbeat(2, 10, 0, 50), 95).unwrap();
+        tracker.record(heartbeat(3, 10, 1, 1), 40).unwrap();
+
+        // Replayed heartbeats are rejected.
+        assert_eq!(tracker.record(first, 96), Err(Error::InvalidHeartbeat));
+
+        // Heartbeats not signed by the announced signer are rejected.
+        let mut forged = heartbeat(1, 11, 1, 100);
+        forged.index = 1;
+        assert_eq!(tracker.record(forged, 96), Err(Error::InvalidHeartbeat));
+        assert_eq!(tracker.latest(1).unwrap().remaining_commitments(), 5);
+
+        assert_eq!(tracker.live_signers(100, 30), vec![1, 2, 3]);
+        assert_eq!(tracker.stale_signers(100, 30), vec![4]);
+        assert_eq!(tracker.standby_candidates(100, 30, 1, 2), vec![2, 1]);
+        assert_eq!(tracker.signers_with_version("1.2.0"), vec![1, 2, 3, 4]);
+
+        tracker.record(heartbeat(3, 20, 1, 2), 100).unwrap();
+        assert_eq!(tracker.standby_candidates(100, 30, 1, 2), vec![2, 1, 4]);
+    }
+}
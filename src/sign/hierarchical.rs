@@ -0,0 +1,426 @@
+//! The hierarchical module for aggregating the partial signatures of
+//! geographically distributed signers through a tree of coordinators.
+//!
+//! Each regional coordinator runs a [`SignatureAggregator`] over the full set of
+//! signers of the session, but only collects the partial signatures of its local
+//! signers. It verifies them individually, and sums them into a [`PartialAggregate`]
+//! listing these signers. As partial signatures are already weighted by the Lagrange
+//! coefficient of their signer in the session, a [`PartialAggregate`] is the share of
+//! its signers in the final response, and partial aggregates of disjoint subsets of
+//! signers can be merged again by intermediate coordinators.
+//!
+//! The root coordinator then combines the partial aggregates of the regions into the
+//! final [`ThresholdSignature`], and only verifies the partial aggregates themselves
+//! if the final signature is invalid, in order to find the faulty regions.
+
+use core::ops::Mul;
+
+use ark_ec::Group;
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::error::SerializationErrorKind;
+use crate::utils::{vec, Scalar, ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::randomized::compute_randomized_binding_factors;
+use super::signature::{
+    compute_group_commitment, compute_session_challenge, Initial, SignatureAggregator,
+    ThresholdSignature,
+};
+
+/// The sum of the [`PartialThresholdSignature`]s of a subset of the signers of a
+/// session, as verified by a regional coordinator.
+///
+/// [`PartialThresholdSignature`]: super::PartialThresholdSignature
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PartialAggregate<C: CipherSuite> {
+    /// The hashed context and message to be signed.
+    pub(crate) message_hash: Vec<u8>,
+    /// The sorted indices of the signers whose partial signatures are aggregated.
+    pub(crate) signer_indices: Vec<u32>,
+    /// The sum of the Lagrange-weighted partial signatures of the signers.
+    pub(crate) z: Scalar<C>,
+}
+
+impl<C: CipherSuite> PartialAggregate<C> {
+    /// The hashed context and message signed by the aggregated partial signatures.
+    pub fn message_hash(&self) -> &[u8] {
+        &self.message_hash
+    }
+
+    /// The sorted indices of the signers whose partial signatures are aggregated.
+    pub fn signer_indices(&self) -> &[u32] {
+        &self.signer_indices
+    }
+
+    /// Merge the partial aggregates of disjoint subsets of signers of the same
+    /// session into a single [`PartialAggregate`], as done by the intermediate
+    /// coordinators of a tree.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the merged [`PartialAggregate`],
+    /// otherwise an [`Error::Custom`] if the `aggregates` are empty, were made for
+    /// different messages, or share a signer.
+    pub fn merge(aggregates: &[Self]) -> FrostResult<C, Self> {
+        let first = aggregates.first().ok_or(Error::Custom(
+            "There is no partial aggregate to merge".to_string(),
+        ))?;
+        if aggregates
+            .iter()
+            .any(|aggregate| aggregate.message_hash != first.message_hash)
+        {
+            return Err(Error::Custom(
+                "The partial aggregates were made for different messages".to_string(),
+            ));
+        }
+
+        let mut signer_indices: Vec<u32> = aggregates
+            .iter()
+            .flat_map(|aggregate| aggregate.signer_indices.iter().copied())
+            .collect();
+        let count = signer_indices.len();
+        signer_indices.sort_unstable();
+        signer_indices.dedup();
+        if signer_indices.len() != count {
+            return Err(Error::Custom(
+                "The partial aggregates share a signer".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            message_hash: first.message_hash.clone(),
+            signer_indices,
+            z: aggregates.iter().map(|aggregate| aggregate.z).sum(),
+        })
+    }
+
+    /// Serialize this [`PartialAggregate`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PartialAggregate`] from a vector of bytes.
+    ///
+    /// Aggregates whose signer indices are not sorted and deduplicated are rejected.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let aggregate = Self::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        if aggregate.signer_indices.is_empty()
+            || aggregate.signer_indices[0] == 0
+            || aggregate
+                .signer_indices
+                .windows(2)
+                .any(|pair| pair[0] >= pair[1])
+        {
+            return Err(Error::DeserializationError(
+                SerializationErrorKind::InvalidData,
+            ));
+        }
+
+        Ok(aggregate)
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Verify the partial signatures collected so far, and sum them into a
+    /// [`PartialAggregate`] to be forwarded to the next coordinator of the tree.
+    ///
+    /// All signers of the session must have been included, as the binding factors
+    /// and the Lagrange coefficients of the local signers depend on the full set of
+    /// signers, but only the partial signatures of the local signers are expected.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PartialAggregate`] of the local
+    /// signers, otherwise an [`Error::MisbehavingParticipants`] listing the signers
+    /// whose partial signature is invalid, or an [`Error::Custom`] if no partial
+    /// signature was collected.
+    pub fn partial_aggregate(&mut self) -> FrostResult<C, PartialAggregate<C>> {
+        if self.state.partial_signatures.is_empty() {
+            return Err(Error::Custom(
+                "There is no partial signature to aggregate".to_string(),
+            ));
+        }
+
+        let message_hash = self.message_hash()?;
+        let all_participant_indices: Vec<u32> = self
+            .get_signers()
+            .iter()
+            .map(|x| x.participant_index)
+            .collect();
+
+        let binding_factor_list = compute_randomized_binding_factors(
+            message_hash.as_ref(),
+            &self.state.signers,
+            &self.state.randomizers,
+        )?;
+        let group_commitment = compute_group_commitment(&self.state.signers, &binding_factor_list)?;
+        let challenge = compute_session_challenge::<C>(
+            &group_commitment,
+            &self.state.group_key,
+            message_hash.as_ref(),
+            self.aggregator.dry_run,
+        )?;
+        let interpolated_shares = self.interpolated_verification_shares(&all_participant_indices);
+
+        let mut misbehaving_participants = Vec::new();
+        for (index, z) in self.state.partial_signatures.iter() {
+            let is_valid = match all_participant_indices.binary_search(index) {
+                Ok(position) => self.state.verify_partial_signature(
+                    *index,
+                    z,
+                    message_hash.as_ref(),
+                    challenge,
+                    interpolated_shares[position].as_ref(),
+                ),
+                Err(_) => false,
+            };
+            if !is_valid {
+                misbehaving_participants.push(*index);
+            }
+        }
+
+        if !misbehaving_participants.is_empty() {
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        Ok(PartialAggregate {
+            message_hash: message_hash.as_ref().to_vec(),
+            signer_indices: self.state.partial_signatures.keys().copied().collect(),
+            z: self.state.partial_signatures.values().sum(),
+        })
+    }
+
+    /// Combine the partial aggregates of the regional coordinators into the final
+    /// [`ThresholdSignature`] of the session.
+    ///
+    /// The partial aggregates must cover all signers of the session, each exactly
+    /// once. They are only verified individually if the final signature is invalid.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`ThresholdSignature`], otherwise an
+    /// [`Error::MisbehavingParticipants`] listing the signers which are not covered, or
+    /// whose partial aggregate is invalid, or an [`Error::Custom`] if the `aggregates`
+    /// cannot be merged or were made for another message.
+    ///
+    /// As with [`SignatureAggregator::finalize`], a missing signer is reported along with
+    /// the aggregator itself, as "participant 0".
+    pub fn aggregate_partial_aggregates(
+        &mut self,
+        aggregates: &[PartialAggregate<C>],
+    ) -> FrostResult<C, ThresholdSignature<C>> {
+        let message_hash = self.message_hash()?;
+        let merged = PartialAggregate::merge(aggregates)?;
+        if merged.message_hash != message_hash.as_ref() {
+            return Err(Error::Custom(
+                "The partial aggregates were made for another message".to_string(),
+            ));
+        }
+
+        let all_participant_indices: Vec<u32> = self
+            .get_signers()
+            .iter()
+            .map(|x| x.participant_index)
+            .collect();
+        if merged.signer_indices != all_participant_indices {
+            let mut misbehaving_participants = vec![0];
+            misbehaving_participants.extend(
+                all_participant_indices
+                    .iter()
+                    .chain(merged.signer_indices.iter())
+                    .filter(|index| {
+                        all_participant_indices.binary_search(index).is_err()
+                            || merged.signer_indices.binary_search(index).is_err()
+                    }),
+            );
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        let binding_factor_list = compute_randomized_binding_factors(
+            message_hash.as_ref(),
+            &self.state.signers,
+            &self.state.randomizers,
+        )?;
+        let group_commitment = compute_group_commitment(&self.state.signers, &binding_factor_list)?;
+        let signature = ThresholdSignature {
+            group_commitment,
+            z: merged.z,
+        };
+        let challenge = compute_session_challenge::<C>(
+            &group_commitment,
+            &self.state.group_key,
+            message_hash.as_ref(),
+            self.aggregator.dry_run,
+        )?;
+
+        if signature
+            .verify_with_challenge(&self.state.group_key, challenge)
+            .is_ok()
+        {
+            return Ok(signature);
+        }
+
+        // Find the invalid partial aggregates, by checking each of them against the
+        // commitments and interpolated verification shares of its signers.
+        let interpolated_shares = self.interpolated_verification_shares(&all_participant_indices);
+
+        let mut misbehaving_participants = Vec::new();
+        for aggregate in aggregates.iter() {
+            let mut commitment = C::G::zero();
+            let mut verification_share = Some(C::G::zero());
+            for index in aggregate.signer_indices.iter() {
+                // An aggregate claiming a signer outside of the session is invalid.
+                let (position, binding_factor) = match (
+                    all_participant_indices.binary_search(index),
+                    binding_factor_list.get(index),
+                ) {
+                    (Ok(position), Some(binding_factor)) => (position, *binding_factor),
+                    _ => {
+                        verification_share = None;
+                        break;
+                    }
+                };
+                let (hiding, binding) = self.state.signers[position].published_commitment_share;
+
+                commitment += hiding + binding.mul(binding_factor);
+                verification_share = verification_share
+                    .zip(interpolated_shares[position])
+                    .map(|(sum, share)| sum + share);
+            }
+
+            let is_valid = match verification_share {
+                Some(verification_share) => {
+                    C::G::generator() * aggregate.z
+                        == commitment + verification_share.mul(challenge)
+                }
+                None => false,
+            };
+            if !is_valid {
+                misbehaving_participants.extend(aggregate.signer_indices.iter().copied());
+            }
+        }
+        misbehaving_participants.sort_unstable();
+
+        Err(Error::MisbehavingParticipants(misbehaving_participants))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn hierarchical_aggregation_5_out_of_6() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(6, 5, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut published_comshares = Vec::new();
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(5) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            published_comshares.push(public_comshares.commitments[0]);
+            secret_comshares.push(secret_comshare);
+        }
+
+        // Every coordinator of the tree knows all the signers of the session.
+        let new_aggregator = || {
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+            for (sk, comshare) in signing_keys.iter().zip(published_comshares.iter()) {
+                aggregator
+                    .include_signer(sk.index, *comshare, sk.into())
+                    .unwrap();
+            }
+            aggregator
+        };
+        let signers = new_aggregator().get_signers().clone();
+
+        let mut partials = Vec::new();
+        for (sk, secret_comshare) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            partials.push(
+                sk.sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                    .unwrap(),
+            );
+        }
+
+        let regions: [&[usize]; 3] = [&[0, 1], &[2], &[3, 4]];
+        let mut aggregates = Vec::new();
+        for region in regions.iter() {
+            let mut aggregator = new_aggregator();
+            for position in region.iter() {
+                aggregator
+                    .include_partial_signature(partials[*position].clone())
+                    .unwrap();
+            }
+            aggregates.push(aggregator.partial_aggregate().unwrap());
+        }
+        assert_eq!(aggregates[2].signer_indices(), &[4, 5]);
+
+        let bytes = aggregates[0].to_bytes().unwrap();
+        assert_eq!(PartialAggregate::from_bytes(&bytes).unwrap(), aggregates[0]);
+
+        // Intermediate coordinators may merge the aggregates of disjoint regions.
+        let merged = PartialAggregate::merge(&aggregates[1..]).unwrap();
+        assert_eq!(merged.signer_indices(), &[3, 4, 5]);
+        assert!(PartialAggregate::merge(&[aggregates[0].clone(), aggregates[0].clone()]).is_err());
+
+        let signature = new_aggregator()
+            .aggregate_partial_aggregates(&[aggregates[0].clone(), merged.clone()])
+            .unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+        assert_eq!(
+            new_aggregator()
+                .aggregate_partial_aggregates(&aggregates)
+                .unwrap(),
+            signature
+        );
+
+        // The root reports the signers which are not covered by the aggregates.
+        assert_eq!(
+            new_aggregator().aggregate_partial_aggregates(&aggregates[..2]),
+            Err(Error::MisbehavingParticipants(vec![0, 4, 5]))
+        );
+
+        // A region whose aggregate is invalid is reported as a whole.
+        let mut tampered = aggregates.clone();
+        tampered[2].z += Fr::from(1u64);
+        assert_eq!(
+            new_aggregator().aggregate_partial_aggregates(&tampered),
+            Err(Error::MisbehavingParticipants(vec![4, 5]))
+        );
+
+        // Regional coordinators check the partial signatures of their signers.
+        let mut aggregator = new_aggregator();
+        let mut invalid_partial = partials[1].clone();
+        invalid_partial.z += Fr::from(1u64);
+        aggregator
+            .include_partial_signature(partials[0].clone())
+            .unwrap();
+        aggregator
+            .include_partial_signature(invalid_partial)
+            .unwrap();
+        assert_eq!(
+            aggregator.partial_aggregate(),
+            Err(Error::MisbehavingParticipants(vec![2]))
+        );
+    }
+}
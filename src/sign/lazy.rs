@@ -0,0 +1,386 @@
+//! The lazy module for deriving the commitment shares of a signer on demand.
+//!
+//! Pre-computing a [`PublicCommitmentShareList`] of \\( n \\) commitment shares with
+//! [`generate_commitment_share_lists`](super::generate_commitment_share_lists) performs
+//! \\( 2n \\) scalar multiplications upfront, which delays the startup of signers
+//! precomputing tens of thousands of shares. A [`LazyCommitmentList`] instead holds
+//! a single seed, from which the nonce pair at position \\( i \\) is derived when needed,
+//! and publishes the commitments of its shares in batches.
+//!
+//! Unlike a [`CommitmentRatchet`](super::CommitmentRatchet), published commitment shares
+//! can be consumed in any order, at the cost of not being forward-secure: the seed
+//! allows recomputing the nonces of all positions, and must be kept secret until
+//! the list is exhausted. For the same reason, a seed must never be used for more
+//! than one [`LazyCommitmentList`], whose commitment shares would otherwise share
+//! their nonces, leaking the signing key of their owner.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{vec, Scalar, Vec};
+use crate::{Error, FrostResult};
+
+use super::precomputation::{
+    CommitmentShare, NoncePair, PublicCommitmentShareList, SecretCommitmentShareList,
+};
+use super::signature::{PartialThresholdSignature, Signer};
+
+/// The maximum number of commitment shares which can be published at once
+/// from a [`LazyCommitmentList`].
+pub const MAX_LAZY_BATCH_SIZE: u64 = 1 << 16;
+
+/// A seeded list of commitment shares of a signer, whose nonces are only derived
+/// when their commitments are published or when they are consumed.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct LazyCommitmentList<C: CipherSuite> {
+    /// The seed from which all the nonces of this list are derived.
+    pub(crate) seed: Vec<u8>,
+    /// The total number of commitment shares of this list.
+    pub(crate) number_of_shares: u64,
+    /// The number of commitment shares whose commitments were published.
+    pub(crate) published: u64,
+    /// A bitmap of the published commitment shares which were consumed.
+    pub(crate) consumed: Vec<u8>,
+    #[zeroize(skip)]
+    _phantom: core::marker::PhantomData<C>,
+}
+
+impl<C: CipherSuite> Drop for LazyCommitmentList<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> ConstantTimeEq for LazyCommitmentList<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.seed.as_slice().ct_eq(other.seed.as_slice())
+            & self.number_of_shares.ct_eq(&other.number_of_shares)
+            & self.published.ct_eq(&other.published)
+            & self.consumed.as_slice().ct_eq(other.consumed.as_slice())
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for LazyCommitmentList<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for LazyCommitmentList<C> {}
+
+impl<C: CipherSuite> LazyCommitmentList<C> {
+    /// Construct a new [`LazyCommitmentList`] of `number_of_shares` commitment
+    /// shares, derived from the given secret `seed`.
+    ///
+    /// This must never be called twice with the same `seed`, as both lists would
+    /// derive the same nonces, from which the signing key can be recovered once two
+    /// distinct messages are signed with them. Prefer [`LazyCommitmentList::random`]
+    /// unless the seed is derived deterministically from a unique source.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`LazyCommitmentList`], otherwise an
+    /// [`Error::SeedTooShort`] if the `seed` is shorter than this [`CipherSuite`]'s
+    /// hash output.
+    pub fn new(seed: &[u8], number_of_shares: u64) -> FrostResult<C, Self> {
+        let minimum_length = C::HashOutput::default().as_ref().len();
+        if seed.len() < minimum_length {
+            return Err(Error::SeedTooShort(seed.len(), minimum_length));
+        }
+
+        Ok(Self {
+            seed: seed.to_vec(),
+            number_of_shares,
+            published: 0,
+            consumed: Vec::new(),
+            _phantom: core::marker::PhantomData,
+        })
+    }
+
+    /// Construct a new [`LazyCommitmentList`] of `number_of_shares` commitment
+    /// shares, from a freshly sampled seed.
+    pub fn random(number_of_shares: u64, mut csprng: impl RngCore + CryptoRng) -> Self {
+        let mut seed = vec![0u8; C::HashOutput::default().as_ref().len()];
+        csprng.fill_bytes(&mut seed);
+
+        Self {
+            seed,
+            number_of_shares,
+            published: 0,
+            consumed: Vec::new(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// The total number of commitment shares of this list.
+    pub fn number_of_shares(&self) -> u64 {
+        self.number_of_shares
+    }
+
+    /// The number of commitment shares whose commitments were published, which
+    /// is also the position of the first commitment share of the next batch.
+    pub fn published(&self) -> u64 {
+        self.published
+    }
+
+    /// Compute the public commitments of the next `batch_size` commitment shares
+    /// of this list, or of all its remaining ones if there are fewer of them.
+    ///
+    /// The commitment at index \\( j \\) of the returned list is the one of the
+    /// commitment share at position [`LazyCommitmentList::published`] \\( + j \\),
+    /// as seen before this call.
+    ///
+    /// At most [`MAX_LAZY_BATCH_SIZE`] commitment shares can be published at once.
+    pub fn publish_next(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        batch_size: u64,
+    ) -> FrostResult<C, PublicCommitmentShareList<C>> {
+        if batch_size > MAX_LAZY_BATCH_SIZE {
+            return Err(Error::TooManyCommitmentShares(
+                batch_size,
+                MAX_LAZY_BATCH_SIZE,
+            ));
+        }
+
+        let end = self
+            .published
+            .saturating_add(batch_size)
+            .min(self.number_of_shares);
+
+        let mut commitments = Vec::new();
+        for position in self.published..end {
+            commitments.push(
+                self.derive_commitment_share(signing_key, position)?
+                    .publish(),
+            );
+        }
+
+        self.published = end;
+        self.consumed.resize(bitmap_length(end) as usize, 0);
+
+        Ok(PublicCommitmentShareList {
+            participant_index: signing_key.index,
+            commitments,
+        })
+    }
+
+    /// Consume the published commitment share at the given `position`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`CommitmentShare`], otherwise
+    /// an [`Error::MissingCommitmentShares`] if the position was not published yet
+    /// or was already consumed.
+    pub fn consume(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        position: u64,
+    ) -> FrostResult<C, CommitmentShare<C>> {
+        if position >= self.published {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let (byte, mask) = ((position / 8) as usize, 1u8 << (position % 8));
+        if self.consumed[byte] & mask != 0 {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let commitment_share = self.derive_commitment_share(signing_key, position)?;
+        self.consumed[byte] |= mask;
+
+        Ok(commitment_share)
+    }
+
+    /// Create a [`PartialThresholdSignature`] as with [`IndividualSigningKey::sign`],
+    /// with the commitment share at the given `position`, which is consumed.
+    pub fn sign(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        position: u64,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        let commitment_share = self.consume(signing_key, position)?;
+
+        let is_published = signers.iter().any(|signer| {
+            signer.participant_index == signing_key.index
+                && signer.published_commitment_share == commitment_share.publish()
+        });
+        if !is_published {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let mut secret_commitment_share_list = SecretCommitmentShareList {
+            commitments: vec![commitment_share],
+        };
+        signing_key.sign(
+            message_hash,
+            group_key,
+            &mut secret_commitment_share_list,
+            0,
+            signers,
+        )
+    }
+
+    /// Serialize this [`LazyCommitmentList`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`LazyCommitmentList`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let list = Self::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        if list.published > list.number_of_shares
+            || list.consumed.len() as u64 != bitmap_length(list.published)
+        {
+            return Err(Error::DeserializationError(
+                ark_serialize::SerializationError::InvalidData.into(),
+            ));
+        }
+
+        Ok(list)
+    }
+
+    /// Derive the commitment share of a `signing_key` at the given `position`.
+    fn derive_commitment_share(
+        &self,
+        signing_key: &IndividualSigningKey<C>,
+        position: u64,
+    ) -> FrostResult<C, CommitmentShare<C>> {
+        let derive_nonce = |domain: &str| -> FrostResult<C, Scalar<C>> {
+            let mut nonce_input = domain.as_bytes().to_vec();
+            nonce_input.extend(&self.seed);
+            nonce_input.extend(&position.to_le_bytes());
+            nonce_input.extend(&signing_key.to_bytes()?);
+            let nonce = C::h3(&nonce_input);
+            nonce_input.zeroize();
+
+            nonce
+        };
+
+        Ok(CommitmentShare::from(NoncePair(
+            derive_nonce("lazy hiding")?,
+            derive_nonce("lazy binding")?,
+        )))
+    }
+}
+
+/// The length in bytes of a bitmap of `number_of_bits` bits.
+pub(super) fn bitmap_length(number_of_bits: u64) -> u64 {
+    number_of_bits / 8 + u64::from(number_of_bits & 7 > 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::SignatureAggregator;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn lazy_commitment_list_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut lists: Vec<_> = (0..2)
+            .map(|_| LazyCommitmentList::<Secp256k1Sha256>::random(5, OsRng))
+            .collect();
+
+        // Commitments are published in batches, the last one being truncated.
+        let mut published = [Vec::new(), Vec::new()];
+        for batch_size in [2, 4] {
+            for ((sk, list), commitments) in signing_keys
+                .iter()
+                .zip(lists.iter_mut())
+                .zip(published.iter_mut())
+            {
+                let batch = list.publish_next(sk, batch_size).unwrap();
+                assert_eq!(batch.participant_index, sk.index);
+                commitments.extend(batch.commitments.iter().copied());
+            }
+        }
+        assert_eq!(published[0].len(), 5);
+        assert_eq!(lists[0].published(), lists[0].number_of_shares());
+        assert!(lists[0]
+            .publish_next(&signing_keys[0], 1)
+            .unwrap()
+            .commitments
+            .is_empty());
+        assert_eq!(
+            lists[0]
+                .publish_next(&signing_keys[0], MAX_LAZY_BATCH_SIZE + 1)
+                .unwrap_err(),
+            Error::TooManyCommitmentShares(MAX_LAZY_BATCH_SIZE + 1, MAX_LAZY_BATCH_SIZE)
+        );
+
+        // Restoring a persisted list keeps deriving the same commitment shares.
+        let mut lists: Vec<_> = lists
+            .iter()
+            .map(|list| LazyCommitmentList::from_bytes(&list.to_bytes().unwrap()).unwrap())
+            .collect();
+
+        for position in [4, 1] {
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+            for (sk, commitments) in signing_keys.iter().zip(published.iter()) {
+                aggregator
+                    .include_signer(sk.index, commitments[position], sk.into())
+                    .unwrap();
+            }
+            let signers = aggregator.get_signers().clone();
+
+            for (sk, list) in signing_keys.iter().zip(lists.iter_mut()) {
+                let partial = list
+                    .sign(sk, &message_hash, &group_key, position as u64, &signers)
+                    .unwrap();
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            assert!(threshold_signature
+                .verify(&group_key, &message_hash)
+                .is_ok());
+
+            // Consumed commitment shares cannot be used again.
+            assert_eq!(
+                lists[0]
+                    .sign(
+                        &signing_keys[0],
+                        &message_hash,
+                        &group_key,
+                        position as u64,
+                        &signers
+                    )
+                    .unwrap_err(),
+                Error::MissingCommitmentShares
+            );
+        }
+
+        // Seeds shorter than the hash output are rejected.
+        assert!(LazyCommitmentList::<Secp256k1Sha256>::new(&[0u8; 16], 5).is_err());
+        let mut list = LazyCommitmentList::<Secp256k1Sha256>::new(&[0u8; 32], 5).unwrap();
+        assert_eq!(
+            list.consume(&signing_keys[0], 0).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+    }
+}
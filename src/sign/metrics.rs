@@ -0,0 +1,200 @@
+//! The metrics module for monitoring the latency and load of ICE-FROST
+//! signature aggregation.
+//!
+//! A [`SignatureAggregator`] can report [`SigningMetrics`] for each aggregation,
+//! either as returned by [`SignatureAggregator::aggregate_with_metrics`], or through
+//! a callback registered with [`SignatureAggregator::with_metrics_callback`], which
+//! is then invoked on every call to [`SignatureAggregator::aggregate`].
+
+use ark_ff::Zero;
+use ark_serialize::CanonicalSerialize;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{Scalar, Vec};
+use crate::FrostResult;
+
+use super::signature::{Aggregator, Finalized, SignatureAggregator, ThresholdSignature};
+
+/// The statistics of an aggregation of partial signatures.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SigningMetrics {
+    /// The sorted indices of the signers whose partial signatures were aggregated.
+    pub signers: Vec<u32>,
+    /// The time spent verifying each partial signature individually, along with
+    /// the index of its signer.
+    ///
+    /// Partial signatures are only verified individually when the aggregated
+    /// signature is invalid, to identify the misbehaving signers, and this is
+    /// empty otherwise.
+    pub partial_verification_times: Vec<(u32, Duration)>,
+    /// The total time spent aggregating the partial signatures.
+    pub total_time: Duration,
+    /// The number of bytes of message hash, commitment shares and partial signatures
+    /// processed by the aggregation.
+    pub bytes_processed: usize,
+}
+
+/// A callback receiving the [`SigningMetrics`] of each aggregation.
+#[derive(Clone)]
+pub(crate) struct MetricsCallback(pub(crate) Arc<dyn Fn(&SigningMetrics) + Send + Sync>);
+
+impl core::fmt::Debug for MetricsCallback {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("MetricsCallback")
+    }
+}
+
+impl<C: CipherSuite, A: Aggregator> SignatureAggregator<C, A> {
+    /// Invoke the given `callback` with the [`SigningMetrics`] of each call to
+    /// [`SignatureAggregator::aggregate`].
+    pub fn with_metrics_callback(
+        mut self,
+        callback: impl Fn(&SigningMetrics) + Send + Sync + 'static,
+    ) -> Self {
+        self.metrics_callback = Some(MetricsCallback(Arc::new(callback)));
+
+        self
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
+    /// Aggregate a set of previously-collected partial signatures, as with
+    /// [`SignatureAggregator::aggregate`], and report the [`SigningMetrics`] of
+    /// the aggregation, whether it succeeded or not.
+    pub fn aggregate_with_metrics(
+        &self,
+    ) -> (FrostResult<C, ThresholdSignature<C>>, SigningMetrics) {
+        let start = Instant::now();
+        let mut partial_verification_times = Vec::new();
+
+        let result = self.aggregate_inner(&mut |index, verify| {
+            let verification_start = Instant::now();
+            let is_valid = verify();
+            partial_verification_times.push((index, verification_start.elapsed()));
+            is_valid
+        });
+
+        let metrics = SigningMetrics {
+            signers: self
+                .state
+                .signers
+                .iter()
+                .map(|signer| signer.participant_index)
+                .collect(),
+            partial_verification_times,
+            total_time: start.elapsed(),
+            bytes_processed: self.bytes_processed(),
+        };
+
+        (result, metrics)
+    }
+
+    /// Report the [`SigningMetrics`] of an aggregation to the registered callback, if any.
+    pub(super) fn report_metrics(&self, metrics: &SigningMetrics) {
+        if let Some(callback) = &self.metrics_callback {
+            (callback.0)(metrics);
+        }
+    }
+
+    fn bytes_processed(&self) -> usize {
+        let partial_signature_size = 4 + Scalar::<C>::zero().compressed_size();
+
+        self.aggregator.message_hash.as_ref().len()
+            + self.state.signers.compressed_size()
+            + self.state.partial_signatures.len() * partial_signature_size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, PartialThresholdSignature};
+    use crate::testing::Secp256k1Sha256;
+    use crate::Error;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn signing_metrics_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let reported = std::sync::Arc::new(std::sync::Mutex::new(Vec::<SigningMetrics>::new()));
+        let reported_clone = reported.clone();
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None)
+            .with_metrics_callback(move |metrics| {
+                reported_clone.lock().unwrap().push(metrics.clone())
+            });
+
+        let mut partials = Vec::new();
+        for sk in signing_keys.iter().skip(1) {
+            let (public_comshares, secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            partials.push((sk, secret_comshares));
+        }
+        let signers = aggregator.get_signers().clone();
+        for (sk, mut secret_comshares) in partials {
+            let partial = sk
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].signers, vec![2, 3]);
+        assert!(reported[0].partial_verification_times.is_empty());
+        assert!(reported[0].bytes_processed > 2 * 32);
+
+        // Partial signatures are timed when individually verified.
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, _) = generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            aggregator
+                .include_partial_signature(PartialThresholdSignature {
+                    index: sk.index,
+                    z: Fr::rand(&mut OsRng),
+                    epoch_tag: None,
+                })
+                .unwrap();
+        }
+        let (result, metrics) = aggregator.finalize().unwrap().aggregate_with_metrics();
+        assert_eq!(result, Err(Error::MisbehavingParticipants(vec![1, 2])));
+        assert_eq!(
+            metrics
+                .partial_verification_times
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(metrics.total_time >= metrics.partial_verification_times[0].1);
+    }
+}
@@ -1,8 +1,77 @@
+mod abort;
+mod attributed;
+mod blinded;
+mod cache;
+mod certificate;
+mod compact;
+mod delegated;
+mod dry_run;
+mod epoch_tag;
+mod external;
+mod fixed_size;
+mod heartbeat;
+mod hierarchical;
+mod lazy;
+#[cfg(feature = "std")]
+mod metrics;
+mod multisig;
+mod nonce_manager;
+mod optimistic;
+mod package;
+mod policy;
 mod precomputation;
+mod preflight;
+mod preprocessed;
+mod presign;
+mod pseudonym;
+mod randomized;
+mod ratchet;
+mod rerequest;
+mod selection;
+mod session_store;
 mod signature;
+mod transcript;
 
+pub use abort::AbortedSession;
+pub use attributed::AttributedSignature;
+pub use blinded::{BlindedPartialSignature, PartialSignatureBlinding};
+#[cfg(feature = "std")]
+pub use cache::SignerSubsetCache;
+pub use certificate::QuorumCertificate;
+pub use compact::PartialSigBundle;
+pub use delegated::{BlindingKey, DelegatedCommitments, DelegatedPrecompute};
+pub use dry_run::{dry_run_message_hash, generate_dry_run_commitment_share, DryRunCommitmentShare};
+pub use epoch_tag::EpochTag;
+pub use external::{sign_with, signing_commitment_hash, LocalShareSigner, ShareSigner};
+pub use fixed_size::SignatureAggregatorConst;
+pub use heartbeat::{Heartbeat, LivenessTracker};
+pub use hierarchical::PartialAggregate;
+pub use lazy::{LazyCommitmentList, MAX_LAZY_BATCH_SIZE};
+#[cfg(feature = "std")]
+pub use metrics::SigningMetrics;
+pub use multisig::{AdditiveSigningKey, AdditiveVerifyingKey, MultiSignatureAggregator};
+pub use nonce_manager::{NonceManager, NonceStatus, RefillPolicy};
+pub use package::SigningPackage;
+pub use policy::SignerPolicy;
+#[cfg(all(test, feature = "zeroize-audit"))]
+pub(crate) use precomputation::NoncePair;
 pub use precomputation::{
     generate_commitment_share_lists, CommitmentShare, PublicCommitmentShareList,
     SecretCommitmentShareList,
 };
+pub use preflight::{PartialRejection, PreflightReport};
+pub use preprocessed::PreprocessedSession;
+pub use presign::PreSignature;
+pub use pseudonym::{
+    PseudonymKey, PseudonymMap, PseudonymousCommitment, PseudonymousPartialSignature,
+    SessionPseudonym,
+};
+pub use randomized::CommitmentRandomizer;
+pub use ratchet::{CommitmentRatchet, MAX_RATCHET_WINDOW};
+pub use rerequest::{
+    signing_session_id, ReRequest, ReRequestReason, ReRequestRefusal, SignatureShareLog,
+};
+pub use selection::select_signers;
+pub use session_store::{AggregatorPool, SessionHandle, SessionStorage, SlabStorage};
 pub use signature::*;
+pub use transcript::SigningTranscript;
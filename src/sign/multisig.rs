@@ -0,0 +1,615 @@
+//! The multisig module for n-out-of-n ICE-FROST signing sessions, where
+//! all participants of the group co-sign every message.
+//!
+//! When the threshold equals the number of participants, the set of signers
+//! is always the full group, so the Lagrange coefficient of each participant
+//! is constant. Each participant can hence convert its [`IndividualSigningKey`]
+//! once into an [`AdditiveSigningKey`], such that the group key is the plain sum
+//! of the matching [`AdditiveVerifyingKey`]s. Signing and aggregation then skip
+//! all interpolation, while producing regular [`ThresholdSignature`]s.
+
+use ark_ec::Group;
+use ark_ff::{Field, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use core::ops::Mul;
+
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{calculate_lagrange_coefficients, ct_eq_encoded, vec, Scalar, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Element, Error, FrostResult};
+
+use super::precomputation::SecretCommitmentShareList;
+use super::signature::{
+    binding_factor_for_participant, check_canonical_ordering, compute_binding_factors,
+    compute_challenge, compute_group_commitment, PartialThresholdSignature, Signer,
+    ThresholdSignature,
+};
+
+/// Check that the group with the given `parameters` is an n-out-of-n group.
+fn check_full_group<C: CipherSuite>(parameters: &ThresholdParameters<C>) -> FrostResult<C, ()> {
+    if !parameters.is_full_group() {
+        return Err(Error::UnsupportedParameters(parameters.n, parameters.t));
+    }
+
+    Ok(())
+}
+
+/// Compute the constant Lagrange coefficient of the participant with index
+/// `participant_index` in an n-out-of-n group with the given `parameters`.
+fn full_group_coefficient<C: CipherSuite>(
+    participant_index: u32,
+    parameters: &ThresholdParameters<C>,
+) -> FrostResult<C, Scalar<C>> {
+    check_full_group(parameters)?;
+    if participant_index == 0 {
+        return Err(Error::IndexIsZero);
+    }
+    if participant_index > parameters.n {
+        return Err(Error::UnknownParticipant(participant_index));
+    }
+
+    let all_participant_indices: Vec<u32> = (1..=parameters.n).collect();
+
+    calculate_lagrange_coefficients::<C>(participant_index, &all_participant_indices)
+}
+
+/// An additive share of the group signing key of an n-out-of-n group,
+/// i.e. an [`IndividualSigningKey`] premultiplied by its Lagrange coefficient.
+///
+/// Its secret share is redacted from the [`Debug`](core::fmt::Debug) output of the key.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct AdditiveSigningKey<C: CipherSuite> {
+    /// The participant index to which this key belongs.
+    pub(crate) index: u32,
+    /// The participant's additive share of the group signing key.
+    pub(crate) key: Scalar<C>,
+}
+
+impl<C: CipherSuite> core::fmt::Debug for AdditiveSigningKey<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AdditiveSigningKey")
+            .field("index", &self.index)
+            .field("key", &format_args!("<redacted>"))
+            .finish()
+    }
+}
+
+impl<C: CipherSuite> Drop for AdditiveSigningKey<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> ConstantTimeEq for AdditiveSigningKey<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.index.ct_eq(&other.index) & ct_eq_encoded(&self.key, &other.key)
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for AdditiveSigningKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for AdditiveSigningKey<C> {}
+
+/// The public half of an [`AdditiveSigningKey`].
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AdditiveVerifyingKey<C: CipherSuite> {
+    /// The participant index to which this key belongs.
+    pub index: u32,
+    /// The public additive share.
+    pub share: C::G,
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Convert this [`IndividualSigningKey`] into an [`AdditiveSigningKey`], for
+    /// n-out-of-n groups with the given `parameters`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`AdditiveSigningKey`], otherwise an
+    /// [`Error`] if the threshold of the group differs from its number of participants.
+    pub fn to_additive(
+        &self,
+        parameters: &ThresholdParameters<C>,
+    ) -> FrostResult<C, AdditiveSigningKey<C>> {
+        let lambda = full_group_coefficient(self.index, parameters)?;
+
+        Ok(AdditiveSigningKey {
+            index: self.index,
+            key: lambda * self.key,
+        })
+    }
+}
+
+impl<C: CipherSuite> IndividualVerifyingKey<C> {
+    /// Convert this [`IndividualVerifyingKey`] into an [`AdditiveVerifyingKey`], for
+    /// n-out-of-n groups with the given `parameters`.
+    pub fn to_additive(
+        &self,
+        parameters: &ThresholdParameters<C>,
+    ) -> FrostResult<C, AdditiveVerifyingKey<C>> {
+        let lambda = full_group_coefficient(self.index, parameters)?;
+
+        Ok(AdditiveVerifyingKey {
+            index: self.index,
+            share: self.share.mul(lambda),
+        })
+    }
+}
+
+impl<C: CipherSuite> AdditiveSigningKey<C> {
+    /// Derive the corresponding public key for this secret key.
+    pub fn to_public(&self) -> AdditiveVerifyingKey<C> {
+        AdditiveVerifyingKey {
+            index: self.index,
+            share: C::G::generator() * self.key,
+        }
+    }
+
+    /// Compute an individual signer's [`PartialThresholdSignature`] contribution to
+    /// a [`ThresholdSignature`] on a message, signed by all participants of the group.
+    ///
+    /// This is equivalent to [`IndividualSigningKey::sign`], without the interpolation
+    /// of the signer's share, the `signers` having to include every participant of the
+    /// n-out-of-n group with the given `parameters`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the partial signature, otherwise an
+    /// [`Error::UnsortedSigners`] if the `signers` are not sorted by strictly increasing
+    /// index, or an [`Error::InvalidNumberOfParticipants`] if they do not include every
+    /// participant. The commitment share is only consumed once the `signers` are checked.
+    pub fn sign(
+        &self,
+        parameters: &ThresholdParameters<C>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        check_full_group(parameters)?;
+        check_canonical_ordering(signers)?;
+        if !signers
+            .iter()
+            .map(|signer| signer.participant_index)
+            .eq(1..=parameters.n)
+        {
+            return Err(Error::InvalidNumberOfParticipants(
+                signers.len(),
+                parameters.n,
+            ));
+        }
+
+        if my_commitment_share_index + 1 > my_secret_commitment_share_list.commitments.len() {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let binding_factor_list = compute_binding_factors(message_hash, signers)?;
+        let binding_factor = binding_factor_for_participant::<C>(self.index, &binding_factor_list)?;
+
+        let group_commitment = compute_group_commitment(signers, &binding_factor_list)?;
+
+        let my_commitment_share =
+            my_secret_commitment_share_list.commitments[my_commitment_share_index].clone();
+
+        let challenge = compute_challenge::<C>(&group_commitment, group_key, message_hash)?;
+
+        let z = my_commitment_share.hiding.secret
+            + (my_commitment_share.binding.secret * binding_factor)
+            + (self.key * challenge);
+
+        // Zero out our secrets from memory to prevent nonce reuse.
+        my_secret_commitment_share_list.drop_share(my_commitment_share);
+
+        Ok(PartialThresholdSignature {
+            index: self.index,
+            z,
+            epoch_tag: None,
+        })
+    }
+
+    /// Serialize this [`AdditiveSigningKey`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`AdditiveSigningKey`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+impl<C: CipherSuite> AdditiveVerifyingKey<C> {
+    /// Aggregate the [`AdditiveVerifyingKey`]s of all participants of a group
+    /// into its [`GroupVerifyingKey`].
+    pub fn aggregate(verifying_keys: &[Self]) -> GroupVerifyingKey<C> {
+        GroupVerifyingKey::new(Element(
+            verifying_keys
+                .iter()
+                .fold(C::G::zero(), |acc, key| acc + key.share),
+        ))
+    }
+
+    /// Serialize this [`AdditiveVerifyingKey`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize an [`AdditiveVerifyingKey`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for AdditiveVerifyingKey<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.share)
+    }
+}
+
+/// A signature aggregator for n-out-of-n groups, where every participant signs
+/// with its [`AdditiveSigningKey`].
+///
+/// This is equivalent to a [`super::SignatureAggregator`] whose signers are all
+/// the participants of the group, without any interpolation of their shares.
+#[derive(Debug)]
+pub struct MultiSignatureAggregator<C: CipherSuite> {
+    /// The group public key, equal to the sum of all the additive verifying keys.
+    group_key: GroupVerifyingKey<C>,
+    /// The hash of the message to be signed.
+    message_hash: C::HashOutput,
+    /// The additive verifying keys of the participants, sorted by index.
+    verifying_keys: Vec<AdditiveVerifyingKey<C>>,
+    /// The published commitment shares of the participants, in the same order.
+    commitments: Vec<Option<(C::G, C::G)>>,
+    /// The partial signatures received from the participants, in the same order.
+    partial_signatures: Vec<Option<Scalar<C>>>,
+}
+
+impl<C: CipherSuite> MultiSignatureAggregator<C> {
+    /// Construct a new signature aggregator for the n-out-of-n group with the given
+    /// `parameters` and `group_key`, from the `message` to be signed and the
+    /// [`AdditiveVerifyingKey`]s of all participants.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the aggregator, otherwise an
+    /// [`Error::InvalidGroupKey`] if the sum of the `verifying_keys` differs
+    /// from the `group_key`.
+    pub fn new(
+        parameters: ThresholdParameters<C>,
+        group_key: GroupVerifyingKey<C>,
+        message: &[u8],
+        mut verifying_keys: Vec<AdditiveVerifyingKey<C>>,
+    ) -> FrostResult<C, Self> {
+        check_full_group(&parameters)?;
+
+        verifying_keys.sort_by_key(|key| key.index);
+        verifying_keys.dedup_by_key(|key| key.index);
+        if verifying_keys.len() != parameters.n as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                verifying_keys.len(),
+                parameters.n,
+            ));
+        }
+
+        if AdditiveVerifyingKey::aggregate(&verifying_keys) != group_key {
+            return Err(Error::InvalidGroupKey);
+        }
+
+        let n = verifying_keys.len();
+        Ok(Self {
+            group_key,
+            message_hash: C::h4(message)?,
+            verifying_keys,
+            commitments: vec![None; n],
+            partial_signatures: vec![None; n],
+        })
+    }
+
+    /// The [`GroupVerifyingKey`] of the group.
+    pub fn group_key(&self) -> &GroupVerifyingKey<C> {
+        &self.group_key
+    }
+
+    /// Get the hash of the message to be signed by the participants.
+    pub fn message_hash(&self) -> &[u8] {
+        self.message_hash.as_ref()
+    }
+
+    /// Find the position of the participant with index `participant_index`.
+    fn position(&self, participant_index: u32) -> FrostResult<C, usize> {
+        self.verifying_keys
+            .binary_search_by_key(&participant_index, |key| key.index)
+            .map_err(|_| Error::MisbehavingParticipants(vec![participant_index]))
+    }
+
+    /// Include the published commitment share of a participant.
+    pub fn include_signer(
+        &mut self,
+        participant_index: u32,
+        published_commitment_share: (C::G, C::G),
+    ) -> FrostResult<C, ()> {
+        let position = self.position(participant_index)?;
+        self.commitments[position] = Some(published_commitment_share);
+
+        Ok(())
+    }
+
+    /// Get the sorted list of signers, once all participants have been included.
+    pub fn signers(&self) -> FrostResult<C, Vec<Signer<C>>> {
+        self.verifying_keys
+            .iter()
+            .zip(self.commitments.iter())
+            .map(|(key, commitment)| {
+                commitment
+                    .map(|published_commitment_share| Signer {
+                        participant_index: key.index,
+                        published_commitment_share,
+                    })
+                    .ok_or(Error::MissingCommitmentShares)
+            })
+            .collect()
+    }
+
+    /// Include a partial signature from a participant.
+    pub fn include_partial_signature(
+        &mut self,
+        partial_signature: PartialThresholdSignature<C>,
+    ) -> FrostResult<C, ()> {
+        let position = self.position(partial_signature.index)?;
+        self.partial_signatures[position] = Some(partial_signature.z);
+
+        Ok(())
+    }
+
+    /// Aggregate a set of previously-collected partial signatures.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose [`Ok`] value is a [`ThresholdSignature`], otherwise a
+    /// [`Error::MisbehavingParticipants`] listing the participants whose partial
+    /// signature is missing or invalid. As with [`super::SignatureAggregator`],
+    /// missing partial signatures are also blamed on the aggregator, "participant 0".
+    pub fn aggregate(&self) -> FrostResult<C, ThresholdSignature<C>> {
+        let signers = self.signers()?;
+
+        if self.partial_signatures.iter().any(Option::is_none) {
+            let mut misbehaving_participants = vec![0];
+            misbehaving_participants.extend(
+                self.verifying_keys
+                    .iter()
+                    .zip(self.partial_signatures.iter())
+                    .filter(|(_, z)| z.is_none())
+                    .map(|(key, _)| key.index),
+            );
+
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+
+        let message_hash = self.message_hash.as_ref();
+        let binding_factor_list = compute_binding_factors(message_hash, &signers)?;
+        let group_commitment = compute_group_commitment(&signers, &binding_factor_list)?;
+
+        let z = self
+            .partial_signatures
+            .iter()
+            .flatten()
+            .fold(Scalar::<C>::ZERO, |acc, z| acc + z);
+
+        let signature = ThresholdSignature {
+            z,
+            group_commitment,
+        };
+
+        // Verify the obtained signature, listing malicious participants
+        // if the verification failed.
+        match signature.verify(&self.group_key, message_hash) {
+            Ok(()) => Ok(signature),
+            Err(_) => {
+                let challenge =
+                    compute_challenge::<C>(&group_commitment, &self.group_key, message_hash)?;
+
+                let mut misbehaving_participants = Vec::new();
+                for ((signer, key), z_i) in signers
+                    .iter()
+                    .zip(self.verifying_keys.iter())
+                    .zip(self.partial_signatures.iter().flatten())
+                {
+                    let binding_factor = binding_factor_for_participant::<C>(
+                        signer.participant_index,
+                        &binding_factor_list,
+                    )?;
+                    let (hiding, binding) = signer.published_commitment_share;
+
+                    if C::G::generator() * z_i
+                        != hiding + binding.mul(binding_factor) + key.share.mul(challenge)
+                    {
+                        misbehaving_participants.push(signer.participant_index);
+                    }
+                }
+
+                Err(Error::MisbehavingParticipants(misbehaving_participants))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::{Fr, Projective};
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn multisig_3_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 3, None, None).unwrap();
+
+        let additive_keys: Vec<AdditiveSigningKey<Secp256k1Sha256>> = signing_keys
+            .iter()
+            .map(|sk| sk.to_additive(&params).unwrap())
+            .collect();
+        let verifying_keys: Vec<AdditiveVerifyingKey<Secp256k1Sha256>> = signing_keys
+            .iter()
+            .map(|sk| sk.to_public().to_additive(&params).unwrap())
+            .collect();
+        for (sk, vk) in additive_keys.iter().zip(verifying_keys.iter()) {
+            assert_eq!(&sk.to_public(), vk);
+            assert_eq!(
+                &AdditiveVerifyingKey::from_bytes(&vk.to_bytes().unwrap()).unwrap(),
+                vk
+            );
+            assert_eq!(
+                &AdditiveSigningKey::from_bytes(&sk.to_bytes().unwrap()).unwrap(),
+                sk
+            );
+
+            let debug = format!("{:?}", sk);
+            assert!(debug.contains("<redacted>"));
+            assert!(!debug.contains(&sk.key.to_string()));
+        }
+        assert_eq!(AdditiveVerifyingKey::aggregate(&verifying_keys), group_key);
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        // Verifying keys which do not add up to the group key are rejected.
+        let mut wrong_verifying_keys = verifying_keys.clone();
+        wrong_verifying_keys[0].share += Projective::generator();
+        assert_eq!(
+            MultiSignatureAggregator::new(params, group_key, &message[..], wrong_verifying_keys)
+                .unwrap_err(),
+            Error::InvalidGroupKey
+        );
+
+        let mut aggregator =
+            MultiSignatureAggregator::new(params, group_key, &message[..], verifying_keys).unwrap();
+        assert_eq!(aggregator.group_key(), &group_key);
+
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter() {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0])
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.signers().unwrap();
+
+        // Signers not covering the whole group are rejected, without consuming a nonce.
+        assert_eq!(
+            additive_keys[0].sign(
+                &params,
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &signers[..2]
+            ),
+            Err(Error::InvalidNumberOfParticipants(2, 3))
+        );
+        let duplicated_signers = [signers[0], signers[0], signers[1], signers[2]];
+        assert_eq!(
+            additive_keys[0].sign(
+                &params,
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &duplicated_signers
+            ),
+            Err(Error::UnsortedSigners)
+        );
+        let reversed_signers: Vec<_> = signers.iter().rev().copied().collect();
+        assert_eq!(
+            additive_keys[0].sign(
+                &params,
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &reversed_signers
+            ),
+            Err(Error::UnsortedSigners)
+        );
+        assert_eq!(secret_comshares[0].commitments.len(), 1);
+
+        let mut partial_signatures = Vec::new();
+        for (sk, secret_comshare) in additive_keys.iter().zip(secret_comshares.iter_mut()) {
+            partial_signatures.push(
+                sk.sign(
+                    &params,
+                    &message_hash,
+                    &group_key,
+                    secret_comshare,
+                    0,
+                    &signers,
+                )
+                .unwrap(),
+            );
+        }
+
+        // A missing partial signature is blamed on the aggregator and its signer.
+        aggregator
+            .include_partial_signature(partial_signatures[0].clone())
+            .unwrap();
+        assert_eq!(
+            aggregator.aggregate(),
+            Err(Error::MisbehavingParticipants(vec![0, 2, 3]))
+        );
+
+        // An invalid partial signature is blamed on its signer.
+        let mut invalid_partial_signature = partial_signatures[2].clone();
+        invalid_partial_signature.z += Fr::from(1u8);
+        aggregator
+            .include_partial_signature(partial_signatures[1].clone())
+            .unwrap();
+        aggregator
+            .include_partial_signature(invalid_partial_signature)
+            .unwrap();
+        assert_eq!(
+            aggregator.aggregate(),
+            Err(Error::MisbehavingParticipants(vec![3]))
+        );
+
+        aggregator
+            .include_partial_signature(partial_signatures[2].clone())
+            .unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // The fast path is only available to n-out-of-n groups.
+        let (params, signing_keys, _, _, _) = do_keygen(3, 2, None, None).unwrap();
+        assert!(signing_keys[0].to_additive(&params).is_err());
+    }
+}
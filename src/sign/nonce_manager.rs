@@ -0,0 +1,326 @@
+//! The nonce manager module, holding the precomputed commitment shares of a
+//! long-running signer and refilling them ahead of exhaustion.
+//!
+//! A [`NonceManager`] consumes one [`CommitmentShare`] per signing session. Once
+//! the number of remaining shares drops below the low watermark of its
+//! [`RefillPolicy`], a new batch must be generated and its
+//! [`PublicCommitmentShareList`] published, before the signer runs out of shares
+//! in the middle of a burst of sessions. This is either done by the manager itself,
+//! which hands the new batch over to a refill callback for publication, or left to
+//! the caller, which is signalled by a [`NonceStatus::NeedsRefill`].
+//!
+//! [`CommitmentShare`]: super::CommitmentShare
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{Box, ToString};
+use crate::{Error, FrostResult};
+
+use rand::{CryptoRng, Rng};
+
+use super::precomputation::{
+    generate_commitment_share_lists, PublicCommitmentShareList, SecretCommitmentShareList,
+};
+use super::signature::{PartialThresholdSignature, Signer};
+
+/// A callback publishing the [`PublicCommitmentShareList`] of a new batch.
+type RefillCallback<C> = Box<dyn Fn(&PublicCommitmentShareList<C>) + Send + Sync>;
+
+/// The policy for refilling the commitment shares of a [`NonceManager`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RefillPolicy {
+    /// The number of remaining commitment shares below which a refill is needed.
+    pub low_watermark: usize,
+    /// The number of commitment shares generated per batch.
+    pub batch_size: usize,
+}
+
+impl Default for RefillPolicy {
+    fn default() -> Self {
+        Self {
+            low_watermark: 16,
+            batch_size: 64,
+        }
+    }
+}
+
+/// Whether a [`NonceManager`] holds enough commitment shares.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NonceStatus {
+    /// The number of remaining commitment shares is at or above the low watermark.
+    Sufficient,
+    /// The number of remaining commitment shares is below the low watermark, and a
+    /// new batch should be generated with [`NonceManager::refill`] and published.
+    NeedsRefill,
+}
+
+/// A manager of the precomputed commitment shares of a participant.
+pub struct NonceManager<C: CipherSuite> {
+    signing_key: IndividualSigningKey<C>,
+    secret_shares: SecretCommitmentShareList<C>,
+    policy: RefillPolicy,
+    on_refill: Option<RefillCallback<C>>,
+}
+
+impl<C: CipherSuite> core::fmt::Debug for NonceManager<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NonceManager")
+            .field("index", &self.signing_key.index)
+            .field("remaining", &self.remaining())
+            .field("policy", &self.policy)
+            .field("on_refill", &self.on_refill.is_some())
+            .finish()
+    }
+}
+
+impl<C: CipherSuite> NonceManager<C> {
+    /// Construct a new [`NonceManager`] for the participant owning the given
+    /// `signing_key`, along with its first batch of commitment shares.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`NonceManager`] and the
+    /// [`PublicCommitmentShareList`] of its first batch, to be published, otherwise
+    /// an [`Error::Custom`] if the batch size of the `policy` does not exceed its
+    /// low watermark, as a refill would then not be enough to satisfy it.
+    pub fn new(
+        signing_key: IndividualSigningKey<C>,
+        policy: RefillPolicy,
+        csprng: impl CryptoRng + Rng,
+    ) -> FrostResult<C, (Self, PublicCommitmentShareList<C>)> {
+        if policy.batch_size <= policy.low_watermark {
+            return Err(Error::Custom(
+                "The refill batch size must exceed the low watermark".to_string(),
+            ));
+        }
+
+        let (public_shares, secret_shares) =
+            generate_commitment_share_lists(csprng, &signing_key, policy.batch_size)?;
+
+        Ok((
+            Self {
+                signing_key,
+                secret_shares,
+                policy,
+                on_refill: None,
+            },
+            public_shares,
+        ))
+    }
+
+    /// Refill automatically when the low watermark is crossed, handing the
+    /// [`PublicCommitmentShareList`] of each new batch over to `callback`
+    /// for publication.
+    pub fn with_refill_callback(
+        mut self,
+        callback: impl Fn(&PublicCommitmentShareList<C>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_refill = Some(Box::new(callback));
+        self
+    }
+
+    /// The refill policy of this [`NonceManager`].
+    pub fn policy(&self) -> RefillPolicy {
+        self.policy
+    }
+
+    /// The number of remaining commitment shares.
+    pub fn remaining(&self) -> usize {
+        self.secret_shares.commitments.len()
+    }
+
+    /// Whether this [`NonceManager`] needs to be refilled.
+    pub fn status(&self) -> NonceStatus {
+        match self.remaining() < self.policy.low_watermark {
+            true => NonceStatus::NeedsRefill,
+            false => NonceStatus::Sufficient,
+        }
+    }
+
+    /// Generate a new batch of commitment shares, and hand its
+    /// [`PublicCommitmentShareList`] over to the refill callback, if any.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PublicCommitmentShareList`] of the
+    /// new batch, to be published.
+    pub fn refill(
+        &mut self,
+        csprng: impl CryptoRng + Rng,
+    ) -> FrostResult<C, PublicCommitmentShareList<C>> {
+        let (public_shares, secret_shares) =
+            generate_commitment_share_lists(csprng, &self.signing_key, self.policy.batch_size)?;
+        self.secret_shares
+            .commitments
+            .extend(secret_shares.commitments.iter().cloned());
+
+        if let Some(callback) = &self.on_refill {
+            callback(&public_shares);
+        }
+
+        Ok(public_shares)
+    }
+
+    /// Sign the given `message_hash` with the commitment share published as
+    /// `commitment`, as with [`IndividualSigningKey::sign`].
+    ///
+    /// If this leaves fewer commitment shares than the low watermark, a new batch
+    /// is generated when a refill callback is set, otherwise the caller is expected
+    /// to call [`NonceManager::refill`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PartialThresholdSignature`] and the
+    /// [`NonceStatus`] after signing, otherwise an [`Error::MissingCommitmentShares`] if
+    /// no remaining commitment share was published as `commitment`.
+    pub fn sign(
+        &mut self,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        commitment: &(C::G, C::G),
+        signers: &[Signer<C>],
+        csprng: impl CryptoRng + Rng,
+    ) -> FrostResult<C, (PartialThresholdSignature<C>, NonceStatus)> {
+        let position = self
+            .secret_shares
+            .commitments
+            .iter()
+            .position(|share| share.publish() == *commitment)
+            .ok_or(Error::MissingCommitmentShares)?;
+
+        let partial_signature = self.signing_key.sign(
+            message_hash,
+            group_key,
+            &mut self.secret_shares,
+            position,
+            signers,
+        )?;
+
+        if self.status() == NonceStatus::NeedsRefill && self.on_refill.is_some() {
+            self.refill(csprng)?;
+        }
+
+        Ok((partial_signature, self.status()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keys::IndividualVerifyingKey;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::{Scalar, Vec};
+    use crate::Element;
+
+    use ark_ff::UniformRand;
+    use rand::rngs::OsRng;
+
+    #[cfg(feature = "std")]
+    use std::sync::{Arc, Mutex};
+
+    fn sign_once(
+        manager: &mut NonceManager<Secp256k1Sha256>,
+        commitment: &(
+            <Secp256k1Sha256 as CipherSuite>::G,
+            <Secp256k1Sha256 as CipherSuite>::G,
+        ),
+        group_key: &GroupVerifyingKey<Secp256k1Sha256>,
+    ) -> FrostResult<Secp256k1Sha256, NonceStatus> {
+        let signers = [Signer {
+            participant_index: 1,
+            published_commitment_share: *commitment,
+        }];
+        let (partial_signature, status) =
+            manager.sign(b"message", group_key, commitment, &signers, OsRng)?;
+        assert_eq!(partial_signature.index, 1);
+
+        Ok(status)
+    }
+
+    #[test]
+    fn refill_below_low_watermark() {
+        let signing_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Scalar::<Secp256k1Sha256>::rand(&mut OsRng),
+        };
+        let verifying_key: IndividualVerifyingKey<Secp256k1Sha256> = signing_key.to_public();
+        let group_key = GroupVerifyingKey::new(Element(verifying_key.share));
+        let policy = RefillPolicy {
+            low_watermark: 2,
+            batch_size: 3,
+        };
+
+        assert!(NonceManager::new(
+            signing_key.clone(),
+            RefillPolicy {
+                low_watermark: 3,
+                batch_size: 3,
+            },
+            OsRng
+        )
+        .is_err());
+
+        // Without a callback, the caller is told to refill.
+        let (mut manager, public_shares) =
+            NonceManager::new(signing_key.clone(), policy, OsRng).unwrap();
+        assert_eq!(manager.remaining(), 3);
+        assert_eq!(
+            sign_once(&mut manager, &public_shares.commitments[0], &group_key).unwrap(),
+            NonceStatus::Sufficient
+        );
+        assert_eq!(
+            sign_once(&mut manager, &public_shares.commitments[1], &group_key).unwrap(),
+            NonceStatus::NeedsRefill
+        );
+
+        // A consumed commitment share cannot be used again.
+        assert_eq!(
+            sign_once(&mut manager, &public_shares.commitments[1], &group_key),
+            Err(Error::MissingCommitmentShares)
+        );
+
+        let refilled = manager.refill(OsRng).unwrap();
+        assert_eq!(manager.remaining(), 4);
+        assert_eq!(manager.status(), NonceStatus::Sufficient);
+        let commitments: Vec<_> = public_shares.commitments[2..]
+            .iter()
+            .chain(refilled.commitments.iter())
+            .collect();
+        for commitment in commitments {
+            sign_once(&mut manager, commitment, &group_key).unwrap();
+        }
+        assert_eq!(manager.remaining(), 0);
+
+        // With a callback, new batches are generated and published automatically.
+        #[cfg(feature = "std")]
+        {
+            let published = Arc::new(Mutex::new(Vec::new()));
+            let sink = published.clone();
+            let (mut manager, public_shares) = NonceManager::new(signing_key, policy, OsRng)
+                .map(|(manager, shares)| {
+                    (
+                        manager.with_refill_callback(move |list| {
+                            sink.lock().unwrap().push(list.commitments.clone())
+                        }),
+                        shares,
+                    )
+                })
+                .unwrap();
+
+            let mut available = public_shares.commitments;
+            for _ in 0..10 {
+                let commitment = available.remove(0);
+                assert_eq!(
+                    sign_once(&mut manager, &commitment, &group_key).unwrap(),
+                    NonceStatus::Sufficient
+                );
+                for batch in published.lock().unwrap().drain(..) {
+                    available.extend(batch);
+                }
+                assert_eq!(available.len(), manager.remaining());
+                assert!(manager.remaining() >= policy.low_watermark);
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! The optimistic module, aggregating the partial signatures of very large sets of
+//! signers without verifying each of them.
+//!
+//! [`SignatureAggregator::aggregate`] already verifies the aggregated signature before
+//! the partial signatures, but falls back to verifying every partial signature as soon
+//! as one of them is invalid, i.e. to one scalar multiplication per signer on top of
+//! the interpolation of their verification shares. With a thousand partially trusted
+//! signers, a single faulty one hence makes the aggregation orders of magnitude slower.
+//!
+//! [`SignatureAggregator::aggregate_optimistic`] instead identifies the invalid partial
+//! signatures by batch-splitting: the verification equations of a subset of signers are
+//! combined with random weights into a single multi-scalar multiplication, and only the
+//! subsets failing this check are split in halves and checked again. Identifying \\( k \\)
+//! invalid partial signatures among \\( n \\) then takes \\( O(k \log n) \\) batch checks,
+//! while the random weights prevent several invalid partial signatures from cancelling
+//! each other out, except with negligible probability.
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, UniformRand, Zero};
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{calculate_lagrange_coefficients, points_to_affine_batch, Scalar, Vec};
+use crate::{Error, FrostResult};
+
+use super::randomized::compute_randomized_binding_factors;
+use super::signature::{
+    compute_group_commitment, compute_session_challenge, Finalized, SignatureAggregator,
+    ThresholdSignature,
+};
+
+/// The verification equation of the partial signature of a signer, i.e.
+/// \\( z_i \cdot G = D_i + \rho_i \cdot E_i + c \cdot \lambda_i \cdot Y_i \\).
+struct PartialEquation<C: CipherSuite> {
+    /// The index of the signer.
+    index: u32,
+    /// The partial signature \\( z_i \\) of the signer.
+    z: Scalar<C>,
+    /// The binding factor \\( \rho_i \\) of the signer.
+    binding_factor: Scalar<C>,
+    /// The challenge of the session times the Lagrange coefficient of the signer.
+    weighted_challenge: Scalar<C>,
+    /// The hiding and binding commitments \\( D_i, E_i \\) of the signer, followed
+    /// by its public key \\( Y_i \\).
+    bases: [<C::G as CurveGroup>::Affine; 3],
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
+    /// Aggregate a set of previously-collected partial signatures, identifying the
+    /// invalid ones by batch-splitting instead of verifying them all if the resulting
+    /// signature is invalid.
+    ///
+    /// This is intended for very large sets of signers, of which only a few are
+    /// expected to misbehave. The randomness of the batch checks is drawn from `csprng`.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok() value is a [`ThresholdSignature`], otherwise the same
+    /// error as [`SignatureAggregator::aggregate`].
+    pub fn aggregate_optimistic(
+        &self,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, ThresholdSignature<C>> {
+        let message_hash = self.aggregator.message_hash.as_ref();
+        let binding_factor_list = compute_randomized_binding_factors(
+            message_hash,
+            &self.state.signers,
+            &self.state.randomizers,
+        )?;
+        let group_commitment = compute_group_commitment(&self.state.signers, &binding_factor_list)?;
+        let challenge = compute_session_challenge::<C>(
+            &group_commitment,
+            &self.state.group_key,
+            message_hash,
+            self.aggregator.dry_run,
+        )?;
+
+        let mut z = Scalar::<C>::ZERO;
+        for signer in self.state.signers.iter() {
+            z += self
+                .state
+                .partial_signatures
+                .get(&signer.participant_index)
+                .ok_or(Error::MissingShares)?;
+        }
+
+        let signature = ThresholdSignature {
+            z,
+            group_commitment,
+        };
+        if signature
+            .verify_with_challenge(&self.state.group_key, challenge)
+            .is_ok()
+        {
+            return Ok(signature);
+        }
+
+        let all_participant_indices: Vec<u32> = self
+            .state
+            .signers
+            .iter()
+            .map(|x| x.participant_index)
+            .collect();
+
+        let mut misbehaving_participants = Vec::new();
+        let mut equations = Vec::with_capacity(self.state.signers.len());
+        let mut points = Vec::with_capacity(3 * self.state.signers.len());
+        for signer in self.state.signers.iter() {
+            let index = signer.participant_index;
+            let public_key = match self.state.public_keys.get(&index) {
+                Some(public_key) => public_key,
+                None => {
+                    misbehaving_participants.push(index);
+                    continue;
+                }
+            };
+            let lambda = calculate_lagrange_coefficients::<C>(index, &all_participant_indices)?;
+
+            equations.push((
+                index,
+                *self
+                    .state
+                    .partial_signatures
+                    .get(&index)
+                    .ok_or(Error::MissingShares)?,
+                *binding_factor_list
+                    .get(&index)
+                    .ok_or(Error::MissingShares)?,
+                challenge * lambda,
+            ));
+            points.extend_from_slice(&[
+                signer.published_commitment_share.0,
+                signer.published_commitment_share.1,
+                *public_key,
+            ]);
+        }
+
+        let affine_points = points_to_affine_batch::<C>(&points);
+        let equations: Vec<PartialEquation<C>> = equations
+            .into_iter()
+            .zip(affine_points.chunks_exact(3))
+            .map(
+                |((index, z, binding_factor, weighted_challenge), bases)| PartialEquation {
+                    index,
+                    z,
+                    binding_factor,
+                    weighted_challenge,
+                    bases: [bases[0], bases[1], bases[2]],
+                },
+            )
+            .collect();
+
+        identify_invalid_partials(&equations, &mut csprng, &mut misbehaving_participants)?;
+        misbehaving_participants.sort_unstable();
+
+        Err(Error::MisbehavingParticipants(misbehaving_participants))
+    }
+}
+
+/// Push the indices of the signers of the given `equations` whose partial signature
+/// is invalid to `misbehaving_participants`, by recursively splitting the subsets of
+/// equations failing a batch check.
+fn identify_invalid_partials<C: CipherSuite>(
+    equations: &[PartialEquation<C>],
+    csprng: &mut (impl RngCore + CryptoRng),
+    misbehaving_participants: &mut Vec<u32>,
+) -> FrostResult<C, ()> {
+    if equations.is_empty() || batch_check(equations, csprng)? {
+        return Ok(());
+    }
+
+    if equations.len() == 1 {
+        misbehaving_participants.push(equations[0].index);
+        return Ok(());
+    }
+
+    let (left, right) = equations.split_at(equations.len() / 2);
+    identify_invalid_partials(left, csprng, misbehaving_participants)?;
+    identify_invalid_partials(right, csprng, misbehaving_participants)
+}
+
+/// Check the random linear combination of the given `equations` with a single
+/// multi-scalar multiplication.
+fn batch_check<C: CipherSuite>(
+    equations: &[PartialEquation<C>],
+    csprng: &mut (impl RngCore + CryptoRng),
+) -> FrostResult<C, bool> {
+    let mut bases = Vec::with_capacity(3 * equations.len() + 1);
+    let mut scalars = Vec::with_capacity(3 * equations.len() + 1);
+    let mut z = Scalar::<C>::ZERO;
+
+    for equation in equations.iter() {
+        let weight = Scalar::<C>::rand(csprng);
+        z += weight * equation.z;
+
+        bases.extend_from_slice(&equation.bases);
+        scalars.extend_from_slice(&[
+            -weight,
+            -weight * equation.binding_factor,
+            -weight * equation.weighted_challenge,
+        ]);
+    }
+    bases.push(<C::G as CurveGroup>::Affine::generator());
+    scalars.push(z);
+
+    Ok(C::msm(&bases, &scalars)?.is_zero())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn optimistic_aggregation_5_out_of_7() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(7, 5, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut public_comshares = Vec::new();
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(5) {
+            let (public_comshare, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            public_comshares.push(public_comshare);
+            secret_comshares.push(secret_comshare);
+        }
+        let new_aggregator = || {
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+            for (sk, public_comshare) in signing_keys.iter().zip(public_comshares.iter()) {
+                aggregator
+                    .include_signer(sk.index, public_comshare.commitments[0], sk.into())
+                    .unwrap();
+            }
+            aggregator
+        };
+
+        let mut aggregator = new_aggregator();
+        let signers = aggregator.get_signers().clone();
+        let mut partials = Vec::new();
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            partials.push(
+                sk.sign(&message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap(),
+            );
+        }
+
+        // Invalid partial signatures summing up to zero do not cancel each other out.
+        let mut invalid_aggregator = new_aggregator();
+        for (i, partial) in partials.iter().enumerate() {
+            let mut partial = partial.clone();
+            match i {
+                1 => partial.z += Fr::ONE,
+                3 => partial.z -= Fr::ONE,
+                4 => partial.z += Fr::from(2u8),
+                _ => (),
+            }
+            invalid_aggregator
+                .include_partial_signature(partial)
+                .unwrap();
+        }
+        let invalid_aggregator = invalid_aggregator.finalize().unwrap();
+        assert_eq!(
+            invalid_aggregator.aggregate_optimistic(OsRng).unwrap_err(),
+            Error::MisbehavingParticipants(vec![2, 4, 5])
+        );
+        assert_eq!(
+            invalid_aggregator.aggregate_optimistic(OsRng).unwrap_err(),
+            invalid_aggregator.aggregate().unwrap_err()
+        );
+
+        for partial in partials {
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate_optimistic(OsRng).unwrap();
+        assert_eq!(threshold_signature, aggregator.aggregate().unwrap());
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+}
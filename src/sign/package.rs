@@ -0,0 +1,348 @@
+//! The package module for preventing a coordinator of ICE-FROST signing
+//! sessions from equivocating.
+//!
+//! The coordinator of a signing session chooses which commitment share each signer
+//! must use, by selecting one of the commitments it published in the pre-computation
+//! phase. A malicious coordinator could send distinct choices to distinct signers,
+//! splitting their views of the session.
+//!
+//! To prevent this, the coordinator signs its choice in a [`SigningPackage`], which
+//! is broadcast to all signers. Before responding, each signer echoes the
+//! [`SigningPackage::package_hash`] it received to the other signers, and only signs
+//! once all of them reported the same hash, through [`IndividualSigningKey::sign_package`].
+//! As packages are signed, a coordinator sending distinct packages can be proven to
+//! have equivocated.
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey};
+use crate::utils::{hash_to_array, Scalar, Vec};
+use crate::validation::{deserialize_validated, validate_point, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::precomputation::SecretCommitmentShareList;
+use super::signature::{check_canonical_ordering, PartialThresholdSignature, Signer};
+
+/// The choice of a coordinator of the signers of a message, along with their
+/// commitment shares, signed by the coordinator.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SigningPackage<C: CipherSuite> {
+    /// The index of the coordinator, who is also a participant of the group.
+    pub(crate) coordinator_index: u32,
+    /// The hash of the message to be signed.
+    pub(crate) message_hash: Vec<u8>,
+    /// The signers along with their chosen commitment shares, sorted by index.
+    pub(crate) signers: Vec<Signer<C>>,
+    /// The commitment of the coordinator's Schnorr signature.
+    pub(crate) r: C::G,
+    /// The response of the coordinator's Schnorr signature.
+    pub(crate) s: Scalar<C>,
+}
+
+impl<C: CipherSuite> SigningPackage<C> {
+    /// Construct a new [`SigningPackage`] for the given `message_hash` and `signers`,
+    /// signed with the [`IndividualSigningKey`] of the coordinator.
+    pub fn new(
+        message_hash: &[u8],
+        mut signers: Vec<Signer<C>>,
+        coordinator_key: &IndividualSigningKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> FrostResult<C, Self> {
+        signers.sort();
+        signers.dedup();
+
+        let k = Scalar::<C>::rand(&mut csprng);
+
+        let mut package = Self {
+            coordinator_index: coordinator_key.index,
+            message_hash: message_hash.to_vec(),
+            signers,
+            r: C::G::generator() * k,
+            s: Scalar::<C>::ZERO,
+        };
+        let challenge = package.challenge(&(C::G::generator() * coordinator_key.key))?;
+        package.s = k + coordinator_key.key * challenge;
+
+        Ok(package)
+    }
+
+    /// The index of the coordinator who signed this [`SigningPackage`].
+    pub fn coordinator_index(&self) -> u32 {
+        self.coordinator_index
+    }
+
+    /// The hash of the message to be signed.
+    pub fn message_hash(&self) -> &[u8] {
+        &self.message_hash
+    }
+
+    /// The sorted list of participating signers.
+    pub fn signers(&self) -> &[Signer<C>] {
+        &self.signers
+    }
+
+    /// Compute the hash of the content of this [`SigningPackage`], to be
+    /// echoed by each signer to the other ones.
+    pub fn package_hash(&self) -> FrostResult<C, C::HashOutput> {
+        let mut bytes = self.coordinator_index.to_le_bytes().to_vec();
+        self.message_hash
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        self.signers
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+
+        hash_to_array::<C>((C::context_string() + "signing package").as_bytes(), &bytes)
+    }
+
+    /// Verify the signature of this [`SigningPackage`] against the
+    /// [`IndividualVerifyingKey`] of its coordinator.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the package is valid.
+    pub fn verify(&self, coordinator_key: &IndividualVerifyingKey<C>) -> FrostResult<C, ()> {
+        if coordinator_key.index != self.coordinator_index {
+            return Err(Error::InvalidSigningPackage);
+        }
+
+        let challenge = self.challenge(&coordinator_key.share)?;
+
+        match C::G::generator() * self.s == self.r + coordinator_key.share * challenge {
+            true => Ok(()),
+            false => Err(Error::InvalidSigningPackage),
+        }
+    }
+
+    /// Check that all the signers of this [`SigningPackage`] echoed its hash.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that all signers share the
+    /// same view of this package, otherwise an [`Error::InconsistentSigningPackage`]
+    /// listing the signers whose echoed hash is missing or different.
+    pub fn check_echoes(
+        &self,
+        echoed_package_hashes: &[(u32, C::HashOutput)],
+    ) -> FrostResult<C, ()> {
+        let package_hash = self.package_hash()?;
+
+        let inconsistent_signers: Vec<u32> = self
+            .signers
+            .iter()
+            .map(|signer| signer.participant_index)
+            .filter(|index| {
+                !echoed_package_hashes
+                    .iter()
+                    .any(|(i, hash)| i == index && hash.as_ref() == package_hash.as_ref())
+            })
+            .collect();
+
+        match inconsistent_signers.is_empty() {
+            true => Ok(()),
+            false => Err(Error::InconsistentSigningPackage(inconsistent_signers)),
+        }
+    }
+
+    /// Serialize this [`SigningPackage`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`SigningPackage`] from a vector of bytes.
+    ///
+    /// Packages whose signers are not sorted by strictly increasing participant
+    /// index are rejected.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let package: Self = deserialize_validated(bytes)?;
+        check_canonical_ordering(&package.signers)?;
+
+        Ok(package)
+    }
+
+    /// Compute the challenge of the coordinator's Schnorr signature.
+    fn challenge(&self, coordinator_public_key: &C::G) -> FrostResult<C, Scalar<C>> {
+        let mut message = Vec::new();
+        self.r
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        coordinator_public_key
+            .serialize_compressed(&mut message)
+            .map_err(|e| Error::CompressionError(e.into()))?;
+        message.extend(self.package_hash()?.as_ref());
+
+        C::hash_to_field(
+            (C::context_string() + "signing package").as_bytes(),
+            &message,
+        )
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for SigningPackage<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.r)?;
+        self.signers
+            .iter()
+            .try_for_each(ValidatePoints::validate_points)
+    }
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Compute an individual signer's [`PartialThresholdSignature`] contribution to
+    /// a [`super::ThresholdSignature`] on a message, as specified by a [`SigningPackage`].
+    ///
+    /// # Inputs
+    ///
+    /// * The `message_hash` this signer expects to sign,
+    /// * The public `group_key` for this threshold signature,
+    /// * The [`SigningPackage`] received from the coordinator,
+    /// * The [`IndividualVerifyingKey`] of the coordinator,
+    /// * The package hashes echoed by all the signers of the package,
+    /// * This signer's [`SecretCommitmentShareList`], from which the commitment
+    ///   share chosen by the coordinator is dropped after use.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok value contains a [`PartialThresholdSignature`], which
+    /// should be sent to the Signature Aggregator, otherwise an
+    /// [`Error::InvalidSigningPackage`] if the package is not correctly signed or
+    /// is not for the expected message, or an [`Error::InconsistentSigningPackage`]
+    /// if the signers do not share the same view of the package.
+    pub fn sign_package(
+        &self,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        package: &SigningPackage<C>,
+        coordinator_key: &IndividualVerifyingKey<C>,
+        echoed_package_hashes: &[(u32, C::HashOutput)],
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        package.verify(coordinator_key)?;
+        if package.message_hash != message_hash {
+            return Err(Error::InvalidSigningPackage);
+        }
+        package.check_echoes(echoed_package_hashes)?;
+
+        let my_signer = package
+            .signers
+            .iter()
+            .find(|signer| signer.participant_index == self.index)
+            .ok_or(Error::InvalidSigningPackage)?;
+
+        let my_commitment_share_index = my_secret_commitment_share_list
+            .commitments
+            .iter()
+            .position(|share| share.publish() == my_signer.published_commitment_share)
+            .ok_or(Error::MissingCommitmentShares)?;
+
+        self.sign(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            &package.signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::Projective;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn signing_package_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 2).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[1], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        // Participant 3 coordinates the session.
+        let coordinator_sk = &signing_keys[2];
+        let coordinator_vk = coordinator_sk.to_public();
+        let signers = aggregator.get_signers().clone();
+        let package =
+            SigningPackage::new(&message_hash, signers.clone(), coordinator_sk, OsRng).unwrap();
+        let package = SigningPackage::from_bytes(&package.to_bytes().unwrap()).unwrap();
+        assert!(package.verify(&coordinator_vk).is_ok());
+
+        // A coordinator sending another package to participant 2 is detected.
+        let mut other_signers = signers.clone();
+        other_signers[1].published_commitment_share.0 += Projective::generator();
+        let other_package =
+            SigningPackage::new(&message_hash, other_signers, coordinator_sk, OsRng).unwrap();
+        let split_echoes = [
+            (1, package.package_hash().unwrap()),
+            (2, other_package.package_hash().unwrap()),
+        ];
+        assert_eq!(
+            signing_keys[0]
+                .sign_package(
+                    &message_hash,
+                    &group_key,
+                    &package,
+                    &coordinator_vk,
+                    &split_echoes,
+                    &mut secret_comshares[0],
+                )
+                .unwrap_err(),
+            Error::InconsistentSigningPackage(vec![2])
+        );
+
+        let echoes = [
+            (1, package.package_hash().unwrap()),
+            (2, package.package_hash().unwrap()),
+        ];
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign_package(
+                    &message_hash,
+                    &group_key,
+                    &package,
+                    &coordinator_vk,
+                    &echoes,
+                    comshares,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // A package not signed by the expected coordinator is rejected.
+        assert_eq!(
+            package.verify(&signing_keys[0].to_public()).unwrap_err(),
+            Error::InvalidSigningPackage
+        );
+    }
+}
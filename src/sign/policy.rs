@@ -0,0 +1,218 @@
+//! The policy module for restricting the signers accepted by a signature
+//! aggregator, according to operational allow and ban lists, and to diversity
+//! requirements across the organizations running the participants.
+//!
+//! A [`SignerPolicy`] is given to a [`SignatureAggregator`] upon construction with
+//! [`SignatureAggregator::new`]. Signers which are not allowed, or currently banned,
+//! are then rejected upon inclusion, and the aggregator refuses to finalize a session
+//! whose signers do not span enough distinct organizations.
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{BTreeMap, Vec};
+use crate::{Error, FrostResult};
+
+use super::signature::{Initial, SignatureAggregator, Signer};
+
+/// A policy restricting the signers accepted by a [`SignatureAggregator`].
+///
+/// Ban expiries are expressed in the same time unit as the current time given to
+/// [`SignerPolicy::new`], for instance in seconds since the UNIX epoch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerPolicy {
+    /// The sorted indices of the allowed signers, if restricted.
+    allowed: Option<Vec<u32>>,
+    /// The banned signers, along with the expiry of their ban, if any.
+    banned: BTreeMap<u32, Option<u64>>,
+    /// The organization running each participant.
+    organizations: BTreeMap<u32, u32>,
+    /// The minimum number of distinct organizations among the signers of a session.
+    minimum_organizations: usize,
+    /// The current time, against which ban expiries are checked.
+    now: u64,
+}
+
+impl SignerPolicy {
+    /// Construct a new [`SignerPolicy`] at the current time `now`, accepting all signers.
+    pub fn new(now: u64) -> Self {
+        Self {
+            allowed: None,
+            banned: BTreeMap::new(),
+            organizations: BTreeMap::new(),
+            minimum_organizations: 0,
+            now,
+        }
+    }
+
+    /// Only accept the signers with the given `indices`.
+    pub fn allow(mut self, indices: &[u32]) -> Self {
+        let mut allowed = indices.to_vec();
+        allowed.sort_unstable();
+        allowed.dedup();
+        self.allowed = Some(allowed);
+
+        self
+    }
+
+    /// Reject the signer with the given `index`, until the time `until` if provided,
+    /// or permanently otherwise.
+    pub fn ban(mut self, index: u32, until: Option<u64>) -> Self {
+        self.banned.insert(index, until);
+
+        self
+    }
+
+    /// Require the signers of each session to be run by at least `minimum` distinct
+    /// organizations, given the `organizations` running each participant.
+    ///
+    /// Signers without an organization are rejected if `minimum` is not zero.
+    pub fn require_organizations(
+        mut self,
+        organizations: BTreeMap<u32, u32>,
+        minimum: usize,
+    ) -> Self {
+        self.organizations = organizations;
+        self.minimum_organizations = minimum;
+
+        self
+    }
+
+    /// Update the current time, against which ban expiries are checked.
+    pub fn at_time(mut self, now: u64) -> Self {
+        self.now = now;
+
+        self
+    }
+
+    /// Whether the signer with the given `index` is currently banned.
+    pub fn is_banned(&self, index: u32) -> bool {
+        match self.banned.get(&index) {
+            Some(Some(until)) => self.now < *until,
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    /// Check that the signer with the given `index` is accepted by this policy.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signer is accepted,
+    /// otherwise an [`Error::ForbiddenSigner`].
+    pub fn check_signer<C: CipherSuite>(&self, index: u32) -> FrostResult<C, ()> {
+        let is_allowed = match &self.allowed {
+            Some(allowed) => allowed.binary_search(&index).is_ok(),
+            None => true,
+        };
+        let has_organization =
+            self.minimum_organizations == 0 || self.organizations.contains_key(&index);
+
+        match is_allowed && has_organization && !self.is_banned(index) {
+            true => Ok(()),
+            false => Err(Error::ForbiddenSigner(index)),
+        }
+    }
+
+    /// Check that the given `signers` span enough distinct organizations.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signers are diverse
+    /// enough, otherwise an [`Error::InsufficientOrganizations`].
+    pub fn check_organizations<C: CipherSuite>(&self, signers: &[Signer<C>]) -> FrostResult<C, ()> {
+        let mut organizations: Vec<u32> = signers
+            .iter()
+            .filter_map(|signer| self.organizations.get(&signer.participant_index))
+            .copied()
+            .collect();
+        organizations.sort_unstable();
+        organizations.dedup();
+
+        match organizations.len() >= self.minimum_organizations {
+            true => Ok(()),
+            false => Err(Error::InsufficientOrganizations(
+                organizations.len(),
+                self.minimum_organizations,
+            )),
+        }
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Check that the signer with the given `participant_index` is accepted by the
+    /// [`SignerPolicy`] of this aggregator, if any.
+    pub(super) fn check_signer_policy(&self, participant_index: u32) -> FrostResult<C, ()> {
+        match &self.policy {
+            Some(policy) => policy.check_signer(participant_index),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn signer_policy_enforcement() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+        let organizations = [(1, 1), (2, 1), (3, 1), (4, 2)].into_iter().collect();
+        let policy = SignerPolicy::new(50)
+            .allow(&[1, 2, 3, 4])
+            .ban(4, Some(100))
+            .require_organizations(organizations, 2);
+
+        let run_session = |policy: SignerPolicy, indices: &[u32]| {
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &message[..], Some(policy));
+            let mut secret_comshares = Vec::new();
+            for index in indices {
+                let sk = &signing_keys[*index as usize - 1];
+                let (public_comshares, secret) =
+                    generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+                aggregator.include_signer(sk.index, public_comshares.commitments[0], sk.into())?;
+                secret_comshares.push(secret);
+            }
+
+            let signers = aggregator.get_signers().clone();
+            for (index, secret) in indices.iter().zip(secret_comshares.iter_mut()) {
+                let sk = &signing_keys[*index as usize - 1];
+                let partial = sk.sign(&message_hash, &group_key, secret, 0, &signers)?;
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            aggregator.finalize()?.aggregate()
+        };
+
+        // Signers outside the allow list, or currently banned, are rejected.
+        assert_eq!(
+            run_session(policy.clone(), &[1, 2, 5]).unwrap_err(),
+            Error::ForbiddenSigner(5)
+        );
+        assert!(policy.is_banned(4));
+        assert_eq!(
+            run_session(policy.clone(), &[1, 2, 4]).unwrap_err(),
+            Error::ForbiddenSigner(4)
+        );
+
+        // Signers of a single organization are not diverse enough.
+        assert_eq!(
+            run_session(policy.clone(), &[1, 2, 3]).unwrap_err(),
+            Error::InsufficientOrganizations(1, 2)
+        );
+
+        // Once the ban has expired, the signers of both organizations can sign.
+        let policy = policy.at_time(100);
+        assert!(!policy.is_banned(4));
+        let signature = run_session(policy, &[1, 2, 4]).unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+    }
+}
@@ -5,28 +5,32 @@
 use core::ops::Mul;
 
 use crate::keys::IndividualSigningKey;
-use crate::utils::{Scalar, Vec};
+use crate::utils::{ct_eq_encoded, Scalar, Vec};
+use crate::validation::{deserialize_validated, validate_points, ValidatePoints};
 use crate::{Error, FrostResult};
 
 use crate::ciphersuite::CipherSuite;
 
-use ark_ec::{CurveGroup, Group};
+use ark_ec::Group;
 use ark_ff::Zero;
 use ark_serialize::CanonicalDeserialize;
 use ark_serialize::CanonicalSerialize;
 
 use rand::CryptoRng;
 use rand::Rng;
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::Zeroize;
 
 fn nonce_generate<C: CipherSuite>(
     secret_key: &IndividualSigningKey<C>,
+    domain: &[u8],
     mut csprng: impl CryptoRng + Rng,
 ) -> FrostResult<C, Scalar<C>> {
     let mut random_bytes = C::HashOutput::default();
     csprng.fill_bytes(random_bytes.as_mut());
 
-    let mut nonce_input = random_bytes.as_ref().to_vec();
+    let mut nonce_input = domain.to_vec();
+    nonce_input.extend(random_bytes.as_ref());
     nonce_input.extend(&secret_key.to_bytes()?);
     C::h3(&nonce_input)
 }
@@ -40,12 +44,31 @@ impl<C: CipherSuite> Drop for NoncePair<C> {
     }
 }
 
+impl<C: CipherSuite> ConstantTimeEq for NoncePair<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_eq_encoded(&self.0, &other.0) & ct_eq_encoded(&self.1, &other.1)
+    }
+}
+
 impl<C: CipherSuite> NoncePair<C> {
-    pub fn new(secret_key: &IndividualSigningKey<C>, mut csprng: impl CryptoRng + Rng) -> Self {
-        NoncePair(
-            nonce_generate(secret_key, &mut csprng).unwrap(),
-            nonce_generate(secret_key, &mut csprng).unwrap(),
-        )
+    pub fn new(
+        secret_key: &IndividualSigningKey<C>,
+        csprng: impl CryptoRng + Rng,
+    ) -> FrostResult<C, Self> {
+        Self::new_with_domain(secret_key, &[], csprng)
+    }
+
+    /// Generate a new [`NoncePair`], whose derivation is separated from the
+    /// one of regular nonces by the provided `domain` tag.
+    pub fn new_with_domain(
+        secret_key: &IndividualSigningKey<C>,
+        domain: &[u8],
+        mut csprng: impl CryptoRng + Rng,
+    ) -> FrostResult<C, Self> {
+        Ok(NoncePair(
+            nonce_generate(secret_key, domain, &mut csprng)?,
+            nonce_generate(secret_key, domain, &mut csprng)?,
+        ))
     }
 }
 
@@ -63,6 +86,7 @@ impl<C: CipherSuite> From<NoncePair<C>> for CommitmentShare<C> {
                 secret: other.1,
                 commit: y,
             },
+            randomizer: None,
         }
     }
 }
@@ -92,10 +116,16 @@ impl<C: CipherSuite> Drop for Commitment<C> {
     }
 }
 
+impl<C: CipherSuite> ConstantTimeEq for Commitment<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_eq_encoded(&self.secret, &other.secret) & ct_eq_encoded(&self.commit, &other.commit)
+    }
+}
+
 /// Test equality in constant-time.
 impl<C: CipherSuite> PartialEq for Commitment<C> {
     fn eq(&self, other: &Self) -> bool {
-        self.secret.eq(&other.secret) & self.commit.into_affine().eq(&other.commit.into_affine())
+        self.ct_eq(other).into()
     }
 }
 
@@ -110,6 +140,9 @@ pub struct CommitmentShare<C: CipherSuite> {
     ///
     /// This is \\((e\_{ij}, E\_{ij})\\) in the paper.
     pub(crate) binding: Commitment<C>,
+    /// The commitment randomizer derived from this share, if any, recorded so that
+    /// a signer hands out at most one randomizer per commitment share.
+    pub(crate) randomizer: Option<Scalar<C>>,
 }
 
 impl<C: CipherSuite> CommitmentShare<C> {
@@ -118,14 +151,20 @@ impl<C: CipherSuite> CommitmentShare<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`CommitmentShare`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for CommitmentShare<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>([&self.hiding.commit, &self.binding.commit])
     }
 }
 
@@ -135,10 +174,16 @@ impl<C: CipherSuite> Drop for CommitmentShare<C> {
     }
 }
 
+impl<C: CipherSuite> ConstantTimeEq for CommitmentShare<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.hiding.ct_eq(&other.hiding) & self.binding.ct_eq(&other.binding)
+    }
+}
+
 /// Test equality in constant-time.
 impl<C: CipherSuite> PartialEq for CommitmentShare<C> {
     fn eq(&self, other: &Self) -> bool {
-        self.hiding.eq(&other.hiding) & self.binding.eq(&other.binding)
+        self.ct_eq(other).into()
     }
 }
 
@@ -163,14 +208,22 @@ impl<C: CipherSuite> SecretCommitmentShareList<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`SecretCommitmentShareList`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for SecretCommitmentShareList<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.commitments
+            .iter()
+            .try_for_each(ValidatePoints::validate_points)
     }
 }
 
@@ -193,14 +246,24 @@ impl<C: CipherSuite> PublicCommitmentShareList<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`PublicCommitmentShareList`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PublicCommitmentShareList<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_points::<C>(
+            self.commitments
+                .iter()
+                .flat_map(|(hiding, binding)| [hiding, binding]),
+        )
     }
 }
 
@@ -214,19 +277,20 @@ impl<C: CipherSuite> PublicCommitmentShareList<C> {
 ///
 /// # Returns
 ///
-/// A tuple of ([`PublicCommitmentShareList`], [`SecretCommitmentShareList`]).
+/// A tuple of ([`PublicCommitmentShareList`], [`SecretCommitmentShareList`]),
+/// or an [`Error`] if the nonce derivation failed.
 pub fn generate_commitment_share_lists<C: CipherSuite>(
     mut csprng: impl CryptoRng + Rng,
     participant_secret_key: &IndividualSigningKey<C>,
     number_of_shares: usize,
-) -> (PublicCommitmentShareList<C>, SecretCommitmentShareList<C>) {
+) -> FrostResult<C, (PublicCommitmentShareList<C>, SecretCommitmentShareList<C>)> {
     let mut commitments: Vec<CommitmentShare<C>> = Vec::with_capacity(number_of_shares);
 
     for _ in 0..number_of_shares {
         commitments.push(CommitmentShare::from(NoncePair::new(
             participant_secret_key,
             &mut csprng,
-        )));
+        )?));
     }
 
     let mut published: Vec<(C::G, C::G)> = Vec::with_capacity(number_of_shares);
@@ -235,13 +299,13 @@ pub fn generate_commitment_share_lists<C: CipherSuite>(
         published.push(commitment.publish());
     }
 
-    (
+    Ok((
         PublicCommitmentShareList {
             participant_index: participant_secret_key.index,
             commitments: published,
         },
         SecretCommitmentShareList { commitments },
-    )
+    ))
 }
 
 impl<C: CipherSuite> SecretCommitmentShareList<C> {
@@ -263,12 +327,18 @@ impl<C: CipherSuite> SecretCommitmentShareList<C> {
             drop(self.commitments.remove(index as usize));
         }
         drop(share);
+        trace!(
+            debug,
+            "ice-frost: zeroized a used commitment share, {} remaining",
+            self.commitments.len()
+        );
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::sign::AdditiveSigningKey;
     use crate::testing::Secp256k1Sha256;
 
     use ark_ec::{CurveGroup, Group};
@@ -284,7 +354,7 @@ mod test {
             index: 1,
             key: Fr::zero(),
         };
-        let _secret_pair = NoncePair::<Secp256k1Sha256>::new(&secret_key, &mut OsRng);
+        let _secret_pair = NoncePair::<Secp256k1Sha256>::new(&secret_key, &mut OsRng).unwrap();
     }
 
     #[test]
@@ -294,7 +364,7 @@ mod test {
             key: Fr::zero(),
         };
         let _commitment_share: CommitmentShare<Secp256k1Sha256> =
-            NoncePair::new(&secret_key, &mut OsRng).into();
+            NoncePair::new(&secret_key, &mut OsRng).unwrap().into();
     }
 
     #[test]
@@ -319,7 +389,11 @@ mod test {
             let commit = Projective::generator().mul(secret);
             let binding = Commitment::<Secp256k1Sha256> { secret, commit };
             let hiding = binding.clone();
-            let commitment_share = CommitmentShare { binding, hiding };
+            let commitment_share = CommitmentShare {
+                binding,
+                hiding,
+                randomizer: None,
+            };
             let mut bytes = Vec::new();
 
             commitment_share.serialize_compressed(&mut bytes).unwrap();
@@ -344,7 +418,7 @@ mod test {
             key: Fr::zero(),
         };
         let (public_share_list, secret_share_list) =
-            generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &secret_key, 1);
+            generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &secret_key, 1).unwrap();
 
         assert_eq!(
             public_share_list.commitments[0].0.into_affine(),
@@ -360,7 +434,7 @@ mod test {
             key: Fr::zero(),
         };
         let (_public_share_list, mut secret_share_list) =
-            generate_commitment_share_lists(&mut OsRng, &secret_key, 8);
+            generate_commitment_share_lists(&mut OsRng, &secret_key, 8).unwrap();
 
         assert!(secret_share_list.commitments.len() == 8);
 
@@ -370,4 +444,46 @@ mod test {
 
         assert!(secret_share_list.commitments.len() == 7);
     }
+
+    #[test]
+    fn constant_time_equality() {
+        let mut rng = OsRng;
+        let a = NoncePair::<Secp256k1Sha256>(Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let b = NoncePair::<Secp256k1Sha256>(a.0, Fr::rand(&mut rng));
+
+        let a_copy = NoncePair::<Secp256k1Sha256>(a.0, a.1);
+
+        assert!(bool::from(a.ct_eq(&a_copy)));
+        assert!(!bool::from(a.ct_eq(&b)));
+
+        let share_a = CommitmentShare::from(a);
+        let share_b = CommitmentShare::from(b);
+        assert!(share_a == CommitmentShare::from(a_copy));
+        assert!(share_a != share_b);
+        assert!(share_a.hiding == share_b.hiding);
+        assert!(share_a.binding != share_b.binding);
+
+        let key_a = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Fr::rand(&mut rng),
+        };
+        let key_b = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 2,
+            key: key_a.key,
+        };
+        assert!(key_a == key_a.clone());
+        assert!(key_a != key_b);
+
+        let additive_a = AdditiveSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: key_a.key,
+        };
+        let additive_b = AdditiveSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Fr::rand(&mut rng),
+        };
+        assert!(bool::from(additive_a.ct_eq(&additive_a.clone())));
+        assert!(!bool::from(additive_a.ct_eq(&additive_b)));
+        assert!(additive_a != additive_b);
+    }
 }
@@ -0,0 +1,271 @@
+//! The preflight module, letting a signature aggregator predict the outcome of the
+//! aggregation of the partial signatures received so far.
+//!
+//! Instead of calling [`SignatureAggregator::finalize`] and
+//! [`SignatureAggregator::aggregate`] speculatively and parsing their errors, an
+//! orchestrator runs [`SignatureAggregator::preflight`] whenever partial signatures
+//! arrive. The resulting [`PreflightReport`] tells whether the aggregation would
+//! succeed, and otherwise which signers must be re-requested, and why.
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::Vec;
+use crate::FrostResult;
+
+use super::randomized::compute_randomized_binding_factors;
+use super::rerequest::ReRequestReason;
+use super::signature::{
+    compute_group_commitment, compute_session_challenge, Initial, SignatureAggregator,
+};
+
+/// The reason for a partial signature received by a [`SignatureAggregator`] to be
+/// rejected by the aggregation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PartialRejection {
+    /// The partial signature was sent by a participant who is not a signer of the
+    /// session, and is ignored by the aggregation.
+    UnexpectedSigner,
+    /// The public key of the signer was not provided to the aggregator.
+    MissingPublicKey,
+    /// The partial signature does not verify against the public key and published
+    /// commitment share of its signer.
+    InvalidPartialSignature,
+}
+
+/// The predicted outcome of the aggregation of the partial signatures received so far
+/// by a [`SignatureAggregator`], as given by [`SignatureAggregator::preflight`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreflightReport {
+    /// Whether finalizing the aggregator and aggregating would yield a valid signature.
+    pub(crate) will_succeed: bool,
+    /// The sorted indices of the signers whose partial signature was not received.
+    pub(crate) missing_signers: Vec<u32>,
+    /// The partial signatures which would be rejected, sorted by signer index.
+    pub(crate) rejected: Vec<(u32, PartialRejection)>,
+    /// Whether the signers satisfy the [`SignerPolicy`](super::SignerPolicy) of the
+    /// aggregator, if any.
+    pub(crate) policy_satisfied: bool,
+}
+
+impl PreflightReport {
+    /// Whether finalizing the aggregator and aggregating the partial signatures
+    /// received so far would yield a valid signature.
+    pub fn will_succeed(&self) -> bool {
+        self.will_succeed
+    }
+
+    /// The sorted indices of the signers whose partial signature was not received.
+    pub fn missing_signers(&self) -> &[u32] {
+        &self.missing_signers
+    }
+
+    /// The indices of the participants whose partial signature would be rejected,
+    /// sorted by index, along with the reason for their rejection.
+    pub fn rejected(&self) -> &[(u32, PartialRejection)] {
+        &self.rejected
+    }
+
+    /// Whether the signers satisfy the [`SignerPolicy`](super::SignerPolicy) of the
+    /// aggregator, if any.
+    pub fn policy_satisfied(&self) -> bool {
+        self.policy_satisfied
+    }
+
+    /// The signers whose partial signature should be re-requested with
+    /// [`SignatureAggregator::re_request`], sorted by index, along with the
+    /// [`ReRequestReason`] to provide.
+    pub fn re_requests(&self) -> Vec<(u32, ReRequestReason)> {
+        let mut re_requests: Vec<(u32, ReRequestReason)> = self
+            .missing_signers
+            .iter()
+            .map(|index| (*index, ReRequestReason::Missing))
+            .chain(
+                self.rejected
+                    .iter()
+                    .filter(|(_, rejection)| {
+                        *rejection == PartialRejection::InvalidPartialSignature
+                    })
+                    .map(|(index, _)| (*index, ReRequestReason::Invalid)),
+            )
+            .collect();
+        re_requests.sort_by_key(|(index, _)| *index);
+
+        re_requests
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Predict the outcome of finalizing this aggregator and aggregating the partial
+    /// signatures received so far, without consuming it.
+    ///
+    /// All partial signatures received are checked against the public key and
+    /// published commitment share of their signer, in the context of the full set
+    /// of expected signers, as done by [`SignatureAggregator::abort`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PreflightReport`] of the session,
+    /// otherwise an [`Error`](crate::Error) if the message to be signed is invalid.
+    pub fn preflight(&mut self) -> FrostResult<C, PreflightReport> {
+        let message_hash = self.message_hash()?;
+        let signers = self.get_signers().clone();
+        let all_participant_indices: Vec<u32> =
+            signers.iter().map(|x| x.participant_index).collect();
+
+        let missing_signers: Vec<u32> = self
+            .get_remaining_signers()
+            .iter()
+            .map(|signer| signer.participant_index)
+            .collect();
+
+        let mut rejected = Vec::new();
+        if signers.is_empty() {
+            rejected.extend(
+                self.state
+                    .partial_signatures
+                    .keys()
+                    .map(|index| (*index, PartialRejection::UnexpectedSigner)),
+            );
+        } else {
+            let binding_factor_list = compute_randomized_binding_factors(
+                message_hash.as_ref(),
+                &signers,
+                &self.state.randomizers,
+            )?;
+            let group_commitment = compute_group_commitment(&signers, &binding_factor_list)?;
+            let challenge = compute_session_challenge::<C>(
+                &group_commitment,
+                &self.state.group_key,
+                message_hash.as_ref(),
+                self.aggregator.dry_run,
+            )?;
+            let interpolated_shares =
+                self.interpolated_verification_shares(&all_participant_indices);
+
+            for (index, z) in self.state.partial_signatures.iter() {
+                let rejection = match all_participant_indices.binary_search(index) {
+                    Err(_) => PartialRejection::UnexpectedSigner,
+                    Ok(_) if !self.state.public_keys.contains_key(index) => {
+                        PartialRejection::MissingPublicKey
+                    }
+                    Ok(position) => {
+                        if self.state.verify_partial_signature(
+                            *index,
+                            z,
+                            message_hash.as_ref(),
+                            challenge,
+                            interpolated_shares[position].as_ref(),
+                        ) {
+                            continue;
+                        }
+                        PartialRejection::InvalidPartialSignature
+                    }
+                };
+                rejected.push((*index, rejection));
+            }
+        }
+
+        let policy_satisfied = match &self.policy {
+            Some(policy) => policy.check_organizations(&signers).is_ok(),
+            None => true,
+        };
+
+        // Valid partial signatures from all signers always aggregate to a valid signature.
+        let will_succeed = !signers.is_empty()
+            && missing_signers.is_empty()
+            && policy_satisfied
+            && rejected
+                .iter()
+                .all(|(_, rejection)| *rejection == PartialRejection::UnexpectedSigner);
+
+        Ok(PreflightReport {
+            will_succeed,
+            missing_signers,
+            rejected,
+            policy_satisfied,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::Field;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn preflight_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+
+        let mut partials = Vec::new();
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            partials.push(
+                sk.sign(&message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap(),
+            );
+        }
+
+        let report = aggregator.preflight().unwrap();
+        assert!(!report.will_succeed());
+        assert_eq!(report.missing_signers(), &[1, 2]);
+        assert!(report.rejected().is_empty());
+
+        // The partial signature of participant 2 is lost, that of participant 1 corrupted,
+        // and participant 3 is not a signer of the session.
+        let mut corrupted = partials[0].clone();
+        corrupted.z += Fr::ONE;
+        aggregator.include_partial_signature(corrupted).unwrap();
+        let mut unexpected = partials[0].clone();
+        unexpected.index = 3;
+        aggregator.include_partial_signature(unexpected).unwrap();
+
+        let report = aggregator.preflight().unwrap();
+        assert!(!report.will_succeed());
+        assert!(report.policy_satisfied());
+        assert_eq!(report.missing_signers(), &[2]);
+        assert_eq!(
+            report.rejected(),
+            &[
+                (1, PartialRejection::InvalidPartialSignature),
+                (3, PartialRejection::UnexpectedSigner)
+            ]
+        );
+        assert_eq!(
+            report.re_requests(),
+            vec![(1, ReRequestReason::Invalid), (2, ReRequestReason::Missing)]
+        );
+
+        for partial in partials {
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let report = aggregator.preflight().unwrap();
+        assert!(report.will_succeed());
+        assert!(report.missing_signers().is_empty());
+        assert!(report.re_requests().is_empty());
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+}
@@ -0,0 +1,306 @@
+//! The preprocessed module for single-round ICE-FROST signing sessions
+//! with commitment shares bound to message indices.
+//!
+//! Once every participant has published a [`PublicCommitmentShareList`], a
+//! [`PreprocessedSession`] binds the \\( k \\)-th commitment share of each list
+//! to the \\( (s + k) \\)-th message signed during a given epoch, for a starting
+//! message index \\( s \\). The coordinator and the signers can then independently
+//! derive which commitment shares to use for a given message index, so that
+//! signing only requires sending the message to the signers.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{ToString, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::precomputation::{PublicCommitmentShareList, SecretCommitmentShareList};
+use super::signature::{PartialThresholdSignature, Signer};
+
+/// A preprocessed signing session, binding the commitment shares published
+/// by the participants to the indices of the messages signed during an epoch.
+#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PreprocessedSession<C: CipherSuite> {
+    /// The epoch during which this session is valid.
+    pub(crate) epoch: u64,
+    /// The index of the message bound to the first commitment share of each list.
+    pub(crate) first_message_index: u64,
+    /// The published commitment share lists, sorted by participant index.
+    pub(crate) commitment_share_lists: Vec<PublicCommitmentShareList<C>>,
+}
+
+impl<C: CipherSuite> PreprocessedSession<C> {
+    /// Construct a new [`PreprocessedSession`] for the given `epoch`, binding the
+    /// commitment shares of the provided `commitment_share_lists` to the messages
+    /// indexed from `first_message_index` onwards.
+    ///
+    /// All lists must contain the same number of commitment shares, and
+    /// must have been published by distinct participants.
+    pub fn new(
+        epoch: u64,
+        first_message_index: u64,
+        mut commitment_share_lists: Vec<PublicCommitmentShareList<C>>,
+    ) -> FrostResult<C, Self> {
+        commitment_share_lists.sort_by_key(|list| list.participant_index);
+
+        if commitment_share_lists
+            .windows(2)
+            .any(|w| w[0].participant_index == w[1].participant_index)
+        {
+            return Err(Error::DuplicateIndices);
+        }
+        if commitment_share_lists
+            .windows(2)
+            .any(|w| w[0].commitments.len() != w[1].commitments.len())
+        {
+            return Err(Error::Custom(
+                "Commitment share lists must have the same length".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            epoch,
+            first_message_index,
+            commitment_share_lists,
+        })
+    }
+
+    /// The epoch during which this session is valid.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The range of message indices covered by this session.
+    pub fn message_indices(&self) -> core::ops::Range<u64> {
+        let length = self
+            .commitment_share_lists
+            .first()
+            .map_or(0, |list| list.commitments.len() as u64);
+
+        self.first_message_index..self.first_message_index.saturating_add(length)
+    }
+
+    /// Derive the position of the commitment shares bound to the message with
+    /// index `message_index` of the given `epoch`.
+    pub fn commitment_index(&self, epoch: u64, message_index: u64) -> FrostResult<C, usize> {
+        if epoch != self.epoch {
+            return Err(Error::InactiveEpoch(epoch));
+        }
+        if !self.message_indices().contains(&message_index) {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        Ok((message_index - self.first_message_index) as usize)
+    }
+
+    /// Derive the [`Signer`]s for the message with index `message_index` of the given
+    /// `epoch`, from the indices of the `participants` expected to sign it.
+    ///
+    /// # Returns
+    ///
+    /// The sorted list of [`Signer`]s, to be included in a [`super::SignatureAggregator`]
+    /// by the coordinator, and to be provided to
+    /// [`IndividualSigningKey::sign_preprocessed`] by the signers.
+    pub fn signers(
+        &self,
+        epoch: u64,
+        message_index: u64,
+        participants: &[u32],
+    ) -> FrostResult<C, Vec<Signer<C>>> {
+        let commitment_index = self.commitment_index(epoch, message_index)?;
+
+        let mut signers = participants
+            .iter()
+            .map(|index| {
+                self.commitment_share_lists
+                    .binary_search_by_key(index, |list| list.participant_index)
+                    .map(|position| Signer {
+                        participant_index: *index,
+                        published_commitment_share: self.commitment_share_lists[position]
+                            .commitments[commitment_index],
+                    })
+                    .map_err(|_| Error::MissingCommitmentShares)
+            })
+            .collect::<FrostResult<C, Vec<Signer<C>>>>()?;
+        signers.sort();
+        signers.dedup();
+
+        Ok(signers)
+    }
+
+    /// Serialize this [`PreprocessedSession`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PreprocessedSession`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PreprocessedSession<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        self.commitment_share_lists
+            .iter()
+            .try_for_each(ValidatePoints::validate_points)
+    }
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Compute an individual signer's [`PartialThresholdSignature`] contribution to
+    /// the [`super::ThresholdSignature`] of the message with index `message_index` of
+    /// the given `epoch`, using the commitment share bound to it in `session`.
+    ///
+    /// # Inputs
+    ///
+    /// * The `message_hash` to be signed by every individual signer, this should be
+    ///   the `Sha256` digest of the message, optionally along with some application-specific
+    ///   context string, and can be calculated with the helper function [`CipherSuite::h4`],
+    /// * The public `group_key` for this threshold signature,
+    /// * The [`PreprocessedSession`] binding commitment shares to message indices,
+    /// * This signer's [`SecretCommitmentShareList`] being used in this instantiation,
+    ///   from which the bound commitment share is dropped after use,
+    /// * The `epoch` and `message_index` of the message to be signed,
+    /// * The list of all the currently participating [`Signer`]s, as derived with
+    ///   [`PreprocessedSession::signers`].
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok value contains a [`PartialThresholdSignature`], which
+    /// should be sent to the Signature Aggregator. Signing a message twice fails
+    /// with [`Error::MissingCommitmentShares`], as its commitment share has been consumed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_preprocessed(
+        &self,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        session: &PreprocessedSession<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        epoch: u64,
+        message_index: u64,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        let my_signer = session
+            .signers(epoch, message_index, &[self.index])?
+            .pop()
+            .ok_or(Error::MissingCommitmentShares)?;
+
+        let my_commitment_share_index = my_secret_commitment_share_list
+            .commitments
+            .iter()
+            .position(|share| share.publish() == my_signer.published_commitment_share)
+            .ok_or(Error::MissingCommitmentShares)?;
+
+        self.sign(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn preprocessed_signing_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let mut public_lists = Vec::new();
+        let mut secret_lists = Vec::new();
+        for sk in signing_keys.iter() {
+            let (public_list, secret_list) =
+                generate_commitment_share_lists(&mut OsRng, sk, 3).unwrap();
+            public_lists.push(public_list);
+            secret_lists.push(secret_list);
+        }
+
+        let session = PreprocessedSession::new(7, 4710, public_lists).unwrap();
+        assert_eq!(session.message_indices(), 4710..4713);
+
+        let bytes = session.to_bytes().unwrap();
+        let session = PreprocessedSession::from_bytes(&bytes).unwrap();
+
+        for (message_index, participants) in [(4711, [1, 3, 5]), (4710, [2, 3, 4])] {
+            let message = message_index.to_string();
+            let message_hash = Secp256k1Sha256::h4(message.as_bytes()).unwrap();
+
+            // Both sides derive the signers from the message index alone.
+            let signers = session.signers(7, message_index, &participants).unwrap();
+
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, message.as_bytes(), None);
+            for signer in signers.iter() {
+                let sk = &signing_keys[signer.participant_index as usize - 1];
+                aggregator
+                    .include_signer(
+                        signer.participant_index,
+                        signer.published_commitment_share,
+                        sk.into(),
+                    )
+                    .unwrap();
+            }
+
+            for index in participants {
+                let partial = signing_keys[index as usize - 1]
+                    .sign_preprocessed(
+                        &message_hash,
+                        &group_key,
+                        &session,
+                        &mut secret_lists[index as usize - 1],
+                        7,
+                        message_index,
+                        &signers,
+                    )
+                    .unwrap();
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            let aggregator = aggregator.finalize().unwrap();
+            let threshold_signature = aggregator.aggregate().unwrap();
+            assert!(threshold_signature
+                .verify(&group_key, &message_hash)
+                .is_ok());
+        }
+
+        // Commitment shares cannot be reused, nor used outside of the session.
+        let signers = session.signers(7, 4711, &[1, 3, 5]).unwrap();
+        assert_eq!(
+            signing_keys[0].sign_preprocessed(
+                &[0u8; 32],
+                &group_key,
+                &session,
+                &mut secret_lists[0],
+                7,
+                4711,
+                &signers
+            ),
+            Err(Error::MissingCommitmentShares)
+        );
+        assert_eq!(
+            session.signers(7, 4713, &[1, 2, 3]),
+            Err(Error::MissingCommitmentShares)
+        );
+        assert_eq!(
+            session.signers(8, 4711, &[1, 2, 3]),
+            Err(Error::InactiveEpoch(8))
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! The presign module, splitting the computation of a partial signature into a
+//! message-independent phase, run ahead of time, and a fast message-dependent phase.
+//!
+//! Once the signers of a session and their published commitment shares are known,
+//! a signer calls [`IndividualSigningKey::presign`] during idle time. The resulting
+//! [`PreSignature`] takes the secret nonces of the session out of the signer's
+//! [`SecretCommitmentShareList`], and holds its share weighted by its Lagrange
+//! coefficient, the hash of the commitment list of the session and the commitments
+//! to be weighted by the binding factors, normalized to affine coordinates.
+//!
+//! [`PreSignature::sign`] then only hashes the message into the binding factors and
+//! the challenge, and computes the group commitment in a single multi-scalar
+//! multiplication, cutting the latency of the critical path of interactive signing.
+//! A [`PreSignature`] is consumed when signing, and cannot be cloned, so that its
+//! nonces are never reused.
+
+use ark_ec::CurveGroup;
+use ark_ff::Zero;
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{calculate_lagrange_coefficients, points_to_affine_batch, Scalar, Vec};
+use crate::{Error, FrostResult};
+
+use super::precomputation::{CommitmentShare, SecretCommitmentShareList};
+use super::signature::{compute_commitment_list_hash, PartialThresholdSignature, Signer};
+
+/// The message-independent part of the [`PartialThresholdSignature`] of a signer,
+/// as given by [`IndividualSigningKey::presign`].
+#[derive(Debug)]
+pub struct PreSignature<C: CipherSuite> {
+    /// The index of the signer.
+    pub(crate) index: u32,
+    /// The public key of the group.
+    pub(crate) group_key: GroupVerifyingKey<C>,
+    /// The secret commitment share of the signer for this session.
+    pub(crate) commitment_share: CommitmentShare<C>,
+    /// The secret share of the signer times its Lagrange coefficient.
+    pub(crate) weighted_key: Scalar<C>,
+    /// The indices of the signers of the session, in canonical order.
+    pub(crate) signer_indices: Vec<u32>,
+    /// The hash of the commitment list of the session.
+    pub(crate) commitment_list_hash: Vec<u8>,
+    /// The sum of the hiding commitments of the signers.
+    pub(crate) hiding_commitment: C::G,
+    /// The binding commitments of the signers, in canonical order.
+    pub(crate) binding_commitments: Vec<<C::G as CurveGroup>::Affine>,
+}
+
+impl<C: CipherSuite> PreSignature<C> {
+    /// The index of the signer of this [`PreSignature`].
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The indices of the signers of the session of this [`PreSignature`], sorted
+    /// by increasing index.
+    pub fn signers(&self) -> &[u32] {
+        &self.signer_indices
+    }
+
+    /// Complete this [`PreSignature`] into the [`PartialThresholdSignature`] of the
+    /// message with the given `message_hash`, as computed by [`IndividualSigningKey::sign`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value contains a [`PartialThresholdSignature`], which
+    /// should be sent to the [`SignatureAggregator`](super::SignatureAggregator).
+    pub fn sign(self, message_hash: &[u8]) -> FrostResult<C, PartialThresholdSignature<C>> {
+        let mut rho_input_prefix = C::h4(message_hash)?.as_ref().to_vec();
+        rho_input_prefix.extend_from_slice(&self.commitment_list_hash);
+
+        let mut binding_factors = Vec::with_capacity(self.signer_indices.len());
+        let mut binding_factor = Scalar::<C>::zero();
+        for index in self.signer_indices.iter() {
+            let mut rho_input = rho_input_prefix.clone();
+            // RFC Note: identifier should be a ScalarField element that we serialize
+            rho_input.extend(&index.to_le_bytes()[..]);
+            let rho = C::h1(&rho_input)?;
+            if *index == self.index {
+                binding_factor = rho;
+            }
+            binding_factors.push(rho);
+        }
+
+        let group_commitment =
+            self.hiding_commitment + C::msm(&self.binding_commitments, &binding_factors)?;
+        let challenge = C::challenge(&group_commitment, &self.group_key.key, message_hash)?;
+
+        let z = self.commitment_share.hiding.secret
+            + (self.commitment_share.binding.secret * binding_factor)
+            + (self.weighted_key * challenge);
+
+        Ok(PartialThresholdSignature {
+            index: self.index,
+            z,
+            epoch_tag: None,
+        })
+    }
+}
+
+impl<C: CipherSuite> Drop for PreSignature<C> {
+    fn drop(&mut self) {
+        self.weighted_key.zeroize();
+    }
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Compute the message-independent part of this signer's [`PartialThresholdSignature`],
+    /// to be completed with [`PreSignature::sign`] once the message is known.
+    ///
+    /// The inputs are the same as those of [`IndividualSigningKey::sign`], except for
+    /// the message. The commitment share used is removed from
+    /// `my_secret_commitment_share_list`, and only lives in the [`PreSignature`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value contains a [`PreSignature`], otherwise an
+    /// [`Error::MissingCommitmentShares`] if there is no commitment share at the given
+    /// index, an [`Error::InvalidBindingFactor`] if this signer is not among the `signers`,
+    /// or an [`Error::UnsortedSigners`] if they are not in canonical order.
+    pub fn presign(
+        &self,
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PreSignature<C>> {
+        if my_commitment_share_index + 1 > my_secret_commitment_share_list.commitments.len() {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let signer_indices: Vec<u32> = signers.iter().map(|x| x.participant_index).collect();
+        if !signer_indices.contains(&self.index) {
+            return Err(Error::InvalidBindingFactor);
+        }
+
+        let commitment_list_hash = compute_commitment_list_hash(signers)?;
+        let weighted_key =
+            calculate_lagrange_coefficients::<C>(self.index, &signer_indices)? * self.key;
+
+        let mut hiding_commitment = C::G::zero();
+        let mut binding_commitments = Vec::with_capacity(signers.len());
+        for signer in signers.iter() {
+            hiding_commitment += signer.published_commitment_share.0;
+            binding_commitments.push(signer.published_commitment_share.1);
+        }
+
+        Ok(PreSignature {
+            index: self.index,
+            group_key: *group_key,
+            commitment_share: my_secret_commitment_share_list
+                .commitments
+                .remove(my_commitment_share_index),
+            weighted_key,
+            signer_indices,
+            commitment_list_hash,
+            hiding_commitment,
+            binding_commitments: points_to_affine_batch::<C>(&binding_commitments),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn presigning_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().skip(1).take(3) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 2).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[1], sk.into())
+                .unwrap();
+            secret_comshares.push((sk, secret_comshare));
+        }
+        let signers = aggregator.get_signers().clone();
+
+        // Presignatures are computed before the message is known.
+        let mut presignatures = Vec::new();
+        for (sk, secret_comshare) in secret_comshares.iter_mut() {
+            let expected_partial = sk
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut secret_comshare.clone(),
+                    1,
+                    &signers,
+                )
+                .unwrap();
+
+            let presignature = sk
+                .presign(&group_key, secret_comshare, 1, &signers)
+                .unwrap();
+            assert_eq!(secret_comshare.commitments.len(), 1);
+            assert_eq!(presignature.signers(), &[2, 3, 4]);
+            presignatures.push((presignature, expected_partial));
+        }
+
+        for (presignature, expected_partial) in presignatures {
+            let partial = presignature.sign(&message_hash).unwrap();
+            assert_eq!(partial, expected_partial);
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        let (_, mut secret_comshare) =
+            generate_commitment_share_lists(&mut OsRng, &signing_keys[0], 1).unwrap();
+        assert_eq!(
+            signing_keys[0]
+                .presign(&group_key, &mut secret_comshare, 0, &signers)
+                .unwrap_err(),
+            Error::InvalidBindingFactor
+        );
+        assert_eq!(
+            signing_keys[1]
+                .presign(&group_key, &mut secret_comshare, 1, &signers)
+                .unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+    }
+}
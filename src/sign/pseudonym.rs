@@ -0,0 +1,379 @@
+//! The pseudonym module for hiding which participants take part in signing
+//! sessions from external observers of the signing traffic.
+//!
+//! By default, the commitment shares and partial signatures sent by signers to
+//! the signature aggregator carry their fixed participant index, allowing anyone
+//! monitoring the network to track which participants are active. In this mode,
+//! each signer instead tags its messages with a [`SessionPseudonym`], derived from
+//! a [`PseudonymKey`] it shares with the coordinator of the signing sessions and
+//! from an identifier chosen per session by the coordinator. Pseudonyms of distinct
+//! sessions cannot be linked together without the key, while the coordinator maps
+//! them back to participant indices with a [`PseudonymMap`].
+//!
+//! The pseudonym key of a signer is derived from the Diffie-Hellman shared secret
+//! between its Diffie-Hellman key and the one of the coordinator, so that only the
+//! signer and the coordinator can compute or recognize its pseudonyms.
+//!
+//! Note that the list of [`Signer`](super::Signer)s sent back by the coordinator
+//! still holds the participant indices needed to compute binding factors and
+//! Lagrange coefficients, and should be sent to the signers over confidential channels.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use core::marker::PhantomData;
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::{secret_share::serialize_dh_shared_key, Participant, Role};
+use crate::keys::{DhKeyProvider, DiffieHellmanPublicKey};
+use crate::utils::{hash_to_array, BTreeMap, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_points, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::signature::PartialThresholdSignature;
+
+/// An ephemeral identifier of a signer, only valid for a single signing session.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
+pub struct SessionPseudonym(pub u64);
+
+/// A secret key shared between a signer and the coordinator of its signing
+/// sessions, from which the signer's [`SessionPseudonym`]s are derived.
+#[derive(Clone, Debug, Zeroize)]
+pub struct PseudonymKey<C: CipherSuite> {
+    key: Vec<u8>,
+    #[zeroize(skip)]
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> Drop for PseudonymKey<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> PseudonymKey<C> {
+    /// Derive the [`PseudonymKey`] shared with the owner of the `peer_dh_public_key`,
+    /// from the Diffie-Hellman key held by the given `dh_key_provider`.
+    ///
+    /// A signer derives it with the public key of the coordinator, and the
+    /// coordinator with the public key of the signer.
+    pub fn new(
+        dh_key_provider: &impl DhKeyProvider<C>,
+        peer_dh_public_key: &DiffieHellmanPublicKey<C>,
+    ) -> FrostResult<C, Self> {
+        let dh_shared_key = dh_key_provider.dh_shared_key(peer_dh_public_key)?;
+        let mut dh_key_bytes = serialize_dh_shared_key::<C>(&dh_shared_key)?;
+        let key = hash_to_array::<C>(
+            (C::context_string() + "pseudonym key").as_bytes(),
+            &dh_key_bytes,
+        );
+        dh_key_bytes.zeroize();
+
+        Ok(Self {
+            key: key?.as_ref().to_vec(),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Compute the [`SessionPseudonym`] of the signing session with the given `session_id`.
+    pub fn pseudonym(&self, session_id: &[u8]) -> FrostResult<C, SessionPseudonym> {
+        let mut input = self.key.clone();
+        input.extend_from_slice(session_id);
+        let digest = hash_to_array::<C>(
+            (C::context_string() + "session pseudonym").as_bytes(),
+            &input,
+        );
+        input.zeroize();
+
+        let mut bytes = [0u8; 8];
+        for (byte, digest_byte) in bytes.iter_mut().zip(digest?.as_ref().iter()) {
+            *byte = *digest_byte;
+        }
+
+        Ok(SessionPseudonym(u64::from_le_bytes(bytes)))
+    }
+}
+
+/// A published commitment share of a signer, tagged with its [`SessionPseudonym`]
+/// instead of its participant index.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PseudonymousCommitment<C: CipherSuite> {
+    /// The pseudonym of the signer for this session.
+    pub pseudonym: SessionPseudonym,
+    /// The published commitment share of the signer.
+    pub(crate) commitment: (C::G, C::G),
+}
+
+impl<C: CipherSuite> PseudonymousCommitment<C> {
+    /// Tag the given published `commitment` share with the pseudonym derived from
+    /// the signer's `pseudonym_key` for the session with the given `session_id`.
+    pub fn new(
+        pseudonym_key: &PseudonymKey<C>,
+        session_id: &[u8],
+        commitment: (C::G, C::G),
+    ) -> FrostResult<C, Self> {
+        Ok(Self {
+            pseudonym: pseudonym_key.pseudonym(session_id)?,
+            commitment,
+        })
+    }
+
+    /// Serialize this [`PseudonymousCommitment`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PseudonymousCommitment`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for PseudonymousCommitment<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        let (hiding, binding) = &self.commitment;
+        validate_points::<C>([hiding, binding])
+    }
+}
+
+/// A partial signature of a signer, tagged with its [`SessionPseudonym`]
+/// instead of its participant index.
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PseudonymousPartialSignature<C: CipherSuite> {
+    /// The pseudonym of the signer for this session.
+    pub pseudonym: SessionPseudonym,
+    pub(crate) z: Scalar<C>,
+}
+
+impl<C: CipherSuite> PseudonymousPartialSignature<C> {
+    /// Tag the given `partial_signature` with the pseudonym derived from the
+    /// signer's `pseudonym_key` for the session with the given `session_id`.
+    pub fn new(
+        pseudonym_key: &PseudonymKey<C>,
+        session_id: &[u8],
+        partial_signature: &PartialThresholdSignature<C>,
+    ) -> FrostResult<C, Self> {
+        Ok(Self {
+            pseudonym: pseudonym_key.pseudonym(session_id)?,
+            z: partial_signature.z,
+        })
+    }
+
+    /// Serialize this [`PseudonymousPartialSignature`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`PseudonymousPartialSignature`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
+    }
+}
+
+/// The mapping from the [`SessionPseudonym`]s of a signing session to the
+/// participant indices of the signers, only computable by the coordinator.
+#[derive(Clone, Debug)]
+pub struct PseudonymMap<C: CipherSuite> {
+    indices: BTreeMap<SessionPseudonym, u32>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> PseudonymMap<C> {
+    /// Compute the pseudonyms of the given `participants` for the signing session
+    /// with the given `session_id`, from the Diffie-Hellman key of the coordinator
+    /// held by the given `dh_key_provider`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PseudonymMap`], otherwise
+    /// an [`Error`] if two participants were assigned the same pseudonym, in which
+    /// case the coordinator should pick another session identifier.
+    pub fn new<R: Role<C>>(
+        dh_key_provider: &impl DhKeyProvider<C>,
+        participants: &[Participant<C, R>],
+        session_id: &[u8],
+    ) -> FrostResult<C, Self> {
+        let mut indices = BTreeMap::new();
+        for participant in participants.iter() {
+            let pseudonym = PseudonymKey::new(dh_key_provider, &participant.dh_public_key)?
+                .pseudonym(session_id)?;
+
+            if indices.insert(pseudonym, participant.index).is_some() {
+                return Err(Error::Custom(
+                    "Two participants share the same session pseudonym".to_string(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            indices,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The participant index of the signer with the given `pseudonym`.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the participant index, otherwise
+    /// an [`Error::UnknownPseudonym`] if no participant has this pseudonym.
+    pub fn participant_index(&self, pseudonym: &SessionPseudonym) -> FrostResult<C, u32> {
+        self.indices
+            .get(pseudonym)
+            .copied()
+            .ok_or(Error::UnknownPseudonym)
+    }
+
+    /// Recover the participant index of the signer of a [`PseudonymousCommitment`],
+    /// along with its published commitment share, to be included in a
+    /// [`SignatureAggregator`](super::SignatureAggregator).
+    pub fn resolve_commitment(
+        &self,
+        commitment: &PseudonymousCommitment<C>,
+    ) -> FrostResult<C, (u32, (C::G, C::G))> {
+        Ok((
+            self.participant_index(&commitment.pseudonym)?,
+            commitment.commitment,
+        ))
+    }
+
+    /// Recover the [`PartialThresholdSignature`] of the signer of a
+    /// [`PseudonymousPartialSignature`].
+    pub fn resolve_partial_signature(
+        &self,
+        partial_signature: &PseudonymousPartialSignature<C>,
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        Ok(PartialThresholdSignature {
+            index: self.participant_index(&partial_signature.pseudonym)?,
+            z: partial_signature.z,
+            epoch_tag: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keys::DiffieHellmanPrivateKey;
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::{generate_commitment_share_lists, SignatureAggregator};
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn pseudonymous_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let coordinator_dh_sk = DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut OsRng));
+        let coordinator_dh_pk = coordinator_dh_sk.dh_public_key().unwrap();
+
+        let mut participants = Vec::new();
+        let mut pseudonym_keys = Vec::new();
+        for sk in signing_keys.iter() {
+            let (participant, dh_sk) =
+                Participant::<Secp256k1Sha256, crate::dkg::SignerRole>::new_signer(
+                    &params, sk.index, OsRng,
+                )
+                .unwrap();
+            participants.push(participant);
+            pseudonym_keys.push(PseudonymKey::new(&dh_sk, &coordinator_dh_pk).unwrap());
+        }
+
+        let mut pseudonyms = Vec::new();
+        for session_id in [&b"session 1"[..], &b"session 2"[..]] {
+            let map = PseudonymMap::new(&coordinator_dh_sk, &participants, session_id).unwrap();
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+
+            let mut secret_comshares = Vec::new();
+            for (sk, pseudonym_key) in signing_keys.iter().zip(pseudonym_keys.iter()).skip(1) {
+                let (public_comshares, secret_comshare) =
+                    generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+                secret_comshares.push(secret_comshare);
+
+                // Only the pseudonymous commitment is sent over the network.
+                let commitment = PseudonymousCommitment::from_bytes(
+                    &PseudonymousCommitment::new(
+                        pseudonym_key,
+                        session_id,
+                        public_comshares.commitments[0],
+                    )
+                    .unwrap()
+                    .to_bytes()
+                    .unwrap(),
+                )
+                .unwrap();
+                pseudonyms.push(commitment.pseudonym);
+
+                let (index, commitment) = map.resolve_commitment(&commitment).unwrap();
+                assert_eq!(index, sk.index);
+                aggregator
+                    .include_signer(index, commitment, sk.into())
+                    .unwrap();
+            }
+            let signers = aggregator.get_signers().clone();
+
+            for ((sk, pseudonym_key), secret_comshare) in signing_keys
+                .iter()
+                .zip(pseudonym_keys.iter())
+                .skip(1)
+                .zip(secret_comshares.iter_mut())
+            {
+                let partial = sk
+                    .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                    .unwrap();
+                let partial =
+                    PseudonymousPartialSignature::new(pseudonym_key, session_id, &partial).unwrap();
+                aggregator
+                    .include_partial_signature(map.resolve_partial_signature(&partial).unwrap())
+                    .unwrap();
+            }
+
+            let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            assert!(threshold_signature
+                .verify(&group_key, &message_hash)
+                .is_ok());
+        }
+
+        // Pseudonyms of distinct sessions and signers cannot be linked together.
+        pseudonyms.sort_unstable();
+        pseudonyms.dedup();
+        assert_eq!(pseudonyms.len(), 4);
+
+        // Pseudonyms are only recognized by the coordinator.
+        let other_dh_sk = DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut OsRng));
+        let map = PseudonymMap::new(&other_dh_sk, &participants, b"session 1").unwrap();
+        let pseudonym = pseudonym_keys[0].pseudonym(b"session 1").unwrap();
+        assert_eq!(
+            map.participant_index(&pseudonym).unwrap_err(),
+            Error::UnknownPseudonym
+        );
+    }
+}
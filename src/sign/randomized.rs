@@ -0,0 +1,425 @@
+//! The randomized module, letting each signer contribute a randomizer to the binding
+//! factors of a signing session, to prevent the coordinator from grinding the group
+//! nonce commitment \\( R \\).
+//!
+//! With plain ICE-FROST, the group nonce commitment of a session is a public function
+//! of the message and of the published commitment shares of its signers. A coordinator
+//! holding more commitment shares than needed can hence evaluate \\( R \\) for many
+//! candidate sets of signers and commitments, and only start the session yielding a
+//! value it favours, for instance with specific bit patterns.
+//!
+//! Once the set of signers is fixed, each of them derives a [`CommitmentRandomizer`]
+//! from its secret nonces and the commitment list of the session, which the coordinator
+//! cannot predict. The randomizers of all signers are included with
+//! [`SignatureAggregator::include_randomizer`], and hashed into the binding factors by
+//! [`IndividualSigningKey::sign_randomized`].
+//!
+//! Each signer checks that its own randomizer is included unchanged before signing, so
+//! that the coordinator cannot substitute them. The randomizer derived from a commitment
+//! share is recorded in the [`SecretCommitmentShareList`], and a signer refuses to derive
+//! a second one, so that a coordinator cannot obtain randomizers for several commitment
+//! lists and pick the most favourable one.
+//!
+//! This defence only holds against a coordinator colluding with no signer. As signers
+//! do not commit to their randomizer before revealing it, and cannot check the ones of
+//! other signers, a single corrupted signer revealing its randomizer last can still
+//! grind \\( R \\) on behalf of the coordinator.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{hash_to_array, BTreeMap, Scalar, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::precomputation::{CommitmentShare, SecretCommitmentShareList};
+use super::signature::{
+    compute_rho_input_prefix, BindingFactors, Initial, PartialThresholdSignature,
+    SignatureAggregator, Signer,
+};
+
+/// A randomizer contributed by a signer to the binding factors of a signing session.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CommitmentRandomizer<C: CipherSuite> {
+    pub(crate) index: u32,
+    pub(crate) randomizer: Scalar<C>,
+}
+
+impl<C: CipherSuite> CommitmentRandomizer<C> {
+    /// The index of the signer who contributed this randomizer.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Serialize this [`CommitmentRandomizer`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`CommitmentRandomizer`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for CommitmentRandomizer<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        Ok(())
+    }
+}
+
+impl<C: CipherSuite> IndividualSigningKey<C> {
+    /// Derive the [`CommitmentRandomizer`] of this signer for the session signing the
+    /// message with the given `message_hash`, between the given `signers`.
+    ///
+    /// The randomizer is derived from the secret nonces of the commitment share with
+    /// index `my_commitment_share_index`, which must be the one used to sign, and from
+    /// the commitment list of the session. It is recorded in the commitment share,
+    /// from which no other randomizer can be derived afterwards.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`CommitmentRandomizer`] to be sent
+    /// to the coordinator, otherwise an [`Error::MissingCommitmentShares`] if the
+    /// commitment share does not exist, or an [`Error::CommitmentShareRandomized`]
+    /// if a randomizer was already derived from it.
+    pub fn commitment_randomizer(
+        &self,
+        message_hash: &[u8],
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, CommitmentRandomizer<C>> {
+        let commitment_share = my_secret_commitment_share_list
+            .commitments
+            .get_mut(my_commitment_share_index)
+            .ok_or(Error::MissingCommitmentShares)?;
+        if commitment_share.randomizer.is_some() {
+            return Err(Error::CommitmentShareRandomized);
+        }
+
+        let randomizer = self.derive_randomizer(message_hash, commitment_share, signers)?;
+        commitment_share.randomizer = Some(randomizer.randomizer);
+
+        Ok(randomizer)
+    }
+
+    /// Derive the [`CommitmentRandomizer`] of this signer from its `commitment_share`
+    /// and the commitment list of the session.
+    fn derive_randomizer(
+        &self,
+        message_hash: &[u8],
+        commitment_share: &CommitmentShare<C>,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, CommitmentRandomizer<C>> {
+        let mut randomizer_input = Vec::new();
+        commitment_share
+            .hiding
+            .secret
+            .serialize_compressed(&mut randomizer_input)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        commitment_share
+            .binding
+            .secret
+            .serialize_compressed(&mut randomizer_input)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        randomizer_input.extend(compute_rho_input_prefix(message_hash, signers)?);
+        randomizer_input.extend(&self.index.to_le_bytes()[..]);
+
+        let randomizer = C::hash_to_field(
+            (C::binding_factor_domain_tag() + "randomizer").as_bytes(),
+            &randomizer_input,
+        );
+        randomizer_input.zeroize();
+
+        Ok(CommitmentRandomizer {
+            index: self.index,
+            randomizer: randomizer?,
+        })
+    }
+
+    /// Compute an individual signer's [`PartialThresholdSignature`] as with
+    /// [`IndividualSigningKey::sign`], with the commitment `randomizers` of all
+    /// `signers` hashed into the binding factors.
+    ///
+    /// The randomizer of this signer must have been derived beforehand with
+    /// [`IndividualSigningKey::commitment_randomizer`], for the same session.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value contains a [`PartialThresholdSignature`],
+    /// otherwise an [`Error::InvalidRandomizers`] if the randomizers do not match the
+    /// signers, or if the randomizer of this signer was altered or not derived for
+    /// this session.
+    pub fn sign_randomized(
+        &self,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+        randomizers: &[CommitmentRandomizer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        let commitment_share = my_secret_commitment_share_list
+            .commitments
+            .get(my_commitment_share_index)
+            .ok_or(Error::MissingCommitmentShares)?;
+        let my_randomizer = self.derive_randomizer(message_hash, commitment_share, signers)?;
+        if commitment_share.randomizer != Some(my_randomizer.randomizer)
+            || !randomizers.contains(&my_randomizer)
+        {
+            return Err(Error::InvalidRandomizers);
+        }
+
+        let randomizers = randomizers
+            .iter()
+            .map(|randomizer| (randomizer.index, randomizer.randomizer))
+            .collect();
+
+        self.sign_inner(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+            &randomizers,
+            false,
+        )
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Include the [`CommitmentRandomizer`] of a signer, to be hashed into the binding
+    /// factors of this session.
+    ///
+    /// Once a randomizer is included, every signer must contribute one, and sign with
+    /// [`IndividualSigningKey::sign_randomized`]. The first randomizer included for a
+    /// signer wins. Its value cannot be checked by the coordinator nor by the other
+    /// signers, each of whom only checks its own.
+    pub fn include_randomizer(&mut self, randomizer: CommitmentRandomizer<C>) {
+        self.state
+            .randomizers
+            .entry(randomizer.index)
+            .or_insert(randomizer.randomizer);
+    }
+
+    /// The commitment randomizers included so far, to be sent to the signers.
+    pub fn get_randomizers(&self) -> Vec<CommitmentRandomizer<C>> {
+        self.state
+            .randomizers
+            .iter()
+            .map(|(index, randomizer)| CommitmentRandomizer {
+                index: *index,
+                randomizer: *randomizer,
+            })
+            .collect()
+    }
+}
+
+/// Compute the common prefix of the inputs to the binding factors of all `signers`,
+/// followed by the hash of their commitment `randomizers`, if any.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is the prefix, otherwise an
+/// [`Error::InvalidRandomizers`] if the randomizers are not exactly those of the
+/// `signers`.
+pub(super) fn compute_randomized_rho_input_prefix<C: CipherSuite>(
+    message: &[u8],
+    signers: &[Signer<C>],
+    randomizers: &BTreeMap<u32, Scalar<C>>,
+) -> FrostResult<C, Vec<u8>> {
+    let mut rho_input_prefix = compute_rho_input_prefix(message, signers)?;
+    if randomizers.is_empty() {
+        return Ok(rho_input_prefix);
+    }
+
+    if randomizers.len() != signers.len()
+        || signers
+            .iter()
+            .any(|signer| !randomizers.contains_key(&signer.participant_index))
+    {
+        return Err(Error::InvalidRandomizers);
+    }
+
+    let mut encoded_randomizers = Vec::new();
+    for (index, randomizer) in randomizers.iter() {
+        encoded_randomizers.extend(&index.to_le_bytes()[..]);
+        randomizer
+            .serialize_compressed(&mut encoded_randomizers)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+    }
+    rho_input_prefix.extend(
+        hash_to_array::<C>(
+            (C::context_string() + "randomizers").as_bytes(),
+            &encoded_randomizers,
+        )?
+        .as_ref(),
+    );
+
+    Ok(rho_input_prefix)
+}
+
+/// Compute the binding factors of all `signers` as with
+/// [`compute_binding_factors`](super::compute_binding_factors), with their commitment
+/// `randomizers` hashed into them, if any.
+pub(super) fn compute_randomized_binding_factors<C: CipherSuite>(
+    message: &[u8],
+    signers: &[Signer<C>],
+    randomizers: &BTreeMap<u32, Scalar<C>>,
+) -> FrostResult<C, BindingFactors<C>> {
+    let mut binding_factor_list = BindingFactors::new();
+
+    let rho_input_prefix = compute_randomized_rho_input_prefix(message, signers, randomizers)?;
+
+    for signer in signers.iter() {
+        let mut rho_input = rho_input_prefix.clone();
+        // RFC Note: identifier should be a ScalarField element that we serialize
+        rho_input.extend(&signer.participant_index.to_le_bytes()[..]);
+        let binding_factor = C::h1(&rho_input)?;
+        binding_factor_list.insert(signer.participant_index, binding_factor);
+    }
+
+    Ok(binding_factor_list)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::Field;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn randomized_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().skip(1) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+        let plain_group_nonce = aggregator.group_nonce().unwrap();
+
+        for (sk, comshares) in signing_keys.iter().skip(1).zip(secret_comshares.iter_mut()) {
+            let randomizer = sk
+                .commitment_randomizer(&message_hash, comshares, 0, &signers)
+                .unwrap();
+            assert_eq!(
+                CommitmentRandomizer::from_bytes(&randomizer.to_bytes().unwrap()).unwrap(),
+                randomizer
+            );
+            aggregator.include_randomizer(randomizer);
+        }
+
+        // A coordinator cannot obtain a second randomizer for the same commitment share,
+        // be it for the same or another commitment list.
+        assert_eq!(
+            signing_keys[1].commitment_randomizer(
+                &message_hash,
+                &mut secret_comshares[0],
+                0,
+                &signers
+            ),
+            Err(Error::CommitmentShareRandomized)
+        );
+        assert_eq!(
+            signing_keys[1].commitment_randomizer(
+                &message_hash,
+                &mut secret_comshares[0],
+                0,
+                &signers[..1]
+            ),
+            Err(Error::CommitmentShareRandomized)
+        );
+        let randomizers = aggregator.get_randomizers();
+
+        // The randomizers change the group nonce commitment.
+        let group_nonce = aggregator.group_nonce().unwrap();
+        assert_ne!(group_nonce, plain_group_nonce);
+
+        // Signers reject randomizers derived for another commitment list.
+        assert_eq!(
+            signing_keys[1].sign_randomized(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0].clone(),
+                0,
+                &signers[..1],
+                &randomizers
+            ),
+            Err(Error::InvalidRandomizers)
+        );
+
+        // Signers reject altered randomizers.
+        let mut altered_randomizers = randomizers.clone();
+        altered_randomizers[0].randomizer += Fr::ONE;
+        assert_eq!(
+            signing_keys[1].sign_randomized(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0].clone(),
+                0,
+                &signers,
+                &altered_randomizers
+            ),
+            Err(Error::InvalidRandomizers)
+        );
+        assert_eq!(
+            signing_keys[1].sign_randomized(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0].clone(),
+                0,
+                &signers,
+                &randomizers[..1]
+            ),
+            Err(Error::InvalidRandomizers)
+        );
+
+        for (sk, comshares) in signing_keys.iter().skip(1).zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign_randomized(
+                    &message_hash,
+                    &group_key,
+                    comshares,
+                    0,
+                    &signers,
+                    &randomizers,
+                )
+                .unwrap();
+            assert!(aggregator.verify_partial_signature(&partial).is_ok());
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert_eq!(
+            threshold_signature.group_commitment,
+            group_nonce.into_inner()
+        );
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+}
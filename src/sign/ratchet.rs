@@ -0,0 +1,419 @@
+//! The ratchet module for deriving the commitment shares of long-lived
+//! signers from a forward-secure chain of seeds.
+//!
+//! Instead of storing the secrets of thousands of precomputed commitment shares,
+//! a signer keeps a [`CommitmentRatchet`] holding a single seed. The commitment share
+//! at position \\( i \\) is derived from the seed \\( s\_i \\) of the chain given by
+//! \\( s\_{i+1} = H(s\_i) \\), and consuming it replaces the stored seed with
+//! \\( s\_{i+1} \\). As the hash function cannot be inverted, the compromise of the
+//! device only exposes the nonces of future sessions, never the ones of past sessions.
+//!
+//! Commitment shares must be consumed in increasing order of position: consuming a
+//! share forfeits all the previous positions which have not been consumed yet.
+//! Positions are only reachable within [`MAX_RATCHET_WINDOW`] of the current one,
+//! bounding the number of hashes needed to advance the ratchet.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{hash_to_array, vec, Scalar, ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::precomputation::{
+    CommitmentShare, NoncePair, PublicCommitmentShareList, SecretCommitmentShareList,
+};
+use super::signature::{PartialThresholdSignature, Signer};
+
+/// The maximum number of positions ahead of the current one which can be
+/// published or consumed from a [`CommitmentRatchet`].
+pub const MAX_RATCHET_WINDOW: u64 = 1 << 16;
+
+/// A forward-secure source of the commitment shares of a signer.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Zeroize)]
+pub struct CommitmentRatchet<C: CipherSuite> {
+    /// The index of the participant owning this ratchet.
+    pub(crate) participant_index: u32,
+    /// The position of the next commitment share to be consumed.
+    pub(crate) position: u64,
+    /// The seed of the commitment share at `position`.
+    pub(crate) seed: Vec<u8>,
+    #[zeroize(skip)]
+    _phantom: core::marker::PhantomData<C>,
+}
+
+impl<C: CipherSuite> Drop for CommitmentRatchet<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CipherSuite> ConstantTimeEq for CommitmentRatchet<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.participant_index.ct_eq(&other.participant_index)
+            & self.position.ct_eq(&other.position)
+            & self.seed.as_slice().ct_eq(other.seed.as_slice())
+    }
+}
+
+/// Test equality in constant-time.
+impl<C: CipherSuite> PartialEq for CommitmentRatchet<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CipherSuite> Eq for CommitmentRatchet<C> {}
+
+impl<C: CipherSuite> CommitmentRatchet<C> {
+    /// Construct a new [`CommitmentRatchet`] for the participant with the given
+    /// `signing_key`, from a freshly sampled seed.
+    pub fn new(
+        signing_key: &IndividualSigningKey<C>,
+        mut csprng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let mut seed = vec![0u8; C::HashOutput::default().as_ref().len()];
+        csprng.fill_bytes(&mut seed);
+
+        Self {
+            participant_index: signing_key.index,
+            position: 0,
+            seed,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// The position of the next commitment share to be consumed.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Compute the public commitments of the `number_of_shares` next commitment
+    /// shares, starting from the current position, without consuming them.
+    ///
+    /// At most [`MAX_RATCHET_WINDOW`] commitment shares can be published at once.
+    pub fn public_commitments(
+        &self,
+        signing_key: &IndividualSigningKey<C>,
+        number_of_shares: usize,
+    ) -> FrostResult<C, PublicCommitmentShareList<C>> {
+        self.check_signing_key(signing_key)?;
+        if number_of_shares as u64 > MAX_RATCHET_WINDOW {
+            return Err(Error::TooManyCommitmentShares(
+                number_of_shares as u64,
+                MAX_RATCHET_WINDOW,
+            ));
+        }
+
+        let mut seed = self.seed.clone();
+        let mut commitments = Vec::with_capacity(number_of_shares);
+        for _ in 0..number_of_shares {
+            commitments.push(derive_commitment_share(signing_key, &seed)?.publish());
+            seed = next_seed::<C>(seed)?;
+        }
+        seed.zeroize();
+
+        Ok(PublicCommitmentShareList {
+            participant_index: self.participant_index,
+            commitments,
+        })
+    }
+
+    /// Consume the commitment share at the given `position`, advancing the
+    /// ratchet past it and deleting the seeds of all previous positions.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`CommitmentShare`], otherwise
+    /// an [`Error::MissingCommitmentShares`] if the position was already consumed
+    /// or forfeited, or lies [`MAX_RATCHET_WINDOW`] or more positions ahead of the
+    /// current one.
+    pub fn consume(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        position: u64,
+    ) -> FrostResult<C, CommitmentShare<C>> {
+        self.check_signing_key(signing_key)?;
+        if position < self.position || position - self.position >= MAX_RATCHET_WINDOW {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        while self.position < position {
+            self.advance()?;
+        }
+        let commitment_share = derive_commitment_share(signing_key, &self.seed)?;
+        self.advance()?;
+
+        Ok(commitment_share)
+    }
+
+    /// Create a [`PartialThresholdSignature`] as with [`IndividualSigningKey::sign`],
+    /// with the commitment share at the given `position`, which is consumed.
+    pub fn sign(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        position: u64,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        let commitment_share = self.consume(signing_key, position)?;
+
+        let is_published = signers.iter().any(|signer| {
+            signer.participant_index == signing_key.index
+                && signer.published_commitment_share == commitment_share.publish()
+        });
+        if !is_published {
+            return Err(Error::MissingCommitmentShares);
+        }
+
+        let mut secret_commitment_share_list = SecretCommitmentShareList {
+            commitments: vec![commitment_share],
+        };
+        signing_key.sign(
+            message_hash,
+            group_key,
+            &mut secret_commitment_share_list,
+            0,
+            signers,
+        )
+    }
+
+    /// Serialize this [`CommitmentRatchet`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`CommitmentRatchet`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let ratchet = Self::deserialize_compressed(bytes)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        if ratchet.participant_index == 0
+            || ratchet.seed.len() != C::HashOutput::default().as_ref().len()
+        {
+            return Err(Error::DeserializationError(
+                ark_serialize::SerializationError::InvalidData.into(),
+            ));
+        }
+
+        Ok(ratchet)
+    }
+
+    fn check_signing_key(&self, signing_key: &IndividualSigningKey<C>) -> FrostResult<C, ()> {
+        if signing_key.index != self.participant_index {
+            return Err(Error::Custom(
+                "The signing key does not belong to the ratchet owner".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Replace the current seed with the next one of the chain, erasing it.
+    fn advance(&mut self) -> FrostResult<C, ()> {
+        let seed = core::mem::take(&mut self.seed);
+        self.seed = next_seed::<C>(seed)?;
+        self.position += 1;
+
+        Ok(())
+    }
+}
+
+/// Compute the seed following `seed` in the chain, erasing `seed`.
+fn next_seed<C: CipherSuite>(mut seed: Vec<u8>) -> FrostResult<C, Vec<u8>> {
+    let next = hash_to_array::<C>(
+        (C::context_string() + "commitment ratchet").as_bytes(),
+        &seed,
+    );
+    seed.zeroize();
+
+    Ok(next?.as_ref().to_vec())
+}
+
+/// Derive the commitment share of a `signing_key` from the given `seed`.
+fn derive_commitment_share<C: CipherSuite>(
+    signing_key: &IndividualSigningKey<C>,
+    seed: &[u8],
+) -> FrostResult<C, CommitmentShare<C>> {
+    let derive_nonce = |domain: &str| -> FrostResult<C, Scalar<C>> {
+        let mut nonce_input = domain.as_bytes().to_vec();
+        nonce_input.extend(seed);
+        nonce_input.extend(&signing_key.to_bytes()?);
+        let nonce = C::h3(&nonce_input);
+        nonce_input.zeroize();
+
+        nonce
+    };
+
+    Ok(CommitmentShare::from(NoncePair(
+        derive_nonce("ratchet hiding")?,
+        derive_nonce("ratchet binding")?,
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::signature::test::do_keygen;
+    use crate::sign::SignatureAggregator;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn ratchet_consumption() {
+        let signing_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Fr::rand(&mut OsRng),
+        };
+        let mut ratchet = CommitmentRatchet::new(&signing_key, OsRng);
+        let published = ratchet.public_commitments(&signing_key, 4).unwrap();
+        assert_eq!(published.participant_index, 1);
+        assert_eq!(published.commitments.len(), 4);
+
+        let share = ratchet.consume(&signing_key, 0).unwrap();
+        assert_eq!(share.publish(), published.commitments[0]);
+
+        // Restoring a persisted ratchet resumes from the same position.
+        let mut ratchet = CommitmentRatchet::from_bytes(&ratchet.to_bytes().unwrap()).unwrap();
+        assert_eq!(ratchet.position(), 1);
+        assert_eq!(
+            ratchet
+                .public_commitments(&signing_key, 3)
+                .unwrap()
+                .commitments[..],
+            published.commitments[1..]
+        );
+
+        // Skipping ahead forfeits the intermediate positions.
+        let share = ratchet.consume(&signing_key, 3).unwrap();
+        assert_eq!(share.publish(), published.commitments[3]);
+        assert_eq!(ratchet.position(), 4);
+        assert_eq!(
+            ratchet.consume(&signing_key, 0).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+        assert_eq!(
+            ratchet.consume(&signing_key, 2).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+
+        let other_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 2,
+            key: Fr::rand(&mut OsRng),
+        };
+        assert!(ratchet.public_commitments(&other_key, 1).is_err());
+
+        // Positions too far ahead cannot be published nor consumed.
+        assert!(ratchet
+            .public_commitments(&signing_key, MAX_RATCHET_WINDOW as usize + 1)
+            .is_err());
+        assert_eq!(
+            ratchet
+                .consume(&signing_key, ratchet.position() + MAX_RATCHET_WINDOW)
+                .unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+        assert_eq!(
+            ratchet.consume(&signing_key, u64::MAX).unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+        assert_eq!(ratchet.position(), 4);
+    }
+
+    #[test]
+    fn ratchet_deserialization_checks_state() {
+        let signing_key = IndividualSigningKey::<Secp256k1Sha256> {
+            index: 1,
+            key: Fr::rand(&mut OsRng),
+        };
+        let ratchet = CommitmentRatchet::new(&signing_key, OsRng);
+
+        let mut truncated = CommitmentRatchet::<Secp256k1Sha256> {
+            participant_index: 1,
+            position: 0,
+            seed: ratchet.seed[1..].to_vec(),
+            _phantom: core::marker::PhantomData,
+        };
+        assert!(
+            CommitmentRatchet::<Secp256k1Sha256>::from_bytes(&truncated.to_bytes().unwrap())
+                .is_err()
+        );
+
+        truncated.seed = ratchet.seed.clone();
+        truncated.participant_index = 0;
+        assert!(
+            CommitmentRatchet::<Secp256k1Sha256>::from_bytes(&truncated.to_bytes().unwrap())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn ratchet_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut ratchets: Vec<_> = signing_keys
+            .iter()
+            .take(2)
+            .map(|sk| CommitmentRatchet::new(sk, OsRng))
+            .collect();
+        let published: Vec<_> = signing_keys
+            .iter()
+            .zip(ratchets.iter())
+            .map(|(sk, ratchet)| ratchet.public_commitments(sk, 3).unwrap())
+            .collect();
+
+        for position in [0, 2] {
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+            for (sk, public_comshares) in signing_keys.iter().zip(published.iter()) {
+                aggregator
+                    .include_signer(sk.index, public_comshares.commitments[position], sk.into())
+                    .unwrap();
+            }
+            let signers = aggregator.get_signers().clone();
+
+            for (sk, ratchet) in signing_keys.iter().zip(ratchets.iter_mut()) {
+                let partial = ratchet
+                    .sign(sk, &message_hash, &group_key, position as u64, &signers)
+                    .unwrap();
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            assert!(threshold_signature
+                .verify(&group_key, &message_hash)
+                .is_ok());
+        }
+
+        // Consumed and forfeited commitment shares cannot be used again.
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        for (sk, public_comshares) in signing_keys.iter().zip(published.iter()) {
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[1], sk.into())
+                .unwrap();
+        }
+        let signers = aggregator.get_signers().clone();
+        assert_eq!(
+            ratchets[0]
+                .sign(&signing_keys[0], &message_hash, &group_key, 1, &signers)
+                .unwrap_err(),
+            Error::MissingCommitmentShares
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! The rerequest module defining how a signature aggregator can ask again for
+//! a missing or invalid partial signature of an ICE-FROST signing session.
+//!
+//! Signing a second time with the same commitment share on a different message,
+//! or alongside different commitments from the other signers, would reveal the
+//! secret key share of the signer. Hence signers never sign twice with the same
+//! commitment share: they record the [`PartialThresholdSignature`]s they issued
+//! in a [`SignatureShareLog`], and answer a [`ReRequest`] by sending back the
+//! exact same partial signature, only if the request is for the very same
+//! message and signers. Otherwise, they refuse with a typed [`ReRequestRefusal`].
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use core::marker::PhantomData;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::utils::{hash_to_array, BTreeMap, Vec};
+use crate::{Error, FrostResult};
+
+use super::precomputation::SecretCommitmentShareList;
+use super::signature::{Initial, PartialThresholdSignature, SignatureAggregator, Signer};
+
+/// Compute the identifier of a signing session, binding the hash of the message
+/// to be signed and the signers along with their chosen commitment shares.
+pub fn signing_session_id<C: CipherSuite>(
+    message_hash: &[u8],
+    signers: &[Signer<C>],
+) -> FrostResult<C, C::HashOutput> {
+    let mut bytes = Vec::new();
+    message_hash
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+    signers
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+
+    hash_to_array::<C>((C::context_string() + "signing session").as_bytes(), &bytes)
+}
+
+/// The reason for a signature aggregator to re-request a partial signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReRequestReason {
+    /// The partial signature was not received.
+    Missing,
+    /// The partial signature received does not verify, possibly because it
+    /// was corrupted in transit.
+    Invalid,
+}
+
+impl ReRequestReason {
+    fn to_byte(self) -> u8 {
+        match self {
+            ReRequestReason::Missing => 0,
+            ReRequestReason::Invalid => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(ReRequestReason::Missing),
+            1 => Some(ReRequestReason::Invalid),
+            _ => None,
+        }
+    }
+}
+
+/// The reason for a signer to refuse answering a [`ReRequest`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReRequestRefusal {
+    /// The request is addressed to another signer.
+    WrongSigner,
+    /// The request is not for the message and signers known to the signer,
+    /// and answering it could lead to a commitment share being reused.
+    SessionMismatch,
+    /// The signer has no record of signing in this session.
+    UnknownSession,
+}
+
+/// A request from a signature aggregator to a signer to send again its
+/// [`PartialThresholdSignature`] for a given signing session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReRequest<C: CipherSuite> {
+    /// The identifier of the signing session, as given by [`signing_session_id`].
+    pub(crate) session_id: Vec<u8>,
+    /// The index of the signer whose partial signature is requested.
+    pub(crate) signer: u32,
+    /// The reason for requesting the partial signature again.
+    pub(crate) reason: ReRequestReason,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> ReRequest<C> {
+    /// Construct a new [`ReRequest`] to `signer` for its partial signature on
+    /// `message_hash` with the given `signers`.
+    pub fn new(
+        message_hash: &[u8],
+        signers: &[Signer<C>],
+        signer: u32,
+        reason: ReRequestReason,
+    ) -> FrostResult<C, Self> {
+        Ok(Self {
+            session_id: signing_session_id(message_hash, signers)?.as_ref().to_vec(),
+            signer,
+            reason,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The identifier of the signing session.
+    pub fn session_id(&self) -> &[u8] {
+        &self.session_id
+    }
+
+    /// The index of the signer whose partial signature is requested.
+    pub fn signer(&self) -> u32 {
+        self.signer
+    }
+
+    /// The reason for requesting the partial signature again.
+    pub fn reason(&self) -> ReRequestReason {
+        self.reason
+    }
+
+    /// Serialize this [`ReRequest`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.session_id
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        self.signer
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        self.reason
+            .to_byte()
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`ReRequest`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        let mut reader = bytes;
+
+        let session_id = Vec::<u8>::deserialize_compressed(&mut reader)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+        let signer = u32::deserialize_compressed(&mut reader)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+        let reason = u8::deserialize_compressed(&mut reader)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        if session_id.len() != C::HashOutput::default().as_ref().len() || !reader.is_empty() {
+            return Err(Error::DeserializationError(
+                ark_serialize::SerializationError::InvalidData.into(),
+            ));
+        }
+
+        let reason = ReRequestReason::from_byte(reason).ok_or_else(|| {
+            Error::DeserializationError(ark_serialize::SerializationError::InvalidData.into())
+        })?;
+
+        Ok(Self {
+            session_id,
+            signer,
+            reason,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// The record kept by a signer of the [`PartialThresholdSignature`]s it issued,
+/// indexed by signing session, to answer [`ReRequest`]s without ever signing
+/// twice with the same commitment share.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignatureShareLog<C: CipherSuite> {
+    /// The index of the signer keeping this log.
+    pub(crate) index: u32,
+    /// The partial signatures issued, by session identifier.
+    pub(crate) entries: BTreeMap<Vec<u8>, PartialThresholdSignature<C>>,
+}
+
+impl<C: CipherSuite> SignatureShareLog<C> {
+    /// Construct an empty [`SignatureShareLog`] for the signer with the given `index`.
+    pub fn new(index: u32) -> Self {
+        Self {
+            index,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// The number of signing sessions recorded in this log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether this log has no recorded signing session.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget all recorded signing sessions, for instance once they are completed.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Create a [`PartialThresholdSignature`] as with [`IndividualSigningKey::sign`],
+    /// and record it in this log.
+    ///
+    /// If this signer already signed for the same message and signers, the recorded
+    /// partial signature is returned instead, and no commitment share is consumed.
+    pub fn sign(
+        &mut self,
+        signing_key: &IndividualSigningKey<C>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        if signing_key.index != self.index {
+            return Err(Error::ReRequestRefused(ReRequestRefusal::WrongSigner));
+        }
+
+        let session_id = signing_session_id(message_hash, signers)?;
+        if let Some(partial_signature) = self.entries.get(session_id.as_ref()) {
+            return Ok(partial_signature.clone());
+        }
+
+        let partial_signature = signing_key.sign(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+        )?;
+        self.entries
+            .insert(session_id.as_ref().to_vec(), partial_signature.clone());
+
+        Ok(partial_signature)
+    }
+
+    /// Answer a [`ReRequest`] for the partial signature on `message_hash` with the
+    /// given `signers`, as known to this signer.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the exact [`PartialThresholdSignature`]
+    /// issued for this session, otherwise an [`Error::ReRequestRefused`] if the
+    /// request is for another signer, for another message or set of signers, or
+    /// for a session this signer has not signed in.
+    pub fn respond(
+        &self,
+        request: &ReRequest<C>,
+        message_hash: &[u8],
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        if request.signer != self.index {
+            return Err(Error::ReRequestRefused(ReRequestRefusal::WrongSigner));
+        }
+        if signing_session_id(message_hash, signers)?.as_ref() != request.session_id {
+            return Err(Error::ReRequestRefused(ReRequestRefusal::SessionMismatch));
+        }
+
+        self.entries
+            .get(&request.session_id)
+            .cloned()
+            .ok_or(Error::ReRequestRefused(ReRequestRefusal::UnknownSession))
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Build a [`ReRequest`] to `signer` for its partial signature in this session.
+    ///
+    /// A partial signature received again replaces the previous one for the
+    /// same signer.
+    pub fn re_request(
+        &mut self,
+        signer: u32,
+        reason: ReRequestReason,
+    ) -> FrostResult<C, ReRequest<C>> {
+        let message_hash = self.message_hash()?;
+        let signers = self.get_signers().clone();
+
+        ReRequest::new(message_hash.as_ref(), &signers, signer, reason)
+    }
+
+    /// Build a [`ReRequest`] to each of the signers which have not yet contributed
+    /// their partial signature, as given by [`SignatureAggregator::get_remaining_signers`].
+    pub fn re_request_missing(&mut self) -> FrostResult<C, Vec<ReRequest<C>>> {
+        let message_hash = self.message_hash()?;
+        let signers = self.get_signers().clone();
+
+        self.get_remaining_signers()
+            .iter()
+            .map(|signer| {
+                ReRequest::new(
+                    message_hash.as_ref(),
+                    &signers,
+                    signer.participant_index,
+                    ReRequestReason::Missing,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_ff::Field;
+    use ark_secp256k1::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn re_request_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+
+        let mut logs = Vec::new();
+        let mut partials = Vec::new();
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let mut log = SignatureShareLog::new(sk.index);
+            let partial = log
+                .sign(sk, &message_hash, &group_key, comshares, 0, &signers)
+                .unwrap();
+            // Signing again in the same session returns the recorded partial signature,
+            // even though the commitment share has been consumed.
+            assert_eq!(
+                log.sign(sk, &message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap(),
+                partial
+            );
+            assert_eq!(log.len(), 1);
+            logs.push(log);
+            partials.push(partial);
+        }
+
+        // The partial signature of participant 2 is lost, and that of participant 1 corrupted.
+        let mut corrupted = partials[0].clone();
+        corrupted.z += Fr::ONE;
+        aggregator.include_partial_signature(corrupted).unwrap();
+
+        let missing = aggregator.re_request_missing().unwrap();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].signer(), 2);
+        assert_eq!(missing[0].reason(), ReRequestReason::Missing);
+        let invalid = aggregator.re_request(1, ReRequestReason::Invalid).unwrap();
+        let invalid = ReRequest::from_bytes(&invalid.to_bytes().unwrap()).unwrap();
+        assert_eq!(invalid.session_id(), missing[0].session_id());
+
+        // Signers refuse requests for another signer, or for another message.
+        assert_eq!(
+            logs[0]
+                .respond(&missing[0], &message_hash, &signers)
+                .unwrap_err(),
+            Error::ReRequestRefused(ReRequestRefusal::WrongSigner)
+        );
+        let other_hash = Secp256k1Sha256::h4(b"Another message").unwrap();
+        assert_eq!(
+            logs[1]
+                .respond(&missing[0], &other_hash, &signers)
+                .unwrap_err(),
+            Error::ReRequestRefused(ReRequestRefusal::SessionMismatch)
+        );
+        let other_request =
+            ReRequest::new(&other_hash, &signers, 2, ReRequestReason::Missing).unwrap();
+        assert_eq!(
+            logs[1]
+                .respond(&other_request, &other_hash, &signers)
+                .unwrap_err(),
+            Error::ReRequestRefused(ReRequestRefusal::UnknownSession)
+        );
+
+        for (log, request) in logs.iter().zip([&invalid, &missing[0]]) {
+            let partial = log.respond(request, &message_hash, &signers).unwrap();
+            assert_eq!(partial, partials[request.signer() as usize - 1]);
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+}
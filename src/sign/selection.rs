@@ -0,0 +1,137 @@
+//! The selection module, deterministically assigning the signers of an ICE-FROST
+//! group to the messages they are requested to sign.
+//!
+//! Schedulers dispatching signing duties, e.g. among the validators of a chain, call
+//! [`select_signers`] with the message to be signed, the indices of the members of the
+//! group and the current epoch. Each member is ranked by the hash of its index along
+//! with the message and the epoch, and the `t` first ones are selected. All nodes hence
+//! independently agree on the signers of each message, while every subset of `t`
+//! members is equally likely to be selected, spreading the load evenly over the group.
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{hash_to_array, Vec};
+use crate::{Error, FrostResult};
+
+/// Deterministically select `t` signers for the given `message` among the participants
+/// with the given `roster` indices, in the given `epoch`.
+///
+/// The selection does not depend on the order of the `roster`, e.g. as given by
+/// [`ParticipantRoster::indices`](crate::dkg::ParticipantRoster::indices).
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is the indices of the selected signers, sorted
+/// by increasing index, otherwise an [`Error::UnsupportedParameters`] if `t` is zero or
+/// larger than the `roster`, or an [`Error`] if the `roster` contains zero or duplicates.
+pub fn select_signers<C: CipherSuite>(
+    message: &[u8],
+    roster: &[u32],
+    t: u32,
+    epoch: u64,
+) -> FrostResult<C, Vec<u32>> {
+    let n = u32::try_from(roster.len()).map_err(|_| Error::UnsupportedParameters(u32::MAX, t))?;
+    if t == 0 || t > n {
+        return Err(Error::UnsupportedParameters(n, t));
+    }
+
+    let mut sorted_roster = roster.to_vec();
+    sorted_roster.sort_unstable();
+    sorted_roster.dedup();
+    if sorted_roster.len() != roster.len() {
+        return Err(Error::DuplicateIndices);
+    }
+    if sorted_roster.first() == Some(&0) {
+        return Err(Error::IndexIsZero);
+    }
+
+    let mut seed = epoch.to_le_bytes().to_vec();
+    seed.extend_from_slice(
+        hash_to_array::<C>(
+            (C::context_string() + "signer selection message").as_bytes(),
+            message,
+        )?
+        .as_ref(),
+    );
+
+    let mut ranked_roster = Vec::with_capacity(sorted_roster.len());
+    for index in sorted_roster {
+        let mut bytes = seed.clone();
+        bytes.extend_from_slice(&index.to_le_bytes());
+        let rank = hash_to_array::<C>(
+            (C::context_string() + "signer selection rank").as_bytes(),
+            &bytes,
+        )?;
+        ranked_roster.push((rank.as_ref().to_vec(), index));
+    }
+    // Ranks are unique except with negligible probability, ties being broken by index.
+    ranked_roster.sort_unstable();
+
+    let mut signers: Vec<u32> = ranked_roster
+        .into_iter()
+        .take(t as usize)
+        .map(|(_, index)| index)
+        .collect();
+    signers.sort_unstable();
+
+    Ok(signers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+
+    #[test]
+    fn select_signers_is_deterministic() {
+        let roster: Vec<u32> = (1..=10).collect();
+        let signers = select_signers::<Secp256k1Sha256>(b"duty", &roster, 4, 7).unwrap();
+
+        assert_eq!(signers.len(), 4);
+        assert!(signers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(signers.iter().all(|index| roster.contains(index)));
+
+        let mut shuffled_roster = roster.clone();
+        shuffled_roster.reverse();
+        assert_eq!(
+            select_signers::<Secp256k1Sha256>(b"duty", &shuffled_roster, 4, 7).unwrap(),
+            signers
+        );
+        assert_eq!(
+            select_signers::<Secp256k1Sha256>(b"duty", &roster, 10, 7).unwrap(),
+            roster
+        );
+    }
+
+    #[test]
+    fn select_signers_spreads_load() {
+        let roster: Vec<u32> = (1..=5).collect();
+        let mut duties = [0u32; 5];
+        for nonce in 0u32..500 {
+            let signers =
+                select_signers::<Secp256k1Sha256>(&nonce.to_le_bytes(), &roster, 2, 1).unwrap();
+            for index in signers {
+                duties[index as usize - 1] += 1;
+            }
+        }
+
+        // Each participant is expected to serve 200 duties.
+        assert!(duties.iter().all(|count| (140..260).contains(count)));
+    }
+
+    #[test]
+    fn select_signers_invalid_parameters() {
+        assert_eq!(
+            select_signers::<Secp256k1Sha256>(b"duty", &[1, 2, 3], 0, 1),
+            Err(Error::UnsupportedParameters(3, 0))
+        );
+        assert_eq!(
+            select_signers::<Secp256k1Sha256>(b"duty", &[1, 2, 3], 4, 1),
+            Err(Error::UnsupportedParameters(3, 4))
+        );
+        assert_eq!(
+            select_signers::<Secp256k1Sha256>(b"duty", &[0, 2, 3], 2, 1),
+            Err(Error::IndexIsZero)
+        );
+        assert!(select_signers::<Secp256k1Sha256>(b"duty", &[1, 2, 2], 2, 1).is_err());
+    }
+}
@@ -0,0 +1,483 @@
+//! The session store module for holding large numbers of concurrent signing
+//! sessions, each kept suspended in a compact serialized form between events.
+//!
+//! A live [`SignatureAggregator`] holds its state behind several heap allocations,
+//! which adds up for services running millions of low-rate sessions. An aggregator
+//! can instead be suspended with [`SignatureAggregator::suspend`] into a single
+//! byte string, holding its compressed state and the message to be signed, and be
+//! resumed with [`SignatureAggregator::resume`] when the next commitment share or
+//! partial signature of the session arrives.
+//!
+//! An [`AggregatorPool`] manages such suspended sessions on top of any
+//! [`SessionStorage`], by default a [`SlabStorage`] packing all of them in a
+//! single arena.
+
+use core::marker::PhantomData;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{Box, Vec};
+use crate::{Error, FrostResult};
+
+#[cfg(feature = "std")]
+use super::cache::SignerSubsetCache;
+use super::policy::SignerPolicy;
+use super::signature::{AggregatorState, Finalized, Initial, SignatureAggregator};
+
+/// The flag of a suspended session marking a dry-run session.
+const DRY_RUN_FLAG: u8 = 1;
+/// The flag of a suspended session marking a prehashed message.
+const PREHASHED_FLAG: u8 = 2;
+
+/// An identifier of a suspended session within a [`SessionStorage`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SessionHandle(pub u64);
+
+/// A storage of suspended signing sessions, each held as a byte string.
+///
+/// Besides the in-memory [`SlabStorage`], this can be implemented on top of an
+/// external key-value store, to keep suspended sessions out of memory.
+pub trait SessionStorage {
+    /// Store a new suspended session, and return its handle.
+    fn insert(&mut self, bytes: &[u8]) -> SessionHandle;
+
+    /// The suspended session with the given `handle`, if any.
+    fn get(&self, handle: SessionHandle) -> Option<&[u8]>;
+
+    /// Replace the suspended session with the given `handle`, returning whether it existed.
+    fn replace(&mut self, handle: SessionHandle, bytes: &[u8]) -> bool;
+
+    /// Remove the suspended session with the given `handle`, returning whether it existed.
+    fn remove(&mut self, handle: SessionHandle) -> bool;
+
+    /// The number of suspended sessions in this storage.
+    fn len(&self) -> usize;
+
+    /// Whether this storage holds no suspended session.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A slot of a [`SlabStorage`], locating a suspended session within its arena.
+#[derive(Clone, Copy, Debug, Default)]
+struct Slot {
+    /// The offset of the session in the arena.
+    offset: usize,
+    /// The length in bytes of the session.
+    length: usize,
+    /// The generation of this slot, incremented each time it is freed.
+    generation: u32,
+    /// Whether this slot currently holds a session.
+    occupied: bool,
+}
+
+/// A [`SessionStorage`] packing all suspended sessions in a single arena.
+///
+/// Removed or outgrown sessions leave unused bytes in the arena, which is compacted
+/// once these make up more than half of it. Handles embed the generation of their
+/// slot, so that the handle of a removed session is never resolved to a later one.
+#[derive(Clone, Debug, Default)]
+pub struct SlabStorage {
+    /// The serialized sessions, back to back.
+    arena: Vec<u8>,
+    /// The slots of the sessions, indexed by the lower half of their handles.
+    slots: Vec<Slot>,
+    /// The indices of the free slots.
+    free_slots: Vec<u32>,
+    /// The number of unused bytes in the arena.
+    wasted: usize,
+}
+
+impl SlabStorage {
+    /// Construct a new, empty [`SlabStorage`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The total size in bytes of the arena, including unused bytes.
+    pub fn arena_size(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// The occupied slot of the given `handle`, if any.
+    fn slot(&self, handle: SessionHandle) -> Option<usize> {
+        let index = (handle.0 & u64::from(u32::MAX)) as usize;
+        let generation = (handle.0 >> 32) as u32;
+
+        match self.slots.get(index) {
+            Some(slot) if slot.occupied && slot.generation == generation => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Rebuild the arena without its unused bytes, if they make up more than half of it.
+    fn maybe_compact(&mut self) {
+        if self.wasted <= self.arena.len() / 2 {
+            return;
+        }
+
+        let mut arena = Vec::with_capacity(self.arena.len() - self.wasted);
+        for slot in self.slots.iter_mut().filter(|slot| slot.occupied) {
+            let offset = arena.len();
+            arena.extend_from_slice(&self.arena[slot.offset..slot.offset + slot.length]);
+            slot.offset = offset;
+        }
+
+        self.arena = arena;
+        self.wasted = 0;
+    }
+}
+
+impl SessionStorage for SlabStorage {
+    fn insert(&mut self, bytes: &[u8]) -> SessionHandle {
+        let index = match self.free_slots.pop() {
+            Some(index) => index as usize,
+            None => {
+                self.slots.push(Slot::default());
+                self.slots.len() - 1
+            }
+        };
+
+        let slot = &mut self.slots[index];
+        slot.offset = self.arena.len();
+        slot.length = bytes.len();
+        slot.occupied = true;
+        self.arena.extend_from_slice(bytes);
+
+        SessionHandle(u64::from(slot.generation) << 32 | index as u64)
+    }
+
+    fn get(&self, handle: SessionHandle) -> Option<&[u8]> {
+        let slot = &self.slots[self.slot(handle)?];
+
+        Some(&self.arena[slot.offset..slot.offset + slot.length])
+    }
+
+    fn replace(&mut self, handle: SessionHandle, bytes: &[u8]) -> bool {
+        let index = match self.slot(handle) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let arena_length = self.arena.len();
+        let slot = &mut self.slots[index];
+        if bytes.len() <= slot.length {
+            self.arena[slot.offset..slot.offset + bytes.len()].copy_from_slice(bytes);
+            self.wasted += slot.length - bytes.len();
+        } else {
+            self.wasted += slot.length;
+            slot.offset = arena_length;
+            self.arena.extend_from_slice(bytes);
+        }
+        slot.length = bytes.len();
+
+        self.maybe_compact();
+
+        true
+    }
+
+    fn remove(&mut self, handle: SessionHandle) -> bool {
+        let index = match self.slot(handle) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let slot = &mut self.slots[index];
+        slot.occupied = false;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.wasted += slot.length;
+        self.free_slots.push(index as u32);
+
+        self.maybe_compact();
+
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len() - self.free_slots.len()
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
+    /// Suspend this signature aggregator to a compact serialized form, holding its
+    /// state and the message to be signed, until it is resumed with
+    /// [`SignatureAggregator::resume`].
+    ///
+    /// The [`SignerPolicy`], cache and metrics callback of this aggregator are not
+    /// part of the suspended form, and must be attached again after resuming it.
+    pub fn suspend(&self) -> FrostResult<C, Vec<u8>> {
+        let mut flags = 0u8;
+        if self.aggregator.dry_run {
+            flags |= DRY_RUN_FLAG;
+        }
+        if self.aggregator.prehashed {
+            flags |= PREHASHED_FLAG;
+        }
+
+        let mut bytes = Vec::with_capacity(1 + self.aggregator.message.len());
+        bytes.push(flags);
+        self.state
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+        bytes.extend_from_slice(self.aggregator.message);
+
+        Ok(bytes)
+    }
+
+    /// Resume a signature aggregator suspended with [`SignatureAggregator::suspend`].
+    ///
+    /// The message to be signed is borrowed from the given `bytes`.
+    pub fn resume(bytes: &[u8]) -> FrostResult<C, SignatureAggregator<C, Initial<'_>>> {
+        let (flags, mut reader) = match bytes.split_first() {
+            Some((flags, reader)) if flags & !(DRY_RUN_FLAG | PREHASHED_FLAG) == 0 => {
+                (*flags, reader)
+            }
+            _ => {
+                return Err(Error::DeserializationError(
+                    ark_serialize::SerializationError::InvalidData.into(),
+                ))
+            }
+        };
+
+        let state = AggregatorState::<C>::deserialize_compressed(&mut reader)
+            .map_err(|e| Error::DeserializationError(e.into()))?;
+
+        Ok(SignatureAggregator {
+            state: Box::new(state),
+            aggregator: Initial {
+                message: reader,
+                dry_run: flags & DRY_RUN_FLAG != 0,
+                prehashed: flags & PREHASHED_FLAG != 0,
+            },
+            #[cfg(feature = "std")]
+            cache: None,
+            #[cfg(feature = "std")]
+            metrics_callback: None,
+            policy: None,
+            epoch: None,
+        })
+    }
+}
+
+/// A pool of signing sessions, kept suspended in a [`SessionStorage`] between
+/// the events of each session.
+#[derive(Debug)]
+pub struct AggregatorPool<C: CipherSuite, S: SessionStorage = SlabStorage> {
+    /// The storage of the suspended sessions.
+    storage: S,
+    /// The policy restricting the signers of all sessions.
+    policy: Option<SignerPolicy>,
+    /// The cache of interpolated verification shares shared by all sessions.
+    #[cfg(feature = "std")]
+    cache: Option<SignerSubsetCache<C>>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite, S: SessionStorage> AggregatorPool<C, S> {
+    /// Construct a new [`AggregatorPool`] on top of the given `storage`, enforcing the
+    /// given [`SignerPolicy`], if any, on the signers of all its sessions.
+    pub fn new(storage: S, policy: Option<SignerPolicy>) -> Self {
+        Self {
+            storage,
+            policy,
+            #[cfg(feature = "std")]
+            cache: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Share a [`SignerSubsetCache`] with all sessions of this pool.
+    #[cfg(feature = "std")]
+    pub fn with_cache(mut self, cache: &SignerSubsetCache<C>) -> Self {
+        self.cache = Some(cache.clone());
+
+        self
+    }
+
+    /// The storage of the suspended sessions of this pool.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// The number of sessions in this pool.
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Whether this pool holds no session.
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// Suspend the given `aggregator` into this pool, and return the handle of its session.
+    pub fn open(
+        &mut self,
+        aggregator: &SignatureAggregator<C, Initial<'_>>,
+    ) -> FrostResult<C, SessionHandle> {
+        Ok(self.storage.insert(&aggregator.suspend()?))
+    }
+
+    /// Resume the session with the given `handle`, apply `f` to its aggregator, and
+    /// suspend it again.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the output of `f`, otherwise an
+    /// [`Error::UnknownSession`] if no session has this handle, or the error of `f`,
+    /// in which case the session is left unchanged.
+    pub fn update<T>(
+        &mut self,
+        handle: SessionHandle,
+        f: impl FnOnce(&mut SignatureAggregator<C, Initial<'_>>) -> FrostResult<C, T>,
+    ) -> FrostResult<C, T> {
+        let bytes = self
+            .storage
+            .get(handle)
+            .ok_or(Error::UnknownSession)?
+            .to_vec();
+        let mut aggregator = self.resume(&bytes)?;
+
+        let output = f(&mut aggregator)?;
+        self.storage.replace(handle, &aggregator.suspend()?);
+
+        Ok(output)
+    }
+
+    /// Resume and finalize the session with the given `handle`, which is removed
+    /// from this pool upon success.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the finalized aggregator, otherwise an
+    /// [`Error::UnknownSession`] if no session has this handle, or the error of
+    /// [`SignatureAggregator::finalize`], in which case the session is left unchanged.
+    pub fn finalize(
+        &mut self,
+        handle: SessionHandle,
+    ) -> FrostResult<C, SignatureAggregator<C, Finalized<C>>> {
+        let finalized = {
+            let bytes = self.storage.get(handle).ok_or(Error::UnknownSession)?;
+            self.resume(bytes)?.finalize()?
+        };
+        self.storage.remove(handle);
+
+        Ok(finalized)
+    }
+
+    /// Drop the session with the given `handle`, returning whether it existed.
+    pub fn close(&mut self, handle: SessionHandle) -> bool {
+        self.storage.remove(handle)
+    }
+
+    /// Resume a suspended session, with the policy and cache of this pool.
+    fn resume<'a>(&self, bytes: &'a [u8]) -> FrostResult<C, SignatureAggregator<C, Initial<'a>>> {
+        let mut aggregator = SignatureAggregator::resume(bytes)?;
+        aggregator.policy = self.policy.clone();
+        #[cfg(feature = "std")]
+        {
+            aggregator.cache = self.cache.clone();
+        }
+
+        Ok(aggregator)
+    }
+}
+
+impl<C: CipherSuite> Default for AggregatorPool<C, SlabStorage> {
+    fn default() -> Self {
+        Self::new(SlabStorage::new(), None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn suspended_sessions_in_aggregator_pool() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+        let message = b"Notarize this document";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut pool = AggregatorPool::<Secp256k1Sha256>::default();
+        let handles: Vec<SessionHandle> = (0..4)
+            .map(|_| {
+                pool.open(&SignatureAggregator::new(
+                    params,
+                    group_key,
+                    &message[..],
+                    None,
+                ))
+                .unwrap()
+            })
+            .collect();
+        assert_eq!(pool.len(), 4);
+
+        // The handle of a closed session must not resolve to a later one.
+        assert!(pool.close(handles[3]));
+        let prehashed =
+            SignatureAggregator::new_prehashed(params, group_key, &message_hash, None).unwrap();
+        assert_ne!(pool.open(&prehashed).unwrap(), handles[3]);
+        assert_eq!(
+            pool.update(handles[3], |_| Ok(())),
+            Err(Error::UnknownSession)
+        );
+
+        for handle in handles.iter().take(3) {
+            let mut secret_comshares = Vec::new();
+            for sk in signing_keys.iter().take(params.t as usize) {
+                let (public_comshares, secret_comshare) =
+                    generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+                pool.update(*handle, |aggregator| {
+                    aggregator
+                        .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                        .unwrap();
+                    Ok(())
+                })
+                .unwrap();
+                secret_comshares.push(secret_comshare);
+            }
+
+            let signers = pool
+                .update(*handle, |aggregator| Ok(aggregator.get_signers().clone()))
+                .unwrap();
+            assert!(pool.finalize(*handle).is_err());
+
+            for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+                let partial = sk
+                    .sign(&message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap();
+                pool.update(*handle, |aggregator| {
+                    aggregator.include_partial_signature(partial).unwrap();
+                    Ok(())
+                })
+                .unwrap();
+            }
+
+            let signature = pool.finalize(*handle).unwrap().aggregate().unwrap();
+            assert!(signature.verify(&group_key, &message_hash).is_ok());
+        }
+
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.finalize(handles[0]).err(), Some(Error::UnknownSession));
+        assert!(pool.storage().arena_size() <= 2 * prehashed.suspend().unwrap().len());
+
+        // The flags of a suspended session are preserved, and unknown flags rejected.
+        let mut bytes = SignatureAggregator::new_dry_run(params, group_key)
+            .suspend()
+            .unwrap();
+        let resumed = SignatureAggregator::<Secp256k1Sha256, Initial<'_>>::resume(&bytes).unwrap();
+        assert!(resumed.aggregator.dry_run && !resumed.aggregator.prehashed);
+        assert!(resumed.aggregator.message.is_empty());
+
+        bytes[0] |= 4;
+        assert!(SignatureAggregator::<Secp256k1Sha256, Initial<'_>>::resume(&bytes).is_err());
+    }
+}
@@ -4,21 +4,34 @@
 
 use crate::ciphersuite::CipherSuite;
 
-use ark_ec::{Group, VariableBaseMSM};
-use ark_ff::{Field, Zero};
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{Field, PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use core::cmp::Ordering;
-use core::ops::{Add, Deref, DerefMut, Mul};
+use core::ops::{Deref, DerefMut, Mul};
 
-use crate::utils::calculate_lagrange_coefficients;
-use crate::utils::{BTreeMap, Box, Scalar, Vec};
-use crate::{Error, FrostResult};
+use crate::utils::{calculate_lagrange_coefficients, points_to_affine_batch};
+use crate::utils::{vec, BTreeMap, Box, Scalar, ToString, Vec};
+use crate::validation::{deserialize_validated, validate_point, validate_points, ValidatePoints};
+use crate::{Element, Error, FrostResult};
 
-use crate::keys::{GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey};
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, IndividualVerifyingKey, KeyRandomizer};
 use crate::parameters::ThresholdParameters;
 
+use super::abort::AbortedSession;
+use super::attributed::AttributedSignature;
+use super::cache::interpolate_verification_shares;
+#[cfg(feature = "std")]
+use super::cache::SignerSubsetCache;
+use super::certificate::QuorumCertificate;
+use super::dry_run::{compute_dry_run_challenge, dry_run_message_hash};
+use super::epoch_tag::EpochTag;
+#[cfg(feature = "std")]
+use super::metrics::MetricsCallback;
+use super::policy::SignerPolicy;
 use super::precomputation::SecretCommitmentShareList;
+use super::randomized::{compute_randomized_binding_factors, compute_randomized_rho_input_prefix};
 
 /// An individual signer in the threshold signature scheme.
 #[derive(Clone, Copy, Debug, Eq, CanonicalSerialize, CanonicalDeserialize)]
@@ -32,18 +45,14 @@ pub struct Signer<C: CipherSuite> {
 
 impl<C: CipherSuite> Ord for Signer<C> {
     fn cmp(&self, other: &Signer<C>) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        // WARNING: Participants cannot have identical indices, so dedup() MUST be called.
+        self.participant_index.cmp(&other.participant_index)
     }
 }
 
 impl<C: CipherSuite> PartialOrd for Signer<C> {
     fn partial_cmp(&self, other: &Signer<C>) -> Option<Ordering> {
-        match self.participant_index.cmp(&other.participant_index) {
-            Ordering::Less => Some(Ordering::Less),
-            // WARNING: Participants cannot have identical indices, so dedup() MUST be called.
-            Ordering::Equal => Some(Ordering::Equal),
-            Ordering::Greater => Some(Ordering::Greater),
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -53,12 +62,20 @@ impl<C: CipherSuite> PartialEq for Signer<C> {
     }
 }
 
+impl<C: CipherSuite> ValidatePoints<C> for Signer<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        let (hiding, binding) = &self.published_commitment_share;
+        validate_points::<C>([hiding, binding])
+    }
+}
+
 /// A partially-constructed threshold signature, made by each participant in the
 /// signing protocol during the first phase of a signature creation.
-#[derive(Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct PartialThresholdSignature<C: CipherSuite> {
     pub(crate) index: u32,
     pub(crate) z: Scalar<C>,
+    pub(crate) epoch_tag: Option<EpochTag>,
 }
 
 impl<C: CipherSuite> PartialThresholdSignature<C> {
@@ -67,14 +84,14 @@ impl<C: CipherSuite> PartialThresholdSignature<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`PartialThresholdSignature`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        Self::deserialize_compressed(bytes).map_err(|e| Error::DeserializationError(e.into()))
     }
 }
 
@@ -91,22 +108,29 @@ impl<C: CipherSuite> ThresholdSignature<C> {
         let mut bytes = Vec::new();
 
         self.serialize_compressed(&mut bytes)
-            .map_err(|_| Error::SerializationError)?;
+            .map_err(|e| Error::SerializationError(e.into()))?;
 
         Ok(bytes)
     }
 
     /// Attempt to deserialize a [`ThresholdSignature`] from a vector of bytes.
     pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
-        Self::deserialize_compressed(bytes).map_err(|_| Error::DeserializationError)
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for ThresholdSignature<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        validate_point::<C>(&self.group_commitment)
     }
 }
 
 /// A struct for storing signers' binding factors with their index.
 #[derive(Debug, Default, CanonicalSerialize, CanonicalDeserialize)]
-struct BindingFactors<C: CipherSuite>(pub(crate) BTreeMap<u32, Scalar<C>>);
+pub struct BindingFactors<C: CipherSuite>(pub(crate) BTreeMap<u32, Scalar<C>>);
 
 impl<C: CipherSuite> BindingFactors<C> {
+    /// Construct an empty list of binding factors.
     pub fn new() -> Self {
         Self(BTreeMap::new())
     }
@@ -176,30 +200,65 @@ impl<C: CipherSuite> DerefMut for IndividualPublicKeys<C> {
     }
 }
 
-fn encode_group_commitment_list<C: CipherSuite>(commitment_list: &[(u32, C::G, C::G)]) -> Vec<u8> {
+pub(super) fn encode_group_commitment_list<C: CipherSuite>(
+    commitment_list: &[(u32, C::G, C::G)],
+) -> FrostResult<C, Vec<u8>> {
     let mut encoded_group_commitment =
-        Vec::with_capacity(commitment_list.len() * 2 * commitment_list[0].1.compressed_size());
+        Vec::with_capacity(commitment_list.len() * (4 + 2 * C::G::zero().compressed_size()));
     for (identifier, hiding_nonce_commitment, binding_nonce_commitment) in commitment_list.iter() {
         // RFC Note: identifier should be a ScalarField element that we serialize
         encoded_group_commitment.extend(&identifier.to_le_bytes()[..]);
         hiding_nonce_commitment
             .serialize_compressed(&mut encoded_group_commitment)
-            .unwrap();
+            .map_err(|e| Error::CompressionError(e.into()))?;
         binding_nonce_commitment
             .serialize_compressed(&mut encoded_group_commitment)
-            .unwrap();
+            .map_err(|e| Error::CompressionError(e.into()))?;
     }
 
-    encoded_group_commitment
+    Ok(encoded_group_commitment)
+}
+
+/// Check that the `signers` are in the canonical order of the commitment lists, that is
+/// sorted by strictly increasing participant index, as the binding factors depend on it.
+pub(super) fn check_canonical_ordering<C: CipherSuite>(
+    signers: &[Signer<C>],
+) -> FrostResult<C, ()> {
+    match signers
+        .windows(2)
+        .any(|pair| pair[0].participant_index >= pair[1].participant_index)
+    {
+        true => Err(Error::UnsortedSigners),
+        false => Ok(()),
+    }
 }
 
-fn compute_binding_factors<C: CipherSuite>(
+/// Compute the common prefix of the inputs to the binding factors of all `signers`.
+///
+/// The `signers` must be in canonical order, otherwise an [`Error::UnsortedSigners`]
+/// is returned.
+pub(super) fn compute_rho_input_prefix<C: CipherSuite>(
     message: &[u8],
     signers: &[Signer<C>],
-) -> FrostResult<C, BindingFactors<C>> {
-    let mut binding_factor_list = BindingFactors::new();
+) -> FrostResult<C, Vec<u8>> {
+    let encoded_comm_hash = compute_commitment_list_hash(signers)?;
 
     let mut msg_hash = C::h4(message)?.as_ref().to_vec();
+    // [`extend`] operates in place, hence msg_hash is now equal to [`rho_input_prefix`] .
+    msg_hash.extend(encoded_comm_hash);
+
+    Ok(msg_hash)
+}
+
+/// Compute the hash of the encoded published commitment shares of all `signers`,
+/// which ends the inputs to their binding factors and does not depend on the message.
+///
+/// The `signers` must be in canonical order, otherwise an [`Error::UnsortedSigners`]
+/// is returned.
+pub(super) fn compute_commitment_list_hash<C: CipherSuite>(
+    signers: &[Signer<C>],
+) -> FrostResult<C, Vec<u8>> {
+    check_canonical_ordering(signers)?;
 
     let mut commitment_list = Vec::with_capacity(signers.len());
     for signer in signers.iter() {
@@ -209,59 +268,47 @@ fn compute_binding_factors<C: CipherSuite>(
         commitment_list.push((signer.participant_index, hiding, binding));
     }
 
-    let encoded_comm_hash = C::h5(&encode_group_commitment_list::<C>(&commitment_list))?;
-    // [`extend`] operates in place, hence msg_hash is now equal to [`rho_input_prefix`] .
-    msg_hash.extend(encoded_comm_hash.as_ref());
-
-    for (identifier, _, _) in commitment_list.iter() {
-        let mut rho_input = msg_hash.clone();
-        // RFC Note: identifier should be a ScalarField element that we serialize
-        rho_input.extend(&identifier.to_le_bytes()[..]);
-        let binding_factor = C::h1(&rho_input)?;
-        binding_factor_list.insert(*identifier, binding_factor);
-    }
+    Ok(
+        C::h5(&encode_group_commitment_list::<C>(&commitment_list)?)?
+            .as_ref()
+            .to_vec(),
+    )
+}
 
-    Ok(binding_factor_list)
+/// Compute the binding factors of all `signers` of the message with the given
+/// hash, from their published commitment shares.
+pub fn compute_binding_factors<C: CipherSuite>(
+    message: &[u8],
+    signers: &[Signer<C>],
+) -> FrostResult<C, BindingFactors<C>> {
+    compute_randomized_binding_factors(message, signers, &BTreeMap::new())
 }
 
-fn binding_factor_for_participant<C: CipherSuite>(
+/// Retrieve the binding factor of the participant with the given index, or an
+/// [`Error::InvalidBindingFactor`] if it is not among the signers.
+pub(super) fn binding_factor_for_participant<C: CipherSuite>(
     participant_index: u32,
     binding_factor_list: &BTreeMap<u32, Scalar<C>>,
-) -> Scalar<C> {
-    for (i, binding_factor) in binding_factor_list.iter() {
-        if participant_index == *i {
-            return *binding_factor;
-        }
-    }
-
-    panic!()
+) -> FrostResult<C, Scalar<C>> {
+    binding_factor_list
+        .get(&participant_index)
+        .copied()
+        .ok_or(Error::InvalidBindingFactor)
 }
 
-fn commitment_for_participant<C: CipherSuite>(
+pub(super) fn commitment_for_participant<C: CipherSuite>(
     participant_index: u32,
     message: &[u8],
     signers: &[Signer<C>],
+    randomizers: &BTreeMap<u32, Scalar<C>>,
 ) -> FrostResult<C, C::G> {
-    let mut msg_hash = C::h4(message)?.as_ref().to_vec();
-
-    let mut commitment_list = Vec::with_capacity(signers.len());
-    let (mut participant_hiding, mut participant_binding) = (C::G::zero(), C::G::zero());
-    for signer in signers.iter() {
-        let hiding = signer.published_commitment_share.0;
-        let binding = signer.published_commitment_share.1;
+    let (participant_hiding, participant_binding) = signers
+        .iter()
+        .find(|signer| signer.participant_index == participant_index)
+        .map(|signer| signer.published_commitment_share)
+        .unwrap_or((C::G::zero(), C::G::zero()));
 
-        commitment_list.push((signer.participant_index, hiding, binding));
-
-        if participant_index == signer.participant_index {
-            (participant_hiding, participant_binding) = signer.published_commitment_share;
-        }
-    }
-
-    let encoded_comm_hash = C::h5(&encode_group_commitment_list::<C>(&commitment_list))?;
-    // [`extend`] operates in place, hence msg_hash is now equal to [`rho_input_prefix`] .
-    msg_hash.extend(encoded_comm_hash.as_ref());
-
-    let mut rho_input = msg_hash.clone();
+    let mut rho_input = compute_randomized_rho_input_prefix(message, signers, randomizers)?;
     // RFC Note: identifier should be a ScalarField element that we serialize
     rho_input.extend(&participant_index.to_le_bytes()[..]);
     let binding_factor = C::h1(&rho_input)?;
@@ -269,23 +316,61 @@ fn commitment_for_participant<C: CipherSuite>(
     Ok(participant_hiding + participant_binding.mul(binding_factor))
 }
 
-fn compute_group_commitment<C: CipherSuite>(
+pub(super) fn compute_group_commitment<C: CipherSuite>(
     signers: &[Signer<C>],
     binding_factor_list: &BTreeMap<u32, Scalar<C>>,
-) -> C::G {
-    let mut group_commitment = C::G::zero();
+) -> FrostResult<C, C::G> {
+    let mut hiding_nonce_commitments = C::G::zero();
+    let mut binding_nonce_commitments = Vec::with_capacity(signers.len());
+    let mut binding_factors = Vec::with_capacity(signers.len());
 
     for signer in signers.iter() {
-        let hiding_nonce_commitment = signer.published_commitment_share.0;
-        let binding_nonce_commitment = signer.published_commitment_share.1;
+        hiding_nonce_commitments += signer.published_commitment_share.0;
+        binding_nonce_commitments.push(signer.published_commitment_share.1);
+        binding_factors.push(binding_factor_for_participant::<C>(
+            signer.participant_index,
+            binding_factor_list,
+        )?);
+    }
+
+    // The binding nonce commitments are weighted in a single multi-scalar multiplication,
+    // which dominates the cost of aggregating signatures of large groups.
+    let binding_nonce_commitments = points_to_affine_batch::<C>(&binding_nonce_commitments);
+
+    Ok(hiding_nonce_commitments + C::msm(&binding_nonce_commitments, &binding_factors)?)
+}
 
-        let binding_factor =
-            binding_factor_for_participant::<C>(signer.participant_index, binding_factor_list);
-        group_commitment +=
-            hiding_nonce_commitment.add(binding_nonce_commitment.mul(binding_factor));
+/// Check that a pre-hashed `message_hash` has the length of the `HashOutput`
+/// of this [`CipherSuite`].
+fn check_message_hash_length<C: CipherSuite>(message_hash: &[u8]) -> FrostResult<C, ()> {
+    let expected = C::HashOutput::default().as_ref().len();
+    if message_hash.len() != expected {
+        return Err(Error::InvalidMessageHashLength(
+            message_hash.len(),
+            expected,
+        ));
     }
 
-    group_commitment
+    Ok(())
+}
+
+/// Compute the group nonce commitment \\( R \\) of a signing session, from the
+/// published commitment shares of its `signers` and their `binding_factors`, as given
+/// by [`compute_binding_factors`].
+///
+/// This allows a coordinator to publish \\( R \\) before any partial signature is
+/// received, and to check the partial signatures against it as they arrive, with
+/// [`SignatureAggregator::verify_partial_signature`].
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is the group nonce commitment, otherwise an
+/// [`Error::InvalidBindingFactor`] if a signer has no binding factor.
+pub fn compute_group_nonce<C: CipherSuite>(
+    signers: &[Signer<C>],
+    binding_factors: &BindingFactors<C>,
+) -> FrostResult<C, Element<C>> {
+    Ok(Element(compute_group_commitment(signers, binding_factors)?))
 }
 
 pub(crate) fn compute_challenge<C: CipherSuite>(
@@ -293,16 +378,22 @@ pub(crate) fn compute_challenge<C: CipherSuite>(
     group_key: &GroupVerifyingKey<C>,
     message_hash: &[u8],
 ) -> FrostResult<C, Scalar<C>> {
-    let mut challenge_input = Vec::new();
-    group_commitment
-        .serialize_compressed(&mut challenge_input)
-        .map_err(|_| Error::CompressionError)?;
-    group_key
-        .serialize_compressed(&mut challenge_input)
-        .map_err(|_| Error::CompressionError)?;
-    challenge_input.extend(message_hash);
-
-    Ok(C::h2(&challenge_input).unwrap())
+    C::challenge(group_commitment, &group_key.key, message_hash)
+}
+
+/// Compute the challenge of a signature as with [`compute_challenge`], or under
+/// the reserved domain of dry-run sessions if `dry_run` is set.
+pub(crate) fn compute_session_challenge<C: CipherSuite>(
+    group_commitment: &C::G,
+    group_key: &GroupVerifyingKey<C>,
+    message_hash: &[u8],
+    dry_run: bool,
+) -> FrostResult<C, Scalar<C>> {
+    if dry_run {
+        return compute_dry_run_challenge(group_commitment, group_key, message_hash);
+    }
+
+    compute_challenge(group_commitment, group_key, message_hash)
 }
 
 impl<C: CipherSuite> IndividualSigningKey<C> {
@@ -322,7 +413,7 @@ impl<C: CipherSuite> IndividualSigningKey<C> {
     ///
     /// The secret share `index` here **must** be the same secret share
     /// corresponding to its public commitment which is passed to
-    /// [`SignatureAggregrator.include_signer()`] .
+    /// [`SignatureAggregator::include_signer`].
     ///
     /// # Returns
     ///
@@ -335,25 +426,105 @@ impl<C: CipherSuite> IndividualSigningKey<C> {
         my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
         my_commitment_share_index: usize,
         signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        self.sign_inner(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+            &BTreeMap::new(),
+            false,
+        )
+    }
+
+    /// Compute the [`PartialThresholdSignature`] of this signer as with
+    /// [`IndividualSigningKey::sign`], for the usage `domain` of the group with
+    /// the given root `group_key`.
+    ///
+    /// All signers apply the tweak of the `domain` to their share and to the group
+    /// key, hence the signature aggregator must be instantiated with the derived
+    /// [`GroupVerifyingKey::domain`] and the verifying keys of
+    /// [`PublicKeyPackage::domain`](crate::keys::PublicKeyPackage::domain).
+    pub fn sign_in_domain(
+        &self,
+        domain: &str,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        self.domain(group_key, domain)?.sign(
+            message_hash,
+            &group_key.domain(domain)?,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+        )
+    }
+
+    /// Compute the [`PartialThresholdSignature`] of this signer as with
+    /// [`IndividualSigningKey::sign`], under the group key obtained by randomizing
+    /// the given `group_key` with the given `randomizer`.
+    ///
+    /// All signers apply the `randomizer` to their share and to the group key, hence
+    /// the signature aggregator must be instantiated with the randomized
+    /// [`GroupVerifyingKey::randomize`] and the verifying keys of
+    /// [`PublicKeyPackage::randomize`](crate::keys::PublicKeyPackage::randomize).
+    pub fn sign_with_randomized_key(
+        &self,
+        randomizer: &KeyRandomizer<C>,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+    ) -> FrostResult<C, PartialThresholdSignature<C>> {
+        self.randomize(randomizer).sign(
+            message_hash,
+            &group_key.randomize(randomizer),
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+        )
+    }
+
+    /// Compute the [`PartialThresholdSignature`] of this signer as with
+    /// [`IndividualSigningKey::sign`], with the given commitment `randomizers`
+    /// hashed into the binding factors, if any, and the challenge of dry-run
+    /// sessions if `dry_run` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn sign_inner(
+        &self,
+        message_hash: &[u8],
+        group_key: &GroupVerifyingKey<C>,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList<C>,
+        my_commitment_share_index: usize,
+        signers: &[Signer<C>],
+        randomizers: &BTreeMap<u32, Scalar<C>>,
+        dry_run: bool,
     ) -> FrostResult<C, PartialThresholdSignature<C>> {
         if my_commitment_share_index + 1 > my_secret_commitment_share_list.commitments.len() {
             return Err(Error::MissingCommitmentShares);
         }
 
-        let binding_factor_list = compute_binding_factors(message_hash, signers)?;
-        let binding_factor = binding_factor_for_participant::<C>(self.index, &binding_factor_list);
+        let binding_factor_list =
+            compute_randomized_binding_factors(message_hash, signers, randomizers)?;
+        let binding_factor = binding_factor_for_participant::<C>(self.index, &binding_factor_list)?;
 
-        let group_commitment = compute_group_commitment(signers, &binding_factor_list);
+        let group_commitment = compute_group_commitment(signers, &binding_factor_list)?;
 
         let all_participant_indices: Vec<u32> =
             signers.iter().map(|x| x.participant_index).collect();
         let lambda: Scalar<C> =
-            calculate_lagrange_coefficients::<C>(self.index, &all_participant_indices).unwrap();
+            calculate_lagrange_coefficients::<C>(self.index, &all_participant_indices)?;
 
         let my_commitment_share =
             my_secret_commitment_share_list.commitments[my_commitment_share_index].clone();
 
-        let challenge = compute_challenge::<C>(&group_commitment, group_key, message_hash).unwrap();
+        let challenge =
+            compute_session_challenge::<C>(&group_commitment, group_key, message_hash, dry_run)?;
 
         let z = my_commitment_share.hiding.secret
             + (my_commitment_share.binding.secret * binding_factor)
@@ -361,10 +532,16 @@ impl<C: CipherSuite> IndividualSigningKey<C> {
 
         // Zero out our secrets from memory to prevent nonce reuse.
         my_secret_commitment_share_list.drop_share(my_commitment_share);
+        trace!(
+            debug,
+            "ice-frost: signer {} produced a partial signature",
+            self.index
+        );
 
         Ok(PartialThresholdSignature {
             index: self.index,
             z,
+            epoch_tag: None,
         })
     }
 }
@@ -386,6 +563,41 @@ pub(crate) struct AggregatorState<C: CipherSuite> {
     pub(crate) partial_signatures: PartialThresholdSignatures<C>,
     /// The group public key for all the participants.
     pub(crate) group_key: GroupVerifyingKey<C>,
+    /// The commitment randomizers contributed by the signers, if any.
+    pub(crate) randomizers: BTreeMap<u32, Scalar<C>>,
+}
+
+impl<C: CipherSuite> AggregatorState<C> {
+    /// Check the partial signature `z` of the signer with index `participant_index`
+    /// against its verification share interpolated in the current subset of signers,
+    /// and its published commitment share.
+    ///
+    /// The `challenge` must be the challenge of the session.
+    pub(super) fn verify_partial_signature(
+        &self,
+        participant_index: u32,
+        z: &Scalar<C>,
+        message_hash: &[u8],
+        challenge: Scalar<C>,
+        interpolated_share: Option<&C::G>,
+    ) -> bool {
+        let interpolated_share = match interpolated_share {
+            Some(interpolated_share) => interpolated_share,
+            None => return false,
+        };
+
+        let participant_commitment = match commitment_for_participant(
+            participant_index,
+            message_hash,
+            &self.signers,
+            &self.randomizers,
+        ) {
+            Ok(commitment) => commitment,
+            Err(_) => return false,
+        };
+
+        C::G::generator() * z == participant_commitment + interpolated_share.mul(challenge)
+    }
 }
 
 /// A signature aggregator is an untrusted party who coalesces all of the
@@ -399,6 +611,44 @@ pub struct SignatureAggregator<C: CipherSuite, A: Aggregator> {
     pub(crate) state: Box<AggregatorState<C>>,
     /// The aggregator's additional state.
     pub(crate) aggregator: A,
+    /// The cache of interpolated verification shares shared with other aggregators.
+    #[cfg(feature = "std")]
+    pub(crate) cache: Option<SignerSubsetCache<C>>,
+    /// The callback receiving the metrics of each aggregation.
+    #[cfg(feature = "std")]
+    pub(crate) metrics_callback: Option<MetricsCallback>,
+    /// The policy restricting the signers of the session.
+    pub(crate) policy: Option<SignerPolicy>,
+    /// The key epoch expected from the tagged partial signatures of the session.
+    pub(crate) epoch: Option<u64>,
+}
+
+impl<C: CipherSuite, A: Aggregator> SignatureAggregator<C, A> {
+    /// Share a [`SignerSubsetCache`] with this signature aggregator, to reuse the
+    /// interpolated verification shares of recurring subsets of signers when
+    /// partial signatures must be individually verified.
+    #[cfg(feature = "std")]
+    pub fn with_cache(mut self, cache: &SignerSubsetCache<C>) -> Self {
+        self.cache = Some(cache.clone());
+
+        self
+    }
+
+    /// Compute the verification shares of the signers with the sorted and deduplicated
+    /// `indices`, interpolated with their Lagrange coefficients in this subset.
+    pub(super) fn interpolated_verification_shares(&self, indices: &[u32]) -> Vec<Option<C::G>> {
+        let public_keys: Vec<Option<C::G>> = indices
+            .iter()
+            .map(|index| self.state.public_keys.get(index).copied())
+            .collect();
+
+        #[cfg(feature = "std")]
+        if let Some(cache) = &self.cache {
+            return cache.get_or_compute(indices, public_keys).to_vec();
+        }
+
+        interpolate_verification_shares::<C>(indices, &public_keys)
+    }
 }
 
 /// The initial state for a [`SignatureAggregator`], which may include invalid
@@ -407,6 +657,10 @@ pub struct SignatureAggregator<C: CipherSuite, A: Aggregator> {
 pub struct Initial<'sa> {
     /// The message to be signed.
     pub(crate) message: &'sa [u8],
+    /// Whether this is a dry-run session, signing a reserved message instead.
+    pub(crate) dry_run: bool,
+    /// Whether the message is already the hash to be signed.
+    pub(crate) prehashed: bool,
 }
 
 impl Aggregator for Initial<'_> {}
@@ -428,6 +682,8 @@ impl Aggregator for Initial<'_> {}
 pub struct Finalized<C: CipherSuite> {
     /// The hashed context and message for signing.
     pub(crate) message_hash: C::HashOutput,
+    /// Whether this is a dry-run session, signing under the reserved dry-run domain.
+    pub(crate) dry_run: bool,
 }
 
 impl<C: CipherSuite> Aggregator for Finalized<C> {}
@@ -440,7 +696,8 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
     ///
     /// * The [`ThresholdParameters`] for this threshold signing operation,
     /// * The public [`GroupVerifyingKey`] for the intended sets of signers,
-    /// * The `message` to be signed.
+    /// * The `message` to be signed,
+    /// * An optional [`SignerPolicy`] restricting the signers of this session.
     ///
     /// # Notes
     ///
@@ -454,6 +711,7 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
         parameters: ThresholdParameters<C>,
         group_key: GroupVerifyingKey<C>,
         message: &[u8],
+        policy: Option<SignerPolicy>,
     ) -> SignatureAggregator<C, Initial<'_>> {
         let signers: Vec<Signer<C>> = Vec::with_capacity(parameters.t as usize);
         let public_keys = IndividualPublicKeys::<C>::new();
@@ -464,14 +722,69 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
             public_keys,
             partial_signatures,
             group_key,
+            randomizers: BTreeMap::new(),
         };
 
         SignatureAggregator {
             state: Box::new(state),
-            aggregator: Initial { message },
+            aggregator: Initial {
+                message,
+                dry_run: false,
+                prehashed: false,
+            },
+            #[cfg(feature = "std")]
+            cache: None,
+            #[cfg(feature = "std")]
+            metrics_callback: None,
+            policy,
+            epoch: None,
         }
     }
 
+    /// Construct a new signature aggregator for a dry-run signing session of the group
+    /// with the given `parameters` and `group_key`.
+    ///
+    /// The signers of a dry-run session sign a reserved message under a dedicated domain,
+    /// with ephemeral commitment shares, through [`IndividualSigningKey::dry_run_sign`].
+    /// The resulting [`ThresholdSignature`] is only valid under the dry-run domain, and can
+    /// be checked with [`ThresholdSignature::verify_dry_run`].
+    pub fn new_dry_run(
+        parameters: ThresholdParameters<C>,
+        group_key: GroupVerifyingKey<C>,
+    ) -> SignatureAggregator<C, Initial<'static>> {
+        let mut aggregator = SignatureAggregator::new(parameters, group_key, &[], None);
+        aggregator.aggregator.dry_run = true;
+
+        aggregator
+    }
+
+    /// Construct a new signature aggregator from some protocol instantiation
+    /// `parameters` and the `message_hash` to be signed.
+    ///
+    /// Unlike [`SignatureAggregator::new`], the `message_hash` is signed as is, instead
+    /// of being hashed with [`CipherSuite::h4`]. It must hence have the length of this
+    /// [`CipherSuite`]'s `HashOutput`, and be computed by a hash function with its own
+    /// domain separation, as done for instance by the `interop::eip712` module.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`SignatureAggregator`], otherwise
+    /// an [`Error::InvalidMessageHashLength`] if the `message_hash` does not have the
+    /// length of this [`CipherSuite`]'s `HashOutput`.
+    pub fn new_prehashed(
+        parameters: ThresholdParameters<C>,
+        group_key: GroupVerifyingKey<C>,
+        message_hash: &[u8],
+        policy: Option<SignerPolicy>,
+    ) -> FrostResult<C, SignatureAggregator<C, Initial<'_>>> {
+        check_message_hash_length::<C>(message_hash)?;
+
+        let mut aggregator = SignatureAggregator::new(parameters, group_key, message_hash, policy);
+        aggregator.aggregator.prehashed = true;
+
+        Ok(aggregator)
+    }
+
     /// Include a signer in the protocol.
     ///
     /// # Warning
@@ -481,26 +794,54 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
     /// [`SignatureAggregator.include_partial_signature`], otherwise the signing
     /// procedure will fail.
     ///
-    /// # Panics
+    /// # Returns
     ///
-    /// If the [`signer.participant_index`] doesn't match the [`public_key.index`] .
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signer was included,
+    /// otherwise an [`Error::IndexIsZero`] or an [`Error::UnknownParticipant`] if the
+    /// `participant_index` is not part of the group, an [`Error::SignerKeyMismatch`] if
+    /// the `public_key` is for another participant, or an [`Error::ForbiddenSigner`] if
+    /// the signer is rejected by the [`SignerPolicy`] of this aggregator.
     pub fn include_signer(
         &mut self,
         participant_index: u32,
         published_commitment_share: (C::G, C::G),
         public_key: IndividualVerifyingKey<C>,
-    ) {
-        assert_eq!(participant_index, public_key.index,
-                   "Tried to add signer with participant index {}, but public key is for participant with index {}",
-                   participant_index, public_key.index);
+    ) -> FrostResult<C, ()> {
+        if participant_index == 0 {
+            return Err(Error::IndexIsZero);
+        }
+        if participant_index > self.state.parameters.n {
+            return Err(Error::UnknownParticipant(participant_index));
+        }
+        if participant_index != public_key.index {
+            return Err(Error::SignerKeyMismatch(
+                participant_index,
+                public_key.index,
+            ));
+        }
+        self.check_signer_policy(participant_index)?;
 
-        self.state.signers.push(Signer {
-            participant_index,
-            published_commitment_share,
-        });
+        // Keep the signers in canonical order, the first commitment share and
+        // public key included for a participant taking precedence.
+        if let Err(position) = self
+            .state
+            .signers
+            .binary_search_by_key(&participant_index, |signer| signer.participant_index)
+        {
+            self.state.signers.insert(
+                position,
+                Signer {
+                    participant_index,
+                    published_commitment_share,
+                },
+            );
+        }
         self.state
             .public_keys
-            .insert(public_key.index, public_key.share);
+            .entry(public_key.index)
+            .or_insert(public_key.share);
+
+        Ok(())
     }
 
     /// Get the list of partipating signers.
@@ -509,15 +850,41 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
     ///
     /// A `&Vec<Signer>` of the participating signers in this round.
     pub fn get_signers(&'_ mut self) -> &'_ Vec<Signer<C>> {
+        // [`SignatureAggregator::include_signer`] only includes distinct participants
+        // of the group, hence there are at most `n` signers.
         self.state.signers.sort();
         self.state.signers.dedup();
 
-        // Sanity check
-        assert!(self.state.signers.len() <= self.state.parameters.n as usize);
-
         &self.state.signers
     }
 
+    /// Encode the message hash and the commitment list of this session canonically,
+    /// as hashed into the binding factors of the signers.
+    ///
+    /// The encoding is the message hash, followed by the participant index and the
+    /// compressed hiding and binding commitments of each signer, by increasing index.
+    /// Implementations computing different bytes for the same session would disagree
+    /// on the binding factors, and hence on the validity of the partial signatures.
+    pub fn canonical_signing_package_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = self.message_hash()?.as_ref().to_vec();
+        if self.state.signers.is_empty() {
+            return Ok(bytes);
+        }
+
+        let commitment_list: Vec<(u32, C::G, C::G)> = self
+            .state
+            .signers
+            .iter()
+            .map(|signer| {
+                let (hiding, binding) = signer.published_commitment_share;
+                (signer.participant_index, hiding, binding)
+            })
+            .collect();
+        bytes.extend(encode_group_commitment_list::<C>(&commitment_list)?);
+
+        Ok(bytes)
+    }
+
     /// Helper function to get the remaining signers who were expected to sign,
     /// but have not yet contributed their [`PartialThresholdSignature`]s.
     ///
@@ -548,11 +915,100 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
         remaining_signers
     }
 
+    /// Compute the group nonce commitment \\( R \\) of this session, from the
+    /// commitment shares of the signers included so far.
+    ///
+    /// See [`compute_group_nonce`].
+    pub fn group_nonce(&mut self) -> FrostResult<C, Element<C>> {
+        let message_hash = self.message_hash()?;
+        self.get_signers();
+        let binding_factors = compute_randomized_binding_factors(
+            message_hash.as_ref(),
+            &self.state.signers,
+            &self.state.randomizers,
+        )?;
+
+        compute_group_nonce(&self.state.signers, &binding_factors)
+    }
+
+    /// Verify a [`PartialThresholdSignature`] against the group nonce commitment
+    /// of this session, and the public key and published commitment share of its
+    /// signer, before all partial signatures are received.
+    ///
+    /// The set of signers must be complete, as the group nonce commitment and the
+    /// Lagrange coefficient of the signer depend on it.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the partial signature is
+    /// valid, otherwise an [`Error::MisbehavingParticipants`] listing its signer, or the
+    /// error of [`SignatureAggregator::include_partial_signature`] if it is not tagged with
+    /// the expected key epoch of this session.
+    pub fn verify_partial_signature(
+        &mut self,
+        partial_signature: &PartialThresholdSignature<C>,
+    ) -> FrostResult<C, ()> {
+        self.check_epoch_tag(partial_signature)?;
+
+        let message_hash = self.message_hash()?;
+        let group_nonce = self.group_nonce()?;
+        let challenge = compute_session_challenge::<C>(
+            &group_nonce.0,
+            &self.state.group_key,
+            message_hash.as_ref(),
+            self.aggregator.dry_run,
+        )?;
+
+        let all_participant_indices: Vec<u32> = self
+            .state
+            .signers
+            .iter()
+            .map(|x| x.participant_index)
+            .collect();
+        let position = all_participant_indices
+            .binary_search(&partial_signature.index)
+            .map_err(|_| Error::MisbehavingParticipants(vec![partial_signature.index]))?;
+        let interpolated_shares = self.interpolated_verification_shares(&all_participant_indices);
+
+        match self.state.verify_partial_signature(
+            partial_signature.index,
+            &partial_signature.z,
+            message_hash.as_ref(),
+            challenge,
+            interpolated_shares[position].as_ref(),
+        ) {
+            true => Ok(()),
+            false => {
+                trace!(
+                    warn,
+                    "ice-frost: aggregator rejected the partial signature of signer {}",
+                    partial_signature.index
+                );
+                Err(Error::MisbehavingParticipants(vec![
+                    partial_signature.index,
+                ]))
+            }
+        }
+    }
+
     /// Add a [`PartialThresholdSignature`] to be included in the aggregation.
-    pub fn include_partial_signature(&mut self, partial_signature: PartialThresholdSignature<C>) {
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the partial signature was
+    /// included, otherwise an [`Error::MissingEpochTag`], an [`Error::EpochMismatch`] or an
+    /// [`Error::KeyFingerprintMismatch`] if this aggregator expects a key epoch, set with
+    /// [`SignatureAggregator::with_epoch`], and the partial signature is not tagged with it.
+    pub fn include_partial_signature(
+        &mut self,
+        partial_signature: PartialThresholdSignature<C>,
+    ) -> FrostResult<C, ()> {
+        self.check_epoch_tag(&partial_signature)?;
         self.state
             .partial_signatures
             .insert(partial_signature.index, partial_signature.z);
+
+        Ok(())
     }
 
     /// Ensure that this signature aggregator is in a proper state to run the aggregation protocol.
@@ -597,16 +1053,142 @@ impl<C: CipherSuite> SignatureAggregator<C, Initial<'_>> {
         }
 
         if !misbehaving_participants.is_empty() {
+            trace!(
+                warn,
+                "ice-frost: aggregator cannot finalize with {} missing signers or keys",
+                misbehaving_participants.len()
+            );
             return Err(Error::MisbehavingParticipants(misbehaving_participants));
         }
+        trace!(
+            info,
+            "ice-frost: aggregator finalized a session with {} signers",
+            self.state.signers.len()
+        );
+
+        if let Some(policy) = &self.policy {
+            policy.check_organizations(&self.state.signers)?;
+        }
 
-        let message_hash = C::h4(self.aggregator.message)?;
+        let message_hash = self.message_hash()?;
 
         Ok(SignatureAggregator {
             state: self.state,
-            aggregator: Finalized { message_hash },
+            aggregator: Finalized {
+                message_hash,
+                dry_run: self.aggregator.dry_run,
+            },
+            #[cfg(feature = "std")]
+            cache: self.cache,
+            #[cfg(feature = "std")]
+            metrics_callback: self.metrics_callback,
+            policy: self.policy,
+            epoch: self.epoch,
+        })
+    }
+
+    /// Abort this signing session, for instance once a deadline has elapsed
+    /// before all expected signers contributed their partial signatures.
+    ///
+    /// All partial signatures received so far are checked against the public key
+    /// and published commitment share of their signer, in the context of the full
+    /// set of expected signers.
+    ///
+    /// # Returns
+    ///
+    /// An [`AbortedSession`] recording the given `reason`, the partial signatures
+    /// received, which of them are valid, and which expected signers timed out.
+    pub fn abort(mut self, reason: &str) -> FrostResult<C, AbortedSession<C>> {
+        // Ensure that our state is ordered and deduplicated.
+        self.state.signers = self.get_signers().clone();
+
+        let message_hash = self.message_hash()?;
+        let all_participant_indices: Vec<u32> = self
+            .state
+            .signers
+            .iter()
+            .map(|x| x.participant_index)
+            .collect();
+
+        let mut valid_signers = Vec::new();
+        if !self.state.signers.is_empty() {
+            let binding_factor_list = compute_randomized_binding_factors(
+                message_hash.as_ref(),
+                &self.state.signers,
+                &self.state.randomizers,
+            )?;
+            let group_commitment =
+                compute_group_commitment(&self.state.signers, &binding_factor_list)?;
+            let challenge = compute_session_challenge::<C>(
+                &group_commitment,
+                &self.state.group_key,
+                message_hash.as_ref(),
+                self.aggregator.dry_run,
+            )?;
+
+            let interpolated_shares =
+                self.interpolated_verification_shares(&all_participant_indices);
+
+            for (index, z) in self.state.partial_signatures.iter() {
+                if let Ok(position) = all_participant_indices.binary_search(index) {
+                    if self.state.verify_partial_signature(
+                        *index,
+                        z,
+                        message_hash.as_ref(),
+                        challenge,
+                        interpolated_shares[position].as_ref(),
+                    ) {
+                        valid_signers.push(*index);
+                    }
+                }
+            }
+        }
+
+        let timed_out_signers = self
+            .get_remaining_signers()
+            .iter()
+            .map(|signer| signer.participant_index)
+            .collect();
+
+        let partial_signatures = self
+            .state
+            .partial_signatures
+            .iter()
+            .map(|(index, z)| PartialThresholdSignature {
+                index: *index,
+                z: *z,
+                epoch_tag: None,
+            })
+            .collect();
+
+        Ok(AbortedSession {
+            reason: reason.to_string(),
+            message_hash: message_hash.as_ref().to_vec(),
+            signers: self.state.signers,
+            partial_signatures,
+            valid_signers,
+            timed_out_signers,
         })
     }
+
+    /// Hash the message to be signed unless already pre-hashed, or the reserved
+    /// message of dry-run sessions.
+    pub(super) fn message_hash(&self) -> FrostResult<C, C::HashOutput> {
+        if self.aggregator.dry_run {
+            return dry_run_message_hash(&self.state.group_key);
+        }
+        if !self.aggregator.prehashed {
+            return C::h4(self.aggregator.message);
+        }
+
+        check_message_hash_length::<C>(self.aggregator.message)?;
+        let mut message_hash = C::HashOutput::default();
+        message_hash
+            .as_mut()
+            .copy_from_slice(self.aggregator.message);
+
+        Ok(message_hash)
+    }
 }
 
 impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
@@ -617,14 +1199,38 @@ impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
     /// A Result whose Ok() value is a [`ThresholdSignature`], otherwise a
     /// [`BTreeMap<u32, &'static str>`] containing the participant indices of the misbehaving
     /// signers and a description of their misbehaviour.
+    ///
+    /// If a callback was registered with [`SignatureAggregator::with_metrics_callback`],
+    /// it is invoked with the [`SigningMetrics`](super::SigningMetrics) of the aggregation.
     pub fn aggregate(&self) -> FrostResult<C, ThresholdSignature<C>> {
-        let binding_factor_list =
-            compute_binding_factors(self.aggregator.message_hash.as_ref(), &self.state.signers)?;
-        let group_commitment = compute_group_commitment(&self.state.signers, &binding_factor_list);
-        let challenge = compute_challenge::<C>(
+        #[cfg(feature = "std")]
+        if self.metrics_callback.is_some() {
+            let (result, metrics) = self.aggregate_with_metrics();
+            self.report_metrics(&metrics);
+            return result;
+        }
+
+        self.aggregate_inner(&mut |_, verify| verify())
+    }
+
+    /// Aggregate the partial signatures, calling `verify_partial` with the index of
+    /// each signer whose partial signature must be individually verified, along with
+    /// the verification itself.
+    pub(super) fn aggregate_inner(
+        &self,
+        verify_partial: &mut dyn FnMut(u32, &mut dyn FnMut() -> bool) -> bool,
+    ) -> FrostResult<C, ThresholdSignature<C>> {
+        let binding_factor_list = compute_randomized_binding_factors(
+            self.aggregator.message_hash.as_ref(),
+            &self.state.signers,
+            &self.state.randomizers,
+        )?;
+        let group_commitment = compute_group_commitment(&self.state.signers, &binding_factor_list)?;
+        let challenge = compute_session_challenge::<C>(
             &group_commitment,
             &self.state.group_key,
             self.aggregator.message_hash.as_ref(),
+            self.aggregator.dry_run,
         )?;
 
         let all_participant_indices: Vec<u32> = self
@@ -639,13 +1245,13 @@ impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
         // We first combine all partial signatures together, to remove the need for individual
         // signature verification in case the final group signature is valid.
         for signer in self.state.signers.iter() {
-            // This unwrap() cannot fail, because [`SignatureAggregator<Initial>::finalize()`]
-            // checks that we have partial signature for every expected signer.
+            // [`SignatureAggregator<Initial>::finalize()`] checks that we have
+            // a partial signature for every expected signer.
             let partial_sig = self
                 .state
                 .partial_signatures
                 .get(&signer.participant_index)
-                .unwrap();
+                .ok_or(Error::MissingShares)?;
 
             z += partial_sig;
         }
@@ -657,48 +1263,45 @@ impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
 
         // Verify the obtained signature, listing malicious participants
         // if the verification failed.
-        match signature.verify(&self.state.group_key, self.aggregator.message_hash.as_ref()) {
-            Ok(()) => Ok(signature),
-            Err(_) => {
-                let mut misbehaving_participants = Vec::new();
-                for signer in self.state.signers.iter() {
-                    // This unwrap() cannot fail, since the attempted division by zero in
-                    // the calculation of the Lagrange interpolation cannot happen,
-                    // because we use the typestate pattern,
-                    // i.e. [`SignatureAggregator<Initial>::finalize()`], to ensure that
-                    // there are no duplicate signers, which is the only thing that
-                    // would cause a denominator of zero.
-                    let lambda = calculate_lagrange_coefficients::<C>(
-                        signer.participant_index,
-                        &all_participant_indices,
-                    )
-                    .unwrap();
+        match signature.verify_with_challenge(&self.state.group_key, challenge) {
+            Ok(()) => {
+                trace!(
+                    info,
+                    "ice-frost: aggregator produced a threshold signature from {} signers",
+                    self.state.signers.len()
+                );
+                Ok(signature)
+            }
+            Err(_) => {
+                let interpolated_shares =
+                    self.interpolated_verification_shares(&all_participant_indices);
 
-                    // This cannot fail, and has already been performed previously.
+                let mut misbehaving_participants = Vec::new();
+                for (signer, interpolated_share) in
+                    self.state.signers.iter().zip(interpolated_shares.iter())
+                {
+                    // This has already been checked previously.
                     let partial_sig = self
                         .state
                         .partial_signatures
                         .get(&signer.participant_index)
-                        .unwrap();
-
-                    // This cannot fail, as it is checked when calling finalize().
-                    let pk_i = self
-                        .state
-                        .public_keys
-                        .get(&signer.participant_index)
-                        .unwrap();
-
-                    let check = C::G::generator() * partial_sig;
-
-                    // This cannot fail, as the group commitment has already been computed.
-                    let participant_commitment = commitment_for_participant(
-                        signer.participant_index,
-                        self.aggregator.message_hash.as_ref(),
-                        &self.state.signers,
-                    )
-                    .unwrap();
-
-                    if check != participant_commitment + (pk_i.mul(challenge * lambda)) {
+                        .ok_or(Error::MissingShares)?;
+
+                    let is_valid = verify_partial(signer.participant_index, &mut || {
+                        self.state.verify_partial_signature(
+                            signer.participant_index,
+                            partial_sig,
+                            self.aggregator.message_hash.as_ref(),
+                            challenge,
+                            interpolated_share.as_ref(),
+                        )
+                    });
+                    if !is_valid {
+                        trace!(
+                            warn,
+                            "ice-frost: aggregator rejected the partial signature of signer {}",
+                            signer.participant_index
+                        );
                         misbehaving_participants.push(signer.participant_index);
                     }
                 }
@@ -708,6 +1311,34 @@ impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
     }
 }
 
+impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
+    /// Aggregate a set of previously-collected partial signatures, and certify
+    /// the resulting [`ThresholdSignature`] along its signers.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok() value is a [`QuorumCertificate`], otherwise the
+    /// same error as [`SignatureAggregator::aggregate`].
+    pub fn aggregate_with_certificate(&self) -> FrostResult<C, QuorumCertificate<C>> {
+        let signature = self.aggregate()?;
+
+        QuorumCertificate::new(&self.state.signers, signature)
+    }
+
+    /// Aggregate a set of previously-collected partial signatures, and attribute
+    /// the resulting [`ThresholdSignature`] to its signers.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok() value is an [`AttributedSignature`], otherwise the
+    /// same error as [`SignatureAggregator::aggregate`].
+    pub fn aggregate_attributed(&self) -> FrostResult<C, AttributedSignature<C>> {
+        let signature = self.aggregate()?;
+
+        AttributedSignature::new(self.state.parameters.n, &self.state.signers, signature)
+    }
+}
+
 impl<C: CipherSuite> ThresholdSignature<C> {
     /// Verify this [`ThresholdSignature`].
     ///
@@ -719,10 +1350,18 @@ impl<C: CipherSuite> ThresholdSignature<C> {
         group_key: &GroupVerifyingKey<C>,
         message_hash: &[u8],
     ) -> FrostResult<C, ()> {
-        let challenge =
-            compute_challenge::<C>(&self.group_commitment, group_key, message_hash).unwrap();
+        let challenge = compute_challenge::<C>(&self.group_commitment, group_key, message_hash)?;
 
-        let retrieved_commitment: C::G = <C as CipherSuite>::G::msm(
+        self.verify_with_challenge(group_key, challenge)
+    }
+
+    /// Verify this [`ThresholdSignature`] against an already computed `challenge`.
+    pub(crate) fn verify_with_challenge(
+        &self,
+        group_key: &GroupVerifyingKey<C>,
+        challenge: Scalar<C>,
+    ) -> FrostResult<C, ()> {
+        let retrieved_commitment = C::msm(
             &[C::G::generator().into(), (-group_key.key).into()],
             &[self.z, challenge],
         )
@@ -733,24 +1372,73 @@ impl<C: CipherSuite> ThresholdSignature<C> {
             false => Err(Error::InvalidSignature),
         }
     }
+
+    /// Verify this [`ThresholdSignature`] without a multi-scalar multiplication.
+    ///
+    /// Instead of retrieving the group commitment \\( R \\) through a multi-scalar
+    /// multiplication, the verification equation is rebalanced as
+    /// \\( z \cdot B = R + c \cdot Y \\), whose both sides are computed with single
+    /// scalar multiplications over affine points, hence without allocating the
+    /// intermediate tables of group elements of the multi-scalar multiplication.
+    /// With the default [`CipherSuite::challenge`], the group commitment, group key and
+    /// message hash are fed incrementally into the [`CipherSuite::h2_hasher`], without
+    /// being collected in a buffer.
+    ///
+    /// Over secp256k1, its peak heap usage is a few hundred bytes, against about 10 KiB
+    /// for [`ThresholdSignature::verify`], as measured by the `heap_usage` integration
+    /// test. It is however slower than [`ThresholdSignature::verify`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the signature was computed correctly.
+    pub fn verify_lowmem(
+        &self,
+        group_key: &GroupVerifyingKey<C>,
+        message_hash: &[u8],
+    ) -> FrostResult<C, ()> {
+        let challenge = compute_challenge::<C>(&self.group_commitment, group_key, message_hash)?;
+
+        let lhs = <C::G as CurveGroup>::Affine::generator().mul_bigint(self.z.into_bigint());
+        let mut rhs = group_key
+            .key
+            .into_affine()
+            .mul_bigint(challenge.into_bigint());
+        rhs += self.group_commitment.into_affine();
+
+        match lhs == rhs {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
 }
 
 #[cfg(test)]
-mod test {
+pub(super) mod test {
     use super::*;
 
-    use crate::dkg::{Coefficients, EncryptedSecretShare, Participant};
+    use crate::dkg::{
+        Coefficients, DealerRole, EncryptedSecretShare, Participant, ParticipantExpulsion,
+    };
     use crate::dkg::{DistributedKeyGeneration, RoundOne};
-    use crate::keys::DiffieHellmanPrivateKey;
-    use crate::sign::{generate_commitment_share_lists, PublicCommitmentShareList};
+    use crate::epoch::{EpochTransitionProof, RevocationRecord};
+    use crate::keys::{verify_key_consistency, DiffieHellmanPrivateKey, PublicKeyPackage};
+    use crate::sign::{
+        generate_commitment_share_lists, PartialSigBundle, PublicCommitmentShareList,
+        SigningPackage,
+    };
     use crate::testing::Secp256k1Sha256;
 
     use ark_secp256k1::{Fr, Projective};
 
-    use ark_ff::{UniformRand, Zero};
+    use ark_ff::UniformRand;
     use rand::rngs::OsRng;
+    use rand::Rng;
 
-    fn do_keygen(
+    /// Run a distributed key generation of `n1` participants with threshold `t1`,
+    /// followed by a resharing to `n2` signers with threshold `t2` if both are given.
+    ///
+    /// This is the key generation fixture shared by the signing tests of all modules.
+    pub(crate) fn do_keygen(
         n1: u32,
         t1: u32,
         n2: Option<u32>,
@@ -767,16 +1455,16 @@ mod test {
     > {
         type Dkg<T> = DistributedKeyGeneration<T, Secp256k1Sha256>;
 
-        let params = ThresholdParameters::new(n1, t1);
+        let params = ThresholdParameters::new(n1, t1).unwrap();
         let rng = OsRng;
 
-        let mut participants = Vec::<Participant<Secp256k1Sha256>>::new();
+        let mut participants = Vec::<Participant<Secp256k1Sha256, DealerRole>>::new();
         let mut coefficients = Vec::<Coefficients<Secp256k1Sha256>>::new();
         let mut dh_secret_keys = Vec::<DiffieHellmanPrivateKey<Secp256k1Sha256>>::new();
 
         for i in 1..n1 + 1 {
             let (p, c, dh_sk) =
-                Participant::<Secp256k1Sha256>::new_dealer(&params, i, rng).unwrap();
+                Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, i, rng).unwrap();
             participants.push(p);
             coefficients.push(c);
             dh_secret_keys.push(dh_sk);
@@ -850,14 +1538,17 @@ mod test {
         }
 
         if let (Some(n2), Some(t2)) = (n2, t2) {
-            let new_params = ThresholdParameters::new(n2, t2);
+            let new_params = ThresholdParameters::new(n2, t2).unwrap();
 
-            let mut signers = Vec::<Participant<Secp256k1Sha256>>::new();
+            let mut signers = Vec::<Participant<Secp256k1Sha256, crate::dkg::SignerRole>>::new();
             let mut signers_dh_secret_keys = Vec::<DiffieHellmanPrivateKey<Secp256k1Sha256>>::new();
 
             for i in 1..n2 + 1 {
                 let (p, dh_sk) =
-                    Participant::<Secp256k1Sha256>::new_signer(&params, i, rng).unwrap();
+                    Participant::<Secp256k1Sha256, crate::dkg::SignerRole>::new_signer(
+                        &params, i, rng,
+                    )
+                    .unwrap();
                 signers.push(p);
                 signers_dh_secret_keys.push(dh_sk);
             }
@@ -935,6 +1626,37 @@ mod test {
         Ok((params, participants_secret_keys, group_key, None, None))
     }
 
+    /// Sign the `message` with the first `t` of the given `signing_keys`.
+    fn threshold_sign(
+        params: ThresholdParameters<Secp256k1Sha256>,
+        signing_keys: &[IndividualSigningKey<Secp256k1Sha256>],
+        group_key: GroupVerifyingKey<Secp256k1Sha256>,
+        message: &[u8],
+    ) -> ThresholdSignature<Secp256k1Sha256> {
+        let message_hash = Secp256k1Sha256::h4(message).unwrap();
+        let mut aggregator = SignatureAggregator::new(params, group_key, message, None);
+
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(params.t as usize) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign(&message_hash, &group_key, comshares, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        aggregator.finalize().unwrap().aggregate().unwrap()
+    }
+
     #[test]
     fn signing_and_verification_single_party() {
         let (params, signing_keys, group_key, _, _) = do_keygen(1, 1, None, None).unwrap();
@@ -942,11 +1664,13 @@ mod test {
 
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
 
-        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -961,7 +1685,7 @@ mod test {
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let signing_result = aggregator.aggregate();
@@ -983,11 +1707,13 @@ mod test {
 
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
 
-        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1002,7 +1728,7 @@ mod test {
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let threshold_signature = aggregator.aggregate().unwrap();
@@ -1020,11 +1746,13 @@ mod test {
 
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
 
-        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1039,7 +1767,7 @@ mod test {
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let threshold_signature = aggregator.aggregate().unwrap();
@@ -1050,6 +1778,167 @@ mod test {
         assert!(verification_result2.is_ok());
     }
 
+    #[test]
+    fn single_signer_group_1_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 1, None, None).unwrap();
+        assert!(params.is_single_signer());
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        // Every participant holds the group signing key, and can sign alone.
+        for sk in signing_keys.iter() {
+            assert_eq!(sk.to_public().share, group_key.key);
+
+            let (public_comshares, mut secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+
+            let signers = aggregator.get_signers().clone();
+            let partial = sk
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+
+            let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            assert!(threshold_signature
+                .verify(&group_key, &message_hash)
+                .is_ok());
+        }
+
+        // The n-out-of-n fast path is not available to such groups.
+        assert_eq!(
+            signing_keys[0].to_additive(&params),
+            Err(Error::UnsupportedParameters(3, 1))
+        );
+    }
+
+    #[test]
+    fn include_signer_rejects_invalid_signers() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let (public_comshares, _) =
+            generate_commitment_share_lists(&mut OsRng, &signing_keys[0], 1).unwrap();
+        let commitment = public_comshares.commitments[0];
+
+        assert_eq!(
+            aggregator.include_signer(0, commitment, (&signing_keys[0]).into()),
+            Err(Error::IndexIsZero)
+        );
+        assert_eq!(
+            aggregator.include_signer(4, commitment, (&signing_keys[0]).into()),
+            Err(Error::UnknownParticipant(4))
+        );
+        assert_eq!(
+            aggregator.include_signer(2, commitment, (&signing_keys[0]).into()),
+            Err(Error::SignerKeyMismatch(2, 1))
+        );
+        assert!(aggregator.get_signers().is_empty());
+
+        aggregator
+            .include_signer(1, commitment, (&signing_keys[0]).into())
+            .unwrap();
+        assert_eq!(aggregator.get_signers().len(), 1);
+    }
+
+    #[test]
+    fn canonical_signer_ordering() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut secret_comshares = Vec::new();
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut reversed_aggregator =
+            SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut commitments = Vec::new();
+        for sk in signing_keys[..3].iter() {
+            let (public_comshares, secret) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            commitments.push((sk, public_comshares.commitments[0]));
+            secret_comshares.push(secret);
+        }
+        for (sk, commitment) in commitments.iter() {
+            aggregator
+                .include_signer(sk.index, *commitment, (*sk).into())
+                .unwrap();
+        }
+        for (sk, commitment) in commitments.iter().rev() {
+            reversed_aggregator
+                .include_signer(sk.index, *commitment, (*sk).into())
+                .unwrap();
+        }
+
+        // The inclusion order of the signers does not change the session.
+        assert_eq!(
+            aggregator.canonical_signing_package_bytes().unwrap(),
+            reversed_aggregator
+                .canonical_signing_package_bytes()
+                .unwrap()
+        );
+        assert_eq!(aggregator.get_signers(), reversed_aggregator.get_signers());
+
+        // Including a signer again changes neither its commitment share nor its key.
+        let (sk, commitment) = commitments[0];
+        let forged_key = IndividualVerifyingKey {
+            index: sk.index,
+            share: commitment.0,
+        };
+        aggregator
+            .include_signer(sk.index, commitments[1].1, forged_key)
+            .unwrap();
+        assert_eq!(aggregator.get_signers(), reversed_aggregator.get_signers());
+        assert_eq!(
+            aggregator.state.public_keys.get(&sk.index),
+            Some(&IndividualVerifyingKey::from(sk).share)
+        );
+
+        // Unsorted signers received from the wire are rejected.
+        let mut signers = aggregator.get_signers().clone();
+        signers.reverse();
+        assert_eq!(
+            signing_keys[0].sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &signers,
+            ),
+            Err(Error::UnsortedSigners)
+        );
+
+        let mut package = crate::sign::SigningPackage::new(
+            message_hash.as_ref(),
+            signers.clone(),
+            &signing_keys[0],
+            OsRng,
+        )
+        .unwrap();
+        assert_eq!(
+            crate::sign::SigningPackage::from_bytes(&package.to_bytes().unwrap()).unwrap(),
+            package
+        );
+        package.signers = signers;
+        assert_eq!(
+            crate::sign::SigningPackage::<Secp256k1Sha256>::from_bytes(
+                &package.to_bytes().unwrap()
+            ),
+            Err(Error::UnsortedSigners)
+        );
+    }
+
     #[test]
     fn signing_and_verification_3_out_of_5() {
         let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
@@ -1059,17 +1948,23 @@ mod test {
 
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
         let (p3_public_comshares, mut p3_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p3_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p3_sk, 1).unwrap();
         let (p4_public_comshares, mut p4_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p4_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p4_sk, 1).unwrap();
 
-        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into());
-        aggregator.include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1102,9 +1997,9 @@ mod test {
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p3_partial);
-        aggregator.include_partial_signature(p4_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p3_partial).unwrap();
+        aggregator.include_partial_signature(p4_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let threshold_signature = aggregator.aggregate().unwrap();
@@ -1115,6 +2010,548 @@ mod test {
         assert!(verification_result2.is_ok());
     }
 
+    #[test]
+    fn key_consistency_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+        let verifying_keys: Vec<_> = signing_keys.iter().map(|sk| sk.to_public()).collect();
+        let package = PublicKeyPackage::new(params, group_key, verifying_keys.clone());
+        assert!(package.verify_consistency().is_ok());
+
+        let mut shuffled = verifying_keys.clone();
+        shuffled.reverse();
+        assert!(verify_key_consistency(&group_key, &shuffled, &params).is_ok());
+
+        for position in 0..5 {
+            let mut tampered = verifying_keys.clone();
+            tampered[position].share += Projective::generator();
+            assert_eq!(
+                verify_key_consistency(&group_key, &tampered, &params),
+                Err(Error::InvalidGroupKey)
+            );
+        }
+
+        let other_group_key = GroupVerifyingKey::new(Element(group_key.key + group_key.key));
+        assert_eq!(
+            verify_key_consistency(&other_group_key, &verifying_keys, &params),
+            Err(Error::InvalidGroupKey)
+        );
+
+        assert_eq!(
+            verify_key_consistency(&group_key, &verifying_keys[..4], &params),
+            Err(Error::InvalidNumberOfParticipants(4, 5))
+        );
+        let mut duplicated = verifying_keys.clone();
+        duplicated[4].index = 1;
+        assert!(verify_key_consistency(&group_key, &duplicated, &params).is_err());
+    }
+
+    #[test]
+    fn domain_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+
+        let treasury = package.domain("treasury").unwrap();
+        let votes = package.domain("consensus votes").unwrap();
+        assert_eq!(treasury, package.domain("treasury").unwrap());
+        assert_ne!(treasury.group_key(), &group_key);
+        assert_ne!(treasury.group_key(), votes.group_key());
+        for sk in signing_keys.iter() {
+            assert_eq!(
+                sk.domain(&group_key, "treasury").unwrap().to_public(),
+                *treasury.verifying_key(sk.index).unwrap()
+            );
+        }
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator =
+            SignatureAggregator::new(params, *treasury.group_key(), &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in [&signing_keys[0], &signing_keys[2]] {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(
+                    sk.index,
+                    public_comshares.commitments[0],
+                    treasury.verifying_key(sk.index).unwrap().clone(),
+                )
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in [&signing_keys[0], &signing_keys[2]]
+            .into_iter()
+            .zip(secret_comshares.iter_mut())
+        {
+            let partial = sk
+                .sign_in_domain(
+                    "treasury",
+                    &message_hash,
+                    &group_key,
+                    secret_comshare,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature
+            .verify(treasury.group_key(), &message_hash)
+            .is_ok());
+
+        // The signature cannot be replayed in another domain, nor under the root key.
+        assert!(signature.verify(votes.group_key(), &message_hash).is_err());
+        assert!(signature.verify(&group_key, &message_hash).is_err());
+    }
+
+    #[test]
+    fn randomized_key_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+
+        let randomizer = KeyRandomizer::new(OsRng);
+        let randomized = package.randomize(&randomizer);
+        assert_eq!(randomized.group_key(), &group_key.randomize(&randomizer));
+        assert!(randomized.verify_consistency().is_ok());
+        for sk in signing_keys.iter() {
+            assert_eq!(
+                sk.randomize(&randomizer).to_public(),
+                *randomized.verifying_key(sk.index).unwrap()
+            );
+        }
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator =
+            SignatureAggregator::new(params, *randomized.group_key(), &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in [&signing_keys[0], &signing_keys[2]] {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(
+                    sk.index,
+                    public_comshares.commitments[0],
+                    randomized.verifying_key(sk.index).unwrap().clone(),
+                )
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in [&signing_keys[0], &signing_keys[2]]
+            .into_iter()
+            .zip(secret_comshares.iter_mut())
+        {
+            let partial = sk
+                .sign_with_randomized_key(
+                    &randomizer,
+                    &message_hash,
+                    &group_key,
+                    secret_comshare,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature
+            .verify(randomized.group_key(), &message_hash)
+            .is_ok());
+        assert!(signature.verify(&group_key, &message_hash).is_err());
+    }
+
+    #[test]
+    fn prehashed_signing_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message_hash = [0x42; 32];
+        let mut aggregator =
+            SignatureAggregator::new_prehashed(params, group_key, &message_hash, None).unwrap();
+
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+
+        let mut bundle = PartialSigBundle::new(&message_hash);
+        for (sk, comshares) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            bundle.push(
+                sk.sign(&message_hash, &group_key, comshares, 0, &signers)
+                    .unwrap(),
+            );
+        }
+        let bundle = PartialSigBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+        aggregator.include_partial_signature_bundle(bundle).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // The message hash of a pre-hashed session must match the hash output size.
+        assert_eq!(
+            SignatureAggregator::new_prehashed(params, group_key, &[0x42; 31], None).unwrap_err(),
+            Error::InvalidMessageHashLength(31, 32)
+        );
+    }
+
+    #[test]
+    fn epoch_transition_proof() {
+        let to_package =
+            |params, signing_keys: &[IndividualSigningKey<Secp256k1Sha256>], group_key| {
+                PublicKeyPackage::new(
+                    params,
+                    group_key,
+                    signing_keys.iter().map(|sk| sk.to_public()).collect(),
+                )
+            };
+
+        let (params_0, keys_0, group_key_0, _, _) = do_keygen(3, 2, None, None).unwrap();
+        let (params_1, keys_1, group_key_1, _, _) = do_keygen(4, 3, None, None).unwrap();
+        let (params_2, keys_2, group_key_2, _, _) = do_keygen(3, 2, None, None).unwrap();
+        let package_1 = to_package(params_1, &keys_1, group_key_1);
+        let package_2 = to_package(params_2, &keys_2, group_key_2);
+
+        let message = EpochTransitionProof::message(1, &package_1).unwrap();
+        let signature = threshold_sign(params_0, &keys_0, group_key_0, &message);
+        let proof_1 = EpochTransitionProof::new(1, &package_1, signature).unwrap();
+        let proof_1 = EpochTransitionProof::from_bytes(&proof_1.to_bytes().unwrap()).unwrap();
+        assert!(proof_1.verify(&group_key_0).is_ok());
+        assert!(proof_1.verify_package(&package_1).is_ok());
+        assert_eq!(
+            proof_1.verify_package(&package_2),
+            Err(Error::InvalidEpochTransitionProof)
+        );
+
+        let message = EpochTransitionProof::message(2, &package_2).unwrap();
+        let signature = threshold_sign(params_1, &keys_1, group_key_1, &message);
+        let proof_2 = EpochTransitionProof::new(2, &package_2, signature).unwrap();
+
+        // A light client follows the rotations from the initial group key only.
+        let proofs = [proof_1, proof_2];
+        assert_eq!(
+            EpochTransitionProof::verify_chain(0, &group_key_0, &proofs),
+            Ok((2, group_key_2))
+        );
+        assert_eq!(
+            EpochTransitionProof::verify_chain(1, &group_key_1, &proofs[1..]),
+            Ok((2, group_key_2))
+        );
+
+        // Proofs must be signed by the previous group, for consecutive epochs.
+        assert_eq!(
+            proofs[1].verify(&group_key_0),
+            Err(Error::InvalidEpochTransitionProof)
+        );
+        assert_eq!(
+            EpochTransitionProof::verify_chain(0, &group_key_0, &proofs[1..]),
+            Err(Error::InvalidEpochTransitionProof)
+        );
+        assert_eq!(
+            EpochTransitionProof::verify_chain(1, &group_key_0, &proofs[..1]),
+            Err(Error::InvalidEpochTransitionProof)
+        );
+    }
+
+    #[test]
+    fn participant_expulsion_and_revocation_record() {
+        let rng = OsRng;
+        let (_, keys_0, group_key, _, _) = do_keygen(4, 2, None, None).unwrap();
+        let package_0 = PublicKeyPackage::new(
+            ThresholdParameters::new(4, 2).unwrap(),
+            group_key,
+            keys_0.iter().map(|sk| sk.to_public()).collect(),
+        );
+
+        // Participant 2 is expelled, and participants 1, 3 and 4 are renumbered 1, 2 and 3.
+        let expulsion = ParticipantExpulsion::new(&package_0, 2).unwrap();
+        assert_eq!(expulsion.remaining_indices(), &[1, 3, 4]);
+        assert_eq!(expulsion.new_index(3), Some(2));
+        assert_eq!(expulsion.new_index(2), None);
+        assert!(expulsion.new_signer(2, rng).is_err());
+
+        let (signers, signers_dh_sk): (Vec<_>, Vec<_>) = expulsion
+            .remaining_indices()
+            .iter()
+            .map(|&index| expulsion.new_signer(index, rng).unwrap())
+            .unzip();
+        assert!(expulsion.reshare(keys_0[1].clone(), &signers, rng).is_err());
+
+        let (dealers, encrypted_shares): (Vec<_>, Vec<_>) = [0, 2, 3]
+            .iter()
+            .map(|&i| expulsion.reshare(keys_0[i].clone(), &signers, rng).unwrap())
+            .unzip();
+
+        let mut keys_1 = Vec::new();
+        for (position, (signer, dh_sk)) in signers.iter().zip(signers_dh_sk.iter()).enumerate() {
+            let (state, _participant_lists) =
+                expulsion.start(dh_sk, signer.index, &dealers, rng).unwrap();
+            let my_encrypted_secret_shares = encrypted_shares
+                .iter()
+                .map(|shares| shares[position].clone())
+                .collect();
+            let (new_group_key, signing_key) = state
+                .to_round_two(my_encrypted_secret_shares, rng)
+                .unwrap()
+                .finish()
+                .unwrap();
+            assert_eq!(new_group_key, group_key);
+            keys_1.push(signing_key);
+        }
+
+        let package_1 = expulsion.new_package(&dealers).unwrap();
+        assert_eq!(
+            package_1.parameters(),
+            &ThresholdParameters::new(3, 2).unwrap()
+        );
+        assert_eq!(
+            package_1.verifying_keys().to_vec(),
+            keys_1.iter().map(|sk| sk.to_public()).collect::<Vec<_>>()
+        );
+
+        // A dealer resharing another secret than its own share is rejected.
+        let forged_key = IndividualSigningKey {
+            index: 1,
+            key: Fr::rand(&mut OsRng),
+        };
+        let mut forged_dealers = dealers.clone();
+        forged_dealers[0] = Participant::reshare(expulsion.parameters(), forged_key, &signers, rng)
+            .unwrap()
+            .0;
+        assert_eq!(
+            expulsion.verify_dealers(&forged_dealers),
+            Err(Error::MisbehavingParticipants(vec![1]))
+        );
+        assert_eq!(
+            expulsion.verify_dealers(&dealers[1..]),
+            Err(Error::InvalidNumberOfParticipants(2, 3))
+        );
+
+        // The remaining members attest the expulsion with their new shares.
+        let expelled = expulsion.expelled_verifying_key().unwrap();
+        let message = RevocationRecord::message(1, expelled, &package_1).unwrap();
+        let signature = threshold_sign(*package_1.parameters(), &keys_1, group_key, &message);
+        let record = RevocationRecord::new(1, expelled, &package_1, signature).unwrap();
+        let record = RevocationRecord::from_bytes(&record.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(record.expelled().index, 2);
+        assert!(record.verify(&group_key).is_ok());
+        assert!(record.verify_package(&package_1).is_ok());
+        assert_eq!(
+            record.verify_package(&package_0),
+            Err(Error::InvalidRevocationRecord)
+        );
+        assert_eq!(
+            record.verify(&GroupVerifyingKey::new(Element::random(OsRng))),
+            Err(Error::InvalidRevocationRecord)
+        );
+    }
+
+    #[test]
+    fn share_linear_combination_2_out_of_3() {
+        let (params, first_keys, first_group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+        let (_, second_keys, second_group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let packages: Vec<PublicKeyPackage<Secp256k1Sha256>> = [
+            (first_group_key, &first_keys),
+            (second_group_key, &second_keys),
+        ]
+        .iter()
+        .map(|(group_key, keys)| {
+            PublicKeyPackage::new(
+                params,
+                *group_key,
+                keys.iter().map(|sk| sk.to_public()).collect(),
+            )
+        })
+        .collect();
+
+        let coefficients = [crate::Scalar::from(3u64), crate::Scalar::from(5u64)];
+        let package = PublicKeyPackage::linear_combination(&packages, &coefficients).unwrap();
+        let group_key = *package.group_key();
+        assert_eq!(
+            group_key.element(),
+            first_group_key.element() * coefficients[0]
+                + second_group_key.element() * coefficients[1]
+        );
+
+        let signing_keys: Vec<_> = first_keys
+            .iter()
+            .zip(second_keys.iter())
+            .map(|(first, second)| {
+                IndividualSigningKey::linear_combination(
+                    &[first.clone(), second.clone()],
+                    &coefficients,
+                )
+                .unwrap()
+            })
+            .collect();
+        for sk in signing_keys.iter() {
+            assert_eq!(Some(&sk.to_public()), package.verifying_key(sk.index));
+        }
+
+        let added = first_keys[0].add_share(&second_keys[0]).unwrap();
+        assert_eq!(
+            added.to_public(),
+            first_keys[0]
+                .to_public()
+                .add_share(&second_keys[0].to_public())
+                .unwrap()
+        );
+
+        // Shares of distinct participants cannot be combined.
+        assert_eq!(
+            first_keys[0].add_share(&second_keys[1]),
+            Err(Error::InvalidShareCombination)
+        );
+        assert_eq!(
+            IndividualSigningKey::linear_combination(&first_keys[..1], &coefficients),
+            Err(Error::InvalidShareCombination)
+        );
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial_signature = sk
+                .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                .unwrap();
+            aggregator
+                .include_partial_signature(partial_signature)
+                .unwrap();
+        }
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+
+    #[test]
+    fn group_commitment_of_many_signers() {
+        let message_hash = Secp256k1Sha256::h4(b"many signers").unwrap();
+        let signers: Vec<Signer<Secp256k1Sha256>> = (1..=50)
+            .map(|participant_index| Signer {
+                participant_index,
+                published_commitment_share: (
+                    Projective::rand(&mut OsRng),
+                    Projective::rand(&mut OsRng),
+                ),
+            })
+            .collect();
+        let binding_factors = compute_binding_factors(&message_hash, &signers).unwrap();
+
+        let expected = signers.iter().fold(Projective::zero(), |sum, signer| {
+            let (hiding, binding) = signer.published_commitment_share;
+            sum + hiding + binding * binding_factors[&signer.participant_index]
+        });
+        assert_eq!(
+            compute_group_nonce(&signers, &binding_factors).unwrap(),
+            Element(expected)
+        );
+    }
+
+    #[test]
+    fn early_group_nonce_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().skip(1) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+
+        // The group nonce commitment is known before any partial signature.
+        let group_nonce = aggregator.group_nonce().unwrap();
+        let binding_factors = compute_binding_factors(&message_hash, &signers).unwrap();
+        assert_eq!(
+            compute_group_nonce(&signers, &binding_factors).unwrap(),
+            group_nonce
+        );
+        assert_eq!(
+            compute_group_nonce(&signers, &BindingFactors::new()).unwrap_err(),
+            Error::InvalidBindingFactor
+        );
+
+        for (sk, comshares) in signing_keys.iter().skip(1).zip(secret_comshares.iter_mut()) {
+            let partial = sk
+                .sign(&message_hash, &group_key, comshares, 0, &signers)
+                .unwrap();
+            assert!(aggregator.verify_partial_signature(&partial).is_ok());
+
+            let mut invalid_partial = partial.clone();
+            invalid_partial.z += Fr::ONE;
+            assert_eq!(
+                aggregator.verify_partial_signature(&invalid_partial),
+                Err(Error::MisbehavingParticipants(vec![sk.index]))
+            );
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let unexpected_partial = PartialThresholdSignature {
+            index: 1,
+            z: Fr::ONE,
+            epoch_tag: None,
+        };
+        assert_eq!(
+            aggregator.verify_partial_signature(&unexpected_partial),
+            Err(Error::MisbehavingParticipants(vec![1]))
+        );
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert_eq!(
+            threshold_signature.group_commitment,
+            group_nonce.into_inner()
+        );
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+
     #[test]
     fn signing_and_verification_2_out_of_3() {
         let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
@@ -1123,14 +2560,18 @@ mod test {
 
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
         let (p2_public_comshares, mut p2_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p2_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p2_sk, 1).unwrap();
 
-        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1154,8 +2595,8 @@ mod test {
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p2_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let signing_result = aggregator.aggregate();
@@ -1170,6 +2611,52 @@ mod test {
         assert!(verification_result.is_ok());
     }
 
+    #[test]
+    fn low_memory_verification_2_out_of_3() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+        let signature = threshold_sign(params, &signing_keys, group_key, &message[..]);
+
+        assert!(signature.verify_lowmem(&group_key, &message_hash).is_ok());
+
+        // Both verification methods reject a tampered response.
+        let tampered_z = ThresholdSignature {
+            group_commitment: signature.group_commitment,
+            z: signature.z + Fr::ONE,
+        };
+        assert_eq!(
+            tampered_z.verify_lowmem(&group_key, &message_hash),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            tampered_z.verify(&group_key, &message_hash),
+            Err(Error::InvalidSignature)
+        );
+
+        // Both verification methods reject a tampered group commitment.
+        let tampered_r = ThresholdSignature {
+            group_commitment: signature.group_commitment + Projective::generator(),
+            z: signature.z,
+        };
+        assert_eq!(
+            tampered_r.verify_lowmem(&group_key, &message_hash),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            tampered_r.verify(&group_key, &message_hash),
+            Err(Error::InvalidSignature)
+        );
+
+        // Nor is the signature valid for another message.
+        let other_message_hash = Secp256k1Sha256::h4(b"Another message").unwrap();
+        assert_eq!(
+            signature.verify_lowmem(&group_key, &other_message_hash),
+            Err(Error::InvalidSignature)
+        );
+    }
+
     #[test]
     fn signing_and_verification_static_2_out_of_3() {
         if let (params, dealers_signing_keys, group_key, _, Some(signers_signing_keys)) =
@@ -1183,14 +2670,18 @@ mod test {
 
             let message = b"This is a test of the tsunami alert system. This is only a test.";
             let (d1_public_comshares, mut d1_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &d1_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &d1_sk, 1).unwrap();
             let (d2_public_comshares, mut d2_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &d2_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &d2_sk, 1).unwrap();
 
-            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-            aggregator.include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into());
-            aggregator.include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into());
+            aggregator
+                .include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into())
+                .unwrap();
+            aggregator
+                .include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into())
+                .unwrap();
 
             let signers = aggregator.get_signers();
             let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1214,8 +2705,8 @@ mod test {
                 )
                 .unwrap();
 
-            aggregator.include_partial_signature(d1_partial);
-            aggregator.include_partial_signature(d2_partial);
+            aggregator.include_partial_signature(d1_partial).unwrap();
+            aggregator.include_partial_signature(d2_partial).unwrap();
 
             let aggregator = aggregator.finalize().unwrap();
             let signing_result = aggregator.aggregate();
@@ -1229,14 +2720,18 @@ mod test {
 
             let message = b"This is a test of the tsunami alert system. This is only a test.";
             let (s1_public_comshares, mut s1_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &s1_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &s1_sk, 1).unwrap();
             let (s2_public_comshares, mut s2_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &s2_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &s2_sk, 1).unwrap();
 
-            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+            let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-            aggregator.include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into());
-            aggregator.include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into());
+            aggregator
+                .include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into())
+                .unwrap();
+            aggregator
+                .include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into())
+                .unwrap();
 
             let signers = aggregator.get_signers();
             let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1260,8 +2755,8 @@ mod test {
                 )
                 .unwrap();
 
-            aggregator.include_partial_signature(s1_partial);
-            aggregator.include_partial_signature(s2_partial);
+            aggregator.include_partial_signature(s1_partial).unwrap();
+            aggregator.include_partial_signature(s2_partial).unwrap();
 
             let aggregator = aggregator.finalize().unwrap();
             let signing_result = aggregator.aggregate();
@@ -1298,14 +2793,18 @@ mod test {
 
             let message = b"This is a test of the tsunami alert system. This is only a test.";
             let (d1_public_comshares, mut d1_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &d1_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &d1_sk, 1).unwrap();
             let (d2_public_comshares, mut d2_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &d2_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &d2_sk, 1).unwrap();
 
-            let mut aggregator = SignatureAggregator::new(d_params, group_key, &message[..]);
+            let mut aggregator = SignatureAggregator::new(d_params, group_key, &message[..], None);
 
-            aggregator.include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into());
-            aggregator.include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into());
+            aggregator
+                .include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into())
+                .unwrap();
+            aggregator
+                .include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into())
+                .unwrap();
 
             let signers = aggregator.get_signers();
             let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1329,8 +2828,8 @@ mod test {
                 )
                 .unwrap();
 
-            aggregator.include_partial_signature(d1_partial);
-            aggregator.include_partial_signature(d2_partial);
+            aggregator.include_partial_signature(d1_partial).unwrap();
+            aggregator.include_partial_signature(d2_partial).unwrap();
 
             let aggregator = aggregator.finalize().unwrap();
             let signing_result = aggregator.aggregate();
@@ -1344,17 +2843,23 @@ mod test {
 
             let message = b"This is a test of the tsunami alert system. This is only a test.";
             let (s1_public_comshares, mut s1_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &s1_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &s1_sk, 1).unwrap();
             let (s2_public_comshares, mut s2_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &s2_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &s2_sk, 1).unwrap();
             let (s3_public_comshares, mut s3_secret_comshares) =
-                generate_commitment_share_lists(&mut OsRng, &s3_sk, 1);
+                generate_commitment_share_lists(&mut OsRng, &s3_sk, 1).unwrap();
 
-            let mut aggregator = SignatureAggregator::new(s_params, group_key, &message[..]);
+            let mut aggregator = SignatureAggregator::new(s_params, group_key, &message[..], None);
 
-            aggregator.include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into());
-            aggregator.include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into());
-            aggregator.include_signer(3, s3_public_comshares.commitments[0], (&s3_sk).into());
+            aggregator
+                .include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into())
+                .unwrap();
+            aggregator
+                .include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into())
+                .unwrap();
+            aggregator
+                .include_signer(3, s3_public_comshares.commitments[0], (&s3_sk).into())
+                .unwrap();
 
             let signers = aggregator.get_signers();
             let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1387,9 +2892,9 @@ mod test {
                 )
                 .unwrap();
 
-            aggregator.include_partial_signature(s1_partial);
-            aggregator.include_partial_signature(s2_partial);
-            aggregator.include_partial_signature(s3_partial);
+            aggregator.include_partial_signature(s1_partial).unwrap();
+            aggregator.include_partial_signature(s2_partial).unwrap();
+            aggregator.include_partial_signature(s3_partial).unwrap();
 
             let aggregator = aggregator.finalize().unwrap();
             let signing_result = aggregator.aggregate();
@@ -1409,7 +2914,7 @@ mod test {
 
     #[test]
     fn aggregator_get_signers() {
-        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2);
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
         let message = b"This is a test of the tsunami alert system. This is only a test.";
 
         let p1_sk = IndividualSigningKey {
@@ -1422,19 +2927,26 @@ mod test {
         };
 
         let (p1_public_comshares, _) =
-            generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &p1_sk, 1).unwrap();
         let (p2_public_comshares, _) =
-            generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &p2_sk, 1);
+            generate_commitment_share_lists::<Secp256k1Sha256>(&mut OsRng, &p2_sk, 1).unwrap();
 
         let mut aggregator = SignatureAggregator::new(
             params,
-            GroupVerifyingKey::new(Projective::zero()),
+            GroupVerifyingKey::new(Element::identity()),
             &message[..],
+            None,
         );
 
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
 
@@ -1454,6 +2966,158 @@ mod test {
         assert!(signers[1].published_commitment_share.1 == p2_public_comshares.commitments[0].1);
     }
 
+    #[test]
+    fn adversarial_signing_inputs_return_errors() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
+
+        let message_hash = Secp256k1Sha256::h4(&b"adversarial"[..]).unwrap();
+        let mut aggregator = SignatureAggregator::new(params, group_key, &b"adversarial"[..], None);
+
+        let mut secret_comshares = Vec::new();
+        for sk in signing_keys.iter().take(2) {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+        let signers = aggregator.get_signers().clone();
+
+        // A signer outside of the session, or an empty session, cannot sign.
+        let (_, mut outsider_comshares) =
+            generate_commitment_share_lists(&mut OsRng, &signing_keys[2], 1).unwrap();
+        assert!(signing_keys[2]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut outsider_comshares,
+                0,
+                &signers
+            )
+            .is_err());
+        assert!(signing_keys[0]
+            .sign(&message_hash, &group_key, &mut secret_comshares[0], 0, &[])
+            .is_err());
+
+        // Out of range commitment shares are reported.
+        assert!(matches!(
+            signing_keys[0].sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                1,
+                &signers
+            ),
+            Err(Error::MissingCommitmentShares)
+        ));
+
+        // Binding factors not matching the signers are reported.
+        let binding_factors = compute_binding_factors(&message_hash, &signers[..1]).unwrap();
+        assert!(matches!(
+            compute_group_commitment(&signers, &binding_factors),
+            Err(Error::InvalidBindingFactor)
+        ));
+
+        // Garbage bytes are rejected by the deserialization of every public type.
+        macro_rules! deserialize_garbage {
+            ($bytes:expr; $($ty:ty),+ $(,)?) => {
+                $(let _ = <$ty>::from_bytes($bytes);)+
+            };
+        }
+
+        type C = Secp256k1Sha256;
+        for length in 0..256 {
+            let bytes: Vec<u8> = (0..length).map(|_| OsRng.gen()).collect();
+
+            deserialize_garbage!(&bytes;
+                // Signing
+                ThresholdSignature<C>,
+                PartialThresholdSignature<C>,
+                PublicCommitmentShareList<C>,
+                SecretCommitmentShareList<C>,
+                crate::sign::CommitmentShare<C>,
+                PartialSigBundle<C>,
+                SigningPackage<C>,
+                crate::sign::AbortedSession<C>,
+                crate::sign::AttributedSignature<C>,
+                crate::sign::BlindedPartialSignature<C>,
+                crate::sign::PartialSignatureBlinding<C>,
+                crate::sign::QuorumCertificate<C>,
+                crate::sign::DelegatedPrecompute<C>,
+                crate::sign::DelegatedCommitments<C>,
+                crate::sign::BlindingKey<C>,
+                crate::sign::Heartbeat<C>,
+                crate::sign::PartialAggregate<C>,
+                crate::sign::LazyCommitmentList<C>,
+                crate::sign::AdditiveSigningKey<C>,
+                crate::sign::AdditiveVerifyingKey<C>,
+                crate::sign::PreprocessedSession<C>,
+                crate::sign::PseudonymousCommitment<C>,
+                crate::sign::PseudonymousPartialSignature<C>,
+                crate::sign::CommitmentRandomizer<C>,
+                crate::sign::CommitmentRatchet<C>,
+                crate::sign::ReRequest<C>,
+                crate::sign::SigningTranscript<C>,
+                // Distributed key generation
+                crate::dkg::AdmissionContribution<C>,
+                crate::dkg::AdmissionShare<C>,
+                crate::dkg::IndexAssignment<C>,
+                crate::dkg::ShareBundle<C>,
+                crate::dkg::Complaint<C>,
+                crate::dkg::ComplaintProof<C>,
+                crate::dkg::SealedComplaint<C>,
+                DistributedKeyGeneration<RoundOne, C>,
+                DistributedKeyGeneration<crate::dkg::RoundTwo, C>,
+                crate::dkg::NizkPokOfSecretKey<C>,
+                crate::dkg::Participant<C, crate::dkg::DealerRole>,
+                crate::dkg::Participant<C, crate::dkg::SignerRole>,
+                crate::dkg::PedersenCommitment<C>,
+                crate::dkg::PedersenCoefficients<C>,
+                crate::dkg::PedersenParticipant<C>,
+                crate::dkg::EncryptedPedersenShare<C>,
+                crate::dkg::PedersenDistributedKeyGeneration<RoundOne, C>,
+                crate::dkg::PedersenDistributedKeyGeneration<crate::dkg::RoundTwo, C>,
+                crate::dkg::ShareReceipt<C>,
+                crate::dkg::ResharingShare<C>,
+                crate::dkg::ParticipantRoster<C>,
+                crate::dkg::MembershipProof<C>,
+                crate::dkg::Coefficients<C>,
+                crate::dkg::SecretShare<C>,
+                crate::dkg::EncryptedSecretShare<C>,
+                crate::dkg::VerifiableSecretSharingCommitment<C>,
+                // Keys, parameters and group elements
+                GroupVerifyingKey<C>,
+                crate::keys::DiffieHellmanPrivateKey<C>,
+                crate::keys::DiffieHellmanPublicKey<C>,
+                crate::keys::IndividualVerifyingKey<C>,
+                crate::keys::IndividualSigningKey<C>,
+                crate::keys::PublicKeyPackage<C>,
+                crate::keys::KeyRandomizer<C>,
+                crate::parameters::ThresholdParameters<C>,
+                crate::Scalar<C>,
+                crate::Element<C>,
+                // Epochs, escrow, framing and negotiation
+                crate::epoch::EpochedKeyManager<C>,
+                EpochTransitionProof<C>,
+                RevocationRecord<C>,
+                crate::escrow::EscrowedShare<C>,
+                crate::message::ProtocolFrame<C>,
+                crate::negotiation::VersionAdvert<C>,
+            );
+            #[cfg(feature = "daemon")]
+            deserialize_garbage!(&bytes; crate::daemon::DaemonMessage<C>);
+            #[cfg(feature = "bls")]
+            deserialize_garbage!(&bytes;
+                crate::bls::BlsPartialSignature<crate::testing::Bls12381G1Sha256>,
+                crate::bls::BlsSignature<crate::testing::Bls12381G1Sha256>,
+            );
+            #[cfg(feature = "backup")]
+            let _ =
+                crate::backup::KeyBackupShare::<C>::from_mnemonic(&String::from_utf8_lossy(&bytes));
+        }
+    }
+
     #[test]
     fn test_serialization() {
         let (params, dealers_signing_keys, group_key, _, _) = do_keygen(3, 2, None, None).unwrap();
@@ -1463,14 +3127,18 @@ mod test {
 
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
         let (p2_public_comshares, mut p2_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, &p2_sk, 1);
+            generate_commitment_share_lists(&mut OsRng, &p2_sk, 1).unwrap();
 
-        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -1516,8 +3184,8 @@ mod test {
 
         // Continue signature
 
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p2_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let signing_result = aggregator.aggregate();
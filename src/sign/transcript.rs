@@ -0,0 +1,331 @@
+//! The transcript module for independently auditing the signing sessions run by a
+//! coordinator.
+//!
+//! A [`SigningTranscript`] records everything a coordinator used to produce a
+//! [`ThresholdSignature`]: the message hash, the commitment shares of the signers,
+//! their binding factors, their partial signatures and the final signature. A party
+//! who did not take part in the session can check with [`SigningTranscript::verify_full`]
+//! that each step was computed correctly from the public key material of the group,
+//! hence that the coordinator neither forged nor tampered with any contribution.
+
+use ark_ec::Group;
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use core::ops::Mul;
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::PublicKeyPackage;
+use crate::utils::{calculate_lagrange_coefficients, BTreeMap, Scalar, Vec};
+use crate::validation::{deserialize_validated, ValidatePoints};
+use crate::{Error, FrostResult};
+
+use super::randomized::compute_randomized_binding_factors;
+use super::signature::{
+    check_canonical_ordering, commitment_for_participant, compute_challenge,
+    compute_group_commitment, Finalized, SignatureAggregator, Signer, ThresholdSignature,
+};
+
+/// The transcript of a signing session, from which a [`ThresholdSignature`] can be
+/// verified step by step.
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SigningTranscript<C: CipherSuite> {
+    /// The hash of the signed message.
+    pub(crate) message_hash: Vec<u8>,
+    /// The sorted signers, along with their published commitment shares.
+    pub(crate) signers: Vec<Signer<C>>,
+    /// The commitment randomizers contributed by the signers, if any.
+    pub(crate) randomizers: BTreeMap<u32, Scalar<C>>,
+    /// The binding factors of the signers.
+    pub(crate) binding_factors: BTreeMap<u32, Scalar<C>>,
+    /// The partial signatures of the signers.
+    pub(crate) partial_signatures: BTreeMap<u32, Scalar<C>>,
+    /// The final aggregated signature.
+    pub(crate) signature: ThresholdSignature<C>,
+}
+
+impl<C: CipherSuite> SigningTranscript<C> {
+    /// The hash of the signed message.
+    pub fn message_hash(&self) -> &[u8] {
+        &self.message_hash
+    }
+
+    /// The sorted signers of the session, along with their published commitment shares.
+    pub fn signers(&self) -> &[Signer<C>] {
+        &self.signers
+    }
+
+    /// The binding factor of the signer with the given `index`, if any.
+    pub fn binding_factor(&self, index: u32) -> Option<crate::Scalar<C>> {
+        self.binding_factors
+            .get(&index)
+            .map(|binding_factor| crate::Scalar(*binding_factor))
+    }
+
+    /// The partial signature of the signer with the given `index`, if any.
+    pub fn partial_signature(&self, index: u32) -> Option<crate::Scalar<C>> {
+        self.partial_signatures
+            .get(&index)
+            .map(|partial_signature| crate::Scalar(*partial_signature))
+    }
+
+    /// The final [`ThresholdSignature`] of the session.
+    pub fn signature(&self) -> &ThresholdSignature<C> {
+        &self.signature
+    }
+
+    /// Recompute every step of the signing session recorded by this transcript, against
+    /// the public key material of the group.
+    ///
+    /// This checks that:
+    ///
+    /// * the signers are sorted, unique, reach the threshold of the group, and each have
+    ///   a binding factor and a partial signature, and nothing more;
+    /// * the recorded binding factors are the ones derived from the message hash, the
+    ///   commitment shares of the signers and their randomizers, if any;
+    /// * each partial signature is valid for the commitment share and the verifying key
+    ///   of its signer;
+    /// * the partial signatures sum to the response of the final signature, whose nonce
+    ///   is the group commitment of the signers, and which is valid for the group key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that the transcript is valid,
+    /// otherwise an [`Error::MisbehavingParticipants`] listing the signers with invalid
+    /// partial signatures, or an [`Error::InvalidSigningTranscript`] for any other
+    /// inconsistency.
+    pub fn verify_full(&self, public_key_package: &PublicKeyPackage<C>) -> FrostResult<C, ()> {
+        let signer_indices: Vec<u32> = self
+            .signers
+            .iter()
+            .map(|signer| signer.participant_index)
+            .collect();
+        if check_canonical_ordering(&self.signers).is_err()
+            || signer_indices.len() < public_key_package.parameters().t as usize
+            || !self.binding_factors.keys().eq(signer_indices.iter())
+            || !self.partial_signatures.keys().eq(signer_indices.iter())
+        {
+            return Err(Error::InvalidSigningTranscript);
+        }
+
+        let binding_factors = compute_randomized_binding_factors(
+            &self.message_hash,
+            &self.signers,
+            &self.randomizers,
+        )
+        .map_err(|_| Error::InvalidSigningTranscript)?;
+        if binding_factors.0 != self.binding_factors {
+            return Err(Error::InvalidSigningTranscript);
+        }
+
+        let group_commitment = compute_group_commitment(&self.signers, &binding_factors)?;
+        if group_commitment != self.signature.group_commitment {
+            return Err(Error::InvalidSigningTranscript);
+        }
+        let challenge = compute_challenge::<C>(
+            &group_commitment,
+            public_key_package.group_key(),
+            &self.message_hash,
+        )?;
+
+        let mut misbehaving_participants = Vec::new();
+        let mut z = Scalar::<C>::ZERO;
+        for index in signer_indices.iter() {
+            let verifying_key = public_key_package
+                .verifying_key(*index)
+                .ok_or(Error::InvalidSigningTranscript)?;
+            let lambda = calculate_lagrange_coefficients::<C>(*index, &signer_indices)?;
+            let commitment = commitment_for_participant(
+                *index,
+                &self.message_hash,
+                &self.signers,
+                &self.randomizers,
+            )?;
+            let partial_signature = self.partial_signatures[index];
+
+            if C::G::generator() * partial_signature
+                != commitment + verifying_key.share.mul(lambda * challenge)
+            {
+                misbehaving_participants.push(*index);
+            }
+            z += partial_signature;
+        }
+
+        if !misbehaving_participants.is_empty() {
+            return Err(Error::MisbehavingParticipants(misbehaving_participants));
+        }
+        if z != self.signature.z {
+            return Err(Error::InvalidSigningTranscript);
+        }
+
+        self.signature
+            .verify(public_key_package.group_key(), &self.message_hash)
+    }
+
+    /// Serialize this [`SigningTranscript`] to a vector of bytes.
+    pub fn to_bytes(&self) -> FrostResult<C, Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| Error::SerializationError(e.into()))?;
+
+        Ok(bytes)
+    }
+
+    /// Attempt to deserialize a [`SigningTranscript`] from a vector of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> FrostResult<C, Self> {
+        deserialize_validated(bytes)
+    }
+}
+
+impl<C: CipherSuite> ValidatePoints<C> for SigningTranscript<C> {
+    fn validate_points(&self) -> FrostResult<C, ()> {
+        for signer in self.signers.iter() {
+            signer.validate_points()?;
+        }
+        self.signature.validate_points()
+    }
+}
+
+impl<C: CipherSuite> SignatureAggregator<C, Finalized<C>> {
+    /// Aggregate a set of previously-collected partial signatures, and record the
+    /// [`SigningTranscript`] of the session, to be handed to auditors.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok() value is a [`SigningTranscript`], otherwise the
+    /// same error as [`SignatureAggregator::aggregate`].
+    pub fn aggregate_with_transcript(&self) -> FrostResult<C, SigningTranscript<C>> {
+        let signature = self.aggregate()?;
+        let message_hash = self.aggregator.message_hash.as_ref();
+        let binding_factors = compute_randomized_binding_factors(
+            message_hash,
+            &self.state.signers,
+            &self.state.randomizers,
+        )?;
+
+        Ok(SigningTranscript {
+            message_hash: message_hash.to_vec(),
+            signers: self.state.signers.clone(),
+            randomizers: self.state.randomizers.clone(),
+            binding_factors: binding_factors.0,
+            partial_signatures: self
+                .state
+                .signers
+                .iter()
+                .filter_map(|signer| {
+                    self.state
+                        .partial_signatures
+                        .get(&signer.participant_index)
+                        .map(|z| (signer.participant_index, *z))
+                })
+                .collect(),
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::sign::generate_commitment_share_lists;
+    use crate::sign::signature::test::do_keygen;
+    use crate::testing::Secp256k1Sha256;
+
+    use ark_secp256k1::{Fr, Projective};
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn signing_transcript_3_out_of_5() {
+        let (params, signing_keys, group_key, _, _) = do_keygen(5, 3, None, None).unwrap();
+
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+        let mut secret_comshares = Vec::new();
+        for sk in [&signing_keys[0], &signing_keys[3], &signing_keys[4]] {
+            let (public_comshares, secret_comshare) =
+                generate_commitment_share_lists(&mut OsRng, sk, 1).unwrap();
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.into())
+                .unwrap();
+            secret_comshares.push(secret_comshare);
+        }
+
+        let signers = aggregator.get_signers().clone();
+        for (sk, secret_comshare) in [&signing_keys[0], &signing_keys[3], &signing_keys[4]]
+            .into_iter()
+            .zip(secret_comshares.iter_mut())
+        {
+            let partial = sk
+                .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+
+        let aggregator = aggregator.finalize().unwrap();
+        let transcript = aggregator.aggregate_with_transcript().unwrap();
+        assert_eq!(transcript.signature(), &aggregator.aggregate().unwrap());
+        assert_eq!(transcript.signers().len(), 3);
+        assert!(transcript.binding_factor(4).is_some());
+        assert!(transcript.partial_signature(2).is_none());
+
+        // An auditor only needs the public key material of the group.
+        let package = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys.iter().map(|sk| sk.to_public()).collect(),
+        );
+        let bytes = transcript.to_bytes().unwrap();
+        let transcript = SigningTranscript::from_bytes(&bytes).unwrap();
+        assert_eq!(transcript.to_bytes().unwrap(), bytes);
+        assert!(transcript.verify_full(&package).is_ok());
+
+        // Any tampering by the coordinator is detected.
+        let mut tampered = SigningTranscript::from_bytes(&bytes).unwrap();
+        *tampered.partial_signatures.get_mut(&4).unwrap() += Fr::from(1u8);
+        assert_eq!(
+            tampered.verify_full(&package),
+            Err(Error::MisbehavingParticipants(vec![4]))
+        );
+
+        let mut tampered = SigningTranscript::from_bytes(&bytes).unwrap();
+        *tampered.binding_factors.get_mut(&1).unwrap() += Fr::from(1u8);
+        assert_eq!(
+            tampered.verify_full(&package),
+            Err(Error::InvalidSigningTranscript)
+        );
+
+        let mut tampered = SigningTranscript::from_bytes(&bytes).unwrap();
+        tampered.signers[1].published_commitment_share.0 = Projective::generator();
+        assert_eq!(
+            tampered.verify_full(&package),
+            Err(Error::InvalidSigningTranscript)
+        );
+
+        let mut tampered = SigningTranscript::from_bytes(&bytes).unwrap();
+        tampered.message_hash[0] ^= 1;
+        assert_eq!(
+            tampered.verify_full(&package),
+            Err(Error::InvalidSigningTranscript)
+        );
+
+        let mut tampered = SigningTranscript::from_bytes(&bytes).unwrap();
+        tampered.signature.z += Fr::from(1u8);
+        assert_eq!(
+            tampered.verify_full(&package),
+            Err(Error::InvalidSigningTranscript)
+        );
+
+        let mut tampered = SigningTranscript::from_bytes(&bytes).unwrap();
+        tampered.signers.pop();
+        tampered.binding_factors.remove(&5);
+        tampered.partial_signatures.remove(&5);
+        assert_eq!(
+            tampered.verify_full(&package),
+            Err(Error::InvalidSigningTranscript)
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! The spki module, encoding group public keys as X.509 `SubjectPublicKeyInfo`
+//! structures.
+//!
+//! A `SubjectPublicKeyInfo`, specified in
+//! [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.7), is the DER
+//! encoding of a public key along with the identifier of its algorithm, as found in
+//! X.509 certificates, certificate signing requests and PEM `PUBLIC KEY` files.
+//! Exporting the [`GroupVerifyingKey`](crate::keys::GroupVerifyingKey) of an ICE-FROST
+//! group in this format allows enrolling the group in existing PKI tooling, for instance
+//! as a certificate authority or as a TLS identity.
+//!
+//! The algorithm of a [`CipherSuite`] is given by its [`CipherSuite::SPKI_ALGORITHM`]:
+//!
+//! * points of secp256k1 and P-256 are encoded as uncompressed SEC1 points under the
+//!   `id-ecPublicKey` algorithm of [RFC 5480](https://www.rfc-editor.org/rfc/rfc5480),
+//!   with the object identifier of their named curve;
+//! * points of edwards25519 are encoded as in
+//!   [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.2) under the
+//!   `id-Ed25519` algorithm of [RFC 8410](https://www.rfc-editor.org/rfc/rfc8410).
+
+use ark_ec::short_weierstrass::SWFlags;
+use ark_ec::twisted_edwards::TEFlags;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, CanonicalSerializeWithFlags, SerializationError,
+};
+
+use crate::ciphersuite::CipherSuite;
+use crate::utils::{vec, ToString, Vec};
+use crate::{Error, FrostResult};
+
+/// The DER encoding of the `id-ecPublicKey` object identifier (1.2.840.10045.2.1).
+const ID_EC_PUBLIC_KEY: &[u8] = &[0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// The DER encoding of the `secp256k1` object identifier (1.3.132.0.10).
+const SECP256K1: &[u8] = &[0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+/// The DER encoding of the `prime256v1` object identifier (1.2.840.10045.3.1.7).
+const PRIME256V1: &[u8] = &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+/// The DER encoding of the `id-Ed25519` object identifier (1.3.101.112).
+const ID_ED25519: &[u8] = &[0x06, 0x03, 0x2b, 0x65, 0x70];
+
+/// The DER tag of a `SEQUENCE`.
+const SEQUENCE_TAG: u8 = 0x30;
+/// The DER tag of a `BIT STRING`.
+const BIT_STRING_TAG: u8 = 0x03;
+
+/// The algorithm of the public keys of a [`CipherSuite`] within a `SubjectPublicKeyInfo`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpkiAlgorithm {
+    /// Elliptic curve keys over secp256k1.
+    Secp256k1,
+    /// Elliptic curve keys over secp256r1 (P-256).
+    P256,
+    /// Ed25519 keys over edwards25519.
+    Ed25519,
+}
+
+impl SpkiAlgorithm {
+    /// The DER encoding of the `AlgorithmIdentifier` of this algorithm.
+    pub fn algorithm_identifier(&self) -> Vec<u8> {
+        match self {
+            Self::Secp256k1 => der_sequence(&[ID_EC_PUBLIC_KEY, SECP256K1].concat()),
+            Self::P256 => der_sequence(&[ID_EC_PUBLIC_KEY, PRIME256V1].concat()),
+            Self::Ed25519 => der_sequence(ID_ED25519),
+        }
+    }
+}
+
+/// The algorithm of the public keys of the [`CipherSuite`] `C`.
+fn spki_algorithm<C: CipherSuite>() -> FrostResult<C, SpkiAlgorithm> {
+    C::SPKI_ALGORITHM.ok_or_else(|| {
+        Error::Custom("The ciphersuite has no SubjectPublicKeyInfo algorithm".to_string())
+    })
+}
+
+/// An error for malformed `SubjectPublicKeyInfo` structures.
+fn invalid_data<C: CipherSuite>() -> Error<C> {
+    Error::DeserializationError(SerializationError::InvalidData.into())
+}
+
+/// Encode the public key `point` as a DER `SubjectPublicKeyInfo`.
+pub(crate) fn encode<C: CipherSuite>(point: &C::G) -> FrostResult<C, Vec<u8>> {
+    let algorithm = spki_algorithm::<C>()?;
+    let affine = point.into_affine();
+    let (x, y) = affine.xy().ok_or(Error::InvalidPoint)?;
+
+    let subject_public_key = match algorithm {
+        SpkiAlgorithm::Secp256k1 | SpkiAlgorithm::P256 => {
+            let mut encoding = vec![0x04];
+            encoding.extend(to_bytes_be::<C>(x)?);
+            encoding.extend(to_bytes_be::<C>(y)?);
+            encoding
+        }
+        SpkiAlgorithm::Ed25519 => ed25519_encoding::<C>(x, y)?,
+    };
+
+    let mut bit_string = vec![0x00];
+    bit_string.extend(subject_public_key);
+
+    Ok(der_sequence(
+        &[
+            algorithm.algorithm_identifier(),
+            der_tlv(BIT_STRING_TAG, &bit_string),
+        ]
+        .concat(),
+    ))
+}
+
+/// Encode the point `point` of edwards25519 as specified in
+/// [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.2).
+pub(crate) fn encode_ed25519_point<C: CipherSuite>(point: &C::G) -> FrostResult<C, Vec<u8>> {
+    let affine = point.into_affine();
+    let (x, y) = affine.xy().ok_or(Error::InvalidPoint)?;
+
+    ed25519_encoding::<C>(x, y)
+}
+
+/// The RFC 8032 encoding of the point of edwards25519 with coordinates `x` and `y`.
+fn ed25519_encoding<C: CipherSuite>(
+    x: &<C::G as CurveGroup>::BaseField,
+    y: &<C::G as CurveGroup>::BaseField,
+) -> FrostResult<C, Vec<u8>> {
+    sign_bit_encoding::<C>(y, x)
+}
+
+/// The little-endian encoding of the base field element `coordinate`, whose most
+/// significant bit is set to the parity of the base field element `other`, as used
+/// for the points of RFC 8032 and RedDSA.
+pub(crate) fn sign_bit_encoding<C: CipherSuite>(
+    coordinate: &<C::G as CurveGroup>::BaseField,
+    other: &<C::G as CurveGroup>::BaseField,
+) -> FrostResult<C, Vec<u8>> {
+    type BaseField<C> = <<C as CipherSuite>::G as CurveGroup>::BaseField;
+
+    // The sign bit must not overlap with the bits of the coordinate.
+    if <<BaseField<C> as Field>::BasePrimeField as PrimeField>::MODULUS_BIT_SIZE % 8 == 0 {
+        return Err(Error::Custom(
+            "Sign bit encodings require a spare bit in the base field".to_string(),
+        ));
+    }
+
+    let mut encoding = to_bytes_be::<C>(coordinate)?;
+    encoding.reverse();
+    let last = encoding.last_mut().ok_or(Error::InvalidPoint)?;
+    *last |= u8::from(is_odd::<C>(other)?) << 7;
+
+    Ok(encoding)
+}
+
+/// Decode a public key from a DER `SubjectPublicKeyInfo`, checking that its
+/// algorithm is the one of the [`CipherSuite`] `C`.
+pub(crate) fn decode<C: CipherSuite>(bytes: &[u8]) -> FrostResult<C, C::G> {
+    let algorithm = spki_algorithm::<C>()?;
+
+    let (content, rest) = read_tlv::<C>(bytes, SEQUENCE_TAG)?;
+    if !rest.is_empty() {
+        return Err(invalid_data());
+    }
+    let algorithm_identifier = algorithm.algorithm_identifier();
+    if !content.starts_with(&algorithm_identifier) {
+        return Err(invalid_data());
+    }
+    let (bit_string, rest) = read_tlv::<C>(&content[algorithm_identifier.len()..], BIT_STRING_TAG)?;
+    let subject_public_key = match bit_string.split_first() {
+        Some((0, subject_public_key)) if rest.is_empty() => subject_public_key,
+        _ => return Err(invalid_data()),
+    };
+
+    let field_size = field_size::<C>();
+    let point = match algorithm {
+        SpkiAlgorithm::Secp256k1 | SpkiAlgorithm::P256 => match subject_public_key.split_first() {
+            Some((0x04, coordinates)) if coordinates.len() == 2 * field_size => {
+                let x = from_bytes_be::<C>(&coordinates[..field_size])?;
+                let y = from_bytes_be::<C>(&coordinates[field_size..])?;
+
+                let mut encoding = Vec::new();
+                x.serialize_uncompressed(&mut encoding)
+                    .map_err(|e| Error::SerializationError(e.into()))?;
+                y.serialize_with_flags(&mut encoding, SWFlags::from_y_coordinate(y))
+                    .map_err(|e| Error::SerializationError(e.into()))?;
+
+                <C::G as CurveGroup>::Affine::deserialize_uncompressed(&encoding[..])
+                    .map_err(|e| Error::DeserializationError(e.into()))?
+                    .into_group()
+            }
+            Some((prefix @ (0x02 | 0x03), x)) if x.len() == field_size => {
+                let x = from_bytes_be::<C>(x)?;
+
+                let mut encoding = Vec::new();
+                x.serialize_with_flags(&mut encoding, SWFlags::YIsPositive)
+                    .map_err(|e| Error::SerializationError(e.into()))?;
+                let affine = <C::G as CurveGroup>::Affine::deserialize_compressed(&encoding[..])
+                    .map_err(|e| Error::DeserializationError(e.into()))?;
+
+                let (_, y) = affine.xy().ok_or_else(invalid_data)?;
+                match is_odd::<C>(y)? == (*prefix == 0x03) {
+                    true => affine.into_group(),
+                    false => -affine.into_group(),
+                }
+            }
+            _ => return Err(invalid_data()),
+        },
+        SpkiAlgorithm::Ed25519 => {
+            if subject_public_key.len() != field_size {
+                return Err(invalid_data());
+            }
+            let mut y = subject_public_key.to_vec();
+            let x_is_odd = y[field_size - 1] >> 7 == 1;
+            y[field_size - 1] &= 0x7f;
+            y.reverse();
+            let y = from_bytes_be::<C>(&y)?;
+
+            let mut encoding = Vec::new();
+            y.serialize_with_flags(&mut encoding, TEFlags::XIsPositive)
+                .map_err(|e| Error::SerializationError(e.into()))?;
+            let affine = <C::G as CurveGroup>::Affine::deserialize_compressed(&encoding[..])
+                .map_err(|e| Error::DeserializationError(e.into()))?;
+
+            let (x, _) = affine.xy().ok_or_else(invalid_data)?;
+            if x_is_odd && x.is_zero() {
+                return Err(invalid_data());
+            }
+            match is_odd::<C>(x)? == x_is_odd {
+                true => affine.into_group(),
+                false => -affine.into_group(),
+            }
+        }
+    };
+
+    Ok(point)
+}
+
+/// The size in bytes of the encoding of an element of the base field of `C`.
+fn field_size<C: CipherSuite>() -> usize {
+    type BaseField<C> = <<C as CipherSuite>::G as CurveGroup>::BaseField;
+
+    let bits = <<BaseField<C> as Field>::BasePrimeField as PrimeField>::MODULUS_BIT_SIZE as usize;
+
+    bits / 8 + usize::from(bits & 7 > 0)
+}
+
+/// The single prime field element of the base field element `element`.
+fn prime_field_element<C: CipherSuite>(
+    element: &<C::G as CurveGroup>::BaseField,
+) -> FrostResult<C, <<C::G as CurveGroup>::BaseField as Field>::BasePrimeField> {
+    let mut elements = element.to_base_prime_field_elements();
+    match (elements.next(), elements.next()) {
+        (Some(element), None) => Ok(element),
+        _ => Err(Error::Custom(
+            "SubjectPublicKeyInfo encodings require a prime base field".to_string(),
+        )),
+    }
+}
+
+/// Whether the base field element `element` is odd.
+fn is_odd<C: CipherSuite>(element: &<C::G as CurveGroup>::BaseField) -> FrostResult<C, bool> {
+    Ok(prime_field_element::<C>(element)?.into_bigint().is_odd())
+}
+
+/// The big-endian encoding of the base field element `element`, on [`field_size`] bytes.
+fn to_bytes_be<C: CipherSuite>(
+    element: &<C::G as CurveGroup>::BaseField,
+) -> FrostResult<C, Vec<u8>> {
+    let bytes = prime_field_element::<C>(element)?
+        .into_bigint()
+        .to_bytes_be();
+
+    Ok(bytes[bytes.len() - field_size::<C>()..].to_vec())
+}
+
+/// Decode a base field element from its canonical big-endian encoding.
+fn from_bytes_be<C: CipherSuite>(bytes: &[u8]) -> FrostResult<C, <C::G as CurveGroup>::BaseField> {
+    let element =
+        <<C::G as CurveGroup>::BaseField as Field>::BasePrimeField::from_be_bytes_mod_order(bytes);
+    let element = <C::G as CurveGroup>::BaseField::from_base_prime_field_elems(&[element])
+        .ok_or_else(invalid_data)?;
+
+    match to_bytes_be::<C>(&element)? == bytes {
+        true => Ok(element),
+        false => Err(invalid_data()),
+    }
+}
+
+/// Encode a DER element with the given `tag` and `content`.
+fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![tag];
+    match content.len() {
+        length @ 0..=0x7f => bytes.push(length as u8),
+        length => {
+            let length_bytes: Vec<u8> = length
+                .to_be_bytes()
+                .iter()
+                .copied()
+                .skip_while(|byte| *byte == 0)
+                .collect();
+            bytes.push(0x80 | length_bytes.len() as u8);
+            bytes.extend(length_bytes);
+        }
+    }
+    bytes.extend_from_slice(content);
+
+    bytes
+}
+
+/// Encode a DER `SEQUENCE` with the given `content`.
+fn der_sequence(content: &[u8]) -> Vec<u8> {
+    der_tlv(SEQUENCE_TAG, content)
+}
+
+/// Read a DER element with the expected `tag` from `bytes`, returning its content
+/// and the remaining bytes.
+fn read_tlv<C: CipherSuite>(bytes: &[u8], tag: u8) -> FrostResult<C, (&[u8], &[u8])> {
+    let (length, rest) = match bytes {
+        [actual_tag, length, rest @ ..] if *actual_tag == tag && *length < 0x80 => {
+            (*length as usize, rest)
+        }
+        [actual_tag, 0x81, length, rest @ ..] if *actual_tag == tag && *length >= 0x80 => {
+            (*length as usize, rest)
+        }
+        [actual_tag, 0x82, high, low, rest @ ..] if *actual_tag == tag && *high > 0 => {
+            (usize::from(*high) << 8 | usize::from(*low), rest)
+        }
+        _ => return Err(invalid_data()),
+    };
+
+    match length <= rest.len() {
+        true => Ok(rest.split_at(length)),
+        false => Err(invalid_data()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keys::GroupVerifyingKey;
+    use crate::testing::Secp256k1Sha256;
+    use crate::Element;
+
+    use ark_ec::Group;
+    use rand::rngs::OsRng;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn secp256k1_spki() {
+        // The SubjectPublicKeyInfo of the generator, as output by
+        // `openssl ec -pubout -outform DER` for the private key 1.
+        let generator = GroupVerifyingKey::<Secp256k1Sha256>::new(Element(
+            ark_secp256k1::Projective::generator(),
+        ));
+        let expected = from_hex(concat!(
+            "3056301006072a8648ce3d020106052b8104000a034200",
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        ));
+        assert_eq!(generator.to_spki_der().unwrap(), expected);
+        assert_eq!(
+            GroupVerifyingKey::from_spki_der(&expected).unwrap(),
+            generator
+        );
+
+        // Compressed points are accepted as well.
+        let compressed = from_hex(concat!(
+            "3036301006072a8648ce3d020106052b8104000a032200",
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+        assert_eq!(
+            GroupVerifyingKey::from_spki_der(&compressed).unwrap(),
+            generator
+        );
+
+        for _ in 0..16 {
+            let key = GroupVerifyingKey::<Secp256k1Sha256>::new(Element::random(OsRng));
+            let bytes = key.to_spki_der().unwrap();
+            assert_eq!(GroupVerifyingKey::from_spki_der(&bytes).unwrap(), key);
+
+            let mut negated = bytes.clone();
+            negated[23] = if key.key.into_affine().y.into_bigint().is_odd() {
+                0x02
+            } else {
+                0x03
+            };
+            negated.truncate(24 + 32);
+            negated[1] -= 32;
+            negated[21] -= 32;
+            assert_eq!(
+                GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&negated).unwrap(),
+                GroupVerifyingKey::new(Element(-key.key))
+            );
+        }
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn p256_spki() {
+        use crate::testing::P256Sha256;
+
+        // A public key generated with `openssl ecparam -name prime256v1 -genkey`,
+        // exported in both point formats with `openssl ec -pubout -outform DER`.
+        let uncompressed = from_hex(concat!(
+            "3059301306072a8648ce3d020106082a8648ce3d030107034200",
+            "0434e0e543aeed0b9769cba76697f4246c7a082d579f2f1ba0b0d3ecb3506e4c31",
+            "e1741032c0029baa960574e50f358da9e6ce21d54e1cc0ce4c12415fa7a54cbd",
+        ));
+        let compressed = from_hex(concat!(
+            "3039301306072a8648ce3d020106082a8648ce3d030107032200",
+            "0334e0e543aeed0b9769cba76697f4246c7a082d579f2f1ba0b0d3ecb3506e4c31",
+        ));
+
+        let key = GroupVerifyingKey::<P256Sha256>::from_spki_der(&uncompressed).unwrap();
+        assert_eq!(
+            GroupVerifyingKey::<P256Sha256>::from_spki_der(&compressed).unwrap(),
+            key
+        );
+        assert_eq!(key.to_spki_der().unwrap(), uncompressed);
+
+        // Keys of other curves are rejected.
+        assert!(GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&uncompressed).is_err());
+    }
+
+    #[test]
+    fn malformed_spki() {
+        let key = GroupVerifyingKey::<Secp256k1Sha256>::new(Element::random(OsRng));
+        let bytes = key.to_spki_der().unwrap();
+
+        // Points off the curve are rejected.
+        let mut off_curve = bytes.clone();
+        *off_curve.last_mut().unwrap() ^= 1;
+        assert!(GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&off_curve).is_err());
+
+        // As are other curves, truncated and trailing bytes.
+        let mut other_curve = bytes.clone();
+        other_curve[19] = 0x0b;
+        assert!(GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&other_curve).is_err());
+        assert!(GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&bytes[..80]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&trailing).is_err());
+
+        // Non-canonical coordinates are rejected.
+        let mut non_canonical = bytes;
+        non_canonical[24..56].copy_from_slice(&[0xff; 32]);
+        assert!(GroupVerifyingKey::<Secp256k1Sha256>::from_spki_der(&non_canonical).is_err());
+
+        // The identity has no encoding.
+        let identity = GroupVerifyingKey::<Secp256k1Sha256>::new(Element(
+            ark_secp256k1::Projective::default(),
+        ));
+        assert!(identity.to_spki_der().is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Builders for misbehaving ICE-FROST participants.
+//!
+//! These are intended for downstream integrators to assert that their
+//! orchestration layer surfaces the expected [`Error`] variants, and
+//! excludes the expected peers, when facing cheating participants.
+
+use core::ops::Mul;
+
+use ark_ec::Group;
+use ark_ff::{Field, UniformRand};
+use ark_serialize::CanonicalSerialize;
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::secret_share::{
+    decrypt_share, encrypt_share, share_session_id, VerifiableSecretSharingCommitment,
+};
+use crate::dkg::{
+    Coefficients, DealerRole, EncryptedSecretShare, NizkPokOfSecretKey, Participant, Role,
+    SecretShare,
+};
+use crate::keys::{DiffieHellmanPrivateKey, DiffieHellmanPublicKey};
+use crate::parameters::ThresholdParameters;
+use crate::utils::{Scalar, ToString, Vec};
+use crate::{Element, Error, FrostResult};
+
+/// Construct a dealer whose proof of knowledge of its secret key is invalid.
+///
+/// Honest participants will list this dealer's index among the misbehaving ones
+/// when starting their DKG session.
+pub fn dealer_with_invalid_proof_of_secret_key<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    index: u32,
+    mut rng: impl RngCore + CryptoRng,
+) -> FrostResult<
+    C,
+    (
+        Participant<C, DealerRole>,
+        Coefficients<C>,
+        DiffieHellmanPrivateKey<C>,
+    ),
+> {
+    let (mut dealer, coefficients, dh_private_key) =
+        Participant::new_dealer(parameters, index, &mut rng)?;
+
+    // Prove knowledge of an unrelated secret.
+    dealer.material.proof_of_secret_key = NizkPokOfSecretKey::prove(
+        index,
+        &crate::Scalar::random(&mut rng),
+        &dealer.public_key().ok_or(Error::InvalidProofOfKnowledge)?,
+        &mut rng,
+    )?;
+
+    Ok((dealer, coefficients, dh_private_key))
+}
+
+/// Construct a dealer whose proof of knowledge of its Diffie-Hellman private key is invalid.
+///
+/// Honest participants will list this dealer's index among the misbehaving ones
+/// when starting their DKG session.
+pub fn dealer_with_invalid_proof_of_dh_private_key<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    index: u32,
+    mut rng: impl RngCore + CryptoRng,
+) -> FrostResult<
+    C,
+    (
+        Participant<C, DealerRole>,
+        Coefficients<C>,
+        DiffieHellmanPrivateKey<C>,
+    ),
+> {
+    let (mut dealer, coefficients, dh_private_key) =
+        Participant::new_dealer(parameters, index, &mut rng)?;
+
+    // Prove knowledge of an unrelated secret.
+    dealer.proof_of_dh_private_key = NizkPokOfSecretKey::<C>::prove(
+        index,
+        &crate::Scalar::random(&mut rng),
+        &dealer.dh_public_key.element(),
+        &mut rng,
+    )?;
+
+    Ok((dealer, coefficients, dh_private_key))
+}
+
+/// Construct a dealer announcing the identity as its Diffie-Hellman public key, along
+/// with a valid proof of knowledge of the corresponding zero private key.
+///
+/// Honest participants will list this dealer's index among the misbehaving ones
+/// when starting their DKG session, and reject it with an
+/// [`Error::IdentityDhPublicKey`] upon deserialization.
+pub fn dealer_with_identity_dh_public_key<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    index: u32,
+    mut rng: impl RngCore + CryptoRng,
+) -> FrostResult<
+    C,
+    (
+        Participant<C, DealerRole>,
+        Coefficients<C>,
+        DiffieHellmanPrivateKey<C>,
+    ),
+> {
+    let (mut dealer, coefficients, _) = Participant::new_dealer(parameters, index, &mut rng)?;
+
+    dealer.dh_public_key = DiffieHellmanPublicKey::new(Element::identity());
+    dealer.proof_of_dh_private_key = NizkPokOfSecretKey::<C>::prove(
+        index,
+        &crate::Scalar::zero(),
+        &dealer.dh_public_key.element(),
+        &mut rng,
+    )?;
+
+    Ok((
+        dealer,
+        coefficients,
+        DiffieHellmanPrivateKey(Scalar::<C>::ZERO),
+    ))
+}
+
+/// Construct a dealer committing to a secret polynomial of `length` coefficients,
+/// instead of the `t` coefficients expected from the [`ThresholdParameters`].
+///
+/// The dealer's proofs of knowledge are valid, but honest participants will list
+/// this dealer's index among the misbehaving ones when bootstrapping their DKG session.
+pub fn dealer_with_wrong_commitment_length<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    index: u32,
+    length: usize,
+    mut rng: impl RngCore + CryptoRng,
+) -> FrostResult<
+    C,
+    (
+        Participant<C, DealerRole>,
+        Coefficients<C>,
+        DiffieHellmanPrivateKey<C>,
+    ),
+> {
+    if length == 0 {
+        return Err(Error::Custom(
+            "Commitments must contain at least one point".to_string(),
+        ));
+    }
+
+    let (mut dealer, _, dh_private_key) = Participant::new_dealer(parameters, index, &mut rng)?;
+
+    let coefficients: Vec<Scalar<C>> = (0..length).map(|_| Scalar::<C>::rand(&mut rng)).collect();
+    let points = coefficients
+        .iter()
+        .map(|c| C::G::generator().mul(c))
+        .collect();
+    let commitments = VerifiableSecretSharingCommitment { index, points };
+
+    dealer.material.proof_of_secret_key = NizkPokOfSecretKey::prove(
+        index,
+        &crate::Scalar(coefficients[0]),
+        &Element(commitments.points[0]),
+        &mut rng,
+    )?;
+    dealer.material.commitments = commitments;
+
+    Ok((dealer, Coefficients(coefficients), dh_private_key))
+}
+
+/// Construct a valid dealer reusing the index of an `existing` participant.
+///
+/// As participants sharing an index cannot be told apart, honest participants
+/// will list all of them among the misbehaving ones when starting their DKG session.
+pub fn dealer_with_reused_index<C: CipherSuite, R: Role<C>>(
+    parameters: &ThresholdParameters<C>,
+    existing: &Participant<C, R>,
+    rng: impl RngCore + CryptoRng,
+) -> FrostResult<
+    C,
+    (
+        Participant<C, DealerRole>,
+        Coefficients<C>,
+        DiffieHellmanPrivateKey<C>,
+    ),
+> {
+    Participant::new_dealer(parameters, existing.index, rng)
+}
+
+/// Tamper with an honestly generated `encrypted_share`, so that it decrypts to the evaluation
+/// of the dealer's polynomial off by one, i.e. \\( f\_i(l) + 1 \\).
+///
+/// The share is re-encrypted under the same Diffie-Hellman shared key and nonce, hence its
+/// recipient will issue a valid complaint against the dealer during round two.
+pub fn off_by_one_share<C: CipherSuite>(
+    encrypted_share: &EncryptedSecretShare<C>,
+    dealer_dh_private_key: &DiffieHellmanPrivateKey<C>,
+    recipient_dh_public_key: &DiffieHellmanPublicKey<C>,
+) -> FrostResult<C, EncryptedSecretShare<C>> {
+    let dh_key = recipient_dh_public_key.key * dealer_dh_private_key.0;
+    let mut dh_key_bytes = Vec::new();
+    dh_key
+        .serialize_compressed(&mut dh_key_bytes)
+        .map_err(|e| Error::CompressionError(e.into()))?;
+
+    let dealer_dh_public_key =
+        DiffieHellmanPublicKey::new(Element(C::G::generator().mul(dealer_dh_private_key.0)));
+    let session_id = share_session_id(&dealer_dh_public_key, recipient_dh_public_key)?;
+
+    let share = decrypt_share(encrypted_share, &dh_key_bytes, &session_id)?;
+    let tampered_share = SecretShare {
+        sender_index: share.sender_index,
+        receiver_index: share.receiver_index,
+        polynomial_evaluation: crate::Scalar(share.polynomial_evaluation.0 + Scalar::<C>::ONE),
+    };
+
+    encrypt_share(
+        &tampered_share,
+        &dh_key_bytes,
+        &session_id,
+        &encrypted_share.salt,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dkg::{DistributedKeyGeneration, RoundOne};
+    use crate::testing::Secp256k1Sha256;
+
+    use rand::rngs::OsRng;
+
+    type Dkg = DistributedKeyGeneration<RoundOne, Secp256k1Sha256>;
+
+    #[test]
+    fn invalid_proofs_are_detected() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1_coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, rng).unwrap();
+        let (p2, _, _) = dealer_with_invalid_proof_of_secret_key(&params, 2, rng).unwrap();
+        let (p3, _, _) = dealer_with_invalid_proof_of_dh_private_key(&params, 3, rng).unwrap();
+
+        let participants = vec![p1.clone(), p2, p3];
+        let result = Dkg::bootstrap(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1_coeffs,
+            &participants,
+            rng,
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::TooManyInvalidParticipants(vec![2, 3])
+        );
+    }
+
+    #[test]
+    fn identity_dh_public_key_is_detected() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1_coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, rng).unwrap();
+        let (p2, _, _) = Participant::new_dealer(&params, 2, rng).unwrap();
+        let (p3, _, _) = dealer_with_identity_dh_public_key(&params, 3, rng).unwrap();
+
+        // The proof of knowledge of the zero private key is valid.
+        assert!(p3
+            .proof_of_dh_private_key
+            .verify(3, &p3.dh_public_key.element())
+            .is_ok());
+        assert_eq!(
+            Participant::<Secp256k1Sha256, DealerRole>::from_bytes(&p3.to_bytes().unwrap()),
+            Err(Error::IdentityDhPublicKey(3))
+        );
+
+        let participants = vec![p1.clone(), p2, p3];
+        let (_, participant_lists) = Dkg::bootstrap(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1_coeffs,
+            &participants,
+            rng,
+        )
+        .unwrap();
+
+        assert_eq!(participant_lists.misbehaving_participants, Some(vec![3]));
+        assert_eq!(participant_lists.valid_participants.len(), 2);
+    }
+
+    #[test]
+    fn wrong_commitment_length_is_detected() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1_coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, rng).unwrap();
+        let (p2, _, _) = Participant::new_dealer(&params, 2, rng).unwrap();
+        let (p3, _, _) = dealer_with_wrong_commitment_length(&params, 3, 3, rng).unwrap();
+
+        let participants = vec![p1.clone(), p2, p3];
+        let (_, participant_lists) = Dkg::bootstrap(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1_coeffs,
+            &participants,
+            rng,
+        )
+        .unwrap();
+
+        assert_eq!(participant_lists.misbehaving_participants, Some(vec![3]));
+        assert_eq!(participant_lists.valid_participants.len(), 2);
+    }
+
+    #[test]
+    fn reused_index_is_detected() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(3, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1_coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, rng).unwrap();
+        let (p2, _, _) = Participant::new_dealer(&params, 2, rng).unwrap();
+        let (p3, _, _) = dealer_with_reused_index(&params, &p2, rng).unwrap();
+
+        let participants = vec![p1.clone(), p2, p3];
+        let result = Dkg::bootstrap(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1_coeffs,
+            &participants,
+            rng,
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::TooManyInvalidParticipants(vec![2, 2])
+        );
+    }
+
+    #[test]
+    fn off_by_one_share_is_blamed_on_dealer() {
+        let params = ThresholdParameters::<Secp256k1Sha256>::new(2, 2).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1_coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, rng).unwrap();
+        let (p2, p2_coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, rng).unwrap();
+
+        let participants = vec![p1.clone(), p2.clone()];
+        let (p1_state, _) = Dkg::bootstrap(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1_coeffs,
+            &participants,
+            rng,
+        )
+        .unwrap();
+        let (p2_state, _) = Dkg::bootstrap(
+            &params,
+            &p2_dh_sk,
+            &p2.index,
+            &p2_coeffs,
+            &participants,
+            rng,
+        )
+        .unwrap();
+
+        let p1_shares = p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let p2_shares = p2_state.their_encrypted_secret_shares().unwrap().clone();
+
+        let bad_share = off_by_one_share(&p1_shares[1], &p1_dh_sk, &p2.dh_public_key).unwrap();
+
+        let p1_state = p1_state
+            .clone()
+            .to_round_two(vec![p1_shares[0].clone(), p2_shares[0].clone()], rng)
+            .unwrap();
+        let complaints = match p2_state
+            .to_round_two(vec![bad_share.clone(), p2_shares[1].clone()], rng)
+            .unwrap_err()
+        {
+            Error::Complaint(complaints) => complaints,
+            _ => panic!("expected a complaint"),
+        };
+
+        assert_eq!(complaints.len(), 1);
+        assert_eq!(complaints[0].accused_index, 1);
+        assert_eq!(p1_state.blame(&bad_share, &complaints[0]), 1);
+    }
+}
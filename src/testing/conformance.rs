@@ -0,0 +1,376 @@
+//! Conformance checks for ICE-FROST ciphersuites.
+//!
+//! These are intended for integrators defining their own [`CipherSuite`], for instance
+//! with the [`define_ciphersuite!`](crate::define_ciphersuite) macro, to assert that it
+//! is correctly wired before relying on it. [`run_all`] runs every check, and returns
+//! an error describing the first failure instead of letting an incompatible ciphersuite
+//! silently produce keys or signatures other implementations reject.
+
+use ark_ec::Group;
+use ark_ff::{BigInteger, PrimeField};
+use digest::Digest;
+use rand::rngs::OsRng;
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::{DealerRole, DistributedKeyGeneration, Participant, RoundOne};
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, PublicKeyPackage};
+use crate::parameters::ThresholdParameters;
+use crate::sign::{generate_commitment_share_lists, SignatureAggregator, ThresholdSignature};
+use crate::utils::{calculate_lagrange_coefficients, vec, Scalar, ToString, Vec};
+use crate::{Element, Error, FrostResult};
+
+/// Run all conformance checks against the ciphersuite `C`.
+///
+/// This runs the [`self_test`](crate::self_test) of the ciphersuite, followed by
+/// [`check_hash_to_field`], [`check_serialization`], [`check_key_generation`] and
+/// [`check_signing`].
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value indicates that all checks succeeded,
+/// otherwise an [`Error`] describing the first failure.
+pub fn run_all<C: CipherSuite>() -> FrostResult<C, ()> {
+    crate::self_test::<C>()?;
+    check_hash_to_field::<C>()?;
+    check_serialization::<C>()?;
+    check_key_generation::<C>(5, 3)?;
+    check_signing::<C>(3, 2)
+}
+
+/// Check that [`CipherSuite::hash_to_field`] handles the edge cases of its inputs:
+/// empty and oversized domain separation tags, empty and long messages, and inputs
+/// only differing by their length or by the boundary between tag and message.
+pub fn check_hash_to_field<C: CipherSuite>() -> FrostResult<C, ()> {
+    let long_dst = vec![b'D'; 300];
+    let long_message = vec![b'M'; 4096];
+
+    let inputs: [(&[u8], &[u8]); 8] = [
+        (b"", b""),
+        (b"", b"abc"),
+        (b"ICE-FROST", b"abc"),
+        (b"ICE-FROST", b"abc\0"),
+        (b"ICE-FROST", &long_message),
+        (&long_dst, b"abc"),
+        (&long_dst[..255], b"abc"),
+        (b"ICE-FROSTa", b"bc"),
+    ];
+
+    let mut scalars = Vec::with_capacity(inputs.len());
+    for (dst, message) in inputs.iter() {
+        let scalar = C::hash_to_field(dst, message)?;
+        if scalar != C::hash_to_field(dst, message)? {
+            return Err(Error::Custom(
+                "Hashing to the scalar field is not deterministic".to_string(),
+            ));
+        }
+        if scalar == Scalar::<C>::from(0u64) {
+            return Err(Error::Custom(
+                "Hashing to the scalar field returned zero".to_string(),
+            ));
+        }
+        scalars.push(scalar);
+    }
+
+    if scalars
+        .iter()
+        .enumerate()
+        .any(|(i, s)| scalars[i + 1..].contains(s))
+    {
+        return Err(Error::Custom(
+            "Hashing distinct inputs to the scalar field collides".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that the scalars and group elements of the ciphersuite `C` have
+/// fixed-length, canonical encodings which survive a serialization roundtrip
+/// unchanged, and that invalid encodings are rejected.
+pub fn check_serialization<C: CipherSuite>() -> FrostResult<C, ()> {
+    let invalid = |what: &str| Err(Error::Custom(what.to_string()));
+
+    let scalar_length = crate::Scalar::<C>::zero().to_bytes()?.len();
+    let scalars = [
+        crate::Scalar::<C>::zero(),
+        crate::Scalar::one(),
+        -crate::Scalar::one(),
+        crate::Scalar::from_inner(C::h3(b"ICE-FROST conformance")?),
+    ];
+    for scalar in scalars.iter() {
+        let bytes = scalar.to_bytes()?;
+        let deserialized = crate::Scalar::<C>::from_bytes(&bytes)?;
+        if bytes.len() != scalar_length
+            || deserialized != *scalar
+            || deserialized.to_bytes()? != bytes
+        {
+            return invalid("Scalars do not survive a serialization roundtrip");
+        }
+    }
+
+    let mut modulus = Scalar::<C>::MODULUS.to_bytes_le();
+    modulus.resize(scalar_length, 0);
+    if crate::Scalar::<C>::from_bytes(&modulus).is_ok() {
+        return invalid("Non-canonical scalar encodings are accepted");
+    }
+
+    let generator = Element::<C>::generator();
+    let two = crate::Scalar::<C>::from(2u64);
+    let element_length = generator.to_bytes()?.len();
+    if (generator + generator).to_bytes()? != (generator * two).to_bytes()? {
+        return invalid("Equal group elements have distinct encodings");
+    }
+
+    let elements = [
+        generator,
+        -generator,
+        generator * scalars[3],
+        Element::from_inner(C::G::generator().double()),
+    ];
+    for element in elements.iter() {
+        let bytes = element.to_bytes()?;
+        let deserialized = Element::<C>::from_bytes(&bytes)?;
+        if bytes.len() != element_length
+            || deserialized != *element
+            || deserialized.to_bytes()? != bytes
+        {
+            return invalid("Group elements do not survive a serialization roundtrip");
+        }
+    }
+
+    if Element::<C>::from_bytes(&Element::<C>::identity().to_bytes()?).is_ok() {
+        return invalid("The identity element is accepted");
+    }
+
+    Ok(())
+}
+
+/// Check that the hash functions of the ciphersuite `C` are consistent
+/// with its declared output size, deterministic, and domain-separated,
+/// with distinct domain tags for each operation.
+pub fn check_hash_functions<C: CipherSuite>() -> FrostResult<C, ()> {
+    if C::context_string().is_empty() {
+        return Err(Error::Custom("The context string is empty".to_string()));
+    }
+    if !C::HASH_OUTPUT_POLICY.is_compatible(
+        C::HashOutput::default().as_ref().len(),
+        <C::InnerHasher as Digest>::output_size(),
+    ) {
+        return Err(Error::Custom(
+            "The hash output size does not match the inner hasher".to_string(),
+        ));
+    }
+
+    let domain_tags = [
+        C::nizk_domain_tag(),
+        C::binding_factor_domain_tag(),
+        C::challenge_domain_tag(),
+        C::nonce_domain_tag(),
+        C::message_domain_tag(),
+        C::commitment_domain_tag(),
+        C::share_encryption_domain_tag(),
+        C::complaint_domain_tag(),
+    ];
+    if domain_tags
+        .iter()
+        .enumerate()
+        .any(|(i, tag)| domain_tags[i + 1..].contains(tag))
+    {
+        return Err(Error::Custom("Domain tags are not distinct".to_string()));
+    }
+
+    let message = b"ICE-FROST conformance";
+    let scalars = [
+        C::h0(message)?,
+        C::h1(message)?,
+        C::h2(message)?,
+        C::h3(message)?,
+    ];
+    if scalars
+        != [
+            C::h0(message)?,
+            C::h1(message)?,
+            C::h2(message)?,
+            C::h3(message)?,
+        ]
+    {
+        return Err(Error::Custom(
+            "Hashing to scalars is not deterministic".to_string(),
+        ));
+    }
+    if scalars
+        .iter()
+        .enumerate()
+        .any(|(i, s)| scalars[i + 1..].contains(s))
+    {
+        return Err(Error::Custom(
+            "Scalar oracles are not domain-separated".to_string(),
+        ));
+    }
+
+    let (h4, h5) = (C::h4(message)?, C::h5(message)?);
+    if h4.as_ref() != C::h4(message)?.as_ref() || h5.as_ref() != C::h5(message)?.as_ref() {
+        return Err(Error::Custom(
+            "Hashing to bytes is not deterministic".to_string(),
+        ));
+    }
+    if h4.as_ref() == h5.as_ref() {
+        return Err(Error::Custom(
+            "Byte oracles are not domain-separated".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run an honest distributed key generation session among `params.n` participants
+/// simulated in-process, with indices from 1 to `params.n`.
+///
+/// # Returns
+///
+/// The [`GroupVerifyingKey`] of the group, along with the [`IndividualSigningKey`]s
+/// of all participants, sorted by index.
+pub fn generate_keys<C: CipherSuite>(
+    params: &ThresholdParameters<C>,
+) -> FrostResult<C, (GroupVerifyingKey<C>, Vec<IndividualSigningKey<C>>)> {
+    let n = params.n;
+    let rng = OsRng;
+
+    let mut participants = Vec::with_capacity(n as usize);
+    let mut coefficients = Vec::with_capacity(n as usize);
+    let mut dh_secret_keys = Vec::with_capacity(n as usize);
+    for i in 1..=n {
+        let (participant, coefficient, dh_sk) =
+            Participant::<C, DealerRole>::new_dealer(params, i, rng)?;
+        participants.push(participant);
+        coefficients.push(coefficient);
+        dh_secret_keys.push(dh_sk);
+    }
+
+    let mut states = Vec::with_capacity(n as usize);
+    for i in 0..n as usize {
+        let (state, _) = DistributedKeyGeneration::<RoundOne, C>::bootstrap(
+            params,
+            &dh_secret_keys[i],
+            &participants[i].index,
+            &coefficients[i],
+            &participants,
+            rng,
+        )?;
+        states.push(state);
+    }
+
+    let mut group_key = None;
+    let mut signing_keys = Vec::with_capacity(n as usize);
+    for (i, state) in states.iter().enumerate() {
+        let mut my_encrypted_shares = Vec::with_capacity(n as usize);
+        for other in states.iter() {
+            my_encrypted_shares.push(
+                other
+                    .their_encrypted_secret_shares()?
+                    .get(i)
+                    .ok_or(Error::MissingShares)?
+                    .clone(),
+            );
+        }
+
+        let (key, signing_key) = state
+            .clone()
+            .to_round_two(my_encrypted_shares, rng)?
+            .finish()?;
+        if group_key.get_or_insert(key) != &key {
+            return Err(Error::InvalidGroupKey);
+        }
+        signing_keys.push(signing_key);
+    }
+
+    Ok((group_key.ok_or(Error::InvalidGroupKey)?, signing_keys))
+}
+
+/// Check that the ciphersuite `C` supports a full `t`-out-of-`n` distributed key
+/// generation session, whose keys survive a serialization roundtrip, and such that
+/// any `t` consecutive signing keys interpolate to the group key.
+pub fn check_key_generation<C: CipherSuite>(n: u32, t: u32) -> FrostResult<C, ()> {
+    let params = ThresholdParameters::new(n, t)?;
+    let invalid = |what: &str| Err(Error::Custom(what.to_string()));
+
+    let (group_key, signing_keys) = generate_keys(&params)?;
+    if signing_keys.iter().map(|key| key.index).ne(1..=params.n) {
+        return invalid("The signing keys do not match the participants");
+    }
+
+    for signing_key in signing_keys.iter() {
+        let bytes = signing_key.to_bytes()?;
+        if IndividualSigningKey::<C>::from_bytes(&bytes)?.to_bytes()? != bytes {
+            return invalid("Signing keys do not survive a serialization roundtrip");
+        }
+    }
+
+    let package = PublicKeyPackage::new(
+        params,
+        group_key,
+        signing_keys.iter().map(|key| key.to_public()).collect(),
+    );
+    let bytes = package.to_bytes()?;
+    if PublicKeyPackage::<C>::from_bytes(&bytes)? != package {
+        return invalid("Public key packages do not survive a serialization roundtrip");
+    }
+
+    for subset in signing_keys.windows(t as usize) {
+        let indices: Vec<u32> = subset.iter().map(|key| key.index).collect();
+
+        let mut secret = Scalar::<C>::from(0u64);
+        for signing_key in subset {
+            secret += calculate_lagrange_coefficients::<C>(signing_key.index, &indices)?
+                * signing_key.key;
+        }
+        if C::G::generator() * secret != group_key.key {
+            return invalid("The signing keys do not interpolate to the group key");
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the ciphersuite `C` supports a full `t`-out-of-`n` distributed key
+/// generation session, followed by a signing session of its first `t` participants.
+pub fn check_signing<C: CipherSuite>(n: u32, t: u32) -> FrostResult<C, ()> {
+    let params = ThresholdParameters::new(n, t)?;
+    let rng = OsRng;
+
+    let (group_key, signing_keys) = generate_keys(&params)?;
+
+    let message = b"ICE-FROST conformance";
+    let message_hash = C::h4(message)?;
+
+    let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
+    let mut secret_comshares = Vec::with_capacity(t as usize);
+    for signing_key in signing_keys.iter().take(t as usize) {
+        let (public_comshares, secret_comshare) =
+            generate_commitment_share_lists(rng, signing_key, 1)?;
+        aggregator.include_signer(
+            signing_key.index,
+            public_comshares.commitments[0],
+            signing_key.into(),
+        )?;
+        secret_comshares.push(secret_comshare);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    for (signing_key, secret_comshare) in signing_keys.iter().zip(secret_comshares.iter_mut()) {
+        let partial = signing_key.sign(
+            message_hash.as_ref(),
+            &group_key,
+            secret_comshare,
+            0,
+            &signers,
+        )?;
+        aggregator.include_partial_signature(partial)?;
+    }
+
+    let signature = aggregator.finalize()?.aggregate()?;
+    signature.verify(&group_key, message_hash.as_ref())?;
+
+    ThresholdSignature::from_bytes(&signature.to_bytes()?)?
+        .verify(&group_key, message_hash.as_ref())
+}
@@ -0,0 +1,447 @@
+//! A simulator of network partitions among the participants of an ICE-FROST group.
+//!
+//! The [`Simulator`] splits the participants of a group into disjoint sides, which
+//! can only reach their own members, and lets each side attempt a distributed key
+//! generation session and a signing session on its own. The invariant checks of
+//! this module are public, for downstream integrators to assert the same properties
+//! when chaos-testing their own orchestration layer:
+//!
+//! * [`check_group_keys`]: no side lacking some participants completes a
+//!   distributed key generation session, hence no divergent group keys exist.
+//! * [`check_signatures`]: no side lacking a quorum produces a signature, and
+//!   signatures of distinct sessions never share their nonce commitment.
+//!
+//! Sides lacking a quorum still attempt to sign, their coordinator choosing the
+//! commitment shares published before the partition by signers of other sides. As
+//! signers only sign a [`SigningPackage`] once all of its signers echoed the same
+//! package hash, such attempts fail with an [`Error::InconsistentSigningPackage`].
+
+use rand::rngs::OsRng;
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::{DealerRole, DistributedKeyGeneration, Participant, RoundOne};
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey};
+use crate::parameters::ThresholdParameters;
+use crate::sign::{
+    generate_commitment_share_lists, signing_session_id, PublicCommitmentShareList,
+    SecretCommitmentShareList, SignatureAggregator, Signer, SigningPackage, ThresholdSignature,
+};
+use crate::utils::{ToString, Vec};
+use crate::{Error, FrostResult};
+
+use super::conformance::generate_keys;
+
+/// The outcome of a signing session completed by a side of a partition.
+#[derive(Debug, Eq, PartialEq)]
+pub struct SideSignature<C: CipherSuite> {
+    /// The identifier of the signing session, as given by [`signing_session_id`].
+    pub session_id: Vec<u8>,
+    /// The resulting signature.
+    pub signature: ThresholdSignature<C>,
+}
+
+/// The outcomes of the sessions attempted by each side of a partition.
+#[derive(Debug)]
+pub struct PartitionReport<C: CipherSuite> {
+    /// The sorted indices of the members of each side.
+    pub sides: Vec<Vec<u32>>,
+    /// The group key obtained by each side from its own distributed key generation
+    /// session, if it completed.
+    pub group_keys: Vec<Option<GroupVerifyingKey<C>>>,
+    /// The signature produced by each side on the simulated message, if any.
+    pub signatures: Vec<Option<SideSignature<C>>>,
+}
+
+impl<C: CipherSuite> PartitionReport<C> {
+    /// Run all invariant checks of this module against this report.
+    pub fn check_invariants(
+        &self,
+        parameters: &ThresholdParameters<C>,
+        group_key: &GroupVerifyingKey<C>,
+        message_hash: &[u8],
+    ) -> FrostResult<C, ()> {
+        check_group_keys(parameters, &self.sides, &self.group_keys)?;
+        check_signatures(
+            parameters,
+            &self.sides,
+            &self.signatures,
+            group_key,
+            message_hash,
+        )
+    }
+}
+
+/// A simulator of network partitions among the participants of an ICE-FROST group.
+#[derive(Debug)]
+pub struct Simulator<C: CipherSuite> {
+    parameters: ThresholdParameters<C>,
+    group_key: GroupVerifyingKey<C>,
+    signing_keys: Vec<IndividualSigningKey<C>>,
+}
+
+impl<C: CipherSuite> Simulator<C> {
+    /// Construct a new [`Simulator`], running a distributed key generation session
+    /// among all participants of a group with the given `parameters`.
+    pub fn new(parameters: ThresholdParameters<C>) -> FrostResult<C, Self> {
+        let (group_key, signing_keys) = generate_keys(&parameters)?;
+
+        Ok(Self {
+            parameters,
+            group_key,
+            signing_keys,
+        })
+    }
+
+    /// The parameters of the simulated group.
+    pub fn parameters(&self) -> &ThresholdParameters<C> {
+        &self.parameters
+    }
+
+    /// The group key of the simulated group.
+    pub fn group_key(&self) -> &GroupVerifyingKey<C> {
+        &self.group_key
+    }
+
+    /// Split the participants into the given disjoint `sides`, and let each of them
+    /// attempt a new distributed key generation session, and a signing session of the
+    /// simulated group on the `message_hash`, on its own.
+    ///
+    /// All participants publish their commitment shares before the partition. The
+    /// coordinator of each side is its first member, who selects the members of its
+    /// side as signers if they form a quorum, or completes them with the first
+    /// participants of the other sides otherwise.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the [`PartitionReport`] of the sessions,
+    /// otherwise an [`Error::Custom`] if the `sides` are not disjoint, or contain an
+    /// unknown participant.
+    pub fn partition(
+        &self,
+        sides: &[Vec<u32>],
+        message_hash: &[u8],
+    ) -> FrostResult<C, PartitionReport<C>> {
+        let mut sides: Vec<Vec<u32>> = sides.to_vec();
+        for side in sides.iter_mut() {
+            side.sort_unstable();
+        }
+        let mut all_members: Vec<u32> = sides.iter().flatten().copied().collect();
+        let count = all_members.len();
+        all_members.sort_unstable();
+        all_members.dedup();
+        if all_members.len() != count
+            || sides.iter().any(|side| side.is_empty())
+            || all_members
+                .iter()
+                .any(|index| *index == 0 || *index > self.parameters.n)
+        {
+            return Err(Error::Custom(
+                "The sides of a partition must be disjoint non-empty sets of participants"
+                    .to_string(),
+            ));
+        }
+
+        let group_keys = sides
+            .iter()
+            .map(|side| self.side_key_generation(side).ok())
+            .collect();
+
+        let (published, mut secret_lists): (Vec<_>, Vec<_>) = self
+            .signing_keys
+            .iter()
+            .map(|key| generate_commitment_share_lists(OsRng, key, 1))
+            .collect::<FrostResult<C, Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let signatures = sides
+            .iter()
+            .map(|side| {
+                self.side_signing(side, message_hash, &published, &mut secret_lists)
+                    .ok()
+            })
+            .collect();
+
+        Ok(PartitionReport {
+            sides,
+            group_keys,
+            signatures,
+        })
+    }
+
+    /// Attempt a distributed key generation session among the members of a `side`.
+    fn side_key_generation(&self, side: &[u32]) -> FrostResult<C, GroupVerifyingKey<C>> {
+        let mut participants = Vec::with_capacity(side.len());
+        let mut coefficients = Vec::with_capacity(side.len());
+        let mut dh_secret_keys = Vec::with_capacity(side.len());
+        for index in side {
+            let (participant, coefficient, dh_sk) =
+                Participant::<C, DealerRole>::new_dealer(&self.parameters, *index, OsRng)?;
+            participants.push(participant);
+            coefficients.push(coefficient);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let mut states = Vec::with_capacity(side.len());
+        for i in 0..side.len() {
+            let (state, _) = DistributedKeyGeneration::<RoundOne, C>::bootstrap(
+                &self.parameters,
+                &dh_secret_keys[i],
+                &participants[i].index,
+                &coefficients[i],
+                &participants,
+                OsRng,
+            )?;
+            states.push(state);
+        }
+
+        let mut group_key = None;
+        for (i, state) in states.iter().enumerate() {
+            let mut my_encrypted_shares = Vec::with_capacity(side.len());
+            for other in states.iter() {
+                my_encrypted_shares.push(
+                    other
+                        .their_encrypted_secret_shares()?
+                        .get(i)
+                        .ok_or(Error::MissingShares)?
+                        .clone(),
+                );
+            }
+
+            let (key, _) = state
+                .clone()
+                .to_round_two(my_encrypted_shares, OsRng)?
+                .finish()?;
+            if group_key.get_or_insert(key) != &key {
+                return Err(Error::InvalidGroupKey);
+            }
+        }
+
+        group_key.ok_or(Error::InvalidGroupKey)
+    }
+
+    /// Attempt a signing session coordinated by the first member of a `side`, where
+    /// only the members of the `side` receive the package and exchange their echoes.
+    fn side_signing(
+        &self,
+        side: &[u32],
+        message_hash: &[u8],
+        published: &[PublicCommitmentShareList<C>],
+        secret_lists: &mut [SecretCommitmentShareList<C>],
+    ) -> FrostResult<C, SideSignature<C>> {
+        let t = self.parameters.t as usize;
+        let mut signer_indices = side.to_vec();
+        for index in 1..=self.parameters.n {
+            if signer_indices.len() >= t {
+                break;
+            }
+            if !signer_indices.contains(&index) {
+                signer_indices.push(index);
+            }
+        }
+        signer_indices.sort_unstable();
+
+        let key = |index: u32| &self.signing_keys[index as usize - 1];
+        let coordinator = key(side[0]);
+
+        let signers = signer_indices
+            .iter()
+            .map(|index| {
+                Ok(Signer {
+                    participant_index: *index,
+                    published_commitment_share: *published[*index as usize - 1]
+                        .commitments
+                        .first()
+                        .ok_or(Error::MissingCommitmentShares)?,
+                })
+            })
+            .collect::<FrostResult<C, Vec<_>>>()?;
+        let package = SigningPackage::new(message_hash, signers.clone(), coordinator, OsRng)?;
+
+        // Only the signers of this side receive the package, and echo its hash.
+        let echoes: Vec<_> = signer_indices
+            .iter()
+            .filter(|index| side.contains(index))
+            .map(|index| Ok((*index, package.package_hash()?)))
+            .collect::<FrostResult<C, _>>()?;
+
+        let mut aggregator = SignatureAggregator::new_prehashed(
+            self.parameters,
+            self.group_key,
+            message_hash,
+            None,
+        )?;
+        for signer in signers.iter() {
+            aggregator.include_signer(
+                signer.participant_index,
+                signer.published_commitment_share,
+                key(signer.participant_index).to_public(),
+            )?;
+        }
+        for index in signer_indices.iter().filter(|index| side.contains(index)) {
+            let partial = key(*index).sign_package(
+                message_hash,
+                &self.group_key,
+                &package,
+                &coordinator.to_public(),
+                &echoes,
+                &mut secret_lists[*index as usize - 1],
+            )?;
+            aggregator.include_partial_signature(partial)?;
+        }
+
+        let signature = aggregator.finalize()?.aggregate()?;
+
+        Ok(SideSignature {
+            session_id: signing_session_id(message_hash, &signers)?
+                .as_ref()
+                .to_vec(),
+            signature,
+        })
+    }
+}
+
+/// Check that no side lacking some of the `parameters.n` participants completed a
+/// distributed key generation session, and that all sides which did agree on the
+/// same group key.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value indicates that the invariant holds,
+/// otherwise an [`Error::Custom`] describing the violation.
+pub fn check_group_keys<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    sides: &[Vec<u32>],
+    group_keys: &[Option<GroupVerifyingKey<C>>],
+) -> FrostResult<C, ()> {
+    for (side, group_key) in sides.iter().zip(group_keys.iter()) {
+        if group_key.is_some() && side.len() < parameters.n as usize {
+            return Err(Error::Custom(
+                "A side of the partition completed a key generation session on its own".to_string(),
+            ));
+        }
+    }
+
+    let mut completed = group_keys.iter().flatten();
+    if let Some(first) = completed.next() {
+        if completed.any(|group_key| group_key != first) {
+            return Err(Error::Custom(
+                "The sides obtained divergent group keys".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that no side lacking a quorum of `parameters.t` members produced a
+/// signature, that all produced signatures are valid under the `group_key`, and
+/// that signatures of distinct sessions do not share their nonce commitment.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value indicates that the invariant holds,
+/// otherwise an [`Error::Custom`] describing the violation.
+pub fn check_signatures<C: CipherSuite>(
+    parameters: &ThresholdParameters<C>,
+    sides: &[Vec<u32>],
+    signatures: &[Option<SideSignature<C>>],
+    group_key: &GroupVerifyingKey<C>,
+    message_hash: &[u8],
+) -> FrostResult<C, ()> {
+    for (side, signature) in sides.iter().zip(signatures.iter()) {
+        if let Some(signature) = signature {
+            if side.len() < parameters.t as usize {
+                return Err(Error::Custom(
+                    "A side of the partition without a quorum produced a signature".to_string(),
+                ));
+            }
+            signature.signature.verify(group_key, message_hash)?;
+        }
+    }
+
+    let produced: Vec<&SideSignature<C>> = signatures.iter().flatten().collect();
+    for (i, first) in produced.iter().enumerate() {
+        for second in produced[i + 1..].iter() {
+            if first.session_id == second.session_id
+                || first.signature.group_commitment == second.signature.group_commitment
+            {
+                return Err(Error::Custom(
+                    "Two signatures were produced for the same session or nonce".to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::testing::Secp256k1Sha256;
+
+    #[test]
+    fn network_partitions() {
+        let message_hash = Secp256k1Sha256::h4(b"partitioned message").unwrap();
+
+        // A majority threshold leaves at most one side able to sign.
+        let simulator =
+            Simulator::<Secp256k1Sha256>::new(ThresholdParameters::new(5, 3).unwrap()).unwrap();
+        let report = simulator
+            .partition(&[vec![1, 2, 3], vec![4, 5]], message_hash.as_ref())
+            .unwrap();
+        assert!(report
+            .check_invariants(
+                simulator.parameters(),
+                simulator.group_key(),
+                message_hash.as_ref()
+            )
+            .is_ok());
+        assert_eq!(report.group_keys, vec![None, None]);
+        assert!(report.signatures[0].is_some());
+        assert!(report.signatures[1].is_none());
+
+        // A minority threshold lets both sides sign, in distinct sessions.
+        let simulator =
+            Simulator::<Secp256k1Sha256>::new(ThresholdParameters::new(5, 2).unwrap()).unwrap();
+        let report = simulator
+            .partition(&[vec![1, 2, 3], vec![4, 5]], message_hash.as_ref())
+            .unwrap();
+        assert!(report
+            .check_invariants(
+                simulator.parameters(),
+                simulator.group_key(),
+                message_hash.as_ref()
+            )
+            .is_ok());
+        assert!(report.signatures.iter().all(Option::is_some));
+
+        // Without a partition, the key generation session completes.
+        let mut report = simulator
+            .partition(&[vec![1, 2, 3, 4, 5]], message_hash.as_ref())
+            .unwrap();
+        assert!(report.group_keys[0].is_some());
+
+        // The invariant checks catch violations.
+        let sides = [vec![1], vec![2, 3, 4, 5]];
+        assert!(check_group_keys(
+            simulator.parameters(),
+            &sides,
+            &[report.group_keys[0], None]
+        )
+        .is_err());
+        assert!(check_signatures(
+            simulator.parameters(),
+            &sides,
+            &[report.signatures.remove(0), None],
+            simulator.group_key(),
+            message_hash.as_ref()
+        )
+        .is_err());
+
+        assert!(simulator
+            .partition(&[vec![1, 2], vec![2, 3]], message_hash.as_ref())
+            .is_err());
+    }
+}
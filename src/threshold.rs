@@ -0,0 +1,363 @@
+//! The threshold module, exposing the key material of ICE-FROST groups to threshold
+//! signature schemes other than FROST, such as threshold ECDSA.
+//!
+//! The output of a distributed key generation session, i.e. the [`IndividualSigningKey`]
+//! of a participant along with the [`PublicKeyPackage`] of its group, is a Shamir sharing
+//! of the group secret key, which most threshold signature protocols start from. A
+//! [`ThresholdKeyShare`] bundles this output after checking its consistency, and converts
+//! it into the additive shares over a set of signers which these protocols operate on,
+//! with the [`lagrange_coefficient`] of each signer.
+//!
+//! External round protocols then implement the [`ThresholdScheme`] trait, consuming
+//! [`ThresholdKeyShare`]s and the indices of the signers of a session, e.g. as given by
+//! [`ParticipantRoster::indices`](crate::dkg::ParticipantRoster::indices), without any
+//! format conversion.
+//!
+//! [`IndividualSigningKey`]: crate::keys::IndividualSigningKey
+//! [`PublicKeyPackage`]: crate::keys::PublicKeyPackage
+//! [`ThresholdKeyShare`]: crate::threshold::ThresholdKeyShare
+//! [`lagrange_coefficient`]: crate::threshold::lagrange_coefficient
+//! [`ThresholdScheme`]: crate::threshold::ThresholdScheme
+
+use crate::ciphersuite::CipherSuite;
+use crate::keys::{GroupVerifyingKey, IndividualSigningKey, PublicKeyPackage};
+use crate::parameters::ThresholdParameters;
+use crate::utils::calculate_lagrange_coefficients;
+use crate::{Element, Error, FrostResult, Scalar};
+
+/// Compute the Lagrange coefficient of the participant with the given `index`, for
+/// interpolating a polynomial at zero from its evaluations at all the given `indices`.
+///
+/// # Returns
+///
+/// A [`FrostResult`] whose [`Ok`] value is the Lagrange coefficient, otherwise an
+/// [`Error`] if `indices` contain zero or duplicates, or do not contain `index`.
+pub fn lagrange_coefficient<C: CipherSuite>(
+    index: u32,
+    indices: &[u32],
+) -> FrostResult<C, Scalar<C>> {
+    if !indices.contains(&index) {
+        return Err(Error::UnknownParticipant(index));
+    }
+
+    calculate_lagrange_coefficients::<C>(index, indices).map(Scalar)
+}
+
+/// The key share of a participant of an ICE-FROST group, as obtained at the end of a
+/// distributed key generation session, for use by any [`ThresholdScheme`].
+#[derive(Clone, Debug)]
+pub struct ThresholdKeyShare<C: CipherSuite> {
+    /// The long-lived secret share of the participant.
+    pub(crate) signing_key: IndividualSigningKey<C>,
+    /// The public material of the group of the participant.
+    pub(crate) public_keys: PublicKeyPackage<C>,
+}
+
+impl<C: CipherSuite> ThresholdKeyShare<C> {
+    /// Instantiates a new [`ThresholdKeyShare`] from the `group_key` and `signing_key`
+    /// returned by a distributed key generation session, along with the
+    /// [`PublicKeyPackage`] of the group.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the new [`ThresholdKeyShare`], otherwise an
+    /// [`Error::InvalidGroupKey`] if the `group_key` does not match the `public_keys`, an
+    /// [`Error::UnknownParticipant`] if the participant of the `signing_key` is not part of
+    /// the group, or an [`Error::ShareVerificationError`] if its share does not match its
+    /// verifying key.
+    pub fn from_dkg_output(
+        group_key: &GroupVerifyingKey<C>,
+        signing_key: IndividualSigningKey<C>,
+        public_keys: PublicKeyPackage<C>,
+    ) -> FrostResult<C, Self> {
+        if public_keys.group_key() != group_key {
+            return Err(Error::InvalidGroupKey);
+        }
+
+        let verifying_key = public_keys
+            .verifying_key(signing_key.index)
+            .ok_or(Error::UnknownParticipant(signing_key.index))?;
+        if *verifying_key != signing_key.to_public() {
+            return Err(Error::ShareVerificationError);
+        }
+
+        Ok(Self {
+            signing_key,
+            public_keys,
+        })
+    }
+
+    /// The index of the participant holding this [`ThresholdKeyShare`].
+    pub fn index(&self) -> u32 {
+        self.signing_key.index
+    }
+
+    /// The [`ThresholdParameters`] of the group of this [`ThresholdKeyShare`].
+    pub fn parameters(&self) -> &ThresholdParameters<C> {
+        self.public_keys.parameters()
+    }
+
+    /// The [`GroupVerifyingKey`] of the group of this [`ThresholdKeyShare`].
+    pub fn group_key(&self) -> &GroupVerifyingKey<C> {
+        self.public_keys.group_key()
+    }
+
+    /// The [`PublicKeyPackage`] of the group of this [`ThresholdKeyShare`].
+    pub fn public_keys(&self) -> &PublicKeyPackage<C> {
+        &self.public_keys
+    }
+
+    /// The Shamir share of the group secret key held by the participant.
+    pub fn secret_share(&self) -> Scalar<C> {
+        Scalar(self.signing_key.key)
+    }
+
+    /// The public share of the participant with the given `index`, i.e. its Shamir
+    /// share of the group secret key times the generator, if it is part of the group.
+    pub fn verification_share(&self, index: u32) -> Option<Element<C>> {
+        self.public_keys
+            .verifying_key(index)
+            .map(|verifying_key| Element(verifying_key.share))
+    }
+
+    /// Check that the given `signers`, sorted by strictly increasing participant index,
+    /// are enough members of the group to sign, and include the holder of this share.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates valid `signers`, otherwise an
+    /// [`Error::UnsortedSigners`], an [`Error::UnknownParticipant`] for the first signer
+    /// not part of the group, or an [`Error::InvalidNumberOfParticipants`].
+    pub fn check_signers(&self, signers: &[u32]) -> FrostResult<C, ()> {
+        if signers.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(Error::UnsortedSigners);
+        }
+
+        if let Some(index) = signers
+            .iter()
+            .find(|index| self.public_keys.verifying_key(**index).is_none())
+        {
+            return Err(Error::UnknownParticipant(*index));
+        }
+
+        if signers.binary_search(&self.index()).is_err() {
+            return Err(Error::UnknownParticipant(self.index()));
+        }
+
+        if signers.len() < self.parameters().t as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                signers.len(),
+                self.parameters().t,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Convert this share into an additive share of the group secret key over the
+    /// given `signers`, i.e. its Shamir share times its Lagrange coefficient, such that
+    /// the additive shares of all `signers` sum up to the group secret key.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the additive share, otherwise the same
+    /// error as [`ThresholdKeyShare::check_signers`].
+    pub fn additive_share(&self, signers: &[u32]) -> FrostResult<C, Scalar<C>> {
+        self.check_signers(signers)?;
+        let lambda = calculate_lagrange_coefficients::<C>(self.index(), signers)?;
+
+        Ok(Scalar(lambda * self.signing_key.key))
+    }
+
+    /// Compute the public counterpart of the additive share over the given `signers`
+    /// of the signer with the given `index`, as obtained by this signer with
+    /// [`ThresholdKeyShare::additive_share`].
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value is the public additive share, otherwise the
+    /// same error as [`ThresholdKeyShare::check_signers`], or an
+    /// [`Error::UnknownParticipant`] if `index` is not one of the `signers`.
+    pub fn additive_verification_share(
+        &self,
+        index: u32,
+        signers: &[u32],
+    ) -> FrostResult<C, Element<C>> {
+        self.check_signers(signers)?;
+        let lambda = lagrange_coefficient::<C>(index, signers)?;
+        let share = self
+            .verification_share(index)
+            .ok_or(Error::UnknownParticipant(index))?;
+
+        Ok(share * lambda)
+    }
+}
+
+/// A threshold signature scheme run by the participants of an ICE-FROST group over
+/// their [`ThresholdKeyShare`]s, such as a threshold ECDSA round protocol.
+///
+/// ICE-FROST does not drive the rounds of external schemes: an implementation only
+/// starts the signing session of a participant, whose messages it then exchanges
+/// on its own, and verifies the resulting signatures.
+pub trait ThresholdScheme<C: CipherSuite> {
+    /// The signature produced by a signing session of this scheme.
+    type Signature;
+    /// The state of a participant in a signing session of this scheme.
+    type Session;
+
+    /// Start the signing session of `message` of the holder of `key_share`, among the
+    /// given `signers`, sorted by strictly increasing participant index.
+    ///
+    /// Implementations are expected to reject `signers` failing
+    /// [`ThresholdKeyShare::check_signers`].
+    fn start_session(
+        &self,
+        key_share: &ThresholdKeyShare<C>,
+        signers: &[u32],
+        message: &[u8],
+    ) -> FrostResult<C, Self::Session>;
+
+    /// Verify the `signature` of `message` under the given `group_key`.
+    fn verify(
+        &self,
+        group_key: &GroupVerifyingKey<C>,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> FrostResult<C, ()>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keys::IndividualVerifyingKey;
+    use crate::testing::conformance::generate_keys;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::Vec;
+
+    /// A trivial scheme whose sessions output the public additive share of their
+    /// participant, and whose signature is the sum of these shares.
+    struct AdditiveKeyScheme;
+
+    impl ThresholdScheme<Secp256k1Sha256> for AdditiveKeyScheme {
+        type Signature = Element<Secp256k1Sha256>;
+        type Session = Element<Secp256k1Sha256>;
+
+        fn start_session(
+            &self,
+            key_share: &ThresholdKeyShare<Secp256k1Sha256>,
+            signers: &[u32],
+            _message: &[u8],
+        ) -> FrostResult<Secp256k1Sha256, Self::Session> {
+            Ok(Element::generator() * key_share.additive_share(signers)?)
+        }
+
+        fn verify(
+            &self,
+            group_key: &GroupVerifyingKey<Secp256k1Sha256>,
+            _message: &[u8],
+            signature: &Self::Signature,
+        ) -> FrostResult<Secp256k1Sha256, ()> {
+            match group_key.element() == *signature {
+                true => Ok(()),
+                false => Err(Error::InvalidSignature),
+            }
+        }
+    }
+
+    fn key_shares(n: u32, t: u32) -> Vec<ThresholdKeyShare<Secp256k1Sha256>> {
+        let params = ThresholdParameters::new(n, t).unwrap();
+        let (group_key, signing_keys) = generate_keys::<Secp256k1Sha256>(&params).unwrap();
+        let public_keys = PublicKeyPackage::new(
+            params,
+            group_key,
+            signing_keys
+                .iter()
+                .map(IndividualVerifyingKey::from)
+                .collect(),
+        );
+
+        signing_keys
+            .into_iter()
+            .map(|signing_key| {
+                ThresholdKeyShare::from_dkg_output(&group_key, signing_key, public_keys.clone())
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn additive_shares_3_out_of_5() {
+        let key_shares = key_shares(5, 3);
+        let signers = [1, 3, 4];
+
+        let mut sessions = Vec::new();
+        for index in signers {
+            let key_share = &key_shares[index as usize - 1];
+            let session = AdditiveKeyScheme
+                .start_session(key_share, &signers, b"message")
+                .unwrap();
+            assert_eq!(
+                session,
+                key_shares[0]
+                    .additive_verification_share(index, &signers)
+                    .unwrap()
+            );
+            sessions.push(session);
+        }
+
+        let signature = sessions.into_iter().sum();
+        assert!(AdditiveKeyScheme
+            .verify(key_shares[0].group_key(), b"message", &signature)
+            .is_ok());
+
+        assert_eq!(
+            key_shares[1].check_signers(&signers),
+            Err(Error::UnknownParticipant(2))
+        );
+        assert_eq!(
+            key_shares[0].check_signers(&[1, 3]),
+            Err(Error::InvalidNumberOfParticipants(2, 3))
+        );
+        assert_eq!(
+            key_shares[0].check_signers(&[1, 4, 3]),
+            Err(Error::UnsortedSigners)
+        );
+        assert_eq!(
+            key_shares[0].check_signers(&[1, 3, 6]),
+            Err(Error::UnknownParticipant(6))
+        );
+    }
+
+    #[test]
+    fn mismatching_dkg_output() {
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let (group_key, signing_keys) = generate_keys::<Secp256k1Sha256>(&params).unwrap();
+        let mut verifying_keys: Vec<IndividualVerifyingKey<Secp256k1Sha256>> = signing_keys
+            .iter()
+            .map(IndividualVerifyingKey::from)
+            .collect();
+        verifying_keys.swap(0, 1);
+        verifying_keys[0].index = 1;
+        verifying_keys[1].index = 2;
+        let public_keys = PublicKeyPackage::new(params, group_key, verifying_keys);
+
+        assert_eq!(
+            ThresholdKeyShare::from_dkg_output(
+                &group_key,
+                signing_keys[0].clone(),
+                public_keys.clone()
+            )
+            .unwrap_err(),
+            Error::ShareVerificationError
+        );
+        assert_eq!(
+            ThresholdKeyShare::from_dkg_output(
+                &GroupVerifyingKey::new(Element::generator()),
+                signing_keys[2].clone(),
+                public_keys
+            )
+            .unwrap_err(),
+            Error::InvalidGroupKey
+        );
+    }
+}
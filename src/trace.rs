@@ -0,0 +1,94 @@
+//! Feature-gated tracing of the protocol transitions, validation failures and
+//! zeroization events of ICE-FROST sessions.
+//!
+//! Events are emitted through the `log` crate with the `log` feature, and through
+//! `defmt` with the `defmt` feature, for embedded targets. They only ever carry
+//! participant indices and counts, never secret material, hence their format
+//! strings only use the `{}` placeholder, supported by both backends.
+
+/// Emit a tracing event at the given `log` and `defmt` level, one of `debug`,
+/// `info` or `warn`.
+///
+/// Without any tracing feature, the arguments are only borrowed, so that values
+/// computed for tracing purposes are not reported as unused.
+macro_rules! trace {
+    ($level:ident, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "log")]
+        log::$level!($fmt $(, $arg)*);
+        #[cfg(feature = "defmt")]
+        defmt::$level!($fmt $(, $arg)*);
+        #[cfg(not(any(feature = "log", feature = "defmt")))]
+        {
+            $(let _ = &$arg;)*
+        }
+    }};
+}
+
+#[cfg(all(test, feature = "log"))]
+mod test {
+    use crate::dkg::{DealerRole, DistributedKeyGeneration, Participant, RoundOne};
+    use crate::parameters::ThresholdParameters;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::{String, ToString, Vec};
+
+    use rand::rngs::OsRng;
+    use std::sync::Mutex;
+
+    /// A logger recording the messages of all events.
+    struct RecordingLogger(Mutex<Vec<String>>);
+
+    impl log::Log for RecordingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: RecordingLogger = RecordingLogger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn dkg_transitions_are_traced() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let params = ThresholdParameters::new(1, 1).unwrap();
+        let rng = OsRng;
+
+        let (p1, p1_coefficients, p1_dh_sk) =
+            Participant::<Secp256k1Sha256, DealerRole>::new_dealer(&params, 1, rng).unwrap();
+        let (p1_state, _) = DistributedKeyGeneration::<RoundOne, Secp256k1Sha256>::bootstrap(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1_coefficients,
+            core::slice::from_ref(&p1),
+            rng,
+        )
+        .unwrap();
+        let p1_my_encrypted_secret_shares =
+            p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let (_, p1_secret_key) = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, rng)
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        let messages = LOGGER.0.lock().unwrap();
+        for event in [
+            "dkg participant 1 entered round one",
+            "dkg participant 1 entered round two",
+            "dkg participant 1 derived its signing key",
+        ] {
+            assert!(messages.iter().any(|message| message.contains(event)));
+        }
+
+        // Secrets are never traced.
+        let secret = p1_secret_key.key.to_string();
+        assert!(messages.iter().all(|message| !message.contains(&secret)));
+    }
+}
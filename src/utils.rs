@@ -8,7 +8,8 @@ pub use alloc::{
     boxed::Box,
     collections::btree_map::BTreeMap,
     string::{String, ToString},
-    vec::{self, Vec},
+    vec,
+    vec::Vec,
 };
 
 #[cfg(feature = "std")]
@@ -17,20 +18,24 @@ pub use std::{
     boxed::Box,
     collections::btree_map::BTreeMap,
     string::{String, ToString},
-    vec::{self, Vec},
+    vec,
+    vec::Vec,
 };
 
 use crate::{ciphersuite::CipherSuite, HASH_SEC_PARAM};
 
 use crate::{Error, FrostResult};
-use ark_ec::Group;
+use ark_ec::{CurveGroup, Group};
 use ark_ff::Field;
 
+use ark_serialize::CanonicalSerialize;
 use digest::Digest;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
 
-// Convenient type alias to reduce verbosity when needing to access the
-// internal ScalarField type of a `CipherSuite`.
-pub(crate) type Scalar<C> = <<C as CipherSuite>::G as Group>::ScalarField;
+/// Convenient type alias to reduce verbosity when needing to access the
+/// internal ScalarField type of a `CipherSuite`.
+pub type Scalar<C> = <<C as CipherSuite>::G as Group>::ScalarField;
 
 /// Interpolate a polynomial with Lagrange method.
 ///
@@ -47,7 +52,7 @@ pub(crate) fn calculate_lagrange_coefficients<C: CipherSuite>(
     sorted_indices.sort();
     sorted_indices.dedup();
     if sorted_indices.len() != all_indices.len() {
-        return Err(Error::Custom("Duplicate indices provided".to_string()));
+        return Err(Error::DuplicateIndices);
     }
 
     // Also handles the case where `my_index` is 0.
@@ -55,6 +60,11 @@ pub(crate) fn calculate_lagrange_coefficients<C: CipherSuite>(
         return Err(Error::IndexIsZero);
     }
 
+    // A lone signer holds the whole secret, e.g. in groups with a threshold of 1.
+    if sorted_indices == [my_index] {
+        return Ok(Scalar::<C>::ONE);
+    }
+
     let mut numerator = Scalar::<C>::ONE;
     let mut denominator = Scalar::<C>::ONE;
 
@@ -70,10 +80,46 @@ pub(crate) fn calculate_lagrange_coefficients<C: CipherSuite>(
         denominator *= s - my_index_field;
     }
 
-    Ok(numerator
-        * denominator
-            .inverse()
-            .ok_or_else(|| Error::Custom("Duplicate indices provided".to_string()))?)
+    Ok(numerator * denominator.inverse().ok_or(Error::DuplicateIndices)?)
+}
+
+/// Normalize the given `points` to affine coordinates at the cost of a single field
+/// inversion, with Montgomery's batch inversion trick, instead of one inversion per
+/// point as when calling `into_affine()` on each of them.
+pub(crate) fn points_to_affine_batch<C: CipherSuite>(
+    points: &[C::G],
+) -> Vec<<C::G as CurveGroup>::Affine> {
+    C::G::normalize_batch(points)
+}
+
+/// Reserve capacity for at least `additional` more elements in the given `vector`.
+///
+/// With the `fallible-alloc` feature, an allocation failure is reported as an
+/// [`Error::AllocationFailure`] instead of aborting the process.
+pub(crate) fn try_reserve<C: CipherSuite, T>(
+    vector: &mut Vec<T>,
+    additional: usize,
+) -> FrostResult<C, ()> {
+    #[cfg(feature = "fallible-alloc")]
+    return vector
+        .try_reserve(additional)
+        .map_err(|_| Error::AllocationFailure);
+
+    #[cfg(not(feature = "fallible-alloc"))]
+    {
+        vector.reserve(additional);
+        Ok(())
+    }
+}
+
+/// Allocate an empty vector with room for `capacity` elements, for the buffers of
+/// DKG sessions whose size grows with the number of participants, as with
+/// [`try_reserve`].
+pub(crate) fn try_with_capacity<C: CipherSuite, T>(capacity: usize) -> FrostResult<C, Vec<T>> {
+    let mut vector = Vec::new();
+    try_reserve::<C, T>(&mut vector, capacity)?;
+
+    Ok(vector)
 }
 
 pub fn hash_to_field<C: CipherSuite>(
@@ -87,6 +133,26 @@ pub fn hash_to_field<C: CipherSuite>(
     Ok(h.hash_to_field(message_to_hash, 1)[0])
 }
 
+/// Compare two values in constant time, through their compressed encodings.
+///
+/// The equality operators of field elements and group elements are not guaranteed
+/// to run in constant time, and must not be used to compare secret values.
+pub(crate) fn ct_eq_encoded<T: CanonicalSerialize>(a: &T, b: &T) -> Choice {
+    let mut a_bytes = Vec::new();
+    let mut b_bytes = Vec::new();
+    let choice = match (
+        a.serialize_compressed(&mut a_bytes),
+        b.serialize_compressed(&mut b_bytes),
+    ) {
+        (Ok(()), Ok(())) => a_bytes.as_slice().ct_eq(b_bytes.as_slice()),
+        _ => Choice::from(0),
+    };
+    a_bytes.zeroize();
+    b_bytes.zeroize();
+
+    choice
+}
+
 pub fn hash_to_array<C: CipherSuite>(
     context_string: &[u8],
     message_to_hash: &[u8],
@@ -95,8 +161,27 @@ pub fn hash_to_array<C: CipherSuite>(
     h.update(context_string);
     h.update(message_to_hash);
 
+    let digest = h.finalize();
+
     let mut output = C::HashOutput::default();
-    output.as_mut().copy_from_slice(h.finalize().as_slice());
+    let output_length = output.as_ref().len();
+    if !C::HASH_OUTPUT_POLICY.is_compatible(output_length, digest.len()) {
+        return Err(Error::Custom(
+            "The hash output size is incompatible with the inner hasher".to_string(),
+        ));
+    }
+
+    if digest.len() >= output_length {
+        output.as_mut().copy_from_slice(&digest[..output_length]);
+    } else {
+        // MGF1, seeded with the digest.
+        for (counter, chunk) in output.as_mut().chunks_mut(digest.len()).enumerate() {
+            let mut h = C::InnerHasher::new();
+            h.update(digest.as_slice());
+            h.update((counter as u32).to_be_bytes());
+            chunk.copy_from_slice(&h.finalize()[..chunk.len()]);
+        }
+    }
 
     Ok(output)
 }
@@ -106,6 +191,29 @@ mod test {
     use super::*;
 
     use crate::testing::Secp256k1Sha256;
+    use crate::{define_ciphersuite, Aes256Gcm, HashOutputPolicy};
+
+    define_ciphersuite! {
+        struct Secp256k1Sha512 {
+            group: ark_secp256k1::Projective,
+            hasher: sha2::Sha512,
+            hash_output: [u8; 32],
+            share_cipher: Aes256Gcm,
+            context_string: "ICE-FROST_SECP256K1_SHA512",
+            hash_output_policy: HashOutputPolicy::Truncate,
+        }
+    }
+
+    define_ciphersuite! {
+        struct Secp256k1Sha224 {
+            group: ark_secp256k1::Projective,
+            hasher: sha2::Sha224,
+            hash_output: [u8; 32],
+            share_cipher: Aes256Gcm,
+            context_string: "ICE-FROST_SECP256K1_SHA224",
+            hash_output_policy: HashOutputPolicy::Expand,
+        }
+    }
 
     #[test]
     fn invalid_lagrange_interpolation() {
@@ -146,4 +254,75 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn batch_affine_normalization() {
+        use ark_ec::Group;
+        use ark_ff::{UniformRand, Zero};
+        use ark_secp256k1::Projective;
+        use rand::rngs::OsRng;
+
+        let mut points: Vec<Projective> = (0..10).map(|_| Projective::rand(&mut OsRng)).collect();
+        points.push(Projective::zero());
+        points.push(Projective::generator().double());
+
+        let affine_points = points_to_affine_batch::<Secp256k1Sha256>(&points);
+        assert_eq!(affine_points.len(), points.len());
+        for (point, affine_point) in points.iter().zip(affine_points.iter()) {
+            assert_eq!(point.into_affine(), *affine_point);
+        }
+        assert!(points_to_affine_batch::<Secp256k1Sha256>(&[]).is_empty());
+    }
+
+    #[test]
+    fn hash_output_policies() {
+        assert!(HashOutputPolicy::Exact.is_compatible(32, 32));
+        assert!(!HashOutputPolicy::Exact.is_compatible(32, 64));
+        assert!(HashOutputPolicy::Truncate.is_compatible(32, 64));
+        assert!(!HashOutputPolicy::Truncate.is_compatible(32, 28));
+        assert!(HashOutputPolicy::Expand.is_compatible(32, 28));
+
+        let digest = sha2::Sha512::new()
+            .chain_update(b"context")
+            .chain_update(b"message")
+            .finalize();
+        let output = hash_to_array::<Secp256k1Sha512>(b"context", b"message").unwrap();
+        assert_eq!(output[..], digest[..32]);
+
+        let digest = sha2::Sha224::new()
+            .chain_update(b"context")
+            .chain_update(b"message")
+            .finalize();
+        let output = hash_to_array::<Secp256k1Sha224>(b"context", b"message").unwrap();
+        let first_block = sha2::Sha224::new()
+            .chain_update(digest)
+            .chain_update(0u32.to_be_bytes())
+            .finalize();
+        let second_block = sha2::Sha224::new()
+            .chain_update(digest)
+            .chain_update(1u32.to_be_bytes())
+            .finalize();
+        assert_eq!(output[..28], first_block[..]);
+        assert_eq!(output[28..], second_block[..4]);
+
+        assert!(crate::testing::conformance::run_all::<Secp256k1Sha512>().is_ok());
+        assert!(crate::testing::conformance::run_all::<Secp256k1Sha224>().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "fallible-alloc")]
+    fn fallible_allocation() {
+        let mut vector = try_with_capacity::<Secp256k1Sha256, u64>(16).unwrap();
+        assert!(vector.capacity() >= 16);
+
+        assert_eq!(
+            try_reserve::<Secp256k1Sha256, _>(&mut vector, usize::MAX),
+            Err(Error::AllocationFailure)
+        );
+        assert_eq!(
+            try_with_capacity::<Secp256k1Sha256, u64>(usize::MAX).unwrap_err(),
+            Error::AllocationFailure
+        );
+        assert!(vector.capacity() >= 16);
+    }
 }
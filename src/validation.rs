@@ -0,0 +1,242 @@
+//! The validation module defining the checks applied to group elements
+//! upon deserialization of ICE-FROST types.
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, Compress, Valid, Validate};
+
+use crate::ciphersuite::CipherSuite;
+use crate::{Error, FrostResult};
+
+/// The policy of a [`CipherSuite`] for validating deserialized group elements.
+///
+/// Regardless of the policy, the identity point is always rejected with
+/// [`Error::IdentityPoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointValidationPolicy {
+    /// Check that every deserialized point lies on the curve and in its prime-order
+    /// subgroup, rejecting it with [`Error::InvalidPoint`] otherwise.
+    Strict,
+    /// Skip the curve and subgroup membership checks of deserialized points, relying
+    /// on point decompression to only yield points on the curve.
+    ///
+    /// This is only suited to prime-order groups, such as Secp256k1, where all points
+    /// of the curve belong to the prime-order subgroup.
+    Fast,
+}
+
+/// A trait for types holding group elements, to be validated after deserialization.
+pub(crate) trait ValidatePoints<C: CipherSuite> {
+    /// Validate all group elements of this type according to the
+    /// [`PointValidationPolicy`] of the [`CipherSuite`].
+    fn validate_points(&self) -> FrostResult<C, ()>;
+}
+
+/// Validate a single group element according to the [`PointValidationPolicy`]
+/// of the [`CipherSuite`].
+pub(crate) fn validate_point<C: CipherSuite>(point: &C::G) -> FrostResult<C, ()> {
+    if point.is_zero() {
+        return Err(Error::IdentityPoint);
+    }
+
+    match C::POINT_VALIDATION_POLICY {
+        PointValidationPolicy::Strict => point.check().map_err(|_| Error::InvalidPoint),
+        PointValidationPolicy::Fast => Ok(()),
+    }
+}
+
+/// Validate the Diffie-Hellman public `key` of the participant with the given `index`.
+///
+/// On top of the checks of [`validate_point`], keys of small order, i.e. cancelled out
+/// by the cofactor of the curve, are rejected regardless of the [`PointValidationPolicy`],
+/// as the shares encrypted to such keys would be derived from a guessable shared key.
+pub(crate) fn validate_dh_public_key<C: CipherSuite>(index: u32, key: &C::G) -> FrostResult<C, ()> {
+    if key.is_zero() {
+        return Err(Error::IdentityDhPublicKey(index));
+    }
+    if key.into_affine().mul_by_cofactor_to_group().is_zero() {
+        return Err(Error::LowOrderDhPublicKey(index));
+    }
+
+    validate_point::<C>(key)
+}
+
+/// Validate all the provided group elements according to the [`PointValidationPolicy`]
+/// of the [`CipherSuite`].
+pub(crate) fn validate_points<'a, C: CipherSuite>(
+    points: impl IntoIterator<Item = &'a C::G>,
+) -> FrostResult<C, ()> {
+    points.into_iter().try_for_each(validate_point::<C>)
+}
+
+/// Deserialize a compressed instance of `T` from a vector of bytes, and validate
+/// all its group elements according to the [`PointValidationPolicy`] of the [`CipherSuite`].
+pub(crate) fn deserialize_validated<C: CipherSuite, T: CanonicalDeserialize + ValidatePoints<C>>(
+    bytes: &[u8],
+) -> FrostResult<C, T> {
+    // Group elements are validated separately, to report dedicated errors.
+    let value = T::deserialize_with_mode(bytes, Compress::Yes, Validate::No)
+        .map_err(|e| Error::DeserializationError(e.into()))?;
+    value.validate_points()?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dkg::{DealerRole, Participant};
+    use crate::keys::{DiffieHellmanPublicKey, GroupVerifyingKey};
+    use crate::parameters::ThresholdParameters;
+    use crate::sign::ThresholdSignature;
+    use crate::testing::adversarial::dealer_with_identity_dh_public_key;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::{String, ToOwned};
+    use crate::{Aes256Gcm, Element};
+
+    use ark_secp256k1::{Fr, Projective};
+    use ark_serialize::CanonicalSerialize;
+    use rand::rngs::OsRng;
+    use sha2::Sha256;
+    use zeroize::Zeroize;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Zeroize)]
+    struct Secp256k1Fast;
+
+    impl CipherSuite for Secp256k1Fast {
+        type G = Projective;
+
+        type HashOutput = [u8; 32];
+
+        type InnerHasher = Sha256;
+
+        type ShareCipher = Aes256Gcm;
+
+        const POINT_VALIDATION_POLICY: PointValidationPolicy = PointValidationPolicy::Fast;
+
+        fn context_string() -> String {
+            "ICE-FROST_SECP256K1_FAST".to_owned()
+        }
+    }
+
+    #[test]
+    fn identity_points_are_rejected() {
+        let identity = GroupVerifyingKey::<Secp256k1Sha256>::new(Element::identity());
+        let bytes = identity.to_bytes().unwrap();
+
+        assert_eq!(
+            GroupVerifyingKey::<Secp256k1Sha256>::from_bytes(&bytes),
+            Err(Error::IdentityPoint)
+        );
+        assert_eq!(
+            DiffieHellmanPublicKey::<Secp256k1Sha256>::from_bytes(&bytes),
+            Err(Error::IdentityPoint)
+        );
+        assert_eq!(
+            GroupVerifyingKey::<Secp256k1Fast>::from_bytes(&bytes),
+            Err(Error::IdentityPoint)
+        );
+
+        let mut bytes = Vec::new();
+        Projective::zero().serialize_compressed(&mut bytes).unwrap();
+        Fr::from(42u8).serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            ThresholdSignature::<Secp256k1Sha256>::from_bytes(&bytes),
+            Err(Error::IdentityPoint)
+        );
+    }
+
+    #[test]
+    fn valid_points_are_accepted() {
+        let key = GroupVerifyingKey::<Secp256k1Sha256>::new(Element::generator());
+        let bytes = key.to_bytes().unwrap();
+
+        assert_eq!(GroupVerifyingKey::from_bytes(&bytes), Ok(key));
+        assert_eq!(
+            GroupVerifyingKey::<Secp256k1Fast>::from_bytes(&bytes),
+            Ok(GroupVerifyingKey::new(Element::generator()))
+        );
+    }
+
+    #[test]
+    fn off_curve_points_are_rejected() {
+        // No point of Secp256k1 has an x-coordinate of 5.
+        let mut bytes = Vec::new();
+        ark_secp256k1::Fq::from(5u8)
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+
+        assert!(matches!(
+            GroupVerifyingKey::<Secp256k1Sha256>::from_bytes(&bytes),
+            Err(Error::DeserializationError(_))
+        ));
+    }
+
+    fn check_dh_public_keys<C: CipherSuite>() {
+        let params = ThresholdParameters::<C>::new(3, 2).unwrap();
+
+        let (dealer, _, _) = Participant::<C, DealerRole>::new_dealer(&params, 1, OsRng).unwrap();
+        assert_eq!(
+            validate_dh_public_key::<C>(1, &dealer.dh_public_key.key),
+            Ok(())
+        );
+        assert_eq!(
+            Participant::<C, DealerRole>::from_bytes(&dealer.to_bytes().unwrap()),
+            Ok(dealer)
+        );
+
+        let (dealer, _, _) = dealer_with_identity_dh_public_key(&params, 2, OsRng).unwrap();
+        assert_eq!(
+            validate_dh_public_key::<C>(2, &dealer.dh_public_key.key),
+            Err(Error::IdentityDhPublicKey(2))
+        );
+        assert_eq!(
+            Participant::<C, DealerRole>::from_bytes(&dealer.to_bytes().unwrap()),
+            Err(Error::IdentityDhPublicKey(2))
+        );
+    }
+
+    #[test]
+    fn dh_public_keys_secp256k1_sha256() {
+        check_dh_public_keys::<Secp256k1Sha256>();
+        check_dh_public_keys::<Secp256k1Fast>();
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn dh_public_keys_secp256k1_blake3() {
+        check_dh_public_keys::<crate::testing::Secp256k1Blake3>();
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn dh_public_keys_p256_sha256() {
+        check_dh_public_keys::<crate::testing::P256Sha256>();
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn dh_public_keys_bls12_381_g1_sha256() {
+        use crate::testing::Bls12381G1Sha256;
+        use ark_bls12_381::{Fq, G1Affine};
+        use ark_ff::Field;
+
+        check_dh_public_keys::<Bls12381G1Sha256>();
+
+        // The point (0, 2) of the curve has order 3, which divides the cofactor of G1.
+        let low_order_point = G1Affine::new_unchecked(Fq::ZERO, Fq::from(2u8)).into();
+        assert_eq!(
+            validate_dh_public_key::<Bls12381G1Sha256>(1, &low_order_point),
+            Err(Error::LowOrderDhPublicKey(1))
+        );
+
+        let params = ThresholdParameters::new(3, 2).unwrap();
+        let (mut dealer, _, _) =
+            Participant::<Bls12381G1Sha256, DealerRole>::new_dealer(&params, 1, OsRng).unwrap();
+        dealer.dh_public_key = DiffieHellmanPublicKey::new(Element(low_order_point));
+        assert_eq!(
+            Participant::<Bls12381G1Sha256, DealerRole>::from_bytes(&dealer.to_bytes().unwrap()),
+            Err(Error::LowOrderDhPublicKey(1))
+        );
+    }
+}
@@ -0,0 +1,600 @@
+//! The wire specification module, describing the byte-level encoding of the
+//! serialized protocol types of ICE-FROST, with the `wire-spec` feature.
+//!
+//! All protocol types are serialized with the compressed canonical encoding of
+//! `ark-serialize`, as the concatenation of their fields in declaration order:
+//! integers are little-endian, scalars and points use the compressed encoding of
+//! the curve of the [`CipherSuite`], vectors are prefixed with their length as a
+//! little-endian `u64`, and optional values with a one-byte flag. For the short
+//! Weierstrass curves of `arkworks`, a compressed point is the little-endian encoding
+//! of its x-coordinate, whose last byte carries the sign of its y-coordinate and the
+//! point at infinity as flag bits.
+//!
+//! A [`WireSpec`] describes the layout of each protocol type for a given ciphersuite,
+//! along with the encoding of a fixed example value computed by this crate, against
+//! which the layout is checked with [`WireSpec::verify`]. It is emitted as JSON with
+//! [`WireSpec::to_json`], so that implementations in other languages can be generated
+//! and tested from it.
+//!
+//! Golden files of the specifications of the provided ciphersuites are kept under
+//! `tests/golden`, and checked against the code by the test suite. They are regenerated
+//! by running the `wire_spec` tests with the `WIRE_SPEC_BLESS` environment variable set.
+//!
+//! [`WireSpec`]: crate::wire_spec::WireSpec
+//! [`WireSpec::verify`]: crate::wire_spec::WireSpec::verify
+//! [`WireSpec::to_json`]: crate::wire_spec::WireSpec::to_json
+
+use ark_ec::Group;
+use ark_ff::Zero;
+use ark_serialize::CanonicalSerialize;
+
+use core::marker::PhantomData;
+use serde_json::{json, Value};
+
+use crate::ciphersuite::CipherSuite;
+use crate::dkg::participant::DealerMaterial;
+use crate::dkg::{
+    Complaint, ComplaintProof, DealerRole, EncryptedSecretShare, NizkPokOfSecretKey, Participant,
+    VerifiableSecretSharingCommitment,
+};
+use crate::fingerprint::FINGERPRINT_LENGTH;
+use crate::keys::{
+    DiffieHellmanPublicKey, GroupVerifyingKey, IndividualVerifyingKey, PublicKeyPackage,
+};
+use crate::parameters::ThresholdParameters;
+use crate::share_cipher::ShareCipher;
+use crate::sign::{
+    EpochTag, PartialThresholdSignature, PublicCommitmentShareList, Signer, ThresholdSignature,
+};
+use crate::utils::{vec, Box, Scalar, String, ToString, Vec};
+use crate::{Element, Error, FrostResult};
+
+/// The encoding of a serialized value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// A single byte.
+    U8,
+    /// A boolean, encoded as a single byte equal to 0 or 1.
+    Bool,
+    /// A little-endian 32-bit unsigned integer.
+    U32,
+    /// A little-endian 64-bit unsigned integer.
+    U64,
+    /// A compressed scalar of the ciphersuite.
+    Scalar,
+    /// A compressed point of the ciphersuite.
+    Point,
+    /// A fixed number of raw bytes.
+    Bytes(usize),
+    /// The concatenation of the given encodings.
+    Tuple(Vec<Encoding>),
+    /// A length-prefixed vector of values of the given encoding.
+    Vec(Box<Encoding>),
+    /// An optional value of the given encoding, prefixed with a presence flag.
+    Option(Box<Encoding>),
+    /// A value of another type of the [`WireSpec`].
+    Type(&'static str),
+}
+
+/// A field of a serialized protocol type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldLayout {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The encoding of the field.
+    pub encoding: Encoding,
+}
+
+/// The layout of a serialized protocol type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeLayout {
+    /// The name of the type.
+    pub name: &'static str,
+    /// The fields of the type, in serialization order.
+    pub fields: Vec<FieldLayout>,
+    /// The serialization of a fixed example value of the type.
+    pub example: Vec<u8>,
+}
+
+/// The byte-level layouts of the serialized protocol types for a [`CipherSuite`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WireSpec<C: CipherSuite> {
+    /// The size of a compressed scalar.
+    pub scalar_size: usize,
+    /// The size of a compressed point.
+    pub point_size: usize,
+    /// The layouts of the protocol types.
+    pub types: Vec<TypeLayout>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: CipherSuite> WireSpec<C> {
+    /// Describe the layouts of the serialized protocol types for this [`CipherSuite`].
+    pub fn new() -> FrostResult<C, Self> {
+        let point = |k: u64| C::G::generator() * Scalar::<C>::from(k);
+        let scalar = |k: u64| Scalar::<C>::from(k);
+        let nonce_size = <C::ShareCipher as ShareCipher>::Nonce::default()
+            .as_ref()
+            .len();
+
+        let parameters = ThresholdParameters::<C>::new(3, 2)?;
+        let group_key = GroupVerifyingKey::<C>::new(Element(point(1)));
+        let verifying_key = IndividualVerifyingKey::<C> {
+            index: 1,
+            share: point(2),
+        };
+        let proof = NizkPokOfSecretKey::<C> {
+            s: scalar(3),
+            r: scalar(4),
+        };
+        let commitment = VerifiableSecretSharingCommitment::<C> {
+            index: 1,
+            points: vec![point(5), point(6)],
+        };
+        let participant = Participant::<C, DealerRole> {
+            index: 1,
+            dh_public_key: DiffieHellmanPublicKey::new(Element(point(7))),
+            material: DealerMaterial {
+                commitments: commitment.clone(),
+                proof_of_secret_key: proof.clone(),
+            },
+            proof_of_dh_private_key: proof.clone(),
+            _role: PhantomData,
+        };
+        let mut nonce = <C::ShareCipher as ShareCipher>::Nonce::default();
+        for (i, byte) in nonce.as_mut().iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let encrypted_share = EncryptedSecretShare::<C>::new(
+            1,
+            2,
+            nonce,
+            (0..48).map(|i| 0xa0 ^ i).collect(),
+            (0..32).map(|i| 0x50 ^ i).collect(),
+        );
+        let complaint_proof = ComplaintProof::<C> {
+            a1: point(8),
+            a2: point(9),
+            z: scalar(10),
+        };
+        let complaint = Complaint::<C> {
+            maker_index: 2,
+            accused_index: 1,
+            dh_shared_key: point(11),
+            proof: complaint_proof,
+        };
+        let commitment_share_list = PublicCommitmentShareList::<C> {
+            participant_index: 1,
+            commitments: vec![(point(12), point(13)), (point(14), point(15))],
+        };
+        let signer = Signer::<C> {
+            participant_index: 1,
+            published_commitment_share: (point(12), point(13)),
+        };
+        let epoch_tag = EpochTag {
+            epoch: 19,
+            key_fingerprint: *group_key.fingerprint()?.as_bytes(),
+        };
+        let partial_signature = PartialThresholdSignature::<C> {
+            index: 1,
+            z: scalar(16),
+            epoch_tag: Some(epoch_tag),
+        };
+        let signature = ThresholdSignature::<C> {
+            group_commitment: point(17),
+            z: scalar(18),
+        };
+        let package = PublicKeyPackage::new(parameters, group_key, vec![verifying_key.clone()]);
+
+        let field = |name, encoding| FieldLayout { name, encoding };
+        let layout = |name, fields, example: FrostResult<C, Vec<u8>>| -> FrostResult<C, _> {
+            Ok(TypeLayout {
+                name,
+                fields,
+                example: example?,
+            })
+        };
+        let boxed = |name| Box::new(Encoding::Type(name));
+
+        let types = vec![
+            layout(
+                "ThresholdParameters",
+                vec![field("n", Encoding::U32), field("t", Encoding::U32)],
+                serialize::<C, _>(&parameters),
+            )?,
+            layout(
+                "GroupVerifyingKey",
+                vec![field("key", Encoding::Point)],
+                serialize::<C, _>(&group_key),
+            )?,
+            layout(
+                "DiffieHellmanPublicKey",
+                vec![field("key", Encoding::Point)],
+                serialize::<C, _>(&participant.dh_public_key),
+            )?,
+            layout(
+                "IndividualVerifyingKey",
+                vec![
+                    field("index", Encoding::U32),
+                    field("share", Encoding::Point),
+                ],
+                serialize::<C, _>(&verifying_key),
+            )?,
+            layout(
+                "NizkPokOfSecretKey",
+                vec![field("s", Encoding::Scalar), field("r", Encoding::Scalar)],
+                serialize::<C, _>(&proof),
+            )?,
+            layout(
+                "VerifiableSecretSharingCommitment",
+                vec![
+                    field("index", Encoding::U32),
+                    field("points", Encoding::Vec(Box::new(Encoding::Point))),
+                ],
+                serialize::<C, _>(&commitment),
+            )?,
+            layout(
+                "Participant",
+                vec![
+                    field("index", Encoding::U32),
+                    field("dh_public_key", Encoding::Type("DiffieHellmanPublicKey")),
+                    field(
+                        "commitments",
+                        Encoding::Option(boxed("VerifiableSecretSharingCommitment")),
+                    ),
+                    field(
+                        "proof_of_secret_key",
+                        Encoding::Option(boxed("NizkPokOfSecretKey")),
+                    ),
+                    field(
+                        "proof_of_dh_private_key",
+                        Encoding::Type("NizkPokOfSecretKey"),
+                    ),
+                ],
+                serialize::<C, _>(&participant),
+            )?,
+            layout(
+                "EncryptedSecretShare",
+                vec![
+                    field("sender_index", Encoding::U32),
+                    field("receiver_index", Encoding::U32),
+                    field("nonce", Encoding::Bytes(nonce_size)),
+                    field(
+                        "encrypted_polynomial_evaluation",
+                        Encoding::Vec(Box::new(Encoding::U8)),
+                    ),
+                    field("salt", Encoding::Vec(Box::new(Encoding::U8))),
+                ],
+                serialize::<C, _>(&encrypted_share),
+            )?,
+            layout(
+                "ComplaintProof",
+                vec![
+                    field("a1", Encoding::Point),
+                    field("a2", Encoding::Point),
+                    field("z", Encoding::Scalar),
+                ],
+                serialize::<C, _>(&complaint_proof),
+            )?,
+            layout(
+                "Complaint",
+                vec![
+                    field("maker_index", Encoding::U32),
+                    field("accused_index", Encoding::U32),
+                    field("dh_shared_key", Encoding::Point),
+                    field("proof", Encoding::Type("ComplaintProof")),
+                ],
+                serialize::<C, _>(&complaint),
+            )?,
+            layout(
+                "PublicCommitmentShareList",
+                vec![
+                    field("participant_index", Encoding::U32),
+                    field(
+                        "commitments",
+                        Encoding::Vec(Box::new(Encoding::Tuple(vec![
+                            Encoding::Point,
+                            Encoding::Point,
+                        ]))),
+                    ),
+                ],
+                serialize::<C, _>(&commitment_share_list),
+            )?,
+            layout(
+                "Signer",
+                vec![
+                    field("participant_index", Encoding::U32),
+                    field(
+                        "published_commitment_share",
+                        Encoding::Tuple(vec![Encoding::Point, Encoding::Point]),
+                    ),
+                ],
+                serialize::<C, _>(&signer),
+            )?,
+            layout(
+                "EpochTag",
+                vec![
+                    field("epoch", Encoding::U64),
+                    field("key_fingerprint", Encoding::Bytes(FINGERPRINT_LENGTH)),
+                ],
+                serialize::<C, _>(&epoch_tag),
+            )?,
+            layout(
+                "PartialThresholdSignature",
+                vec![
+                    field("index", Encoding::U32),
+                    field("z", Encoding::Scalar),
+                    field("epoch_tag", Encoding::Option(boxed("EpochTag"))),
+                ],
+                serialize::<C, _>(&partial_signature),
+            )?,
+            layout(
+                "ThresholdSignature",
+                vec![
+                    field("group_commitment", Encoding::Point),
+                    field("z", Encoding::Scalar),
+                ],
+                serialize::<C, _>(&signature),
+            )?,
+            layout(
+                "PublicKeyPackage",
+                vec![
+                    field("parameters", Encoding::Type("ThresholdParameters")),
+                    field("group_key", Encoding::Type("GroupVerifyingKey")),
+                    field(
+                        "verifying_keys",
+                        Encoding::Vec(boxed("IndividualVerifyingKey")),
+                    ),
+                ],
+                serialize::<C, _>(&package),
+            )?,
+        ];
+
+        Ok(Self {
+            scalar_size: Scalar::<C>::zero().compressed_size(),
+            point_size: C::G::zero().compressed_size(),
+            types,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The layout of the type with the given `name`, if any.
+    pub fn layout(&self, name: &str) -> Option<&TypeLayout> {
+        self.types.iter().find(|layout| layout.name == name)
+    }
+
+    /// The size of the given `encoding`, or [`None`] if it is of variable size.
+    pub fn size(&self, encoding: &Encoding) -> Option<usize> {
+        match encoding {
+            Encoding::U8 | Encoding::Bool => Some(1),
+            Encoding::U32 => Some(4),
+            Encoding::U64 => Some(8),
+            Encoding::Scalar => Some(self.scalar_size),
+            Encoding::Point => Some(self.point_size),
+            Encoding::Bytes(size) => Some(*size),
+            Encoding::Tuple(encodings) => {
+                encodings.iter().map(|encoding| self.size(encoding)).sum()
+            }
+            Encoding::Vec(_) | Encoding::Option(_) => None,
+            Encoding::Type(name) => self.layout(name).and_then(|layout| {
+                layout
+                    .fields
+                    .iter()
+                    .map(|field| self.size(&field.encoding))
+                    .sum()
+            }),
+        }
+    }
+
+    /// Check that the example of each layout is exactly parsed by the layout.
+    ///
+    /// # Returns
+    ///
+    /// A [`FrostResult`] whose [`Ok`] value indicates that all the layouts match
+    /// the encodings of this crate, otherwise an [`Error`] naming the first one
+    /// which does not.
+    pub fn verify(&self) -> FrostResult<C, ()> {
+        for layout in self.types.iter() {
+            let mut offset = 0;
+            let parsed = self.parse(&Encoding::Type(layout.name), &layout.example, &mut offset);
+
+            if !parsed || offset != layout.example.len() {
+                return Err(Error::Custom(
+                    "The wire layout of ".to_string()
+                        + layout.name
+                        + " does not match its encoding",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Emit this [`WireSpec`] as pretty-printed JSON.
+    pub fn to_json(&self) -> FrostResult<C, String> {
+        let types: Vec<Value> = self
+            .types
+            .iter()
+            .map(|layout| {
+                let fields: Vec<Value> = layout
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        json!({
+                            "name": field.name,
+                            "encoding": encoding_to_json(&field.encoding),
+                            "size": self.size(&field.encoding),
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "name": layout.name,
+                    "size": self.size(&Encoding::Type(layout.name)),
+                    "fields": fields,
+                    "example": to_hex(&layout.example),
+                })
+            })
+            .collect();
+
+        let spec = json!({
+            "ciphersuite": C::context_string(),
+            "scalar_size": self.scalar_size,
+            "point_size": self.point_size,
+            "types": types,
+        });
+
+        serde_json::to_string_pretty(&spec)
+            .map(|json| json + "\n")
+            .map_err(|e| Error::Custom(e.to_string()))
+    }
+
+    /// Advance the `offset` past a value of the given `encoding` in `bytes`,
+    /// returning whether the value is well-formed.
+    fn parse(&self, encoding: &Encoding, bytes: &[u8], offset: &mut usize) -> bool {
+        let mut take = |length: usize| -> Option<&[u8]> {
+            let end = offset
+                .checked_add(length)
+                .filter(|&end| end <= bytes.len())?;
+            let slice = &bytes[*offset..end];
+            *offset = end;
+
+            Some(slice)
+        };
+
+        match encoding {
+            Encoding::Bool => matches!(take(1), Some([0]) | Some([1])),
+            Encoding::Vec(item) => {
+                let length = match take(8) {
+                    Some(prefix) => {
+                        let mut length = [0u8; 8];
+                        length.copy_from_slice(prefix);
+                        u64::from_le_bytes(length)
+                    }
+                    None => return false,
+                };
+
+                (0..length).all(|_| self.parse(item, bytes, offset))
+            }
+            Encoding::Option(value) => match take(1) {
+                Some([0]) => true,
+                Some([1]) => self.parse(value, bytes, offset),
+                _ => false,
+            },
+            Encoding::Tuple(encodings) => encodings
+                .iter()
+                .all(|encoding| self.parse(encoding, bytes, offset)),
+            Encoding::Type(name) => match self.layout(name) {
+                Some(layout) => layout
+                    .fields
+                    .iter()
+                    .all(|field| self.parse(&field.encoding, bytes, offset)),
+                None => false,
+            },
+            _ => match self.size(encoding) {
+                Some(size) => take(size).is_some(),
+                None => false,
+            },
+        }
+    }
+}
+
+/// Serialize a `value` with the compressed canonical encoding.
+fn serialize<C: CipherSuite, T: CanonicalSerialize>(value: &T) -> FrostResult<C, Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| Error::SerializationError(e.into()))?;
+
+    Ok(bytes)
+}
+
+/// Describe an `encoding` in JSON, as a string for primitive encodings
+/// and as a single-key object for composite ones.
+fn encoding_to_json(encoding: &Encoding) -> Value {
+    match encoding {
+        Encoding::U8 => json!("u8"),
+        Encoding::Bool => json!("bool"),
+        Encoding::U32 => json!("u32"),
+        Encoding::U64 => json!("u64"),
+        Encoding::Scalar => json!("scalar"),
+        Encoding::Point => json!("point"),
+        Encoding::Bytes(size) => json!({ "bytes": size }),
+        Encoding::Tuple(encodings) => {
+            let encodings: Vec<Value> = encodings.iter().map(encoding_to_json).collect();
+            json!({ "tuple": encodings })
+        }
+        Encoding::Vec(item) => json!({ "vec": encoding_to_json(item) }),
+        Encoding::Option(value) => json!({ "option": encoding_to_json(value) }),
+        Encoding::Type(name) => json!({ "type": name }),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    bytes
+        .iter()
+        .flat_map(|byte| {
+            [
+                HEX_DIGITS[(byte >> 4) as usize] as char,
+                HEX_DIGITS[(byte & 0x0f) as usize] as char,
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Secp256k1Sha256;
+
+    /// Check the JSON specification of a ciphersuite against its golden file,
+    /// or regenerate the golden file if `WIRE_SPEC_BLESS` is set.
+    fn check_golden_file<C: CipherSuite>(file_name: &str) {
+        let spec = WireSpec::<C>::new().unwrap();
+        assert!(spec.verify().is_ok());
+
+        let json = spec.to_json().unwrap();
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("golden")
+            .join(file_name);
+
+        if std::env::var_os("WIRE_SPEC_BLESS").is_some() {
+            std::fs::write(&path, json).unwrap();
+        } else {
+            let golden = std::fs::read_to_string(&path).unwrap();
+            assert!(
+                golden == json,
+                "The wire format of {} changed, rerun with WIRE_SPEC_BLESS=1 if intended",
+                file_name
+            );
+        }
+    }
+
+    #[test]
+    fn wire_spec_secp256k1_sha256() {
+        check_golden_file::<Secp256k1Sha256>("wire_spec_secp256k1_sha256.json");
+    }
+
+    #[test]
+    fn wire_spec_rejects_mismatching_layouts() {
+        let mut spec = WireSpec::<Secp256k1Sha256>::new().unwrap();
+        assert_eq!(
+            spec.size(&Encoding::Type("ThresholdSignature")),
+            Some(spec.point_size + spec.scalar_size)
+        );
+        assert_eq!(spec.size(&Encoding::Type("Participant")), None);
+
+        let layout = spec
+            .types
+            .iter_mut()
+            .find(|layout| layout.name == "Complaint")
+            .unwrap();
+        layout.fields.swap(2, 3);
+        layout.fields.pop();
+        assert!(spec.verify().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! The zeroize audit module, checking that the memory backing secret-bearing types
+//! is actually overwritten before being released, with the `zeroize-audit` feature.
+//!
+//! The test binary of this crate is then built with an allocator shim, which scans
+//! every released heap block for a canary value during an audit. Each audit places
+//! canary values in secret-bearing types, such as nonces, polynomial coefficients,
+//! decrypted shares and Diffie-Hellman shared secrets, and drops them: a sighting of a
+//! canary in released memory is evidence that a secret outlived its owner.
+//!
+//! Only heap memory is audited. Secrets held on the stack, or copied into registers,
+//! are out of reach of an allocator, hence secret-bearing types are boxed by the audits.
+
+#![allow(unsafe_code)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The maximum length of a canary value.
+const MAX_CANARY_LENGTH: usize = 128;
+
+/// The length of the canary slices looked for in released memory, so that partial
+/// copies of a secret are also sighted.
+const CANARY_WINDOW: usize = 16;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO_BYTE: AtomicU8 = AtomicU8::new(0);
+
+/// Whether an audit is running.
+static ARMED: AtomicBool = AtomicBool::new(false);
+/// The canary value of the running audit.
+static CANARY: [AtomicU8; MAX_CANARY_LENGTH] = [ZERO_BYTE; MAX_CANARY_LENGTH];
+/// The length of the canary value of the running audit.
+static CANARY_LENGTH: AtomicUsize = AtomicUsize::new(0);
+/// The number of released blocks in which the canary value was sighted.
+static SIGHTINGS: AtomicUsize = AtomicUsize::new(0);
+/// A lock serializing audits.
+static AUDIT: Mutex<()> = Mutex::new(());
+
+/// An allocator shim around the [`System`] allocator, scanning released blocks
+/// for the canary value of the running audit.
+struct AuditAllocator;
+
+#[global_allocator]
+static ALLOCATOR: AuditAllocator = AuditAllocator;
+
+unsafe impl GlobalAlloc for AuditAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        System.alloc_zeroed(layout)
+    }
+
+    // Reallocations go through `alloc` and `dealloc`, so that blocks released
+    // while growing a buffer are scanned as well.
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ARMED.load(Ordering::SeqCst)
+            && contains_canary(core::slice::from_raw_parts(ptr, layout.size()))
+        {
+            SIGHTINGS.fetch_add(1, Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Whether the given `block` contains any slice of [`CANARY_WINDOW`] bytes
+/// of the canary value.
+fn contains_canary(block: &[u8]) -> bool {
+    let length = CANARY_LENGTH.load(Ordering::SeqCst);
+    let mut canary = [0u8; MAX_CANARY_LENGTH];
+    for (byte, stored) in canary.iter_mut().zip(CANARY.iter()).take(length) {
+        *byte = stored.load(Ordering::SeqCst);
+    }
+
+    canary[..length]
+        .windows(CANARY_WINDOW)
+        .any(|slice| block.windows(CANARY_WINDOW).any(|window| window == slice))
+}
+
+/// Run `f`, and count the heap blocks released in the meantime in which
+/// the `canary` value was sighted.
+pub(crate) fn audit(canary: &[u8], f: impl FnOnce()) -> usize {
+    assert!((CANARY_WINDOW..=MAX_CANARY_LENGTH).contains(&canary.len()));
+    let _guard = AUDIT.lock().unwrap_or_else(|e| e.into_inner());
+
+    for (stored, byte) in CANARY.iter().zip(canary.iter()) {
+        stored.store(*byte, Ordering::SeqCst);
+    }
+    CANARY_LENGTH.store(canary.len(), Ordering::SeqCst);
+    SIGHTINGS.store(0, Ordering::SeqCst);
+
+    ARMED.store(true, Ordering::SeqCst);
+    f();
+    ARMED.store(false, Ordering::SeqCst);
+
+    SIGHTINGS.load(Ordering::SeqCst)
+}
+
+/// Copy the in-memory representation of the given `value`, to be used as canary.
+pub(crate) fn memory_of<T>(value: &T) -> std::vec::Vec<u8> {
+    unsafe {
+        core::slice::from_raw_parts(value as *const T as *const u8, core::mem::size_of::<T>())
+    }
+    .to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dkg::secret_share::{
+        derive_share_cipher_key, encrypt_share, share_session_id, Coefficients, SecretShare,
+    };
+    use crate::keys::{DhKeyProvider, DiffieHellmanPrivateKey};
+    use crate::sign::NoncePair;
+    use crate::testing::Secp256k1Sha256;
+    use crate::utils::{Box, Vec};
+
+    use ark_ff::UniformRand;
+    use ark_secp256k1::Fr;
+    use ark_serialize::CanonicalSerialize;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn canaries_are_sighted_without_zeroization() {
+        let secret = Fr::rand(&mut OsRng);
+
+        assert!(audit(&memory_of(&secret), || drop(Box::new([secret; 2]))) > 0);
+    }
+
+    #[test]
+    fn nonce_pairs_are_zeroized() {
+        let hiding = Fr::rand(&mut OsRng);
+        let binding = Fr::rand(&mut OsRng);
+
+        for canary in [memory_of(&hiding), memory_of(&binding)] {
+            assert_eq!(
+                audit(&canary, || drop(Box::new(NoncePair::<Secp256k1Sha256>(
+                    hiding, binding
+                )))),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn coefficients_are_zeroized() {
+        let secrets: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut OsRng)).collect();
+
+        for secret in secrets.iter() {
+            assert_eq!(
+                audit(&memory_of(secret), || drop(Box::new(Coefficients::<
+                    Secp256k1Sha256,
+                >(
+                    secrets.clone()
+                )))),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn decrypted_shares_and_dh_shared_secrets_are_zeroized() {
+        let dealer_dh_private_key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut OsRng));
+        let recipient_dh_private_key =
+            DiffieHellmanPrivateKey::<Secp256k1Sha256>(Fr::rand(&mut OsRng));
+        let dealer_dh_public_key = dealer_dh_private_key.dh_public_key().unwrap();
+        let recipient_dh_public_key = recipient_dh_private_key.dh_public_key().unwrap();
+
+        let evaluation = Fr::rand(&mut OsRng);
+        let share = SecretShare::<Secp256k1Sha256> {
+            sender_index: 1,
+            receiver_index: 2,
+            polynomial_evaluation: crate::Scalar(evaluation),
+        };
+        let mut dh_key_bytes = Vec::new();
+        dealer_dh_private_key
+            .dh_shared_key(&recipient_dh_public_key)
+            .unwrap()
+            .serialize_compressed(&mut dh_key_bytes)
+            .unwrap();
+        let session_id = share_session_id(&dealer_dh_public_key, &recipient_dh_public_key).unwrap();
+        let encrypted_share = encrypt_share(&share, &dh_key_bytes, &session_id, &[]).unwrap();
+
+        let mut evaluation_bytes = Vec::new();
+        evaluation
+            .serialize_compressed(&mut evaluation_bytes)
+            .unwrap();
+        let share_cipher_key = derive_share_cipher_key::<Secp256k1Sha256>(&dh_key_bytes).unwrap();
+
+        for canary in [
+            memory_of(&evaluation),
+            evaluation_bytes,
+            dh_key_bytes,
+            share_cipher_key,
+        ] {
+            assert_eq!(
+                audit(&canary, || {
+                    let decrypted_share = encrypted_share
+                        .decrypt_with(&recipient_dh_private_key, &dealer_dh_public_key)
+                        .unwrap();
+                    assert_eq!(decrypted_share.expose_secret().0, evaluation);
+                    drop(Box::new(decrypted_share));
+                }),
+                0
+            );
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Heap usage of signature verification, measured with a counting allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ark_serialize::CanonicalSerialize;
+use rand::rngs::OsRng;
+
+use ice_frost::keys::GroupVerifyingKey;
+use ice_frost::sign::ThresholdSignature;
+use ice_frost::testing::Secp256k1Sha256;
+use ice_frost::{CipherSuite, Element, Scalar};
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The peak number of heap bytes allocated while running `f`, on top of the
+/// bytes already allocated beforehand.
+fn peak_heap_usage(f: impl FnOnce()) -> usize {
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    f();
+
+    PEAK.load(Ordering::SeqCst) - baseline
+}
+
+#[test]
+fn low_memory_verification_heap_usage() {
+    let secret_key = Scalar::<Secp256k1Sha256>::random(OsRng);
+    let nonce = Scalar::<Secp256k1Sha256>::random(OsRng);
+    let group_key = Element::generator() * secret_key;
+    let group_commitment = Element::generator() * nonce;
+
+    let message_hash = Secp256k1Sha256::h4(b"This is a test of the tsunami alert system.").unwrap();
+    let challenge = Secp256k1Sha256::challenge(
+        &group_commitment.into_inner(),
+        &group_key.into_inner(),
+        &message_hash,
+    )
+    .unwrap();
+    let z = nonce + Scalar::from_inner(challenge) * secret_key;
+
+    let mut bytes = Vec::new();
+    group_commitment
+        .into_inner()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    z.serialize_compressed(&mut bytes).unwrap();
+    let signature = ThresholdSignature::<Secp256k1Sha256>::from_bytes(&bytes).unwrap();
+    let group_key = GroupVerifyingKey::new(group_key);
+
+    let verify = peak_heap_usage(|| assert!(signature.verify(&group_key, &message_hash).is_ok()));
+    let verify_lowmem =
+        peak_heap_usage(|| assert!(signature.verify_lowmem(&group_key, &message_hash).is_ok()));
+
+    // The multi-scalar multiplication of `verify` allocates about 10 KiB of tables,
+    // while `verify_lowmem` only allocates the domain separation tag of the challenge.
+    assert!(verify_lowmem < verify);
+    assert!(verify_lowmem < 1024);
+}
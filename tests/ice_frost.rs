@@ -4,7 +4,7 @@ use rand::rngs::OsRng;
 
 use ice_frost::CipherSuite;
 
-use ice_frost::dkg::{DistributedKeyGeneration, Participant};
+use ice_frost::dkg::{DealerRole, DistributedKeyGeneration, Participant};
 use ice_frost::parameters::ThresholdParameters;
 use ice_frost::sign::generate_commitment_share_lists;
 
@@ -12,12 +12,12 @@ use ice_frost::sign::SignatureAggregator;
 
 use ice_frost::testing::Secp256k1Sha256;
 
-type ParticipantDKG = Participant<Secp256k1Sha256>;
+type ParticipantDKG = Participant<Secp256k1Sha256, DealerRole>;
 type Dkg<T> = DistributedKeyGeneration<T, Secp256k1Sha256>;
 
 #[test]
 fn signing_and_verification_3_out_of_5() {
-    let params = ThresholdParameters::new(5, 3);
+    let params = ThresholdParameters::new(5, 3).unwrap();
     let rng = OsRng;
 
     let (p1, p1coeffs, p1_dh_sk) = ParticipantDKG::new_dealer(&params, 1, rng).unwrap();
@@ -112,17 +112,23 @@ fn signing_and_verification_3_out_of_5() {
 
     let message = b"This is a test of the tsunami alert system. This is only a test.";
     let (p1_public_comshares, mut p1_secret_comshares) =
-        generate_commitment_share_lists(&mut OsRng, &p1_sk, 1);
+        generate_commitment_share_lists(&mut OsRng, &p1_sk, 1).unwrap();
     let (p3_public_comshares, mut p3_secret_comshares) =
-        generate_commitment_share_lists(&mut OsRng, &p3_sk, 1);
+        generate_commitment_share_lists(&mut OsRng, &p3_sk, 1).unwrap();
     let (p4_public_comshares, mut p4_secret_comshares) =
-        generate_commitment_share_lists(&mut OsRng, &p4_sk, 1);
+        generate_commitment_share_lists(&mut OsRng, &p4_sk, 1).unwrap();
 
-    let mut aggregator = SignatureAggregator::new(params, group_key, &message[..]);
+    let mut aggregator = SignatureAggregator::new(params, group_key, &message[..], None);
 
-    aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-    aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into());
-    aggregator.include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into());
+    aggregator
+        .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+        .unwrap();
+    aggregator
+        .include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into())
+        .unwrap();
+    aggregator
+        .include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into())
+        .unwrap();
 
     let signers = aggregator.get_signers();
     let message_hash = Secp256k1Sha256::h4(&message[..]).unwrap();
@@ -155,9 +161,9 @@ fn signing_and_verification_3_out_of_5() {
         )
         .unwrap();
 
-    aggregator.include_partial_signature(p1_partial);
-    aggregator.include_partial_signature(p3_partial);
-    aggregator.include_partial_signature(p4_partial);
+    aggregator.include_partial_signature(p1_partial).unwrap();
+    aggregator.include_partial_signature(p3_partial).unwrap();
+    aggregator.include_partial_signature(p4_partial).unwrap();
 
     let aggregator = aggregator.finalize().unwrap();
     let threshold_signature = aggregator.aggregate().unwrap();